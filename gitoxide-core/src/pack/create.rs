@@ -233,6 +233,7 @@ where
                 allow_thin_pack: thin,
                 chunk_size,
                 version: Default::default(),
+                max_depth: 50,
             },
         ))
     };
@@ -319,6 +320,7 @@ fn human_output(
                 missing_objects,
                 objects_copied_from_pack,
                 ref_delta_objects,
+                objects_exceeding_max_depth,
             },
     }: Statistics,
     mut out: impl std::io::Write,
@@ -339,10 +341,11 @@ fn human_output(
     #[rustfmt::skip]
     writeln!(
         out,
-        "\t{:<width$} {}\n\t{:<width$} {}\n\t{:<width$} {}\n\t{:<width$} {}",
+        "\t{:<width$} {}\n\t{:<width$} {}\n\t{:<width$} {}\n\t{:<width$} {}\n\t{:<width$} {}",
         "decoded and recompressed", decoded_and_recompressed_objects,
         "pack-to-pack copies", objects_copied_from_pack,
         "ref-delta-objects", ref_delta_objects,
+        "exceeding max depth", objects_exceeding_max_depth,
         "missing objects", missing_objects,
         width = width
     )?;
@@ -5,7 +5,10 @@ use gix_glob::search::{pattern, Pattern};
 
 use super::Attributes;
 use crate::{
-    search::{Assignments, MetadataCollection, Outcome, TrackedAssignment, Value},
+    search::{
+        Assignments, AttributeId, Match, MatchKind, MatchLocation, MetadataCollection, Outcome, TrackedAssignment,
+        Value,
+    },
     Search,
 };
 
@@ -15,6 +18,9 @@ impl Search {
     ///
     /// See [`Source`][crate::Source] for a way to obtain these paths.
     ///
+    /// The built-ins, namely the `binary` macro, have the lowest precedence, and each of the `files` takes precedence
+    /// over the ones before it.
+    ///
     /// Note that parsing is lenient and errors are logged.
     ///
     /// * `buf` is used to read `files` from disk which will be ignored if they do not exist.
@@ -115,12 +121,125 @@ impl Search {
         has_match
     }
 
+    /// Like [`pattern_matching_relative_path()`][Self::pattern_matching_relative_path()], but additionally append each
+    /// assignment of all patterns matching `relative_path` to `trace`, even if they were overridden.
+    ///
+    /// `trace` is in order of precedence, so earlier entries override later ones for the same attribute.
+    /// Macros are followed by the assignments they expand to, which refer to their macro through their [`kind`][Match::kind],
+    /// and all expanded assignments are attributed to the pattern and location that matched.
+    /// Return `true` if at least one pattern matched.
+    ///
+    /// This is useful to learn why an attribute has a particular state, but is slower than a normal search as it never
+    /// stops early.
+    pub fn pattern_matching_relative_path_with_trace<'a>(
+        &'a self,
+        relative_path: &BStr,
+        case: gix_glob::pattern::Case,
+        is_dir: Option<bool>,
+        out: &mut Outcome,
+        trace: &mut Vec<Match<'a>>,
+    ) -> bool {
+        let has_match = self.pattern_matching_relative_path(relative_path, case, is_dir, out);
+        let basename_pos = relative_path.rfind(b"/").map(|p| p + 1);
+        let mut macro_stack = Vec::new();
+        for list in self.patterns.iter().rev() {
+            let Some((relative_path, basename_start_pos)) =
+                list.strip_base_handle_recompute_basename_pos(relative_path, basename_pos, case)
+            else {
+                continue;
+            };
+            for pattern::Mapping {
+                pattern,
+                value,
+                sequence_number,
+            } in list
+                .patterns
+                .iter()
+                .rev()
+                .filter(|pm| Attributes::may_use_glob_pattern(&pm.pattern))
+            {
+                let Value::Assignments(attrs) = value else {
+                    unreachable!("we can't match on macros as they have no pattern")
+                };
+                if !pattern.matches_repo_relative_path(
+                    relative_path,
+                    basename_start_pos,
+                    is_dir,
+                    case,
+                    gix_glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+                ) {
+                    continue;
+                }
+                let location = MatchLocation {
+                    source: list.source.as_deref(),
+                    sequence_number: *sequence_number,
+                };
+                for attr in attrs {
+                    self.trace_assignment(attr, None, pattern, &location, &mut macro_stack, trace);
+                }
+            }
+        }
+        has_match
+    }
+
     /// Return the amount of pattern lists contained in this instance.
     pub fn num_pattern_lists(&self) -> usize {
         self.patterns.len()
     }
 }
 
+impl Search {
+    /// Push `attr` to `trace` and recursively expand it if it's a macro, unless it's already being expanded.
+    fn trace_assignment<'a>(
+        &'a self,
+        attr: &'a TrackedAssignment,
+        parent_macro_id: Option<AttributeId>,
+        pattern: &'a gix_glob::Pattern,
+        location: &MatchLocation<'a>,
+        macro_stack: &mut Vec<AttributeId>,
+        trace: &mut Vec<Match<'a>>,
+    ) {
+        let expansion = self.macro_assignments(attr.id);
+        trace.push(Match {
+            pattern,
+            assignment: attr.inner.as_ref(),
+            kind: if expansion.is_some() {
+                MatchKind::Macro { parent_macro_id }
+            } else {
+                MatchKind::Attribute {
+                    macro_id: parent_macro_id,
+                }
+            },
+            location: location.clone(),
+        });
+        if let Some(attrs) = expansion {
+            if macro_stack.contains(&attr.id) {
+                return;
+            }
+            macro_stack.push(attr.id);
+            for macro_attr in attrs {
+                self.trace_assignment(macro_attr, Some(attr.id), pattern, location, macro_stack, trace);
+            }
+            macro_stack.pop();
+        }
+    }
+
+    /// Return the assignments of the macro with `id` as defined last, or `None` if `id` isn't a macro.
+    fn macro_assignments(&self, id: AttributeId) -> Option<&Assignments> {
+        self.patterns
+            .iter()
+            .rev()
+            .flat_map(|list| list.patterns.iter().rev())
+            .find_map(|mapping| match &mapping.value {
+                Value::MacroAssignments {
+                    id: macro_id,
+                    assignments,
+                } if *macro_id == id && !assignments.is_empty() => Some(assignments),
+                _ => None,
+            })
+    }
+}
+
 impl Pattern for Attributes {
     type Value = Value;
 
@@ -266,6 +266,131 @@ fn given_attributes_are_made_available_in_given_order() -> crate::Result {
     Ok(())
 }
 
+mod trace {
+    use std::path::Path;
+
+    use gix_attributes::{
+        search::{MatchKind, MetadataCollection, Outcome},
+        Search,
+    };
+    use gix_glob::pattern::Case;
+
+    #[test]
+    fn all_matching_patterns_are_listed_in_order_of_precedence_with_expanded_macros() -> crate::Result {
+        let mut collection = MetadataCollection::default();
+        let mut search = Search::new_globals(None::<&str>, &mut Vec::new(), &mut collection)?;
+        search.add_patterns_buffer(
+            b"[attr]my-binary binary -custom\n*.bin my-binary\n*.bin text\n/a.bin -text eol=lf\n*.txt other",
+            Path::new("<memory>").into(),
+            None,
+            &mut collection,
+            true,
+        );
+        let mut out = Outcome::default();
+        out.initialize(&collection);
+        let mut trace = Vec::new();
+        assert!(search.pattern_matching_relative_path_with_trace(
+            "a.bin".into(),
+            Case::Sensitive,
+            None,
+            &mut out,
+            &mut trace
+        ));
+
+        let actual: Vec<_> = trace
+            .iter()
+            .map(|m| {
+                assert_eq!(m.location.source, Some(Path::new("<memory>")));
+                format!("{}:{} {}", m.location.sequence_number, m.pattern, m.assignment)
+            })
+            .collect();
+        assert_eq!(
+            actual,
+            [
+                "4:/a.bin -text",
+                "4:/a.bin eol=lf",
+                "3:*.bin text",
+                "2:*.bin my-binary",
+                "2:*.bin binary",
+                "2:*.bin -diff",
+                "2:*.bin -merge",
+                "2:*.bin -text",
+                "2:*.bin -custom",
+            ],
+            "overridden assignments are listed as well, and expanded ones are attributed to the matching pattern"
+        );
+
+        let id_of = |name: &str| collection.iter().find(|(n, _)| *n == name).map(|(_, meta)| meta.id);
+        let kinds: Vec<_> = trace.iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds[3..],
+            [
+                MatchKind::Macro { parent_macro_id: None },
+                MatchKind::Macro {
+                    parent_macro_id: id_of("my-binary")
+                },
+                MatchKind::Attribute {
+                    macro_id: id_of("binary")
+                },
+                MatchKind::Attribute {
+                    macro_id: id_of("binary")
+                },
+                MatchKind::Attribute {
+                    macro_id: id_of("binary")
+                },
+                MatchKind::Attribute {
+                    macro_id: id_of("my-binary")
+                },
+            ]
+        );
+
+        let actual: Vec<_> = out.iter().map(|m| m.assignment.to_string()).collect();
+        assert_eq!(
+            actual,
+            ["binary", "-diff", "-merge", "-text", "my-binary", "-custom", "eol=lf"],
+            "the outcome is the same as without trace"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn macro_cycles_are_expanded_once() {
+        let mut collection = MetadataCollection::default();
+        let mut search = Search::default();
+        search.add_patterns_buffer(
+            b"[attr]a b\n[attr]b a\nfile a",
+            Path::new("<memory>").into(),
+            None,
+            &mut collection,
+            true,
+        );
+        let mut out = Outcome::default();
+        out.initialize(&collection);
+        let mut trace = Vec::new();
+        search.pattern_matching_relative_path_with_trace("file".into(), Case::Sensitive, None, &mut out, &mut trace);
+        let actual: Vec<_> = trace.iter().map(|m| m.assignment.to_string()).collect();
+        assert_eq!(actual, ["a", "b", "a"]);
+    }
+
+    #[test]
+    fn nothing_is_traced_without_match() {
+        let mut collection = MetadataCollection::default();
+        let mut search = Search::default();
+        search.add_patterns_buffer(b"*.txt text", Path::new("<memory>").into(), None, &mut collection, true);
+        let mut out = Outcome::default();
+        out.initialize(&collection);
+        let mut trace = Vec::new();
+        assert!(!search.pattern_matching_relative_path_with_trace(
+            "file.bin".into(),
+            Case::Sensitive,
+            None,
+            &mut out,
+            &mut trace
+        ));
+        assert!(trace.is_empty());
+    }
+}
+
 #[test]
 fn size_of_outcome() {
     assert_eq!(
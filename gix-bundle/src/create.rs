@@ -0,0 +1,331 @@
+use gix_hash::ObjectId;
+
+use crate::{Header, Version};
+
+/// The error returned by [`create()`](crate::create()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Refusing to create an empty bundle as all refs are excluded or none were given")]
+    Empty,
+    #[error("The bundle would require {} object(s) which aren't present and thus couldn't be available to the receiver either: {}", ids.len(), ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    DanglingPrerequisites { ids: Vec<ObjectId> },
+    #[error(transparent)]
+    FindExistingCommit(#[from] gix_object::find::existing_object::Error),
+    #[error(transparent)]
+    FindExistingTree(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    FindExisting(#[from] gix_object::find::existing::Error),
+    #[error(transparent)]
+    WalkCommits(#[from] gix_traverse::commit::simple::Error),
+    #[error(transparent)]
+    DecodeObject(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    CountObjects(#[from] gix_pack::data::output::count::objects::Error),
+    #[error(transparent)]
+    CreateEntries(#[from] gix_pack::data::output::entry::iter_from_counts::Error),
+    #[error(transparent)]
+    WritePack(#[from] gix_pack::data::output::bytes::Error<gix_pack::data::output::entry::iter_from_counts::Error>),
+    #[error("Could not write the bundle header")]
+    Io(#[from] std::io::Error),
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+/// Options for [`create()`](crate::create()).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The version of the bundle to write. Both versions produce the same pack, but [v3](Version::V3) also states the
+    /// object format explicitly.
+    pub version: Version,
+    /// If `true`, commits that aren't present in the object database despite being needed, like the parents of shallow
+    /// commits, are listed as prerequisites instead of failing.
+    ///
+    /// Bundles created like this can only be used by receivers who obtained these commits in some other way.
+    pub allow_dangling_prerequisites: bool,
+    /// The amount of threads to use when creating pack entries, or `None` to use all logical cores.
+    pub thread_limit: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            version: Version::V2,
+            allow_dangling_prerequisites: false,
+            thread_limit: None,
+        }
+    }
+}
+
+/// The outcome of [`create()`](crate::create()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The header that was written, with the refs that were actually included and all prerequisites.
+    pub header: Header,
+    /// The amount of objects in the pack.
+    pub num_objects: usize,
+    /// The hash of the pack, as stored in its trailer.
+    pub pack_hash: ObjectId,
+}
+
+pub(crate) mod function {
+    use std::{
+        cell::RefCell,
+        io::Write,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use gix_features::{
+        parallel::InOrderIter,
+        progress,
+        progress::{DynNestedProgress, Progress},
+    };
+    use gix_hash::{oid, ObjectId};
+    use gix_hashtable::HashSet;
+    use gix_object::FindExt;
+    use gix_pack::data::output;
+
+    use super::{Error, Options, Outcome};
+    use crate::{Header, Prerequisite, Ref};
+
+    /// Write a bundle with all `refs` into `out`, along with a pack containing all objects reachable from them but not
+    /// from the `excluded` tips, similar to `git bundle create <file> <refs> ^<excluded>`, and return the written header.
+    ///
+    /// Refs may point to annotated tags, and those whose objects are reachable from `excluded` are left out.
+    /// Commits reachable from `excluded` that are parents of included commits become the prerequisites of the bundle, and
+    /// the objects in their trees are assumed to be present on the receiving side as well.
+    ///
+    /// `objects` is used to look up all objects, and `progress` and `should_interrupt` are used while creating the pack.
+    /// Note that object database handles need to be configured to prevent packs from being unloaded, as pack entries are
+    /// copied directly.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create<F>(
+        out: &mut dyn Write,
+        refs: Vec<Ref>,
+        excluded: &[ObjectId],
+        objects: F,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        Options {
+            version,
+            allow_dangling_prerequisites,
+            thread_limit,
+        }: Options,
+    ) -> Result<Outcome, Error>
+    where
+        F: gix_pack::Find + gix_object::Find + gix_object::Exists + Send + Clone + 'static,
+    {
+        let mut buf = Vec::new();
+        let excluded_commits = excluded
+            .iter()
+            .map(|id| peel(&objects, *id, &mut buf).map(|(id, _tags)| id))
+            .collect::<Result<Vec<_>, _>>()?;
+        // Parents beyond a shallow boundary remain unknown, but don't matter either as we only need to know what to leave out.
+        let hidden = gix_traverse::commit::Simple::filtered(excluded_commits, &objects, |id: &oid| objects.exists(id))
+            .map(|info| info.map(|info| info.id))
+            .collect::<Result<HashSet<_>, _>>()?;
+
+        let mut pack_ids = Vec::new();
+        let mut included_refs = Vec::new();
+        let mut commit_tips = Vec::new();
+        let mut other_tips = Vec::new();
+        for r in refs {
+            let (peeled, tags) = peel(&objects, r.id, &mut buf)?;
+            if hidden.contains(&peeled) {
+                continue;
+            }
+            pack_ids.extend(tags);
+            if objects.find(&peeled, &mut buf)?.kind == gix_object::Kind::Commit {
+                commit_tips.push(peeled);
+            } else {
+                other_tips.push(peeled);
+            }
+            included_refs.push(r);
+        }
+        let Some(object_hash) = included_refs.first().map(|r| r.id.kind()) else {
+            return Err(Error::Empty);
+        };
+
+        let missing = RefCell::new(Vec::new());
+        let mut commits = Vec::new();
+        let mut prerequisites = Vec::new();
+        let mut seen_prerequisites = HashSet::default();
+        for info in gix_traverse::commit::Simple::filtered(commit_tips, &objects, |id: &oid| {
+            if hidden.contains(id) {
+                return false;
+            }
+            let exists = objects.exists(id);
+            if !exists {
+                missing.borrow_mut().push(id.to_owned());
+            }
+            exists
+        }) {
+            let info = info?;
+            for parent in &info.parent_ids {
+                if hidden.contains(parent) && seen_prerequisites.insert(*parent) {
+                    prerequisites.push(*parent);
+                }
+            }
+            commits.push(info.id);
+        }
+        let dangling = missing.into_inner();
+        if !dangling.is_empty() && !allow_dangling_prerequisites {
+            return Err(Error::DanglingPrerequisites { ids: dangling });
+        }
+
+        let mut known = HashSet::default();
+        for id in &prerequisites {
+            let tree = objects.find_commit(id, &mut buf)?.tree();
+            collect_tree(&objects, tree, &mut known, &mut Vec::new(), &mut buf)?;
+        }
+        for id in commits {
+            if should_interrupt.load(Ordering::Relaxed) {
+                return Err(Error::Interrupted);
+            }
+            pack_ids.push(id);
+            let tree = objects.find_commit(&id, &mut buf)?.tree();
+            collect_tree(&objects, tree, &mut known, &mut pack_ids, &mut buf)?;
+        }
+        for id in other_tips {
+            if objects.find(&id, &mut buf)?.kind == gix_object::Kind::Tree {
+                collect_tree(&objects, id, &mut known, &mut pack_ids, &mut buf)?;
+            } else if known.insert(id) {
+                pack_ids.push(id);
+            }
+        }
+
+        let mut all_prerequisites = Vec::with_capacity(prerequisites.len() + dangling.len());
+        for id in prerequisites {
+            let comment = objects.find_commit(&id, &mut buf)?.message_summary().into_owned();
+            all_prerequisites.push(Prerequisite { id, comment });
+        }
+        all_prerequisites.extend(dangling.into_iter().map(|id| Prerequisite {
+            id,
+            comment: Default::default(),
+        }));
+        let header = Header {
+            version,
+            object_hash,
+            filter: None,
+            prerequisites: all_prerequisites,
+            refs: included_refs,
+        };
+        header.write_to(out)?;
+
+        let (num_objects, pack_hash) = write_pack(
+            out,
+            pack_ids,
+            objects,
+            object_hash,
+            progress,
+            should_interrupt,
+            thread_limit,
+        )?;
+        Ok(Outcome {
+            header,
+            num_objects,
+            pack_hash,
+        })
+    }
+
+    /// Follow annotated tags starting at `id` and return the first non-tag object along with all tags on the way.
+    fn peel(
+        objects: &dyn gix_object::Find,
+        mut id: ObjectId,
+        buf: &mut Vec<u8>,
+    ) -> Result<(ObjectId, Vec<ObjectId>), Error> {
+        let mut tags = Vec::new();
+        loop {
+            let data = objects.find(&id, buf)?;
+            if data.kind != gix_object::Kind::Tag {
+                return Ok((id, tags));
+            }
+            tags.push(id);
+            id = data.decode()?.into_tag().expect("is tag").target();
+        }
+    }
+
+    /// Add `tree` and everything reachable from it to `out` unless it's already `known`, without
+    /// descending into trees we know.
+    fn collect_tree(
+        objects: &dyn gix_object::Find,
+        tree: ObjectId,
+        known: &mut HashSet<ObjectId>,
+        out: &mut Vec<ObjectId>,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), Error> {
+        if !known.insert(tree) {
+            return Ok(());
+        }
+        out.push(tree);
+        let mut trees = vec![tree];
+        while let Some(tree) = trees.pop() {
+            for entry in objects.find_tree_iter(&tree, buf)? {
+                let entry = entry?;
+                if entry.mode.is_commit() {
+                    continue;
+                }
+                let id = entry.oid.to_owned();
+                if known.insert(id) {
+                    out.push(id);
+                    if entry.mode.is_tree() {
+                        trees.push(id);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_pack<F>(
+        out: &mut dyn Write,
+        ids: Vec<ObjectId>,
+        objects: F,
+        object_hash: gix_hash::Kind,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        thread_limit: Option<usize>,
+    ) -> Result<(usize, ObjectId), Error>
+    where
+        F: gix_pack::Find + Send + Clone + 'static,
+    {
+        let counts = {
+            let mut progress = progress.add_child("counting".into());
+            progress.init(Some(ids.len()), progress::count("objects"));
+            let (counts, _outcome) = output::count::objects_unthreaded(
+                &objects,
+                &mut ids.into_iter().map(Ok),
+                &progress,
+                should_interrupt,
+                output::count::objects::ObjectExpansion::AsIs,
+            )?;
+            counts
+        };
+        let num_objects = counts.len();
+        let mut entries = InOrderIter::from(output::entry::iter_from_counts(
+            counts,
+            objects,
+            Box::new(progress.add_child("creating entries".into())),
+            output::entry::iter_from_counts::Options {
+                thread_limit,
+                mode: output::entry::iter_from_counts::Mode::PackCopyAndBaseObjects,
+                allow_thin_pack: false,
+                chunk_size: 50,
+                version: Default::default(),
+                max_depth: 50,
+            },
+        ));
+        let mut write = output::bytes::FromEntriesIter::new(
+            entries.by_ref(),
+            out,
+            num_objects as u32,
+            gix_pack::data::Version::V2,
+            object_hash,
+        );
+        for res in write.by_ref() {
+            res?;
+        }
+        let pack_hash = write.digest().expect("iteration is done");
+        Ok((num_objects, pack_hash))
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    io::{BufReader, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use gix_features::progress::DynNestedProgress;
+
+use crate::{File, Header};
+
+///
+#[allow(clippy::empty_docs)]
+pub mod init {
+    use std::path::PathBuf;
+
+    /// The error returned by [`File::at()`](crate::File::at()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not open bundle at '{}'", path.display())]
+        Io { path: PathBuf, source: std::io::Error },
+        #[error("Could not decode the header of the bundle at '{}'", path.display())]
+        Header {
+            path: PathBuf,
+            source: crate::header::decode::Error,
+        },
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod write {
+    use gix_hash::ObjectId;
+
+    /// The error returned by [`File::write_pack_to_directory()`](crate::File::write_pack_to_directory()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The bundle uses {bundle} object ids, but {expected} was expected")]
+        ObjectHashMismatch {
+            bundle: gix_hash::Kind,
+            expected: gix_hash::Kind,
+        },
+        #[error("The bundle requires {} object(s) which aren't present: {}", ids.len(), ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+        MissingPrerequisites { ids: Vec<ObjectId> },
+        #[error("Could not re-open the bundle to read its pack")]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        WritePack(#[from] gix_pack::bundle::write::Error),
+    }
+}
+
+/// Initialization
+impl File {
+    /// Open the bundle at `path` and decode its header.
+    pub fn at(path: impl Into<PathBuf>) -> Result<Self, init::Error> {
+        let path = path.into();
+        let mut read = std::fs::File::open(&path)
+            .map(BufReader::new)
+            .map_err(|source| init::Error::Io {
+                path: path.clone(),
+                source,
+            })?;
+        let (header, pack_offset) = Header::from_read(&mut read).map_err(|source| init::Error::Header {
+            path: path.clone(),
+            source,
+        })?;
+        Ok(File {
+            header,
+            path,
+            pack_offset,
+        })
+    }
+}
+
+/// Writing
+impl File {
+    /// Write the pack of this bundle along with a newly generated index into `directory`, or only verify it if `directory` is `None`,
+    /// after assuring that all prerequisites are present in `objects`.
+    ///
+    /// `objects` is also used to resolve the bases of deltas as packs in bundles are thin if they have prerequisites.
+    /// `progress` and `should_interrupt` are passed to [`gix_pack::Bundle::write_to_directory()`] along with `options`, whose
+    /// `object_hash` must match the one of the bundle.
+    pub fn write_pack_to_directory<F>(
+        &self,
+        directory: Option<&Path>,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        objects: F,
+        options: gix_pack::bundle::write::Options,
+    ) -> Result<gix_pack::bundle::write::Outcome, write::Error>
+    where
+        F: gix_object::Find + gix_object::Exists,
+    {
+        if options.object_hash != self.header.object_hash {
+            return Err(write::Error::ObjectHashMismatch {
+                bundle: self.header.object_hash,
+                expected: options.object_hash,
+            });
+        }
+        let missing = self.header.missing_prerequisites(&objects);
+        if !missing.is_empty() {
+            return Err(write::Error::MissingPrerequisites { ids: missing });
+        }
+
+        let mut file = std::fs::File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.pack_offset))?;
+        let is_thin = !self.header.prerequisites.is_empty();
+        Ok(gix_pack::Bundle::write_to_directory(
+            &mut BufReader::new(file),
+            directory,
+            progress,
+            should_interrupt,
+            is_thin.then_some(objects),
+            options,
+        )?)
+    }
+}
@@ -0,0 +1,194 @@
+use std::io::BufRead;
+
+use bstr::ByteSlice;
+use gix_hash::ObjectId;
+
+use crate::{Header, Prerequisite, Ref, Version};
+
+///
+#[allow(clippy::empty_docs)]
+pub mod decode {
+    use bstr::BString;
+
+    /// The error returned by [`Header::from_read()`](crate::Header::from_read()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not read the bundle header")]
+        Io(#[from] std::io::Error),
+        #[error("Expected a '# v2 git bundle' or '# v3 git bundle' signature, got {signature:?}")]
+        UnknownSignature { signature: BString },
+        #[error("The bundle capability {capability:?} is not supported")]
+        UnsupportedCapability { capability: BString },
+        #[error("The object format {format:?} of the bundle is not supported")]
+        UnsupportedObjectFormat { format: BString },
+        #[error("Could not parse prerequisite or ref line {line:?}")]
+        InvalidLine { line: BString },
+        #[error("The bundle header ended before the empty line separating it from the pack")]
+        UnexpectedEof,
+    }
+}
+
+/// The length of the signature line of all bundle versions, including the newline.
+pub(crate) const SIGNATURE_LEN: usize = b"# v2 git bundle\n".len();
+
+pub(crate) fn version_from_signature(line: &[u8]) -> Option<Version> {
+    Some(match line.strip_suffix(b"\n").unwrap_or(line) {
+        b"# v2 git bundle" => Version::V2,
+        b"# v3 git bundle" => Version::V3,
+        _ => return None,
+    })
+}
+
+/// Decoding
+impl Header {
+    /// Read a bundle header from `read`, which leaves `read` positioned at the start of the pack, and return it
+    /// along with the amount of bytes it occupies.
+    ///
+    /// Capabilities are only understood in [v3](Version::V3) bundles, and like `git`, we fail on unknown ones
+    /// as they might change how the bundle has to be interpreted.
+    pub fn from_read(read: &mut dyn BufRead) -> Result<(Self, u64), decode::Error> {
+        let mut line = Vec::new();
+        let mut bytes_read = read.read_until(b'\n', &mut line)? as u64;
+        let version = version_from_signature(&line).ok_or_else(|| decode::Error::UnknownSignature {
+            signature: line.trim_end().into(),
+        })?;
+        let mut header = Header {
+            version,
+            object_hash: gix_hash::Kind::Sha1,
+            filter: None,
+            prerequisites: Vec::new(),
+            refs: Vec::new(),
+        };
+        loop {
+            line.clear();
+            let num_bytes = read.read_until(b'\n', &mut line)?;
+            if num_bytes == 0 {
+                return Err(decode::Error::UnexpectedEof);
+            }
+            bytes_read += num_bytes as u64;
+            let line = line.strip_suffix(b"\n").unwrap_or(&line);
+            if line.is_empty() {
+                break;
+            }
+            match line.strip_prefix(b"@") {
+                Some(capability) if version == Version::V3 => header.parse_capability(capability)?,
+                _ => header.parse_prerequisite_or_ref(line)?,
+            }
+        }
+        Ok((header, bytes_read))
+    }
+
+    fn parse_capability(&mut self, capability: &[u8]) -> Result<(), decode::Error> {
+        let (name, value) = match capability.find_byte(b'=') {
+            Some(pos) => (&capability[..pos], Some(&capability[pos + 1..])),
+            None => (capability, None),
+        };
+        match (name, value) {
+            (b"object-format", Some(format)) => {
+                self.object_hash = format
+                    .to_str()
+                    .ok()
+                    .and_then(|format| format.parse().ok())
+                    .ok_or_else(|| decode::Error::UnsupportedObjectFormat { format: format.into() })?;
+            }
+            (b"filter", Some(filter)) => self.filter = Some(filter.into()),
+            _ => {
+                return Err(decode::Error::UnsupportedCapability {
+                    capability: capability.into(),
+                })
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_prerequisite_or_ref(&mut self, line: &[u8]) -> Result<(), decode::Error> {
+        let invalid = || decode::Error::InvalidLine { line: line.into() };
+        let (is_prerequisite, rest) = match line.strip_prefix(b"-") {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let hex_len = self.object_hash.len_in_hex();
+        let hex = rest.get(..hex_len).ok_or_else(invalid)?;
+        let id = ObjectId::from_hex(hex).map_err(|_| invalid())?;
+        let rest = &rest[hex_len..];
+        if is_prerequisite {
+            let comment = match rest.strip_prefix(b" ") {
+                Some(comment) => comment,
+                None if rest.is_empty() => rest,
+                None => return Err(invalid()),
+            };
+            self.prerequisites.push(Prerequisite {
+                id,
+                comment: comment.into(),
+            });
+        } else {
+            let name = rest
+                .strip_prefix(b" ")
+                .filter(|name| !name.is_empty())
+                .ok_or_else(invalid)?;
+            self.refs.push(Ref { name: name.into(), id });
+        }
+        Ok(())
+    }
+}
+
+/// Encoding
+impl Header {
+    /// Write this header to `out`, including the empty line that separates it from the pack.
+    ///
+    /// Capabilities are only written for [v3](Version::V3) bundles, while [v2](Version::V2) bundles imply SHA1.
+    /// Thus, writing a v2 header fails with [`std::io::ErrorKind::InvalidInput`] if it uses another hash or a filter,
+    /// as these couldn't be represented.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        if self.version == Version::V2 {
+            let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidInput, msg);
+            if self.object_hash != gix_hash::Kind::Sha1 {
+                return Err(invalid("v2 bundles can only use SHA1 object ids"));
+            }
+            if self.filter.is_some() {
+                return Err(invalid("v2 bundles can't be filtered"));
+            }
+        }
+        out.write_all(match self.version {
+            Version::V2 => b"# v2 git bundle\n",
+            Version::V3 => b"# v3 git bundle\n",
+        })?;
+        if self.version == Version::V3 {
+            writeln!(out, "@object-format={}", self.object_hash.to_string().to_lowercase())?;
+            if let Some(filter) = &self.filter {
+                out.write_all(b"@filter=")?;
+                out.write_all(filter)?;
+                out.write_all(b"\n")?;
+            }
+        }
+        for prerequisite in &self.prerequisites {
+            write!(out, "-{}", prerequisite.id)?;
+            if !prerequisite.comment.is_empty() {
+                out.write_all(b" ")?;
+                out.write_all(&prerequisite.comment)?;
+            }
+            out.write_all(b"\n")?;
+        }
+        for r in &self.refs {
+            write!(out, "{} ", r.id)?;
+            out.write_all(&r.name)?;
+            out.write_all(b"\n")?;
+        }
+        out.write_all(b"\n")
+    }
+}
+
+/// Access
+impl Header {
+    /// Return the ids of all prerequisites that aren't present in `objects`, in the order they were listed.
+    ///
+    /// Note that unlike `git`, we don't check if the prerequisites are also reachable from existing references.
+    pub fn missing_prerequisites(&self, objects: &dyn gix_object::Exists) -> Vec<ObjectId> {
+        self.prerequisites
+            .iter()
+            .filter(|prerequisite| !objects.exists(&prerequisite.id))
+            .map(|prerequisite| prerequisite.id)
+            .collect()
+    }
+}
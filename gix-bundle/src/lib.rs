@@ -0,0 +1,98 @@
+//! Read and write [git bundles](https://git-scm.com/docs/gitformat-bundle), files which contain a pack along with the references
+//! it provides and the objects it expects to be present already.
+//!
+//! Bundles are typically created with `git bundle create` and are used to transfer objects without a network connection.
+#![deny(missing_docs, rust_2018_idioms)]
+#![forbid(unsafe_code)]
+
+use std::path::PathBuf;
+
+use bstr::BString;
+use gix_hash::ObjectId;
+
+/// The version of a bundle as indicated by its signature line.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Version {
+    /// The original bundle format with the `# v2 git bundle` signature, which always uses SHA1.
+    V2,
+    /// The bundle format with the `# v3 git bundle` signature which additionally supports capabilities.
+    V3,
+}
+
+/// An object that has to be present in the object database before the pack of a bundle can be used.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Prerequisite {
+    /// The id of the object, usually a commit.
+    pub id: ObjectId,
+    /// A human-readable comment, typically the subject of the commit, which may be empty.
+    pub comment: BString,
+}
+
+/// A reference provided by a bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Ref {
+    /// The full name of the reference, like `refs/heads/main` or `HEAD`.
+    pub name: BString,
+    /// The object the reference points to, which is contained in the pack or one of the prerequisites.
+    pub id: ObjectId,
+}
+
+/// The header of a bundle which precedes its pack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    /// The version of the bundle.
+    pub version: Version,
+    /// The kind of hash used for all object ids in the bundle, as set by the `object-format` capability.
+    pub object_hash: gix_hash::Kind,
+    /// The object filter the pack was created with, as set by the `filter` capability, like `blob:none`.
+    ///
+    /// If set, the pack is incomplete and objects excluded by the filter won't be present.
+    pub filter: Option<BString>,
+    /// The objects that must be present before the pack can be used.
+    pub prerequisites: Vec<Prerequisite>,
+    /// The references provided by the bundle, in the order they were listed.
+    pub refs: Vec<Ref>,
+}
+
+/// A bundle file on disk along with its parsed header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    /// The header of the bundle.
+    pub header: Header,
+    /// The path at which the bundle was opened.
+    pub path: PathBuf,
+    /// The offset in bytes at which the pack data starts.
+    pub pack_offset: u64,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod header;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod file;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod create;
+pub use create::function::create;
+
+/// Return `true` if the file at `path` starts with a bundle signature, which is what `git` uses to decide if a path
+/// should be treated as bundle.
+pub fn is_bundle(path: &std::path::Path) -> bool {
+    use std::io::Read;
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    // Read no more than the signature, as any file may be passed here.
+    let mut signature = Vec::with_capacity(header::SIGNATURE_LEN);
+    if file
+        .take(header::SIGNATURE_LEN as u64)
+        .read_to_end(&mut signature)
+        .is_err()
+    {
+        return false;
+    }
+    header::version_from_signature(&signature).is_some()
+}
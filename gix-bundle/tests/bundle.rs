@@ -0,0 +1,419 @@
+use std::{path::PathBuf, sync::atomic::AtomicBool};
+
+use gix_bundle::{file, header, File, Header, Prerequisite, Ref, Version};
+use gix_features::progress;
+use gix_hash::ObjectId;
+
+type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn hex_to_id(hex: &str) -> ObjectId {
+    ObjectId::from_hex(hex.as_bytes()).expect("40 bytes hex")
+}
+
+fn fixture(name: &str) -> PathBuf {
+    gix_testtools::scripted_fixture_read_only("make_bundles.sh")
+        .expect("fixture available")
+        .join(name)
+}
+
+fn odb(repo: &str) -> gix_odb::Handle {
+    gix_odb::at(fixture(repo).join(".git").join("objects")).expect("valid odb")
+}
+
+fn options() -> gix_pack::bundle::write::Options {
+    gix_pack::bundle::write::Options {
+        thread_limit: Some(1),
+        iteration_mode: gix_pack::data::input::Mode::Verify,
+        index_version: Default::default(),
+        object_hash: gix_hash::Kind::Sha1,
+    }
+}
+
+const FIRST: &str = "7cc695c1f307a6e9ac4509590c41e16dddf56bd5";
+const SECOND: &str = "2d1e7bfd3dfc05a225d330e82b8627eadf3c5df7";
+
+mod from_read {
+    use super::*;
+
+    fn decode(input: &str) -> std::result::Result<(Header, u64), header::decode::Error> {
+        Header::from_read(&mut input.as_bytes())
+    }
+
+    #[test]
+    fn v3_with_capabilities_prerequisites_and_refs() -> Result {
+        let input = format!(
+            "# v3 git bundle\n@object-format=sha1\n@filter=blob:none\n-{FIRST} first\n-{SECOND}\n{SECOND} refs/heads/main\n\nPACK"
+        );
+        let (header, pack_offset) = decode(&input)?;
+        assert_eq!(
+            header,
+            Header {
+                version: Version::V3,
+                object_hash: gix_hash::Kind::Sha1,
+                filter: Some("blob:none".into()),
+                prerequisites: vec![
+                    Prerequisite {
+                        id: hex_to_id(FIRST),
+                        comment: "first".into()
+                    },
+                    Prerequisite {
+                        id: hex_to_id(SECOND),
+                        comment: "".into()
+                    }
+                ],
+                refs: vec![Ref {
+                    name: "refs/heads/main".into(),
+                    id: hex_to_id(SECOND)
+                }]
+            }
+        );
+        assert_eq!(&input[pack_offset as usize..], "PACK", "the offset points to the pack");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_headers() {
+        assert!(matches!(
+            decode("# v4 git bundle\n\nPACK"),
+            Err(header::decode::Error::UnknownSignature { signature }) if signature == "# v4 git bundle"
+        ));
+        assert!(matches!(
+            decode("# v3 git bundle\n@object-format=sha512\n\n"),
+            Err(header::decode::Error::UnsupportedObjectFormat { format }) if format == "sha512"
+        ));
+        assert!(matches!(
+            decode("# v3 git bundle\n@unknown\n\n"),
+            Err(header::decode::Error::UnsupportedCapability { capability }) if capability == "unknown"
+        ));
+        assert!(
+            matches!(
+                decode("# v2 git bundle\n@object-format=sha1\n\n"),
+                Err(header::decode::Error::InvalidLine { line }) if line == "@object-format=sha1"
+            ),
+            "capabilities are only known in v3"
+        );
+        assert!(matches!(
+            decode(&format!("# v2 git bundle\n{FIRST}\n\n")),
+            Err(header::decode::Error::InvalidLine { .. })
+        ));
+        assert!(matches!(
+            decode(&format!("# v2 git bundle\n{FIRST} refs/heads/main\n")),
+            Err(header::decode::Error::UnexpectedEof)
+        ));
+    }
+}
+
+mod write_to {
+    use super::*;
+
+    fn header(version: Version) -> Header {
+        Header {
+            version,
+            object_hash: gix_hash::Kind::Sha1,
+            filter: None,
+            prerequisites: Vec::new(),
+            refs: vec![Ref {
+                name: "refs/heads/main".into(),
+                id: hex_to_id(SECOND),
+            }],
+        }
+    }
+
+    #[test]
+    fn v2_and_v3_round_trip() -> Result {
+        for version in [Version::V2, Version::V3] {
+            let header = header(version);
+            let mut buf = Vec::new();
+            header.write_to(&mut buf)?;
+            assert_eq!(Header::from_read(&mut buf.as_slice())?, (header, buf.len() as u64));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn v2_with_filter_is_refused() {
+        let header = Header {
+            filter: Some("blob:none".into()),
+            ..header(Version::V2)
+        };
+        let mut buf = Vec::new();
+        let err = header.write_to(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(buf.is_empty(), "nothing is written");
+    }
+
+    #[test]
+    #[cfg(feature = "sha256")]
+    fn v2_with_sha256_is_refused() {
+        let header = Header {
+            object_hash: gix_hash::Kind::Sha256,
+            refs: Vec::new(),
+            ..header(Version::V2)
+        };
+        let mut buf = Vec::new();
+        let err = header.write_to(&mut buf).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(buf.is_empty(), "nothing is written");
+    }
+}
+
+#[test]
+fn full_bundle_can_be_verified_without_objects() -> Result {
+    let bundle = File::at(fixture("full.bundle"))?;
+    assert_eq!(bundle.header.version, Version::V2);
+    assert!(bundle.header.prerequisites.is_empty());
+    assert_eq!(
+        bundle.header.refs,
+        [("refs/heads/main", SECOND), ("refs/tags/v1", FIRST), ("HEAD", SECOND)]
+            .into_iter()
+            .map(|(name, id)| Ref {
+                name: name.into(),
+                id: hex_to_id(id)
+            })
+            .collect::<Vec<_>>()
+    );
+
+    let outcome = bundle.write_pack_to_directory(
+        None,
+        &mut progress::Discard,
+        &AtomicBool::default(),
+        gix_object::find::Never,
+        options(),
+    )?;
+    assert_eq!(outcome.index.num_objects, 6, "two commits, two trees and two blobs");
+    assert!(outcome.data_path.is_none(), "nothing was written");
+    Ok(())
+}
+
+#[test]
+fn v3_bundle() -> Result {
+    let bundle = File::at(fixture("v3.bundle"))?;
+    assert_eq!(bundle.header.version, Version::V3);
+    assert_eq!(bundle.header.object_hash, gix_hash::Kind::Sha1);
+    assert_eq!(bundle.header.filter, None);
+    Ok(())
+}
+
+#[test]
+fn incremental_bundle_needs_its_prerequisites() -> Result {
+    let bundle = File::at(fixture("incremental.bundle"))?;
+    assert_eq!(
+        bundle.header.prerequisites,
+        vec![Prerequisite {
+            id: hex_to_id(FIRST),
+            comment: "first".into()
+        }]
+    );
+
+    let err = bundle
+        .write_pack_to_directory(
+            None,
+            &mut progress::Discard,
+            &AtomicBool::default(),
+            gix_object::find::Never,
+            options(),
+        )
+        .unwrap_err();
+    assert!(matches!(err, file::write::Error::MissingPrerequisites { ids } if ids == [hex_to_id(FIRST)]));
+
+    let base = odb("base");
+    assert!(!gix_object::Exists::exists(&base, &hex_to_id(SECOND)));
+    let dir = gix_testtools::tempfile::TempDir::new()?;
+    let outcome = bundle.write_pack_to_directory(
+        Some(dir.path()),
+        &mut progress::Discard,
+        &AtomicBool::default(),
+        base,
+        options(),
+    )?;
+    assert!(outcome.data_path.expect("written").is_file());
+    assert_eq!(
+        outcome.index.num_objects, 3,
+        "the thin pack was completed and contains one commit, tree and blob each"
+    );
+    Ok(())
+}
+
+mod create {
+    use super::*;
+
+    fn create(
+        repo: &str,
+        refs: &[(&str, ObjectId)],
+        excluded: &[ObjectId],
+        options: gix_bundle::create::Options,
+    ) -> std::result::Result<(Vec<u8>, gix_bundle::create::Outcome), gix_bundle::create::Error> {
+        let mut out = Vec::new();
+        let outcome = gix_bundle::create(
+            &mut out,
+            refs.iter()
+                .map(|(name, id)| Ref {
+                    name: (*name).into(),
+                    id: *id,
+                })
+                .collect(),
+            excluded,
+            {
+                let mut odb = odb(repo).into_arc().expect("no error with fresh handle");
+                odb.prevent_pack_unload();
+                odb
+            },
+            &mut progress::Discard,
+            &AtomicBool::default(),
+            options,
+        )?;
+        Ok((out, outcome))
+    }
+
+    fn write_and_open(data: &[u8]) -> Result<(gix_testtools::tempfile::TempDir, File)> {
+        let dir = gix_testtools::tempfile::TempDir::new()?;
+        let path = dir.path().join("created.bundle");
+        std::fs::write(&path, data)?;
+        let bundle = File::at(path)?;
+        Ok((dir, bundle))
+    }
+
+    #[test]
+    fn full() -> Result {
+        let (data, outcome) = create(
+            "repo",
+            &[("refs/heads/main", hex_to_id(SECOND)), ("HEAD", hex_to_id(SECOND))],
+            &[],
+            Default::default(),
+        )?;
+        assert_eq!(outcome.num_objects, 6, "two commits, two trees and two blobs");
+        assert!(data.starts_with(b"# v2 git bundle\n"));
+
+        let (_dir, bundle) = write_and_open(&data)?;
+        assert_eq!(bundle.header, outcome.header, "what we write is what we read");
+        assert!(bundle.header.prerequisites.is_empty());
+        let written = bundle.write_pack_to_directory(
+            None,
+            &mut progress::Discard,
+            &AtomicBool::default(),
+            gix_object::find::Never,
+            options(),
+        )?;
+        assert_eq!(written.index.num_objects, 6);
+        assert_eq!(written.index.data_hash, outcome.pack_hash);
+        Ok(())
+    }
+
+    #[test]
+    fn incremental_with_annotated_tag_and_v3() -> Result {
+        let tag = hex_to_id(std::fs::read_to_string(fixture("repo/.git/refs/tags/annotated"))?.trim());
+        let (data, outcome) = create(
+            "repo",
+            &[("refs/heads/main", hex_to_id(SECOND)), ("refs/tags/annotated", tag)],
+            &[hex_to_id(FIRST)],
+            gix_bundle::create::Options {
+                version: Version::V3,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(outcome.num_objects, 4, "the tag, and one commit, tree and blob each");
+        assert_eq!(
+            outcome.header.prerequisites,
+            vec![Prerequisite {
+                id: hex_to_id(FIRST),
+                comment: "first".into()
+            }]
+        );
+        assert!(data.starts_with(b"# v3 git bundle\n@object-format=sha1\n"));
+
+        let (_dir, bundle) = write_and_open(&data)?;
+        assert_eq!(bundle.header, outcome.header);
+        let dir = gix_testtools::tempfile::TempDir::new()?;
+        let written = bundle.write_pack_to_directory(
+            Some(dir.path()),
+            &mut progress::Discard,
+            &AtomicBool::default(),
+            odb("base"),
+            options(),
+        )?;
+        assert_eq!(written.index.num_objects, 4, "the pack isn't thin and complete");
+        Ok(())
+    }
+
+    #[test]
+    fn excluded_refs_are_skipped_and_empty_bundles_are_refused() -> Result {
+        let (_data, outcome) = create(
+            "repo",
+            &[
+                ("refs/tags/v1", hex_to_id(FIRST)),
+                ("refs/heads/main", hex_to_id(SECOND)),
+            ],
+            &[hex_to_id(FIRST)],
+            Default::default(),
+        )?;
+        assert_eq!(
+            outcome.header.refs,
+            vec![Ref {
+                name: "refs/heads/main".into(),
+                id: hex_to_id(SECOND)
+            }],
+            "refs that the receiver has already are left out"
+        );
+
+        let err = create(
+            "repo",
+            &[("refs/tags/v1", hex_to_id(FIRST))],
+            &[hex_to_id(FIRST)],
+            Default::default(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, gix_bundle::create::Error::Empty));
+        assert!(matches!(
+            create("repo", &[], &[], Default::default()).unwrap_err(),
+            gix_bundle::create::Error::Empty
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn dangling_prerequisites_need_to_be_allowed() -> Result {
+        let refs = [("refs/heads/main", hex_to_id(SECOND))];
+        let err = create("shallow", &refs, &[], Default::default()).unwrap_err();
+        assert!(
+            matches!(err, gix_bundle::create::Error::DanglingPrerequisites { ids } if ids == [hex_to_id(FIRST)]),
+            "the parent of the shallow commit is missing"
+        );
+
+        let (_data, outcome) = create(
+            "shallow",
+            &refs,
+            &[],
+            gix_bundle::create::Options {
+                allow_dangling_prerequisites: true,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(
+            outcome.header.prerequisites,
+            vec![Prerequisite {
+                id: hex_to_id(FIRST),
+                comment: "".into()
+            }],
+            "there is no comment as the commit isn't available"
+        );
+        assert_eq!(outcome.num_objects, 3);
+        Ok(())
+    }
+}
+
+#[test]
+fn is_bundle() {
+    assert!(gix_bundle::is_bundle(&fixture("full.bundle")));
+    assert!(gix_bundle::is_bundle(&fixture("v3.bundle")));
+    assert!(!gix_bundle::is_bundle(&fixture("repo")));
+    assert!(!gix_bundle::is_bundle(&fixture("repo").join("file")));
+    assert!(!gix_bundle::is_bundle(&fixture("does-not-exist")));
+
+    let dir = gix_testtools::tempfile::tempdir().expect("can create a temp dir");
+    let path = dir.path().join("no-newline");
+    std::fs::write(&path, "# v2 git bundle and more without newline").expect("can write");
+    assert!(
+        !gix_bundle::is_bundle(&path),
+        "only signatures followed by a newline count"
+    );
+}
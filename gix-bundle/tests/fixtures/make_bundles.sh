@@ -0,0 +1,27 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q repo
+(cd repo
+  git checkout -q -b main
+  echo one > file && git add file && git commit -q -m "first"
+  git tag v1
+  echo two > file && git commit -q -am "second"
+
+  git bundle create -q ../full.bundle --all
+  git bundle create -q ../incremental.bundle main~1..main
+  git bundle create -q --version=3 ../v3.bundle main
+)
+
+git clone -q --no-local repo base
+(cd base
+  git remote remove origin
+  git reset -q --hard HEAD~1
+  git reflog expire --expire=now --all
+  git gc -q --prune=now
+)
+
+git clone -q --depth 1 "file://$PWD/repo" shallow
+(cd repo
+  git tag -a -m "annotated" annotated
+)
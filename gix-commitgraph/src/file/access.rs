@@ -4,7 +4,7 @@ use std::{
 };
 
 use crate::{
-    file::{self, commit::Commit, COMMIT_DATA_ENTRY_SIZE_SANS_HASH},
+    file::{self, commit::Commit, COMMIT_DATA_ENTRY_SIZE_SANS_HASH, GENERATION_DATA_OVERFLOW_MASK},
     File,
 };
 
@@ -26,6 +26,12 @@ impl File {
         Commit::new(self, pos)
     }
 
+    /// Return `true` if this file stores [corrected commit dates][Commit::corrected_commit_date()] as generation
+    /// numbers v2 in addition to topological generation numbers.
+    pub fn has_generation_data(&self) -> bool {
+        self.generation_data_offset.is_some()
+    }
+
     /// The kind of hash used in this File.
     ///
     /// Note that it is always conforming to the hash used in the owning repository.
@@ -127,6 +133,21 @@ impl File {
         &self.data[start..][..entry_size]
     }
 
+    /// Returns the difference between the corrected commit date and the committer timestamp of the commit at `pos`,
+    /// as stored in this file's Generation Data (GDA2) chunk and its overflow (GDO2) chunk.
+    ///
+    /// It's `None` if there is no generation data or if it refers to a non-existing overflow entry.
+    pub(crate) fn corrected_commit_date_offset(&self, pos: file::Position) -> Option<u64> {
+        let start = self.generation_data_offset? + pos.0 as usize * 4;
+        let offset = u32::from_be_bytes(self.data[start..][..4].try_into().unwrap());
+        if offset & GENERATION_DATA_OVERFLOW_MASK == 0 {
+            return Some(offset.into());
+        }
+        let overflow_index = (offset & !GENERATION_DATA_OVERFLOW_MASK) as usize;
+        let overflow = self.data[self.generation_data_overflow_range.clone()?].get(overflow_index * 8..)?;
+        Some(u64::from_be_bytes(overflow.get(..8)?.try_into().unwrap()))
+    }
+
     /// Returns the byte slice for this file's entire Extra Edge List (EDGE) chunk.
     pub(crate) fn extra_edges_data(&self) -> Option<&[u8]> {
         Some(&self.data[self.extra_edges_list_range.clone()?])
@@ -74,6 +74,17 @@ impl<'a> Commit<'a> {
         self.generation
     }
 
+    /// Returns the corrected commit date of this commit, which is used as generation number v2, or `None` if the
+    /// owning [File] doesn't store generation data.
+    ///
+    /// It's the committer timestamp of this commit, unless a parent has a corrected commit date that is the same or
+    /// later, in which case it is one more than the largest corrected commit date among the parents.
+    pub fn corrected_commit_date(&self) -> Option<u64> {
+        self.file
+            .corrected_commit_date_offset(self.pos)
+            .map(|offset| self.commit_timestamp + offset)
+    }
+
     /// Returns an iterator over the parent positions for lookup in the owning [Graph][crate::Graph].
     pub fn iter_parents(self) -> Parents<'a> {
         // I didn't find a combinator approach that a) was as strict as ParentIterator, b) supported
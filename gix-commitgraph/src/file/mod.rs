@@ -8,6 +8,7 @@ mod access;
 pub mod commit;
 mod init;
 pub mod verify;
+pub mod write;
 
 const COMMIT_DATA_ENTRY_SIZE_SANS_HASH: usize = 16;
 pub(crate) const FAN_LEN: usize = 256;
@@ -19,6 +20,8 @@ type ChunkId = gix_chunk::Id;
 const BASE_GRAPHS_LIST_CHUNK_ID: ChunkId = *b"BASE";
 const COMMIT_DATA_CHUNK_ID: ChunkId = *b"CDAT";
 const EXTENDED_EDGES_LIST_CHUNK_ID: ChunkId = *b"EDGE";
+const GENERATION_DATA_CHUNK_ID: ChunkId = *b"GDA2";
+const GENERATION_DATA_OVERFLOW_CHUNK_ID: ChunkId = *b"GDO2";
 const OID_FAN_CHUNK_ID: ChunkId = *b"OIDF";
 const OID_LOOKUP_CHUNK_ID: ChunkId = *b"OIDL";
 
@@ -27,6 +30,8 @@ const OID_LOOKUP_CHUNK_ID: ChunkId = *b"OIDL";
 const NO_PARENT: u32 = 0x7000_0000;
 const EXTENDED_EDGES_MASK: u32 = 0x8000_0000;
 const LAST_EXTENDED_EDGE_MASK: u32 = 0x8000_0000;
+/// If set in a generation data entry, the remaining bits are an index into the generation data overflow list.
+const GENERATION_DATA_OVERFLOW_MASK: u32 = 0x8000_0000;
 
 /// The position of a given commit within a graph file, starting at 0.
 ///
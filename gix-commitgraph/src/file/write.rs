@@ -0,0 +1,349 @@
+//! Write commit-graph files from commit information.
+use std::{
+    cmp::{max, min},
+    io,
+    io::Write,
+};
+
+use gix_hash::ObjectId;
+
+use crate::{
+    file::{
+        BASE_GRAPHS_LIST_CHUNK_ID, COMMIT_DATA_CHUNK_ID, EXTENDED_EDGES_LIST_CHUNK_ID, EXTENDED_EDGES_MASK, FAN_LEN,
+        GENERATION_DATA_CHUNK_ID, GENERATION_DATA_OVERFLOW_CHUNK_ID, GENERATION_DATA_OVERFLOW_MASK, HEADER_LEN,
+        LAST_EXTENDED_EDGE_MASK, NO_PARENT, OID_FAN_CHUNK_ID, OID_LOOKUP_CHUNK_ID, SIGNATURE,
+    },
+    File, Graph, GENERATION_NUMBER_MAX, MAX_COMMITS,
+};
+
+/// The error returned by [`File::write_from_commits()`].
+#[derive(thiserror::Error, Debug)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not write commit-graph file")]
+    Io(#[from] io::Error),
+    #[error("Parent {parent} of commit {id} is neither among the commits to write nor in the base graph")]
+    MissingParent { id: ObjectId, parent: ObjectId },
+    #[error("Commit {id} is its own ancestor")]
+    Cycle { id: ObjectId },
+    #[error(
+        "Commit-graph would contain {0} commits altogether, but only {} commits are allowed",
+        MAX_COMMITS
+    )]
+    TooManyCommits(u64),
+    #[error("The base graph consists of {0} files, but at most 255 are allowed")]
+    TooManyBaseGraphs(usize),
+}
+
+/// A commit to write into a commit-graph file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Commit {
+    /// The id of the commit.
+    pub id: ObjectId,
+    /// The id of the tree the commit points to.
+    pub tree: ObjectId,
+    /// The ids of all parents, in order.
+    pub parents: Vec<ObjectId>,
+    /// The committer timestamp in seconds since 1970-01-01 00:00:00 UTC.
+    ///
+    /// Note that only the lower 34 bits can be stored.
+    pub committer_timestamp: u64,
+}
+
+/// The options for use in [`File::write_from_commits()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// The kind of hash to use for the file's checksum, which must match the kind of hash of all commits.
+    pub object_hash: gix_hash::Kind,
+    /// If `true`, store corrected commit dates as generation numbers v2 along with topological generation numbers.
+    ///
+    /// They are not written if a file of the base graph doesn't have them either, as they are only used if all files do.
+    pub generation_data: bool,
+}
+
+impl Default for Options {
+    /// Options which write generation numbers v2 and SHA1 checksums, just like `git` does by default.
+    fn default() -> Self {
+        Options {
+            object_hash: gix_hash::Kind::Sha1,
+            generation_data: true,
+        }
+    }
+}
+
+/// The outcome of [`File::write_from_commits()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outcome {
+    /// The trailing checksum of the written file, which also is its name if it's part of a split commit-graph.
+    pub checksum: ObjectId,
+    /// The amount of commits in the written file.
+    pub num_commits: u32,
+    /// If `true`, corrected commit dates were written as well.
+    pub generation_data: bool,
+    /// The amount of corrected commit dates that were too far in the future of their commit's committer timestamp
+    /// to be stored directly, and thus were written to the generation data overflow list instead.
+    pub num_generation_data_overflows: u32,
+}
+
+/// The largest difference between a corrected commit date and its committer timestamp that can be stored without overflow.
+const GENERATION_DATA_OFFSET_MAX: u64 = 0x7fff_ffff;
+/// The part of the committer timestamp that can be stored.
+const COMMIT_TIMESTAMP_MASK: u64 = 0x0003_ffff_ffff;
+
+/// The generation numbers of a commit.
+#[derive(Clone, Copy, Default)]
+struct Generation {
+    topological: u32,
+    corrected_commit_date: u64,
+}
+
+/// Writing
+impl File {
+    /// Write a commit-graph file with all `commits` into `out`, skipping all commits that are already contained in `base`.
+    ///
+    /// If `base` is `Some`, the written file is meant to be added as new layer on top of all files of `base`
+    /// in a split commit-graph chain, and parents of `commits` may be contained in `base`.
+    /// Otherwise, all parents must be part of `commits`.
+    ///
+    /// Topological generation numbers that exceed [`GENERATION_NUMBER_MAX`] are capped, while corrected commit dates
+    /// that are too far ahead of their committer timestamp are stored in an overflow list.
+    pub fn write_from_commits(
+        mut commits: Vec<Commit>,
+        base: Option<&Graph>,
+        out: &mut dyn io::Write,
+        options: Options,
+    ) -> Result<Outcome, Error> {
+        if let Some(base) = base {
+            commits.retain(|commit| base.lookup(commit.id).is_none());
+        }
+        commits.sort_by_key(|commit| commit.id);
+        commits.dedup_by(|a, b| a.id == b.id);
+
+        let base_files = base.map_or(&[][..], |base| base.files.as_slice());
+        let num_base_commits = base.map_or(0, Graph::num_commits);
+        let num_commits = u64::from(num_base_commits) + commits.len() as u64;
+        if num_commits > u64::from(MAX_COMMITS) {
+            return Err(Error::TooManyCommits(num_commits));
+        }
+        let num_base_graphs: u8 = base_files
+            .len()
+            .try_into()
+            .map_err(|_| Error::TooManyBaseGraphs(base_files.len()))?;
+        let write_generation_data = options.generation_data && base_files.iter().all(File::has_generation_data);
+
+        let generations = compute_generations(&commits, base)?;
+        let position_of = |commit: &Commit, parent: &ObjectId| -> Result<u32, Error> {
+            match commits.binary_search_by(|c| c.id.cmp(parent)) {
+                Ok(idx) => Ok(num_base_commits + idx as u32),
+                Err(_) => base
+                    .and_then(|base| base.lookup(parent))
+                    .map(|pos| pos.0)
+                    .ok_or(Error::MissingParent {
+                        id: commit.id,
+                        parent: *parent,
+                    }),
+            }
+        };
+
+        let mut parent_edges = Vec::with_capacity(commits.len());
+        let mut extra_edges = Vec::new();
+        for commit in &commits {
+            let edges = match commit.parents.as_slice() {
+                [] => (NO_PARENT, NO_PARENT),
+                [parent] => (position_of(commit, parent)?, NO_PARENT),
+                [parent1, parent2] => (position_of(commit, parent1)?, position_of(commit, parent2)?),
+                [parent1, rest @ ..] => {
+                    let first = position_of(commit, parent1)?;
+                    let extra_edges_index = extra_edges.len() as u32;
+                    for parent in rest {
+                        extra_edges.push(position_of(commit, parent)?);
+                    }
+                    *extra_edges.last_mut().expect("at least two more parents") |= LAST_EXTENDED_EDGE_MASK;
+                    (first, EXTENDED_EDGES_MASK | extra_edges_index)
+                }
+            };
+            parent_edges.push(edges);
+        }
+
+        let mut generation_data = Vec::new();
+        let mut generation_data_overflows = Vec::new();
+        if write_generation_data {
+            generation_data.reserve(commits.len());
+            for (commit, generation) in commits.iter().zip(&generations) {
+                let offset = generation.corrected_commit_date - (commit.committer_timestamp & COMMIT_TIMESTAMP_MASK);
+                generation_data.push(if offset > GENERATION_DATA_OFFSET_MAX {
+                    generation_data_overflows.push(offset);
+                    GENERATION_DATA_OVERFLOW_MASK | (generation_data_overflows.len() as u32 - 1)
+                } else {
+                    offset as u32
+                });
+            }
+        }
+
+        let hash_len = options.object_hash.len_in_bytes();
+        let mut cf = gix_chunk::file::Index::for_writing();
+        cf.plan_chunk(OID_FAN_CHUNK_ID, (FAN_LEN * 4) as u64);
+        cf.plan_chunk(OID_LOOKUP_CHUNK_ID, (commits.len() * hash_len) as u64);
+        cf.plan_chunk(COMMIT_DATA_CHUNK_ID, (commits.len() * (hash_len + 16)) as u64);
+        if write_generation_data {
+            cf.plan_chunk(GENERATION_DATA_CHUNK_ID, (generation_data.len() * 4) as u64);
+            if !generation_data_overflows.is_empty() {
+                cf.plan_chunk(
+                    GENERATION_DATA_OVERFLOW_CHUNK_ID,
+                    (generation_data_overflows.len() * 8) as u64,
+                );
+            }
+        }
+        if !extra_edges.is_empty() {
+            cf.plan_chunk(EXTENDED_EDGES_LIST_CHUNK_ID, (extra_edges.len() * 4) as u64);
+        }
+        if num_base_graphs > 0 {
+            cf.plan_chunk(BASE_GRAPHS_LIST_CHUNK_ID, (base_files.len() * hash_len) as u64);
+        }
+
+        let mut out = gix_features::hash::Write::new(out, options.object_hash);
+        out.write_all(SIGNATURE)?;
+        out.write_all(&[1 /* version */, options.object_hash as u8])?;
+        out.write_all(&[
+            cf.num_chunks().try_into().expect("BUG: wrote more than 256 chunks"),
+            num_base_graphs,
+        ])?;
+
+        let mut chunk_write = cf.into_write(&mut out, HEADER_LEN)?;
+        while let Some(chunk_to_write) = chunk_write.next_chunk() {
+            match chunk_to_write {
+                OID_FAN_CHUNK_ID => {
+                    let mut fan = [0u32; FAN_LEN];
+                    for commit in &commits {
+                        fan[usize::from(commit.id.first_byte())] += 1;
+                    }
+                    let mut total = 0;
+                    for count in fan {
+                        total += count;
+                        chunk_write.write_all(&total.to_be_bytes())?;
+                    }
+                }
+                OID_LOOKUP_CHUNK_ID => {
+                    for commit in &commits {
+                        chunk_write.write_all(commit.id.as_slice())?;
+                    }
+                }
+                COMMIT_DATA_CHUNK_ID => {
+                    for ((commit, (parent1, parent2)), generation) in
+                        commits.iter().zip(&parent_edges).zip(&generations)
+                    {
+                        chunk_write.write_all(commit.tree.as_slice())?;
+                        chunk_write.write_all(&parent1.to_be_bytes())?;
+                        chunk_write.write_all(&parent2.to_be_bytes())?;
+                        let timestamp = commit.committer_timestamp & COMMIT_TIMESTAMP_MASK;
+                        let generation_and_timestamp_high_bits =
+                            (generation.topological << 2) | (timestamp >> 32) as u32;
+                        chunk_write.write_all(&generation_and_timestamp_high_bits.to_be_bytes())?;
+                        chunk_write.write_all(&(timestamp as u32).to_be_bytes())?;
+                    }
+                }
+                GENERATION_DATA_CHUNK_ID => {
+                    for offset in &generation_data {
+                        chunk_write.write_all(&offset.to_be_bytes())?;
+                    }
+                }
+                GENERATION_DATA_OVERFLOW_CHUNK_ID => {
+                    for offset in &generation_data_overflows {
+                        chunk_write.write_all(&offset.to_be_bytes())?;
+                    }
+                }
+                EXTENDED_EDGES_LIST_CHUNK_ID => {
+                    for edge in &extra_edges {
+                        chunk_write.write_all(&edge.to_be_bytes())?;
+                    }
+                }
+                BASE_GRAPHS_LIST_CHUNK_ID => {
+                    for file in base_files {
+                        chunk_write.write_all(file.checksum().as_bytes())?;
+                    }
+                }
+                unknown => unreachable!("BUG: forgot to implement chunk {:?}", std::str::from_utf8(&unknown)),
+            }
+        }
+
+        let checksum = out.hash.digest();
+        out.inner.write_all(checksum.as_slice())?;
+        Ok(Outcome {
+            checksum,
+            num_commits: commits.len() as u32,
+            generation_data: write_generation_data,
+            num_generation_data_overflows: generation_data_overflows.len() as u32,
+        })
+    }
+}
+
+/// Compute the generation numbers of all `commits`, which are sorted by id, in the same order.
+///
+/// Parents that aren't part of `commits` are looked up in `base`, or ignored if they can't be found there, which
+/// is reported later when their position is needed.
+fn compute_generations(commits: &[Commit], base: Option<&Graph>) -> Result<Vec<Generation>, Error> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum State {
+        Unvisited,
+        InProgress,
+        Done,
+    }
+    let mut states = vec![State::Unvisited; commits.len()];
+    let mut generations = vec![Generation::default(); commits.len()];
+    let mut stack = Vec::new();
+    for start in 0..commits.len() {
+        if states[start] != State::Unvisited {
+            continue;
+        }
+        stack.push(start);
+        while let Some(&idx) = stack.last() {
+            if states[idx] == State::Done {
+                stack.pop();
+                continue;
+            }
+            states[idx] = State::InProgress;
+            let commit = &commits[idx];
+            let mut parents_done = true;
+            let mut max_parent = Generation::default();
+            for parent in &commit.parents {
+                let parent_generation = match commits.binary_search_by(|c| c.id.cmp(parent)) {
+                    Ok(parent_idx) => match states[parent_idx] {
+                        State::Done => generations[parent_idx],
+                        State::Unvisited => {
+                            parents_done = false;
+                            stack.push(parent_idx);
+                            continue;
+                        }
+                        State::InProgress => return Err(Error::Cycle { id: commit.id }),
+                    },
+                    Err(_) => match base.and_then(|base| base.commit_by_id(parent)) {
+                        Some(parent) => Generation {
+                            topological: parent.generation(),
+                            corrected_commit_date: parent.corrected_commit_date().unwrap_or_default(),
+                        },
+                        None => continue,
+                    },
+                };
+                max_parent.topological = max(max_parent.topological, parent_generation.topological);
+                max_parent.corrected_commit_date = max(
+                    max_parent.corrected_commit_date,
+                    parent_generation.corrected_commit_date,
+                );
+            }
+            if !parents_done {
+                continue;
+            }
+            generations[idx] = Generation {
+                topological: min(max_parent.topological + 1, GENERATION_NUMBER_MAX),
+                corrected_commit_date: max(
+                    commit.committer_timestamp & COMMIT_TIMESTAMP_MASK,
+                    max_parent.corrected_commit_date + 1,
+                ),
+            };
+            states[idx] = State::Done;
+            stack.pop();
+        }
+    }
+    Ok(generations)
+}
@@ -1,4 +1,4 @@
-//! Read, verify, and traverse git commit graphs.
+//! Read, verify, traverse and write git commit graphs.
 //!
 //! A [commit graph][Graph] is an index of commits in the git commit history.
 //! The [Graph] stores commit data in a way that accelerates lookups considerably compared to
@@ -28,6 +28,8 @@ pub struct File {
     data: memmap2::Mmap,
     extra_edges_list_range: Option<std::ops::Range<usize>>,
     fan: [u32; file::FAN_LEN],
+    generation_data_offset: Option<usize>,
+    generation_data_overflow_range: Option<std::ops::Range<usize>>,
     oid_lookup_offset: usize,
     path: std::path::PathBuf,
     hash_len: usize,
@@ -54,6 +56,7 @@ pub mod file;
 #[allow(clippy::empty_docs)]
 pub mod init;
 pub mod verify;
+pub mod write;
 
 /// The number of generations that are considered 'infinite' commit history.
 pub const GENERATION_NUMBER_INFINITY: u32 = 0xffff_ffff;
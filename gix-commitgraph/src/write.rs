@@ -0,0 +1,161 @@
+//! Write commit-graphs, either as a single file or as new layer of a split commit-graph chain.
+use std::{
+    collections::HashSet,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use gix_hash::ObjectId;
+use gix_tempfile::{handle::Writable, AutoRemove, ContainingDirectory};
+
+use crate::{file, init, File, Graph};
+
+/// The error returned by [`commits_reachable_from()`] and [`Graph::write_split_layer()`].
+#[derive(thiserror::Error, Debug)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not lookup object {id}")]
+    Find {
+        id: ObjectId,
+        source: gix_object::find::Error,
+    },
+    #[error("Commit {id} could not be found")]
+    MissingCommit { id: ObjectId },
+    #[error("{kind} object {id} could not be decoded")]
+    Decode {
+        id: ObjectId,
+        kind: gix_object::Kind,
+        source: gix_object::decode::Error,
+    },
+    #[error("Could not open the existing commit-graph chain")]
+    Init(#[from] init::Error),
+    #[error(transparent)]
+    WriteFile(#[from] file::write::Error),
+    #[error("Could not write commit-graph files into '{}'", .path.display())]
+    Io { path: PathBuf, source: std::io::Error },
+    #[error("Could not move a temporary file into its desired place")]
+    Persist(#[from] gix_tempfile::handle::persist::Error<Writable>),
+}
+
+/// The outcome of [`Graph::write_split_layer()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The outcome of writing the new layer.
+    pub file: file::write::Outcome,
+    /// The path to the new layer, or `None` if no layer was written as all commits were already part of the chain.
+    pub path: Option<PathBuf>,
+}
+
+/// Return information about all commits that are reachable from `tips` along with the commits themselves, reading
+/// them from `objects`, for use in [`File::write_from_commits()`].
+///
+/// Annotated tags among `tips` are peeled, and tips that don't point to a commit are ignored.
+/// Commits that are contained in `known` are neither returned nor traversed, which allows to only find the commits
+/// that are missing in an existing commit-graph.
+pub fn commits_reachable_from(
+    tips: impl IntoIterator<Item = ObjectId>,
+    objects: &dyn gix_object::Find,
+    known: Option<&Graph>,
+) -> Result<Vec<file::write::Commit>, Error> {
+    let mut buf = Vec::new();
+    let mut seen = HashSet::new();
+    let mut out = Vec::new();
+    let mut queue: Vec<_> = tips.into_iter().map(|id| (id, true)).collect();
+    while let Some((id, is_tip)) = queue.pop() {
+        if !seen.insert(id) || known.and_then(|known| known.lookup(id)).is_some() {
+            continue;
+        }
+        let object = objects
+            .try_find(&id, &mut buf)
+            .map_err(|source| Error::Find { id, source })?
+            .ok_or(Error::MissingCommit { id })?;
+        let kind = object.kind;
+        let decode_err = |source| Error::Decode { id, kind, source };
+        match kind {
+            gix_object::Kind::Commit => {
+                let commit = gix_object::CommitRef::from_bytes(object.data).map_err(decode_err)?;
+                let parents: Vec<_> = commit.parents().collect();
+                queue.extend(parents.iter().map(|parent| (*parent, false)));
+                out.push(file::write::Commit {
+                    id,
+                    tree: commit.tree(),
+                    parents,
+                    committer_timestamp: commit.committer.time.seconds.max(0) as u64,
+                });
+            }
+            gix_object::Kind::Tag if is_tip => {
+                let target = gix_object::TagRefIter::from_bytes(object.data)
+                    .target_id()
+                    .map_err(decode_err)?;
+                queue.push((target, true));
+            }
+            _ if is_tip => {}
+            _ => return Err(Error::MissingCommit { id }),
+        }
+    }
+    Ok(out)
+}
+
+/// Writing
+impl Graph {
+    /// Write all `commits` that aren't yet part of the split commit-graph chain in `commit_graphs_dir`,
+    /// typically `.git/objects/info/commit-graphs`, as new layer on top of the chain, or create the chain if there is none.
+    ///
+    /// The new layer is made available by updating the `commit-graph-chain` file only once it was written completely,
+    /// so that existing layers are never rewritten.
+    /// Note that parents of `commits` must either be part of `commits` or of the existing chain.
+    pub fn write_split_layer(
+        commit_graphs_dir: &Path,
+        commits: Vec<file::write::Commit>,
+        options: file::write::Options,
+    ) -> Result<Outcome, Error> {
+        let io_err = |source| Error::Io {
+            path: commit_graphs_dir.to_owned(),
+            source,
+        };
+        std::fs::create_dir_all(commit_graphs_dir).map_err(io_err)?;
+        let chain_path = commit_graphs_dir.join("commit-graph-chain");
+        let base = if chain_path.is_file() {
+            Some(Graph::from_commit_graphs_dir(commit_graphs_dir)?)
+        } else {
+            None
+        };
+
+        let new_tempfile = || {
+            gix_tempfile::new(commit_graphs_dir, ContainingDirectory::Exists, AutoRemove::Tempfile)
+                .map(std::io::BufWriter::new)
+                .map_err(io_err)
+        };
+        let mut layer = new_tempfile()?;
+        let outcome = File::write_from_commits(commits, base.as_ref(), &mut layer, options)?;
+        if outcome.num_commits == 0 {
+            return Ok(Outcome {
+                file: outcome,
+                path: None,
+            });
+        }
+        let layer_path = commit_graphs_dir.join(format!("graph-{}.graph", outcome.checksum));
+        layer
+            .into_inner()
+            .map_err(|err| io_err(err.into_error()))?
+            .persist(&layer_path)?;
+
+        let mut chain = new_tempfile()?;
+        for checksum in base
+            .iter()
+            .flat_map(|base| base.files.iter().map(File::checksum))
+            .chain(Some(outcome.checksum.as_ref()))
+        {
+            writeln!(chain, "{checksum}").map_err(io_err)?;
+        }
+        chain
+            .into_inner()
+            .map_err(|err| io_err(err.into_error()))?
+            .persist(&chain_path)?;
+
+        Ok(Outcome {
+            file: outcome,
+            path: Some(layer_path),
+        })
+    }
+}
@@ -0,0 +1,211 @@
+use std::{path::Path, process::Command};
+
+use gix_commitgraph::{file, write, File, Graph};
+use gix_testtools::scripted_fixture_writable;
+
+fn tips(repo_dir: &Path) -> Vec<gix_hash::ObjectId> {
+    let output = Command::new("git")
+        .args(["for-each-ref", "--format=%(objectname)"])
+        .current_dir(repo_dir)
+        .output()
+        .expect("git can be executed");
+    assert!(output.status.success());
+    output
+        .stdout
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|hex| gix_hash::ObjectId::from_hex(hex).expect("valid hex"))
+        .collect()
+}
+
+fn rev_parse(repo_dir: &Path, spec: &str) -> gix_hash::ObjectId {
+    let output = Command::new("git")
+        .args(["rev-parse", spec])
+        .current_dir(repo_dir)
+        .output()
+        .expect("git can be executed");
+    assert!(output.status.success());
+    gix_hash::ObjectId::from_hex(
+        std::str::from_utf8(&output.stdout)
+            .expect("hex is valid UTF-8")
+            .trim()
+            .as_bytes(),
+    )
+    .expect("valid hex")
+}
+
+fn assert_git_verifies(repo_dir: &Path) {
+    let output = Command::new("git")
+        .args(["commit-graph", "verify"])
+        .current_dir(repo_dir)
+        .output()
+        .expect("git can be executed");
+    assert!(
+        output.status.success() && output.stderr.is_empty(),
+        "git commit-graph verify failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+fn info_dir(repo_dir: &Path) -> std::path::PathBuf {
+    repo_dir.join(".git").join("objects").join("info")
+}
+
+fn reachable_commits(
+    repo_dir: &Path,
+    tips: Vec<gix_hash::ObjectId>,
+    known: Option<&Graph>,
+) -> Vec<file::write::Commit> {
+    let odb = gix_odb::at(repo_dir.join(".git").join("objects")).expect("valid odb");
+    write::commits_reachable_from(tips, &odb, known).expect("all commits are present")
+}
+
+fn assert_same_commits(actual: &Graph, expected: &Graph) {
+    assert_eq!(actual.num_commits(), expected.num_commits());
+    for expected_commit in expected.iter_commits() {
+        let actual_commit = actual
+            .commit_by_id(expected_commit.id())
+            .expect("all commits are present");
+        assert_eq!(actual_commit.root_tree_id(), expected_commit.root_tree_id());
+        assert_eq!(
+            actual_commit.committer_timestamp(),
+            expected_commit.committer_timestamp()
+        );
+        assert_eq!(actual_commit.generation(), expected_commit.generation());
+        let parent_ids = |graph: &Graph, commit: file::Commit<'_>| -> Vec<gix_hash::ObjectId> {
+            commit
+                .iter_parents()
+                .map(|pos| graph.id_at(pos.expect("valid parent")).to_owned())
+                .collect()
+        };
+        assert_eq!(parent_ids(actual, actual_commit), parent_ids(expected, expected_commit));
+    }
+}
+
+#[test]
+fn single_files_contain_the_same_commits_as_written_by_git() -> gix_testtools::Result {
+    for script in [
+        "single_commit.sh",
+        "single_parent.sh",
+        "two_parents.sh",
+        "octopus_merges.sh",
+    ] {
+        let repo = scripted_fixture_writable(script)?;
+        let graph_path = info_dir(repo.path()).join("commit-graph");
+        let expected = Graph::from_file(&graph_path)?;
+
+        let commits = reachable_commits(repo.path(), tips(repo.path()), None);
+        let mut buf = Vec::new();
+        let outcome = File::write_from_commits(commits, None, &mut buf, Default::default())?;
+        assert_eq!(outcome.num_commits, expected.num_commits(), "{script}");
+        assert!(outcome.generation_data);
+        assert_eq!(outcome.num_generation_data_overflows, 0);
+        std::fs::write(&graph_path, &buf)?;
+
+        assert_git_verifies(repo.path());
+        let actual = Graph::from_file(&graph_path)?;
+        actual.verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+        assert_eq!(
+            actual
+                .iter_commits()
+                .next()
+                .map(|c| c.corrected_commit_date().is_some()),
+            Some(true)
+        );
+        assert_eq!(File::at(&graph_path)?.checksum(), outcome.checksum);
+        assert_same_commits(&actual, &expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn corrected_commit_dates_that_overflow_are_stored_separately() -> gix_testtools::Result {
+    let repo = scripted_fixture_writable("generation_number_overflow.sh")?;
+    let info_dir = info_dir(repo.path());
+    let expected = Graph::from_info_dir(&info_dir)?;
+    std::fs::remove_dir_all(info_dir.join("commit-graphs"))?;
+
+    let commits = reachable_commits(repo.path(), tips(repo.path()), None);
+    let mut buf = Vec::new();
+    let outcome = File::write_from_commits(commits, None, &mut buf, Default::default())?;
+    assert_eq!(
+        outcome.num_generation_data_overflows, 3,
+        "all commits with a commit in the far future as ancestor need the overflow list"
+    );
+    std::fs::write(info_dir.join("commit-graph"), &buf)?;
+
+    assert_git_verifies(repo.path());
+    let actual = Graph::from_info_dir(&info_dir)?;
+    assert_same_commits(&actual, &expected);
+    let future = actual
+        .commit_by_id(rev_parse(repo.path(), "future-1"))
+        .expect("present");
+    let old = actual.commit_by_id(rev_parse(repo.path(), "old-1")).expect("present");
+    assert_eq!(old.committer_timestamp(), 0);
+    assert_eq!(
+        old.corrected_commit_date(),
+        future.corrected_commit_date().map(|date| date + 1),
+        "the corrected commit date is always larger than the one of its parent"
+    );
+    Ok(())
+}
+
+#[test]
+fn split_layers_are_added_incrementally() -> gix_testtools::Result {
+    let repo = scripted_fixture_writable("generation_number_overflow.sh")?;
+    let commit_graphs_dir = info_dir(repo.path()).join("commit-graphs");
+    let expected = Graph::from_info_dir(&info_dir(repo.path()))?;
+    std::fs::remove_dir_all(&commit_graphs_dir)?;
+
+    for (tip, expected_num_commits, expected_num_layers) in [("old-1", 2, 1), ("old-2", 2, 2), ("extra", 1, 3)] {
+        let known = Graph::from_commit_graphs_dir(&commit_graphs_dir).ok();
+        let commits = reachable_commits(repo.path(), vec![rev_parse(repo.path(), tip)], known.as_ref());
+        drop(known);
+        let outcome = Graph::write_split_layer(&commit_graphs_dir, commits, Default::default())?;
+        assert_eq!(outcome.file.num_commits, expected_num_commits, "{tip}");
+        assert!(outcome.path.expect("layer was written").is_file());
+
+        assert_git_verifies(repo.path());
+        let graph = Graph::from_commit_graphs_dir(&commit_graphs_dir)?;
+        graph.verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+        let chain = std::fs::read_to_string(commit_graphs_dir.join("commit-graph-chain"))?;
+        assert_eq!(chain.lines().count(), expected_num_layers);
+    }
+    assert_same_commits(&Graph::from_commit_graphs_dir(&commit_graphs_dir)?, &expected);
+
+    let commits = reachable_commits(repo.path(), tips(repo.path()), None);
+    let outcome = Graph::write_split_layer(&commit_graphs_dir, commits, Default::default())?;
+    assert_eq!(outcome.file.num_commits, 0);
+    assert_eq!(outcome.path, None, "all commits are known, there is nothing to write");
+    Ok(())
+}
+
+#[test]
+fn generation_data_is_only_written_if_all_layers_have_it() -> gix_testtools::Result {
+    let repo = scripted_fixture_writable("split_chain.sh")?;
+    let commit_graphs_dir = info_dir(repo.path()).join("commit-graphs");
+    std::fs::remove_dir_all(&commit_graphs_dir)?;
+
+    let commits = reachable_commits(repo.path(), vec![rev_parse(repo.path(), "commit1")], None);
+    let outcome = Graph::write_split_layer(
+        &commit_graphs_dir,
+        commits,
+        file::write::Options {
+            generation_data: false,
+            ..Default::default()
+        },
+    )?;
+    assert!(!outcome.file.generation_data);
+
+    let known = Graph::from_commit_graphs_dir(&commit_graphs_dir)?;
+    let commits = reachable_commits(repo.path(), vec![rev_parse(repo.path(), "commit3")], Some(&known));
+    drop(known);
+    let outcome = Graph::write_split_layer(&commit_graphs_dir, commits, Default::default())?;
+    assert_eq!(outcome.file.num_commits, 2);
+    assert!(
+        !outcome.file.generation_data,
+        "the base layer has no generation data, so it's not used for any layer"
+    );
+    assert_git_verifies(repo.path());
+    Ok(())
+}
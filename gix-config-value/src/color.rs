@@ -1,7 +1,7 @@
 #![allow(missing_docs)]
 use std::{borrow::Cow, fmt::Display, str::FromStr};
 
-use bstr::{BStr, BString};
+use bstr::{BStr, BString, ByteSlice};
 
 use crate::{Color, Error};
 
@@ -38,6 +38,16 @@ fn color_err(input: impl Into<BString>) -> Error {
     )
 }
 
+impl Color {
+    /// Parse `input` as a `git` color value, like `red bold ul` or `#ff0000 blue`.
+    ///
+    /// The first color is the foreground, the second one the background, and attributes may appear in any position.
+    /// Use the [`Display`] implementation to obtain the normalized form of the color.
+    pub fn from_bytes(input: &[u8]) -> Result<Self, Error> {
+        Self::try_from(input.as_bstr())
+    }
+}
+
 impl TryFrom<&BStr> for Color {
     type Error = Error;
 
@@ -174,6 +184,9 @@ impl FromStr for Name {
             false
         };
 
+        // Like `git`, color names are case-insensitive, but the `bright` prefix isn't.
+        let lowercase = s.to_ascii_lowercase();
+        let s = lowercase.as_str();
         match s {
             "normal" if !bright => return Ok(Self::Normal),
             "-1" if !bright => return Ok(Self::Normal),
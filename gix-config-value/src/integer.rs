@@ -54,12 +54,39 @@ fn int_err(input: impl Into<BString>) -> Error {
     )
 }
 
+/// Parse `s` like `strtoimax()` with automatic base detection would, i.e. with an optional sign followed by
+/// a decimal number, a hexadecimal number with `0x` prefix, or an octal number with a leading `0`.
+/// Unlike `strtoimax()`, leading whitespace isn't allowed, and all of `s` has to be consumed.
+fn parse_number(s: &str) -> Option<i64> {
+    let (is_negative, unsigned) = match s.as_bytes().first()? {
+        b'-' => (true, &s[1..]),
+        b'+' => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (radix, digits) = if let Some(hex) = unsigned.strip_prefix("0x").or_else(|| unsigned.strip_prefix("0X")) {
+        (16, hex)
+    } else if unsigned.len() > 1 && unsigned.starts_with('0') {
+        (8, &unsigned[1..])
+    } else {
+        (10, unsigned)
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    let magnitude = u64::from_str_radix(digits, radix).ok()?;
+    if is_negative {
+        0i64.checked_sub_unsigned(magnitude)
+    } else {
+        i64::try_from(magnitude).ok()
+    }
+}
+
 impl TryFrom<&BStr> for Integer {
     type Error = Error;
 
     fn try_from(s: &BStr) -> Result<Self, Self::Error> {
         let s = std::str::from_utf8(s).map_err(|err| int_err(s).with_err(err))?;
-        if let Ok(value) = s.parse() {
+        if let Some(value) = parse_number(s) {
             return Ok(Self { value, suffix: None });
         }
 
@@ -73,7 +100,7 @@ impl TryFrom<&BStr> for Integer {
         }
 
         let (number, suffix) = s.split_at(s.len() - 1);
-        if let (Ok(value), Ok(suffix)) = (number.parse(), suffix.parse()) {
+        if let (Some(value), Ok(suffix)) = (parse_number(number), suffix.parse()) {
             Ok(Self {
                 value,
                 suffix: Some(suffix),
@@ -44,6 +44,8 @@ pub mod interpolate {
         },
         #[error("Ill-formed UTF-8 in username")]
         UsernameConversion(#[from] std::str::Utf8Error),
+        #[error("The home directory of user {name:?} could not be found")]
+        UserNotFound { name: String },
         #[error("User interpolation is not available on this platform")]
         UserInterpolationUnsupported,
     }
@@ -113,6 +115,7 @@ impl<'a> Path<'a> {
     ///  - `~user/` to the specified user’s home directory, e.g `~alice` might get expanded to `/home/alice` on linux, but requires
     ///    the `home_for_user` function to be provided.
     ///    The interpolation uses `getpwnam` sys call and is therefore not available on windows.
+    ///    If the user can't be found, [`UserNotFound`][interpolate::Error::UserNotFound] is returned.
     ///  - `%(prefix)/` is expanded to the location where `gitoxide` is installed.
     ///     This location is not known at compile time and therefore need to be
     ///     optionally provided by the caller through `git_install_dir`.
@@ -183,7 +186,9 @@ impl<'a> Path<'a> {
             .ok_or(interpolate::Error::Missing { what: "/" })?;
         let (username, path_with_leading_slash) = val.split_at(i);
         let username = std::str::from_utf8(username)?;
-        let home = home_for_user(username).ok_or(interpolate::Error::Missing { what: "pwd user info" })?;
+        let home = home_for_user(username).ok_or_else(|| interpolate::Error::UserNotFound {
+            name: username.to_owned(),
+        })?;
         let path_past_user_prefix =
             gix_path::try_from_byte_slice(&path_with_leading_slash["/".len()..]).map_err(|err| {
                 interpolate::Error::Utf8Conversion {
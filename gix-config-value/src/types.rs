@@ -20,7 +20,8 @@ pub struct Color {
 /// Any value that can be interpreted as an integer.
 ///
 /// This supports any numeric value that can fit in a [`i64`], excluding the
-/// suffix. The suffix is parsed separately from the value itself, so if you
+/// suffix. Like in `git`, values may be hexadecimal with a `0x` prefix, or octal
+/// with a leading `0`. The suffix is parsed separately from the value itself, so if you
 /// wish to obtain the true value of the integer, you must account for the
 /// suffix after fetching the value. [`integer::Suffix`] provides
 /// [`bitwise_offset()`][integer::Suffix::bitwise_offset] to help with the
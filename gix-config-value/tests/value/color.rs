@@ -43,6 +43,15 @@ mod name {
         assert_eq!(Name::from_str("#000000"), Ok(Name::Rgb(0, 0, 0)));
     }
 
+    #[test]
+    fn names_are_case_insensitive_but_the_bright_prefix_is_not() {
+        assert_eq!(Name::from_str("Red"), Ok(Name::Red));
+        assert_eq!(Name::from_str("NORMAL"), Ok(Name::Normal));
+        assert_eq!(Name::from_str("brightBlue"), Ok(Name::BrightBlue));
+        assert_eq!(Name::from_str("#FF0010"), Ok(Name::Rgb(255, 0, 16)));
+        assert!(Name::from_str("Brightblue").is_err());
+    }
+
     #[test]
     fn invalid() {
         assert!(Name::from_str("-2").is_err());
@@ -111,7 +120,10 @@ mod attribute {
 
 mod from_git {
     use bstr::BStr;
-    use gix_config_value::Color;
+    use gix_config_value::{
+        color::{Attribute, Name},
+        Color,
+    };
 
     #[test]
     fn reset() {
@@ -188,6 +200,21 @@ mod from_git {
         );
     }
 
+    #[test]
+    fn from_bytes_exposes_all_parts() -> crate::Result {
+        let actual = Color::from_bytes(b"bold #FF0000 no-ul Blue")?;
+        assert_eq!(actual.foreground, Some(Name::Rgb(255, 0, 0)));
+        assert_eq!(actual.background, Some(Name::Blue));
+        assert_eq!(actual.attributes, Attribute::BOLD | Attribute::NO_UL);
+        assert_eq!(actual.to_string(), "#ff0000 blue bold noul");
+        assert_eq!(
+            Color::from_bytes(actual.to_string().as_bytes())?,
+            actual,
+            "the normalized form round-trips"
+        );
+        Ok(())
+    }
+
     fn color<'a>(name: impl Into<&'a BStr>) -> String {
         try_color(name).expect("input color is expected to be valid")
     }
@@ -42,6 +42,41 @@ fn from_str_with_suffix() {
     );
 }
 
+#[test]
+fn from_str_with_base() {
+    fn decimal(input: &str) -> Option<i64> {
+        Integer::try_from(b(input)).ok()?.to_decimal()
+    }
+    assert_eq!(decimal("0x10"), Some(16), "hexadecimal");
+    assert_eq!(decimal("0XfF"), Some(255), "hexadecimal is case-insensitive");
+    assert_eq!(decimal("-0x10"), Some(-16), "signs come before the base prefix");
+    assert_eq!(decimal("+010"), Some(8), "a leading zero means octal");
+    assert_eq!(decimal("0"), Some(0), "zero is just zero");
+    assert_eq!(decimal("-0"), Some(0));
+    assert_eq!(decimal("0x10k"), Some(16 * 1024), "suffixes work with any base");
+    assert_eq!(decimal("012M"), Some(10 * 1024 * 1024));
+}
+
+#[test]
+fn boundaries() {
+    fn decimal(input: &str) -> Option<i64> {
+        Integer::try_from(b(input)).ok()?.to_decimal()
+    }
+    assert_eq!(decimal("9223372036854775807"), Some(i64::MAX));
+    assert_eq!(decimal("0x7fffffffffffffff"), Some(i64::MAX));
+    assert_eq!(decimal("0777777777777777777777"), Some(i64::MAX));
+    assert_eq!(decimal("-9223372036854775808"), Some(i64::MIN));
+    assert_eq!(decimal("-0x8000000000000000"), Some(i64::MIN));
+    assert!(Integer::try_from(b("9223372036854775808")).is_err(), "overflow");
+    assert!(Integer::try_from(b("0x8000000000000000")).is_err(), "overflow");
+    assert!(Integer::try_from(b("-9223372036854775809")).is_err(), "underflow");
+    assert!(Integer::try_from(b("-0x8000000000000001")).is_err(), "underflow");
+    assert_eq!(decimal("8589934591g"), Some(8589934591 * 1024 * 1024 * 1024));
+    assert_eq!(decimal("8589934592g"), None, "overflow through the suffix");
+    assert_eq!(decimal("-8589934592g"), Some(i64::MIN));
+    assert_eq!(decimal("-8589934593g"), None, "underflow through the suffix");
+}
+
 #[test]
 fn invalid_from_str() {
     assert!(Integer::try_from(b("")).is_err());
@@ -51,6 +86,16 @@ fn invalid_from_str() {
     assert!(Integer::try_from(b("g")).is_err());
     assert!(Integer::try_from(b("123123123123123123123123")).is_err());
     assert!(Integer::try_from(b("gg")).is_err());
+    assert!(Integer::try_from(b("1kk")).is_err(), "only one suffix is allowed");
+    assert!(Integer::try_from(b("1 k")).is_err(), "no spaces are allowed");
+    assert!(Integer::try_from(b(" 1")).is_err());
+    assert!(Integer::try_from(b("1 ")).is_err());
+    assert!(Integer::try_from(b("0x")).is_err(), "hex numbers need digits");
+    assert!(Integer::try_from(b("08")).is_err(), "8 isn't an octal digit");
+    assert!(Integer::try_from(b("0xg")).is_err());
+    assert!(Integer::try_from(b("--1")).is_err());
+    assert!(Integer::try_from(b("0x-1")).is_err());
+    assert!(Integer::try_from(b("+")).is_err());
     assert!(Integer::try_from(b("™️🤦‍♂️")).is_err());
 }
 
@@ -112,6 +112,18 @@ mod interpolate {
         Ok(())
     }
 
+    #[cfg(not(windows))]
+    #[test]
+    fn tilde_with_unknown_user_is_an_error() {
+        let err = gix_config_value::Path::from(cow_str("~unknown/foo"))
+            .interpolate(path::interpolate::Context {
+                home_for_user: Some(|_name| None),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(matches!(err, path::interpolate::Error::UserNotFound { name } if name == "unknown"));
+    }
+
     fn interpolate_without_context(
         path: impl AsRef<str>,
     ) -> Result<Cow<'static, Path>, gix_config_value::path::interpolate::Error> {
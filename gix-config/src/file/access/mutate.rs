@@ -305,13 +305,59 @@ impl<'event> File<'event> {
             .ok()?
             .rev()
             .find(|id| filter(self.sections.get(id).expect("each id has a section").meta()))?;
-        self.section_order.remove(
-            self.section_order
+        self.remove_section_by_id(id)
+    }
+
+    /// Remove the last value named `value_name` in the last section with `name` and `subsection_name` that has it,
+    /// and return the value, or `None` if there was no such value.
+    ///
+    /// The whitespace and comments surrounding the value are kept, as is the rest of the file.
+    /// If `remove_empty_section` is `true` and the section doesn't contain any other values or comments
+    /// after the removal, the whole section will be removed as well.
+    ///
+    /// ```
+    /// # use gix_config::File;
+    /// # use std::convert::TryFrom;
+    /// let mut git_config = gix_config::File::try_from("[core]\n\ta = 1\n\tb = 2\n[other]\n\tc = 3\n")?;
+    ///
+    /// assert_eq!(git_config.remove_value("core", None, "a", true).as_deref(), Some("1".into()));
+    /// assert_eq!(git_config.remove_value("other", None, "c", true).as_deref(), Some("3".into()));
+    /// assert_eq!(git_config.to_string(), "[core]\n\tb = 2\n");
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn remove_value<'a>(
+        &mut self,
+        name: impl AsRef<str>,
+        subsection_name: impl Into<Option<&'a BStr>>,
+        value_name: &str,
+        remove_empty_section: bool,
+    ) -> Option<Cow<'event, BStr>> {
+        let id = self
+            .section_ids_by_name_and_subname(name.as_ref(), subsection_name.into())
+            .ok()?
+            .rev()
+            .find(|id| {
+                self.sections
+                    .get(id)
+                    .expect("each id has a section")
+                    .contains_value_name(value_name)
+            })?;
+        let value = self
+            .section_mut_by_id(id)
+            .expect("known section id")
+            .remove(value_name)?;
+        if remove_empty_section
+            && self
+                .sections
+                .get(&id)
+                .expect("known section id")
+                .as_ref()
                 .iter()
-                .position(|v| *v == id)
-                .expect("known section id"),
-        );
-        self.sections.remove(&id)
+                .all(|event| matches!(event, Event::Whitespace(_) | Event::Newline(_)))
+        {
+            self.remove_section_by_id(id);
+        }
+        Some(value)
     }
 
     /// Adds the provided `section` to the config, returning a mutable reference to it for immediate editing.
@@ -923,4 +923,91 @@ impl<'event> File<'event> {
         self.raw_values_mut_by(section_name, subsection_name, value_name.as_ref())
             .map(|mut v| v.set_values(new_values))
     }
+
+    /// Set `new_value` for the multivar in `section_name`, `subsection_name` and `value_name`, but only replace the values
+    /// for which `value_filter` returns `true`, or all values if there is no filter, similar to
+    /// `git config [--replace-all] <name> <value> [<value-pattern>]`.
+    ///
+    /// If `replace_all` is `false`, a single matching value is replaced, and it's an error if more than one value matches,
+    /// in which case nothing is changed.
+    /// Otherwise, all matching values are removed and `new_value` takes the place of the last of them.
+    /// If no value matches, `new_value` is added to the last section matching `section_name` and `subsection_name`,
+    /// which is created if needed.
+    ///
+    /// Return the number of values that were replaced, which is `0` if `new_value` was added instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use gix_config::File;
+    /// # use std::borrow::Cow;
+    /// # use std::convert::TryFrom;
+    /// # use bstr::{BStr, ByteSlice};
+    /// let mut git_config = gix_config::File::try_from("[remote \"origin\"]\n\tfetch = +refs/heads/*\n\tfetch = +refs/tags/*\n")?;
+    /// let replaced = git_config.set_multi_value(
+    ///     "remote",
+    ///     Some("origin".into()),
+    ///     "fetch",
+    ///     "+refs/tags/v*",
+    ///     Some(&mut |value: &BStr| value.contains_str("tags")),
+    ///     false,
+    /// )?;
+    /// assert_eq!(replaced, 1);
+    /// assert_eq!(
+    ///     git_config.raw_values("remote.origin.fetch")?,
+    ///     vec![
+    ///         Cow::<BStr>::Borrowed("+refs/heads/*".into()),
+    ///         Cow::<BStr>::Borrowed("+refs/tags/v*".into())
+    ///     ]
+    /// );
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn set_multi_value<'b>(
+        &mut self,
+        section_name: impl AsRef<str>,
+        subsection_name: Option<&BStr>,
+        value_name: &str,
+        new_value: impl Into<&'b BStr>,
+        mut value_filter: Option<&mut dyn FnMut(&BStr) -> bool>,
+        replace_all: bool,
+    ) -> Result<usize, crate::file::set_multi_value::Error> {
+        let section_name = section_name.as_ref();
+        let new_value = new_value.into();
+        let key = section::ValueName::try_from(value_name.to_owned())?;
+        if let Ok(mut values) = self.raw_values_mut_by(section_name, subsection_name, value_name) {
+            let matches: Vec<_> = values
+                .get()
+                .unwrap_or_default()
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, value)| {
+                    match &mut value_filter {
+                        Some(filter) => filter(value.as_ref()),
+                        None => true,
+                    }
+                    .then_some(idx)
+                })
+                .collect();
+            match matches.as_slice() {
+                [] => {}
+                [single] => {
+                    values.set_at(*single, new_value);
+                    return Ok(1);
+                }
+                [.., last] if replace_all => {
+                    for idx in matches.iter().rev().skip(1) {
+                        values.delete_line(*idx);
+                    }
+                    values.set_at(last - (matches.len() - 1), new_value);
+                    return Ok(matches.len());
+                }
+                _ => {
+                    return Err(crate::file::set_multi_value::Error::MultipleMatches { count: matches.len() });
+                }
+            }
+        }
+        self.section_mut_or_create_new(section_name, subsection_name)?
+            .push(key, Some(new_value));
+        Ok(0)
+    }
 }
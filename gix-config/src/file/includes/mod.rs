@@ -1,5 +1,6 @@
 use std::{
     borrow::Cow,
+    collections::HashMap,
     path::{Path, PathBuf},
 };
 
@@ -87,6 +88,8 @@ fn append_followed_includes_recursively(
     options: init::Options<'_>,
     buf: &mut Vec<u8>,
 ) -> Result<(), Error> {
+    // Multiple paths in one section must be placed one after another to keep their order.
+    let mut last_inserted_by_section = HashMap::<SectionId, SectionId>::new();
     for (section_id, config_path) in section_ids_and_include_paths {
         let meta = OwnShared::clone(&target_config.sections[&section_id].meta);
         let target_config_path = meta.path.as_deref();
@@ -126,7 +129,12 @@ fn append_followed_includes_recursively(
             })?;
         resolve_includes_recursive(&mut include_config, depth + 1, buf, options)?;
 
-        target_config.append_or_insert(include_config, Some(section_id));
+        let insert_after = last_inserted_by_section.get(&section_id).copied().unwrap_or(section_id);
+        let has_sections = !include_config.section_order.is_empty();
+        target_config.append_or_insert(include_config, Some(insert_after));
+        if has_sections {
+            last_inserted_by_section.insert(section_id, SectionId(target_config.section_id_counter - 1));
+        }
     }
     Ok(())
 }
@@ -283,9 +291,9 @@ fn check_interpolation_result(
     match res {
         Ok(good) => Ok(good.into()),
         Err(err) => match err {
-            path::interpolate::Error::Missing { .. } | path::interpolate::Error::UserInterpolationUnsupported => {
-                Ok(None)
-            }
+            path::interpolate::Error::Missing { .. }
+            | path::interpolate::Error::UserNotFound { .. }
+            | path::interpolate::Error::UserInterpolationUnsupported => Ok(None),
             path::interpolate::Error::UsernameConversion(_) | path::interpolate::Error::Utf8Conversion { .. } => {
                 Err(err)
             }
@@ -56,6 +56,22 @@ pub mod set_raw_value {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod set_multi_value {
+    /// The error returned by [`File::set_multi_value(…)`][crate::File::set_multi_value()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Header(#[from] crate::parse::section::header::Error),
+        #[error(transparent)]
+        ValueName(#[from] crate::parse::section::value_name::Error),
+        #[error("{count} values matched, but only one of them may be replaced unless all should be replaced")]
+        MultipleMatches { count: usize },
+    }
+}
+
 /// Additional information about a section.
 #[derive(Clone, Debug, PartialOrd, PartialEq, Ord, Eq, Hash)]
 pub struct Metadata {
@@ -214,6 +214,53 @@ impl<'borrow, 'lookup, 'event> MultiValueMut<'borrow, 'lookup, 'event> {
         self.indices_and_sizes.remove(index);
     }
 
+    /// Removes the value at the given index along with the line it is on, including its indentation, a trailing comment
+    /// and the newline.
+    ///
+    /// # Safety
+    ///
+    /// This will panic if the index is out of range.
+    pub(crate) fn delete_line(&mut self, index: usize) {
+        let EntryData {
+            section_id,
+            offset_index,
+        } = self.indices_and_sizes[index];
+        let (offset, size) = MultiValueMut::index_and_size(&self.offsets, section_id, offset_index);
+        if size == 0 {
+            return;
+        }
+        let body = self
+            .section
+            .get_mut(&section_id)
+            .expect("known section id")
+            .body
+            .as_mut();
+        let mut start = offset;
+        while start > 0 && matches!(body[start - 1], Event::Whitespace(_)) {
+            start -= 1;
+        }
+        let mut end = offset + size;
+        if start == 0 || matches!(body[start - 1], Event::Newline(_)) {
+            while matches!(body.get(end), Some(Event::Whitespace(_) | Event::Comment(_))) {
+                end += 1;
+            }
+            if matches!(body.get(end), Some(Event::Newline(_))) {
+                end += 1;
+            }
+        } else {
+            start = offset;
+        }
+        body.drain(start..end);
+
+        let offsets = self.offsets.get_mut(&section_id).expect("known section id");
+        offsets[offset_index - 1] -= offset - start;
+        if let Some(gap_after) = offsets.get_mut(offset_index + 1) {
+            *gap_after -= end - (offset + size);
+        }
+        offsets[offset_index] = 0;
+        self.indices_and_sizes.remove(index);
+    }
+
     /// Removes all values. Does nothing when called multiple times in
     /// succession.
     pub fn delete_all(&mut self) {
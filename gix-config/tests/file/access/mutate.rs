@@ -43,6 +43,16 @@ mod remove_section {
         file.section_mut_or_create_new("core", Some("name".into()))
             .expect("creation succeeds");
     }
+
+    #[test]
+    fn removal_with_filter_updates_lookup_tables() {
+        let mut file = gix_config::File::try_from("[core]\na = b\n[core]\na = c\n").unwrap();
+        assert!(file.remove_section_filter("core", None, &mut |_| true).is_some());
+        assert_eq!(file.sections().count(), 1);
+        assert_eq!(file.raw_value("core.a").unwrap().as_ref(), "b");
+        assert!(file.remove_section_filter("core", None, &mut |_| true).is_some());
+        assert!(file.section("core", None).is_err());
+    }
 }
 mod rename_section {
     use std::borrow::Cow;
@@ -94,3 +104,54 @@ mod set_meta {
         Ok(())
     }
 }
+mod remove_value {
+    use bstr::BStr;
+
+    #[test]
+    fn other_lines_are_kept_verbatim() -> crate::Result {
+        let mut file = gix_config::File::try_from(
+            "# head\n[core]\n\t# about a\n\ta = 1\n\tb = 2 ; trailing\n\n[core \"sub\"]\n  a = x\n",
+        )?;
+        assert_eq!(
+            file.remove_value("core", None, "A", false).as_deref(),
+            Some(BStr::new("1")),
+            "value names are case-insensitive"
+        );
+        assert_eq!(
+            file.to_string(),
+            "# head\n[core]\n\t# about a\n\tb = 2 ; trailing\n\n[core \"sub\"]\n  a = x\n"
+        );
+        assert_eq!(file.remove_value("core", None, "a", false), None, "it's gone");
+        Ok(())
+    }
+
+    #[test]
+    fn the_last_value_of_the_last_section_having_it_is_removed() -> crate::Result {
+        let mut file = gix_config::File::try_from("[a]\n\tx = 1\n\tx = 2\n[a]\n\ty = 3\n")?;
+        assert_eq!(file.remove_value("a", None, "x", false).as_deref(), Some(BStr::new("2")));
+        assert_eq!(file.to_string(), "[a]\n\tx = 1\n[a]\n\ty = 3\n");
+        Ok(())
+    }
+
+    #[test]
+    fn empty_sections_are_optionally_removed() -> crate::Result {
+        let input = "[a]\n\tx = 1\n[b \"sub\"]\n\ty = 2\n";
+        let mut file = gix_config::File::try_from(input)?;
+        file.remove_value("b", Some("sub".into()), "y", false);
+        assert_eq!(file.to_string(), "[a]\n\tx = 1\n[b \"sub\"]\n");
+
+        let mut file = gix_config::File::try_from(input)?;
+        file.remove_value("b", Some("sub".into()), "y", true);
+        assert_eq!(file.to_string(), "[a]\n\tx = 1\n");
+        assert!(file.section("b", Some("sub".into())).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn sections_with_comments_are_kept_even_if_empty() -> crate::Result {
+        let mut file = gix_config::File::try_from("[a]\n\t# keep me\n\tx = 1\n")?;
+        file.remove_value("a", None, "x", true);
+        assert_eq!(file.to_string(), "[a]\n\t# keep me\n");
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 mod raw_multi_value;
 mod raw_value;
 mod set_existing_raw_value;
+mod set_multi_value;
 mod set_raw_value;
@@ -0,0 +1,98 @@
+use bstr::{BStr, ByteSlice};
+use gix_config::file::set_multi_value;
+
+fn file(input: &str) -> gix_config::File<'static> {
+    input.parse().unwrap()
+}
+
+const INPUT: &str = "[a]\n\tk = one # first\n\tother = x\n\tk = two\n; between\n[a]\n\tk = three\n";
+
+#[test]
+fn without_filter_a_single_value_is_replaced() -> crate::Result {
+    let mut file = file("[a]\n\tk = one # first\n\tother = x\n");
+    assert_eq!(file.set_multi_value("a", None, "k", "new", None, false)?, 1);
+    assert_eq!(file.to_string(), "[a]\n\tk = new # first\n\tother = x\n");
+    Ok(())
+}
+
+#[test]
+fn without_filter_multiple_values_are_an_error_unless_all_are_replaced() -> crate::Result {
+    let mut file = file(INPUT);
+    assert!(matches!(
+        file.set_multi_value("a", None, "k", "new", None, false),
+        Err(set_multi_value::Error::MultipleMatches { count: 3 })
+    ));
+    assert_eq!(file.to_string(), INPUT, "nothing was changed");
+
+    assert_eq!(file.set_multi_value("a", None, "k", "new", None, true)?, 3);
+    assert_eq!(file.raw_values("a.k")?, vec![BStr::new("new")]);
+    assert_eq!(
+        file.to_string(),
+        "[a]\n\tother = x\n; between\n[a]\n\tk = new\n",
+        "the last match is replaced, the lines of all others are removed, other keys and comments are kept"
+    );
+
+    let mut file = self::file("[a]\n\tk = 1\n\tk = 2 ; two\n\tk = 3\n\tk = 4\n");
+    assert_eq!(
+        file.set_multi_value("a", None, "k", "new", Some(&mut |value: &BStr| value != "3"), true)?,
+        3
+    );
+    assert_eq!(file.to_string(), "[a]\n\tk = 3\n\tk = new\n");
+    assert_eq!(file.raw_values("a.k")?, vec![BStr::new("3"), BStr::new("new")]);
+    Ok(())
+}
+
+#[test]
+fn the_filter_selects_the_values_to_replace() -> crate::Result {
+    let mut file = file(INPUT);
+    assert_eq!(
+        file.set_multi_value(
+            "a",
+            None,
+            "k",
+            "new",
+            Some(&mut |value: &BStr| value.starts_with_str("t")),
+            true
+        )?,
+        2
+    );
+    assert_eq!(file.raw_values("a.k")?, vec![BStr::new("one"), BStr::new("new")]);
+
+    let mut file = self::file(INPUT);
+    assert_eq!(
+        file.set_multi_value("a", None, "k", "new", Some(&mut |value: &BStr| value == "two"), false)?,
+        1
+    );
+    assert_eq!(
+        file.to_string(),
+        "[a]\n\tk = one # first\n\tother = x\n\tk = new\n; between\n[a]\n\tk = three\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn the_value_is_added_if_nothing_matches() -> crate::Result {
+    let mut file = file("[a]\n\tk = one\n");
+    assert_eq!(
+        file.set_multi_value("a", None, "k", "two", Some(&mut |_value: &BStr| false), false)?,
+        0
+    );
+    assert_eq!(file.raw_values("a.k")?, vec![BStr::new("one"), BStr::new("two")]);
+
+    assert_eq!(
+        file.set_multi_value("b", Some("sub".into()), "k", "new", None, false)?,
+        0,
+        "sections are created if needed"
+    );
+    assert_eq!(file.raw_value("b.sub.k")?.as_ref(), "new");
+    Ok(())
+}
+
+#[test]
+fn invalid_value_names_are_rejected() {
+    let mut file = file("[a]\n\tk = one\n");
+    assert!(matches!(
+        file.set_multi_value("a", None, "in valid", "new", None, true),
+        Err(set_multi_value::Error::ValueName(_))
+    ));
+}
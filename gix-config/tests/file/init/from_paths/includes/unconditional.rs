@@ -324,3 +324,32 @@ fn nested() -> crate::Result {
     assert_eq!(config.boolean_by("core", None, "a"), Some(Ok(false)));
     Ok(())
 }
+
+#[test]
+fn multiple_paths_in_one_section_are_included_in_order() -> crate::Result {
+    let dir = tempdir()?;
+
+    let a_path = dir.path().join("a");
+    fs::write(a_path.as_path(), "[core]\n  v = a\n  only-a = a\n")?;
+    let b_path = dir.path().join("b");
+    fs::write(b_path.as_path(), "[core]\n  v = b\n")?;
+
+    let root_path = dir.path().join("root");
+    fs::write(
+        root_path.as_path(),
+        format!(
+            "[core]\n  v = root\n[include]\n  path = {}\n  path = {}\n",
+            escape_backslashes(&a_path),
+            escape_backslashes(&b_path),
+        ),
+    )?;
+
+    let config = File::from_paths_metadata(into_meta(vec![root_path]), follow_options())?.expect("non-empty");
+    assert_eq!(
+        config.strings("core.v").expect("present"),
+        vec![cow_str("root"), cow_str("a"), cow_str("b")],
+        "later includes override earlier ones, just like in git"
+    );
+    assert_eq!(config.string("core.v").expect("present").as_ref(), "b");
+    Ok(())
+}
@@ -72,55 +72,14 @@ impl Cascade {
     pub fn invoke(&mut self, mut action: helper::Action, mut prompt: gix_prompt::Options<'_>) -> protocol::Result {
         let mut url = action
             .context_mut()
-            .map(|ctx| {
-                ctx.destructure_url_in_place(self.use_http_path).map(|ctx| {
-                    if self.query_user_only && ctx.password.is_none() {
-                        ctx.password = Some("".into());
-                    }
-                    ctx
-                })
+            .map(|ctx| match ctx.url {
+                Some(_) => self.prepare_context(ctx),
+                None => Err(protocol::Error::UrlMissing),
             })
             .transpose()?
-            .and_then(|ctx| ctx.url.take());
+            .flatten();
 
-        for program in &mut self.programs {
-            program.stderr = self.stderr;
-            match helper::invoke::raw(program, &action) {
-                Ok(None) => {}
-                Ok(Some(stdout)) => {
-                    let ctx = Context::from_bytes(&stdout)?;
-                    if let Some(dst_ctx) = action.context_mut() {
-                        if let Some(src) = ctx.path {
-                            dst_ctx.path = Some(src);
-                        }
-                        for (src, dst) in [
-                            (ctx.protocol, &mut dst_ctx.protocol),
-                            (ctx.host, &mut dst_ctx.host),
-                            (ctx.username, &mut dst_ctx.username),
-                            (ctx.password, &mut dst_ctx.password),
-                        ] {
-                            if let Some(src) = src {
-                                *dst = Some(src);
-                            }
-                        }
-                        if let Some(src) = ctx.url {
-                            dst_ctx.url = Some(src);
-                            url = dst_ctx.destructure_url_in_place(self.use_http_path)?.url.take();
-                        }
-                        if dst_ctx.username.is_some() && dst_ctx.password.is_some() {
-                            break;
-                        }
-                        if ctx.quit.unwrap_or_default() {
-                            dst_ctx.quit = ctx.quit;
-                            break;
-                        }
-                    }
-                }
-                Err(helper::Error::CredentialsHelperFailed { .. }) => continue, // ignore helpers that we can't call
-                Err(err) if action.context().is_some() => return Err(err.into()), // communication errors are fatal when getting credentials
-                Err(_) => {} // for other actions, ignore everything, try the operation
-            }
-        }
+        self.run_programs(&mut action, &mut url)?;
 
         if prompt.mode != gix_prompt::Mode::Disable {
             if let Some(ctx) = action.context_mut() {
@@ -158,4 +117,119 @@ impl Cascade {
             action,
         )
     }
+
+    /// Ask each program in order to fill in the missing parts of `ctx`, with later programs overriding what earlier
+    /// ones provided, without ever prompting the user.
+    ///
+    /// The cascade stops as soon as `username` and `password` are both present, or if a helper responded with `quit=true`.
+    /// Return the index into our `programs` of the helper that completed the credential or asked to quit,
+    /// or `None` if no helper did.
+    ///
+    /// Note that `ctx` is updated even if the credential remained incomplete.
+    #[allow(clippy::result_large_err)]
+    pub fn fill(&mut self, ctx: &mut Context) -> Result<Option<usize>, protocol::Error> {
+        let mut url = self.prepare_context(ctx)?;
+        let mut action = helper::Action::Get(std::mem::take(ctx));
+        let res = self.run_programs(&mut action, &mut url);
+        if let helper::Action::Get(filled) = action {
+            *ctx = filled;
+        }
+        ctx.url = url;
+        res
+    }
+
+    /// Tell all programs that the credential in `ctx` was used successfully, so they can store it.
+    ///
+    /// Failures of individual programs are ignored, just like `git` does.
+    #[allow(clippy::result_large_err)]
+    pub fn approve(&mut self, ctx: &Context) -> Result<(), protocol::Error> {
+        let mut action = helper::Action::Store(Self::to_payload(ctx)?);
+        self.run_programs(&mut action, &mut None).map(|_| ())
+    }
+
+    /// Tell all programs that the credential in `ctx` was rejected, so they can erase it.
+    ///
+    /// Failures of individual programs are ignored, just like `git` does.
+    #[allow(clippy::result_large_err)]
+    pub fn reject(&mut self, ctx: &Context) -> Result<(), protocol::Error> {
+        let mut action = helper::Action::Erase(Self::to_payload(ctx)?);
+        self.run_programs(&mut action, &mut None).map(|_| ())
+    }
+}
+
+/// Utilities
+impl Cascade {
+    #[allow(clippy::result_large_err)]
+    fn to_payload(ctx: &Context) -> Result<bstr::BString, protocol::Error> {
+        let mut buf = Vec::<u8>::new();
+        ctx.write_to(&mut buf).map_err(helper::Error::from)?;
+        Ok(buf.into())
+    }
+
+    /// Split the url in `ctx` into its parts if there is one, and return it.
+    #[allow(clippy::result_large_err)]
+    fn prepare_context(&self, ctx: &mut Context) -> Result<Option<bstr::BString>, protocol::Error> {
+        if ctx.url.is_some() {
+            ctx.destructure_url_in_place(self.use_http_path)?;
+        }
+        if self.query_user_only && ctx.password.is_none() {
+            ctx.password = Some("".into());
+        }
+        Ok(ctx.url.take())
+    }
+
+    /// Run all programs with `action`, and when getting credentials, update its context with their output until
+    /// the credentials are complete or a program asks to quit. The index of that program is returned.
+    #[allow(clippy::result_large_err)]
+    fn run_programs(
+        &mut self,
+        action: &mut helper::Action,
+        url: &mut Option<bstr::BString>,
+    ) -> Result<Option<usize>, protocol::Error> {
+        for (program_index, program) in self.programs.iter_mut().enumerate() {
+            program.stderr = self.stderr;
+            match helper::invoke::raw(program, action) {
+                Ok(None) => {}
+                Ok(Some(stdout)) => {
+                    let ctx = Context::from_bytes(&stdout)?;
+                    if let Some(dst_ctx) = action.context_mut() {
+                        if let Some(src) = ctx.path {
+                            dst_ctx.path = Some(src);
+                        }
+                        for (src, dst) in [
+                            (ctx.protocol, &mut dst_ctx.protocol),
+                            (ctx.host, &mut dst_ctx.host),
+                            (ctx.username, &mut dst_ctx.username),
+                            (ctx.password, &mut dst_ctx.password),
+                        ] {
+                            if let Some(src) = src {
+                                *dst = Some(src);
+                            }
+                        }
+                        if !ctx.www_authenticate.is_empty() {
+                            dst_ctx.www_authenticate = ctx.www_authenticate;
+                        }
+                        if !ctx.extra.is_empty() {
+                            dst_ctx.extra = ctx.extra;
+                        }
+                        if let Some(src) = ctx.url {
+                            dst_ctx.url = Some(src);
+                            *url = dst_ctx.destructure_url_in_place(self.use_http_path)?.url.take();
+                        }
+                        if ctx.quit.unwrap_or_default() {
+                            dst_ctx.quit = ctx.quit;
+                            return Ok(Some(program_index));
+                        }
+                        if dst_ctx.username.is_some() && dst_ctx.password.is_some() {
+                            return Ok(Some(program_index));
+                        }
+                    }
+                }
+                Err(helper::Error::CredentialsHelperFailed { .. }) => continue, // ignore helpers that we can't call
+                Err(err) if action.context().is_some() => return Err(err.into()), // communication errors are fatal when getting credentials
+                Err(_) => {} // for other actions, ignore everything, try the operation
+            }
+        }
+        Ok(None)
+    }
 }
@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use futures_lite::{AsyncReadExt, AsyncWriteExt};
+
+use crate::helper::{Action, Context, Error, NextAction, Outcome, Result};
+
+/// How often to check if the helper process has exited after its output was consumed.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Like [`invoke()`][crate::helper::invoke()], but performs all communication with the `helper` program without blocking
+/// the calling thread, which makes it suitable for use in async contexts.
+///
+/// Input is written and output is read concurrently, so helpers that produce output before consuming all of their input
+/// won't cause a deadlock. If `timeout` is `Some(…)` and the helper doesn't finish in time, it will be killed and
+/// [`Error::Timeout`] is returned. Like in the blocking version, a helper exiting with a non-zero status code
+/// is reported as [`Error::CredentialsHelperFailed`].
+pub async fn invoke_async(helper: &crate::Program, action: &Action, timeout: Option<Duration>) -> Result {
+    match raw(helper, action, timeout).await? {
+        None => Ok(None),
+        Some(stdout) => {
+            let ctx = Context::from_bytes(stdout.as_slice())?;
+            Ok(Some(Outcome {
+                username: ctx.username,
+                password: ctx.password,
+                quit: ctx.quit.unwrap_or(false),
+                next: NextAction {
+                    previous_output: stdout.into(),
+                },
+            }))
+        }
+    }
+}
+
+async fn raw(
+    helper: &crate::Program,
+    action: &Action,
+    timeout: Option<Duration>,
+) -> std::result::Result<Option<Vec<u8>>, Error> {
+    let mut cmd = helper.to_command(action);
+    gix_trace::debug!(cmd = ?cmd, "launching credential helper");
+    let mut child = cmd.spawn()?;
+    let mut stdin = blocking::Unblock::new(child.stdin.take().expect("stdin to be configured"));
+    let stdout = child.stdout.take().map(blocking::Unblock::new);
+    if let (Action::Get(_), None) = (&action, &stdout) {
+        panic!("BUG: `Helper` impls must return an output handle to read output from if Action::Get is provided")
+    }
+    let mut input = Vec::new();
+    action.send(&mut input)?;
+
+    let communicate = async {
+        let write = async move {
+            // Like in the blocking version, helpers are free to not read their input.
+            stdin.write_all(&input).await.ok();
+            stdin.close().await.ok();
+        };
+        let read = async move {
+            match stdout {
+                Some(mut stdout) => {
+                    let mut buf = Vec::new();
+                    stdout.read_to_end(&mut buf).await.map(|_| Some(buf))
+                }
+                None => Ok(None),
+            }
+        };
+        let ((), stdout) = futures_lite::future::zip(write, read).await;
+        let stdout = stdout.map_err(|err| Error::CredentialsHelperFailed { source: err })?;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            async_io::Timer::after(POLL_INTERVAL).await;
+        };
+        Ok((stdout, status))
+    };
+    let res = match timeout {
+        Some(timeout) => {
+            futures_lite::future::or(communicate, async {
+                async_io::Timer::after(timeout).await;
+                Err(Error::Timeout { timeout })
+            })
+            .await
+        }
+        None => communicate.await,
+    };
+    let (stdout, status) = match res {
+        Ok(res) => res,
+        Err(err) => {
+            child.kill().ok();
+            blocking::unblock(move || child.wait()).await.ok();
+            return Err(err);
+        }
+    };
+    crate::program::exit_status_to_result(status).map_err(|err| Error::CredentialsHelperFailed { source: err })?;
+
+    match matches!(action, Action::Get(_)).then_some(stdout).flatten() {
+        None => Ok(None),
+        Some(stdout) => Ok(Some(stdout)),
+    }
+}
@@ -59,6 +59,8 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error(transparent)]
     CredentialsHelperFailed { source: std::io::Error },
+    #[error("The credentials helper didn't finish within {}s", timeout.as_secs_f32())]
+    Timeout { timeout: std::time::Duration },
 }
 
 /// The action to perform by the credentials [helper][`crate::helper::invoke()`].
@@ -168,5 +170,9 @@ impl NextAction {
 
 mod cascade;
 pub(crate) mod invoke;
+#[cfg(feature = "async-client")]
+mod invoke_async;
 
 pub use invoke::invoke;
+#[cfg(feature = "async-client")]
+pub use invoke_async::invoke_async;
@@ -32,6 +32,10 @@ pub mod program;
 #[allow(clippy::empty_docs)]
 pub mod protocol;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod storage;
+
 /// Call the `git credential` helper program performing the given `action`, which reads all context from the git configuration
 /// and does everything `git` typically does. The `action` should have been created with [`helper::Action::get_for_url()`] to
 /// contain only the URL to kick off the process, or should be created by [`helper::NextAction`].
@@ -131,15 +131,18 @@ impl Program {
 
     pub(crate) fn finish(&mut self) -> std::io::Result<()> {
         let mut child = self.child.take().expect("Call `start()` before calling finish()");
-        let status = child.wait()?;
-        if status.success() {
-            Ok(())
-        } else {
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Credentials helper program failed with status code {:?}", status.code()),
-            ))
-        }
+        exit_status_to_result(child.wait()?)
+    }
+}
+
+pub(crate) fn exit_status_to_result(status: std::process::ExitStatus) -> std::io::Result<()> {
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("Credentials helper program failed with status code {:?}", status.code()),
+        ))
     }
 }
 
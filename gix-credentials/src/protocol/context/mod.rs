@@ -6,17 +6,21 @@ use bstr::BString;
 pub enum Error {
     #[error("{key:?}={value:?} must not contain null bytes or newlines neither in key nor in value.")]
     Encoding { key: String, value: BString },
+    #[error("The value of {key:?} must not be empty")]
+    EmptyValue { key: String },
+    #[error("The key {key:?} must not be empty or contain '='")]
+    InvalidKey { key: String },
 }
 
 mod access {
-    use bstr::BString;
+    use bstr::{BStr, BString, ByteSlice};
 
     use crate::protocol::Context;
 
     impl Context {
         /// Convert all relevant fields into a URL for consumption.
         pub fn to_url(&self) -> Option<BString> {
-            use bstr::{ByteSlice, ByteVec};
+            use bstr::ByteVec;
             let mut buf: BString = self.protocol.clone()?.into();
             buf.push_str(b"://");
             if let Some(user) = &self.username {
@@ -41,6 +45,149 @@ mod access {
                 None => format!("{field}: "),
             }
         }
+
+        /// Return `true` if this context matches the URL `pattern` as used in `credential.<url>.*` configuration sections.
+        ///
+        /// As in `git`, the scheme must match exactly, and the host may start with a `*.` wildcard to match exactly one
+        /// subdomain level. A port and username only have to match if `pattern` specifies one, and the path of `pattern`
+        /// must be a prefix of our path that ends on a `/` boundary.
+        /// If our `protocol` isn't set, our `url` field is parsed to obtain all parts to match.
+        /// Invalid patterns never match.
+        pub fn matches(&self, pattern: &BStr) -> bool {
+            let Ok(pattern) = Context::from_url(pattern) else {
+                return false;
+            };
+            let parsed_url;
+            let ctx = match (&self.protocol, &self.url) {
+                (None, Some(url)) => match Context::from_url(url.as_ref()) {
+                    Ok(ctx) => {
+                        parsed_url = ctx;
+                        &parsed_url
+                    }
+                    Err(_) => return false,
+                },
+                _ => self,
+            };
+
+            let scheme_matches = match (&pattern.protocol, &ctx.protocol) {
+                (Some(pattern), Some(actual)) => pattern.eq_ignore_ascii_case(actual),
+                _ => false,
+            };
+            let user_matches = pattern.username.is_none() || pattern.username == ctx.username;
+            let host_and_port_match = match (pattern.host.as_deref(), ctx.host.as_deref()) {
+                (Some(pattern), Some(actual)) => {
+                    let (pattern_host, pattern_port) = split_port(pattern);
+                    let (host, port) = split_port(actual);
+                    host_matches(pattern_host, host)
+                        && pattern_port.map_or(true, |pattern_port| {
+                            Some(pattern_port) == port.or_else(|| ctx.protocol.as_deref().and_then(default_port))
+                        })
+                }
+                (None, None) => true,
+                (Some(_), None) | (None, Some(_)) => false,
+            };
+            scheme_matches
+                && user_matches
+                && host_and_port_match
+                && path_matches(
+                    pattern.path.as_ref().map(AsRef::as_ref),
+                    ctx.path.as_ref().map(AsRef::as_ref),
+                )
+        }
+    }
+
+    /// Split `host:port` into its components, keeping the brackets of IPv6 addresses.
+    fn split_port(host: &str) -> (&str, Option<&str>) {
+        match host.rfind(':') {
+            Some(pos) if !host[pos..].contains(']') => (&host[..pos], Some(&host[pos + 1..])),
+            _ => (host, None),
+        }
+    }
+
+    /// Return the port that is implied for URLs of `scheme` if they don't specify one.
+    fn default_port(scheme: &str) -> Option<&'static str> {
+        Some(match scheme.to_ascii_lowercase().as_str() {
+            "http" => "80",
+            "https" => "443",
+            "ssh" => "22",
+            "git" => "9418",
+            _ => return None,
+        })
+    }
+
+    fn host_matches(pattern: &str, host: &str) -> bool {
+        match pattern.strip_prefix("*.") {
+            Some(domain) => {
+                matches!(host.split_once('.'), Some((_subdomain, host_domain)) if host_domain.eq_ignore_ascii_case(domain))
+            }
+            None => pattern.eq_ignore_ascii_case(host),
+        }
+    }
+
+    fn path_matches(pattern: Option<&BStr>, path: Option<&BStr>) -> bool {
+        match (pattern, path) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(pattern), Some(path)) => {
+                matches!(path.strip_prefix(pattern.as_bytes()), Some(rest) if rest.is_empty() || rest.starts_with(b"/"))
+            }
+        }
+    }
+}
+
+pub(crate) mod init {
+    use bstr::BStr;
+
+    use crate::{protocol, protocol::Context};
+
+    /// Initialization
+    impl Context {
+        /// Parse `url` and use its parts to fill in `protocol`, `host` (with the port appended as `host:port`), `path`,
+        /// `username` and `password`, leaving all fields `None` that the URL doesn't provide.
+        ///
+        /// Unlike [`destructure_url_in_place()`][Self::destructure_url_in_place()], the path is always kept
+        /// and percent-encoded user information is decoded, just like `git` does when it receives a URL.
+        /// Note that the `url` field remains unset.
+        #[allow(clippy::result_large_err)]
+        pub fn from_url(url: &BStr) -> Result<Self, protocol::Error> {
+            let url = gix_url::parse(url)?;
+            let mut ctx = Context::default();
+            ctx.set_url_parts(&url, true);
+            ctx.username = ctx.username.map(|user| percent_decode(&user));
+            ctx.password = ctx.password.map(|password| percent_decode(&password));
+            Ok(ctx)
+        }
+    }
+
+    /// Decode `%XX` sequences in `input`, falling back to the original if the decoded bytes aren't valid UTF-8.
+    fn percent_decode(input: &str) -> String {
+        String::from_utf8(percent_decode_bytes(input.as_bytes())).unwrap_or_else(|_| input.to_owned())
+    }
+
+    /// Decode `%XX` sequences in `input`, leaving invalid sequences untouched.
+    pub(crate) fn percent_decode_bytes(input: &[u8]) -> Vec<u8> {
+        fn hex(b: u8) -> Option<u8> {
+            char::from(b).to_digit(16).map(|d| d as u8)
+        }
+        let mut out = Vec::with_capacity(input.len());
+        let mut idx = 0;
+        while idx < input.len() {
+            match (
+                input[idx],
+                input.get(idx + 1).copied().and_then(hex),
+                input.get(idx + 2).copied().and_then(hex),
+            ) {
+                (b'%', Some(hi), Some(lo)) => {
+                    out.push((hi << 4) | lo);
+                    idx += 3;
+                }
+                (b, _, _) => {
+                    out.push(b);
+                    idx += 1;
+                }
+            }
+        }
+        out
     }
 }
 
@@ -57,6 +204,11 @@ mod mutate {
         #[allow(clippy::result_large_err)]
         pub fn destructure_url_in_place(&mut self, use_http_path: bool) -> Result<&mut Self, protocol::Error> {
             let url = gix_url::parse(self.url.as_ref().ok_or(protocol::Error::UrlMissing)?.as_ref())?;
+            self.set_url_parts(&url, use_http_path);
+            Ok(self)
+        }
+
+        pub(crate) fn set_url_parts(&mut self, url: &gix_url::Url, use_http_path: bool) {
             self.protocol = Some(url.scheme.as_str().into());
             self.username = url.user().map(ToOwned::to_owned);
             self.password = url.password().map(ToOwned::to_owned);
@@ -71,10 +223,104 @@ mod mutate {
                 let path = url.path.trim_with(|b| b == '/');
                 self.path = (!path.is_empty()).then(|| path.into());
             }
-            Ok(self)
         }
     }
 }
 
+/// A utility to display a [`Context`] with `Debug` without redacting secrets, as returned by
+/// [`Context::debug_unredacted()`].
+pub struct DebugUnredacted<'a>(&'a crate::protocol::Context);
+
+mod debug {
+    use std::borrow::Cow;
+
+    use bstr::{BStr, BString, ByteSlice, ByteVec};
+
+    use crate::protocol::{context::DebugUnredacted, Context};
+
+    const REDACTED: &str = "<redacted>";
+
+    impl Context {
+        /// Return a utility which shows all fields when formatted with `Debug`, including secrets like the `password`.
+        ///
+        /// Only use this if it's known that the output won't be seen by anyone who shouldn't know the secrets.
+        pub fn debug_unredacted(&self) -> DebugUnredacted<'_> {
+            DebugUnredacted(self)
+        }
+    }
+
+    impl std::fmt::Debug for Context {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            fmt(self, f, true)
+        }
+    }
+
+    impl std::fmt::Debug for DebugUnredacted<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            fmt(self.0, f, false)
+        }
+    }
+
+    fn fmt(ctx: &Context, f: &mut std::fmt::Formatter<'_>, redact: bool) -> std::fmt::Result {
+        f.debug_struct("Context")
+            .field("protocol", &ctx.protocol)
+            .field("host", &ctx.host)
+            .field("path", &ctx.path)
+            .field("username", &ctx.username)
+            .field(
+                "password",
+                &ctx.password
+                    .as_ref()
+                    .map(|pw| if redact { REDACTED } else { pw.as_str() }),
+            )
+            .field(
+                "url",
+                &ctx.url.as_ref().map(|url| {
+                    if redact {
+                        redact_url_password(url.as_ref())
+                    } else {
+                        Cow::Borrowed(url.as_bstr())
+                    }
+                }),
+            )
+            .field("quit", &ctx.quit)
+            .field(
+                "www_authenticate",
+                &ctx.www_authenticate
+                    .iter()
+                    .map(|value| if redact { REDACTED.into() } else { value.as_bstr() })
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "extra",
+                &ctx.extra
+                    .iter()
+                    .map(|(key, value)| (key, if redact { REDACTED.into() } else { value.as_bstr() }))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+
+    /// Replace the password in the user-info portion of `url`, if there is one.
+    fn redact_url_password(url: &BStr) -> Cow<'_, BStr> {
+        let Some(authority_start) = url.find("://").map(|pos| pos + 3) else {
+            return Cow::Borrowed(url);
+        };
+        let authority = &url[authority_start..];
+        let authority = authority.find_byte(b'/').map_or(authority, |pos| &authority[..pos]);
+        let Some(password_range) = authority.rfind_byte(b'@').and_then(|at| {
+            authority[..at]
+                .find_byte(b':')
+                .map(|colon| authority_start + colon + 1..authority_start + at)
+        }) else {
+            return Cow::Borrowed(url);
+        };
+        let mut redacted = BString::from(&url[..password_range.start]);
+        redacted.push_str(REDACTED);
+        redacted.push_str(&url[password_range.end..]);
+        Cow::Owned(redacted)
+    }
+}
+
 mod serde;
 pub use self::serde::decode;
@@ -5,7 +5,10 @@ use crate::protocol::context::Error;
 mod write {
     use bstr::{BStr, BString};
 
-    use crate::protocol::{context::serde::validate, Context};
+    use crate::protocol::{
+        context::serde::{validate, validate_extra_key, KNOWN_KEYS},
+        Context,
+    };
 
     impl Context {
         /// Write ourselves to `out` such that [`from_bytes()`][Self::from_bytes()] can decode it losslessly.
@@ -36,6 +39,20 @@ mod write {
                     write_key(&mut out, key, value.as_bytes().as_bstr()).ok();
                 }
             }
+            for value in &self.www_authenticate {
+                let key = "wwwauth[]";
+                validate(key, value.as_ref()).map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                write_key(&mut out, key, value.as_ref()).ok();
+            }
+            for (key, value) in &self.extra {
+                validate_extra_key(key).map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                if KNOWN_KEYS.contains(&key.as_str()) {
+                    // These were written already from our fields, or are not meant to be written.
+                    continue;
+                }
+                validate(key, value.as_ref()).map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                write_key(&mut out, key, value.as_ref()).ok();
+            }
             Ok(())
         }
 
@@ -100,10 +117,11 @@ pub mod decode {
                     }
                     "url" => ctx.url = Some(value),
                     "path" => ctx.path = Some(value),
+                    "wwwauth[]" => ctx.www_authenticate.push(value),
                     "quit" => {
                         ctx.quit = gix_config_value::Boolean::try_from(value.as_ref()).ok().map(Into::into);
                     }
-                    _ => {}
+                    _ => ctx.extra.push((key.into(), value)),
                 }
             }
             Ok(ctx)
@@ -111,6 +129,25 @@ pub mod decode {
     }
 }
 
+/// The keys that have a field of their own, which are never written from the extra keys.
+const KNOWN_KEYS: &[&str] = &[
+    "protocol",
+    "host",
+    "path",
+    "username",
+    "password",
+    "url",
+    "quit",
+    "wwwauth[]",
+];
+
+fn validate_extra_key(key: &str) -> Result<(), Error> {
+    if key.is_empty() || key.contains('=') {
+        return Err(Error::InvalidKey { key: key.to_owned() });
+    }
+    Ok(())
+}
+
 fn validate(key: &str, value: &BStr) -> Result<(), Error> {
     if key.contains('\0') || key.contains('\n') || value.contains(&0) || value.contains(&b'\n') {
         return Err(Error::Encoding {
@@ -118,5 +155,8 @@ fn validate(key: &str, value: &BStr) -> Result<(), Error> {
             value: value.to_owned(),
         });
     }
+    if key == "wwwauth[]" && value.is_empty() {
+        return Err(Error::EmptyValue { key: key.to_owned() });
+    }
     Ok(())
 }
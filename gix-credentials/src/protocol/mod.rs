@@ -35,7 +35,10 @@ pub enum Error {
 }
 
 /// Additional context to be passed to the credentials helper.
-#[derive(Debug, Default, Clone, Eq, PartialEq)]
+///
+/// Note that its `Debug` implementation redacts the `password`, the password in `url` and the values of
+/// `www_authenticate` and `extra`, use [`debug_unredacted()`][Context::debug_unredacted()] to see them.
+#[derive(Default, Clone, Eq, PartialEq)]
 pub struct Context {
     /// The protocol over which the credential will be used (e.g., https).
     pub protocol: Option<String>,
@@ -54,6 +57,15 @@ pub struct Context {
     pub url: Option<BString>,
     /// If true, the caller should stop asking for credentials immediately without calling more credential helpers in the chain.
     pub quit: Option<bool>,
+    /// The values of all `WWW-Authenticate` headers the server sent, as passed via `wwwauth[]`, in the order in which they were received.
+    ///
+    /// Helpers may use these to select an authentication scheme, with the first one having the highest priority.
+    pub www_authenticate: Vec<BString>,
+    /// All `key=value` pairs with keys we don't know, in the order in which they were received.
+    ///
+    /// They are written back verbatim after all known keys, which allows passing values like `authtype` through
+    /// to the next helper in the chain even though we don't interpret them.
+    pub extra: Vec<(String, BString)>,
 }
 
 /// Convert the outcome of a helper invocation to a helper result, assuring that the identity is complete in the process.
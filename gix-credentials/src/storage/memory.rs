@@ -0,0 +1,46 @@
+use crate::{
+    protocol::Context,
+    storage::{is_storable, matches, to_stored, Memory, Storage},
+};
+
+impl Memory {
+    fn key(ctx: &Context) -> super::Key {
+        (
+            ctx.protocol.clone(),
+            ctx.host.clone(),
+            ctx.path.clone(),
+            ctx.username.clone(),
+        )
+    }
+}
+
+impl std::fmt::Debug for Memory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Memory")
+            .field("credentials", &self.credentials.values().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Storage for Memory {
+    fn get(&self, ctx: &Context) -> std::io::Result<Option<Context>> {
+        Ok(self
+            .credentials
+            .get(&Self::key(ctx))
+            .filter(|stored| matches(ctx, stored))
+            .or_else(|| self.credentials.values().find(|stored| matches(ctx, stored)))
+            .cloned())
+    }
+
+    fn store(&mut self, ctx: &Context) -> std::io::Result<()> {
+        if is_storable(ctx) {
+            self.credentials.insert(Self::key(ctx), to_stored(ctx));
+        }
+        Ok(())
+    }
+
+    fn erase(&mut self, ctx: &Context) -> std::io::Result<()> {
+        self.credentials.retain(|_, stored| !matches(ctx, stored));
+        Ok(())
+    }
+}
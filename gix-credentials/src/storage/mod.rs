@@ -0,0 +1,76 @@
+use bstr::BString;
+
+use crate::protocol::Context;
+
+/// A place to store credentials in and retrieve them from, as an alternative to invoking helper programs.
+///
+/// Implementations are expected to follow the semantics of `git credential-store`, i.e. only fields that are set in
+/// the [`Context`] used as query must match.
+pub trait Storage {
+    /// Return the first stored credential that matches `ctx`, or `None` if there is none.
+    fn get(&self, ctx: &Context) -> std::io::Result<Option<Context>>;
+    /// Store the credential in `ctx`, replacing all credentials stored for the same `protocol`, `host`, `path` and `username`.
+    ///
+    /// Credentials without `protocol`, `username` or `password`, or those lacking both `host` and `path`, are ignored.
+    fn store(&mut self, ctx: &Context) -> std::io::Result<()>;
+    /// Remove all credentials that match `ctx`.
+    fn erase(&mut self, ctx: &Context) -> std::io::Result<()>;
+}
+
+/// Credentials stored in memory for the lifetime of the instance, keyed by `(protocol, host, path, username)`.
+///
+/// The `path` is only part of the key if it is set in the stored [`Context`], which for `http` and `https` URLs is
+/// only the case if `credential.useHttpPath` applies, as in [`Context::destructure_url_in_place()`].
+#[derive(Default, Clone)]
+pub struct Memory {
+    credentials: std::collections::BTreeMap<Key, Context>,
+}
+
+type Key = (Option<String>, Option<String>, Option<BString>, Option<String>);
+
+/// Credentials stored in a plain-text file in the format used by `git credential-store`, with one credential per line
+/// in the form of `<protocol>://<username>:<password>@<host>[/<path>]` and percent-encoded user information.
+///
+/// The file is read anew on each access so changes made by other processes, including `git`, are picked up.
+/// Changes are written to a lock file next to it which then replaces it, so it's never left partially written.
+/// **Note** that secrets are stored unencrypted, even though the file is created with user-only permissions where possible.
+#[derive(Debug, Clone)]
+pub struct PlaintextFile {
+    /// The file to read and write credentials from.
+    pub path: std::path::PathBuf,
+}
+
+/// Return `true` if `stored` can be returned as response to `query`, which is when all fields set in `query` are the same
+/// in `stored`.
+fn matches(query: &Context, stored: &Context) -> bool {
+    fn field_matches<T: PartialEq>(query: &Option<T>, stored: &Option<T>) -> bool {
+        query.is_none() || query == stored
+    }
+    field_matches(&query.protocol, &stored.protocol)
+        && field_matches(&query.host, &stored.host)
+        && field_matches(&query.path, &stored.path)
+        && field_matches(&query.username, &stored.username)
+}
+
+/// Return `true` if `ctx` has enough information to be stored.
+fn is_storable(ctx: &Context) -> bool {
+    ctx.protocol.is_some()
+        && ctx.username.is_some()
+        && ctx.password.is_some()
+        && (ctx.host.is_some() || ctx.path.is_some())
+}
+
+/// Only keep the fields that are relevant for storage.
+fn to_stored(ctx: &Context) -> Context {
+    Context {
+        protocol: ctx.protocol.clone(),
+        host: ctx.host.clone(),
+        path: ctx.path.clone(),
+        username: ctx.username.clone(),
+        password: ctx.password.clone(),
+        ..Default::default()
+    }
+}
+
+mod memory;
+mod plaintext_file;
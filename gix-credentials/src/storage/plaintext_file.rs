@@ -0,0 +1,168 @@
+use std::io::Write;
+
+use bstr::{BString, ByteSlice, ByteVec};
+
+use crate::{
+    protocol::{context::init::percent_decode_bytes, Context},
+    storage::{is_storable, matches, to_stored, PlaintextFile, Storage},
+};
+
+/// Initialization
+impl PlaintextFile {
+    /// Use the file at `path` to store credentials, it doesn't have to exist yet.
+    pub fn at(path: impl Into<std::path::PathBuf>) -> Self {
+        PlaintextFile { path: path.into() }
+    }
+
+    /// Return the file used by `git credential-store` by default, which is `~/.git-credentials`.
+    pub fn default_location() -> Option<Self> {
+        gix_path::env::home_dir().map(|home| Self::at(home.join(".git-credentials")))
+    }
+}
+
+impl PlaintextFile {
+    /// Read all lines, along with the credential they parse into. Lines that don't parse are retained as is.
+    fn lines(&self) -> std::io::Result<Vec<(BString, Option<Context>)>> {
+        let buf = match std::fs::read(&self.path) {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        Ok(buf
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| (line.into(), parse_line(line.as_bstr())))
+            .collect())
+    }
+
+    /// Lock the file for an update so no other process or instance can change it until the lock is committed or dropped.
+    ///
+    /// Like `git credential-store`, fail right away if it is already locked.
+    fn lock(&self) -> std::io::Result<gix_lock::File> {
+        let mode = gix_lock::acquire::Fail::Immediately;
+        #[cfg(unix)]
+        let res = gix_lock::File::acquire_to_update_resource_with_permissions(&self.path, mode, None, || {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::Permissions::from_mode(0o600)
+        });
+        #[cfg(not(unix))]
+        let res = gix_lock::File::acquire_to_update_resource(&self.path, mode, None);
+        res.map_err(|err| match err {
+            gix_lock::acquire::Error::Io(err) => err,
+            err @ gix_lock::acquire::Error::PermanentlyLocked { .. } => {
+                std::io::Error::new(std::io::ErrorKind::WouldBlock, err)
+            }
+        })
+    }
+
+    /// Write `first`, followed by all `lines` whose credentials don't match `remove`, into `lock` and commit it
+    /// to replace the file atomically.
+    fn rewrite(
+        mut lock: gix_lock::File,
+        first: Option<&Context>,
+        lines: Vec<(BString, Option<Context>)>,
+        remove: &Context,
+    ) -> std::io::Result<()> {
+        let mut buf = Vec::new();
+        if let Some(ctx) = first {
+            buf.extend_from_slice(&to_line(ctx));
+            buf.push(b'\n');
+        }
+        for (line, ctx) in lines {
+            if matches!(ctx, Some(ctx) if matches(remove, &ctx)) {
+                continue;
+            }
+            buf.extend_from_slice(&line);
+            buf.push(b'\n');
+        }
+        lock.write_all(&buf)?;
+        lock.commit().map_err(|err| err.error)?;
+        Ok(())
+    }
+}
+
+impl Storage for PlaintextFile {
+    fn get(&self, ctx: &Context) -> std::io::Result<Option<Context>> {
+        Ok(self
+            .lines()?
+            .into_iter()
+            .filter_map(|(_, stored)| stored)
+            .find(|stored| matches(ctx, stored)))
+    }
+
+    fn store(&mut self, ctx: &Context) -> std::io::Result<()> {
+        if !is_storable(ctx) {
+            return Ok(());
+        }
+        let ctx = to_stored(ctx);
+        let remove = Context {
+            password: None,
+            ..ctx.clone()
+        };
+        let lock = self.lock()?;
+        Self::rewrite(lock, Some(&ctx), self.lines()?, &remove)
+    }
+
+    fn erase(&mut self, ctx: &Context) -> std::io::Result<()> {
+        let lock = self.lock()?;
+        let lines = self.lines()?;
+        if !lines
+            .iter()
+            .any(|(_, stored)| matches!(stored, Some(stored) if matches(ctx, stored)))
+        {
+            return Ok(());
+        }
+        Self::rewrite(lock, None, lines, ctx)
+    }
+}
+
+fn parse_line(line: &bstr::BStr) -> Option<Context> {
+    let mut ctx = Context::from_url(line).ok()?;
+    ctx.host = ctx
+        .host
+        .map(|host| String::from_utf8(percent_decode_bytes(host.as_bytes())).unwrap_or(host));
+    ctx.path = ctx.path.map(|path| percent_decode_bytes(&path).into());
+    is_storable(&ctx).then_some(ctx)
+}
+
+/// Produce a line exactly like `git credential-store` does.
+fn to_line(ctx: &Context) -> BString {
+    fn is_unreserved(b: u8) -> bool {
+        b.is_ascii_alphanumeric() || b"-._~".contains(&b)
+    }
+    fn is_reserved_or_unreserved(b: u8) -> bool {
+        is_unreserved(b) || b"!*'();:@&=+$,/?#[]".contains(&b)
+    }
+    fn push_encoded(out: &mut BString, input: &[u8], keep: fn(u8) -> bool) {
+        for &b in input {
+            if keep(b) {
+                out.push(b);
+            } else {
+                out.push_str(format!("%{b:02x}"));
+            }
+        }
+    }
+
+    let mut out = BString::from(ctx.protocol.as_deref().unwrap_or_default());
+    out.push_str("://");
+    push_encoded(
+        &mut out,
+        ctx.username.as_deref().unwrap_or_default().as_bytes(),
+        is_unreserved,
+    );
+    out.push(b':');
+    push_encoded(
+        &mut out,
+        ctx.password.as_deref().unwrap_or_default().as_bytes(),
+        is_unreserved,
+    );
+    out.push(b'@');
+    if let Some(host) = &ctx.host {
+        push_encoded(&mut out, host.as_bytes(), is_reserved_or_unreserved);
+    }
+    if let Some(path) = &ctx.path {
+        out.push(b'/');
+        push_encoded(&mut out, path, is_reserved_or_unreserved);
+    }
+    out
+}
@@ -3,3 +3,4 @@ pub use gix_testtools::Result;
 mod helper;
 mod program;
 mod protocol;
+mod storage;
@@ -0,0 +1,8 @@
+#!/usr/bin/env bash
+set -eu
+
+echo username=user
+printf "password="
+head -c 1048576 /dev/zero | tr '\0' x
+echo
+cat > /dev/null
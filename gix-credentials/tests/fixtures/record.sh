@@ -0,0 +1,4 @@
+#!/usr/bin/env bash
+set -eu
+
+{ echo "$2"; cat; } >> "$1"
@@ -0,0 +1,4 @@
+#!/usr/bin/env bash
+
+echo authtype=Bearer
+echo credential=token
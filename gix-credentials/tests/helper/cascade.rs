@@ -90,6 +90,26 @@ mod invoke {
         );
     }
 
+    #[test]
+    fn helpers_can_set_unknown_keys() {
+        let actual = invoke_cascade(
+            ["unknown-keys", "custom-helper"],
+            Action::get_for_url("http://github.com"),
+        )
+        .unwrap()
+        .expect("credentials");
+
+        let ctx: Context = (&actual.next).try_into().unwrap();
+        assert_eq!(
+            ctx.extra,
+            [
+                ("authtype".to_string(), "Bearer".into()),
+                ("credential".to_string(), "token".into())
+            ],
+            "unknown keys are passed on in order"
+        );
+    }
+
     #[test]
     fn helpers_can_set_any_context_value_using_the_url_only() {
         let actual = invoke_cascade(["url", "custom-helper"], Action::get_for_url("http://github.com"))
@@ -170,3 +190,94 @@ mod invoke {
             .collect()
     }
 }
+
+mod fill_approve_reject {
+    use gix_credentials::{helper::Cascade, program, protocol::Context, Program};
+
+    use crate::helper::invoke::script_helper;
+
+    fn cascade<'a>(names: impl IntoIterator<Item = &'a str>) -> Cascade {
+        Cascade::default().extend(names.into_iter().map(script_helper))
+    }
+
+    fn recorder(log: &std::path::Path) -> Program {
+        let mut program = script_helper("record");
+        if let program::Kind::ExternalShellScript(script) = &mut program.kind {
+            script.extend_from_slice(b" ");
+            script.extend_from_slice(gix_path::into_bstr(log).as_ref());
+        }
+        program
+    }
+
+    fn github() -> Context {
+        Context::from_url("https://github.com".into()).unwrap()
+    }
+
+    #[test]
+    fn fill_stops_once_complete_and_reports_the_completing_helper() -> crate::Result {
+        let mut ctx = github();
+        let source = cascade(["fail", "username", "password", "fail"]).fill(&mut ctx)?;
+        assert_eq!(source, Some(2), "the password helper completed the credential");
+        assert_eq!(ctx.username.as_deref(), Some("user"));
+        assert_eq!(ctx.password.as_deref(), Some("pass"));
+        assert_eq!(ctx.host.as_deref(), Some("github.com"), "everything else is retained");
+        Ok(())
+    }
+
+    #[test]
+    fn fill_honors_quit() -> crate::Result {
+        let mut ctx = github();
+        let source = cascade(["username", "last-pass", "custom-helper"]).fill(&mut ctx)?;
+        assert_eq!(source, Some(1));
+        assert_eq!(ctx.quit, Some(true));
+        Ok(())
+    }
+
+    #[test]
+    fn fill_may_remain_incomplete() -> crate::Result {
+        let mut ctx = github();
+        let source = cascade(["username", "fail"]).fill(&mut ctx)?;
+        assert_eq!(source, None);
+        assert_eq!(ctx.username.as_deref(), Some("user"));
+        assert_eq!(ctx.password, None);
+        Ok(())
+    }
+
+    #[test]
+    fn fill_splits_urls() -> crate::Result {
+        let mut ctx = Context {
+            url: Some("https://example.com/repo".into()),
+            ..Default::default()
+        };
+        cascade(["fail"]).fill(&mut ctx)?;
+        assert_eq!(ctx.protocol.as_deref(), Some("https"));
+        assert_eq!(ctx.host.as_deref(), Some("example.com"));
+        assert_eq!(
+            ctx.url.as_ref().map(AsRef::as_ref),
+            Some("https://example.com/repo".as_bytes())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn approve_and_reject_are_forwarded_to_all_helpers() -> crate::Result {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let (first, second) = (dir.path().join("first"), dir.path().join("second"));
+        let mut cascade = Cascade::default().extend([recorder(&first), script_helper("fail"), recorder(&second)]);
+        let mut ctx = github();
+        ctx.username = Some("user".into());
+        ctx.password = Some("pass".into());
+
+        cascade.approve(&ctx)?;
+        cascade.reject(&ctx)?;
+        for log in [first, second] {
+            assert_eq!(
+                std::fs::read_to_string(log)?,
+                "store\nprotocol=https\nhost=github.com\nusername=user\npassword=pass\n\n\
+                 erase\nprotocol=https\nhost=github.com\nusername=user\npassword=pass\n\n",
+                "failing helpers don't prevent others from being called"
+            );
+        }
+        Ok(())
+    }
+}
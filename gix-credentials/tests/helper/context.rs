@@ -12,6 +12,20 @@ fn encode_decode_roundtrip_works_only_for_serializing_fields() {
             url: Some("https://github.com/byron/gitoxide".into()),
             ..Default::default()
         },
+        Context {
+            host: Some("example.com".into()),
+            www_authenticate: vec!["Bearer realm=\"example\"".into(), "Basic realm=\"x\"".into()],
+            ..Default::default()
+        },
+        Context {
+            protocol: Some("https".into()),
+            extra: vec![
+                ("authtype".into(), "Bearer".into()),
+                ("credential".into(), "token".into()),
+                ("authtype".into(), "Basic".into()),
+            ],
+            ..Default::default()
+        },
         Context::default(),
     ] {
         let mut buf = Vec::<u8>::new();
@@ -56,6 +70,79 @@ mod write_to {
             assert_eq!(err.kind(), std::io::ErrorKind::Other);
         }
     }
+
+    #[test]
+    fn null_bytes_and_newlines_are_invalid_in_extra_keys_and_values() {
+        for (key, value) in [
+            ("key\0", "value"),
+            ("key", "value\n"),
+            ("key\n", "value"),
+            ("key", "value\0"),
+        ] {
+            let ctx = Context {
+                extra: vec![(key.into(), value.into())],
+                ..Default::default()
+            };
+            let mut buf = Vec::<u8>::new();
+            let err = ctx.write_to(&mut buf).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::Other);
+        }
+    }
+
+    #[test]
+    fn www_authenticate_values_are_written_in_order_and_must_not_be_empty() {
+        let mut ctx = Context {
+            www_authenticate: vec!["Negotiate".into(), "Basic".into()],
+            extra: vec![("authtype".into(), "Bearer".into())],
+            ..Default::default()
+        };
+        assert_eq!(
+            ctx.to_bstring(),
+            "wwwauth[]=Negotiate\nwwwauth[]=Basic\nauthtype=Bearer\n"
+        );
+
+        ctx.www_authenticate.push("".into());
+        let err = ctx.write_to(Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn extra_keys_must_not_be_empty_or_contain_the_separator() {
+        for key in ["", "key=value", "="] {
+            let ctx = Context {
+                extra: vec![(key.into(), "value".into())],
+                ..Default::default()
+            };
+            let err = ctx.write_to(Vec::new()).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::Other, "{key:?}");
+        }
+    }
+
+    #[test]
+    fn extra_keys_colliding_with_known_keys_are_skipped() {
+        let ctx = Context {
+            host: Some("example.com".into()),
+            extra: vec![
+                ("host".into(), "other.example.com".into()),
+                ("password".into(), "secr3t".into()),
+                ("wwwauth[]".into(), "Basic".into()),
+                ("quit".into(), "1".into()),
+                ("authtype".into(), "Bearer".into()),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(ctx.to_bstring(), "host=example.com\nauthtype=Bearer\n");
+    }
+
+    #[test]
+    fn extra_keys_are_written_after_known_keys() {
+        let ctx = Context {
+            host: Some("example.com".into()),
+            extra: vec![("b".into(), "1".into()), ("a".into(), "2".into())],
+            ..Default::default()
+        };
+        assert_eq!(ctx.to_bstring(), "host=example.com\nb=1\na=2\n");
+    }
 }
 
 mod from_bytes {
@@ -78,15 +165,17 @@ username=bob";
     }
 
     #[test]
-    fn unknown_field_names_are_skipped() {
+    fn unknown_field_names_are_kept_in_order() {
         let input = b"protocol=https
 unknown=value
-username=bob";
+username=bob
+authtype=Bearer";
         assert_eq!(
             Context::from_bytes(input).unwrap(),
             Context {
                 protocol: Some("https".into()),
                 username: Some("bob".into()),
+                extra: vec![("unknown".into(), "value".into()), ("authtype".into(), "Bearer".into())],
                 ..Default::default()
             }
         )
@@ -112,6 +201,32 @@ username=bob";
         }
     }
 
+    #[test]
+    fn www_authenticate_values_are_collected_in_order() {
+        let input = b"wwwauth[]=Bearer
+host=example.com
+wwwauth[]=Basic realm=\"x\"";
+        assert_eq!(
+            Context::from_bytes(input).unwrap(),
+            Context {
+                host: Some("example.com".into()),
+                www_authenticate: vec!["Bearer".into(), "Basic realm=\"x\"".into()],
+                ..Default::default()
+            }
+        )
+    }
+
+    #[test]
+    fn empty_www_authenticate_values_are_rejected() {
+        let err = Context::from_bytes(b"wwwauth[]=").unwrap_err();
+        assert!(matches!(
+            err,
+            gix_credentials::protocol::context::decode::Error::Encoding(
+                gix_credentials::protocol::context::Error::EmptyValue { .. }
+            )
+        ));
+    }
+
     #[test]
     fn null_bytes_when_decoding() {
         let err = Context::from_bytes(b"url=https://foo\0").unwrap_err();
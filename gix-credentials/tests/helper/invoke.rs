@@ -124,6 +124,90 @@ mod program {
     }
 }
 
+#[cfg(feature = "async-client")]
+mod invoke_async {
+    use std::time::Duration;
+
+    use futures_lite::future::block_on;
+    use gix_credentials::{helper, protocol::Context};
+
+    use crate::helper::invoke::script_helper;
+
+    #[test]
+    fn get() {
+        let mut outcome = block_on(gix_credentials::helper::invoke_async(
+            &script_helper("last-pass"),
+            &helper::Action::get_for_url("https://github.com/byron/gitoxide"),
+            None,
+        ))
+        .unwrap()
+        .expect("mock provides credentials");
+        assert_eq!(
+            outcome.consume_identity().expect("complete"),
+            gix_sec::identity::Account {
+                username: "user".into(),
+                password: "pass".into()
+            }
+        );
+        assert_eq!(
+            outcome.next.store().payload().unwrap(),
+            "username=user\npassword=pass\nquit=1\n"
+        );
+    }
+
+    #[test]
+    fn store_has_no_outcome() {
+        let action = helper::Action::Store("url=https://github.com/byron/gitoxide".into());
+        let outcome = block_on(gix_credentials::helper::invoke_async(
+            &script_helper("last-pass"),
+            &action,
+            None,
+        ))
+        .unwrap();
+        assert!(outcome.is_none());
+    }
+
+    #[test]
+    fn large_output_before_reading_large_input_does_not_deadlock() {
+        let action = helper::Action::Get(Context {
+            path: Some(vec![b'p'; 1024 * 1024].into()),
+            ..Default::default()
+        });
+        let outcome = block_on(gix_credentials::helper::invoke_async(
+            &script_helper("large-output-before-input"),
+            &action,
+            Some(Duration::from_secs(60)),
+        ))
+        .unwrap()
+        .expect("present");
+        assert_eq!(outcome.username.as_deref(), Some("user"));
+        assert_eq!(outcome.password.map(|pw| pw.len()), Some(1024 * 1024));
+    }
+
+    #[test]
+    fn non_zero_exit_code_is_a_helper_failure() {
+        let err = block_on(gix_credentials::helper::invoke_async(
+            &script_helper("fail"),
+            &helper::Action::get_for_url("https://github.com/byron/gitoxide"),
+            None,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, helper::Error::CredentialsHelperFailed { .. }));
+    }
+
+    #[test]
+    fn timeouts_are_reported() {
+        let timeout = Duration::from_millis(100);
+        let err = block_on(gix_credentials::helper::invoke_async(
+            &script_helper("sleep"),
+            &helper::Action::get_for_url("https://github.com/byron/gitoxide"),
+            Some(timeout),
+        ))
+        .unwrap_err();
+        assert!(matches!(err, helper::Error::Timeout { timeout: actual } if actual == timeout));
+    }
+}
+
 pub fn script_helper(name: &str) -> Program {
     let mut script = gix_path::to_unix_separators_on_windows(gix_path::into_bstr(
         gix_path::realpath(fixture_path(format!("{name}.sh"))).unwrap(),
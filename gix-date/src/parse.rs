@@ -11,6 +11,9 @@ pub enum Error {
     MissingCurrentTime,
 }
 
+mod approxidate;
+pub use approxidate::{approxidate, approxidate_relative_to};
+
 pub(crate) mod function {
     use std::{str::FromStr, time::SystemTime};
 
@@ -93,7 +96,10 @@ mod relative {
     use crate::parse::Error;
 
     fn parse_inner(input: &str) -> Option<Duration> {
-        let mut split = input.split_whitespace();
+        // Like `git`, allow dots as separators as well to support `2.weeks.ago`.
+        let mut split = input
+            .split(|c: char| c.is_whitespace() || c == '.')
+            .filter(|token| !token.is_empty());
         let multiplier = i64::from_str(split.next()?).ok()?;
         let period = split.next()?;
         if split.next()? != "ago" {
@@ -139,5 +145,11 @@ mod relative {
         fn two_weeks_ago() {
             assert_eq!(parse_inner("2 weeks ago"), Some(Duration::weeks(2)));
         }
+
+        #[test]
+        fn dots_separate_like_whitespace() {
+            assert_eq!(parse_inner("2.days.ago"), Some(Duration::days(2)));
+            assert_eq!(parse_inner("1.week ago"), Some(Duration::weeks(1)));
+        }
     }
 }
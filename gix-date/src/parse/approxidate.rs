@@ -0,0 +1,412 @@
+use std::time::SystemTime;
+
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+use crate::{SecondsSinceUnixEpoch, Time};
+
+const MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+/// Ordered like `tm_wday`, i.e. starting on Sunday, and plural to also match `2 fridays ago`.
+const WEEKDAY_NAMES: [&str; 7] = [
+    "sundays",
+    "mondays",
+    "tuesdays",
+    "wednesdays",
+    "thursdays",
+    "fridays",
+    "saturdays",
+];
+const NUMBER_NAMES: [&str; 11] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+];
+const DAY: i64 = 24 * 60 * 60;
+const PERIODS: [(&str, i64); 5] = [
+    ("seconds", 1),
+    ("minutes", 60),
+    ("hours", 60 * 60),
+    ("days", DAY),
+    ("weeks", 7 * DAY),
+];
+
+/// Parse `input` as fuzzy date like `yesterday`, `2 weeks ago`, `last monday`, `noon` or `Jan 3` relative to `now`,
+/// the way `git` does it in its `approxidate` function, and interpret it in the local timezone.
+///
+/// Return `None` if nothing in `input` could be understood. If the local timezone can't be determined, UTC is used.
+/// See [`approxidate_relative_to()`] for details.
+pub fn approxidate(input: &str, now: SystemTime) -> Option<Time> {
+    let now = OffsetDateTime::from(now);
+    let offset = UtcOffset::local_offset_at(now).unwrap_or(UtcOffset::UTC);
+    approxidate_relative_to(input, Time::new(now.unix_timestamp(), offset.whole_seconds()))
+}
+
+/// Parse `input` as fuzzy date relative to `now` like [`approxidate()`], but interpret it in the timezone of `now`.
+///
+/// All words and numbers that aren't understood are ignored, just like `git` does, so `2 weeks ago` is the same as
+/// `2 weeks`. Only if nothing was understood at all, `None` is returned. The following is supported:
+///
+/// * `N <period> ago`, where `<period>` is one of `seconds`, `minutes`, `hours`, `days`, `weeks`, `months` or `years`,
+///   or their singular forms, and `N` is a number or a number word from `one` to `ten`. Dots may separate words,
+///   as in `2.weeks.ago`.
+/// * `yesterday`, `now`, `never`, and the times of the day `midnight`, `noon` and `tea`, with the latter two being
+///   on the previous day if it isn't yet time.
+/// * `last <weekday>` and `N <weekday>`, which refer to the last occurrence of the weekday, or the one before that,
+///   as well as `3pm` or `10am`.
+/// * Absolute fragments like `Jan 3`, `3 December 2005`, `2005-12-03`, `12/03/2005`, `03.12.2005` or `15:30:00`, with
+///   all missing fields taken from `now`. Dates without year that would be in the future are placed in the previous year.
+///
+/// ### Deviation
+///
+/// * Like `git`, `months` and `years` are calendar months and years, so `1 month ago` on March 31st is February 31st,
+///   which normalizes to the beginning of March, whereas all other periods are a fixed amount of seconds.
+///   Hence `1 day ago` is 24 hours ago even across daylight saving time changes.
+/// * `git` converts back and forth using the local timezone with its daylight saving time rules, whereas we use
+///   the fixed offset of `now`, which is also the offset of the returned time.
+pub fn approxidate_relative_to(input: &str, now: Time) -> Option<Time> {
+    let offset = UtcOffset::from_whole_seconds(now.offset).ok()?;
+    let now = OffsetDateTime::from_unix_timestamp(now.seconds).ok()?.to_offset(offset);
+    let mut state = State::new(now);
+    let mut touched = false;
+    let date = input.as_bytes();
+    let mut pos = 0;
+    while let Some(&c) = date.get(pos) {
+        if c.is_ascii_digit() {
+            state.pending_number();
+            pos += state.digit(&date[pos..])?;
+            touched = true;
+        } else if c.is_ascii_alphabetic() {
+            let word_len = date[pos..].iter().take_while(|b| b.is_ascii_alphabetic()).count();
+            touched |= state.alpha(&date[pos..])?;
+            pos += word_len;
+        } else {
+            pos += 1;
+        }
+    }
+    state.pending_number();
+    if !touched {
+        return None;
+    }
+    let seconds = state.update(0)?;
+    Some(Time::new(seconds, offset.whole_seconds()))
+}
+
+/// The equivalent of `struct tm` in `git`, with unset date fields being `None`.
+struct State {
+    now: OffsetDateTime,
+    year: Option<i32>,
+    /// Zero-based, like `tm_mon`.
+    month: Option<u8>,
+    day: Option<u8>,
+    hour: u8,
+    minute: u8,
+    second: u8,
+    /// Zero-based starting on Sunday, like `tm_wday`.
+    weekday: u8,
+    /// A number that was seen, but isn't yet used.
+    number: i64,
+}
+
+impl State {
+    fn new(now: OffsetDateTime) -> Self {
+        State {
+            now,
+            year: None,
+            month: None,
+            day: None,
+            hour: now.hour(),
+            minute: now.minute(),
+            second: now.second(),
+            weekday: now.weekday().number_days_from_sunday(),
+            number: 0,
+        }
+    }
+
+    /// Fill in all missing date fields from `now` and move back by `seconds`, returning the resulting timestamp.
+    fn update(&mut self, seconds: i64) -> Option<SecondsSinceUnixEpoch> {
+        let now_month = u8::from(self.now.month()) - 1;
+        let day = *self.day.get_or_insert(self.now.day());
+        let month = *self.month.get_or_insert(now_month);
+        let year = *self
+            .year
+            .get_or_insert_with(|| self.now.year() - i32::from(month > now_month));
+        let date = Date::from_calendar_date(year, Month::try_from(month + 1).ok()?, 1)
+            .ok()?
+            // Like `mktime()`, allow the day to overflow into the next month.
+            .checked_add(Duration::days(i64::from(day) - 1))?;
+        let time = time::Time::from_hms(self.hour, self.minute, self.second).ok()?;
+        let timestamp = PrimitiveDateTime::new(date, time)
+            .assume_offset(self.now.offset())
+            .unix_timestamp()
+            .checked_sub(seconds)?;
+        self.set(timestamp)?;
+        Some(timestamp)
+    }
+
+    /// Set all fields to the broken-down `timestamp`.
+    fn set(&mut self, timestamp: SecondsSinceUnixEpoch) -> Option<()> {
+        let time = OffsetDateTime::from_unix_timestamp(timestamp)
+            .ok()?
+            .to_offset(self.now.offset());
+        self.year = Some(time.year());
+        self.month = Some(u8::from(time.month()) - 1);
+        self.day = Some(time.day());
+        self.hour = time.hour();
+        self.minute = time.minute();
+        self.second = time.second();
+        self.weekday = time.weekday().number_days_from_sunday();
+        Some(())
+    }
+
+    /// Use the previously seen number as day, month or year, in that order, whichever isn't set yet.
+    fn pending_number(&mut self) {
+        let number = std::mem::take(&mut self.number);
+        if number == 0 {
+            return;
+        }
+        if self.day.is_none() && number < 32 {
+            self.day = Some(number as u8);
+        } else if self.month.is_none() && number < 13 {
+            self.month = Some(number as u8 - 1);
+        } else if self.year.is_none() {
+            self.year = match number {
+                1970..=2099 => Some(number as i32),
+                70..=99 => Some(1900 + number as i32),
+                0..=37 => Some(2000 + number as i32),
+                _ => None,
+            };
+        }
+    }
+
+    /// Handle the word at the beginning of `date`, and return `Some(true)` if it was understood.
+    fn alpha(&mut self, date: &[u8]) -> Option<bool> {
+        if let Some(month) = MONTH_NAMES.iter().position(|month| match_string(date, month) >= 3) {
+            self.month = Some(month as u8);
+            return Some(true);
+        }
+
+        let is = |word: &str| match_string(date, word) == word.len();
+        if is("yesterday") {
+            self.pending_number();
+            self.update(DAY)?;
+            return Some(true);
+        } else if is("noon") {
+            self.pending_number();
+            self.time_of_day(12)?;
+            return Some(true);
+        } else if is("midnight") {
+            self.pending_number();
+            self.time_of_day(0)?;
+            return Some(true);
+        } else if is("tea") {
+            self.pending_number();
+            self.time_of_day(17)?;
+            return Some(true);
+        } else if is("pm") || is("am") {
+            let hour = if self.number != 0 {
+                self.minute = 0;
+                self.second = 0;
+                std::mem::take(&mut self.number).rem_euclid(12) as u8
+            } else {
+                self.hour % 12
+            };
+            self.hour = if is("pm") { hour + 12 } else { hour };
+            return Some(true);
+        } else if is("never") {
+            self.number = 0;
+            self.set(0)?;
+            return Some(true);
+        } else if is("now") {
+            self.number = 0;
+            self.set(self.now.unix_timestamp())?;
+            return Some(true);
+        }
+
+        if self.number == 0 {
+            if let Some(number) = NUMBER_NAMES.iter().skip(1).position(|name| is(name)) {
+                self.number = number as i64 + 1;
+                return Some(true);
+            }
+            if is("last") {
+                self.number = 1;
+                return Some(true);
+            }
+            return Some(false);
+        }
+
+        if let Some((_, seconds)) = PERIODS
+            .iter()
+            .find(|(period, _)| match_string(date, period) >= period.len() - 1)
+        {
+            let seconds = seconds.checked_mul(std::mem::take(&mut self.number))?;
+            self.update(seconds)?;
+            return Some(true);
+        }
+
+        if let Some(weekday) = WEEKDAY_NAMES.iter().position(|day| match_string(date, day) >= 3) {
+            let mut n = std::mem::take(&mut self.number) - 1;
+            let mut diff = i64::from(self.weekday) - weekday as i64;
+            if diff <= 0 {
+                n += 1;
+            }
+            diff = diff.checked_add(n.checked_mul(7)?)?;
+            self.update(diff.checked_mul(DAY)?)?;
+            return Some(true);
+        }
+
+        if match_string(date, "months") >= 5 {
+            self.update(0)?;
+            let months = i64::from(self.month?) - std::mem::take(&mut self.number);
+            let years_back = i32::try_from(months.div_euclid(12)).ok()?;
+            self.year = Some(self.year?.checked_add(years_back)?);
+            self.month = Some(months.rem_euclid(12) as u8);
+            return Some(true);
+        }
+
+        if match_string(date, "years") >= 4 {
+            self.update(0)?;
+            let years = i32::try_from(std::mem::take(&mut self.number)).ok()?;
+            self.year = Some(self.year?.checked_sub(years)?);
+            return Some(true);
+        }
+        Some(false)
+    }
+
+    /// Set the time to `hour` o'clock, on the previous day if it's not yet that late.
+    fn time_of_day(&mut self, hour: u8) -> Option<()> {
+        if self.hour < hour {
+            self.update(DAY)?;
+        }
+        self.hour = hour;
+        self.minute = 0;
+        self.second = 0;
+        Some(())
+    }
+
+    /// Handle the number at the beginning of `date`, possibly followed by more numbers to form a date or time,
+    /// and return the amount of bytes we consumed.
+    fn digit(&mut self, date: &[u8]) -> Option<usize> {
+        let (number, end) = parse_number(date);
+        if let Some(&separator @ (b':' | b'.' | b'/' | b'-')) = date.get(end) {
+            if is_digit_at(date, end + 1) {
+                if let Some(consumed) = self.multi_number(number, separator, date, end) {
+                    return Some(consumed);
+                }
+            }
+        }
+
+        // Accept zero-padding only for small numbers, like in `Dec 02`, but never `Dec 0002`.
+        if date[0] != b'0' || end <= 2 {
+            self.number = number;
+        }
+        Some(end)
+    }
+
+    /// Parse times like `12:30:00` and dates like `2005-04-07` or `04/07/2005`, to return the amount of consumed bytes,
+    /// or `None` if these weren't valid.
+    fn multi_number(&mut self, number: i64, separator: u8, date: &[u8], mut end: usize) -> Option<usize> {
+        let (number2, len) = parse_number(&date[end + 1..]);
+        end += 1 + len;
+        let mut number3 = -1;
+        if date.get(end) == Some(&separator) && is_digit_at(date, end + 1) {
+            let (number, len) = parse_number(&date[end + 1..]);
+            number3 = number;
+            end += 1 + len;
+        }
+
+        if separator == b':' {
+            number3 = number3.max(0);
+            if number < 25 && (0..60).contains(&number2) && (0..=60).contains(&number3) {
+                self.hour = number as u8;
+                self.minute = number2 as u8;
+                self.second = number3.min(59) as u8;
+                return Some(end);
+            }
+            return None;
+        }
+
+        let found = (number > 70
+            && (self.set_date(number, number2, number3, false) || self.set_date(number, number3, number2, false)))
+            // Prefer `mm/dd/yy[yy]` only when the separator isn't a `.`, as `dd.mm.yy[yy]` is common in Europe.
+            || (separator != b'.' && self.set_date(number3, number, number2, true))
+            || self.set_date(number3, number2, number, true)
+            || (separator == b'.' && self.set_date(number3, number, number2, true));
+        found.then_some(end)
+    }
+
+    /// Set the date if it's valid and return `true`. If `refuse_future` is set, `year` may be `-1` to use the current one,
+    /// and dates more than ten days in the future are rejected.
+    fn set_date(&mut self, year: i64, month: i64, day: i64, refuse_future: bool) -> bool {
+        if !(1..13).contains(&month) || !(1..32).contains(&day) {
+            return false;
+        }
+        let specified_year = match year {
+            -1 if refuse_future => None,
+            -1 => return false,
+            1970..=2099 => Some(year as i32),
+            71..=99 => Some(1900 + year as i32),
+            0..=37 => Some(2000 + year as i32),
+            _ => return false,
+        };
+        let year = specified_year.unwrap_or_else(|| self.now.to_offset(UtcOffset::UTC).year());
+        if refuse_future {
+            let specified = Date::from_calendar_date(year, Month::try_from(month as u8).expect("valid"), 1)
+                .ok()
+                .and_then(|date| date.checked_add(Duration::days(day - 1)))
+                .and_then(|date| {
+                    date.with_hms(self.hour, self.minute, self.second)
+                        .ok()
+                        .map(|time| time.assume_utc().unix_timestamp())
+                });
+            if matches!(specified, Some(specified) if self.now.unix_timestamp() + 10 * DAY < specified) {
+                return false;
+            }
+        }
+        self.month = Some(month as u8 - 1);
+        self.day = Some(day as u8);
+        if specified_year.is_some() {
+            self.year = specified_year;
+        }
+        true
+    }
+}
+
+/// Return the amount of characters the word at the beginning of `date` has in common with `word`,
+/// or 0 if it is longer than `word` or differs from it.
+fn match_string(date: &[u8], word: &str) -> usize {
+    let mut matched = 0;
+    for (&c, expected) in date.iter().zip(word.bytes().map(Some).chain(std::iter::repeat(None))) {
+        if Some(c.to_ascii_lowercase()) == expected {
+            matched += 1;
+            continue;
+        }
+        if !c.is_ascii_alphanumeric() {
+            break;
+        }
+        return 0;
+    }
+    matched
+}
+
+/// Parse the decimal number at the beginning of `date`, saturating on overflow, and return it along with its length.
+fn parse_number(date: &[u8]) -> (i64, usize) {
+    let len = date.iter().take_while(|b| b.is_ascii_digit()).count();
+    let number = date[..len].iter().fold(0_i64, |number, digit| {
+        number.saturating_mul(10).saturating_add(i64::from(digit - b'0'))
+    });
+    (number, len)
+}
+
+fn is_digit_at(date: &[u8], pos: usize) -> bool {
+    matches!(date.get(pos), Some(b) if b.is_ascii_digit())
+}
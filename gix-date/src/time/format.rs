@@ -61,12 +61,48 @@ mod format_impls {
     }
 }
 
+/// Initialization
+impl<'a> Format<'a> {
+    /// Return the format `git` uses for `name` as passed to `--date=<name>` or configured in `log.date`, or `None` if the
+    /// name isn't known. `now` is used for the `relative` format.
+    ///
+    /// Supported are `default`, `relative`, `short`, `iso` or `iso8601`, `iso-strict` or `iso8601-strict`, `rfc` or `rfc2822`,
+    /// `unix`, `raw` and `format:<strftime>`.
+    ///
+    /// ### Deviation
+    ///
+    /// Variants with the `-local` suffix, `format-local:<strftime>`, `local` and `human` aren't supported as
+    /// they require a conversion into the local timezone, whereas we always use the timezone stored with the time.
+    pub fn from_git_name(name: &'a str, now: std::time::SystemTime) -> Option<Self> {
+        if let Some(pattern) = name.strip_prefix("format:") {
+            return Some(Format::Strftime(pattern));
+        }
+        Some(match name {
+            "default" => DEFAULT.into(),
+            "relative" => Format::Relative { now },
+            "short" => SHORT.into(),
+            "iso" | "iso8601" => ISO8601.into(),
+            "iso-strict" | "iso8601-strict" => ISO8601_STRICT.into(),
+            "rfc" | "rfc2822" => GIT_RFC2822.into(),
+            "unix" => Format::Unix,
+            "raw" => Format::Raw,
+            _ => return None,
+        })
+    }
+}
+
 /// Formatting
 impl Time {
-    /// Format this instance according to the given `format`.
+    /// Format this instance according to the given `format`, in the timezone stored with it.
     ///
     /// Use the [`format_description`](https://time-rs.github.io/book/api/format-description.html) macro to create and
     /// validate formats at compile time, courtesy of the [`time`] crate.
+    ///
+    /// [`Format::Strftime`] patterns support the conversion specifications `%a`, `%A`, `%b`, `%h`, `%B`, `%c`, `%C`, `%d`, `%D`,
+    /// `%e`, `%F`, `%H`, `%I`, `%j`, `%k`, `%l`, `%m`, `%M`, `%n`, `%p`, `%P`, `%r`, `%R`, `%s`, `%S`, `%t`, `%T`, `%u`, `%w`,
+    /// `%x`, `%X`, `%y`, `%Y`, `%z`, `%Z` and `%%`, with the names and representations of the `C` locale.
+    /// Like in `git`, `%z` is the stored offset, `%Z` is empty as the name of the timezone isn't known, and `%s` is the
+    /// amount of seconds since UNIX epoch. Unknown conversion specifications are copied verbatim.
     pub fn format<'a>(&self, format: impl Into<Format<'a>>) -> String {
         self.format_inner(format.into())
     }
@@ -79,7 +115,167 @@ impl Time {
                 .expect("well-known format into memory never fails"),
             Format::Unix => self.seconds.to_string(),
             Format::Raw => self.to_bstring().to_string(),
+            Format::Relative { now } => relative::format(self.seconds, now),
+            Format::Strftime(pattern) => strftime::format(self, self.to_time(), pattern),
+        }
+    }
+}
+
+mod relative {
+    use std::time::SystemTime;
+
+    use crate::SecondsSinceUnixEpoch;
+
+    /// Phrase the distance between `seconds` and `now` exactly like `git` does in `show_date_relative()`.
+    pub(super) fn format(seconds: SecondsSinceUnixEpoch, now: SystemTime) -> String {
+        let now = match now.duration_since(SystemTime::UNIX_EPOCH) {
+            Ok(now) => i64::try_from(now.as_secs()).unwrap_or(i64::MAX),
+            Err(before_epoch) => -i64::try_from(before_epoch.duration().as_secs()).unwrap_or(i64::MAX),
+        };
+        if now < seconds {
+            return "in the future".into();
+        }
+        let diff = now.saturating_sub(seconds);
+        let ago = |amount: i64, unit: &str| format!("{amount} {unit}{} ago", if amount == 1 { "" } else { "s" });
+        if diff < 90 {
+            return ago(diff, "second");
+        }
+        let diff = (diff + 30) / 60;
+        if diff < 90 {
+            return ago(diff, "minute");
+        }
+        let diff = (diff + 30) / 60;
+        if diff < 36 {
+            return ago(diff, "hour");
+        }
+        let days = (diff + 12) / 24;
+        if days < 14 {
+            ago(days, "day")
+        } else if days < 70 {
+            ago((days + 3) / 7, "week")
+        } else if days < 365 {
+            ago((days + 15) / 30, "month")
+        } else if days < 1825 {
+            let total_months = (days * 12 * 2 + 365) / (365 * 2);
+            let (years, months) = (total_months / 12, total_months % 12);
+            if months == 0 {
+                ago(years, "year")
+            } else {
+                format!(
+                    "{years} year{}, {}",
+                    if years == 1 { "" } else { "s" },
+                    ago(months, "month")
+                )
+            }
+        } else {
+            ago((days + 183) / 365, "year")
+        }
+    }
+}
+
+mod strftime {
+    use std::fmt::Write;
+
+    use time::OffsetDateTime;
+
+    use crate::{time::Sign, Time};
+
+    const WEEKDAYS: [&str; 7] = [
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ];
+    const MONTHS: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+
+    pub(super) fn format(time: &Time, t: OffsetDateTime, pattern: &str) -> String {
+        let mut out = String::with_capacity(pattern.len() * 2);
+        write(&mut out, time, t, pattern).expect("writing to a string never fails");
+        out
+    }
+
+    fn write(out: &mut String, time: &Time, t: OffsetDateTime, pattern: &str) -> std::fmt::Result {
+        let weekday = WEEKDAYS[t.weekday().number_days_from_monday() as usize];
+        let month = MONTHS[usize::from(u8::from(t.month())) - 1];
+        let hour12 = match t.hour() % 12 {
+            0 => 12,
+            hour => hour,
+        };
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            let Some(spec) = chars.next() else {
+                out.push('%');
+                break;
+            };
+            match spec {
+                'a' => out.push_str(&weekday[..3]),
+                'A' => out.push_str(weekday),
+                'b' | 'h' => out.push_str(&month[..3]),
+                'B' => out.push_str(month),
+                'c' => write(out, time, t, "%a %b %e %H:%M:%S %Y")?,
+                'C' => write!(out, "{:02}", t.year().div_euclid(100))?,
+                'd' => write!(out, "{:02}", t.day())?,
+                'D' | 'x' => write(out, time, t, "%m/%d/%y")?,
+                'e' => write!(out, "{:2}", t.day())?,
+                'F' => write(out, time, t, "%Y-%m-%d")?,
+                'H' => write!(out, "{:02}", t.hour())?,
+                'I' => write!(out, "{hour12:02}")?,
+                'j' => write!(out, "{:03}", t.ordinal())?,
+                'k' => write!(out, "{:2}", t.hour())?,
+                'l' => write!(out, "{hour12:2}")?,
+                'm' => write!(out, "{:02}", u8::from(t.month()))?,
+                'M' => write!(out, "{:02}", t.minute())?,
+                'n' => out.push('\n'),
+                'p' => out.push_str(if t.hour() < 12 { "AM" } else { "PM" }),
+                'P' => out.push_str(if t.hour() < 12 { "am" } else { "pm" }),
+                'r' => write(out, time, t, "%I:%M:%S %p")?,
+                'R' => write(out, time, t, "%H:%M")?,
+                's' => write!(out, "{}", time.seconds)?,
+                'S' => write!(out, "{:02}", t.second())?,
+                't' => out.push('\t'),
+                'T' | 'X' => write(out, time, t, "%H:%M:%S")?,
+                'u' => write!(out, "{}", t.weekday().number_from_monday())?,
+                'w' => write!(out, "{}", t.weekday().number_days_from_sunday())?,
+                'y' => write!(out, "{:02}", t.year().rem_euclid(100))?,
+                'Y' => write!(out, "{}", t.year())?,
+                'z' => {
+                    let offset = time.offset.unsigned_abs() / 60;
+                    let sign = match time.sign {
+                        Sign::Plus => '+',
+                        Sign::Minus => '-',
+                    };
+                    write!(out, "{sign}{:02}{:02}", offset / 60, offset % 60)?;
+                }
+                // Like `git`, we don't know the name of the timezone.
+                'Z' => {}
+                '%' => out.push('%'),
+                unknown => {
+                    out.push('%');
+                    out.push(unknown);
+                }
+            }
         }
+        Ok(())
     }
 }
 
@@ -26,6 +26,15 @@ pub enum Format<'a> {
     Unix,
     /// The seconds since 1970, followed by the offset, like `1660874655 +0800`
     Raw,
+    /// The time relative to `now`, like `3 days ago` or `2 years, 4 months ago`, phrased like `git log --date=relative`.
+    Relative {
+        /// The time to compare to, typically [`SystemTime::now()`](std::time::SystemTime::now()).
+        now: std::time::SystemTime,
+    },
+    /// A `strftime` pattern like `%Y-%m-%d %H:%M:%S %z`, like `git log --date=format:<pattern>`.
+    ///
+    /// See [`Time::format()`] for the supported conversion specifications.
+    Strftime(&'a str),
 }
 
 ///
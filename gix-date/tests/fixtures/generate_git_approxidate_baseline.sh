@@ -0,0 +1,70 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init;
+
+# Thu Apr 7 15:13:13 2005 -0700, which is what all relative dates are relative to.
+export GIT_TEST_DATE_NOW=1112911993
+# A timezone at -0700 without daylight saving time.
+export TZ=XYZ+07
+
+function baseline() {
+    local test_date=$1 # first argument is the date to test
+
+    git -c section.key="$test_date" config --type=expiry-date section.key && status=0 || status=$?
+    {
+        echo "$test_date"
+        echo "$status"
+        if [ $status == 0 ]
+        then
+          git -c section.key="$test_date" config --type=expiry-date section.key
+        else
+          echo "-1"
+        fi
+    } >> baseline.git
+}
+
+# relative
+baseline 'yesterday'
+baseline '1 second ago'
+baseline '2 weeks ago'
+baseline '2.weeks.ago'
+baseline 'three days ago'
+baseline '5 minutes'
+baseline '10 hours ago'
+baseline '1 month ago'
+baseline '14 months ago'
+baseline '2 years ago'
+baseline 'last monday'
+baseline 'last thursday'
+baseline 'last friday'
+baseline '2 fridays ago'
+
+# times of the day
+baseline 'noon'
+baseline 'midnight'
+baseline 'tea'
+baseline 'yesterday noon'
+baseline 'noon yesterday'
+baseline '3pm'
+baseline '10am yesterday'
+baseline '15:30'
+baseline '15:30:42'
+
+# absolute fragments
+baseline 'Jan 3'
+baseline 'Dec 24'
+baseline 'December 24 2003'
+baseline '3 march'
+baseline 'May 2 2004 10:15'
+baseline '2005-04-01'
+baseline '2003.12.24'
+baseline '04/01/2005'
+baseline '01.04.2005'
+baseline '04/01'
+baseline 'Feb 30'
+baseline 'never'
+
+# failure
+baseline 'foobar'
+baseline '-'
@@ -0,0 +1,76 @@
+use gix_date::{parse::approxidate_relative_to, SecondsSinceUnixEpoch, Time};
+use gix_testtools::Result;
+use once_cell::sync::Lazy;
+
+/// The time all baseline dates are relative to, as set with `GIT_TEST_DATE_NOW` and `TZ` in the fixture script.
+const NOW: Time = Time {
+    seconds: 1112911993,
+    offset: -7 * 60 * 60,
+    sign: gix_date::time::Sign::Minus,
+};
+
+static BASELINE: Lazy<Vec<(String, Option<SecondsSinceUnixEpoch>)>> = Lazy::new(|| {
+    (|| -> Result<_> {
+        let base = gix_testtools::scripted_fixture_read_only("generate_git_approxidate_baseline.sh")?;
+        let file = std::fs::read(base.join("baseline.git"))?;
+        let baseline = std::str::from_utf8(&file).expect("valid utf");
+        let mut lines = baseline.lines();
+        let mut out = Vec::new();
+        while let Some(date_str) = lines.next() {
+            let exit_code: usize = lines.next().expect("three lines per baseline").parse()?;
+            let seconds: SecondsSinceUnixEpoch = lines.next().expect("three lines per baseline").parse()?;
+            out.push((date_str.to_owned(), (exit_code == 0).then_some(seconds)));
+        }
+        Ok(out)
+    })()
+    .expect("baseline format is well known and can always be parsed")
+});
+
+#[test]
+fn baseline() {
+    for (input, expected) in BASELINE.iter() {
+        let actual = approxidate_relative_to(input, NOW);
+        assert_eq!(
+            actual.map(|t| t.seconds),
+            *expected,
+            "{input:?} disagrees with baseline"
+        );
+        if let Some(actual) = actual {
+            assert_eq!(actual.offset, NOW.offset, "the offset of `now` is retained");
+        }
+    }
+}
+
+#[test]
+fn nothing_understood_yields_none() {
+    for input in ["", "foobar", "ago", "-", "days", "😀"] {
+        assert_eq!(approxidate_relative_to(input, NOW), None, "{input:?}");
+    }
+}
+
+#[test]
+fn large_values_do_not_panic() {
+    for input in [
+        "99999999999999999999999 weeks ago",
+        "9999999999 weeks ago",
+        "99999999 years ago",
+        "99999999 months ago",
+        "9999999999:00",
+        "9999-99-99",
+    ] {
+        let _ = approxidate_relative_to(input, NOW);
+    }
+}
+
+#[test]
+fn local_time_is_the_default() {
+    let now = std::time::SystemTime::now();
+    let time = gix_date::parse::approxidate("yesterday", now).expect("valid");
+    let expected = now
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("after epoch")
+        .as_secs() as i64
+        - 24 * 60 * 60;
+    assert_eq!(time.seconds, expected);
+    assert_eq!(time.sign, time.offset.into());
+}
@@ -80,6 +80,72 @@ fn custom_compile_time() {
     );
 }
 
+#[test]
+fn relative() {
+    let time = Time::new(1000000000, 9000);
+    for (diff, expected) in [
+        (0, "0 seconds ago"),
+        (1, "1 second ago"),
+        (89, "89 seconds ago"),
+        (90, "2 minutes ago"),
+        (5399, "2 hours ago"),
+        (129599, "2 days ago"),
+        (1209599, "2 weeks ago"),
+        (6047999, "2 months ago"),
+        (31535999, "1 year ago"),
+        (40000000, "1 year, 3 months ago"),
+        (157679999, "5 years ago"),
+        (300000000, "10 years ago"),
+    ] {
+        let now = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1000000000 + diff);
+        assert_eq!(time.format(Format::Relative { now }), expected, "{diff}");
+    }
+    let now = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1000000000 - 5);
+    assert_eq!(time.format(Format::Relative { now }), "in the future");
+}
+
+#[test]
+fn strftime() {
+    let time = Time::new(1000000000, 9000);
+    assert_eq!(
+        time.format(Format::Strftime(
+            "%a %A %b %B %c|%C %d %D %e %F %H %I %j %k %l %m %M %p %r %R %s %S %T %u %w %x %X %y %Y %z|%Z|%% %q"
+        )),
+        "Sun Sunday Sep September Sun Sep  9 04:16:40 2001|20 09 09/09/01  9 2001-09-09 04 04 252  4  4 09 16 AM 04:16:40 AM 04:16 1000000000 40 04:16:40 7 0 09/09/01 04:16:40 01 2001 +0230||% %q",
+        "this is what `git log --date=format:<pattern>` produces, and the stored offset is used"
+    );
+    assert_eq!(
+        Time::new(1000000000, -9000).format(Format::Strftime("%H:%M %z")),
+        "23:16 -0230"
+    );
+    assert_eq!(time.format(Format::Strftime("trailing %")), "trailing %");
+}
+
+#[test]
+fn from_git_name() {
+    let now = std::time::SystemTime::now();
+    for (name, expected) in [
+        ("default", "Fri Nov 30 00:03:09 1973 +0230"),
+        ("short", "1973-11-30"),
+        ("iso", "1973-11-30 00:03:09 +0230"),
+        ("iso8601-strict", "1973-11-30T00:03:09+02:30"),
+        ("rfc", "Fri, 30 Nov 1973 00:03:09 +0230"),
+        ("unix", "123456789"),
+        ("raw", "123456789 +0230"),
+        ("format:%d.%m.%Y", "30.11.1973"),
+    ] {
+        let format = Format::from_git_name(name, now).expect("known");
+        assert_eq!(time().format(format), expected, "{name}");
+    }
+    assert!(matches!(
+        Format::from_git_name("relative", now),
+        Some(Format::Relative { .. })
+    ));
+    for unsupported in ["human", "local", "iso-local", "format-local:%Y", "unknown"] {
+        assert!(Format::from_git_name(unsupported, now).is_none(), "{unsupported}");
+    }
+}
+
 fn time() -> Time {
     Time {
         seconds: 123456789,
@@ -1,5 +1,6 @@
 use gix_date::Time;
 
+mod approxidate;
 mod baseline;
 mod format;
 mod parse;
@@ -0,0 +1,459 @@
+use std::io::Write;
+
+use bstr::{BString, ByteSlice};
+
+/// The kind of data stored in a [`Hunk`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Kind {
+    /// The hunk contains the entire content of the resulting blob, written as `literal <size>`.
+    Literal,
+    /// The hunk contains instructions in git's delta format to produce the resulting blob from the blob the hunk is
+    /// applied to, written as `delta <size>`.
+    Delta,
+}
+
+/// One direction of a binary [`Patch`], able to produce one blob from the other.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Hunk {
+    /// The kind of `data`.
+    pub kind: Kind,
+    /// The amount of bytes of the decompressed `data`, which is the size of the resulting blob for [`Kind::Literal`],
+    /// or the size of the delta instructions for [`Kind::Delta`].
+    pub size: u64,
+    /// The zlib compressed literal content or delta instructions.
+    pub data: Vec<u8>,
+}
+
+/// A binary patch in the format of `git diff --binary`, starting with the `GIT binary patch` line, which can be applied
+/// with `git apply --binary`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Patch {
+    /// The hunk to produce the new blob from the old one.
+    pub forward: Hunk,
+    /// The hunk to produce the old blob from the new one, which is always present unless the patch was produced by ancient
+    /// versions of git.
+    pub reverse: Option<Hunk>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod decode {
+    use bstr::BString;
+
+    /// The error returned by [`Patch::from_bytes()`](super::Patch::from_bytes()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("A binary patch must start with a 'GIT binary patch' line")]
+        MissingHeader,
+        #[error("Expected a 'literal <size>' or 'delta <size>' line, got '{line}'")]
+        InvalidHunkHeader { line: BString },
+        #[error("The line '{line}' of binary data is malformed")]
+        InvalidDataLine { line: BString },
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod apply {
+    /// The error returned by [`Hunk::apply()`](super::Hunk::apply()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not decompress the data of the hunk")]
+        Inflate(#[from] gix_features::zlib::inflate::Error),
+        #[error("The compressed data of the hunk ended unexpectedly")]
+        Truncated,
+        #[error("The decompressed data of the hunk was expected to have {expected} bytes, but got at least {actual}")]
+        SizeMismatch { expected: u64, actual: u64 },
+        #[error("The delta was made for a blob of {expected} bytes, but the blob to apply it to has {actual} bytes")]
+        DeltaSourceSize { expected: u64, actual: u64 },
+        #[error("The delta is supposed to produce a blob of {expected} bytes, but it produced {actual} bytes")]
+        DeltaTargetSize { expected: u64, actual: u64 },
+        #[error("The delta instructions are malformed")]
+        CorruptDelta,
+    }
+}
+
+/// The characters used for base85 encoding in the order of their value, as defined by git.
+const BASE85: &[u8; 85] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+/// The maximum amount of bytes encoded in a single line, like in git.
+const MAX_BYTES_PER_LINE: usize = 52;
+
+/// Lifecycle
+impl Patch {
+    /// Create a patch to turn the `old` blob into the `new` one and back, choosing a literal or a delta for each
+    /// direction depending on which of them is smaller, just like git does.
+    pub fn new(old: &[u8], new: &[u8]) -> Self {
+        Patch {
+            forward: Hunk::new(old, new),
+            reverse: Some(Hunk::new(new, old)),
+        }
+    }
+
+    /// Parse a binary patch from `input`, which must start with the `GIT binary patch` line, as written in the diff
+    /// of one file by `git diff --binary`.
+    ///
+    /// Everything after the patch is ignored.
+    pub fn from_bytes(input: &[u8]) -> Result<Self, decode::Error> {
+        let mut lines = input.lines();
+        if lines.next() != Some(b"GIT binary patch".as_slice()) {
+            return Err(decode::Error::MissingHeader);
+        }
+        let header = lines.next().unwrap_or_default();
+        let forward = parse_hunk_header(header)
+            .ok_or_else(|| decode::Error::InvalidHunkHeader { line: header.into() })
+            .and_then(|(kind, size)| parse_hunk(kind, size, &mut lines))?;
+        let reverse = lines
+            .next()
+            .and_then(parse_hunk_header)
+            .map(|(kind, size)| parse_hunk(kind, size, &mut lines))
+            .transpose()?;
+        Ok(Patch { forward, reverse })
+    }
+}
+
+/// Access
+impl Patch {
+    /// Write this patch to `out` with git's framing, so that it can be applied with `git apply --binary`
+    /// if preceded by a diff header with the full object ids of both blobs.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        out.write_all(b"GIT binary patch\n")?;
+        self.forward.write_to(out)?;
+        if let Some(reverse) = &self.reverse {
+            reverse.write_to(out)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lifecycle
+impl Hunk {
+    /// Create a hunk to obtain the `target` blob from the `source` blob, as a delta if that's smaller than
+    /// the compressed `target` itself.
+    pub fn new(source: &[u8], target: &[u8]) -> Self {
+        let literal = deflate(target);
+        if !source.is_empty() && !target.is_empty() {
+            if let Some(delta) = delta::create(source, target, literal.len()) {
+                let compressed = deflate(&delta);
+                if compressed.len() < literal.len() {
+                    return Hunk {
+                        kind: Kind::Delta,
+                        size: delta.len() as u64,
+                        data: compressed,
+                    };
+                }
+            }
+        }
+        Hunk {
+            kind: Kind::Literal,
+            size: target.len() as u64,
+            data: literal,
+        }
+    }
+}
+
+/// Access
+impl Hunk {
+    /// Apply this hunk to `source`, the blob it was created for, and return the resulting blob.
+    ///
+    /// Note that `source` is ignored for [literals](Kind::Literal).
+    pub fn apply(&self, source: &[u8]) -> Result<Vec<u8>, apply::Error> {
+        let data = inflate(&self.data, self.size)?;
+        match self.kind {
+            Kind::Literal => Ok(data),
+            Kind::Delta => delta::apply(source, &data),
+        }
+    }
+
+    /// Write this hunk to `out` with git's framing, which is terminated by an empty line.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let kind = match self.kind {
+            Kind::Literal => "literal",
+            Kind::Delta => "delta",
+        };
+        writeln!(out, "{kind} {}", self.size)?;
+        let mut line = Vec::with_capacity(1 + MAX_BYTES_PER_LINE / 4 * 5 + 1);
+        for chunk in self.data.chunks(MAX_BYTES_PER_LINE) {
+            line.clear();
+            line.push(match chunk.len() {
+                len @ 1..=26 => b'A' + len as u8 - 1,
+                len => b'a' + len as u8 - 27,
+            });
+            encode_base85(chunk, &mut line);
+            line.push(b'\n');
+            out.write_all(&line)?;
+        }
+        out.write_all(b"\n")
+    }
+}
+
+fn parse_hunk_header(line: &[u8]) -> Option<(Kind, u64)> {
+    let (kind, size) = if let Some(size) = line.strip_prefix(b"literal ") {
+        (Kind::Literal, size)
+    } else {
+        (Kind::Delta, line.strip_prefix(b"delta ")?)
+    };
+    Some((kind, size.to_str().ok()?.parse().ok()?))
+}
+
+/// Read all lines of data from `lines` up to the empty line that terminates the hunk, or up to the end of the input.
+fn parse_hunk<'a>(kind: Kind, size: u64, lines: &mut impl Iterator<Item = &'a [u8]>) -> Result<Hunk, decode::Error> {
+    let mut data = Vec::new();
+    for line in lines.take_while(|line| !line.is_empty()) {
+        let invalid = || decode::Error::InvalidDataLine {
+            line: BString::from(line),
+        };
+        let len = match line[0] {
+            b @ b'A'..=b'Z' => b - b'A' + 1,
+            b @ b'a'..=b'z' => b - b'a' + 27,
+            _ => return Err(invalid()),
+        } as usize;
+        let encoded = &line[1..];
+        if encoded.len() != (len + 3) / 4 * 5 {
+            return Err(invalid());
+        }
+        decode_base85(encoded, len, &mut data).ok_or_else(invalid)?;
+    }
+    Ok(Hunk { kind, size, data })
+}
+
+fn encode_base85(data: &[u8], out: &mut Vec<u8>) {
+    for group in data.chunks(4) {
+        let mut value = group
+            .iter()
+            .chain(std::iter::repeat(&0))
+            .take(4)
+            .fold(0u32, |acc, b| acc << 8 | u32::from(*b));
+        let mut encoded = [0u8; 5];
+        for c in encoded.iter_mut().rev() {
+            *c = BASE85[(value % 85) as usize];
+            value /= 85;
+        }
+        out.extend_from_slice(&encoded);
+    }
+}
+
+/// Decode `len` bytes from `encoded` into `out`, or return `None` if the encoding is invalid.
+fn decode_base85(encoded: &[u8], mut len: usize, out: &mut Vec<u8>) -> Option<()> {
+    for group in encoded.chunks(5) {
+        let mut value = 0u64;
+        for c in group {
+            let digit = BASE85.iter().position(|b| b == c)?;
+            value = value * 85 + digit as u64;
+        }
+        let value = u32::try_from(value).ok()?;
+        let num_bytes = len.min(4);
+        out.extend_from_slice(&value.to_be_bytes()[..num_bytes]);
+        len -= num_bytes;
+    }
+    Some(())
+}
+
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut out = gix_features::zlib::stream::deflate::Write::new(Vec::new());
+    out.write_all(data)
+        .and_then(|_| out.flush())
+        .expect("writing to memory can't fail");
+    out.into_inner()
+}
+
+/// Decompress `data` which is expected to yield exactly `size` bytes.
+fn inflate(data: &[u8], size: u64) -> Result<Vec<u8>, apply::Error> {
+    // Grow the output gradually as we can't trust `size` for allocation.
+    const CHUNK_SIZE: usize = 64 * 1024;
+    let mut inflate = gix_features::zlib::Inflate::default();
+    let mut out = Vec::new();
+    let (mut consumed, mut written) = (0, 0);
+    loop {
+        out.resize(written + CHUNK_SIZE, 0);
+        let (status, num_in, num_out) = inflate.once(&data[consumed..], &mut out[written..])?;
+        consumed += num_in;
+        written += num_out;
+        if written as u64 > size {
+            return Err(apply::Error::SizeMismatch {
+                expected: size,
+                actual: written as u64,
+            });
+        }
+        match status {
+            gix_features::zlib::Status::StreamEnd => break,
+            _ if num_in == 0 && num_out == 0 => return Err(apply::Error::Truncated),
+            _ => {}
+        }
+    }
+    if written as u64 != size {
+        return Err(apply::Error::SizeMismatch {
+            expected: size,
+            actual: written as u64,
+        });
+    }
+    out.truncate(written);
+    Ok(out)
+}
+
+/// An implementation of git's delta format, which is the same as the one used in packs.
+mod delta {
+    use std::collections::HashMap;
+
+    use super::apply::Error;
+
+    /// The size of the blocks of the source that are indexed to find copies.
+    const BLOCK_SIZE: usize = 16;
+    /// The maximum size of a single copy instruction, which git limits to remain compatible with older versions.
+    const MAX_COPY_SIZE: usize = 0x10000;
+    /// The maximum size of a single insert instruction.
+    const MAX_INSERT_SIZE: usize = 0x7f;
+
+    /// Create delta instructions to produce `target` from `source`, or `None` if they would be larger than `max_size`.
+    pub(super) fn create(source: &[u8], target: &[u8], max_size: usize) -> Option<Vec<u8>> {
+        let mut index = HashMap::<&[u8], usize>::new();
+        // Copy instructions can't use offsets that need more than 32 bits.
+        let indexed = &source[..source.len().min(u32::MAX as usize)];
+        for (block_idx, block) in indexed.chunks_exact(BLOCK_SIZE).enumerate() {
+            index.entry(block).or_insert(block_idx * BLOCK_SIZE);
+        }
+
+        let mut out = Vec::new();
+        encode_size(source.len(), &mut out);
+        encode_size(target.len(), &mut out);
+        let (mut pos, mut insert_start) = (0, 0);
+        while pos < target.len() {
+            let Some(mut source_pos) = target
+                .get(pos..pos + BLOCK_SIZE)
+                .and_then(|block| index.get(block))
+                .copied()
+            else {
+                pos += 1;
+                continue;
+            };
+            let mut start = pos;
+            while start > insert_start && source_pos > 0 && target[start - 1] == source[source_pos - 1] {
+                start -= 1;
+                source_pos -= 1;
+            }
+            let len = source[source_pos..indexed.len()]
+                .iter()
+                .zip(&target[start..])
+                .take_while(|(a, b)| a == b)
+                .count();
+            push_inserts(&target[insert_start..start], &mut out);
+            push_copies(source_pos, len, &mut out);
+            pos = start + len;
+            insert_start = pos;
+            if out.len() > max_size {
+                return None;
+            }
+        }
+        push_inserts(&target[insert_start..], &mut out);
+        (out.len() <= max_size).then_some(out)
+    }
+
+    /// Apply the delta instructions in `delta` to `source` to produce the target.
+    pub(super) fn apply(source: &[u8], mut delta: &[u8]) -> Result<Vec<u8>, Error> {
+        let source_size = decode_size(&mut delta)?;
+        if source_size != source.len() as u64 {
+            return Err(Error::DeltaSourceSize {
+                expected: source_size,
+                actual: source.len() as u64,
+            });
+        }
+        let target_size = decode_size(&mut delta)?;
+        let mut out = Vec::new();
+        while let Some((&cmd, rest)) = delta.split_first() {
+            delta = rest;
+            if cmd & 0x80 != 0 {
+                let mut read = |bits: &[(u8, usize)]| -> Result<usize, Error> {
+                    let mut value = 0usize;
+                    for (bit, shift) in bits {
+                        if cmd & bit != 0 {
+                            let (byte, rest) = delta.split_first().ok_or(Error::CorruptDelta)?;
+                            value |= usize::from(*byte) << shift;
+                            delta = rest;
+                        }
+                    }
+                    Ok(value)
+                };
+                let offset = read(&[(0x01, 0), (0x02, 8), (0x04, 16), (0x08, 24)])?;
+                let size = match read(&[(0x10, 0), (0x20, 8), (0x40, 16)])? {
+                    0 => MAX_COPY_SIZE,
+                    size => size,
+                };
+                let copy = offset
+                    .checked_add(size)
+                    .and_then(|end| source.get(offset..end))
+                    .ok_or(Error::CorruptDelta)?;
+                out.extend_from_slice(copy);
+            } else if cmd == 0 {
+                return Err(Error::CorruptDelta);
+            } else {
+                let size = usize::from(cmd);
+                let insert = delta.get(..size).ok_or(Error::CorruptDelta)?;
+                out.extend_from_slice(insert);
+                delta = &delta[size..];
+            }
+            if out.len() as u64 > target_size {
+                break;
+            }
+        }
+        if out.len() as u64 != target_size {
+            return Err(Error::DeltaTargetSize {
+                expected: target_size,
+                actual: out.len() as u64,
+            });
+        }
+        Ok(out)
+    }
+
+    fn push_inserts(data: &[u8], out: &mut Vec<u8>) {
+        for chunk in data.chunks(MAX_INSERT_SIZE) {
+            out.push(chunk.len() as u8);
+            out.extend_from_slice(chunk);
+        }
+    }
+
+    fn push_copies(mut offset: usize, mut len: usize, out: &mut Vec<u8>) {
+        while len > 0 {
+            let size = len.min(MAX_COPY_SIZE);
+            let cmd_pos = out.len();
+            let mut cmd = 0x80;
+            out.push(cmd);
+            for (idx, byte) in (offset as u32).to_le_bytes().into_iter().enumerate() {
+                if byte != 0 {
+                    cmd |= 1 << idx;
+                    out.push(byte);
+                }
+            }
+            for (idx, byte) in (size as u32).to_le_bytes().into_iter().take(3).enumerate() {
+                if byte != 0 {
+                    cmd |= 0x10 << idx;
+                    out.push(byte);
+                }
+            }
+            out[cmd_pos] = cmd;
+            offset += size;
+            len -= size;
+        }
+    }
+
+    fn encode_size(mut size: usize, out: &mut Vec<u8>) {
+        while size >= 0x80 {
+            out.push(size as u8 | 0x80);
+            size >>= 7;
+        }
+        out.push(size as u8);
+    }
+
+    fn decode_size(delta: &mut &[u8]) -> Result<u64, Error> {
+        let mut size = 0u64;
+        for shift in (0..64).step_by(7) {
+            let (byte, rest) = delta.split_first().ok_or(Error::CorruptDelta)?;
+            *delta = rest;
+            size |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(size);
+            }
+        }
+        Err(Error::CorruptDelta)
+    }
+}
@@ -0,0 +1,206 @@
+use std::ops::Range;
+
+use bstr::ByteSlice;
+use imara_diff::{
+    intern::{InternedInput, Interner, Token},
+    Algorithm,
+};
+
+/// Determines how lines are split into words, which are then compared to find the changes within lines.
+///
+/// Whitespace between words is never part of a word, and thus differences in whitespace alone aren't visible,
+/// similar to `git diff --word-diff`.
+#[derive(Default, Debug, Clone)]
+pub enum Tokenizer {
+    /// Words are runs of non-whitespace characters, like `git diff --word-diff` does by default.
+    #[default]
+    Whitespace,
+    /// Each non-whitespace character is a word of its own, for the most fine-grained changes,
+    /// similar to `git diff --word-diff-regex=.`.
+    ///
+    /// Characters are decoded as UTF-8, and each invalid byte is a character of its own.
+    Characters,
+    /// Words are all non-empty matches of the regular expression within each line,
+    /// like `git diff --word-diff-regex=<regex>`.
+    #[cfg(feature = "word-regex")]
+    Regex(regex::bytes::Regex),
+}
+
+/// The options for use in [`lines()`] and [`hunk()`].
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The way lines are split into words.
+    pub tokenizer: Tokenizer,
+    /// The algorithm to use when diffing the words of all lines.
+    pub algorithm: Algorithm,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            tokenizer: Default::default(),
+            algorithm: Algorithm::Myers,
+        }
+    }
+}
+
+/// A word within a line, as found by a [`Tokenizer`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Word {
+    /// The index of the line that contains the word, with `0` being the first line passed for diffing.
+    pub line: usize,
+    /// The byte offsets of the word within its line.
+    pub range: Range<usize>,
+}
+
+/// Identify if the words of a [`Segment`] are the same in both the old and the new lines.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SegmentKind {
+    /// The words are the same in the old and the new lines.
+    Unchanged,
+    /// The words of the old lines were removed, and the words of the new lines were added in their place.
+    Changed,
+}
+
+/// A run of consecutive words that are the same, or that changed, between the old and the new lines.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Segment {
+    /// Whether or not the words changed.
+    pub kind: SegmentKind,
+    /// The indices into [`Outcome::before`] for the words of this segment in the old lines.
+    ///
+    /// It's empty if words were only added.
+    pub before: Range<usize>,
+    /// The indices into [`Outcome::after`] for the words of this segment in the new lines.
+    ///
+    /// It's empty if words were only removed.
+    pub after: Range<usize>,
+}
+
+/// The outcome of [`lines()`] and [`hunk()`].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// All words of the old lines, in order.
+    pub before: Vec<Word>,
+    /// All words of the new lines, in order.
+    pub after: Vec<Word>,
+    /// All segments of words, in order, which together cover all words of [`before`](Self::before) and
+    /// [`after`](Self::after).
+    pub segments: Vec<Segment>,
+}
+
+/// Compute the changes of the words within the `before` lines compared to the `after` lines, which typically are the removed
+/// and added lines of the same hunk, using `options`.
+///
+/// Line terminators are never part of a word, so lines may or may not include them.
+pub fn lines(before: &[&[u8]], after: &[&[u8]], options: &Options) -> Outcome {
+    let mut interner = Interner::new(before.len() * 8 + after.len() * 8);
+    let (before, before_tokens) = tokenize(before, &options.tokenizer, &mut interner);
+    let (after, after_tokens) = tokenize(after, &options.tokenizer, &mut interner);
+
+    let mut segments = Vec::new();
+    let (mut before_pos, mut after_pos) = (0, 0);
+    imara_diff::diff_with_tokens(
+        options.algorithm,
+        &before_tokens,
+        &after_tokens,
+        interner.num_tokens(),
+        |removed: Range<u32>, added: Range<u32>| {
+            let (removed, added) = (
+                removed.start as usize..removed.end as usize,
+                added.start as usize..added.end as usize,
+            );
+            if removed.start > before_pos {
+                segments.push(Segment {
+                    kind: SegmentKind::Unchanged,
+                    before: before_pos..removed.start,
+                    after: after_pos..added.start,
+                });
+            }
+            (before_pos, after_pos) = (removed.end, added.end);
+            segments.push(Segment {
+                kind: SegmentKind::Changed,
+                before: removed,
+                after: added,
+            });
+        },
+    );
+    if before_pos < before.len() {
+        segments.push(Segment {
+            kind: SegmentKind::Unchanged,
+            before: before_pos..before.len(),
+            after: after_pos..after.len(),
+        });
+    }
+
+    Outcome {
+        before,
+        after,
+        segments,
+    }
+}
+
+/// Compute the changes of the words within the lines of a single hunk as produced by a line-based diff of `input`,
+/// with `before` being the range of removed lines and `after` being the range of added lines, using `options`.
+///
+/// Return `None` if the hunk didn't both remove and add lines, as there are no lines to compare with each other then.
+/// Note that [`Word::line`] is relative to the first line of the hunk on the respective side.
+pub fn hunk(input: &InternedInput<&[u8]>, before: Range<u32>, after: Range<u32>, options: &Options) -> Option<Outcome> {
+    if before.is_empty() || after.is_empty() {
+        return None;
+    }
+    let lines_of = |tokens: &[Token]| -> Vec<&[u8]> { tokens.iter().map(|token| input.interner[*token]).collect() };
+    Some(lines(
+        &lines_of(&input.before[before.start as usize..before.end as usize]),
+        &lines_of(&input.after[after.start as usize..after.end as usize]),
+        options,
+    ))
+}
+
+/// Return all words in `lines` along with their tokens.
+fn tokenize<'a>(
+    lines: &[&'a [u8]],
+    tokenizer: &Tokenizer,
+    interner: &mut Interner<&'a [u8]>,
+) -> (Vec<Word>, Vec<Token>) {
+    let mut words = Vec::new();
+    let mut tokens = Vec::new();
+    for (line_idx, line) in lines.iter().enumerate() {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let mut push = |range: Range<usize>| {
+            tokens.push(interner.intern(&line[range.clone()]));
+            words.push(Word { line: line_idx, range });
+        };
+        match tokenizer {
+            Tokenizer::Whitespace => {
+                let mut start = None;
+                for (idx, byte) in line.iter().enumerate() {
+                    match (start, byte.is_ascii_whitespace()) {
+                        (None, false) => start = Some(idx),
+                        (Some(word_start), true) => {
+                            push(word_start..idx);
+                            start = None;
+                        }
+                        _ => {}
+                    }
+                }
+                if let Some(word_start) = start {
+                    push(word_start..line.len());
+                }
+            }
+            Tokenizer::Characters => {
+                for (start, end, _) in line.char_indices().filter(|(_, _, c)| !c.is_whitespace()) {
+                    push(start..end);
+                }
+            }
+            #[cfg(feature = "word-regex")]
+            Tokenizer::Regex(regex) => {
+                for m in regex.find_iter(line).filter(|m| !m.is_empty()) {
+                    push(m.range());
+                }
+            }
+        }
+    }
+    (words, tokens)
+}
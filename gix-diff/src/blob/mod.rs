@@ -13,6 +13,12 @@ pub mod pipeline;
 #[allow(clippy::empty_docs)]
 pub mod platform;
 
+/// Binary patches as produced by `git diff --binary`, for blobs that can't be diffed line by line.
+pub mod binary_patch;
+
+/// Diffs of the words within lines, to see which parts of lines changed in a hunk of a line-based diff.
+pub mod intra_line;
+
 /// Information about the diff performed to detect similarity.
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub struct DiffLineStats {
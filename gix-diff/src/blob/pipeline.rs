@@ -206,6 +206,10 @@ impl Pipeline {
     ///
     /// Use `convert` to control what kind of the resource will be produced.
     ///
+    /// The `diff` attribute decides if the resource is binary: if unset with `-diff`, it's always binary, and if set with `diff`
+    /// it's always text, while a [driver](Driver) named with `diff=name` determines it by its configuration.
+    /// Otherwise, or if the named driver isn't known, the data is checked for null-bytes.
+    ///
     /// ### About Tempfiles
     ///
     /// When querying from the object database and a binary and a [binary-to-text](Driver::binary_to_text_command) is set,
@@ -250,6 +254,9 @@ impl Pipeline {
             driver
                 .is_binary
                 .map(|is_binary| is_binary && driver.binary_to_text_command.is_none())
+        } else if attr.assignment.state.is_set() {
+            // `diff` is explicitly set, so it's text even if it looks binary, just like in `git`.
+            Some(false)
         } else {
             attr.assignment.state.is_unset().then_some(true)
         };
@@ -32,6 +32,7 @@ pub struct Rewrites {
     pub percentage: Option<f32>,
     /// The amount of files to consider for fuzzy rename or copy tracking. Defaults to 1000, meaning that only 1000*1000
     /// combinations can be tested for fuzzy matches, i.e. the ones that try to find matches by comparing similarity.
+    /// Like `diff.renameLimit` in git, either the sources or the destinations must not exceed this amount.
     /// If 0, there is no limit.
     ///
     /// If the limit would not be enough to test the entire set of combinations, the algorithm will trade in precision and not
@@ -47,6 +48,11 @@ pub mod rewrites;
 #[allow(clippy::empty_docs)]
 pub mod tree;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod patch_id;
+pub use patch_id::function::patch_id;
+
 ///
 #[cfg(feature = "blob")]
 pub mod blob;
@@ -0,0 +1,163 @@
+/// Determines how the diffs of multiple files contribute to a [patch id](crate::patch_id()).
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Mode {
+    /// The diff of each file is hashed separately and the hashes are summed up, so the order of files in the diff doesn't
+    /// affect the patch id, like `git patch-id --stable`.
+    #[default]
+    Stable,
+    /// The entire diff is hashed at once, so the same changes with a different order of files have different patch ids,
+    /// like `git patch-id --unstable`, which is what git used before the stable mode was introduced.
+    Unstable,
+}
+
+pub(crate) mod function {
+    use bstr::ByteSlice;
+    use gix_hash::ObjectId;
+
+    use super::Mode;
+
+    /// Compute the id of the patch in `diff` with `mode`, which is the same as `git patch-id` would produce, or `None` if `diff`
+    /// doesn't contain any changes.
+    ///
+    /// `diff` is a unified diff of one or more files as produced by `git diff` or `git show`, and everything before the first
+    /// `diff` line, like a commit message, is ignored. The patch ends at the first line that doesn't belong to it,
+    /// like a `commit <id>` line in the output of `git log -p`.
+    ///
+    /// Line numbers and whitespace don't affect the patch id, while file names, including their renames and copies, do.
+    /// `object_hash` is the kind of hash to produce.
+    pub fn patch_id(diff: &[u8], object_hash: gix_hash::Kind, mode: Mode) -> Option<ObjectId> {
+        let mut result = object_hash.null();
+        let mut hasher = gix_features::hash::hasher(object_hash);
+        let mut patch_len = 0;
+        // The amount of lines of the current hunk before and after the change that we still expect, or `None` if we
+        // are in the header of a file.
+        let mut remaining: Option<(usize, usize)> = None;
+        let mut is_binary = false;
+        let (mut before_id, mut after_id): (&[u8], &[u8]) = (b"", b"");
+
+        for line in diff.lines_with_terminator() {
+            if line.starts_with(b"\\ ") && line.len() > 12 {
+                // Skip the marker of missing newlines at the end of files.
+                continue;
+            }
+            if patch_len > 0 && starts_next_patch(line) {
+                break;
+            }
+            if patch_len == 0 && !line.starts_with(b"diff ") {
+                // Skip the commit header and message.
+                continue;
+            }
+
+            if remaining.is_none() && !is_binary {
+                if line.starts_with(b"GIT binary patch") || line.starts_with(b"Binary files") {
+                    is_binary = true;
+                    hasher.update(before_id);
+                    hasher.update(after_id);
+                    if mode == Mode::Stable {
+                        hasher = flush(&mut result, hasher, object_hash);
+                    }
+                    continue;
+                } else if let Some(ids) = line.strip_prefix(b"index ") {
+                    if let Some(pos) = ids.find(b"..") {
+                        before_id = &ids[..pos];
+                        let after = &ids[pos + 2..];
+                        after_id = after.split(|b| *b == b' ').next().unwrap_or(after).trim_end();
+                    }
+                    continue;
+                } else if line.starts_with(b"--- ") {
+                    remaining = Some((1, 1));
+                } else if !matches!(line.first(), Some(b) if b.is_ascii_alphabetic()) {
+                    break;
+                }
+            }
+
+            if is_binary {
+                // Like git, skip all lines up to and including the header of the next file.
+                if line.starts_with(b"diff ") {
+                    is_binary = false;
+                    remaining = None;
+                }
+                continue;
+            }
+
+            if remaining == Some((0, 0)) {
+                if line.starts_with(b"@@ -") {
+                    remaining = Some(hunk_line_counts(line));
+                    continue;
+                }
+                if !line.starts_with(b"diff ") {
+                    break;
+                }
+                if mode == Mode::Stable {
+                    hasher = flush(&mut result, hasher, object_hash);
+                }
+                remaining = None;
+            }
+
+            if let Some((before, after)) = remaining.as_mut() {
+                match line.first() {
+                    Some(b'-') => *before = before.saturating_sub(1),
+                    Some(b'+') => *after = after.saturating_sub(1),
+                    Some(b' ') => {
+                        *before = before.saturating_sub(1);
+                        *after = after.saturating_sub(1);
+                    }
+                    _ => {}
+                }
+            }
+
+            for chunk in line.split(is_space) {
+                patch_len += chunk.len();
+                hasher.update(chunk);
+            }
+        }
+
+        (patch_len > 0).then(|| {
+            flush(&mut result, hasher, object_hash);
+            result
+        })
+    }
+
+    /// Add the digest of `hasher` to `result`, and return a new hasher.
+    fn flush(
+        result: &mut ObjectId,
+        hasher: gix_features::hash::Hasher,
+        object_hash: gix_hash::Kind,
+    ) -> gix_features::hash::Hasher {
+        let digest = hasher.digest();
+        // Add both hashes like little-endian numbers, which is what git does.
+        let mut carry = 0u16;
+        for (result, digest) in result.as_mut_slice().iter_mut().zip(digest.as_slice().iter().copied()) {
+            carry += u16::from(*result) + u16::from(digest);
+            *result = carry as u8;
+            carry >>= 8;
+        }
+        gix_features::hash::hasher(object_hash)
+    }
+
+    /// Like `isspace()` in C, which is what git uses to drop whitespace from the hashed lines.
+    fn is_space(b: &u8) -> bool {
+        b.is_ascii_whitespace() || *b == 0x0b
+    }
+
+    /// Return `true` if `line` is a `commit <id>` or `From <id>` line which precedes the next patch.
+    fn starts_next_patch(line: &[u8]) -> bool {
+        let id = line
+            .strip_prefix(b"commit ")
+            .or_else(|| line.strip_prefix(b"From "))
+            .and_then(|rest| rest.split(is_space).next());
+        matches!(id, Some(id) if ObjectId::from_hex(id).is_ok())
+    }
+
+    /// Parse the amount of lines before and after the change from a hunk header like `@@ -1,2 +1,3 @@`.
+    fn hunk_line_counts(line: &[u8]) -> (usize, usize) {
+        let mut ranges = line[3..].split(|b| *b == b' ').take(2).map(|range| {
+            let range = &range[1.min(range.len())..];
+            match range.find_byte(b',') {
+                Some(pos) => range[pos + 1..].to_str().ok().and_then(|n| n.parse().ok()).unwrap_or(0),
+                None => 1,
+            }
+        });
+        (ranges.next().unwrap_or(1), ranges.next().unwrap_or(1))
+    }
+}
@@ -306,7 +306,7 @@ impl<T: Change> Tracker<T> {
                 let (num_src, num_dst) =
                     estimate_involved_items(self.items.iter().map(|item| (item.emitted, item.change.kind())), kind);
                 let permutations = num_src * num_dst;
-                if permutations > self.rewrites.limit {
+                if exceeds_limit(num_src, num_dst, self.rewrites.limit) {
                     match kind {
                         visit::SourceKind::Rename => {
                             out.num_similarity_checks_skipped_for_rename_tracking_due_to_limit = permutations;
@@ -424,6 +424,14 @@ fn estimate_involved_items(
         })
 }
 
+/// Return `true` if `num_src` sources and `num_dst` destinations can't all be compared with each other without exceeding
+/// `limit`, which is the amount of files on either side, like git does it.
+fn exceeds_limit(num_src: usize, num_dst: usize, limit: usize) -> bool {
+    let within_limit =
+        (num_src <= limit || num_dst <= limit) && num_src.saturating_mul(num_dst) <= limit.saturating_mul(limit);
+    !within_limit
+}
+
 fn needs_exact_match(percentage: Option<f32>) -> bool {
     percentage.map_or(true, |p| p >= 1.0)
 }
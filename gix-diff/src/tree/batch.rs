@@ -0,0 +1,203 @@
+use std::num::NonZeroUsize;
+
+use gix_features::threading::{lock, Mutable, OwnShared};
+use gix_hash::ObjectId;
+use gix_object::{FindExt, TreeRefIter};
+
+use crate::tree::{self, recorder, visit::Action};
+
+/// The error returned by [`changes()`] and [`changes_as_completed()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindTree(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    Changes(#[from] tree::changes::Error),
+    #[error("The callback cancelled the operation")]
+    Cancelled,
+}
+
+/// A pair of trees to compute the changes between, with the tree to compare with first and the tree to obtain second.
+///
+/// The first tree is `None` to compare with an empty tree, like it's needed for commits without parent.
+pub type Pair = (Option<ObjectId>, ObjectId);
+
+/// Options for use in [`changes()`] and [`changes_as_completed()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The amount of threads to use, or `None` to use all logical cores.
+    ///
+    /// Note that threads are only used if the `parallel` feature of `gix-features` is enabled.
+    pub thread_limit: Option<usize>,
+    /// The amount of bytes the decoded trees shared by all threads may use at most, or `0` to not cache them.
+    ///
+    /// As consecutive trees of a history usually share most of their subtrees, this avoids obtaining and decompressing
+    /// them once per pair.
+    pub object_cache_size_in_bytes: usize,
+    /// How to track the location of changes.
+    pub location: Option<recorder::Location>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            thread_limit: None,
+            object_cache_size_in_bytes: 64 * 1024 * 1024,
+            location: Some(recorder::Location::Path),
+        }
+    }
+}
+
+/// Compute the changes of all `pairs` of trees using `objects` to obtain them, spread over multiple threads according
+/// to `options`, and return them in the order of `pairs`.
+///
+/// All threads share a cache of decoded trees, whose size is bounded by [`Options::object_cache_size_in_bytes`].
+/// The first error that occurs aborts the whole operation.
+pub fn changes<Find>(
+    pairs: impl IntoIterator<Item = Pair>,
+    objects: Find,
+    options: Options,
+) -> Result<Vec<Vec<recorder::Change>>, Error>
+where
+    Find: gix_object::Find + Clone + Send,
+{
+    let pairs: Vec<_> = pairs.into_iter().collect();
+    let mut out: Vec<_> = std::iter::repeat_with(Vec::new).take(pairs.len()).collect();
+    changes_as_completed(pairs, objects, options, |index, changes| {
+        out[index] = changes;
+        Action::Continue
+    })?;
+    Ok(out)
+}
+
+/// Like [`changes()`], but call `on_changes(index, changes)` with the `changes` of each pair of trees right when they
+/// were computed, alongside the `index` of the pair in `pairs`, which means that the order of calls is unspecified.
+/// Return [`Action::Cancel`] to stop the operation, which then fails with [`Error::Cancelled`].
+///
+/// Note that `on_changes` is always called on the current thread.
+pub fn changes_as_completed<Find>(
+    pairs: impl IntoIterator<Item = Pair>,
+    objects: Find,
+    options: Options,
+    on_changes: impl FnMut(usize, Vec<recorder::Change>) -> Action,
+) -> Result<(), Error>
+where
+    Find: gix_object::Find + Clone + Send,
+{
+    let pairs: Vec<_> = pairs.into_iter().collect();
+    let objects = SharedCache {
+        inner: objects,
+        cache: NonZeroUsize::new(options.object_cache_size_in_bytes).map(|size| {
+            OwnShared::new(Mutable::new(clru::CLruCache::with_config(
+                clru::CLruCacheConfig::new(size)
+                    .with_hasher(gix_hashtable::hash::Builder)
+                    .with_scale(Weight),
+            )))
+        }),
+    };
+    let location = options.location;
+    gix_features::parallel::in_parallel(
+        pairs.into_iter().enumerate(),
+        options.thread_limit,
+        move |_thread_index| (objects.clone(), tree::State::default(), Vec::new(), Vec::new()),
+        move |(index, (lhs, rhs)), (objects, state, lhs_buf, rhs_buf)| -> Result<_, Error> {
+            let lhs = match lhs {
+                Some(lhs) => objects.find_tree_iter(&lhs, lhs_buf)?,
+                None => TreeRefIter::from_bytes(&[]),
+            };
+            let rhs = objects.find_tree_iter(&rhs, rhs_buf)?;
+            let mut recorder = tree::Recorder::default().track_location(location);
+            tree::Changes::from(lhs).needed_to_obtain(rhs, &mut *state, &*objects, &mut recorder)?;
+            Ok((index, recorder.records))
+        },
+        Reducer { on_changes },
+    )
+}
+
+struct Reducer<F> {
+    on_changes: F,
+}
+
+impl<F> gix_features::parallel::Reduce for Reducer<F>
+where
+    F: FnMut(usize, Vec<recorder::Change>) -> Action,
+{
+    type Input = Result<(usize, Vec<recorder::Change>), Error>;
+    type FeedProduce = ();
+    type Output = ();
+    type Error = Error;
+
+    fn feed(&mut self, item: Self::Input) -> Result<Self::FeedProduce, Self::Error> {
+        let (index, changes) = item?;
+        match (self.on_changes)(index, changes) {
+            Action::Continue => Ok(()),
+            Action::Cancel => Err(Error::Cancelled),
+        }
+    }
+
+    fn finalize(self) -> Result<Self::Output, Self::Error> {
+        Ok(())
+    }
+}
+
+struct Entry {
+    kind: gix_object::Kind,
+    data: Vec<u8>,
+}
+
+struct Weight;
+
+impl clru::WeightScale<ObjectId, Entry> for Weight {
+    fn weight(&self, key: &ObjectId, value: &Entry) -> usize {
+        value.data.len() + std::mem::size_of::<Entry>() + key.as_bytes().len()
+    }
+}
+
+type Cache = clru::CLruCache<ObjectId, Entry, gix_hashtable::hash::Builder, Weight>;
+
+/// An object database which keeps the objects it obtained from `inner` in a `cache` that is shared among all of its
+/// clones, and thus among threads.
+#[derive(Clone)]
+struct SharedCache<Find> {
+    inner: Find,
+    cache: Option<OwnShared<Mutable<Cache>>>,
+}
+
+impl<Find> gix_object::Find for SharedCache<Find>
+where
+    Find: gix_object::Find,
+{
+    fn try_find<'a>(
+        &self,
+        id: &gix_hash::oid,
+        buffer: &'a mut Vec<u8>,
+    ) -> Result<Option<gix_object::Data<'a>>, gix_object::find::Error> {
+        let Some(cache) = &self.cache else {
+            return self.inner.try_find(id, buffer);
+        };
+        let kind = lock(cache).get(id).map(|entry| {
+            buffer.clear();
+            buffer.extend_from_slice(&entry.data);
+            entry.kind
+        });
+        if let Some(kind) = kind {
+            return Ok(Some(gix_object::Data { kind, data: buffer }));
+        }
+
+        let Some(data) = self.inner.try_find(id, buffer)? else {
+            return Ok(None);
+        };
+        // An object too large for the cache is rejected by it, which is fine.
+        lock(cache)
+            .put_with_weight(
+                id.to_owned(),
+                Entry {
+                    kind: data.kind,
+                    data: data.data.to_vec(),
+                },
+            )
+            .ok();
+        Ok(Some(data))
+    }
+}
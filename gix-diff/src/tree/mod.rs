@@ -37,6 +37,10 @@ where
 #[allow(clippy::empty_docs)]
 pub mod changes;
 
+/// Compute the changes of many pairs of trees at once, using multiple threads.
+#[cfg(feature = "batch")]
+pub mod batch;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod visit;
@@ -0,0 +1,62 @@
+//! Measure how the computation of the changes of each commit of a long history scales with the amount of threads,
+//! e.g. with `cargo bench --bench batch --features gix-features-parallel`.
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use gix_diff::tree::batch;
+use gix_hash::ObjectId;
+use gix_object::{bstr::ByteSlice, FindExt};
+
+fn db() -> gix_odb::HandleArc {
+    let git_dir = gix_testtools::scripted_fixture_read_only_standalone("make_diff_history_repo.sh")
+        .expect("fixture can be created")
+        .join(".git");
+    let mut db = gix_odb::at(git_dir.join("objects"))
+        .expect("valid object database")
+        .into_arc()
+        .expect("no thread-local handle was created yet");
+    db.set_pack_cache(|| Box::<gix_pack::cache::lru::StaticLinkedList<64>>::default());
+    db
+}
+
+/// Return the pairs of the tree of each commit and the tree of its parent.
+fn tree_pairs(db: &gix_odb::HandleArc) -> Vec<batch::Pair> {
+    let head = std::fs::read(db.store_ref().path().join("../refs/heads/main")).expect("main exists");
+    let head = ObjectId::from_hex(head.as_bstr().trim()).expect("valid hex id");
+    let mut buf = Vec::new();
+    let mut tree_of = |id: &ObjectId| db.find_commit(id, &mut buf).expect("commit exists").tree();
+    gix_traverse::commit::Simple::new(Some(head), db)
+        .map(|info| {
+            let info = info.expect("valid history");
+            (info.parent_ids.first().map(&mut tree_of), tree_of(&info.id))
+        })
+        .collect()
+}
+
+fn batch_changes(c: &mut Criterion) {
+    let db = db();
+    let pairs = tree_pairs(&db);
+    let mut group = c.benchmark_group("tree::batch::changes()");
+    group.throughput(Throughput::Elements(pairs.len() as u64));
+    group.sample_size(10);
+    for thread_limit in [Some(1), Some(2), Some(4), None] {
+        let name = thread_limit.map_or_else(|| "all threads".to_owned(), |limit| format!("{limit} thread(s)"));
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                black_box(
+                    batch::changes(
+                        pairs.iter().copied(),
+                        db.clone(),
+                        batch::Options {
+                            thread_limit,
+                            ..Default::default()
+                        },
+                    )
+                    .expect("all trees are present"),
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, batch_changes);
+criterion_main!(benches);
@@ -0,0 +1,150 @@
+use gix_diff::blob::binary_patch::{apply, decode, Kind, Patch};
+use gix_object::bstr::ByteSlice;
+
+const NAMES: [&str; 3] = ["similar", "small", "emptied"];
+
+fn old_and_new(dir: &std::path::Path, name: &str) -> std::io::Result<(Vec<u8>, Vec<u8>)> {
+    Ok((
+        std::fs::read(dir.join(format!("{name}.old")))?,
+        std::fs::read(dir.join(name))?,
+    ))
+}
+
+fn git_patch(dir: &std::path::Path, name: &str) -> crate::Result<Patch> {
+    let diff = std::fs::read(dir.join(format!("{name}.patch")))?;
+    let start = diff
+        .windows(b"GIT binary patch".len())
+        .position(|window| window == b"GIT binary patch")
+        .expect("git wrote a binary patch");
+    Ok(Patch::from_bytes(&diff[start..])?)
+}
+
+#[test]
+fn patches_written_by_git_can_be_applied() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_binary_patch_repo.sh")?;
+    for (name, expected_forward_kind) in NAMES.into_iter().zip([Kind::Delta, Kind::Literal, Kind::Literal]) {
+        let (old, new) = old_and_new(&dir, name)?;
+        let patch = git_patch(&dir, name)?;
+        assert_eq!(patch.forward.kind, expected_forward_kind, "{name}");
+        assert_eq!(patch.forward.apply(&old)?, new, "{name}: forward");
+        let reverse = patch.reverse.expect("git writes reverse hunks");
+        assert_eq!(reverse.apply(&new)?, old, "{name}: reverse");
+    }
+    Ok(())
+}
+
+#[test]
+fn round_trip() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_binary_patch_repo.sh")?;
+    let mut pairs = Vec::new();
+    for name in NAMES {
+        pairs.push(old_and_new(&dir, name)?);
+    }
+    let large: Vec<u8> = (0..200_000u32).flat_map(|n| (n % 251).to_le_bytes()).collect();
+    let mut large_modified = large.clone();
+    large_modified[100_000..100_010].copy_from_slice(b"\0modified\0");
+    large_modified.extend_from_slice(b"appended");
+    pairs.push((large, large_modified));
+    pairs.push((Vec::new(), b"\0from nothing".to_vec()));
+
+    for (old, new) in pairs {
+        let patch = Patch::new(&old, &new);
+        let mut buf = Vec::new();
+        patch.write_to(&mut buf)?;
+        assert!(
+            buf.lines().all(|line| line.len() <= 1 + 52 / 4 * 5),
+            "lines are never longer than in git"
+        );
+        assert_eq!(Patch::from_bytes(&buf)?, patch, "what's written can be read back");
+
+        assert_eq!(patch.forward.apply(&old)?, new);
+        assert_eq!(patch.reverse.as_ref().expect("always written").apply(&new)?, old);
+        if old.len() > 100_000 {
+            assert_eq!(patch.forward.kind, Kind::Delta, "similar blobs will use deltas");
+            assert!(patch.forward.data.len() < 100, "the delta is small");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn patches_can_be_applied_by_git() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_writable_standalone("make_binary_patch_repo.sh")?;
+    for name in NAMES {
+        let (old, new) = old_and_new(dir.path(), name)?;
+        std::fs::write(dir.path().join(name), &old)?;
+
+        let hash = |data: &[u8]| gix_object::compute_hash(gix_hash::Kind::Sha1, gix_object::Kind::Blob, data);
+        let mut patch = format!(
+            "diff --git a/{name} b/{name}\nindex {}..{} 100644\n",
+            hash(&old),
+            hash(&new)
+        )
+        .into_bytes();
+        let ours = Patch::new(&old, &new);
+        if name == "similar" {
+            assert_eq!(ours.forward.kind, Kind::Delta, "deltas are understood by git as well");
+        }
+        ours.write_to(&mut patch)?;
+        std::fs::write(dir.path().join("ours.patch"), patch)?;
+
+        assert!(
+            gix_testtools::run_git(dir.path(), &["apply", "--binary", "ours.patch"])?.success(),
+            "{name}: git can apply our patch"
+        );
+        assert_eq!(std::fs::read(dir.path().join(name))?, new, "{name}: forward");
+        assert!(
+            gix_testtools::run_git(dir.path(), &["apply", "--binary", "--reverse", "ours.patch"])?.success(),
+            "{name}: git can apply our patch in reverse"
+        );
+        assert_eq!(std::fs::read(dir.path().join(name))?, old, "{name}: reverse");
+    }
+    Ok(())
+}
+
+#[test]
+fn invalid_input() -> crate::Result {
+    assert!(matches!(
+        Patch::from_bytes(b"literal 0\n"),
+        Err(decode::Error::MissingHeader)
+    ));
+    assert!(matches!(
+        Patch::from_bytes(b"GIT binary patch\nliteral nan\n"),
+        Err(decode::Error::InvalidHunkHeader { .. })
+    ));
+    assert!(
+        matches!(
+            Patch::from_bytes(b"GIT binary patch\nliteral 0\nB0000\n\n"),
+            Err(decode::Error::InvalidDataLine { .. })
+        ),
+        "the length of the line doesn't match the length prefix"
+    );
+    assert!(
+        matches!(
+            Patch::from_bytes(b"GIT binary patch\nliteral 0\nA\"\"\"\"\"\n\n"),
+            Err(decode::Error::InvalidDataLine { .. })
+        ),
+        "invalid base85 characters"
+    );
+
+    let patch = Patch::new(
+        b"0123456789abcdefghijklmnopqrstuvwxyz",
+        b"0123456789abcdefghijklmnopqrstuvwxyz-",
+    );
+    assert_eq!(patch.forward.kind, Kind::Delta);
+    assert!(matches!(
+        patch.forward.apply(b"too short"),
+        Err(apply::Error::DeltaSourceSize {
+            expected: 36,
+            actual: 9
+        })
+    ));
+
+    let mut patch = Patch::new(b"", b"content");
+    patch.forward.size += 1;
+    assert!(matches!(
+        patch.forward.apply(b""),
+        Err(apply::Error::SizeMismatch { expected: 8, actual: 7 })
+    ));
+    Ok(())
+}
@@ -0,0 +1,170 @@
+use gix_diff::blob::{
+    intern::InternedInput,
+    intra_line::{self, Options, Outcome, SegmentKind, Tokenizer, Word},
+    sources::byte_lines_with_terminator,
+    Algorithm,
+};
+
+/// Render `out` similar to `git diff --word-diff=plain`, but with all words separated by a single space.
+fn render(out: &Outcome, before: &[&str], after: &[&str]) -> String {
+    let text = |words: &[Word], lines: &[&str]| -> Vec<String> {
+        words
+            .iter()
+            .map(|word| lines[word.line][word.range.clone()].to_owned())
+            .collect()
+    };
+    out.segments
+        .iter()
+        .map(|segment| {
+            let removed = text(&out.before[segment.before.clone()], before).join(" ");
+            let added = text(&out.after[segment.after.clone()], after).join(" ");
+            match segment.kind {
+                SegmentKind::Unchanged => {
+                    assert_eq!(removed, added, "unchanged segments have the same words on both sides");
+                    added
+                }
+                SegmentKind::Changed => {
+                    let mut out = String::new();
+                    if !removed.is_empty() {
+                        out.push_str(&format!("[-{removed}-]"));
+                    }
+                    if !added.is_empty() {
+                        out.push_str(&format!("{{+{added}+}}"));
+                    }
+                    out
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn diff(before: &[&str], after: &[&str], tokenizer: Tokenizer) -> String {
+    let out = intra_line::lines(
+        &before.iter().map(|line| line.as_bytes()).collect::<Vec<_>>(),
+        &after.iter().map(|line| line.as_bytes()).collect::<Vec<_>>(),
+        &Options {
+            tokenizer,
+            algorithm: Algorithm::Myers,
+        },
+    );
+    render(&out, before, after)
+}
+
+#[test]
+fn words_are_separated_by_whitespace_by_default() {
+    assert_eq!(
+        diff(&["let x = 1;\n"], &["let  y = 1;\n"], Tokenizer::Whitespace),
+        "let [-x-]{+y+} = 1;",
+        "whitespace is never part of a word, so changes to it aren't visible"
+    );
+    assert_eq!(
+        diff(&["a b c"], &["a c d"], Tokenizer::Whitespace),
+        "a [-b-] c {+d+}",
+        "words may be removed or added"
+    );
+    assert_eq!(
+        diff(&["same words"], &["same\twords\r\n"], Tokenizer::Whitespace),
+        "same words"
+    );
+    assert_eq!(
+        diff(&[" \n"], &["new"], Tokenizer::Whitespace),
+        "{+new+}",
+        "lines without words"
+    );
+}
+
+#[test]
+fn words_know_their_line_and_byte_offsets() {
+    let out = intra_line::lines(
+        &[b"first line\n", b"  second\n"],
+        &[b"first second\n"],
+        &Options::default(),
+    );
+    assert_eq!(
+        out.before,
+        vec![
+            Word { line: 0, range: 0..5 },
+            Word { line: 0, range: 6..10 },
+            Word { line: 1, range: 2..8 },
+        ]
+    );
+    assert_eq!(
+        out.after,
+        vec![Word { line: 0, range: 0..5 }, Word { line: 0, range: 6..12 }]
+    );
+    assert_eq!(
+        out.segments,
+        vec![
+            intra_line::Segment {
+                kind: SegmentKind::Unchanged,
+                before: 0..1,
+                after: 0..1,
+            },
+            intra_line::Segment {
+                kind: SegmentKind::Changed,
+                before: 1..2,
+                after: 1..1,
+            },
+            intra_line::Segment {
+                kind: SegmentKind::Unchanged,
+                before: 2..3,
+                after: 1..2,
+            },
+        ],
+        "words are compared across lines"
+    );
+}
+
+#[test]
+fn characters() {
+    assert_eq!(diff(&["color"], &["colour"], Tokenizer::Characters), "c o l o {+u+} r");
+    assert_eq!(
+        diff(&["naïve"], &["naive"], Tokenizer::Characters),
+        "n a [-ï-]{+i+} v e",
+        "multi-byte characters are a single word"
+    );
+}
+
+#[test]
+fn regex() {
+    let tokenizer = Tokenizer::Regex(regex::bytes::Regex::new(r"(?-u)[a-z]+|[^a-z\s]").expect("valid"));
+    assert_eq!(
+        diff(&["call(a, b)"], &["call(a, c);"], tokenizer),
+        "call ( a , [-b-]{+c+} ) {+;+}"
+    );
+    assert_eq!(
+        diff(
+            &["unchanged"],
+            &["unchanged"],
+            Tokenizer::Regex(regex::bytes::Regex::new("x*").expect("valid"))
+        ),
+        "",
+        "empty matches are ignored"
+    );
+}
+
+#[test]
+fn hunk() {
+    let input = InternedInput::new(
+        byte_lines_with_terminator(b"unchanged\nold value\n"),
+        byte_lines_with_terminator(b"unchanged\nnew value\nadded\n"),
+    );
+    let mut hunks = Vec::new();
+    gix_diff::blob::diff(Algorithm::Myers, &input, |before, after| hunks.push((before, after)));
+    assert_eq!(hunks, vec![(1..2, 1..3)]);
+
+    let options = Options::default();
+    let out = intra_line::hunk(&input, hunks[0].0.clone(), hunks[0].1.clone(), &options).expect("lines on both sides");
+    assert_eq!(
+        render(&out, &["old value\n"], &["new value\n", "added\n"]),
+        "[-old-]{+new+} value {+added+}",
+        "lines are relative to the first line of the hunk"
+    );
+
+    assert_eq!(
+        intra_line::hunk(&input, 1..1, 1..3, &options),
+        None,
+        "there is nothing to compare if lines were only added"
+    );
+}
@@ -1,2 +1,4 @@
+mod binary_patch;
+mod intra_line;
 pub(crate) mod pipeline;
 mod platform;
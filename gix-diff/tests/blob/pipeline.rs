@@ -879,6 +879,51 @@ pub(crate) mod convert_to_diffable {
             assert_eq!(buf.len(), 0);
         }
 
+        let platform = attributes.at_entry("set", None, &gix_object::find::Never)?;
+        for mode in all_modes {
+            let out = filter.convert_to_diffable(
+                &null,
+                EntryKind::Blob,
+                "set".into(),
+                ResourceKind::OldOrSource,
+                &mut |_, out| {
+                    let _ = platform.matching_attributes(out);
+                },
+                &gix_object::find::Never,
+                mode,
+                &mut buf,
+            )?;
+            assert_eq!(
+                out.driver_index, None,
+                "no driver is associated, as `diff` is explicitly set"
+            );
+            assert_eq!(out.data, Some(pipeline::Data::Buffer));
+            assert_eq!(
+                buf.as_bstr(),
+                "set\0binary\n",
+                "set counts as text, even with null-bytes"
+            );
+        }
+
+        let id = db.insert("set\0in-db");
+        for mode in all_modes {
+            let out = filter.convert_to_diffable(
+                &id,
+                EntryKind::Blob,
+                "set".into(),
+                ResourceKind::NewOrDestination,
+                &mut |_, out| {
+                    let _ = platform.matching_attributes(out);
+                },
+                &db,
+                mode,
+                &mut buf,
+            )?;
+            assert_eq!(out.driver_index, None);
+            assert_eq!(out.data, Some(pipeline::Data::Buffer));
+            assert_eq!(buf.as_bstr(), "set\0in-db", "set counts as text for db sources as well");
+        }
+
         let platform = attributes.at_entry("d", None, &gix_object::find::Never)?;
         let id = db.insert("d-in-db");
         for mode in worktree_modes {
@@ -0,0 +1,21 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+
+{ printf '\0'; seq 1 3000; } > similar
+printf 'a\0b' > small
+printf '\0\1\2' > emptied
+git add . && git commit -q -m "init"
+
+for name in similar small emptied; do
+  cp "$name" "$name.old"
+done
+
+{ printf '\0'; seq 1 1500; echo changed; seq 1501 3000; } > similar
+printf '\0c\0d\0e' > small
+: > emptied
+
+for name in similar small emptied; do
+  git diff --binary --full-index -- "$name" > "$name.patch"
+done
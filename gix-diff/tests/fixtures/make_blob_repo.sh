@@ -9,6 +9,7 @@ echo c > c
 echo d > d
 echo e > e-no-attr
 echo unset > unset
+printf 'set\0binary\n' > set
 
 cat <<EOF >.gitattributes
 a diff=a
@@ -17,6 +18,7 @@ c diff=c
 d diff=d
 missing diff=missing
 unset -diff
+set diff
 EOF
 
 git add . && git commit -m "init"
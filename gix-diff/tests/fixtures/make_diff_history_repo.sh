@@ -0,0 +1,48 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+# A long linear history of a big tree, with every commit changing a few files in different subtrees.
+commits=${1:-2000}
+
+git init -q
+git config core.autocrlf false
+
+git checkout -q -b main
+
+{
+  echo "commit refs/heads/main"
+  echo "committer author <author@example.com> 946684800 +0000"
+  echo "data 7"
+  echo "initial"
+  for dir in $(seq 0 49); do
+    for sub in $(seq 0 6); do
+      for file in $(seq 0 12); do
+        echo "M 100644 inline dir$dir/sub$sub/file$file"
+        echo "data <<EOF"
+        echo "$dir $sub $file"
+        echo "EOF"
+      done
+    done
+  done
+
+  for i in $(seq 1 "$commits"); do
+    echo "commit refs/heads/main"
+    echo "committer author <author@example.com> $((946684800 + i)) +0000"
+    echo "data <<EOF"
+    echo "change $i"
+    echo "EOF"
+    echo "M 100644 inline dir$((i % 50))/sub$((i % 7))/file$((i % 13))"
+    echo "data <<EOF"
+    echo "changed in $i"
+    echo "EOF"
+    echo "M 100644 inline dir$(((i * 7) % 50))/new$((i % 3))"
+    echo "data <<EOF"
+    echo "added or changed in $i"
+    echo "EOF"
+    if [ $((i % 10)) = 0 ]; then
+      echo "D dir$(((i * 3) % 50))/sub$((i % 7))/file$(((i + 1) % 13))"
+    fi
+  done
+} | git fast-import --quiet
+
+git repack -adq
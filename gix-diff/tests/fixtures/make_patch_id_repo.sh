@@ -0,0 +1,37 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+
+seq 1 20 > a
+seq 1 40 > b
+seq 100 130 > to-rename
+printf 'binary\0' > bin
+seq 1 3 > c
+git add . && git commit -q -m "init"
+
+{ echo first; seq 1 20; } > a
+seq 1 39 > b
+sed -i'' -e 's/^30$/thirty/' b
+echo new > added
+git add . && git commit -q -m "multiple files" && git tag multi
+
+git mv to-rename renamed
+sed -i'' -e 's/^115$/changed/' renamed
+git rm -q a && git add renamed
+git commit -q -m "rename and deletion" && git tag rename
+
+printf 'other\0binary' > bin
+printf 'no newline' >> b
+echo 4 >> c
+git add . && git commit -q -m "binary and no newline at end of file" && git tag binary
+
+printf '%s\n' b added a > reversed-order
+for rev in multi rename binary; do
+  git show "$rev" > "$rev.diff"
+  git show -Oreversed-order "$rev" > "$rev-reversed.diff"
+  for diff in "$rev.diff" "$rev-reversed.diff"; do
+    git patch-id --stable < "$diff" > "$diff.stable"
+    git patch-id --unstable < "$diff" > "$diff.unstable"
+  done
+done
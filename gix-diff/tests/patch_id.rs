@@ -0,0 +1,81 @@
+use gix_diff::patch_id::Mode;
+use gix_hash::ObjectId;
+
+/// Parse the patch id from the output of `git patch-id`, which uses the object hash of the repository.
+fn git_patch_id(path: &std::path::Path) -> crate::Result<ObjectId> {
+    let baseline = std::fs::read(path)?;
+    let hex = baseline.split(|b| *b == b' ').next().expect("at least one token");
+    Ok(ObjectId::from_hex(hex)?)
+}
+
+fn patch_id(diff: &[u8], mode: Mode) -> Option<ObjectId> {
+    gix_diff::patch_id(diff, gix_hash::Kind::Sha1, mode)
+}
+
+#[test]
+fn baseline() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_patch_id_repo.sh")?;
+    for rev in ["multi", "rename", "binary"] {
+        let mut ids = Vec::new();
+        for name in [format!("{rev}.diff"), format!("{rev}-reversed.diff")] {
+            let diff = std::fs::read(dir.join(&name))?;
+            for (mode, extension) in [(Mode::Stable, "stable"), (Mode::Unstable, "unstable")] {
+                let expected = git_patch_id(&dir.join(format!("{name}.{extension}")))?;
+                let actual = gix_diff::patch_id(&diff, expected.kind(), mode);
+                assert_eq!(actual, Some(expected), "{name}: {mode:?}");
+                ids.push(expected);
+            }
+        }
+        assert_eq!(
+            ids[0], ids[2],
+            "{rev}: the order of files doesn't matter in stable mode"
+        );
+        if rev == "multi" {
+            assert_ne!(ids[1], ids[3], "{rev}: the order of files does matter in unstable mode");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn line_numbers_and_whitespace_are_ignored() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_patch_id_repo.sh")?;
+    let diff = std::fs::read_to_string(dir.join("multi.diff"))?;
+    let expected = patch_id(diff.as_bytes(), Mode::Stable);
+    assert!(expected.is_some());
+
+    let moved = diff.replace("@@ -1,3 +1,4 @@", "@@ -10,3 +10,4 @@");
+    assert_ne!(moved, diff, "the hunk header was changed");
+    assert_eq!(patch_id(moved.as_bytes(), Mode::Stable), expected);
+
+    let indented = diff.replace("+first", "+  first\t");
+    assert_ne!(indented, diff, "a line was changed");
+    assert_eq!(patch_id(indented.as_bytes(), Mode::Stable), expected);
+    Ok(())
+}
+
+#[test]
+fn only_the_first_patch_is_used() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_patch_id_repo.sh")?;
+    let mut diff = std::fs::read(dir.join("multi.diff"))?;
+    let expected = patch_id(&diff, Mode::Stable);
+    diff.extend(std::fs::read(dir.join("rename.diff"))?);
+    assert_eq!(
+        patch_id(&diff, Mode::Stable),
+        expected,
+        "the next commit ends the patch like in the output of `git log -p`"
+    );
+    Ok(())
+}
+
+#[test]
+fn no_changes() {
+    assert_eq!(
+        patch_id(
+            b"commit 0123456789012345678901234567890123456789\n\n    message\n",
+            Mode::Stable
+        ),
+        None
+    );
+    assert_eq!(patch_id(b"", Mode::Unstable), None);
+}
@@ -452,6 +452,55 @@ fn rename_by_similarity_reports_limit_if_encountered() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn rename_by_similarity_limit_is_the_amount_of_files_on_each_side() -> crate::Result {
+    let rewrites = Rewrites {
+        copies: None,
+        percentage: Some(0.5),
+        limit: 2,
+    };
+    let mut track = util::new_tracker(rewrites);
+    let odb = util::add_retained_blobs(
+        &mut track,
+        [
+            (Change::deletion(), "a", "first\nsecond\n"),
+            (Change::addition(), "b", "firt\nsecond\n"),
+            (Change::addition(), "c", "second\nunrelated\n"),
+        ],
+    );
+
+    let mut calls = 0;
+    let out = util::assert_emit_with_objects(
+        &mut track,
+        |dst, src| {
+            match calls {
+                0 => {
+                    assert_eq!(src.expect("fuzzy match").location, "a");
+                    assert_eq!(dst.location, "b");
+                }
+                1 => {
+                    assert!(src.is_none(), "pair already found");
+                    assert_eq!(dst.location, "c");
+                }
+                _ => panic!("too many elements emitted"),
+            };
+            calls += 1;
+            Action::Continue
+        },
+        odb,
+    );
+    assert_eq!(
+        out,
+        rewrites::Outcome {
+            options: rewrites,
+            num_similarity_checks: 1,
+            ..Default::default()
+        },
+        "1 source and 2 destinations are within the limit of 2 files, even though there are 2 permutations, like in git"
+    );
+    Ok(())
+}
+
 #[test]
 fn rename_by_50_percent_similarity() -> crate::Result {
     let rewrites = Rewrites {
@@ -703,4 +703,149 @@ mod changes {
             Ok(())
         }
     }
+
+    mod batch {
+        use gix_diff::tree::{batch, recorder, visit::Action};
+        use gix_hash::ObjectId;
+        use gix_object::{bstr::ByteSlice, FindExt};
+
+        fn db() -> crate::Result<gix_odb::HandleArc> {
+            let mut db = gix_odb::at(
+                gix_testtools::scripted_fixture_read_only_with_args_standalone(
+                    "make_diff_history_repo.sh",
+                    Some("100"),
+                )?
+                .join(".git")
+                .join("objects"),
+            )?
+            .into_arc()?;
+            // The trees are stored as long delta chains, which would be very slow to resolve without a pack cache.
+            db.set_pack_cache(|| Box::<gix_pack::cache::lru::StaticLinkedList<64>>::default());
+            Ok(db)
+        }
+
+        /// Return the pairs of the tree of each commit and the tree of its parent, in topological order.
+        fn tree_pairs(db: &gix_odb::HandleArc) -> crate::Result<Vec<batch::Pair>> {
+            let head = ObjectId::from_hex(
+                std::fs::read(db.store_ref().path().join("../refs/heads/main"))?
+                    .as_bstr()
+                    .trim(),
+            )?;
+            let mut buf = Vec::new();
+            let tree_of =
+                |id: &ObjectId, buf: &mut Vec<u8>| -> crate::Result<ObjectId> { Ok(db.find_commit(id, buf)?.tree()) };
+            let mut pairs = Vec::new();
+            for info in gix_traverse::commit::Simple::new(Some(head), db) {
+                let info = info?;
+                let parent = info.parent_ids.first().map(|id| tree_of(id, &mut buf)).transpose()?;
+                pairs.push((parent, tree_of(&info.id, &mut buf)?));
+            }
+            Ok(pairs)
+        }
+
+        fn sequential_changes(
+            db: &gix_odb::HandleArc,
+            pairs: &[batch::Pair],
+        ) -> crate::Result<Vec<Vec<recorder::Change>>> {
+            let (mut lhs_buf, mut rhs_buf) = (Vec::new(), Vec::new());
+            let mut state = gix_diff::tree::State::default();
+            pairs
+                .iter()
+                .map(|(lhs, rhs)| {
+                    let lhs = lhs.map(|lhs| db.find_tree_iter(&lhs, &mut lhs_buf)).transpose()?;
+                    let rhs = db.find_tree_iter(rhs, &mut rhs_buf)?;
+                    let mut recorder = gix_diff::tree::Recorder::default();
+                    gix_diff::tree::Changes::from(lhs).needed_to_obtain(rhs, &mut state, db, &mut recorder)?;
+                    Ok(recorder.records)
+                })
+                .collect()
+        }
+
+        #[test]
+        fn changes_are_the_same_as_the_ones_computed_one_by_one_and_in_order() -> crate::Result {
+            let db = db()?;
+            let pairs = tree_pairs(&db)?;
+            assert_eq!(pairs.len(), 101, "the fixture has the expected amount of commits");
+            assert_eq!(pairs.last().expect("root").0, None, "the root commit has no parent");
+            let expected = sequential_changes(&db, &pairs)?;
+            assert!(expected.iter().all(|changes| !changes.is_empty()));
+
+            for (thread_limit, object_cache_size_in_bytes) in
+                [(Some(1), 0), (Some(4), 16 * 1024), (None, 64 * 1024 * 1024)]
+            {
+                let actual = batch::changes(
+                    pairs.iter().copied(),
+                    db.clone(),
+                    batch::Options {
+                        thread_limit,
+                        object_cache_size_in_bytes,
+                        ..Default::default()
+                    },
+                )?;
+                assert_eq!(
+                    actual, expected,
+                    "thread_limit = {thread_limit:?}, object_cache_size_in_bytes = {object_cache_size_in_bytes}"
+                );
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn changes_as_completed_passes_the_index_of_each_pair() -> crate::Result {
+            let db = db()?;
+            let pairs = tree_pairs(&db)?;
+            let expected = sequential_changes(&db, &pairs)?;
+
+            let mut actual = Vec::new();
+            batch::changes_as_completed(
+                pairs.iter().copied(),
+                db.clone(),
+                batch::Options {
+                    thread_limit: Some(4),
+                    ..Default::default()
+                },
+                |index, changes| {
+                    actual.push((index, changes));
+                    Action::Continue
+                },
+            )?;
+            assert_eq!(actual.len(), pairs.len(), "each pair is seen exactly once");
+            actual.sort_by_key(|(index, _)| *index);
+            for (expected_index, ((index, changes), expected)) in actual.into_iter().zip(expected).enumerate() {
+                assert_eq!(index, expected_index);
+                assert_eq!(changes, expected, "the changes belong to the pair at {index}");
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn cancellation_stops_the_operation() -> crate::Result {
+            let db = db()?;
+            let pairs = tree_pairs(&db)?;
+            let mut calls = 0;
+            let err = batch::changes_as_completed(pairs, db, batch::Options::default(), |_, _| {
+                calls += 1;
+                Action::Cancel
+            })
+            .unwrap_err();
+            assert!(matches!(err, batch::Error::Cancelled));
+            assert_eq!(calls, 1, "no more results are delivered once cancelled");
+            Ok(())
+        }
+
+        #[test]
+        fn missing_trees_are_an_error() -> crate::Result {
+            let db = db()?;
+            let pairs = tree_pairs(&db)?;
+            let missing = ObjectId::null(gix_hash::Kind::Sha1);
+            let err = batch::changes(
+                pairs.into_iter().take(10).chain(Some((None, missing))),
+                db,
+                Default::default(),
+            )
+            .unwrap_err();
+            assert!(matches!(err, batch::Error::FindTree(_)), "{err:?}");
+            Ok(())
+        }
+    }
 }
@@ -1,5 +1,48 @@
 pub use flate2::{Decompress, Status};
 
+/// The zlib implementation used for all compression and decompression, as selected by the mutually exclusive `zlib-*`
+/// feature toggles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Backend {
+    /// The pure-Rust `miniz_oxide` implementation, which is used if no other backend is selected.
+    RustBackend,
+    /// The C-based `zlib-ng` implementation, selected with the `zlib-ng` feature.
+    ZlibNg,
+    /// The C-based `zlib-ng` implementation used through its zlib-compatible API, selected with the `zlib-ng-compat`
+    /// feature.
+    ZlibNgCompat,
+    /// The C-based stock `zlib` implementation, selected with the `zlib-stock` feature.
+    ZlibStock,
+}
+
+impl Backend {
+    /// Return the backend that was compiled in.
+    ///
+    /// If multiple backends are selected, the C-based ones take precedence in the order `zlib-ng`, `zlib-ng-compat`
+    /// and `zlib-stock`, just like `flate2` does it.
+    pub const fn current() -> Self {
+        if cfg!(feature = "zlib-ng") {
+            Backend::ZlibNg
+        } else if cfg!(feature = "zlib-ng-compat") {
+            Backend::ZlibNgCompat
+        } else if cfg!(feature = "zlib-stock") {
+            Backend::ZlibStock
+        } else {
+            Backend::RustBackend
+        }
+    }
+
+    /// Return the name of the feature toggle that selects this backend.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Backend::RustBackend => "zlib-rust-backend",
+            Backend::ZlibNg => "zlib-ng",
+            Backend::ZlibNgCompat => "zlib-ng-compat",
+            Backend::ZlibStock => "zlib-stock",
+        }
+    }
+}
+
 /// non-streaming interfaces for decompression
 pub mod inflate {
     /// The error returned by various [Inflate methods][super::Inflate]
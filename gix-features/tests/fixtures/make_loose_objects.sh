@@ -0,0 +1,12 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+
+git checkout -q -b main
+touch empty
+echo hello > small
+seq 1 20000 > compressible
+awk 'BEGIN { srand(42); for (i = 0; i < 20000; i++) printf "%c", 32 + int(rand() * 95) }' > incompressible
+git add .
+git commit -q -m "objects of all kinds and sizes"
@@ -0,0 +1,97 @@
+use std::io::{Read, Write};
+
+use bstr::ByteSlice;
+use gix_features::zlib::{self, stream};
+
+/// Loose objects written by `git`, whose ids are the hashes of their decompressed data, which makes them the reference
+/// any backend is compared against when decompressing.
+fn loose_objects() -> gix_testtools::Result<Vec<(String, Vec<u8>)>> {
+    let objects_dir = gix_testtools::scripted_fixture_read_only("make_loose_objects.sh")?.join(".git/objects");
+    let mut out = Vec::new();
+    for dir in std::fs::read_dir(objects_dir)? {
+        let dir = dir?;
+        let prefix = dir.file_name().into_string().expect("valid UTF-8");
+        if prefix.len() != 2 {
+            continue;
+        }
+        for file in std::fs::read_dir(dir.path())? {
+            let file = file?;
+            let id = format!("{prefix}{}", file.file_name().into_string().expect("valid UTF-8"));
+            out.push((id, std::fs::read(file.path())?));
+        }
+    }
+    out.sort();
+    assert_eq!(out.len(), 6, "four blobs, a tree and a commit");
+    Ok(out)
+}
+
+fn hex_hash(data: &[u8]) -> String {
+    let mut hasher = gix_features::hash::hasher(gix_hash::Kind::Sha1);
+    hasher.update(data);
+    hasher.digest().to_hex().to_string()
+}
+
+/// Decompress `compressed` through the streaming interface that is also used when reading packs.
+fn inflate_streaming(compressed: &[u8]) -> Vec<u8> {
+    struct Reader<'a> {
+        input: &'a [u8],
+        state: zlib::Decompress,
+    }
+    impl Read for Reader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            stream::inflate::read(&mut self.input, &mut self.state, buf)
+        }
+    }
+    let mut out = Vec::new();
+    Reader {
+        input: compressed,
+        state: zlib::Decompress::new(true),
+    }
+    .read_to_end(&mut out)
+    .expect("valid stream");
+    out
+}
+
+/// Each backend has to decompress the objects written by `git` to exactly the data `git` hashed.
+#[test]
+fn inflate_once_and_streaming_produce_the_same_bytes_as_git() -> gix_testtools::Result {
+    for (id, compressed) in loose_objects()? {
+        let streamed = inflate_streaming(&compressed);
+        assert_eq!(hex_hash(&streamed), id, "{}", streamed.as_bstr());
+
+        let mut once = vec![0; streamed.len()];
+        let (status, consumed_in, consumed_out) = zlib::Inflate::default()
+            .once(&compressed, &mut once)
+            .expect("valid stream");
+        assert_eq!(status, zlib::Status::StreamEnd);
+        assert_eq!(consumed_in, compressed.len());
+        assert_eq!(consumed_out, streamed.len());
+        assert_eq!(once, streamed, "both ways of decompressing yield the same result");
+    }
+    Ok(())
+}
+
+/// The compressed bytes are implementation-defined and differ between backends, so they aren't compared to a reference.
+/// Instead, each backend has to compress data such that it decompresses to the original again.
+#[test]
+fn deflate_roundtrips_through_inflate() -> gix_testtools::Result {
+    let mut corpus = loose_objects()?
+        .into_iter()
+        .map(|(_, compressed)| inflate_streaming(&compressed))
+        .collect::<Vec<_>>();
+    corpus.push(Vec::new());
+
+    for data in corpus {
+        let mut w = stream::deflate::Write::new(Vec::new());
+        w.write_all(&data)?;
+        w.flush()?;
+        let compressed = w.into_inner();
+
+        assert_eq!(
+            inflate_streaming(&compressed),
+            data,
+            "compressed output may differ between backends, but it always decompresses to the original"
+        );
+    }
+    Ok(())
+}
@@ -1,2 +1,24 @@
-#![deny(rust_2018_idioms)]
+//! Write `.git/FETCH_HEAD` files to record which remote refs were fetched, just like `git fetch` does.
+//!
+//! Each fetched ref is written as line of the form `<id>\t[not-for-merge]\t<description>`, where the description
+//! names the kind of ref, its short name and the URL of the remote, like `branch 'main' of https://example.com/repo`.
+#![deny(rust_2018_idioms, missing_docs)]
 #![forbid(unsafe_code)]
+
+use bstr::BString;
+
+/// A single line of a `FETCH_HEAD` file, representing one ref that was fetched from a remote.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Entry {
+    /// The object the ref pointed to on the remote.
+    pub id: gix_hash::ObjectId,
+    /// If `true`, the ref is a candidate for merging into the current branch, which is typically the case for its upstream.
+    /// Otherwise it's marked as `not-for-merge`.
+    pub for_merge: bool,
+    /// The full name of the ref on the remote, like `refs/heads/main` or `HEAD`,
+    /// or the hexadecimal object id if an object was fetched by id.
+    pub remote_ref_name: BString,
+}
+
+mod write;
+pub use write::write_to;
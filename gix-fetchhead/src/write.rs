@@ -0,0 +1,62 @@
+use bstr::{BStr, ByteSlice};
+
+use crate::Entry;
+
+/// Write all `entries` fetched from the remote at `url` to `out` in the format of a `FETCH_HEAD` file.
+///
+/// Just like `git`, entries that are [for merge](Entry::for_merge) are written first, followed by all others,
+/// while retaining their order otherwise.
+///
+/// Note that `url` is written as is, so credentials should be removed from it beforehand.
+pub fn write_to<'a>(
+    entries: impl IntoIterator<Item = &'a Entry>,
+    url: &BStr,
+    out: &mut dyn std::io::Write,
+) -> std::io::Result<()> {
+    let (for_merge, not_for_merge): (Vec<_>, Vec<_>) = entries.into_iter().partition(|entry| entry.for_merge);
+    for entry in for_merge.into_iter().chain(not_for_merge) {
+        entry.write_to(url, out)?;
+    }
+    Ok(())
+}
+
+impl Entry {
+    /// Write this instance as single line of a `FETCH_HEAD` file to `out`, as fetched from the remote at `url`.
+    pub fn write_to(&self, url: &BStr, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        write!(
+            out,
+            "{}\t{}\t",
+            self.id,
+            if self.for_merge { "" } else { "not-for-merge" }
+        )?;
+        let name = self.remote_ref_name.as_bstr();
+        let (kind, short_name): (&[u8], _) = if name == "HEAD" {
+            (b"", b"".as_bstr())
+        } else if let Some(short_name) = name.strip_prefix(b"refs/heads/") {
+            (b"branch ", short_name.as_bstr())
+        } else if let Some(short_name) = name.strip_prefix(b"refs/tags/") {
+            (b"tag ", short_name.as_bstr())
+        } else if let Some(short_name) = name.strip_prefix(b"refs/remotes/") {
+            (b"remote-tracking branch ", short_name.as_bstr())
+        } else {
+            (b"", name)
+        };
+        if !short_name.is_empty() {
+            out.write_all(kind)?;
+            out.write_all(b"'")?;
+            out.write_all(short_name)?;
+            out.write_all(b"' of ")?;
+        }
+        out.write_all(shorten_url(url))?;
+        out.write_all(b"\n")
+    }
+}
+
+/// Like `git`, remove trailing slashes and the `.git` suffix from `url`.
+fn shorten_url(url: &BStr) -> &BStr {
+    let url = &url[..url.iter().rposition(|b| *b != b'/').map_or(0, |pos| pos + 1)];
+    match url.strip_suffix(b".git") {
+        Some(stripped) if url.len() > 5 => stripped.as_bstr(),
+        _ => url,
+    }
+}
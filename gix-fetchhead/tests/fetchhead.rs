@@ -0,0 +1,76 @@
+use gix_fetchhead::Entry;
+
+fn entry(for_merge: bool, remote_ref_name: &str) -> Entry {
+    Entry {
+        id: gix_hash::ObjectId::from_hex(b"0123456789012345678901234567890123456789").expect("valid"),
+        for_merge,
+        remote_ref_name: remote_ref_name.into(),
+    }
+}
+
+fn write(entries: &[Entry], url: &str) -> String {
+    let mut buf = Vec::new();
+    gix_fetchhead::write_to(entries, url.into(), &mut buf).expect("in-memory writes don't fail");
+    String::from_utf8(buf).expect("valid UTF-8")
+}
+
+#[test]
+fn refs_are_described_by_kind() {
+    assert_eq!(
+        write(
+            &[
+                entry(true, "refs/heads/main"),
+                entry(false, "refs/tags/v1.0"),
+                entry(false, "refs/remotes/origin/main"),
+                entry(false, "refs/notes/commits"),
+                entry(false, "HEAD"),
+            ],
+            "https://example.com/repo"
+        ),
+        "0123456789012345678901234567890123456789\t\tbranch 'main' of https://example.com/repo
+0123456789012345678901234567890123456789\tnot-for-merge\ttag 'v1.0' of https://example.com/repo
+0123456789012345678901234567890123456789\tnot-for-merge\tremote-tracking branch 'origin/main' of https://example.com/repo
+0123456789012345678901234567890123456789\tnot-for-merge\t'refs/notes/commits' of https://example.com/repo
+0123456789012345678901234567890123456789\tnot-for-merge\thttps://example.com/repo
+"
+    );
+}
+
+#[test]
+fn entries_for_merge_come_first() {
+    assert_eq!(
+        write(
+            &[
+                entry(false, "refs/heads/a"),
+                entry(true, "refs/heads/b"),
+                entry(false, "refs/heads/c"),
+                entry(true, "HEAD"),
+            ],
+            "/path/to/repo"
+        ),
+        "0123456789012345678901234567890123456789\t\tbranch 'b' of /path/to/repo
+0123456789012345678901234567890123456789\t\t/path/to/repo
+0123456789012345678901234567890123456789\tnot-for-merge\tbranch 'a' of /path/to/repo
+0123456789012345678901234567890123456789\tnot-for-merge\tbranch 'c' of /path/to/repo
+"
+    );
+}
+
+#[test]
+fn trailing_slashes_and_git_suffix_are_removed_from_the_url() {
+    for (url, expected) in [
+        ("https://example.com/repo.git", "https://example.com/repo"),
+        ("https://example.com/repo.git//", "https://example.com/repo"),
+        ("/path/to/repo/", "/path/to/repo"),
+        ("host:repo.git", "host:repo"),
+        ("a.git", "a.git"),
+        ("b/.git", "b/"),
+        ("/", ""),
+    ] {
+        assert_eq!(
+            write(&[entry(true, "HEAD")], url),
+            format!("0123456789012345678901234567890123456789\t\t{expected}\n"),
+            "{url}"
+        );
+    }
+}
@@ -65,15 +65,14 @@ impl Operation {
 ///
 /// ### Lifecycle
 ///
-/// Note that [`shutdown()`][State::shutdown()] must be called to finalize long-running processes.
-/// Failing to do so will naturally shut them down by terminating their pipes, but finishing explicitly
-/// allows to wait for processes as well.
+/// Long-running processes are launched once and reused for all files they are asked to filter.
+/// When dropped, they are shut down by terminating their pipes and waited for, ignoring errors.
+/// Call [`shutdown()`][State::shutdown()] to learn about their exit status, or to not wait for them at all.
 #[derive(Default)]
 pub struct State {
     /// The list of currently running processes. These are preferred over simple clean-and-smudge programs.
     ///
-    /// Note that these processes are expected to shut-down once their stdin/stdout are dropped, so nothing else
-    /// needs to be done to clean them up after drop.
+    /// Note that these processes are expected to shut-down once their stdin/stdout are dropped.
     running: HashMap<BString, process::Client>,
 
     /// The context to pass to spawned filter programs.
@@ -16,9 +16,10 @@ pub enum Mode {
 impl State {
     /// Handle long-running processes according to `mode`. If an error occurs, all remaining processes will be ignored automatically.
     /// Return a list of `(process, Option<status>)`
-    pub fn shutdown(self, mode: Mode) -> Result<Vec<(BString, Option<std::process::ExitStatus>)>, std::io::Error> {
-        let mut out = Vec::with_capacity(self.running.len());
-        for (cmd, client) in self.running {
+    pub fn shutdown(mut self, mode: Mode) -> Result<Vec<(BString, Option<std::process::ExitStatus>)>, std::io::Error> {
+        let running = std::mem::take(&mut self.running);
+        let mut out = Vec::with_capacity(running.len());
+        for (cmd, client) in running {
             match mode {
                 Mode::WaitForProcesses => {
                     let mut child = client.into_child();
@@ -33,3 +34,13 @@ impl State {
         Ok(out)
     }
 }
+
+impl Drop for State {
+    /// Shut down all long-running processes and wait for them, like [`shutdown(Mode::WaitForProcesses)`](State::shutdown()) would,
+    /// but ignore all errors.
+    fn drop(&mut self) {
+        for (_cmd, client) in self.running.drain() {
+            client.into_child().wait().ok();
+        }
+    }
+}
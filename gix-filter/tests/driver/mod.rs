@@ -55,6 +55,24 @@ mod shutdown {
         );
         Ok(())
     }
+
+    #[test]
+    fn drop_waits_for_processes() -> crate::Result {
+        let mut state = gix_filter::driver::State::default();
+        let driver = driver_with_process();
+        let client = extract_client(state.maybe_launch_process(&driver, Operation::Clean, "does not matter".into())?);
+        assert!(client
+            .invoke("wait-1-s", &mut None.into_iter(), &mut &b""[..])?
+            .is_success());
+
+        let start = std::time::Instant::now();
+        drop(state);
+        assert!(
+            start.elapsed() >= Duration::from_millis(500),
+            "dropping the state shuts the process down and waits for it to finish its work"
+        );
+        Ok(())
+    }
 }
 
 pub(crate) mod apply {
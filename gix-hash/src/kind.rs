@@ -8,6 +8,8 @@ impl TryFrom<u8> for Kind {
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         Ok(match value {
             1 => Kind::Sha1,
+            #[cfg(feature = "sha256")]
+            2 => Kind::Sha256,
             unknown => return Err(unknown),
         })
     }
@@ -19,6 +21,8 @@ impl FromStr for Kind {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "sha1" | "SHA1" => Kind::Sha1,
+            #[cfg(feature = "sha256")]
+            "sha256" | "SHA256" => Kind::Sha256,
             other => return Err(other.into()),
         })
     }
@@ -28,6 +32,8 @@ impl std::fmt::Display for Kind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Kind::Sha1 => f.write_str("SHA1"),
+            #[cfg(feature = "sha256")]
+            Kind::Sha256 => f.write_str("SHA256"),
         }
     }
 }
@@ -42,7 +48,14 @@ impl Kind {
     /// Returns the longest hash we support.
     #[inline]
     pub const fn longest() -> Self {
-        Self::Sha1
+        #[cfg(feature = "sha256")]
+        {
+            Self::Sha256
+        }
+        #[cfg(not(feature = "sha256"))]
+        {
+            Self::Sha1
+        }
     }
 
     /// Returns a buffer suitable to hold the longest possible hash in hex.
@@ -62,6 +75,8 @@ impl Kind {
     pub const fn len_in_hex(&self) -> usize {
         match self {
             Kind::Sha1 => 40,
+            #[cfg(feature = "sha256")]
+            Kind::Sha256 => 64,
         }
     }
     /// Returns the amount of bytes taken up by the hash of this instance.
@@ -69,15 +84,20 @@ impl Kind {
     pub const fn len_in_bytes(&self) -> usize {
         match self {
             Kind::Sha1 => 20,
+            #[cfg(feature = "sha256")]
+            Kind::Sha256 => 32,
         }
     }
 
     /// Returns the kind of hash that would fit the given `hex_len`, or `None` if there is no fitting hash.
-    /// Note that `0` as `hex_len` up to 40 always yields `Sha1`.
+    /// Note that `0` as `hex_len` up to 40 always yields `Sha1`, and everything longer up to 64 yields `Sha256`
+    /// if the `sha256` feature is enabled.
     #[inline]
     pub const fn from_hex_len(hex_len: usize) -> Option<Self> {
         Some(match hex_len {
             0..=40 => Kind::Sha1,
+            #[cfg(feature = "sha256")]
+            41..=64 => Kind::Sha256,
             _ => return None,
         })
     }
@@ -94,6 +114,8 @@ impl Kind {
     pub(crate) fn from_len_in_bytes(bytes: usize) -> Self {
         match bytes {
             20 => Kind::Sha1,
+            #[cfg(feature = "sha256")]
+            32 => Kind::Sha256,
             _ => panic!("BUG: must be called only with valid hash lengths produced by len_in_bytes()"),
         }
     }
@@ -103,6 +125,8 @@ impl Kind {
     pub fn null_ref(&self) -> &'static oid {
         match self {
             Kind::Sha1 => oid::null_sha1(),
+            #[cfg(feature = "sha256")]
+            Kind::Sha256 => oid::null_sha256(),
         }
     }
 
@@ -111,6 +135,8 @@ impl Kind {
     pub const fn null(&self) -> ObjectId {
         match self {
             Kind::Sha1 => ObjectId::null_sha1(),
+            #[cfg(feature = "sha256")]
+            Kind::Sha256 => ObjectId::null_sha256(),
         }
     }
 }
@@ -1,5 +1,7 @@
 use std::hash;
 
+#[cfg(feature = "sha256")]
+use crate::SIZE_OF_SHA256_DIGEST;
 use crate::{Kind, ObjectId, SIZE_OF_SHA1_DIGEST};
 
 /// A borrowed reference to a hash identifying objects.
@@ -57,6 +59,8 @@ impl std::fmt::Debug for oid {
             "{}({})",
             match self.kind() {
                 Kind::Sha1 => "Sha1",
+                #[cfg(feature = "sha256")]
+                Kind::Sha256 => "Sha256",
             },
             self.to_hex(),
         )
@@ -77,12 +81,14 @@ impl oid {
     #[inline]
     pub fn try_from_bytes(digest: &[u8]) -> Result<&Self, Error> {
         match digest.len() {
-            20 => Ok(
+            SIZE_OF_SHA1_DIGEST => Ok(
                 #[allow(unsafe_code)]
                 unsafe {
                     &*(digest as *const [u8] as *const oid)
                 },
             ),
+            #[cfg(feature = "sha256")]
+            SIZE_OF_SHA256_DIGEST => Ok(oid::from_bytes(digest)),
             len => Err(Error::InvalidByteSliceLength(len)),
         }
     }
@@ -144,9 +150,7 @@ impl oid {
     #[inline]
     #[doc(alias = "is_zero", alias = "git2")]
     pub fn is_null(&self) -> bool {
-        match self.kind() {
-            Kind::Sha1 => &self.bytes == oid::null_sha1().as_bytes(),
-        }
+        self.bytes.iter().all(|b| *b == 0)
     }
 }
 
@@ -176,6 +180,13 @@ impl oid {
     pub(crate) fn null_sha1() -> &'static Self {
         oid::from_bytes([0u8; SIZE_OF_SHA1_DIGEST].as_ref())
     }
+
+    #[cfg(feature = "sha256")]
+    /// Returns a Sha256 digest with all bytes being initialized to zero.
+    #[inline]
+    pub(crate) fn null_sha256() -> &'static Self {
+        oid::from_bytes([0u8; SIZE_OF_SHA256_DIGEST].as_ref())
+    }
 }
 
 impl AsRef<oid> for &oid {
@@ -198,6 +209,8 @@ impl ToOwned for oid {
     fn to_owned(&self) -> Self::Owned {
         match self.kind() {
             Kind::Sha1 => ObjectId::Sha1(self.bytes.try_into().expect("no bug in hash detection")),
+            #[cfg(feature = "sha256")]
+            Kind::Sha256 => ObjectId::Sha256(self.bytes.try_into().expect("no bug in hash detection")),
         }
     }
 }
@@ -208,6 +221,13 @@ impl<'a> From<&'a [u8; SIZE_OF_SHA1_DIGEST]> for &'a oid {
     }
 }
 
+#[cfg(feature = "sha256")]
+impl<'a> From<&'a [u8; SIZE_OF_SHA256_DIGEST]> for &'a oid {
+    fn from(v: &'a [u8; SIZE_OF_SHA256_DIGEST]) -> Self {
+        oid::from_bytes(v.as_ref())
+    }
+}
+
 impl std::fmt::Display for &oid {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for b in self.as_bytes() {
@@ -12,6 +12,22 @@ mod from_hex {
         fn twenty_hex_chars_uppercase() {
             assert!(ObjectId::from_hex(b"1234567890ABCDEFAAAAAAAAAAAAAAAAAAAAAAAA").is_ok());
         }
+
+        #[cfg(feature = "sha256")]
+        #[test]
+        fn sixty_four_hex_chars() {
+            let id = ObjectId::from_hex(b"1234567890abcdefaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa").unwrap();
+            assert_eq!(id.kind(), gix_hash::Kind::Sha256);
+        }
+
+        #[test]
+        fn of_kind() {
+            let hex = b"1234567890abcdefaaaaaaaaaaaaaaaaaaaaaaaa";
+            assert_eq!(
+                ObjectId::from_hex_of_kind(hex, gix_hash::Kind::Sha1).unwrap(),
+                ObjectId::from_hex(hex).unwrap()
+            );
+        }
     }
 
     mod invalid {
@@ -39,6 +55,16 @@ mod from_hex {
                 decode::Error::InvalidHexEncodingLength(41)
             ));
         }
+
+        #[cfg(feature = "sha256")]
+        #[test]
+        fn length_not_matching_kind() {
+            assert!(matches!(
+                ObjectId::from_hex_of_kind(b"1234567890abcdefaaaaaaaaaaaaaaaaaaaaaaaa", gix_hash::Kind::Sha256)
+                    .unwrap_err(),
+                decode::Error::InvalidHexEncodingLength(40)
+            ));
+        }
     }
 }
 
@@ -49,16 +75,28 @@ mod empty {
     fn hash_contents(s: &[u8]) -> ObjectId {
         let mut hasher = hasher(Kind::Sha1);
         hasher.update(s);
-        ObjectId::Sha1(hasher.digest())
+        hasher.digest()
     }
 
     #[test]
     fn blob() {
         assert_eq!(ObjectId::empty_blob(Kind::Sha1), hash_contents(b"blob 0\0"));
+        #[cfg(feature = "sha256")]
+        assert_eq!(
+            ObjectId::empty_blob(Kind::Sha256).to_string(),
+            "473a0f4c3be8a93681a267e3b1e9a7dcda1185436fe141f7749120a303721813",
+            "as produced by `git hash-object --stdin` with a SHA256 repository"
+        );
     }
 
     #[test]
     fn tree() {
         assert_eq!(ObjectId::empty_tree(Kind::Sha1), hash_contents(b"tree 0\0"));
+        #[cfg(feature = "sha256")]
+        assert_eq!(
+            ObjectId::empty_tree(Kind::Sha256).to_string(),
+            "6ef19b41225c5369f1c104d45d8d85efa9b057b53b14b4b9b939dd74decc5321",
+            "as produced by `git mktree` with a SHA256 repository"
+        );
     }
 }
@@ -166,6 +166,9 @@ impl Search {
     /// Match `relative_path` and return the first match if found.
     /// `is_dir` is true if `relative_path` is a directory.
     /// `case` specifies whether cases should be folded during matching or not.
+    ///
+    /// Note that only `relative_path` itself is matched, while git also won't re-include paths whose leading directories
+    /// are excluded, even if a negated pattern matches them. Callers should thus match the leading directories first.
     pub fn pattern_matching_relative_path(
         &self,
         relative_path: &BStr,
@@ -37,12 +37,21 @@ EOF
 top-level-local-file-anywhere
 d/e/*
 e/f
+*.log
+!important.log
+build/
+ignored-dir/
+!ignored-dir/reincluded
 EOF
 
   mkdir dir-with-ignore
   cat <<EOF >dir-with-ignore/.gitignore
 # a sample .gitignore
 sub-level-local-file-anywhere
+!top-level-local-file-anywhere
+/anchored
+mid/slash
+!*.log
 EOF
 
   git add .gitignore dir-with-ignore
@@ -50,6 +59,8 @@ EOF
 
   mkdir user-dir-anywhere user-dir-from-top dir-anywhere dir-from-top
   mkdir -p dir/user-dir-anywhere dir/dir-anywhere
+  mkdir build file-in-place-of-dir ignored-dir
+  touch file-in-place-of-dir/build ignored-dir/reincluded ignored-dir/other
 
 git check-ignore -vn --stdin 2>&1 <<EOF >git-check-ignore.baseline || :
 user-file-anywhere
@@ -91,5 +102,24 @@ e/f
 e/F
 E/f
 E/F
+a.log
+dir/a.log
+important.log
+dir/important.log
+dir-with-ignore/a.log
+dir-with-ignore/sub-dir/a.log
+build
+file-in-place-of-dir/build
+dir-with-ignore/top-level-local-file-anywhere
+dir-with-ignore/sub-dir/top-level-local-file-anywhere
+anchored
+dir-with-ignore/anchored
+dir-with-ignore/sub-dir/anchored
+mid/slash
+dir-with-ignore/mid/slash
+dir-with-ignore/sub-dir/mid/slash
+ignored-dir
+ignored-dir/other
+ignored-dir/reincluded
 EOF
 )
@@ -66,26 +66,42 @@ fn baseline_from_git_dir() -> crate::Result {
     for (path, source_and_line) in (Expectations {
         lines: baseline.lines(),
     }) {
-        let actual = group.pattern_matching_relative_path(
-            path,
-            repo_dir
-                .join(path.to_str_lossy().as_ref())
-                .metadata()
-                .ok()
-                .map(|m| m.is_dir()),
-            case,
-        );
+        // Like `git check-ignore`, match the leading directories first, as nothing inside an excluded directory
+        // can be re-included, not even by a negated pattern matching it. Matching a single path doesn't know its parents,
+        // so this is up to the caller.
+        let excluded_parent = path
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| **b == b'/')
+            .map(|(idx, _)| path[..idx].as_bstr())
+            .find_map(|dir| {
+                group
+                    .pattern_matching_relative_path(dir, Some(true), case)
+                    .filter(|m| !m.pattern.is_negative())
+            });
+        let actual = excluded_parent.or_else(|| {
+            group.pattern_matching_relative_path(
+                path,
+                repo_dir
+                    .join(path.to_str_lossy().as_ref())
+                    .metadata()
+                    .ok()
+                    .map(|m| m.is_dir()),
+                case,
+            )
+        });
         match (actual, source_and_line) {
             (
                 Some(Match {
                     sequence_number,
-                    pattern: _,
+                    pattern,
                     source,
                     kind: gix_ignore::Kind::Expendable,
                 }),
-                Some((expected_source, line, _expected_pattern)),
+                Some((expected_source, line, expected_pattern)),
             ) => {
                 assert_eq!(sequence_number, line, "our counting should match the one used in git");
+                assert_eq!(pattern.to_string(), expected_pattern, "{path}");
                 assert_eq!(
                     source.map(|p| p.canonicalize().unwrap()),
                     Some(repo_dir.join(expected_source.to_str_lossy().as_ref()).canonicalize()?)
@@ -100,6 +116,30 @@ fn baseline_from_git_dir() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn negations_cannot_reinclude_paths_in_excluded_directories() {
+    let group = gix_ignore::Search::from_overrides(["ignored-dir/", "!ignored-dir/reincluded"]);
+    let matched = group
+        .pattern_matching_relative_path("ignored-dir/reincluded".into(), Some(false), Case::Sensitive)
+        .expect("matches");
+    assert!(
+        matched.pattern.is_negative(),
+        "in isolation, the path is re-included by the negated pattern"
+    );
+    let matched = group
+        .pattern_matching_relative_path("ignored-dir".into(), Some(true), Case::Sensitive)
+        .expect("matches");
+    assert!(
+        !matched.pattern.is_negative(),
+        "but its leading directory is excluded, so git doesn't even look at its contents"
+    );
+    assert_eq!(
+        group.pattern_matching_relative_path("ignored-dir".into(), Some(false), Case::Sensitive),
+        None,
+        "directory-only patterns don't match files"
+    );
+}
+
 #[test]
 fn from_overrides_with_precious() {
     let input = ["$s?mple", "pattern/"];
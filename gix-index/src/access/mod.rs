@@ -473,6 +473,8 @@ impl State {
     ///
     /// Alternatively, make sure to call [`sort_entries()`][State::sort_entries()] before entry lookup by path to restore
     /// the invariant.
+    ///
+    /// The [tree extension](State::tree()) is invalidated for `path`.
     pub fn dangerously_push_entry(
         &mut self,
         stat: entry::Stat,
@@ -487,6 +489,9 @@ impl State {
             path_start..self.path_backing.len()
         };
 
+        if let Some(tree) = self.tree.as_mut() {
+            tree.invalidate_path(self.path_backing[path.clone()].as_bstr());
+        }
         self.entries.push(Entry {
             stat,
             id,
@@ -519,6 +524,7 @@ impl State {
     /// Physically remove all entries for which `should_remove(idx, path, entry)` returns `true`, traversing them from first to last.
     ///
     /// Note that the memory used for the removed entries paths is not freed, as it's append-only.
+    /// The [tree extension](State::tree()) is invalidated for the paths of all removed entries.
     ///
     /// ### Performance
     ///
@@ -527,10 +533,16 @@ impl State {
     pub fn remove_entries(&mut self, mut should_remove: impl FnMut(usize, &BStr, &mut Entry) -> bool) {
         let mut index = 0;
         let paths = &self.path_backing;
+        let tree = &mut self.tree;
         self.entries.retain_mut(|e| {
             let path = e.path_in(paths);
             let res = !should_remove(index, path, e);
             index += 1;
+            if !res {
+                if let Some(tree) = tree.as_mut() {
+                    tree.invalidate_path(path);
+                }
+            }
             res
         });
     }
@@ -542,6 +554,11 @@ impl State {
     pub fn tree(&self) -> Option<&extension::Tree> {
         self.tree.as_ref()
     }
+    /// Access the `tree` extension mutably, for instance to [invalidate](extension::Tree::invalidate_path()) the paths
+    /// of entries that were changed through [`entries_mut()`](State::entries_mut()).
+    pub fn tree_mut(&mut self) -> Option<&mut extension::Tree> {
+        self.tree.as_mut()
+    }
     /// Access the `link` extension.
     pub fn link(&self) -> Option<&extension::Link> {
         self.link.as_ref()
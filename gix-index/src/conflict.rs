@@ -0,0 +1,156 @@
+use bstr::{BStr, ByteSlice};
+
+use crate::{entry, extension::resolve_undo, Entry, State};
+
+/// The entries of a conflicted path, with each of them being optional as, for instance, a path deleted by one side
+/// of a merge and modified by the other has no entry for the deleting side.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Conflict<'a> {
+    /// The path all entries share.
+    pub path: &'a BStr,
+    /// The entry at stage 1, the common ancestor of `ours` and `theirs`.
+    pub base: Option<&'a Entry>,
+    /// The entry at stage 2, our version of the path.
+    pub ours: Option<&'a Entry>,
+    /// The entry at stage 3, their version of the path.
+    pub theirs: Option<&'a Entry>,
+}
+
+/// The version of a path on one side of a conflict, or the version to resolve a conflict with.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Side {
+    /// The kind of the entry.
+    pub mode: entry::Mode,
+    /// The id of the object the entry points to.
+    pub id: gix_hash::ObjectId,
+}
+
+impl From<&Entry> for Side {
+    fn from(entry: &Entry) -> Self {
+        Side {
+            mode: entry.mode,
+            id: entry.id,
+        }
+    }
+}
+
+/// An iterator over all [conflicts](Conflict) of a [`State`], in the order of their paths.
+pub struct Iter<'a> {
+    state: &'a State,
+    entries: &'a [Entry],
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Conflict<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.entries.iter().position(|e| e.stage_raw() != 0)?;
+        let path = self.entries[start].path(self.state);
+        let mut conflict = Conflict {
+            path,
+            base: None,
+            ours: None,
+            theirs: None,
+        };
+        let mut end = start;
+        for entry in &self.entries[start..] {
+            if entry.path(self.state) != path {
+                break;
+            }
+            match entry.stage() {
+                entry::Stage::Unconflicted => {}
+                entry::Stage::Base => conflict.base = Some(entry),
+                entry::Stage::Ours => conflict.ours = Some(entry),
+                entry::Stage::Theirs => conflict.theirs = Some(entry),
+            }
+            end += 1;
+        }
+        self.entries = &self.entries[end..];
+        Some(conflict)
+    }
+}
+
+/// Conflicts
+impl State {
+    /// Return an iterator over all conflicted paths, with the entries of all of their stages grouped into a [`Conflict`].
+    ///
+    /// Note that entries must be sorted, which is the case unless [`dangerously_push_entry()`](State::dangerously_push_entry())
+    /// was used without [sorting](State::sort_entries()) afterwards.
+    pub fn conflicts(&self) -> Iter<'_> {
+        Iter {
+            state: self,
+            entries: &self.entries,
+        }
+    }
+
+    /// Return the conflict at `path`, or `None` if it's not conflicted.
+    pub fn conflict_by_path(&self, path: &BStr) -> Option<Conflict<'_>> {
+        let range = self.entry_range(path)?;
+        Iter {
+            state: self,
+            entries: &self.entries[range],
+        }
+        .next()
+    }
+
+    /// Replace all entries at `path` with a conflict made of `base`, `ours` and `theirs` as stage 1, 2 and 3 respectively,
+    /// similar to what a merge would produce, and remove the record of a previous resolution of `path` from the
+    /// [resolve-undo extension](State::resolve_undo()).
+    ///
+    /// Note that the new entries have no stat information, and that all entries at `path` are removed if all sides are `None`.
+    pub fn add_conflict(&mut self, path: &BStr, base: Option<Side>, ours: Option<Side>, theirs: Option<Side>) {
+        self.remove_entries(|_, entry_path, _| entry_path == path);
+        for (stage, side) in [
+            (entry::Stage::Base, base),
+            (entry::Stage::Ours, ours),
+            (entry::Stage::Theirs, theirs),
+        ] {
+            if let Some(Side { mode, id }) = side {
+                self.dangerously_push_entry(entry::Stat::default(), id, entry::Flags::from_stage(stage), mode, path);
+            }
+        }
+        self.sort_entries();
+        if let Some(paths) = self.resolve_undo.as_mut() {
+            paths.retain(|p| p.name != path);
+        }
+    }
+
+    /// Resolve the conflict at `path` by replacing the entries of all its stages with a single unconflicted entry
+    /// of `resolution`, or by removing them if `resolution` is `None`, like `git add` and `git rm` would.
+    /// Return `false` if `path` wasn't conflicted, in which case nothing is changed.
+    ///
+    /// The conflicting stages are recorded in the [resolve-undo extension](State::resolve_undo()) so that the conflict can be
+    /// recreated later. Note that the new entry has no stat information.
+    pub fn resolve_conflict(&mut self, path: &BStr, resolution: Option<Side>) -> bool {
+        let Some(conflict) = self.conflict_by_path(path) else {
+            return false;
+        };
+        let to_stage = |entry: Option<&Entry>| {
+            entry.map(|entry| resolve_undo::Stage {
+                mode: entry.mode.bits(),
+                id: entry.id,
+            })
+        };
+        let undo = resolve_undo::ResolvePath {
+            name: path.to_owned(),
+            stages: [
+                to_stage(conflict.base),
+                to_stage(conflict.ours),
+                to_stage(conflict.theirs),
+            ],
+        };
+
+        self.remove_entries(|_, entry_path, _| entry_path == path);
+        if let Some(Side { mode, id }) = resolution {
+            self.dangerously_push_entry(entry::Stat::default(), id, entry::Flags::empty(), mode, path);
+            self.sort_entries();
+        }
+
+        let paths = self.resolve_undo.get_or_insert_with(Default::default);
+        match paths.binary_search_by(|p| p.name.as_bstr().cmp(path)) {
+            Ok(idx) => paths[idx] = undo,
+            Err(idx) => paths.insert(idx, undo),
+        }
+        true
+    }
+}
@@ -71,6 +71,11 @@ impl Flags {
         }
     }
 
+    /// Return flags with nothing but `stage` set.
+    pub fn from_stage(stage: Stage) -> Self {
+        Flags::from_bits_retain((stage as u32) << 12)
+    }
+
     /// Return an entry's stage as raw number between 0 and 4.
     /// Possible values are:
     ///
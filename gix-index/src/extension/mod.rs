@@ -91,7 +91,9 @@ pub(crate) mod index_entry_offset_table;
 #[allow(clippy::empty_docs)]
 pub mod link;
 
-pub(crate) mod resolve_undo;
+///
+#[allow(clippy::empty_docs)]
+pub mod resolve_undo;
 
 ///
 #[allow(clippy::empty_docs)]
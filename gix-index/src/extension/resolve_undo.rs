@@ -6,27 +6,32 @@ use crate::{
     util::{split_at_byte_exclusive, split_at_pos},
 };
 
+/// All paths whose conflicts were resolved, in the order they are stored in the extension.
 pub type Paths = Vec<ResolvePath>;
 
-#[allow(dead_code)]
-#[derive(Clone)]
+/// The conflicting stages of a path before the conflict was resolved, so the conflict can be recreated.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ResolvePath {
     /// relative to the root of the repository, or what would be stored in the index
-    name: BString,
+    pub name: BString,
 
     /// 0 = ancestor/common, 1 = ours, 2 = theirs
-    stages: [Option<Stage>; 3],
+    pub stages: [Option<Stage>; 3],
 }
 
-#[allow(dead_code)]
-#[derive(Clone, Copy)]
+/// A single conflicting stage of a [`ResolvePath`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Stage {
-    mode: u32,
-    id: ObjectId,
+    /// The mode of the entry at this stage.
+    pub mode: u32,
+    /// The id of the object at this stage.
+    pub id: ObjectId,
 }
 
+/// The signature of the resolve-undo extension.
 pub const SIGNATURE: Signature = *b"REUC";
 
+/// Decode the resolve-undo extension from `data`, with object ids of kind `object_hash`.
 pub fn decode(mut data: &[u8], object_hash: gix_hash::Kind) -> Option<Paths> {
     let hash_len = object_hash.len_in_bytes();
     let mut out = Vec::new();
@@ -62,3 +67,25 @@ pub fn decode(mut data: &[u8], object_hash: gix_hash::Kind) -> Option<Paths> {
     }
     out.into()
 }
+
+/// Serialize `paths` as resolve-undo extension to `out`, in the same format that [`decode()`] reads.
+pub fn write_to(mut out: impl std::io::Write, paths: &Paths) -> Result<(), std::io::Error> {
+    let mut data = Vec::new();
+    for path in paths {
+        data.extend_from_slice(&path.name);
+        data.push(0);
+        for stage in &path.stages {
+            let mode = stage.map_or(0, |stage| stage.mode);
+            data.extend_from_slice(format!("{mode:o}").as_bytes());
+            data.push(0);
+        }
+        for stage in path.stages.iter().flatten() {
+            data.extend_from_slice(stage.id.as_bytes());
+        }
+    }
+
+    out.write_all(&SIGNATURE)?;
+    out.write_all(&(u32::try_from(data.len()).expect("less than 4GB resolve-undo extension")).to_be_bytes())?;
+    out.write_all(&data)?;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use bstr::{BStr, ByteSlice};
+
+use crate::extension::Tree;
+
+impl Tree {
+    /// Mark this tree and all trees leading up to the entry at `path` as invalid, as the entry was added, removed or changed.
+    /// If `path` itself names a sub-tree, it is removed entirely.
+    ///
+    /// This is what git does to keep the unaffected portions of the tree cache usable after the index was changed.
+    pub fn invalidate_path(&mut self, path: &BStr) {
+        self.num_entries = None;
+        match path.find_byte(b'/') {
+            Some(pos) => {
+                let (name, rest) = (&path[..pos], &path[pos + 1..]);
+                if let Some(child) = self.children.iter_mut().find(|child| child.name.as_slice() == name) {
+                    child.invalidate_path(rest.as_bstr());
+                }
+            }
+            None => self.children.retain(|child| child.name.as_slice() != path.as_bytes()),
+        }
+    }
+}
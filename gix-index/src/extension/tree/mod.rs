@@ -12,6 +12,8 @@ pub use decode::decode;
 
 mod write;
 
+mod invalidate;
+
 #[cfg(test)]
 mod tests {
 
@@ -45,6 +45,14 @@ pub mod verify;
 #[allow(clippy::empty_docs)]
 pub mod write;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod refresh;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod conflict;
+
 pub mod fs;
 
 /// All known versions of a git index file.
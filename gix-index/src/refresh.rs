@@ -0,0 +1,117 @@
+use std::path::Path;
+
+use bstr::BString;
+
+use crate::{entry, State};
+
+/// Options for use in [`State::refresh()`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// Options that control which stat fields are compared, typically as configured by `core.checkStat` and `core.trustCtime`.
+    pub stat: entry::stat::Options,
+    /// Capabilities of the file system which affect how file modes are compared.
+    pub fs: gix_fs::Capabilities,
+}
+
+/// The outcome of [`State::refresh()`].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of entries whose stat information matched, and which were marked as up-to-date.
+    pub num_up_to_date: usize,
+    /// The paths of all entries that are definitely modified in the worktree, as their stat or type doesn't match, or they were removed.
+    pub modified: Vec<BString>,
+    /// The paths of all entries whose stat information matches, but which are racily clean and thus need their content
+    /// to be compared to be sure they are unchanged.
+    pub needs_content_comparison: Vec<BString>,
+}
+
+/// The error returned by [`State::refresh()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not obtain the metadata of '{}'", path.display())]
+    Io {
+        source: std::io::Error,
+        path: std::path::PathBuf,
+    },
+    #[error("A file modification time was before the unix epoch")]
+    Time(#[from] std::time::SystemTimeError),
+}
+
+impl State {
+    /// Compare the stat information of all our entries with the files in the `worktree` directory, similar to
+    /// `git update-index --refresh`, and return an [`Outcome`] that tells which paths are modified.
+    ///
+    /// Entries whose stat information matches according to `options` are marked as [up-to-date](entry::Flags::UPTODATE)
+    /// and receive the current stat information. If the stat information matches but the file was modified at or after
+    /// our [timestamp](State::timestamp()), the entry is racily clean and has to be compared by content, which is left to the caller.
+    /// Entries that are assumed valid, skipped in the worktree, intended to be added, conflicted or submodules are ignored.
+    pub fn refresh(&mut self, worktree: &Path, options: Options) -> Result<Outcome, Error> {
+        let timestamp = self.timestamp;
+        let mut outcome = Outcome::default();
+        for (entry, rela_path) in self.entries_mut_with_paths() {
+            if entry.flags.intersects(
+                entry::Flags::ASSUME_VALID
+                    | entry::Flags::SKIP_WORKTREE
+                    | entry::Flags::FSMONITOR_VALID
+                    | entry::Flags::INTENT_TO_ADD
+                    | entry::Flags::REMOVE,
+            ) || entry.stage_raw() != 0
+                || entry.mode == entry::Mode::COMMIT
+            {
+                continue;
+            }
+            entry.flags.remove(entry::Flags::UPTODATE);
+
+            let path = worktree.join(gix_path::from_bstr(rela_path));
+            let metadata = match crate::fs::Metadata::from_path_no_follow(&path) {
+                Ok(metadata) => metadata,
+                Err(err) if is_missing(&err) => {
+                    outcome.modified.push(rela_path.to_owned());
+                    continue;
+                }
+                Err(err) => return Err(Error::Io { source: err, path }),
+            };
+            if entry
+                .mode
+                .change_to_match_fs(&metadata, options.fs.symlink, options.fs.executable_bit)
+                .is_some()
+            {
+                outcome.modified.push(rela_path.to_owned());
+                continue;
+            }
+
+            let new_stat = entry::Stat::from_fs(&metadata)?;
+            if new_stat.matches(&entry.stat, options.stat) {
+                // An empty entry with a non-empty object was smudged after it was found to be racily clean, see `racy-git.txt`.
+                if entry.stat.size == 0 && !entry.id.is_empty_blob() {
+                    outcome.modified.push(rela_path.to_owned());
+                } else if new_stat.is_racy(timestamp, options.stat) {
+                    outcome.needs_content_comparison.push(rela_path.to_owned());
+                } else {
+                    entry.stat = new_stat;
+                    entry.flags.insert(entry::Flags::UPTODATE);
+                    outcome.num_up_to_date += 1;
+                }
+            } else if new_stat.size != entry.stat.size && entry.stat.size != 0 {
+                outcome.modified.push(rela_path.to_owned());
+            } else {
+                // Only the times or the inode changed, like after `touch`, which says nothing about the content.
+                outcome.needs_content_comparison.push(rela_path.to_owned());
+            }
+        }
+        Ok(outcome)
+    }
+}
+
+fn is_missing(err: &std::io::Error) -> bool {
+    if err.kind() == std::io::ErrorKind::NotFound {
+        return true;
+    }
+    // A leading path component turned into a file, which git treats like a deleted file as well.
+    #[cfg(not(windows))]
+    if err.raw_os_error() == Some(libc::ENOTDIR) {
+        return true;
+    }
+    false
+}
@@ -17,6 +17,8 @@ pub enum Extensions {
     Given {
         /// Write the tree-cache extension, if present.
         tree_cache: bool,
+        /// Write the resolve-undo extension, if present.
+        resolve_undo: bool,
         /// Write the end-of-index-entry extension.
         end_of_index_entry: bool,
     },
@@ -32,9 +34,11 @@ impl Extensions {
             Extensions::All => Some(signature),
             Extensions::Given {
                 tree_cache,
+                resolve_undo,
                 end_of_index_entry,
             } => match signature {
                 extension::tree::SIGNATURE => tree_cache,
+                extension::resolve_undo::SIGNATURE => resolve_undo,
                 extension::end_of_index_entry::SIGNATURE => end_of_index_entry,
                 _ => &false,
             }
@@ -115,7 +119,33 @@ impl State {
             &|write| {
                 extensions
                     .should_write(extension::tree::SIGNATURE)
-                    .and_then(|signature| self.tree().map(|tree| tree.write_to(write).map(|_| signature)))
+                    .and_then(|signature| {
+                        self.tree().map(|tree| {
+                            let mut removed = self
+                                .entries()
+                                .iter()
+                                .filter(|e| e.flags.contains(entry::Flags::REMOVE))
+                                .peekable();
+                            if removed.peek().is_none() {
+                                tree.write_to(write)
+                            } else {
+                                let mut tree = tree.clone();
+                                for entry in removed {
+                                    tree.invalidate_path(entry.path(self));
+                                }
+                                tree.write_to(write)
+                            }
+                            .map(|_| signature)
+                        })
+                    })
+            },
+            &|write| {
+                extensions
+                    .should_write(extension::resolve_undo::SIGNATURE)
+                    .and_then(|signature| {
+                        self.resolve_undo()
+                            .map(|paths| extension::resolve_undo::write_to(write, paths).map(|_| signature))
+                    })
             },
             &|write| {
                 self.is_sparse()
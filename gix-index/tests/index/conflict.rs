@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use bstr::ByteSlice;
+use gix_index::{conflict::Side, entry::Stage};
+
+use crate::index::Fixture;
+
+/// Create a merge conflict in `dir` in which `a` is modified on both sides, and `b` is deleted by `theirs`, and return the index.
+fn merge_conflict(dir: &Path) -> crate::Result<gix_index::File> {
+    let git = |args: &[&str]| -> crate::Result {
+        assert!(gix_testtools::run_git(dir, args)?.success(), "{args:?}");
+        Ok(())
+    };
+    git(&["config", "user.name", "committer"])?;
+    git(&["config", "user.email", "committer@example.com"])?;
+    git(&["checkout", "-q", "-b", "theirs"])?;
+    std::fs::write(dir.join("a"), "theirs")?;
+    git(&["rm", "-q", "b"])?;
+    git(&["commit", "-qam", "theirs"])?;
+
+    git(&["checkout", "-q", "-"])?;
+    std::fs::write(dir.join("a"), "ours")?;
+    std::fs::write(dir.join("b"), "ours")?;
+    git(&["commit", "-qam", "ours"])?;
+    assert!(
+        !gix_testtools::run_git(dir, &["merge", "-q", "theirs"])?.success(),
+        "the merge fails with conflicts"
+    );
+    open(dir)
+}
+
+fn open(dir: &Path) -> crate::Result<gix_index::File> {
+    Ok(gix_index::File::at(
+        dir.join(".git").join("index"),
+        gix_hash::Kind::Sha1,
+        false,
+        Default::default(),
+    )?)
+}
+
+#[test]
+fn conflicts_group_the_entries_of_all_stages() -> crate::Result {
+    let file = Fixture::Loose("conflicting-file").open();
+    let conflicts: Vec<_> = file.conflicts().collect();
+    assert_eq!(conflicts.len(), 1);
+    let conflict = conflicts[0];
+    assert_eq!(conflict.path, "file");
+    for (entry, stage) in [
+        (conflict.base, Stage::Base),
+        (conflict.ours, Stage::Ours),
+        (conflict.theirs, Stage::Theirs),
+    ] {
+        assert_eq!(entry.map(gix_index::Entry::stage), Some(stage));
+    }
+    assert_eq!(file.conflict_by_path("file".into()), Some(conflict));
+    assert_eq!(file.conflict_by_path("missing".into()), None);
+
+    let dir = gix_testtools::scripted_fixture_writable_standalone("make_index/v2_more_files.sh")?;
+    let file = merge_conflict(dir.path())?;
+    let conflicts: Vec<_> = file
+        .conflicts()
+        .map(|c| (c.path.to_str_lossy().into_owned(), c))
+        .collect();
+    assert_eq!(
+        conflicts.iter().map(|(path, _)| path.as_str()).collect::<Vec<_>>(),
+        ["a", "b"]
+    );
+    let b = conflicts[1].1;
+    assert!(
+        b.base.is_some() && b.ours.is_some() && b.theirs.is_none(),
+        "stages of deleted sides are missing"
+    );
+    assert_eq!(
+        file.conflict_by_path("c".into()),
+        None,
+        "unconflicted entries aren't conflicts"
+    );
+    Ok(())
+}
+
+#[test]
+fn resolve_conflict_like_git_add_and_git_rm() -> crate::Result {
+    let ours_dir = gix_testtools::scripted_fixture_writable_standalone("make_index/v2_more_files.sh")?;
+    let mut ours = merge_conflict(ours_dir.path())?;
+    let theirs_dir = gix_testtools::scripted_fixture_writable_standalone("make_index/v2_more_files.sh")?;
+    merge_conflict(theirs_dir.path())?;
+
+    let resolution = ours.conflict_by_path("a".into()).and_then(|c| c.ours).map(Side::from);
+    assert!(ours.resolve_conflict("a".into(), resolution));
+    assert!(ours.resolve_conflict("b".into(), None));
+    assert!(
+        !ours.resolve_conflict("c".into(), None),
+        "nothing happens to paths without conflict"
+    );
+    assert_eq!(ours.conflicts().count(), 0, "all conflicts are resolved");
+    ours.write(Default::default())?;
+
+    std::fs::write(theirs_dir.path().join("a"), "ours")?;
+    assert!(gix_testtools::run_git(theirs_dir.path(), &["add", "a"])?.success());
+    assert!(gix_testtools::run_git(theirs_dir.path(), &["rm", "-q", "b"])?.success());
+
+    let (actual, expected) = (open(ours_dir.path())?, open(theirs_dir.path())?);
+    let entries = |index: &gix_index::File| {
+        index
+            .entries()
+            .iter()
+            .map(|e| (e.path(index).to_owned(), e.id, e.mode, e.stage()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(entries(&actual), entries(&expected));
+    assert_eq!(
+        actual.resolve_undo(),
+        expected.resolve_undo(),
+        "the conflicting stages are recorded just like git does it"
+    );
+    assert_eq!(actual.resolve_undo().map(Vec::len), Some(2));
+    Ok(())
+}
+
+#[test]
+fn add_conflict_recreates_a_resolved_conflict() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_writable_standalone("make_index/v2_more_files.sh")?;
+    let mut file = merge_conflict(dir.path())?;
+    let entries_with_conflict = file.entries().to_vec();
+    let sides = {
+        let conflict = file.conflict_by_path("b".into()).expect("conflicted");
+        [conflict.base, conflict.ours, conflict.theirs].map(|e| e.map(Side::from))
+    };
+
+    assert!(file.resolve_conflict("b".into(), sides[1]));
+    assert_eq!(
+        file.entry_by_path_and_stage("b".into(), Stage::Unconflicted)
+            .map(|e| e.id),
+        sides[1].map(|s| s.id)
+    );
+    assert_eq!(file.resolve_undo().map(Vec::len), Some(1));
+
+    let [base, ours, theirs] = sides;
+    file.add_conflict("b".into(), base, ours, theirs);
+    assert_eq!(
+        file.entries()
+            .iter()
+            .map(|e| (e.id, e.flags, e.mode))
+            .collect::<Vec<_>>(),
+        entries_with_conflict
+            .iter()
+            .map(|e| (e.id, e.flags, e.mode))
+            .collect::<Vec<_>>(),
+        "the conflict is the same as before"
+    );
+    assert_eq!(
+        file.resolve_undo().map(Vec::len),
+        Some(0),
+        "the resolution of the recreated conflict is forgotten"
+    );
+    Ok(())
+}
@@ -1,3 +1,6 @@
+use std::path::Path;
+
+use bstr::ByteSlice;
 use filetime::FileTime;
 use gix_index::{entry, extension, write, write::Options, State, Version};
 
@@ -15,11 +18,21 @@ fn roundtrips() -> crate::Result {
             options_with(write::Extensions::Given {
                 tree_cache: true,
                 end_of_index_entry: true,
+                resolve_undo: false,
             }),
         ),
         (Generated("V2_empty"), only_tree_ext()),
         (Generated("v2_more_files"), only_tree_ext()),
         (Generated("v2_all_file_kinds"), only_tree_ext()),
+        (Generated("v2_deeper_tree"), only_tree_ext()),
+        (
+            Loose("REUC"),
+            options_with(write::Extensions::Given {
+                tree_cache: true,
+                end_of_index_entry: false,
+                resolve_undo: true,
+            }),
+        ),
     ];
 
     for (fixture, options) in input {
@@ -149,10 +162,12 @@ fn state_comparisons_with_various_extension_configurations() {
             options_with(write::Extensions::Given {
                 tree_cache: true,
                 end_of_index_entry: false,
+                resolve_undo: false,
             }),
             options_with(write::Extensions::Given {
                 tree_cache: false,
                 end_of_index_entry: true,
+                resolve_undo: false,
             }),
         ] {
             let expected = fixture.open();
@@ -210,6 +225,83 @@ fn remove_flag_is_respected() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn tree_cache_is_invalidated_like_git_does_it() -> crate::Result {
+    let fixture = "make_index/v2_deeper_tree.sh";
+    let ours = gix_testtools::scripted_fixture_writable_standalone(fixture)?;
+    let index_path = ours.path().join(".git").join("index");
+    let mut index = gix_index::File::at(&index_path, gix_hash::Kind::Sha1, false, Default::default())?;
+    assert_eq!(
+        index.tree().expect("present").num_entries,
+        Some(11),
+        "the tree cache is valid after committing"
+    );
+    index.remove_entries(|_, path, _| path == "d/nested/1");
+    index.write(Options::default())?;
+
+    let theirs = gix_testtools::scripted_fixture_writable_standalone(fixture)?;
+    assert!(gix_testtools::run_git(theirs.path(), &["rm", "--cached", "-q", "d/nested/1"])?.success());
+
+    let open = |dir: &Path| {
+        gix_index::File::at(
+            dir.join(".git").join("index"),
+            gix_hash::Kind::Sha1,
+            false,
+            Default::default(),
+        )
+    };
+    let (actual, expected) = (open(ours.path())?, open(theirs.path())?);
+    assert_eq!(
+        actual.tree(),
+        expected.tree(),
+        "the root and all trees leading to the removed entry are invalidated, while other trees are kept"
+    );
+    let tree = actual.tree().expect("present");
+    assert_eq!(tree.num_entries, None);
+    assert!(
+        tree.children.iter().any(|child| child.num_entries.is_some()),
+        "unaffected trees stay valid"
+    );
+
+    let status = |dir: &Path| -> crate::Result<_> {
+        let out = std::process::Command::new("git")
+            .args(["status", "--porcelain", "--untracked-files=no"])
+            .current_dir(dir)
+            .output()?;
+        assert!(out.status.success(), "git can read the index");
+        Ok(out.stdout)
+    };
+    let actual_status = status(ours.path())?;
+    assert_eq!(
+        actual_status.as_bstr(),
+        status(theirs.path())?.as_bstr(),
+        "git sees the same as if it removed the entry itself"
+    );
+    assert!(actual_status.lines().any(|line| line == b"D  d/nested/1"));
+    Ok(())
+}
+
+#[test]
+fn pushed_entries_invalidate_the_tree_cache() {
+    let mut index = Generated("v2_deeper_tree").open();
+    let entry = index.entries()[0].clone();
+    index.dangerously_push_entry(entry.stat, entry.id, entry.flags, entry.mode, "d/new".into());
+
+    let tree = index.tree().expect("present");
+    assert_eq!(tree.num_entries, None, "the root tree is always affected");
+    let d = tree
+        .children
+        .iter()
+        .find(|child| child.name.as_slice() == b"d")
+        .expect("present");
+    assert_eq!(d.num_entries, None, "the tree of the new entry is invalid");
+    assert_eq!(
+        d.children[0].num_entries,
+        Some(1),
+        "the sibling tree isn't affected by the new entry"
+    );
+}
+
 fn compare_states_against_baseline(
     actual: &State,
     actual_version: Version,
@@ -243,6 +335,14 @@ fn compare_states(actual: &State, actual_version: Version, expected: &State, opt
             .and_then(|_| expected.tree()),
         "tree extension mismatch, actual vs option in {fixture:?}"
     );
+    assert_eq!(
+        actual.resolve_undo(),
+        options
+            .extensions
+            .should_write(extension::resolve_undo::SIGNATURE)
+            .and_then(|_| expected.resolve_undo()),
+        "resolve-undo extension mismatch, actual vs option in {fixture:?}"
+    );
 
     // As `write_to` does / should not mutate we can test those properties here.
     // Anything that can be configured has to be tested separately when comparing against baseline
@@ -292,6 +392,7 @@ fn only_tree_ext() -> Options {
     Options {
         extensions: write::Extensions::Given {
             end_of_index_entry: false,
+            resolve_undo: false,
             tree_cache: true,
         },
         skip_hash: false,
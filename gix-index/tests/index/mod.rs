@@ -3,10 +3,12 @@ use std::path::{Path, PathBuf};
 use gix_hash::ObjectId;
 
 mod access;
+mod conflict;
 mod entry;
 mod file;
 mod fs;
 mod init;
+mod refresh;
 
 pub fn hex_to_id(hex: &str) -> ObjectId {
     ObjectId::from_hex(hex.as_bytes()).expect("40 bytes hex")
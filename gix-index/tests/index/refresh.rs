@@ -0,0 +1,158 @@
+use std::{path::Path, time::Duration};
+
+use filetime::FileTime;
+use gix_index::{entry, refresh};
+
+const FILES: [&str; 6] = ["a", "b", "c", "d/a", "d/b", "d/c"];
+
+/// Write content to all files so sizes can change, and add them with a modification time in the past
+/// so the resulting index isn't racy.
+fn index_with_content(dir: &Path) -> crate::Result<(gix_index::File, FileTime)> {
+    let mtime = FileTime::from_unix_time(FileTime::now().unix_seconds() - 10, 0);
+    for name in FILES {
+        let path = dir.join(name);
+        std::fs::write(&path, "content")?;
+        filetime::set_file_mtime(&path, mtime)?;
+    }
+    let args: Vec<_> = std::iter::once("add").chain(FILES).collect();
+    assert!(gix_testtools::run_git(dir, &args)?.success());
+    let index = gix_index::File::at(
+        dir.join(".git").join("index"),
+        gix_hash::Kind::Sha1,
+        false,
+        Default::default(),
+    )?;
+    Ok((index, mtime))
+}
+
+fn paths(paths: &[bstr::BString]) -> Vec<&str> {
+    paths
+        .iter()
+        .map(|p| std::str::from_utf8(p).expect("valid UTF-8"))
+        .collect()
+}
+
+#[test]
+fn unchanged_entries_are_marked_up_to_date() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_writable_standalone("make_index/v2_more_files.sh")?;
+    let (mut index, _) = index_with_content(dir.path())?;
+    assert!(index
+        .entries()
+        .iter()
+        .all(|e| !e.flags.contains(entry::Flags::UPTODATE)));
+
+    let outcome = index.refresh(dir.path(), refresh::Options::default())?;
+    assert_eq!(
+        outcome,
+        refresh::Outcome {
+            num_up_to_date: FILES.len(),
+            ..Default::default()
+        }
+    );
+    assert!(index.entries().iter().all(|e| e.flags.contains(entry::Flags::UPTODATE)));
+    Ok(())
+}
+
+#[test]
+fn modified_entries_are_returned() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_writable_standalone("make_index/v2_more_files.sh")?;
+    let (mut index, mtime) = index_with_content(dir.path())?;
+
+    std::fs::write(dir.path().join("a"), "different size")?;
+    filetime::set_file_mtime(dir.path().join("a"), mtime)?;
+    filetime::set_file_mtime(
+        dir.path().join("b"),
+        FileTime::from_unix_time(mtime.unix_seconds() + 1, 0),
+    )?;
+    std::fs::remove_file(dir.path().join("c"))?;
+    std::fs::remove_dir_all(dir.path().join("d"))?;
+    std::fs::write(dir.path().join("d"), "a file where a directory was")?;
+
+    let outcome = index.refresh(dir.path(), refresh::Options::default())?;
+    assert_eq!(
+        paths(&outcome.modified),
+        ["a", "c", "d/a", "d/b", "d/c"],
+        "changed sizes and removed files are modified, even if a leading component is now a file"
+    );
+    assert_eq!(
+        paths(&outcome.needs_content_comparison),
+        ["b"],
+        "a different modification time doesn't mean the content changed"
+    );
+    assert_eq!(outcome.num_up_to_date, 0);
+    assert!(
+        index
+            .entries()
+            .iter()
+            .all(|e| !e.flags.contains(entry::Flags::UPTODATE)),
+        "none of these are up-to-date"
+    );
+    Ok(())
+}
+
+#[test]
+fn racily_clean_entries_need_content_comparison() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_writable_standalone("make_index/v2_more_files.sh")?;
+    let (mut index, mtime) = index_with_content(dir.path())?;
+    index.set_timestamp(mtime);
+
+    let outcome = index.refresh(dir.path(), refresh::Options::default())?;
+    assert_eq!(
+        outcome.needs_content_comparison.len(),
+        FILES.len(),
+        "files modified in the same second as the index was written could have changed without us noticing"
+    );
+    assert_eq!(outcome.num_up_to_date, 0);
+    assert!(outcome.modified.is_empty());
+
+    index.set_timestamp(FileTime::from_unix_time(mtime.unix_seconds() + 1, 0));
+    let outcome = index.refresh(dir.path(), refresh::Options::default())?;
+    assert_eq!(
+        outcome.num_up_to_date,
+        FILES.len(),
+        "once written before, they are trusted"
+    );
+    Ok(())
+}
+
+#[test]
+fn check_stat_and_trust_ctime_control_compared_fields() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_writable_standalone("make_index/v2_more_files.sh")?;
+    let (index, mtime) = index_with_content(dir.path())?;
+
+    // Replace the file with a new one of the same content and modification time, changing its inode and ctime.
+    let tmp = dir.path().join("b.tmp");
+    std::fs::write(&tmp, "content")?;
+    filetime::set_file_mtime(&tmp, mtime)?;
+    std::thread::sleep(Duration::from_millis(10));
+    std::fs::rename(&tmp, dir.path().join("b"))?;
+
+    let outcome = index.clone().refresh(dir.path(), refresh::Options::default())?;
+    if cfg!(windows) {
+        assert_eq!(outcome.num_up_to_date, FILES.len(), "there are no inodes on windows");
+    } else {
+        assert_eq!(
+            paths(&outcome.needs_content_comparison),
+            ["b"],
+            "the inode is compared by default"
+        );
+    }
+
+    let outcome = index.clone().refresh(
+        dir.path(),
+        refresh::Options {
+            stat: entry::stat::Options {
+                check_stat: false,
+                trust_ctime: false,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        outcome.num_up_to_date,
+        FILES.len(),
+        "with `core.checkStat=minimal` and `core.trustCtime=false` only mtime and size are compared"
+    );
+    Ok(())
+}
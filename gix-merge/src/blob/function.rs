@@ -0,0 +1,257 @@
+use std::ops::Range;
+
+use bstr::BStr;
+use gix_diff::blob::{
+    intern::{Interner, Token},
+    sources::byte_lines_with_terminator,
+};
+
+use super::{Conflict, ConflictStyle, Options, Outcome};
+
+/// Perform a three-way merge of the `ours` and `theirs` versions of a blob, based on their common ancestor `base`,
+/// by comparing lines like `git merge-file` does, and return the merged content along with the amount of
+/// conflicts that remain.
+///
+/// Changes of only one side compared to `base`, and changes that both sides made in the same way, are merged automatically.
+/// Hunks that both sides changed differently, or that touch each other, are conflicts which are handled according to
+/// [`Options::conflict`].
+pub fn merge_blobs(base: &[u8], ours: &[u8], theirs: &[u8], options: Options<'_>) -> Outcome {
+    let mut interner = Interner::new(base.len() / 16 + ours.len() / 16 + theirs.len() / 16);
+    let (base_lines, base_tokens) = tokenize(base, &mut interner);
+    let (ours_lines, ours_tokens) = tokenize(ours, &mut interner);
+    let (theirs_lines, theirs_tokens) = tokenize(theirs, &mut interner);
+    let num_tokens = interner.num_tokens();
+    let diff = |before: &[Token], after: &[Token]| {
+        let mut hunks = Vec::new();
+        gix_diff::blob::diff_with_tokens(
+            options.diff_algorithm,
+            before,
+            after,
+            num_tokens,
+            |before: Range<u32>, after: Range<u32>| hunks.push(Hunk { before, after }),
+        );
+        hunks
+    };
+
+    let mut regions = regions(
+        &diff(&base_tokens, &ours_tokens),
+        &diff(&base_tokens, &theirs_tokens),
+        &ours_tokens,
+        &theirs_tokens,
+    );
+    let (style, union) = match options.conflict {
+        Conflict::Keep { style } => (style, false),
+        Conflict::ResolveWithUnion => (ConflictStyle::Merge, true),
+    };
+    // Showing the base of refined conflicts wouldn't make sense as it isn't split along with them.
+    if style == ConflictStyle::Merge {
+        regions = refine_conflicts(regions, &ours_tokens, &theirs_tokens, |before, after| {
+            diff(before, after)
+        });
+        simplify_conflicts(&mut regions, &ours_lines);
+    }
+
+    let mut out = Vec::with_capacity(ours.len().max(theirs.len()));
+    let mut num_conflicts = 0;
+    let mut ours_pos = 0;
+    for region in &regions {
+        write_lines(&mut out, &ours_lines[ours_pos..region.ours.start as usize], false);
+        ours_pos = region.ours.end as usize;
+        let ours = &ours_lines[range(&region.ours)];
+        let theirs = &theirs_lines[range(&region.theirs)];
+        match region.kind {
+            Kind::Ours => write_lines(&mut out, ours, false),
+            Kind::Theirs => write_lines(&mut out, theirs, false),
+            Kind::Conflict if union => {
+                write_lines(&mut out, ours, true);
+                write_lines(&mut out, theirs, false);
+            }
+            Kind::Conflict => {
+                num_conflicts += 1;
+                write_marker(&mut out, b'<', options.marker_size, options.labels.current);
+                write_lines(&mut out, ours, true);
+                if style == ConflictStyle::Diff3 {
+                    write_marker(&mut out, b'|', options.marker_size, options.labels.ancestor);
+                    write_lines(&mut out, &base_lines[range(&region.base)], true);
+                }
+                write_marker(&mut out, b'=', options.marker_size, None);
+                write_lines(&mut out, theirs, true);
+                write_marker(&mut out, b'>', options.marker_size, options.labels.other);
+            }
+        }
+    }
+    write_lines(&mut out, &ours_lines[ours_pos..], false);
+
+    Outcome {
+        merged: out,
+        num_conflicts,
+    }
+}
+
+/// Return all lines of `data` along with their tokens.
+fn tokenize<'a>(data: &'a [u8], interner: &mut Interner<&'a [u8]>) -> (Vec<&'a [u8]>, Vec<Token>) {
+    let lines: Vec<_> = byte_lines_with_terminator(data).collect();
+    let tokens = lines.iter().map(|line| interner.intern(*line)).collect();
+    (lines, tokens)
+}
+
+/// A change between `before` and `after`, in lines.
+struct Hunk {
+    before: Range<u32>,
+    after: Range<u32>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Kind {
+    /// Only *ours* changed, or both sides changed in the same way.
+    Ours,
+    /// Only *theirs* changed.
+    Theirs,
+    /// Both sides changed differently.
+    Conflict,
+}
+
+/// A region of lines in all three versions that changed in at least one of *ours* or *theirs*.
+#[derive(Debug, Clone)]
+struct Region {
+    kind: Kind,
+    base: Range<u32>,
+    ours: Range<u32>,
+    theirs: Range<u32>,
+}
+
+/// Produce all regions that changed compared to the base, by combining the hunks of `ours` and `theirs` that overlap
+/// or touch each other when compared to the base.
+fn regions(ours: &[Hunk], theirs: &[Hunk], ours_tokens: &[Token], theirs_tokens: &[Token]) -> Vec<Region> {
+    let mut out = Vec::new();
+    let (mut ours, mut theirs) = (ours.iter().peekable(), theirs.iter().peekable());
+    // The offset of lines in ours and theirs compared to the same line in the base, after the last processed hunk.
+    let (mut ours_offset, mut theirs_offset) = (0i64, 0i64);
+    loop {
+        let start = match (ours.peek(), theirs.peek()) {
+            (Some(o), Some(t)) => o.before.start.min(t.before.start),
+            (Some(o), None) => o.before.start,
+            (None, Some(t)) => t.before.start,
+            (None, None) => break,
+        };
+        let mut end = start;
+        let (ours_offset_before, theirs_offset_before) = (ours_offset, theirs_offset);
+        let (mut ours_changed, mut theirs_changed, mut num_hunks) = (false, false, 0);
+        loop {
+            if let Some(hunk) = ours.next_if(|h| h.before.start <= end) {
+                end = end.max(hunk.before.end);
+                ours_offset = i64::from(hunk.after.end) - i64::from(hunk.before.end);
+                ours_changed = true;
+                num_hunks += 1;
+            } else if let Some(hunk) = theirs.next_if(|h| h.before.start <= end) {
+                end = end.max(hunk.before.end);
+                theirs_offset = i64::from(hunk.after.end) - i64::from(hunk.before.end);
+                theirs_changed = true;
+                num_hunks += 1;
+            } else {
+                break;
+            }
+        }
+
+        let side = |offset_before: i64, offset_after: i64| {
+            (i64::from(start) + offset_before) as u32..(i64::from(end) + offset_after) as u32
+        };
+        let ours_range = side(ours_offset_before, ours_offset);
+        let theirs_range = side(theirs_offset_before, theirs_offset);
+        let kind = match (ours_changed, theirs_changed) {
+            (true, false) => Kind::Ours,
+            (false, true) => Kind::Theirs,
+            // Only a single hunk on each side that is the same is trivially resolved, everything else
+            // is left to refinement.
+            _ if num_hunks == 2 && ours_tokens[range(&ours_range)] == theirs_tokens[range(&theirs_range)] => Kind::Ours,
+            _ => Kind::Conflict,
+        };
+        out.push(Region {
+            kind,
+            base: start..end,
+            ours: ours_range,
+            theirs: theirs_range,
+        });
+    }
+    out
+}
+
+/// Make conflicts smaller by comparing both sides of each conflict with `diff`, so that lines that are the same in
+/// both sides aren't part of a conflict anymore.
+fn refine_conflicts(
+    regions: Vec<Region>,
+    ours_tokens: &[Token],
+    theirs_tokens: &[Token],
+    mut diff: impl FnMut(&[Token], &[Token]) -> Vec<Hunk>,
+) -> Vec<Region> {
+    let mut out = Vec::with_capacity(regions.len());
+    for region in regions {
+        if region.kind != Kind::Conflict || region.ours.is_empty() || region.theirs.is_empty() {
+            out.push(region);
+            continue;
+        }
+        let hunks = diff(&ours_tokens[range(&region.ours)], &theirs_tokens[range(&region.theirs)]);
+        if hunks.is_empty() {
+            out.push(Region {
+                kind: Kind::Ours,
+                ..region
+            });
+            continue;
+        }
+        for hunk in hunks {
+            out.push(Region {
+                kind: Kind::Conflict,
+                base: region.base.clone(),
+                ours: region.ours.start + hunk.before.start..region.ours.start + hunk.before.end,
+                theirs: region.theirs.start + hunk.after.start..region.theirs.start + hunk.after.end,
+            });
+        }
+    }
+    out
+}
+
+/// Merge conflicts that are only separated by up to three lines, or by lines without any alphanumeric character,
+/// as these lines are easier to understand as part of the conflicts.
+fn simplify_conflicts(regions: &mut Vec<Region>, ours_lines: &[&[u8]]) {
+    let mut idx = 0;
+    while idx + 1 < regions.len() {
+        let (current, next) = (&regions[idx], &regions[idx + 1]);
+        let between = &ours_lines[current.ours.end as usize..next.ours.start as usize];
+        if current.kind != Kind::Conflict
+            || next.kind != Kind::Conflict
+            || (between.len() > 3 && between.iter().any(|line| line.iter().any(u8::is_ascii_alphanumeric)))
+        {
+            idx += 1;
+            continue;
+        }
+        let next = regions.remove(idx + 1);
+        let current = &mut regions[idx];
+        current.base.end = current.base.end.max(next.base.end);
+        current.ours.end = next.ours.end;
+        current.theirs.end = next.theirs.end;
+    }
+}
+
+fn range(r: &Range<u32>) -> Range<usize> {
+    r.start as usize..r.end as usize
+}
+
+/// Write all `lines`, and if `assure_newline` is set, assure the last one ends with a newline so a marker can follow.
+fn write_lines(out: &mut Vec<u8>, lines: &[&[u8]], assure_newline: bool) {
+    for line in lines {
+        out.extend_from_slice(line);
+    }
+    match lines.last() {
+        Some(last) if assure_newline && !last.ends_with(b"\n") => out.push(b'\n'),
+        _ => {}
+    }
+}
+
+fn write_marker(out: &mut Vec<u8>, marker: u8, size: usize, label: Option<&BStr>) {
+    out.extend(std::iter::repeat(marker).take(size));
+    if let Some(label) = label {
+        out.push(b' ');
+        out.extend_from_slice(label);
+    }
+    out.push(b'\n');
+}
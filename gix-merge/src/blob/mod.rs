@@ -0,0 +1,84 @@
+use bstr::BStr;
+
+pub(crate) mod function;
+
+/// Determines how conflicting hunks are written into the merged blob.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ConflictStyle {
+    /// Only show the conflicting hunks of *ours* and *theirs*, like `git merge-file`.
+    ///
+    /// Conflicts are kept as small as possible, so lines that both sides changed in the same way aren't part of them.
+    #[default]
+    Merge,
+    /// Like [`Merge`](Self::Merge), but also show the hunk of the base between both sides, like `git merge-file --diff3`.
+    ///
+    /// Conflicts aren't made smaller as they would be with [`Merge`](Self::Merge) so that the base hunk is always complete.
+    Diff3,
+}
+
+/// Determines what to do with conflicting hunks.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Conflict {
+    /// Write conflict markers around the conflicting hunks in the given `style`, leaving the conflict to be resolved.
+    Keep {
+        /// How to present the conflicting hunks.
+        style: ConflictStyle,
+    },
+    /// Resolve conflicts by writing the hunk of *ours* followed by the hunk of *theirs*, without conflict markers,
+    /// like `git merge-file --union` or the `merge=union` attribute do.
+    ResolveWithUnion,
+}
+
+impl Default for Conflict {
+    fn default() -> Self {
+        Conflict::Keep {
+            style: Default::default(),
+        }
+    }
+}
+
+/// The labels to write after the conflict markers, typically names of branches or files.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Labels<'a> {
+    /// The label of the common ancestor, only used in [`ConflictStyle::Diff3`].
+    pub ancestor: Option<&'a BStr>,
+    /// The label of *ours*, written after `<<<<<<<`.
+    pub current: Option<&'a BStr>,
+    /// The label of *theirs*, written after `>>>>>>>`.
+    pub other: Option<&'a BStr>,
+}
+
+/// The options for use in [`merge_blobs()`](function::merge_blobs()).
+#[derive(Debug, Copy, Clone)]
+pub struct Options<'a> {
+    /// What to do with conflicting hunks.
+    pub conflict: Conflict,
+    /// The amount of characters of each conflict marker, like `<<<<<<<`, which is `7` by default.
+    pub marker_size: usize,
+    /// The labels to write after the conflict markers.
+    pub labels: Labels<'a>,
+    /// The algorithm to use for finding the hunks that changed in *ours* and *theirs* compared to the base.
+    pub diff_algorithm: gix_diff::blob::Algorithm,
+}
+
+impl Default for Options<'_> {
+    fn default() -> Self {
+        Options {
+            conflict: Default::default(),
+            marker_size: 7,
+            labels: Default::default(),
+            diff_algorithm: gix_diff::blob::Algorithm::Myers,
+        }
+    }
+}
+
+/// The outcome of [`merge_blobs()`](function::merge_blobs()).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Outcome {
+    /// The merged content, with conflict markers if there are unresolved conflicts.
+    pub merged: Vec<u8>,
+    /// The amount of conflicting hunks that remain in `merged`, marked with conflict markers.
+    ///
+    /// It's always `0` if conflicts are resolved with [`Conflict::ResolveWithUnion`].
+    pub num_conflicts: usize,
+}
@@ -0,0 +1,8 @@
+//! Merge algorithms for the various kinds of git objects, starting with the three-way merge of blobs.
+#![deny(rust_2018_idioms, missing_docs)]
+#![forbid(unsafe_code)]
+
+///
+#[allow(clippy::empty_docs)]
+pub mod blob;
+pub use blob::function::merge_blobs;
@@ -0,0 +1,70 @@
+use gix_merge::blob::{Conflict, ConflictStyle, Labels, Options};
+
+fn options(variant: &str) -> Options<'static> {
+    let mut options = Options {
+        labels: Labels {
+            ancestor: Some("base".into()),
+            current: Some("ours".into()),
+            other: Some("theirs".into()),
+        },
+        ..Default::default()
+    };
+    match variant {
+        "merge" => {}
+        "diff3" => {
+            options.conflict = Conflict::Keep {
+                style: ConflictStyle::Diff3,
+            }
+        }
+        "union" => options.conflict = Conflict::ResolveWithUnion,
+        "marker-size-3" => options.marker_size = 3,
+        unknown => unreachable!("unknown variant: {unknown}"),
+    }
+    options
+}
+
+#[test]
+fn baselines_match_git_merge_file() -> gix_testtools::Result {
+    let root = gix_testtools::scripted_fixture_read_only("make_blob_merges.sh")?;
+    let mut num_cases = 0;
+    for case in std::fs::read_dir(&root)? {
+        let case = case?.path();
+        let read = |name: &str| std::fs::read(case.join(name)).expect("file exists");
+        let (base, ours, theirs) = (read("base"), read("ours"), read("theirs"));
+        for variant in ["merge", "diff3", "union", "marker-size-3"] {
+            let expected = read(&format!("{variant}.merged"));
+            let expected_conflicts: usize = String::from_utf8(read(&format!("{variant}.exit")))?.trim().parse()?;
+
+            let actual = gix_merge::merge_blobs(&base, &ours, &theirs, options(variant));
+            assert_eq!(
+                actual.merged.as_slice(),
+                expected.as_slice(),
+                "{case:?} {variant}:\n{}",
+                String::from_utf8_lossy(&actual.merged)
+            );
+            assert_eq!(actual.num_conflicts, expected_conflicts, "{case:?} {variant}");
+        }
+        num_cases += 1;
+    }
+    assert_eq!(num_cases, 11, "all cases were tested");
+    Ok(())
+}
+
+#[test]
+fn non_overlapping_changes_are_merged_cleanly() {
+    let actual = gix_merge::merge_blobs(
+        b"1\n2\n3\n4\n",
+        b"one\n2\n3\n4\n",
+        b"1\n2\n3\nfour\n",
+        Default::default(),
+    );
+    assert_eq!(actual.merged, b"one\n2\n3\nfour\n");
+    assert_eq!(actual.num_conflicts, 0);
+}
+
+#[test]
+fn conflicts_without_labels() {
+    let actual = gix_merge::merge_blobs(b"a\n", b"b\n", b"c\n", Default::default());
+    assert_eq!(actual.merged, b"<<<<<<<\nb\n=======\nc\n>>>>>>>\n");
+    assert_eq!(actual.num_conflicts, 1);
+}
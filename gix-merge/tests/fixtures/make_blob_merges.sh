@@ -0,0 +1,109 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+# Each case is a directory with the `base`, `ours` and `theirs` versions of a file, along with the output of
+# `git merge-file` for various options in `<variant>.merged` and its exit code, the amount of conflicts, in `<variant>.exit`.
+function baseline() {
+  local name=${1:?first argument is the name of the case directory}
+  (cd "$name"
+    for variant in merge diff3 union marker-size-3; do
+      local args=()
+      case $variant in
+        merge) ;;
+        diff3) args=(--diff3) ;;
+        union) args=(--union) ;;
+        marker-size-3) args=(--marker-size=3) ;;
+      esac
+      git merge-file -p ${args[@]+"${args[@]}"} -L ours -L base -L theirs ours base theirs > "$variant.merged" && status=0 || status=$?
+      echo $status > "$variant.exit"
+    done
+  )
+}
+
+mkdir non-overlapping
+(cd non-overlapping
+  printf '1\n2\n3\n4\n5\n6\n7\n' > base
+  printf 'one\n2\n3\n4\n5\n6\n7\n' > ours
+  printf '1\n2\n3\n4\n5\n6\nseven\n' > theirs
+)
+baseline non-overlapping
+
+mkdir same-line
+(cd same-line
+  printf 'a\nb\nc\n' > base
+  printf 'a\nours\nc\n' > ours
+  printf 'a\ntheirs\nc\n' > theirs
+)
+baseline same-line
+
+mkdir identical-changes
+(cd identical-changes
+  printf 'a\nb\nc\nd\n' > base
+  printf 'a\nB\nc\nD\n' > ours
+  printf 'a\nB\nc\nd\n' > theirs
+)
+baseline identical-changes
+
+mkdir touching-changes
+(cd touching-changes
+  printf 'a\nb\nc\nd\n' > base
+  printf 'a\nB\nc\nd\n' > ours
+  printf 'a\nb\nC\nd\n' > theirs
+)
+baseline touching-changes
+
+mkdir common-lines-in-conflict
+(cd common-lines-in-conflict
+  printf 'a\nb\nc\nd\ne\n' > base
+  printf 'a\nsame\nours\nsame too\ne\n' > ours
+  printf 'a\nsame\ntheirs\nsame too\ne\n' > theirs
+)
+baseline common-lines-in-conflict
+
+mkdir close-conflicts
+(cd close-conflicts
+  printf '1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n' > base
+  printf '1\nours\n3\n4\nours\n6\n7\n8\n9\n10\nours\n12\n' > ours
+  printf '1\ntheirs\n3\n4\ntheirs\n6\n7\n8\n9\n10\ntheirs\n12\n' > theirs
+)
+baseline close-conflicts
+
+mkdir conflicts-separated-by-punctuation
+(cd conflicts-separated-by-punctuation
+  printf 'start\nx\n}\n\n}\n\n{\ny\nend\n' > base
+  printf 'start\nours\n}\n\n}\n\n{\nours\nend\n' > ours
+  printf 'start\ntheirs\n}\n\n}\n\n{\ntheirs\nend\n' > theirs
+)
+baseline conflicts-separated-by-punctuation
+
+mkdir insertions-at-same-place
+(cd insertions-at-same-place
+  printf 'a\nb\n' > base
+  printf 'a\nours\nb\n' > ours
+  printf 'a\ntheirs 1\ntheirs 2\nb\n' > theirs
+)
+baseline insertions-at-same-place
+
+mkdir no-newline-at-end
+(cd no-newline-at-end
+  printf 'a\nb' > base
+  printf 'a\nours' > ours
+  printf 'a\ntheirs' > theirs
+)
+baseline no-newline-at-end
+
+mkdir deletion-and-modification
+(cd deletion-and-modification
+  printf 'a\nb\nc\nd\n' > base
+  printf 'a\nd\n' > ours
+  printf 'a\nb\nC\nd\n' > theirs
+)
+baseline deletion-and-modification
+
+mkdir empty-base
+(cd empty-base
+  printf '' > base
+  printf 'ours\n' > ours
+  printf 'theirs\n' > theirs
+)
+baseline empty-base
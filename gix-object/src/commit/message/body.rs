@@ -42,6 +42,17 @@ fn parse_single_line_trailer<'a, E: ParserError<&'a [u8]>>(i: &mut &'a [u8]) ->
     }
 }
 
+/// Parse `line` as trailer, or return `None` if it isn't shaped like one.
+pub(crate) fn parse_trailer(mut line: &[u8]) -> Option<TrailerRef<'_>> {
+    terminated(parse_single_line_trailer::<()>, eof)
+        .parse_next(&mut line)
+        .ok()
+        .map(|(token, value)| TrailerRef {
+            token: token.trim().as_bstr(),
+            value: value.trim().as_bstr(),
+        })
+}
+
 impl<'a> Iterator for Trailers<'a> {
     type Item = TrailerRef<'a>;
 
@@ -49,16 +60,9 @@ impl<'a> Iterator for Trailers<'a> {
         if self.cursor.is_empty() {
             return None;
         }
-        for mut line in self.cursor.lines_with_terminator() {
+        for line in self.cursor.lines_with_terminator() {
             self.cursor = &self.cursor[line.len()..];
-            if let Some(trailer) = terminated(parse_single_line_trailer::<()>, eof)
-                .parse_next(&mut line)
-                .ok()
-                .map(|(token, value)| TrailerRef {
-                    token: token.trim().as_bstr(),
-                    value: value.trim().as_bstr(),
-                })
-            {
+            if let Some(trailer) = parse_trailer(line) {
                 return Some(trailer);
             }
         }
@@ -10,6 +10,9 @@ use crate::{
 #[allow(clippy::empty_docs)]
 pub mod body;
 mod decode;
+///
+#[allow(clippy::empty_docs)]
+pub mod trailer;
 
 impl<'a> CommitRef<'a> {
     /// Return exactly the same message as [`MessageRef::summary()`].
@@ -0,0 +1,315 @@
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    commit::message::body::{parse_trailer, TrailerRef},
+};
+
+/// Lines starting with these are generated by git and always count as trailers. If one of them is present, the last
+/// paragraph is a trailer block if at least a quarter of its lines are trailers, instead of all of them.
+const GIT_GENERATED_PREFIXES: &[&[u8]] = &[b"Signed-off-by: ", b"(cherry picked from commit "];
+
+/// A single part of the trailer block of a message.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Item<'a> {
+    /// A trailer as parsed by [`BodyRef::trailers()`](super::BodyRef::trailers()), whose value also contains the
+    /// continuation lines that follow it as they are.
+    Trailer(#[cfg_attr(feature = "serde", serde(borrow))] TrailerRef<'a>),
+    /// A line that isn't shaped like a trailer, like `(cherry picked from commit <id>)`, without its line terminator.
+    Other(&'a BStr),
+}
+
+/// An iterator over the [items](Item) of a trailer block, as obtained by [`Block::items()`].
+pub struct Items<'a> {
+    cursor: &'a [u8],
+}
+
+/// A message split into its trailer block and the text around it, according to the rules of `git interpret-trailers`.
+///
+/// The trailer block is the last paragraph of a message, if it isn't the only one, and if all of its lines are
+/// trailers as recognized by [`BodyRef::trailers()`](super::BodyRef::trailers()), or continuation lines of trailers
+/// which start with whitespace.
+/// If one of its lines was generated by git, like `Signed-off-by: …`, it suffices that at least a quarter of its lines
+/// are trailers, to allow other lines to be mixed in. Otherwise, there is no trailer block, so that a paragraph of
+/// prose that happens to contain a colon isn't mistaken for trailers.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+pub struct Block<'a> {
+    /// The text before the trailer block, without the empty lines separating it from the trailer block.
+    ///
+    /// It's the whole message without trailing empty lines if there is no trailer block.
+    pub before: &'a BStr,
+    /// The empty lines between [`before`](Self::before) and the trailer block, which are empty if there is none.
+    pub separator: &'a BStr,
+    /// All lines of the trailer block, which is empty if there is none.
+    pub lines: &'a BStr,
+    /// The empty lines after the trailer block, or at the end of the message if there is no trailer block.
+    pub after: &'a BStr,
+}
+
+impl<'a> Block<'a> {
+    /// Find the trailer block of `message`.
+    pub fn from_message(message: &'a [u8]) -> Self {
+        let mut lines: Vec<_> = message.lines_with_terminator().collect();
+        let mut end = message.len();
+        while let Some(line) = lines.last().filter(|line| is_blank(line)) {
+            end -= line.len();
+            lines.pop();
+        }
+        let (before, after) = message.split_at(end);
+        let no_block = Block {
+            before: before.as_bstr(),
+            separator: Default::default(),
+            lines: Default::default(),
+            after: after.as_bstr(),
+        };
+
+        // The first paragraph is the title, which is never a trailer block.
+        let Some(end_of_title) = lines.iter().position(|line| is_blank(line)) else {
+            return no_block;
+        };
+        let mut start = end;
+        let (mut trailer_lines, mut non_trailer_lines, mut possible_continuation_lines) = (0, 0, 0);
+        let mut recognized_prefix = false;
+        for line in lines[end_of_title..].iter().rev() {
+            if is_blank(line) {
+                non_trailer_lines += possible_continuation_lines;
+                let is_trailer_block = (recognized_prefix && trailer_lines * 3 >= non_trailer_lines)
+                    || (trailer_lines > 0 && non_trailer_lines == 0);
+                if !is_trailer_block {
+                    return no_block;
+                }
+                break;
+            }
+            start -= line.len();
+            if GIT_GENERATED_PREFIXES.iter().any(|prefix| line.starts_with(prefix)) {
+                trailer_lines += 1;
+                possible_continuation_lines = 0;
+                recognized_prefix = true;
+            } else if starts_with_whitespace(line) {
+                possible_continuation_lines += 1;
+            } else if parse_trailer(line).is_some() {
+                trailer_lines += 1;
+                possible_continuation_lines = 0;
+            } else {
+                non_trailer_lines += 1 + possible_continuation_lines;
+                possible_continuation_lines = 0;
+            }
+        }
+
+        let mut separator_start = start;
+        while let Some(line) = message[..separator_start]
+            .lines_with_terminator()
+            .next_back()
+            .filter(|line| is_blank(line))
+        {
+            separator_start -= line.len();
+        }
+        Block {
+            before: message[..separator_start].as_bstr(),
+            separator: message[separator_start..start].as_bstr(),
+            lines: message[start..end].as_bstr(),
+            after: after.as_bstr(),
+        }
+    }
+
+    /// Return `true` if the message has no trailer block.
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Return all trailers and other lines of the trailer block.
+    pub fn items(&self) -> Items<'a> {
+        Items { cursor: self.lines }
+    }
+
+    /// Return all trailers of the trailer block, without its other lines.
+    pub fn trailers(&self) -> impl Iterator<Item = TrailerRef<'a>> {
+        self.items().filter_map(Item::trailer)
+    }
+}
+
+impl<'a> Item<'a> {
+    /// Return the trailer if this is one.
+    pub fn trailer(self) -> Option<TrailerRef<'a>> {
+        match self {
+            Item::Trailer(trailer) => Some(trailer),
+            Item::Other(_) => None,
+        }
+    }
+}
+
+impl<'a> Items<'a> {
+    /// Return the next item along with the lines it was parsed from, including line terminators.
+    fn next_with_lines(&mut self) -> Option<(Item<'a>, &'a BStr)> {
+        let line_with_terminator = self.cursor.lines_with_terminator().next()?;
+        let mut end = line_with_terminator.len();
+        let line = line_with_terminator.trim_end_with(|c| c == '\n' || c == '\r');
+        let item = match parse_trailer(line).filter(|_| !starts_with_whitespace(line)) {
+            Some(trailer) => {
+                let value_start = trailer.token.len() + 2;
+                for continuation in self.cursor[end..]
+                    .lines_with_terminator()
+                    .take_while(|line| starts_with_whitespace(line))
+                {
+                    end += continuation.len();
+                }
+                Item::Trailer(TrailerRef {
+                    token: trailer.token,
+                    value: self.cursor[value_start..end].trim().as_bstr(),
+                })
+            }
+            None => Item::Other(line.as_bstr()),
+        };
+        let lines = self.cursor[..end].as_bstr();
+        self.cursor = &self.cursor[end..];
+        Some((item, lines))
+    }
+}
+
+impl<'a> Iterator for Items<'a> {
+    type Item = Item<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_lines().map(|(item, _)| item)
+    }
+}
+
+/// A message whose trailers can be added, replaced or removed while keeping all other text as is, similar to
+/// `git interpret-trailers --trailer`.
+///
+/// Tokens are matched case-insensitively, like `git` does.
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+pub struct Editor {
+    before: BString,
+    separator: BString,
+    entries: Vec<Entry>,
+    after: BString,
+}
+
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+enum Entry {
+    /// The lines of an item of the original trailer block, including line terminators.
+    Parsed(BString),
+    /// A trailer added by the editor.
+    Added { token: BString, value: BString },
+}
+
+impl Editor {
+    /// Parse the trailer block of `message` to be able to edit it.
+    pub fn from_message(message: &[u8]) -> Self {
+        let block = Block::from_message(message);
+        let mut items = block.items();
+        Editor {
+            before: block.before.into(),
+            separator: block.separator.into(),
+            entries: std::iter::from_fn(|| items.next_with_lines())
+                .map(|(_, lines)| Entry::Parsed(lines.into()))
+                .collect(),
+            after: block.after.into(),
+        }
+    }
+
+    /// Return all trailers and other lines of the trailer block.
+    pub fn items(&self) -> impl Iterator<Item = Item<'_>> {
+        self.entries.iter().map(Entry::item)
+    }
+
+    /// Return all trailers of the trailer block, without its other lines.
+    pub fn trailers(&self) -> impl Iterator<Item = TrailerRef<'_>> {
+        self.items().filter_map(Item::trailer)
+    }
+
+    /// Add a trailer with `token` and `value` to the end of the trailer block, even if there is one with the same
+    /// token already.
+    pub fn add(&mut self, token: impl Into<BString>, value: impl Into<BString>) -> &mut Self {
+        self.entries.push(Entry::Added {
+            token: token.into(),
+            value: value.into(),
+        });
+        self
+    }
+
+    /// Replace all trailers with `token` with a single one with `value` in place of the first of them, or add it to
+    /// the end of the trailer block if there is none.
+    pub fn replace(&mut self, token: impl Into<BString>, value: impl Into<BString>) -> &mut Self {
+        let (token, value) = (token.into(), value.into());
+        match self.entries.iter().position(|entry| entry.matches(token.as_ref())) {
+            Some(position) => {
+                self.remove(token.as_ref());
+                self.entries.insert(position, Entry::Added { token, value });
+            }
+            None => self.entries.push(Entry::Added { token, value }),
+        }
+        self
+    }
+
+    /// Remove all trailers with `token`, and return how many were removed.
+    pub fn remove(&mut self, token: &BStr) -> usize {
+        let count = self.entries.len();
+        self.entries.retain(|entry| !entry.matches(token));
+        count - self.entries.len()
+    }
+
+    /// Return the message with the edited trailer block, which is separated by an empty line from the text before it
+    /// unless one of them is empty.
+    ///
+    /// Existing lines of the trailer block are kept as they are, while new trailers take a single line of the form
+    /// `token: value`.
+    pub fn to_message(&self) -> BString {
+        let mut out = self.before.clone();
+        if !self.entries.is_empty() {
+            if !out.is_empty() {
+                if !out.ends_with(b"\n") {
+                    out.push_byte(b'\n');
+                }
+                if self.separator.is_empty() {
+                    out.push_byte(b'\n');
+                } else {
+                    out.push_str(&self.separator);
+                }
+            }
+            for entry in &self.entries {
+                match entry {
+                    Entry::Parsed(lines) => out.push_str(lines),
+                    Entry::Added { token, value } => {
+                        out.push_str(token);
+                        out.push_str(b": ");
+                        out.push_str(value);
+                    }
+                }
+                if !out.ends_with(b"\n") {
+                    out.push_byte(b'\n');
+                }
+            }
+        }
+        out.push_str(&self.after);
+        out
+    }
+}
+
+impl Entry {
+    fn item(&self) -> Item<'_> {
+        match self {
+            Entry::Parsed(lines) => Items { cursor: lines }
+                .next()
+                .expect("parsed entries hold the lines of exactly one item"),
+            Entry::Added { token, value } => Item::Trailer(TrailerRef {
+                token: token.as_ref(),
+                value: value.as_ref(),
+            }),
+        }
+    }
+
+    fn matches(&self, token: &BStr) -> bool {
+        self.item()
+            .trailer()
+            .is_some_and(|trailer| trailer.token.eq_ignore_ascii_case(token))
+    }
+}
+
+fn is_blank(line: &[u8]) -> bool {
+    line.iter().all(u8::is_ascii_whitespace)
+}
+
+fn starts_with_whitespace(line: &[u8]) -> bool {
+    line.first().is_some_and(u8::is_ascii_whitespace)
+}
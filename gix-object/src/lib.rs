@@ -408,7 +408,7 @@ pub fn compute_hash(hash_kind: gix_hash::Kind, object_kind: Kind, data: &[u8]) -
     hasher.update(&header);
     hasher.update(data);
 
-    hasher.digest().into()
+    hasher.digest()
 }
 
 /// A function to compute a hash of kind `hash_kind` for an object of `object_kind` and its data read from `stream`
@@ -39,6 +39,10 @@ pub fn git_tag<'a, E: ParserError<&'a [u8]> + AddContext<&'a [u8], StrContext>>(
 pub fn message<'a, E: ParserError<&'a [u8]>>(i: &mut &'a [u8]) -> PResult<(&'a BStr, Option<&'a BStr>), E> {
     const PGP_SIGNATURE_BEGIN: &[u8] = b"\n-----BEGIN PGP SIGNATURE-----";
     const PGP_SIGNATURE_END: &[u8] = b"-----END PGP SIGNATURE-----";
+    const SSH_SIGNATURE_BEGIN: &[u8] = b"\n-----BEGIN SSH SIGNATURE-----";
+    const SSH_SIGNATURE_END: &[u8] = b"-----END SSH SIGNATURE-----";
+    const X509_SIGNATURE_BEGIN: &[u8] = b"\n-----BEGIN SIGNED MESSAGE-----";
+    const X509_SIGNATURE_END: &[u8] = b"-----END SIGNED MESSAGE-----";
 
     if i.is_empty() {
         return Ok((b"".as_bstr(), None));
@@ -46,26 +50,9 @@ pub fn message<'a, E: ParserError<&'a [u8]>>(i: &mut &'a [u8]) -> PResult<(&'a B
     delimited(
         NL,
         alt((
-            (
-                take_until(0.., PGP_SIGNATURE_BEGIN),
-                preceded(
-                    NL,
-                    (
-                        &PGP_SIGNATURE_BEGIN[1..],
-                        take_until(0.., PGP_SIGNATURE_END),
-                        PGP_SIGNATURE_END,
-                        rest,
-                    )
-                        .recognize()
-                        .map(|signature: &[u8]| {
-                            if signature.is_empty() {
-                                None
-                            } else {
-                                Some(signature.as_bstr())
-                            }
-                        }),
-                ),
-            ),
+            signed_message(PGP_SIGNATURE_BEGIN, PGP_SIGNATURE_END),
+            signed_message(SSH_SIGNATURE_BEGIN, SSH_SIGNATURE_END),
+            signed_message(X509_SIGNATURE_BEGIN, X509_SIGNATURE_END),
             rest.map(|rest: &[u8]| (rest, None)),
         )),
         opt(NL),
@@ -73,3 +60,25 @@ pub fn message<'a, E: ParserError<&'a [u8]>>(i: &mut &'a [u8]) -> PResult<(&'a B
     .map(|(message, signature)| (message.as_bstr(), signature))
     .parse_next(i)
 }
+
+/// Parse a message followed by a signature that starts with `begin` (including the leading newline) and ends with `end`.
+fn signed_message<'a, E: ParserError<&'a [u8]>>(
+    begin: &'static [u8],
+    end: &'static [u8],
+) -> impl Parser<&'a [u8], (&'a [u8], Option<&'a BStr>), E> {
+    (
+        take_until(0.., begin),
+        preceded(
+            NL,
+            (&begin[1..], take_until(0.., end), end, rest)
+                .recognize()
+                .map(|signature: &[u8]| {
+                    if signature.is_empty() {
+                        None
+                    } else {
+                        Some(signature.as_bstr())
+                    }
+                }),
+        ),
+    )
+}
@@ -8,8 +8,26 @@ use crate::{
 mod ref_iter;
 ///
 #[allow(clippy::empty_docs)]
+pub mod walk;
+///
+#[allow(clippy::empty_docs)]
 pub mod write;
 
+/// An iterator over the entries of a tree and optionally those of its subtrees, yielding each [entry](walk::Entry)
+/// along with its full path.
+///
+/// Subtrees are read lazily as the iteration descends into them, and entries are yielded depth-first, with
+/// each tree being yielded before its entries.
+pub struct Walk<'find> {
+    objects: &'find dyn crate::Find,
+    recurse: walk::Recurse,
+    /// The trees whose entries are being yielded, with the innermost one last.
+    stack: Vec<walk::Frame>,
+    /// A tree to read and descend into before yielding the next entry.
+    next_tree: Option<(BString, gix_hash::ObjectId)>,
+    buf: Vec<u8>,
+}
+
 /// The mode of items storable in a tree, similar to the file mode on a unix file system.
 ///
 /// Used in [`mutable::Entry`][crate::tree::Entry] and [`EntryRef`].
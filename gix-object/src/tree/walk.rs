@@ -0,0 +1,154 @@
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+use gix_hash::ObjectId;
+
+use crate::{tree, tree::Walk, FindExt};
+
+/// The error returned by [`Walk::new()`] and the [`Walk`] iterator.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Find(#[from] crate::find::existing_iter::Error),
+    #[error(transparent)]
+    Decode(#[from] crate::decode::Error),
+}
+
+/// Determines how a [`Walk`] descends into subtrees.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Recurse {
+    /// Only yield the entries of the tree itself, without descending into subtrees.
+    #[default]
+    No,
+    /// Descend into all subtrees, and yield submodules as leaves.
+    All,
+    /// Descend into all subtrees, but don't yield submodules.
+    SkipSubmodules,
+}
+
+/// An entry yielded by a [`Walk`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// The mode of the entry.
+    pub mode: tree::EntryMode,
+    /// The id of the object the entry points to, which is the id of the commit checked out as submodule for
+    /// [gitlinks](tree::EntryKind::Commit).
+    pub oid: ObjectId,
+    /// The path of the entry relative to the tree the walk started at, with components separated by `/`.
+    pub path: BString,
+}
+
+/// The entries of a tree that still have to be yielded.
+pub(crate) struct Frame {
+    /// The path of the tree, or empty if it's the tree the walk started at.
+    path: BString,
+    entries: std::vec::IntoIter<tree::Entry>,
+}
+
+/// Initialization
+impl<'find> Walk<'find> {
+    /// Prepare a walk through the tree with id `tree`, reading trees from `objects`, that descends into subtrees according to
+    /// `recurse`.
+    ///
+    /// If `prefix` isn't empty, only the entries below the directory at the `/` separated `prefix` are yielded, or
+    /// only the entry at `prefix` itself if it isn't a tree. Only the trees leading up to `prefix` are read, and nothing is
+    /// yielded if there is no entry at `prefix`.
+    pub fn new(
+        tree: impl Into<ObjectId>,
+        prefix: &BStr,
+        recurse: Recurse,
+        objects: &'find dyn crate::Find,
+    ) -> Result<Self, Error> {
+        let mut walk = Walk {
+            objects,
+            recurse,
+            stack: Vec::new(),
+            next_tree: None,
+            buf: Vec::new(),
+        };
+        let mut tree = tree.into();
+        let mut path = BString::default();
+        let mut components = prefix.split_str("/").filter(|c| !c.is_empty()).peekable();
+        while let Some(component) = components.next() {
+            let mut found = None;
+            for entry in objects.find_tree_iter(&tree, &mut walk.buf)? {
+                let entry = entry?;
+                if entry.filename == component {
+                    found = Some(tree::Entry::from(entry));
+                    break;
+                }
+            }
+            let Some(entry) = found else {
+                return Ok(walk);
+            };
+            if entry.mode.is_tree() {
+                tree = entry.oid;
+                push_component(&mut path, entry.filename.as_ref());
+            } else {
+                if components.peek().is_none() {
+                    walk.stack.push(Frame {
+                        path,
+                        entries: vec![entry].into_iter(),
+                    });
+                }
+                return Ok(walk);
+            }
+        }
+        walk.next_tree = Some((path, tree));
+        Ok(walk)
+    }
+}
+
+impl<'find> Walk<'find> {
+    fn read_tree(&mut self, path: BString, id: &gix_hash::oid) -> Result<(), Error> {
+        let entries = self
+            .objects
+            .find_tree_iter(id, &mut self.buf)?
+            .map(|entry| entry.map(tree::Entry::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.stack.push(Frame {
+            path,
+            entries: entries.into_iter(),
+        });
+        Ok(())
+    }
+}
+
+impl<'find> Iterator for Walk<'find> {
+    type Item = Result<Entry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((path, id)) = self.next_tree.take() {
+            if let Err(err) = self.read_tree(path, &id) {
+                self.stack.clear();
+                return Some(Err(err));
+            }
+        }
+        loop {
+            let frame = self.stack.last_mut()?;
+            let Some(entry) = frame.entries.next() else {
+                self.stack.pop();
+                continue;
+            };
+            if entry.mode.is_commit() && self.recurse == Recurse::SkipSubmodules {
+                continue;
+            }
+            let mut path = frame.path.clone();
+            push_component(&mut path, entry.filename.as_ref());
+            if entry.mode.is_tree() && self.recurse != Recurse::No {
+                self.next_tree = Some((path.clone(), entry.oid));
+            }
+            return Some(Ok(Entry {
+                mode: entry.mode,
+                oid: entry.oid,
+                path,
+            }));
+        }
+    }
+}
+
+fn push_component(path: &mut BString, component: &BStr) {
+    if !path.is_empty() {
+        path.push_byte(b'/');
+    }
+    path.push_str(component);
+}
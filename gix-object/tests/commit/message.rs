@@ -220,6 +220,154 @@ mod body {
     }
 }
 
+mod trailer {
+    use gix_object::commit::message::{
+        body::TrailerRef,
+        trailer::{Block, Editor, Item},
+    };
+
+    fn trailer<'a>(token: &'a str, value: &'a str) -> TrailerRef<'a> {
+        TrailerRef {
+            token: token.into(),
+            value: value.into(),
+        }
+    }
+
+    fn trailers(message: &str) -> Vec<TrailerRef<'_>> {
+        Block::from_message(message.as_bytes()).trailers().collect()
+    }
+
+    #[test]
+    fn last_paragraph_of_trailers_is_the_trailer_block() {
+        let message = "title\n\nbody\n\nSigned-off-by: A <a@example.com>\nCo-authored-by: B <b@example.com>\n\n";
+        let block = Block::from_message(message.as_bytes());
+        assert_eq!(block.before, "title\n\nbody\n");
+        assert_eq!(block.separator, "\n");
+        assert_eq!(
+            block.lines,
+            "Signed-off-by: A <a@example.com>\nCo-authored-by: B <b@example.com>\n"
+        );
+        assert_eq!(block.after, "\n");
+        assert_eq!(
+            block.trailers().collect::<Vec<_>>(),
+            [
+                trailer("Signed-off-by", "A <a@example.com>"),
+                trailer("Co-authored-by", "B <b@example.com>")
+            ]
+        );
+    }
+
+    #[test]
+    fn continuation_lines_are_part_of_the_value() {
+        assert_eq!(
+            trailers("title\n\nReviewed-by: x\n  continued here\nAcked-by: y\n"),
+            [trailer("Reviewed-by", "x\n  continued here"), trailer("Acked-by", "y")]
+        );
+    }
+
+    #[test]
+    fn trailers_are_recognized_like_in_the_body() {
+        let message = "title\n\nbody\n\nhello foo: bar there\nKey: value\n";
+        assert_eq!(
+            trailers(message),
+            [trailer("hello foo", "bar there"), trailer("Key", "value")]
+        );
+        assert_eq!(
+            trailers(message),
+            gix_object::commit::MessageRef::from_bytes(message.as_bytes())
+                .body()
+                .expect("present")
+                .trailers()
+                .collect::<Vec<_>>(),
+        );
+        assert!(
+            Block::from_message(b"title\n\nKey: value\nAcked-by : y\n").is_empty(),
+            "`Acked-by : y` isn't a trailer, so the paragraph is prose"
+        );
+    }
+
+    #[test]
+    fn prose_with_a_colon_is_not_a_trailer_block() {
+        for message in [
+            "title\n\nNote: this is prose\nwith a colon.\n",
+            "title\n\nKey: value\nKey:value\n",
+            "Key: value\n",
+            "title\n\nKey: value\n\nprose\n",
+        ] {
+            let block = Block::from_message(message.as_bytes());
+            assert!(block.is_empty(), "{message:?}");
+            assert_eq!(block.before, message);
+        }
+    }
+
+    #[test]
+    fn a_quarter_of_trailers_suffice_if_one_was_generated_by_git() {
+        let message = "title\n\nsome text\nmore text\nother\nSigned-off-by: z\n";
+        let block = Block::from_message(message.as_bytes());
+        assert_eq!(
+            block.items().collect::<Vec<_>>(),
+            [
+                Item::Other("some text".into()),
+                Item::Other("more text".into()),
+                Item::Other("other".into()),
+                Item::Trailer(trailer("Signed-off-by", "z")),
+            ]
+        );
+        assert!(
+            Block::from_message(b"title\n\nsome text\nmore text\nother\nmore\nSigned-off-by: z\n").is_empty(),
+            "but not less"
+        );
+        assert!(
+            Block::from_message(b"title\n\nsome text\nmore text\nother\nReviewed-by: z\n").is_empty(),
+            "all lines must be trailers otherwise"
+        );
+    }
+
+    #[test]
+    fn editing_keeps_all_other_text() {
+        let message = "title\n\nbody: with colon\n\nReviewed-by: x\n  continued\nAcked-by: y\nacked-by: z\n";
+        let mut editor = Editor::from_message(message.as_bytes());
+        assert_eq!(editor.to_message(), message, "unchanged messages round-trip");
+
+        editor.add("Signed-off-by", "A <a@example.com>");
+        assert_eq!(
+            editor.to_message(),
+            format!("{message}Signed-off-by: A <a@example.com>\n"),
+            "existing lines are kept as they are",
+        );
+
+        editor.replace("ACKED-BY", "new");
+        assert_eq!(
+            editor.to_message(),
+            "title\n\nbody: with colon\n\nReviewed-by: x\n  continued\n\
+             ACKED-BY: new\nSigned-off-by: A <a@example.com>\n",
+            "tokens match case-insensitively, and the first match is replaced"
+        );
+
+        assert_eq!(editor.remove("reviewed-by".into()), 1);
+        assert_eq!(editor.remove("signed-off-by".into()), 1);
+        assert_eq!(editor.remove("acked-by".into()), 1);
+        assert_eq!(editor.remove("acked-by".into()), 0);
+        assert_eq!(editor.trailers().count(), 0);
+        assert_eq!(editor.to_message(), "title\n\nbody: with colon\n");
+    }
+
+    #[test]
+    fn trailers_are_added_as_new_paragraph() {
+        for (message, expected) in [
+            ("title", "title\n\nKey: value\n"),
+            ("title\n", "title\n\nKey: value\n"),
+            ("title\n\nprose text.\n\n", "title\n\nprose text.\n\nKey: value\n\n"),
+            ("", "Key: value\n"),
+        ] {
+            let actual = Editor::from_message(message.as_bytes())
+                .replace("Key", "value")
+                .to_message();
+            assert_eq!(actual, expected, "{message:?}");
+        }
+    }
+}
+
 mod summary {
     use std::borrow::Cow;
 
@@ -150,6 +150,17 @@ mod from_bytes {
         Ok(())
     }
 
+    #[test]
+    fn signed_with_ssh() -> crate::Result {
+        let data = fixture_name("tag", "signed-ssh.txt");
+        let tag = TagRef::from_bytes(&data)?;
+        assert_eq!(tag.message, "for the signature");
+        let signature = tag.pgp_signature.expect("present");
+        assert!(signature.starts_with(b"-----BEGIN SSH SIGNATURE-----\n"));
+        assert!(signature.trim_end().ends_with(b"\n-----END SSH SIGNATURE-----"));
+        Ok(())
+    }
+
     #[test]
     fn empty() -> crate::Result {
         assert_eq!(
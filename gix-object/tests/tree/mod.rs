@@ -274,3 +274,169 @@ mod entry_mode {
         }
     }
 }
+
+mod walk {
+    use std::{cell::RefCell, collections::HashMap};
+
+    use gix_hash::ObjectId;
+    use gix_object::{
+        bstr::{BStr, ByteSlice},
+        tree,
+        tree::walk::Recurse,
+        Tree, WriteTo,
+    };
+
+    /// An object database with only trees, which records which trees were read.
+    #[derive(Default)]
+    struct Trees {
+        data: HashMap<ObjectId, Vec<u8>>,
+        reads: RefCell<Vec<ObjectId>>,
+    }
+
+    impl gix_object::Find for Trees {
+        fn try_find<'a>(
+            &self,
+            id: &gix_hash::oid,
+            buffer: &'a mut Vec<u8>,
+        ) -> Result<Option<gix_object::Data<'a>>, gix_object::find::Error> {
+            let Some(data) = self.data.get(id) else {
+                return Ok(None);
+            };
+            self.reads.borrow_mut().push(id.to_owned());
+            buffer.clear();
+            buffer.extend_from_slice(data);
+            Ok(Some(gix_object::Data::new(gix_object::Kind::Tree, buffer)))
+        }
+    }
+
+    impl Trees {
+        fn insert(&mut self, entries: &[(&str, tree::EntryKind, ObjectId)]) -> ObjectId {
+            let mut tree = Tree {
+                entries: entries
+                    .iter()
+                    .map(|(name, kind, oid)| tree::Entry {
+                        mode: (*kind).into(),
+                        filename: (*name).into(),
+                        oid: *oid,
+                    })
+                    .collect(),
+            };
+            tree.entries.sort();
+            let mut data = Vec::new();
+            tree.write_to(&mut data).expect("in-memory write works");
+            let id = gix_object::compute_hash(gix_hash::Kind::Sha1, gix_object::Kind::Tree, &data);
+            self.data.insert(id, data);
+            id
+        }
+    }
+
+    fn blob() -> ObjectId {
+        ObjectId::empty_blob(gix_hash::Kind::Sha1)
+    }
+
+    fn submodule() -> ObjectId {
+        crate::hex_to_id("b2d1b5d684bdfda5f922b466cc13d4ce2d635cf8")
+    }
+
+    /// Returns the trees and the ids of the root tree, `a`, `a/nested` and `z`.
+    fn trees() -> (Trees, [ObjectId; 4]) {
+        use tree::EntryKind::*;
+        let mut trees = Trees::default();
+        let nested = trees.insert(&[("deep", Blob, blob())]);
+        let a = trees.insert(&[("file", BlobExecutable, blob()), ("nested", Tree, nested)]);
+        let z = trees.insert(&[("zfile", Link, blob())]);
+        let root = trees.insert(&[
+            ("a", Tree, a),
+            ("b", Blob, blob()),
+            ("sub", Commit, submodule()),
+            ("z", Tree, z),
+        ]);
+        (trees, [root, a, nested, z])
+    }
+
+    fn paths(trees: &Trees, root: ObjectId, prefix: &str, recurse: Recurse) -> crate::Result<Vec<String>> {
+        Ok(tree::Walk::new(root, prefix.into(), recurse, trees)?
+            .map(|entry| entry.map(|entry| entry.path.to_str_lossy().into_owned()))
+            .collect::<Result<_, _>>()?)
+    }
+
+    #[test]
+    fn recurse_policies() -> crate::Result {
+        let (trees, [root, ..]) = trees();
+        assert_eq!(paths(&trees, root, "", Recurse::No)?, ["a", "b", "sub", "z"]);
+        assert_eq!(trees.reads.take(), [root], "only the root tree is read");
+
+        assert_eq!(
+            paths(&trees, root, "", Recurse::All)?,
+            ["a", "a/file", "a/nested", "a/nested/deep", "b", "sub", "z", "z/zfile"],
+            "trees are yielded before their entries"
+        );
+        assert_eq!(
+            paths(&trees, root, "", Recurse::SkipSubmodules)?,
+            ["a", "a/file", "a/nested", "a/nested/deep", "b", "z", "z/zfile"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn submodules_are_leaves_with_their_commit() -> crate::Result {
+        let (trees, [root, ..]) = trees();
+        let entry = tree::Walk::new(root, "sub".into(), Recurse::All, &trees)?
+            .next()
+            .expect("present")?;
+        assert_eq!(entry.mode, tree::EntryKind::Commit.into());
+        assert_eq!(entry.oid, submodule());
+        assert_eq!(entry.path, "sub");
+        Ok(())
+    }
+
+    #[test]
+    fn prefixes_only_read_the_trees_they_need() -> crate::Result {
+        let (trees, [root, a, nested, _z]) = trees();
+        for prefix in ["a/nested", "/a/nested/", "a//nested"] {
+            assert_eq!(paths(&trees, root, prefix, Recurse::All)?, ["a/nested/deep"]);
+            assert_eq!(trees.reads.take(), [root, a, nested]);
+        }
+
+        assert_eq!(paths(&trees, root, "a", Recurse::No)?, ["a/file", "a/nested"]);
+        assert_eq!(
+            paths(&trees, root, "a/file", Recurse::All)?,
+            ["a/file"],
+            "leaves yield themselves"
+        );
+        for missing in ["missing", "a/missing", "a/file/below-a-blob"] {
+            assert!(paths(&trees, root, missing, Recurse::All)?.is_empty());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn subtrees_are_read_lazily() -> crate::Result {
+        let (trees, [root, a, ..]) = trees();
+        let mut walk = tree::Walk::new(root, BStr::new(""), Recurse::All, &trees)?;
+        assert!(
+            trees.reads.take().is_empty(),
+            "nothing happens until the iteration starts"
+        );
+        assert_eq!(walk.next().expect("present")?.path, "a");
+        assert_eq!(trees.reads.take(), [root]);
+        assert_eq!(walk.next().expect("present")?.path, "a/file");
+        assert_eq!(trees.reads.take(), [a]);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_subtrees_are_errors() -> crate::Result {
+        let mut trees = Trees::default();
+        let missing = crate::hex_to_id("1111111111111111111111111111111111111111");
+        let root = trees.insert(&[("dir", tree::EntryKind::Tree, missing)]);
+        let mut walk = tree::Walk::new(root, "".into(), Recurse::All, &trees)?;
+        assert_eq!(walk.next().expect("present")?.path, "dir");
+        assert!(matches!(
+            walk.next().expect("error"),
+            Err(tree::walk::Error::Find(gix_object::find::existing_iter::Error::NotFound { oid } )) if oid == missing
+        ));
+        assert!(walk.next().is_none(), "the iteration stops after errors");
+        Ok(())
+    }
+}
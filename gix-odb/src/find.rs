@@ -18,6 +18,22 @@ pub enum Header {
     Packed(gix_pack::data::decode::header::Outcome),
 }
 
+/// A reader over the data of an object that decompresses it on demand, as obtained by
+/// [`Handle::try_find_stream()`](crate::store::Handle::try_find_stream()) or
+/// [`loose::Store::try_find_stream()`](crate::loose::Store::try_find_stream()).
+///
+/// This is useful for large objects which shouldn't be held in memory all at once. Loose objects and objects stored as base
+/// in a pack are streamed directly from their compressed representation, while objects stored as delta in a pack are
+/// decoded into memory once as they can't be reconstructed incrementally.
+///
+/// Seeking forward decompresses and discards all data in between, and seeking backwards
+/// restarts decompression from the beginning unless the data is held in memory.
+pub struct Stream {
+    kind: gix_object::Kind,
+    size: u64,
+    inner: stream::Inner,
+}
+
 mod header {
     use super::Header;
 
@@ -60,3 +76,254 @@ mod header {
         }
     }
 }
+
+pub(crate) mod stream {
+    use std::{
+        io,
+        io::{BufRead, Read, Seek, SeekFrom},
+        sync::Arc,
+    };
+
+    use gix_features::zlib;
+
+    use super::Stream;
+
+    /// Where compressed object data is read from.
+    pub(crate) enum Source {
+        Loose(io::BufReader<std::fs::File>),
+        Pack {
+            pack: Arc<gix_pack::data::File>,
+            /// The offset at which the compressed data of the entry starts in the pack.
+            data_offset: gix_pack::data::Offset,
+            /// The amount of compressed bytes consumed so far.
+            pos: u64,
+        },
+    }
+
+    impl Source {
+        fn rewind(&mut self) -> io::Result<()> {
+            match self {
+                Source::Loose(file) => file.seek(SeekFrom::Start(0)).map(|_| ()),
+                Source::Pack { pos, .. } => {
+                    *pos = 0;
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    impl Read for Source {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = {
+                let data = self.fill_buf()?;
+                let n = data.len().min(buf.len());
+                buf[..n].copy_from_slice(&data[..n]);
+                n
+            };
+            self.consume(n);
+            Ok(n)
+        }
+    }
+
+    impl BufRead for Source {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            match self {
+                Source::Loose(file) => file.fill_buf(),
+                Source::Pack { pack, data_offset, pos } => Ok(pack
+                    .entry_slice(*data_offset + *pos..pack.pack_end() as u64)
+                    .unwrap_or_default()),
+            }
+        }
+
+        fn consume(&mut self, amt: usize) {
+            match self {
+                Source::Loose(file) => file.consume(amt),
+                Source::Pack { pos, .. } => *pos += amt as u64,
+            }
+        }
+    }
+
+    pub(crate) enum Inner {
+        /// Decompress the object from `source`, which starts with a header of `header_size` bytes, and keep track of the
+        /// position within the decompressed object data.
+        Inflate {
+            source: Source,
+            state: zlib::Decompress,
+            header_size: usize,
+            pos: u64,
+        },
+        /// The whole object is held in memory.
+        Buffered(io::Cursor<Vec<u8>>),
+    }
+
+    /// Initialization
+    impl Stream {
+        /// Stream a loose object from `source` after its header of `header_size` bytes was already decompressed using `inflate`.
+        pub(crate) fn from_loose(
+            kind: gix_object::Kind,
+            size: u64,
+            source: io::BufReader<std::fs::File>,
+            inflate: zlib::Inflate,
+            header_size: usize,
+        ) -> Self {
+            Stream {
+                kind,
+                size,
+                inner: Inner::Inflate {
+                    source: Source::Loose(source),
+                    state: inflate.state,
+                    header_size,
+                    pos: 0,
+                },
+            }
+        }
+
+        /// Stream a base object in `pack` whose compressed data starts at `data_offset`.
+        pub(crate) fn from_pack(
+            kind: gix_object::Kind,
+            size: u64,
+            pack: Arc<gix_pack::data::File>,
+            data_offset: gix_pack::data::Offset,
+        ) -> Self {
+            Stream {
+                kind,
+                size,
+                inner: Inner::Inflate {
+                    source: Source::Pack {
+                        pack,
+                        data_offset,
+                        pos: 0,
+                    },
+                    state: zlib::Inflate::default().state,
+                    header_size: 0,
+                    pos: 0,
+                },
+            }
+        }
+
+        /// Provide the fully decoded object `data` of the given `kind`.
+        pub(crate) fn from_data(kind: gix_object::Kind, data: Vec<u8>) -> Self {
+            Stream {
+                kind,
+                size: data.len() as u64,
+                inner: Inner::Buffered(io::Cursor::new(data)),
+            }
+        }
+    }
+
+    /// Access
+    impl Stream {
+        /// Return the kind of the object.
+        pub fn kind(&self) -> gix_object::Kind {
+            self.kind
+        }
+
+        /// Return the size of the object's data in bytes, which is also the amount of bytes that can be read from the beginning.
+        pub fn size(&self) -> u64 {
+            self.size
+        }
+    }
+
+    impl std::fmt::Debug for Stream {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Stream")
+                .field("kind", &self.kind)
+                .field("size", &self.size)
+                .finish_non_exhaustive()
+        }
+    }
+
+    fn unexpected_eof() -> io::Error {
+        io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "the compressed object data ended before the object was complete",
+        )
+    }
+
+    /// Decompress `len` bytes from `source` into nothing.
+    fn discard(source: &mut Source, state: &mut zlib::Decompress, mut len: u64) -> io::Result<()> {
+        let mut buf = [0; 8192];
+        while len != 0 {
+            let chunk = len.min(buf.len() as u64) as usize;
+            let n = zlib::stream::inflate::read(source, state, &mut buf[..chunk])?;
+            if n == 0 {
+                return Err(unexpected_eof());
+            }
+            len -= n as u64;
+        }
+        Ok(())
+    }
+
+    impl Read for Stream {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match &mut self.inner {
+                Inner::Buffered(data) => data.read(buf),
+                Inner::Inflate { source, state, pos, .. } => {
+                    let remaining = self.size.saturating_sub(*pos);
+                    if remaining == 0 || buf.is_empty() {
+                        return Ok(0);
+                    }
+                    let len = buf.len().min(remaining.try_into().unwrap_or(usize::MAX));
+                    let n = zlib::stream::inflate::read(source, state, &mut buf[..len])?;
+                    if n == 0 {
+                        return Err(unexpected_eof());
+                    }
+                    *pos += n as u64;
+                    Ok(n)
+                }
+            }
+        }
+    }
+
+    impl Seek for Stream {
+        fn seek(&mut self, seek: SeekFrom) -> io::Result<u64> {
+            let (base, offset) = match seek {
+                SeekFrom::Start(target) => (target, 0),
+                SeekFrom::End(offset) => (self.size, offset),
+                SeekFrom::Current(offset) => (self.stream_position()?, offset),
+            };
+            let target = if offset >= 0 {
+                base.checked_add(offset as u64)
+            } else {
+                base.checked_sub(offset.unsigned_abs())
+            }
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "cannot seek to a negative or overflowing position",
+                )
+            })?;
+
+            match &mut self.inner {
+                Inner::Buffered(data) => data.seek(SeekFrom::Start(target)),
+                Inner::Inflate {
+                    source,
+                    state,
+                    header_size,
+                    pos,
+                } => {
+                    // Past the end, the decompressor stops at the end of the object.
+                    let decompressed = (*pos).min(self.size);
+                    let decompressed = if target < decompressed {
+                        state.reset(true);
+                        source.rewind()?;
+                        discard(source, state, *header_size as u64)?;
+                        0
+                    } else {
+                        decompressed
+                    };
+                    discard(source, state, target.min(self.size).saturating_sub(decompressed))?;
+                    *pos = target;
+                    Ok(target)
+                }
+            }
+        }
+
+        fn stream_position(&mut self) -> io::Result<u64> {
+            Ok(match &mut self.inner {
+                Inner::Buffered(data) => data.position(),
+                Inner::Inflate { pos, .. } => *pos,
+            })
+        }
+    }
+}
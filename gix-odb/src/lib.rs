@@ -140,6 +140,8 @@ pub struct Store {
     use_multi_pack_index: bool,
     /// The hash kind to use for some operations
     object_hash: gix_hash::Kind,
+    /// How to open pack data files.
+    pack_options: gix_pack::data::init::Options,
 }
 
 /// Create a new cached handle to the object store with support for additional options.
@@ -17,6 +17,11 @@ impl Store {
         self.use_multi_pack_index
     }
 
+    /// The options to use when opening pack data files.
+    pub fn pack_options(&self) -> gix_pack::data::init::Options {
+        self.pack_options
+    }
+
     /// An iterator over replacements from object-ids `X` to `X-replaced` as `(X, X-replaced)`, sorted by the original id `X`.
     pub fn replacements(&self) -> impl Iterator<Item = (gix_hash::ObjectId, gix_hash::ObjectId)> + '_ {
         self.replacements.iter().copied()
@@ -46,6 +46,19 @@ pub(crate) mod error {
         },
     }
 
+    impl Error {
+        /// Return the limit that was exceeded if this error was caused by it, for use with
+        /// [`Handle::limits`][crate::store::Handle::limits].
+        pub fn limit_exceeded(&self) -> Option<&pack::data::decode::LimitExceeded> {
+            match self {
+                Error::Loose(loose::find::Error::LimitExceeded { source, .. }) => Some(source),
+                Error::Pack(pack::data::decode::Error::LimitExceeded(err)) => Some(err),
+                Error::DeltaBaseLookup { err, .. } => err.limit_exceeded(),
+                _ => None,
+            }
+        }
+    }
+
     #[derive(Copy, Clone)]
     pub(crate) struct DeltaBaseRecursion<'a> {
         pub depth: usize,
@@ -81,11 +94,24 @@ use gix_features::zlib;
 
 use crate::store::types::PackId;
 
+/// The place an object is stored at, as returned by [`Handle::locate()`](super::Handle::locate()).
+pub(crate) enum Location {
+    /// The object is stored in `pack`, which belongs to the index at `index` in the snapshot.
+    Packed {
+        index: usize,
+        pack_id: PackId,
+        pack_offset: gix_pack::data::Offset,
+        pack: std::sync::Arc<gix_pack::data::File>,
+    },
+    /// The object is stored in the loose object database at `index` in the snapshot.
+    Loose { index: usize },
+}
+
 impl<S> super::Handle<S>
 where
     S: Deref<Target = super::Store> + Clone,
 {
-    fn try_find_cached_inner<'a, 'b>(
+    pub(crate) fn try_find_cached_inner<'a, 'b>(
         &'b self,
         mut id: &'b gix_hash::oid,
         buffer: &'a mut Vec<u8>,
@@ -111,22 +137,185 @@ where
             }
         }
 
+        match self.locate(id, snapshot)? {
+            Some(Location::Packed {
+                index: idx,
+                pack_id,
+                pack_offset,
+                pack,
+            }) => {
+                let index_file = snapshot.indices[idx].intra_pack_lookup(pack_id);
+                let entry = pack.entry(pack_offset)?;
+                let header_size = entry.header_size();
+                let res = pack.decode_entry_with_limits(
+                    entry,
+                    buffer,
+                    inflate,
+                    &|id, _out| {
+                        let pack_offset = index_file.pack_offset_by_id(id)?;
+                        pack.entry(pack_offset)
+                            .ok()
+                            .map(gix_pack::data::decode::entry::ResolvedBase::InPack)
+                    },
+                    pack_cache,
+                    &self.limits,
+                );
+                let res = match res {
+                    Ok(r) => Ok((
+                        gix_object::Data {
+                            kind: r.kind,
+                            data: buffer.as_slice(),
+                        },
+                        Some(gix_pack::data::entry::Location {
+                            pack_id: pack.id,
+                            pack_offset,
+                            entry_size: r.compressed_size + header_size,
+                        }),
+                    )),
+                    Err(gix_pack::data::decode::Error::DeltaBaseUnresolved(base_id)) => {
+                        // Only with multi-pack indices it's allowed to jump to refer to other packs within this
+                        // multi-pack. Otherwise this would constitute a thin pack which is only allowed in transit.
+                        // However, if we somehow end up with that, we will resolve it safely, even though we could
+                        // avoid handling this case and error instead.
+
+                        // Since this is a special case, we just allocate here to make it work. It's an actual delta-ref object
+                        // which is sent by some servers that points to an object outside of the pack we are looking
+                        // at right now. With the complexities of loading packs, we go into recursion here. Git itself
+                        // doesn't do a cycle check, and we won't either but limit the recursive depth.
+                        // The whole ordeal isn't as efficient as it could be due to memory allocation and
+                        // later mem-copying when trying again.
+                        let mut buf = Vec::new();
+                        let obj_kind = self
+                            .try_find_cached_inner(
+                                &base_id,
+                                &mut buf,
+                                inflate,
+                                pack_cache,
+                                snapshot,
+                                recursion
+                                    .map(error::DeltaBaseRecursion::inc_depth)
+                                    .or_else(|| error::DeltaBaseRecursion::new(id).into()),
+                            )
+                            .map_err(|err| Error::DeltaBaseLookup {
+                                err: Box::new(err),
+                                base_id,
+                                id: id.to_owned(),
+                            })?
+                            .ok_or_else(|| Error::DeltaBaseMissing {
+                                base_id,
+                                id: id.to_owned(),
+                            })?
+                            .0
+                            .kind;
+                        let handle::index_lookup::Outcome {
+                            object_index:
+                                handle::IndexForObjectInPack {
+                                    pack_id: _,
+                                    pack_offset,
+                                },
+                            index_file,
+                            pack: possibly_pack,
+                        } = match snapshot.indices[idx].lookup(id) {
+                            Some(res) => res,
+                            None => {
+                                let mut out = None;
+                                for index in &mut snapshot.indices {
+                                    out = index.lookup(id);
+                                    if out.is_some() {
+                                        break;
+                                    }
+                                }
+
+                                out.unwrap_or_else(|| {
+                                    panic!("could not find object {id} in any index after looking up one of its base objects {base_id}" )
+                                })
+                            }
+                        };
+                        let pack = possibly_pack
+                            .as_ref()
+                            .expect("pack to still be available like just now");
+                        let entry = pack.entry(pack_offset)?;
+                        let header_size = entry.header_size();
+                        pack.decode_entry_with_limits(
+                            entry,
+                            buffer,
+                            inflate,
+                            &|id, out| {
+                                index_file
+                                    .pack_offset_by_id(id)
+                                    .and_then(|pack_offset| {
+                                        pack.entry(pack_offset)
+                                            .ok()
+                                            .map(gix_pack::data::decode::entry::ResolvedBase::InPack)
+                                    })
+                                    .or_else(|| {
+                                        (id == base_id).then(|| {
+                                            out.resize(buf.len(), 0);
+                                            out.copy_from_slice(buf.as_slice());
+                                            gix_pack::data::decode::entry::ResolvedBase::OutOfPack {
+                                                kind: obj_kind,
+                                                end: out.len(),
+                                            }
+                                        })
+                                    })
+                            },
+                            pack_cache,
+                            &self.limits,
+                        )
+                        .map(move |r| {
+                            (
+                                gix_object::Data {
+                                    kind: r.kind,
+                                    data: buffer.as_slice(),
+                                },
+                                Some(gix_pack::data::entry::Location {
+                                    pack_id: pack.id,
+                                    pack_offset,
+                                    entry_size: r.compressed_size + header_size,
+                                }),
+                            )
+                        })
+                    }
+                    Err(err) => Err(err),
+                }?;
+
+                if idx != 0 {
+                    snapshot.indices.swap(0, idx);
+                }
+                Ok(Some(res))
+            }
+            Some(Location::Loose { index }) => snapshot.loose_dbs[index]
+                .try_find_with_limits(id, buffer, &self.limits)
+                .map(|obj| obj.map(|obj| (obj, None)))
+                .map_err(Into::into),
+            None => Ok(None),
+        }
+    }
+
+    /// Find where the object with `id` is stored, loading the pack containing it and refreshing `snapshot` as needed.
+    ///
+    /// Note that `id` isn't replaced, which is up to the caller.
+    pub(crate) fn locate(
+        &self,
+        id: &gix_hash::oid,
+        snapshot: &mut load_index::Snapshot,
+    ) -> Result<Option<Location>, Error> {
         'outer: loop {
             {
                 let marker = snapshot.marker;
                 for (idx, index) in snapshot.indices.iter_mut().enumerate() {
                     if let Some(handle::index_lookup::Outcome {
                         object_index: handle::IndexForObjectInPack { pack_id, pack_offset },
-                        index_file,
+                        index_file: _,
                         pack: possibly_pack,
                     }) = index.lookup(id)
                     {
                         let pack = match possibly_pack {
-                            Some(pack) => pack,
+                            Some(pack) => pack.clone(),
                             None => match self.store.load_pack(pack_id, marker)? {
                                 Some(pack) => {
-                                    *possibly_pack = Some(pack);
-                                    possibly_pack.as_deref().expect("just put it in")
+                                    *possibly_pack = Some(pack.clone());
+                                    pack
                                 }
                                 None => {
                                     // The pack wasn't available anymore so we are supposed to try another round with a fresh index
@@ -146,153 +335,19 @@ where
                                 }
                             },
                         };
-                        let entry = pack.entry(pack_offset)?;
-                        let header_size = entry.header_size();
-                        let res = pack.decode_entry(
-                            entry,
-                            buffer,
-                            inflate,
-                            &|id, _out| {
-                                let pack_offset = index_file.pack_offset_by_id(id)?;
-                                pack.entry(pack_offset)
-                                    .ok()
-                                    .map(gix_pack::data::decode::entry::ResolvedBase::InPack)
-                            },
-                            pack_cache,
-                        );
-                        let res = match res {
-                            Ok(r) => Ok((
-                                gix_object::Data {
-                                    kind: r.kind,
-                                    data: buffer.as_slice(),
-                                },
-                                Some(gix_pack::data::entry::Location {
-                                    pack_id: pack.id,
-                                    pack_offset,
-                                    entry_size: r.compressed_size + header_size,
-                                }),
-                            )),
-                            Err(gix_pack::data::decode::Error::DeltaBaseUnresolved(base_id)) => {
-                                // Only with multi-pack indices it's allowed to jump to refer to other packs within this
-                                // multi-pack. Otherwise this would constitute a thin pack which is only allowed in transit.
-                                // However, if we somehow end up with that, we will resolve it safely, even though we could
-                                // avoid handling this case and error instead.
-
-                                // Since this is a special case, we just allocate here to make it work. It's an actual delta-ref object
-                                // which is sent by some servers that points to an object outside of the pack we are looking
-                                // at right now. With the complexities of loading packs, we go into recursion here. Git itself
-                                // doesn't do a cycle check, and we won't either but limit the recursive depth.
-                                // The whole ordeal isn't as efficient as it could be due to memory allocation and
-                                // later mem-copying when trying again.
-                                let mut buf = Vec::new();
-                                let obj_kind = self
-                                    .try_find_cached_inner(
-                                        &base_id,
-                                        &mut buf,
-                                        inflate,
-                                        pack_cache,
-                                        snapshot,
-                                        recursion
-                                            .map(error::DeltaBaseRecursion::inc_depth)
-                                            .or_else(|| error::DeltaBaseRecursion::new(id).into()),
-                                    )
-                                    .map_err(|err| Error::DeltaBaseLookup {
-                                        err: Box::new(err),
-                                        base_id,
-                                        id: id.to_owned(),
-                                    })?
-                                    .ok_or_else(|| Error::DeltaBaseMissing {
-                                        base_id,
-                                        id: id.to_owned(),
-                                    })?
-                                    .0
-                                    .kind;
-                                let handle::index_lookup::Outcome {
-                                    object_index:
-                                        handle::IndexForObjectInPack {
-                                            pack_id: _,
-                                            pack_offset,
-                                        },
-                                    index_file,
-                                    pack: possibly_pack,
-                                } = match snapshot.indices[idx].lookup(id) {
-                                    Some(res) => res,
-                                    None => {
-                                        let mut out = None;
-                                        for index in &mut snapshot.indices {
-                                            out = index.lookup(id);
-                                            if out.is_some() {
-                                                break;
-                                            }
-                                        }
-
-                                        out.unwrap_or_else(|| {
-                                           panic!("could not find object {id} in any index after looking up one of its base objects {base_id}" )
-                                       })
-                                    }
-                                };
-                                let pack = possibly_pack
-                                    .as_ref()
-                                    .expect("pack to still be available like just now");
-                                let entry = pack.entry(pack_offset)?;
-                                let header_size = entry.header_size();
-                                pack.decode_entry(
-                                    entry,
-                                    buffer,
-                                    inflate,
-                                    &|id, out| {
-                                        index_file
-                                            .pack_offset_by_id(id)
-                                            .and_then(|pack_offset| {
-                                                pack.entry(pack_offset)
-                                                    .ok()
-                                                    .map(gix_pack::data::decode::entry::ResolvedBase::InPack)
-                                            })
-                                            .or_else(|| {
-                                                (id == base_id).then(|| {
-                                                    out.resize(buf.len(), 0);
-                                                    out.copy_from_slice(buf.as_slice());
-                                                    gix_pack::data::decode::entry::ResolvedBase::OutOfPack {
-                                                        kind: obj_kind,
-                                                        end: out.len(),
-                                                    }
-                                                })
-                                            })
-                                    },
-                                    pack_cache,
-                                )
-                                .map(move |r| {
-                                    (
-                                        gix_object::Data {
-                                            kind: r.kind,
-                                            data: buffer.as_slice(),
-                                        },
-                                        Some(gix_pack::data::entry::Location {
-                                            pack_id: pack.id,
-                                            pack_offset,
-                                            entry_size: r.compressed_size + header_size,
-                                        }),
-                                    )
-                                })
-                            }
-                            Err(err) => Err(err),
-                        }?;
-
-                        if idx != 0 {
-                            snapshot.indices.swap(0, idx);
-                        }
-                        return Ok(Some(res));
+                        return Ok(Some(Location::Packed {
+                            index: idx,
+                            pack_id,
+                            pack_offset,
+                            pack,
+                        }));
                     }
                 }
             }
 
-            for lodb in snapshot.loose_dbs.iter() {
-                // TODO: remove this double-lookup once the borrow checker allows it.
+            for (idx, lodb) in snapshot.loose_dbs.iter().enumerate() {
                 if lodb.contains(id) {
-                    return lodb
-                        .try_find(id, buffer)
-                        .map(|obj| obj.map(|obj| (obj, None)))
-                        .map_err(Into::into);
+                    return Ok(Some(Location::Loose { index: idx }));
                 }
             }
 
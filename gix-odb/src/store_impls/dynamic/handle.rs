@@ -111,6 +111,19 @@ pub(crate) mod index_lookup {
             })
         }
 
+        /// Return the index for resolving ref-delta objects within the pack with `pack_id`, which is part of this index.
+        pub(crate) fn intra_pack_lookup(&self, pack_id: types::PackId) -> IntraPackLookup<'_> {
+            match &self.file {
+                handle::SingleOrMultiIndex::Single { index, .. } => IntraPackLookup::Single(index),
+                handle::SingleOrMultiIndex::Multi { index, .. } => IntraPackLookup::Multi {
+                    index,
+                    required_pack_index: pack_id.multipack_index.expect(
+                        "BUG: multi-pack index must be set if this is a multi-pack, pack-indices seem unstable",
+                    ),
+                },
+            }
+        }
+
         /// Return true if the given object id exists in this index
         pub(crate) fn contains(&self, object_id: &oid) -> bool {
             match &self.file {
@@ -259,6 +272,7 @@ impl super::Store {
             inflate: RefCell::new(Default::default()),
             snapshot: RefCell::new(self.collect_snapshot()),
             max_recursion_depth: Self::INITIAL_MAX_RECURSION_DEPTH,
+            limits: Default::default(),
             packed_object_count: Default::default(),
         }
     }
@@ -276,6 +290,7 @@ impl super::Store {
             inflate: RefCell::new(Default::default()),
             snapshot: RefCell::new(self.collect_snapshot()),
             max_recursion_depth: Self::INITIAL_MAX_RECURSION_DEPTH,
+            limits: Default::default(),
             packed_object_count: Default::default(),
         }
     }
@@ -352,6 +367,7 @@ impl TryFrom<&super::Store> for super::Store {
                 object_hash: Default::default(),
                 use_multi_pack_index: false,
                 current_dir: s.current_dir.clone().into(),
+                pack_options: s.pack_options,
             },
         )
     }
@@ -364,6 +380,7 @@ impl super::Handle<Rc<super::Store>> {
         let mut cache = store.to_handle_arc();
         cache.refresh = self.refresh;
         cache.max_recursion_depth = self.max_recursion_depth;
+        cache.limits = self.limits;
         Ok(cache)
     }
 }
@@ -395,6 +412,7 @@ where
             inflate: RefCell::new(Default::default()),
             snapshot: RefCell::new(self.store.collect_snapshot()),
             max_recursion_depth: self.max_recursion_depth,
+            limits: self.limits,
             packed_object_count: Default::default(),
         }
     }
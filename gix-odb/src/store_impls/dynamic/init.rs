@@ -19,6 +19,10 @@ pub struct Options {
     /// The current directory of the process at the time of instantiation.
     /// If unset, it will be retrieved using `gix_fs::current_dir(false)`.
     pub current_dir: Option<std::path::PathBuf>,
+    /// Control how the data of packs is held, and which access pattern to advise for it.
+    ///
+    /// By default, pack data is memory-mapped and no particular access pattern is advised.
+    pub pack_options: gix_pack::data::init::Options,
 }
 
 impl Default for Options {
@@ -28,6 +32,7 @@ impl Default for Options {
             object_hash: Default::default(),
             use_multi_pack_index: true,
             current_dir: None,
+            pack_options: Default::default(),
         }
     }
 }
@@ -77,6 +82,7 @@ impl Store {
             object_hash,
             use_multi_pack_index,
             current_dir,
+            pack_options,
         }: Options,
     ) -> std::io::Result<Self> {
         let _span = gix_features::trace::detail!("gix_odb::Store::at()");
@@ -124,6 +130,7 @@ impl Store {
             index: ArcSwap::new(Arc::new(SlotMapIndex::default())),
             use_multi_pack_index,
             object_hash,
+            pack_options,
             num_handles_stable: Default::default(),
             num_handles_unstable: Default::default(),
             num_disk_state_consolidation: Default::default(),
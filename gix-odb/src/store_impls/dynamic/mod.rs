@@ -24,6 +24,13 @@ where
     /// If true, replacements will not be performed even if these are available.
     pub ignore_replacements: bool,
 
+    /// Limits to prevent objects from untrusted repositories from taking an unreasonable amount of memory when they
+    /// are decoded, which are disabled by default.
+    ///
+    /// Lookups of objects exceeding them fail with an error whose source is a
+    /// [`LimitExceeded`](gix_pack::data::decode::LimitExceeded) error.
+    pub limits: gix_pack::data::decode::Limits,
+
     pub(crate) token: Option<handle::Mode>,
     snapshot: RefCell<load_index::Snapshot>,
     inflate: RefCell<zlib::Inflate>,
@@ -60,6 +67,8 @@ pub mod prefix;
 
 mod header;
 
+mod stream;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod iter;
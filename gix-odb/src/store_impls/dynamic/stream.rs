@@ -0,0 +1,57 @@
+use std::ops::Deref;
+
+use gix_hash::oid;
+
+use super::find::{Error, Location};
+use crate::{find::Stream, store::load_index};
+
+impl<S> super::Handle<S>
+where
+    S: Deref<Target = super::Store> + Clone,
+{
+    /// Return a [`Stream`] to read the data of the object identified by `id` while decompressing it on demand,
+    /// or `None` if it doesn't exist.
+    ///
+    /// Loose objects and objects stored as base in a pack are streamed, while objects stored as delta are decoded into
+    /// memory first as their data can't be reconstructed incrementally.
+    /// Use [`Header`](crate::Header) to learn about the kind and size of objects without reading them.
+    pub fn try_find_stream(&self, id: &oid) -> Result<Option<Stream>, Error> {
+        let mut snapshot = self.snapshot.borrow_mut();
+        let mut target = id;
+        if !self.ignore_replacements {
+            if let Ok(pos) = self
+                .store
+                .replacements
+                .binary_search_by(|(map_this, _)| map_this.as_ref().cmp(id))
+            {
+                target = self.store.replacements[pos].1.as_ref();
+            }
+        }
+
+        match self.locate(target, &mut snapshot)? {
+            Some(Location::Packed { pack, pack_offset, .. }) => {
+                let entry = pack.entry(pack_offset)?;
+                match entry.header.as_kind() {
+                    Some(kind) => Ok(Some(Stream::from_pack(
+                        kind,
+                        entry.decompressed_size,
+                        pack,
+                        entry.data_offset,
+                    ))),
+                    None => self.find_delta_into_memory(id, &mut snapshot),
+                }
+            }
+            Some(Location::Loose { index }) => snapshot.loose_dbs[index].try_find_stream(target).map_err(Into::into),
+            None => Ok(None),
+        }
+    }
+
+    fn find_delta_into_memory(&self, id: &oid, snapshot: &mut load_index::Snapshot) -> Result<Option<Stream>, Error> {
+        let mut buf = Vec::new();
+        let mut inflate = self.inflate.borrow_mut();
+        let kind = self
+            .try_find_cached_inner(id, &mut buf, &mut inflate, &mut gix_pack::cache::Never, snapshot, None)?
+            .map(|(obj, _location)| obj.kind);
+        Ok(kind.map(|kind| Stream::from_data(kind, buf)))
+    }
+}
@@ -167,7 +167,15 @@ impl super::Store {
                         let data = match bundle.data.loaded() {
                             Some(pack) => pack.deref(),
                             None => {
-                                pack = pack::data::File::at(bundle.data.path(), self.object_hash)?;
+                                // This instance is private to the verification, which reads all of it.
+                                pack = pack::data::File::at_opts(
+                                    bundle.data.path(),
+                                    self.object_hash,
+                                    pack::data::init::Options {
+                                        advice: pack::data::init::Advice::Sequential,
+                                        ..self.pack_options
+                                    },
+                                )?;
                                 &pack
                             }
                         };
@@ -19,6 +19,11 @@ pub enum Error {
     Decode(#[from] gix_object::decode::LooseHeaderDecodeError),
     #[error("Cannot store {size} in memory as it's not representable")]
     OutOfMemory { size: u64 },
+    #[error("The loose object at '{path}' exceeds a limit")]
+    LimitExceeded {
+        source: gix_pack::data::decode::LimitExceeded,
+        path: PathBuf,
+    },
     #[error("Could not {action} data at '{path}'")]
     Io {
         source: std::io::Error,
@@ -109,9 +114,23 @@ impl Store {
         &self,
         id: &gix_hash::oid,
         out: &'a mut Vec<u8>,
+    ) -> Result<Option<gix_object::Data<'a>>, Error> {
+        self.try_find_with_limits(id, out, &Default::default())
+    }
+
+    /// Like [`try_find()`](Self::try_find()), but fail with [`Error::LimitExceeded`] instead of reading objects
+    /// that exceed the given `limits`, which are checked before allocating memory for the object.
+    ///
+    /// Note that [`max_delta_chain_length`](gix_pack::data::decode::Limits::max_delta_chain_length) doesn't apply
+    /// to loose objects as they are never deltified.
+    pub fn try_find_with_limits<'a>(
+        &self,
+        id: &gix_hash::oid,
+        out: &'a mut Vec<u8>,
+        limits: &gix_pack::data::decode::Limits,
     ) -> Result<Option<gix_object::Data<'a>>, Error> {
         debug_assert_eq!(self.object_hash, id.kind());
-        match self.find_inner(id, out) {
+        match self.find_inner(id, out, limits) {
             Ok(obj) => Ok(Some(obj)),
             Err(err) => match err {
                 Error::Io {
@@ -179,7 +198,59 @@ impl Store {
         Ok(Some((size, kind)))
     }
 
-    fn find_inner<'a>(&self, id: &gix_hash::oid, buf: &'a mut Vec<u8>) -> Result<gix_object::Data<'a>, Error> {
+    /// Return a [`Stream`](crate::find::Stream) to read the data of the object identified by `id` while decompressing it
+    /// on demand, or `None` if there is no such object.
+    ///
+    /// Only the object header is decompressed right away to learn about the kind and size of the object.
+    pub fn try_find_stream(&self, id: &gix_hash::oid) -> Result<Option<crate::find::Stream>, Error> {
+        debug_assert_eq!(self.object_hash, id.kind());
+        let path = hash_path(id, self.path.clone());
+        let mut source = match fs::File::open(&path) {
+            Ok(f) => std::io::BufReader::new(f),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(Error::Io {
+                    source: err,
+                    action: Self::OPEN_ACTION,
+                    path,
+                })
+            }
+        };
+
+        let mut inflate = zlib::Inflate::default();
+        let mut header = [0_u8; HEADER_MAX_SIZE];
+        let mut header_len = 0;
+        // Decompress byte by byte so no object data is consumed along with the header.
+        while header_len < HEADER_MAX_SIZE && (header_len == 0 || header[header_len - 1] != 0) {
+            let num_decompressed_bytes =
+                zlib::stream::inflate::read(&mut source, &mut inflate.state, &mut header[header_len..][..1]).map_err(
+                    |e| Error::Io {
+                        source: e,
+                        action: "deflate",
+                        path: path.to_owned(),
+                    },
+                )?;
+            if num_decompressed_bytes == 0 {
+                break;
+            }
+            header_len += 1;
+        }
+        let (kind, size, header_size) = gix_object::decode::loose_header(&header[..header_len])?;
+        Ok(Some(crate::find::Stream::from_loose(
+            kind,
+            size,
+            source,
+            inflate,
+            header_size,
+        )))
+    }
+
+    fn find_inner<'a>(
+        &self,
+        id: &gix_hash::oid,
+        buf: &'a mut Vec<u8>,
+        limits: &gix_pack::data::decode::Limits,
+    ) -> Result<gix_object::Data<'a>, Error> {
         let path = hash_path(id, self.path.clone());
 
         let mut inflate = zlib::Inflate::default();
@@ -218,6 +289,10 @@ impl Store {
         let decompressed_start = bytes_read;
         let (kind, size, header_size) =
             gix_object::decode::loose_header(&buf[decompressed_start..decompressed_start + consumed_out])?;
+        limits.check_object_size(size).map_err(|source| Error::LimitExceeded {
+            source,
+            path: path.clone(),
+        })?;
 
         if status == zlib::Status::StreamEnd {
             let decompressed_body_bytes_sans_header =
@@ -261,6 +336,9 @@ impl Store {
                 .expect("BUG: here the size is already confirmed to fit into memory"),
             0,
         );
+        limits
+            .check_tree_entries(kind, buf)
+            .map_err(|source| Error::LimitExceeded { source, path })?;
         Ok(gix_object::Data { kind, data: buf })
     }
 }
@@ -57,6 +57,9 @@ pub mod find;
 pub mod iter;
 ///
 #[allow(clippy::empty_docs)]
+pub mod repack;
+///
+#[allow(clippy::empty_docs)]
 pub mod verify;
 
 /// The type for an iterator over `Result<gix_hash::ObjectId, Error>)`
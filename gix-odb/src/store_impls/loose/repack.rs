@@ -0,0 +1,325 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    io,
+    io::Seek,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Instant,
+};
+
+use gix_features::progress::{Count, DynNestedProgress, Progress};
+use gix_hash::ObjectId;
+use gix_tempfile::{AutoRemove, ContainingDirectory};
+
+use crate::loose::{self, Store};
+
+/// The error returned by [`Store::repack()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not iterate loose objects")]
+    Iter(#[from] loose::iter::Error),
+    #[error("Could not read loose object")]
+    Find(#[from] loose::find::Error),
+    #[error("Could not lookup object {id} while traversing the objects reachable from the tips")]
+    FindReachable {
+        id: ObjectId,
+        source: gix_object::find::Error,
+    },
+    #[error("{kind} object {id} could not be decoded while traversing the objects reachable from the tips")]
+    Decode {
+        id: ObjectId,
+        kind: gix_object::Kind,
+        source: gix_object::decode::Error,
+    },
+    #[error("An IO error occurred while writing the pack or removing loose objects")]
+    Io(#[from] io::Error),
+    #[error("Could not write pack entries")]
+    PackEntries(#[from] gix_pack::data::input::Error),
+    #[error("Could not write pack and index")]
+    BundleWrite(#[from] gix_pack::bundle::write::Error),
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+/// Determines which loose objects to put into the new pack in [`Store::repack()`].
+#[derive(Clone, Copy)]
+pub enum Selection<'a> {
+    /// All loose objects in the store.
+    All,
+    /// Only loose objects that are reachable from the given `tips`, following commits to their trees and parents,
+    /// trees to their entries and tags to their targets. Submodule commits aren't followed.
+    ///
+    /// As the objects in such a traversal are commonly stored in packs as well, they are read from `objects`
+    /// which should contain all objects of the repository, not only the loose ones. Objects that can't be
+    /// found aren't followed.
+    ReachableFrom {
+        /// The objects to start the traversal at.
+        tips: &'a [ObjectId],
+        /// The database to read all traversed objects from.
+        objects: &'a dyn gix_object::Find,
+    },
+}
+
+/// The options for use in [`Store::repack()`].
+#[derive(Debug, Default, Clone)]
+pub struct Options {
+    /// If `true`, remove all loose objects that were written into the new pack, but only once the pack and its index
+    /// are in place.
+    pub prune: bool,
+    /// The amount of threads to use at most when indexing the new pack. If `None`, all logical cores are used.
+    pub thread_limit: Option<usize>,
+    /// The version of the pack index to write.
+    pub index_version: gix_pack::index::Version,
+}
+
+/// The outcome of [`Store::repack()`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outcome {
+    /// The outcome of writing the pack and its index, or `None` if there were no objects to pack.
+    pub write: Option<gix_pack::bundle::write::Outcome>,
+    /// The amount of loose objects that were written into the new pack.
+    pub num_objects_packed: usize,
+    /// The amount of loose object files that were removed, which is always `0` unless [`Options::prune`] is set.
+    pub num_loose_objects_removed: usize,
+    /// The amount of bytes the removed loose object files occupied on disk.
+    pub bytes_reclaimed: u64,
+}
+
+/// The progress ids used in [`Store::repack()`].
+///
+/// Use this information to selectively extract the progress of interest in case the parent application has custom visualization.
+#[derive(Debug, Copy, Clone)]
+pub enum ProgressId {
+    /// The amount of loose objects that were written into the new pack.
+    PackObjects,
+    /// The amount of loose objects that were removed after the new pack was written.
+    PruneObjects,
+}
+
+impl From<ProgressId> for gix_features::progress::Id {
+    fn from(v: ProgressId) -> Self {
+        match v {
+            ProgressId::PackObjects => *b"RPPO",
+            ProgressId::PruneObjects => *b"RPRO",
+        }
+    }
+}
+
+impl Store {
+    /// Write the loose objects chosen by `selection` into a single new pack along with its index in the `pack` directory
+    /// of this store, and remove the loose objects that were packed if [`Options::prune`] is set.
+    ///
+    /// The new pack is fully written and moved into place before any loose object is removed, so readers
+    /// can find all objects at any time, either loose or in the new pack.
+    /// Loose objects that are removed by others while the pack is written are skipped.
+    pub fn repack(
+        &self,
+        selection: Selection<'_>,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Outcome, Error> {
+        let _span = gix_features::trace::coarse!("gix_odb::loose::Store::repack()");
+        let ids = match selection {
+            Selection::All => self.iter().collect::<Result<Vec<_>, _>>()?,
+            Selection::ReachableFrom { tips, objects } => {
+                self.reachable_loose_objects(tips, objects, should_interrupt)?
+            }
+        };
+        if ids.is_empty() {
+            return Ok(Outcome {
+                write: None,
+                num_objects_packed: 0,
+                num_loose_objects_removed: 0,
+                bytes_reclaimed: 0,
+            });
+        }
+
+        let (write, packed) = self.write_pack(ids, progress, should_interrupt, &options)?;
+        let (num_loose_objects_removed, bytes_reclaimed) = if options.prune {
+            self.prune(&packed, progress, should_interrupt)?
+        } else {
+            (0, 0)
+        };
+        Ok(Outcome {
+            write: Some(write),
+            num_objects_packed: packed.len(),
+            num_loose_objects_removed,
+            bytes_reclaimed,
+        })
+    }
+
+    /// Return all loose objects that are reachable from `tips`, reading all traversed objects from `objects`.
+    fn reachable_loose_objects(
+        &self,
+        tips: &[ObjectId],
+        objects: &dyn gix_object::Find,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let mut seen = HashSet::new();
+        let mut out = Vec::new();
+        let mut queue: Vec<_> = tips.to_vec();
+        let mut buf = Vec::new();
+        while let Some(id) = queue.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            if should_interrupt.load(Ordering::Relaxed) {
+                return Err(Error::Interrupted);
+            }
+            if self.contains(&id) {
+                out.push(id);
+            }
+            let Some(object) = objects
+                .try_find(&id, &mut buf)
+                .map_err(|source| Error::FindReachable { id, source })?
+            else {
+                continue;
+            };
+            let kind = object.kind;
+            match object.decode().map_err(|source| Error::Decode { id, kind, source })? {
+                gix_object::ObjectRef::Commit(commit) => {
+                    queue.push(commit.tree());
+                    queue.extend(commit.parents());
+                }
+                gix_object::ObjectRef::Tree(tree) => queue.extend(
+                    tree.entries
+                        .iter()
+                        .filter(|entry| !entry.mode.is_commit())
+                        .map(|entry| entry.oid.to_owned()),
+                ),
+                gix_object::ObjectRef::Tag(tag) => queue.push(tag.target()),
+                gix_object::ObjectRef::Blob(_) => {}
+            }
+        }
+        Ok(out)
+    }
+
+    /// Write all loose objects with `ids` into a new pack, and return the outcome along with the ids of the objects
+    /// that were actually packed.
+    fn write_pack(
+        &self,
+        ids: Vec<ObjectId>,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        options: &Options,
+    ) -> Result<(gix_pack::bundle::write::Outcome, Vec<ObjectId>), Error> {
+        let pack_directory = self.path.join("pack");
+        std::fs::create_dir_all(&pack_directory)?;
+        let mut pack_data = gix_tempfile::new(&pack_directory, ContainingDirectory::Exists, AutoRemove::Tempfile)?;
+
+        let mut packed = Vec::with_capacity(ids.len());
+        {
+            let start = Instant::now();
+            let mut pack_progress =
+                progress.add_child_with_id("packing loose objects".into(), ProgressId::PackObjects.into());
+            pack_progress.init(Some(ids.len()), gix_features::progress::count("objects"));
+
+            let mut find_error = None;
+            let mut buf = Vec::new();
+            let entries = ids
+                .into_iter()
+                .map_while(|id| match self.try_find(&id, &mut buf) {
+                    Ok(Some(object)) => {
+                        packed.push(id);
+                        Some(Some(gix_pack::data::input::Entry::from_data_obj(&object, 0)))
+                    }
+                    // The object was removed in the meantime, which is fine as long as it's still present elsewhere.
+                    Ok(None) => Some(None),
+                    Err(err) => {
+                        find_error = Some(err);
+                        None
+                    }
+                })
+                .flatten();
+            for entry in gix_pack::data::input::EntriesToBytesIter::new(
+                entries,
+                &mut pack_data,
+                gix_pack::data::Version::V2,
+                self.object_hash,
+            ) {
+                entry?;
+                pack_progress.inc();
+                if should_interrupt.load(Ordering::Relaxed) {
+                    return Err(Error::Interrupted);
+                }
+            }
+            if let Some(err) = find_error {
+                return Err(err.into());
+            }
+            pack_progress.show_throughput(start);
+        }
+        pack_data.rewind()?;
+
+        let outcome = gix_pack::Bundle::write_to_directory(
+            &mut io::BufReader::with_capacity(64 * 1024, pack_data),
+            Some(&pack_directory),
+            progress,
+            should_interrupt,
+            None::<gix_object::find::Never>,
+            gix_pack::bundle::write::Options {
+                thread_limit: options.thread_limit,
+                iteration_mode: gix_pack::data::input::Mode::Verify,
+                index_version: options.index_version,
+                object_hash: self.object_hash,
+            },
+        )?;
+        // The pack only contains objects we already have, so there is no need to protect it until refs point to it.
+        if let Some(keep_path) = &outcome.keep_path {
+            match std::fs::remove_file(keep_path) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            }
+        }
+        Ok((outcome, packed))
+    }
+
+    /// Remove the loose objects with `ids`, along with their fan-out directories if these end up empty, and return
+    /// the amount of removed objects and the amount of bytes they occupied.
+    fn prune(
+        &self,
+        ids: &[ObjectId],
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<(usize, u64), Error> {
+        let start = Instant::now();
+        let mut prune_progress =
+            progress.add_child_with_id("pruning loose objects".into(), ProgressId::PruneObjects.into());
+        prune_progress.init(Some(ids.len()), gix_features::progress::count("objects"));
+
+        let mut num_removed = 0;
+        let mut bytes_reclaimed = 0;
+        let mut directories = BTreeSet::<PathBuf>::new();
+        for id in ids {
+            if should_interrupt.load(Ordering::Relaxed) {
+                return Err(Error::Interrupted);
+            }
+            let path = self.object_path(id);
+            let size = match std::fs::metadata(&path) {
+                Ok(metadata) => metadata.len(),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+            match std::fs::remove_file(&path) {
+                Ok(()) => {
+                    num_removed += 1;
+                    bytes_reclaimed += size;
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            }
+            if let Some(directory) = path.parent() {
+                directories.insert(directory.to_owned());
+            }
+            prune_progress.inc();
+        }
+        for directory in directories {
+            // Fails if the directory still contains objects, which is expected.
+            std::fs::remove_dir(directory).ok();
+        }
+        prune_progress.show_throughput(start);
+        Ok((num_removed, bytes_reclaimed))
+    }
+}
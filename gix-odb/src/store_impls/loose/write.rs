@@ -2,7 +2,7 @@ use std::{fs, io, io::Write, path::PathBuf};
 
 use gix_features::{hash, zlib::stream::deflate};
 use gix_object::WriteTo;
-use tempfile::NamedTempFile;
+use gix_tempfile::{handle::Writable, AutoRemove, ContainingDirectory};
 
 use super::Store;
 use crate::store_impls::loose;
@@ -21,9 +21,11 @@ pub enum Error {
     IoRaw(#[from] io::Error),
     #[error("Could not turn temporary file into persisted file at '{target}'")]
     Persist {
-        source: tempfile::PersistError,
+        source: gix_tempfile::handle::persist::Error<Writable>,
         target: PathBuf,
     },
+    #[error("The object was declared to be {expected} bytes in size, but {actual} bytes were written")]
+    SizeMismatch { expected: u64, actual: u64 },
 }
 
 impl crate::traits::Write for Store {
@@ -64,36 +66,82 @@ impl crate::traits::Write for Store {
         Ok(self.finalize_object(to)?)
     }
 
-    /// Write the given stream in `from` to disk with at least one syscall.
+    /// Write the given stream in `from` to disk with at least one syscall, reading exactly `size` bytes from it.
     ///
     /// This will cost at least 4 IO operations.
     fn write_stream(
         &self,
         kind: gix_object::Kind,
         size: u64,
-        mut from: &mut dyn io::Read,
+        from: &mut dyn io::Read,
     ) -> Result<gix_hash::ObjectId, crate::write::Error> {
-        let mut to = self.dest().map_err(Box::new)?;
-        to.write_all(&gix_object::encode::loose_header(kind, size))
-            .map_err(|err| Error::Io {
-                source: err,
-                message: "write header to tempfile in",
-                path: self.path.to_owned(),
-            })?;
-
-        io::copy(&mut from, &mut to)
+        let mut to = self.object_writer(kind, size).map_err(Box::new)?;
+        io::copy(&mut io::Read::take(from, size), &mut to)
             .map_err(|err| Error::Io {
                 source: err,
                 message: "stream all data into tempfile in",
                 path: self.path.to_owned(),
             })
             .map_err(Box::new)?;
-        to.flush().map_err(Box::new)?;
-        Ok(self.finalize_object(to)?)
+        Ok(to.finish()?)
+    }
+}
+
+/// A writer for a single loose object whose kind and size are known in advance, as created by [`Store::object_writer()`].
+///
+/// All data written to it is hashed and compressed on the fly into a temporary file, which is moved into place once
+/// [`finish()`](Self::finish()) was called, when the id of the object is known.
+/// If it is dropped beforehand or the process is interrupted, the temporary file is removed to assure no partial
+/// object is left behind.
+pub struct ObjectWriter<'a> {
+    store: &'a Store,
+    to: hash::Write<CompressedTempfile>,
+    size: u64,
+    written: u64,
+}
+
+impl io::Write for ObjectWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written + buf.len() as u64 > self.size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Cannot write more than the {} bytes that the object was declared to have",
+                    self.size
+                ),
+            ));
+        }
+        let written = self.to.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.to.flush()
+    }
+}
+
+impl ObjectWriter<'_> {
+    /// Finish writing the object and move it into place, returning its id.
+    ///
+    /// Fails if fewer bytes than the declared size were written.
+    pub fn finish(mut self) -> Result<gix_hash::ObjectId, Error> {
+        if self.written != self.size {
+            return Err(Error::SizeMismatch {
+                expected: self.size,
+                actual: self.written,
+            });
+        }
+        self.to.flush().map_err(|err| Error::Io {
+            source: err,
+            message: "flush tempfile in",
+            path: self.store.path.to_owned(),
+        })?;
+        self.store.finalize_object(self.to)
     }
 }
 
-type CompressedTempfile = deflate::Write<NamedTempFile>;
+type CompressedTempfile = deflate::Write<gix_tempfile::Handle<Writable>>;
 
 /// Access
 impl Store {
@@ -105,31 +153,60 @@ impl Store {
     }
 }
 
+/// Streaming
+impl Store {
+    /// Return a writer for an object of the given `kind` and `size` in bytes, which allows writing objects of any size
+    /// without holding them in memory.
+    ///
+    /// Exactly `size` bytes have to be written to it before calling [`finish()`](ObjectWriter::finish()).
+    pub fn object_writer(&self, kind: gix_object::Kind, size: u64) -> Result<ObjectWriter<'_>, Error> {
+        let mut to = self.dest()?;
+        to.write_all(&gix_object::encode::loose_header(kind, size))
+            .map_err(|err| Error::Io {
+                source: err,
+                message: "write header to tempfile in",
+                path: self.path.to_owned(),
+            })?;
+        Ok(ObjectWriter {
+            store: self,
+            to,
+            size,
+            written: 0,
+        })
+    }
+}
+
 impl Store {
     fn dest(&self) -> Result<hash::Write<CompressedTempfile>, Error> {
         #[cfg_attr(not(unix), allow(unused_mut))]
-        let mut builder = tempfile::Builder::new();
+        let mut file =
+            gix_tempfile::new(&self.path, ContainingDirectory::Exists, AutoRemove::Tempfile).map_err(|err| {
+                Error::Io {
+                    source: err,
+                    message: "create named temp file in",
+                    path: self.path.to_owned(),
+                }
+            })?;
         #[cfg(unix)]
         {
             use std::os::unix::fs::PermissionsExt;
             let perms = std::fs::Permissions::from_mode(0o444);
-            builder.permissions(perms);
+            file.with_mut(|f| f.as_file().set_permissions(perms))
+                .and_then(|res| res)
+                .map_err(|err| Error::Io {
+                    source: err,
+                    message: "set permissions of temp file in",
+                    path: self.path.to_owned(),
+                })?;
         }
-        Ok(hash::Write::new(
-            deflate::Write::new(builder.tempfile_in(&self.path).map_err(|err| Error::Io {
-                source: err,
-                message: "create named temp file in",
-                path: self.path.to_owned(),
-            })?),
-            self.object_hash,
-        ))
+        Ok(hash::Write::new(deflate::Write::new(file), self.object_hash))
     }
 
     fn finalize_object(
         &self,
         hash::Write { hash, inner: file }: hash::Write<CompressedTempfile>,
     ) -> Result<gix_hash::ObjectId, Error> {
-        let id = gix_hash::ObjectId::from(hash.digest());
+        let id = hash.digest();
         let object_path = loose::hash_path(&id, self.path.clone());
         let object_dir = object_path
             .parent()
@@ -0,0 +1,16 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q --object-format=sha256
+
+git checkout -q -b main
+echo packed > file
+git add file
+git commit -q -m "packed commit"
+git repack -adq
+git multi-pack-index write
+
+git rev-parse HEAD > packed-commit-id
+git rev-parse HEAD:file > packed-blob-id
+echo loose | git hash-object -w --stdin > loose-blob-id
+echo "written by gix" | git hash-object --stdin > unwritten-blob-id
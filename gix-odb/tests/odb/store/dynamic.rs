@@ -221,6 +221,58 @@ fn write() -> crate::Result {
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "sha256")]
+fn sha256_objects_round_trip() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_writable_standalone("make_sha256_repo.sh")?;
+    let read_id = |name: &str| -> crate::Result<ObjectId> {
+        let hex = std::fs::read_to_string(dir.path().join(name))?;
+        Ok(ObjectId::from_hex_of_kind(
+            hex.trim().as_bytes(),
+            gix_hash::Kind::Sha256,
+        )?)
+    };
+    let mut buf = Vec::new();
+    for use_multi_pack_index in [false, true] {
+        let handle = gix_odb::at_opts(
+            dir.path().join(".git/objects"),
+            Vec::new(),
+            store::init::Options {
+                object_hash: gix_hash::Kind::Sha256,
+                use_multi_pack_index,
+                ..Default::default()
+            },
+        )?;
+
+        let tree = handle.find_commit(&read_id("packed-commit-id")?, &mut buf)?.tree();
+        assert_eq!(tree.kind(), gix_hash::Kind::Sha256);
+        assert_eq!(
+            handle.find_blob(&read_id("packed-blob-id")?, &mut buf)?.data,
+            b"packed\n"
+        );
+        assert_eq!(handle.find_blob(&read_id("loose-blob-id")?, &mut buf)?.data, b"loose\n");
+        assert_eq!(handle.packed_object_count()?, 3);
+
+        let written_id = handle.write_buf(gix_object::Kind::Blob, b"written by gix\n")?;
+        assert_eq!(written_id, read_id("unwritten-blob-id")?, "we hash like git does");
+        assert_eq!(handle.find_blob(&written_id, &mut buf)?.data, b"written by gix\n");
+        assert_eq!(
+            handle.iter()?.count(),
+            5,
+            "a commit, tree and blob in the pack, and two loose blobs"
+        );
+    }
+
+    let out = Command::new("git")
+        .args(["cat-file", "blob"])
+        .arg(read_id("unwritten-blob-id")?.to_string())
+        .current_dir(dir.path())
+        .output()?;
+    assert!(out.status.success(), "git can read the object we wrote");
+    assert_eq!(out.stdout, b"written by gix\n");
+    Ok(())
+}
+
 #[test]
 fn alternate_dbs_query() -> crate::Result {
     let dir = gix_testtools::scripted_fixture_read_only_standalone("make_alternates_odb.sh")?;
@@ -321,6 +373,168 @@ fn object_replacement() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn stream() -> crate::Result {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let handle = db();
+    let mut buf = Vec::new();
+    for (hex, desc) in [
+        ("a706d7cd20fc8ce71489f34b50cf01011c104193", "loose blob"),
+        ("15926d8d6d17d1cbdf7f03c457e8ff983270f363", "packed base object"),
+        ("8481dbefa2fb9398a673fe1f48dc480c1f558890", "packed delta object"),
+    ] {
+        let id = hex_to_id(hex);
+        let expected = handle.find(&id, &mut buf)?;
+        let mut stream = handle.try_find_stream(&id)?.expect("present");
+        assert_eq!(stream.kind(), expected.kind, "{desc}");
+        assert_eq!(stream.size(), expected.data.len() as u64, "{desc}");
+
+        let mut actual = Vec::new();
+        stream.read_to_end(&mut actual)?;
+        assert_eq!(actual, expected.data, "{desc}: streamed data matches");
+
+        let mid = expected.data.len() / 2;
+        assert_eq!(stream.seek(SeekFrom::Start(mid as u64))?, mid as u64);
+        let mut buf = [0; 10];
+        stream.read_exact(&mut buf)?;
+        assert_eq!(buf, expected.data[mid..][..10], "{desc}: seeking backwards works");
+
+        stream.seek(SeekFrom::End(-5))?;
+        let mut tail = Vec::new();
+        stream.read_to_end(&mut tail)?;
+        assert_eq!(
+            tail,
+            expected.data[expected.data.len() - 5..],
+            "{desc}: seeking forward works"
+        );
+        assert_eq!(stream.stream_position()?, expected.data.len() as u64);
+        assert!(stream
+            .seek(SeekFrom::Current(-(expected.data.len() as i64) - 1))
+            .is_err());
+    }
+
+    assert!(handle
+        .try_find_stream(&hex_to_id("cccccccccccccccccccccccccccccccccccccccc"))?
+        .is_none());
+    Ok(())
+}
+
+#[test]
+fn pack_cache_avoids_resolving_deltas_repeatedly() -> crate::Result {
+    let mut handle = db();
+    assert_eq!(
+        handle.pack_cache_statistics(),
+        None,
+        "there is no pack cache by default"
+    );
+    let ids = handle.iter()?.collect::<Result<Vec<_>, _>>()?;
+    fn find_all(handle: &impl gix_object::Find, ids: &[ObjectId]) -> crate::Result {
+        let mut buf = Vec::new();
+        for id in ids {
+            handle.find(id, &mut buf)?;
+        }
+        Ok(())
+    }
+
+    let cache = gix_pack::cache::Shared::new(gix_pack::cache::lru::MemoryCappedHashmap::new(64 * 1024 * 1024));
+    handle.set_pack_cache(move || Box::new(cache.clone()));
+    find_all(&handle, &ids)?;
+    let first = handle
+        .pack_cache_statistics()
+        .expect("memory capped hashmaps keep statistics");
+    assert!(first.misses > 0, "some objects are deltas and had to be resolved");
+    assert!(
+        first.hits > 0,
+        "some deltas are bases of other deltas, and could be reused"
+    );
+
+    let other = handle.clone().into_arc()?;
+    let ids = &ids;
+    std::thread::scope(|s| {
+        s.spawn(move || find_all(&other, ids).map_err(|err| err.to_string()))
+            .join()
+    })
+    .expect("no panic")?;
+    let second = handle.pack_cache_statistics().expect("present");
+    assert_eq!(
+        second.misses, first.misses,
+        "the cache is shared among clones and large enough, so no delta was resolved again"
+    );
+    assert!(second.hits > first.hits, "deltas were retrieved from the cache instead");
+    Ok(())
+}
+
+#[test]
+fn limits_prevent_decoding_objects_exceeding_them() -> crate::Result {
+    use gix_object::Find;
+    use gix_odb::pack::data::decode::LimitExceeded;
+
+    let (mut handle, _dir) = db_with_all_object_sources()?;
+    let ids = handle.iter()?.collect::<Result<Vec<_>, _>>()?;
+    let mut buf = Vec::new();
+    let mut find = |handle: &gix_odb::Handle, id: &ObjectId| -> Option<(LimitExceeded, bool)> {
+        let err = handle.try_find(id, &mut buf).err()?;
+        let err = err
+            .downcast_ref::<gix_odb::store::find::Error>()
+            .expect("an error of the dynamic store");
+        let limit = *err.limit_exceeded().expect("only limits cause errors");
+        Some((limit, matches!(err, gix_odb::store::find::Error::Loose(_))))
+    };
+    let unlimited = handle.clone();
+    let mut data = Vec::new();
+
+    handle.limits.max_object_size = Some(100);
+    let (mut num_too_large, mut num_loose_too_large) = (0, 0);
+    for id in &ids {
+        let size = handle.try_header(id)?.expect("present").size();
+        match find(&handle, id) {
+            Some((LimitExceeded::ObjectSize { max: 100, size: actual }, is_loose)) => {
+                assert!(actual > 100);
+                num_too_large += 1;
+                num_loose_too_large += usize::from(is_loose);
+            }
+            Some((limit, _)) => unreachable!("unexpected limit {limit:?}"),
+            None => assert!(size <= 100, "{id} is {size} bytes large and must not be decoded"),
+        }
+    }
+    assert!(num_too_large > 0, "some objects are too large");
+    assert!(num_loose_too_large > 0, "loose objects are limited as well");
+
+    handle.limits = Default::default();
+    handle.limits.max_delta_chain_length = Some(0);
+    let num_deltas = ids
+        .iter()
+        .filter(|id| match find(&handle, id) {
+            Some((LimitExceeded::DeltaChainLength { max: 0 }, is_loose)) => {
+                assert!(!is_loose, "loose objects are never deltified");
+                true
+            }
+            Some((limit, _)) => unreachable!("unexpected limit {limit:?}"),
+            None => false,
+        })
+        .count();
+    assert!(num_deltas > 0, "some packed objects are deltas and can't be decoded");
+
+    handle.limits = Default::default();
+    handle.limits.max_tree_entries = Some(1);
+    let mut num_large_trees = 0;
+    for id in &ids {
+        let object = unlimited.find(id, &mut data)?;
+        let num_entries = object.try_into_tree_iter().map(Iterator::count);
+        match find(&handle, id) {
+            Some((LimitExceeded::TreeEntries { max: 1 }, _)) => {
+                assert!(num_entries.expect("a tree") > 1);
+                num_large_trees += 1;
+            }
+            Some((limit, _)) => unreachable!("unexpected limit {limit:?}"),
+            None => assert!(num_entries.unwrap_or_default() <= 1),
+        }
+    }
+    assert!(num_large_trees > 0, "some trees have more than one entry");
+    Ok(())
+}
+
 #[test]
 fn contains() {
     let handle = db();
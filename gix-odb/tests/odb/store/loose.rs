@@ -45,6 +45,8 @@ fn verify_integrity() {
 }
 
 mod write {
+    use std::io::Write as _;
+
     use gix_odb::{loose, Write};
 
     use crate::store::loose::{locate_oid, object_ids};
@@ -101,6 +103,59 @@ mod write {
         Ok(())
     }
 
+    #[test]
+    fn object_writer_streams_data_of_declared_size() -> crate::Result {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1);
+        let chunk = [b'x'; 64 * 1024];
+        let num_chunks = 20;
+        let mut writer = db.object_writer(gix_object::Kind::Blob, (chunk.len() * num_chunks) as u64)?;
+        for _ in 0..num_chunks {
+            writer.write_all(&chunk)?;
+        }
+        let id = writer.finish()?;
+
+        let expected = chunk.repeat(num_chunks);
+        assert_eq!(
+            id,
+            gix_object::compute_hash(gix_hash::Kind::Sha1, gix_object::Kind::Blob, &expected)
+        );
+        let mut buf = Vec::new();
+        let obj = db.try_find(&id, &mut buf)?.expect("object was written");
+        assert_eq!(obj.kind, gix_object::Kind::Blob);
+        assert!(obj.data == expected, "the data can be read back");
+        Ok(())
+    }
+
+    #[test]
+    fn object_writer_rejects_data_not_matching_the_declared_size() -> crate::Result {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1);
+
+        let mut writer = db.object_writer(gix_object::Kind::Blob, 3)?;
+        writer.write_all(b"ab")?;
+        assert!(matches!(
+            writer.finish(),
+            Err(loose::write::Error::SizeMismatch { expected: 3, actual: 2 })
+        ));
+
+        let mut writer = db.object_writer(gix_object::Kind::Blob, 3)?;
+        let err = writer.write_all(b"abcd").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        drop(writer);
+
+        assert!(
+            db.write_stream(gix_object::Kind::Blob, 3, &mut b"ab".as_slice()).is_err(),
+            "streams that end early are detected as well"
+        );
+        assert_eq!(
+            std::fs::read_dir(dir.path())?.count(),
+            0,
+            "no object or temporary file is left behind"
+        );
+        Ok(())
+    }
+
     #[test]
     fn collisions_do_not_cause_failure() -> crate::Result {
         let dir = gix_testtools::tempfile::tempdir()?;
@@ -131,6 +186,131 @@ mod write {
     }
 }
 
+mod repack {
+    use std::sync::atomic::AtomicBool;
+
+    use gix_features::progress;
+    use gix_odb::loose::{self, repack};
+    use gix_testtools::fixture_path_standalone;
+
+    use crate::{hex_to_id, store::loose::object_ids};
+
+    fn store_with_loose_objects() -> crate::Result<(gix_testtools::tempfile::TempDir, loose::Store)> {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        gix_testtools::copy_recursively_into_existing_dir(fixture_path_standalone("objects"), tmp.path())?;
+        std::fs::remove_dir_all(tmp.path().join("pack"))?;
+        let store = loose::Store::at(tmp.path(), gix_hash::Kind::Sha1);
+        Ok((tmp, store))
+    }
+
+    fn packed_ids(outcome: &repack::Outcome) -> crate::Result<Vec<gix_hash::ObjectId>> {
+        let index_path = outcome
+            .write
+            .as_ref()
+            .and_then(|write| write.index_path.as_ref())
+            .expect("a new pack was written");
+        let index = gix_pack::index::File::at(index_path, gix_hash::Kind::Sha1)?;
+        Ok(index.iter().map(|entry| entry.oid).collect())
+    }
+
+    #[test]
+    fn all_loose_objects_are_packed_and_kept_without_prune() -> crate::Result {
+        let (_tmp, store) = store_with_loose_objects()?;
+        let outcome = store.repack(
+            repack::Selection::All,
+            &mut progress::Discard,
+            &AtomicBool::new(false),
+            Default::default(),
+        )?;
+        assert_eq!(outcome.num_objects_packed, 7);
+        assert_eq!(outcome.num_loose_objects_removed, 0, "pruning is opt-in");
+        assert_eq!(outcome.bytes_reclaimed, 0);
+        assert_eq!(
+            packed_ids(&outcome)?,
+            object_ids(),
+            "the index is sorted just like our ids"
+        );
+        let write = outcome.write.as_ref().expect("pack written");
+        assert!(write.keep_path.as_ref().map_or(true, |path| !path.exists()));
+        assert_eq!(store.iter().count(), 7, "all loose objects are still present");
+        Ok(())
+    }
+
+    #[test]
+    fn prune_removes_packed_loose_objects_and_their_directories() -> crate::Result {
+        let (tmp, store) = store_with_loose_objects()?;
+        let loose_bytes: u64 = object_ids()
+            .iter()
+            .map(|id| store.object_path(id).metadata().map(|m| m.len()))
+            .sum::<std::io::Result<_>>()?;
+        let outcome = store.repack(
+            repack::Selection::All,
+            &mut progress::Discard,
+            &AtomicBool::new(false),
+            repack::Options {
+                prune: true,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(outcome.num_objects_packed, 7);
+        assert_eq!(outcome.num_loose_objects_removed, 7);
+        assert_eq!(outcome.bytes_reclaimed, loose_bytes);
+        assert_eq!(store.iter().count(), 0, "all loose objects are gone");
+        assert_eq!(
+            std::fs::read_dir(tmp.path())?.count(),
+            1,
+            "only the pack directory remains as empty fan-out directories are removed"
+        );
+
+        let odb = gix_odb::at(tmp.path())?;
+        let mut buf = Vec::new();
+        for id in object_ids() {
+            assert!(
+                gix_object::Find::try_find(&odb, &id, &mut buf)?.is_some(),
+                "{id} can be read from the new pack"
+            );
+        }
+
+        let outcome = store.repack(
+            repack::Selection::All,
+            &mut progress::Discard,
+            &AtomicBool::new(false),
+            Default::default(),
+        )?;
+        assert_eq!(outcome.write, None, "without loose objects, there is nothing to do");
+        assert_eq!(outcome.num_objects_packed, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn selection_can_be_limited_to_objects_reachable_from_tips() -> crate::Result {
+        let (tmp, store) = store_with_loose_objects()?;
+        let odb = gix_odb::at(tmp.path())?;
+        let commit = hex_to_id("ffa700b4aca13b80cb6b98a078e7c96804f8e0ec");
+        let outcome = store.repack(
+            repack::Selection::ReachableFrom {
+                tips: &[commit],
+                objects: &odb,
+            },
+            &mut progress::Discard,
+            &AtomicBool::new(false),
+            repack::Options {
+                prune: true,
+                ..Default::default()
+            },
+        )?;
+        let packed = packed_ids(&outcome)?;
+        assert!(packed.contains(&commit));
+        assert!(packed.len() < object_ids().len(), "unreachable objects are left alone");
+        assert_eq!(outcome.num_loose_objects_removed, packed.len());
+        let mut remaining: Vec<_> = store.iter().collect::<Result<_, _>>()?;
+        remaining.extend(packed);
+        remaining.sort();
+        assert_eq!(remaining, object_ids(), "every loose object is either packed or kept");
+        Ok(())
+    }
+}
+
 mod contains {
     use crate::store::loose::ldb;
 
@@ -350,6 +530,38 @@ cjHJZXWmV4CcRfmLsXzU8s2cR9A0DBvOxhPD1TlKC2JhBFXigjuL9U4Rbq9tdegB
         Ok(())
     }
 
+    #[test]
+    fn stream() -> crate::Result {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let mut buf = Vec::new();
+        let id = hex_to_id("a706d7cd20fc8ce71489f34b50cf01011c104193");
+        let expected = ldb().try_find(&id, &mut buf)?.expect("present");
+        let mut stream = ldb().try_find_stream(&id)?.expect("present");
+        assert_eq!(stream.kind(), Kind::Blob);
+        assert_eq!(stream.size(), expected.data.len() as u64);
+
+        stream.seek(SeekFrom::Start(1000))?;
+        let mut actual = Vec::new();
+        stream.read_to_end(&mut actual)?;
+        assert_eq!(actual, expected.data[1000..], "the header is never part of the data");
+
+        stream.rewind()?;
+        actual.clear();
+        stream.read_to_end(&mut actual)?;
+        assert_eq!(actual, expected.data);
+        assert_eq!(
+            stream.read(&mut [0; 10])?,
+            0,
+            "reads at the end of the object yield nothing"
+        );
+
+        assert!(ldb()
+            .try_find_stream(&hex_to_id("cccccccccccccccccccccccccccccccccccccccc"))?
+            .is_none());
+        Ok(())
+    }
+
     fn try_locate<'a>(hex: &str, buf: &'a mut Vec<u8>) -> Option<gix_object::Data<'a>> {
         ldb().try_find(&hex_to_id(hex), buf).ok().flatten()
     }
@@ -0,0 +1,81 @@
+//! Measure how much pack caches speed up decoding all objects of a pack repeatedly, as happens when diffing trees
+//! of related commits, e.g. with `cargo bench -p gix-pack --bench pack-cache --features pack-cache-lru-dynamic,pack-cache-lru-static`.
+//!
+//! The amount of cache misses of each cache is printed before measuring, as each one means a delta base had to be
+//! decompressed and resolved again.
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use gix_features::zlib;
+use gix_pack::{
+    cache::{self, DecodeEntry},
+    Bundle,
+};
+
+const PACK: &str = "tests/fixtures/objects/pack/pack-11fdfa9e156ab73caae3b6da867192221f2089c2.idx";
+const ROUNDS: usize = 3;
+
+type NewCache = fn(usize) -> Box<dyn DecodeEntry>;
+
+fn bundle() -> Bundle {
+    Bundle::at(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(PACK),
+        gix_hash::Kind::Sha1,
+    )
+    .expect("valid fixture")
+}
+
+/// Decode all objects of `bundle` `ROUNDS` times using `cache`, and return the total amount of decoded bytes.
+fn decode_all(bundle: &Bundle, cache: &mut dyn DecodeEntry) -> usize {
+    let mut buf = Vec::new();
+    let mut inflate = zlib::Inflate::default();
+    let mut bytes = 0;
+    for _ in 0..ROUNDS {
+        for idx in 0..bundle.index.num_objects() {
+            let (data, _location) = bundle
+                .get_object_by_index(idx, &mut buf, &mut inflate, cache)
+                .expect("valid object");
+            bytes += data.data.len();
+        }
+    }
+    bytes
+}
+
+fn pack_cache(c: &mut Criterion) {
+    let bundle = bundle();
+    let memory_limit = 64 * 1024 * 1024;
+    let caches: [(&str, NewCache); 4] = [
+        ("Never", |_| Box::new(cache::Never)),
+        ("StaticLinkedList<64>", |_| {
+            Box::<cache::lru::StaticLinkedList<64>>::default()
+        }),
+        ("MemoryCappedHashmap", |limit| {
+            Box::new(cache::lru::MemoryCappedHashmap::new(limit))
+        }),
+        ("Shared<MemoryCappedHashmap>", |limit| {
+            Box::new(cache::Shared::new(cache::lru::MemoryCappedHashmap::new(limit)))
+        }),
+    ];
+
+    for (name, new_cache) in &caches {
+        let mut cache = new_cache(memory_limit);
+        decode_all(&bundle, &mut cache);
+        match cache.statistics() {
+            Some(stats) => eprintln!("{name}: {stats:?}"),
+            None => eprintln!("{name}: no statistics"),
+        }
+    }
+
+    let mut group = c.benchmark_group("pack-cache");
+    for (name, new_cache) in &caches {
+        group.bench_function(format!("decode all objects {ROUNDS} times with {name}"), |b| {
+            b.iter_batched_ref(
+                || new_cache(memory_limit),
+                |cache| black_box(decode_all(&bundle, cache)),
+                criterion::BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pack_cache);
+criterion_main!(benches);
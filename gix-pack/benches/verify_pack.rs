@@ -0,0 +1,52 @@
+//! Measure the throughput of operations that are dominated by decompression, to compare the `zlib` backends
+//! selectable in `gix-features`, e.g. with `cargo bench -p gix-pack --features gix-features/zlib-ng`.
+use std::sync::atomic::AtomicBool;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use gix_features::{progress, zlib::Backend};
+use gix_pack::{index::verify::integrity, Bundle};
+
+const PACK: &str = "tests/fixtures/objects/pack/pack-11fdfa9e156ab73caae3b6da867192221f2089c2.idx";
+
+fn bundle() -> Bundle {
+    Bundle::at(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(PACK),
+        gix_hash::Kind::Sha1,
+    )
+    .expect("valid fixture")
+}
+
+fn verify_pack(c: &mut Criterion) {
+    let bundle = bundle();
+    let mut group = c.benchmark_group(format!("verify-pack({})", Backend::current().as_str()));
+    group.throughput(Throughput::Bytes(bundle.pack.data_len() as u64));
+    group.bench_function("Bundle::verify_integrity()", |b| {
+        b.iter(|| {
+            black_box(
+                bundle
+                    .verify_integrity(
+                        &mut progress::Discard,
+                        &AtomicBool::new(false),
+                        integrity::Options::default(),
+                    )
+                    .expect("valid pack"),
+            )
+        })
+    });
+    group.bench_function("File::streaming_iter()", |b| {
+        b.iter(|| {
+            black_box(
+                bundle
+                    .pack
+                    .streaming_iter()
+                    .expect("valid pack")
+                    .map(|entry| entry.expect("valid entry").decompressed_size)
+                    .sum::<u64>(),
+            )
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, verify_pack);
+criterion_main!(benches);
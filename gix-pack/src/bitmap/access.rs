@@ -0,0 +1,120 @@
+use gix_hash::ObjectId;
+
+use crate::{
+    bitmap::{File, Version},
+    index,
+};
+
+/// Basic file information
+impl File {
+    /// The version of the bitmap file.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+    /// The path of the opened bitmap file.
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+    /// The kind of hash we assume.
+    pub fn object_hash(&self) -> gix_hash::Kind {
+        self.object_hash
+    }
+    /// The amount of commits that have a bitmap.
+    pub fn num_commits(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Lookup
+impl File {
+    /// Return the ids of all objects that are reachable from `commit`, including the commit itself, or `None` if
+    /// there is no bitmap for it in which case a graph traversal is required instead.
+    ///
+    /// `index` has to be the index this instance was [opened](File::at()) with.
+    /// The ids are returned in no particular order.
+    pub fn reachable_objects(&self, commit: &gix_hash::oid, index: &index::File) -> Option<Vec<ObjectId>> {
+        let bitmap = self.bitmap_for_commit(commit, index)?;
+        Some(self.object_ids(&bitmap, index))
+    }
+
+    /// Return the amount of objects that are reachable from `commit`, including the commit itself, or `None`
+    /// if there is no bitmap for it.
+    ///
+    /// `index` has to be the index this instance was [opened](File::at()) with.
+    pub fn count_reachable_objects(&self, commit: &gix_hash::oid, index: &index::File) -> Option<usize> {
+        let bitmap = self.bitmap_for_commit(commit, index)?;
+        Some(bitmap.iter().map(|word| word.count_ones() as usize).sum())
+    }
+
+    /// Return the ids of all objects of the given `kind` in the pack, in no particular order.
+    ///
+    /// `index` has to be the index this instance was [opened](File::at()) with.
+    pub fn objects_of_kind(&self, kind: gix_object::Kind, index: &index::File) -> Vec<ObjectId> {
+        let bitmap_ofs = self.kind_bitmap_ofs[match kind {
+            gix_object::Kind::Commit => 0,
+            gix_object::Kind::Tree => 1,
+            gix_object::Kind::Blob => 2,
+            gix_object::Kind::Tag => 3,
+        }];
+        self.object_ids(&self.decode(bitmap_ofs), index)
+    }
+}
+
+impl File {
+    fn bitmap_for_commit(&self, commit: &gix_hash::oid, index: &index::File) -> Option<Vec<u64>> {
+        let object_index = index.lookup(commit)?;
+        let pos = self
+            .entries_by_object_index
+            .binary_search_by_key(&object_index, |&idx| self.entries[idx].object_index)
+            .ok()?;
+        Some(self.resolve(self.entries_by_object_index[pos]))
+    }
+
+    /// Decode the bitmap of the entry at `entry_index` and apply all bitmaps it is XORed with.
+    fn resolve(&self, mut entry_index: usize) -> Vec<u64> {
+        let mut bitmap = Vec::new();
+        loop {
+            let entry = &self.entries[entry_index];
+            let other = self.decode(entry.bitmap_ofs);
+            if bitmap.len() < other.len() {
+                bitmap.resize(other.len(), 0);
+            }
+            for (word, other) in bitmap.iter_mut().zip(other) {
+                *word ^= other;
+            }
+            if entry.xor_offset == 0 {
+                break bitmap;
+            }
+            entry_index -= entry.xor_offset as usize;
+        }
+    }
+
+    /// Decode the EWAH bitmap at `bitmap_ofs` into uncompressed words, with the first bit in the least significant bit
+    /// of the first word.
+    fn decode(&self, bitmap_ofs: usize) -> Vec<u64> {
+        let (ewah, _) = gix_bitmap::ewah::decode(&self.data[bitmap_ofs..]).expect("bitmap bounds were validated");
+        let mut words = vec![0u64; (ewah.num_bits() + 63) / 64];
+        ewah.for_each_set_bit(|bit| {
+            if let Some(word) = words.get_mut(bit / 64) {
+                *word |= 1 << (bit % 64);
+            }
+            Some(())
+        });
+        words
+    }
+
+    fn object_ids(&self, bitmap: &[u64], index: &index::File) -> Vec<ObjectId> {
+        let mut out = Vec::new();
+        for (word_index, &word) in bitmap.iter().enumerate() {
+            let mut word = word;
+            while word != 0 {
+                let bit = word_index * 64 + word.trailing_zeros() as usize;
+                word &= word - 1;
+                if let Some(&object_index) = self.pack_order.get(bit) {
+                    out.push(index.oid_at_index(object_index).to_owned());
+                }
+            }
+        }
+        out
+    }
+}
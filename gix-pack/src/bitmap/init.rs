@@ -0,0 +1,170 @@
+use std::path::Path;
+
+use crate::{
+    bitmap::{Entry, File, Version},
+    index,
+};
+
+mod error {
+    /// The error returned by [File::at()][super::File::at()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not open bitmap file at '{path}'")]
+        Io {
+            source: std::io::Error,
+            path: std::path::PathBuf,
+        },
+        #[error("{message}")]
+        Corrupt { message: &'static str },
+        #[error("Unsupported bitmap version: {version}")]
+        UnsupportedVersion { version: u16 },
+        #[error("The bitmap was created for pack {actual}, but the index belongs to pack {expected}")]
+        PackMismatch {
+            expected: gix_hash::ObjectId,
+            actual: gix_hash::ObjectId,
+        },
+    }
+}
+
+pub use error::Error;
+
+/// The commits, trees, blobs and tags in the pack each have their own bitmap.
+const NUM_KIND_BITMAPS: usize = 4;
+/// The amount of bytes for an entry before its bitmap: the commit position in the index, the XOR offset and flags.
+const ENTRY_HEADER_LEN: usize = 4 + 1 + 1;
+
+/// Initialization
+impl File {
+    /// Open the bitmap file at the given `path`, which must belong to the pack that is described by `index`.
+    ///
+    /// The same `index` has to be passed to all methods that need one to map bits to objects.
+    pub fn at(path: impl AsRef<Path>, index: &index::File) -> Result<Self, Error> {
+        Self::at_inner(path.as_ref(), index)
+    }
+
+    fn at_inner(path: &Path, index: &index::File) -> Result<Self, Error> {
+        let data = crate::mmap::read_only(path).map_err(|source| Error::Io {
+            source,
+            path: path.to_owned(),
+        })?;
+
+        let object_hash = index.object_hash();
+        let hash_len = object_hash.len_in_bytes();
+        if data.len() < Self::HEADER_LEN + hash_len /* pack checksum */ + hash_len
+        /* trailer */
+        {
+            return Err(Error::Corrupt {
+                message: "bitmap file is truncated and too short",
+            });
+        }
+
+        let (version, num_entries, mut ofs) = {
+            let (signature, d) = data.split_at(4);
+            if signature != Self::SIGNATURE {
+                return Err(Error::Corrupt {
+                    message: "Invalid signature",
+                });
+            }
+            let (version, d) = d.split_at(2);
+            let version = match read_u16(version) {
+                1 => Version::V1,
+                version => return Err(Error::UnsupportedVersion { version }),
+            };
+            let (_options, d) = d.split_at(2);
+            let (num_entries, d) = d.split_at(4);
+            let actual = gix_hash::ObjectId::from_bytes_or_panic(&d[..hash_len]);
+            let expected = index.pack_checksum();
+            if actual != expected {
+                return Err(Error::PackMismatch { expected, actual });
+            }
+            (version, crate::read_u32(num_entries), Self::HEADER_LEN + hash_len)
+        };
+
+        let mut kind_bitmap_ofs = [0; NUM_KIND_BITMAPS];
+        for bitmap_ofs in &mut kind_bitmap_ofs {
+            *bitmap_ofs = ofs;
+            ofs += ewah_len(&data[ofs..]).ok_or(Error::Corrupt {
+                message: "object type bitmap is truncated",
+            })?;
+        }
+
+        let mut entries = Vec::with_capacity(num_entries as usize);
+        for entry_index in 0..num_entries as usize {
+            let header = data.get(ofs..ofs + ENTRY_HEADER_LEN).ok_or(Error::Corrupt {
+                message: "commit bitmap entry is truncated",
+            })?;
+            let object_index = crate::read_u32(&header[..4]);
+            let xor_offset = header[4];
+            if object_index >= index.num_objects() {
+                return Err(Error::Corrupt {
+                    message: "commit bitmap entry refers to an object that isn't in the pack",
+                });
+            }
+            if xor_offset as usize > entry_index {
+                return Err(Error::Corrupt {
+                    message: "commit bitmap entry refers to a bitmap before the first one for XOR",
+                });
+            }
+            ofs += ENTRY_HEADER_LEN;
+            let bitmap_ofs = ofs;
+            ofs += ewah_len(&data[ofs..]).ok_or(Error::Corrupt {
+                message: "commit bitmap is truncated",
+            })?;
+            entries.push(Entry {
+                object_index,
+                xor_offset,
+                bitmap_ofs,
+            });
+        }
+        if data.len() - ofs < hash_len {
+            return Err(Error::Corrupt {
+                message: "bitmap file is missing its trailing checksum",
+            });
+        }
+
+        let mut entries_by_object_index: Vec<_> = (0..entries.len()).collect();
+        entries_by_object_index.sort_by_key(|&idx| entries[idx].object_index);
+
+        let mut by_pack_offset: Vec<_> = index
+            .iter()
+            .enumerate()
+            .map(|(object_index, entry)| (entry.pack_offset, object_index as index::EntryIndex))
+            .collect();
+        by_pack_offset.sort_unstable_by_key(|(pack_offset, _)| *pack_offset);
+
+        Ok(File {
+            data,
+            path: path.to_owned(),
+            version,
+            object_hash,
+            kind_bitmap_ofs,
+            entries,
+            entries_by_object_index,
+            pack_order: by_pack_offset
+                .into_iter()
+                .map(|(_, object_index)| object_index)
+                .collect(),
+        })
+    }
+}
+
+impl File {
+    const SIGNATURE: &'static [u8] = b"BITM";
+    const HEADER_LEN: usize = 4 /*signature*/ +
+        2 /*version*/ +
+        2 /*options*/ +
+        4 /*num entries*/;
+}
+
+#[inline]
+fn read_u16(b: &[u8]) -> u16 {
+    u16::from_be_bytes(b.try_into().unwrap())
+}
+
+/// Return the length in bytes of the EWAH bitmap at the beginning of `data`, or `None` if it is truncated.
+fn ewah_len(data: &[u8]) -> Option<usize> {
+    let num_words = crate::read_u32(data.get(4..8)?) as usize;
+    let len = 4 /*num bits*/ + 4 /*num words*/ + num_words.checked_mul(8)? + 4 /*position of last run-length word*/;
+    (data.len() >= len).then_some(len)
+}
@@ -0,0 +1,52 @@
+use memmap2::Mmap;
+
+use crate::index::EntryIndex;
+
+/// Known bitmap file versions
+#[derive(Default, PartialEq, Eq, Ord, PartialOrd, Debug, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(missing_docs)]
+pub enum Version {
+    #[default]
+    V1 = 1,
+}
+
+/// A representation of a reachability bitmap file, typically stored next to the pack and index it belongs to in a file
+/// named `pack-<hash>.bitmap`.
+///
+/// It stores bitmaps for select commits, each of which has one bit set for every object in the pack that is
+/// reachable from it, which makes it possible to obtain all objects reachable from such a commit without traversal.
+pub struct File {
+    data: Mmap,
+    path: std::path::PathBuf,
+    version: Version,
+    object_hash: gix_hash::Kind,
+    /// The offsets to the EWAH bitmaps of all commits, trees, blobs and tags in the pack, in that order.
+    kind_bitmap_ofs: [usize; 4],
+    /// All entries in the order they are stored in the file, which is relevant for resolving XOR offsets.
+    entries: Vec<Entry>,
+    /// Indices into `entries`, sorted by [`Entry::object_index`] for lookup.
+    entries_by_object_index: Vec<usize>,
+    /// The index of each object in the pack index, in the order the objects appear in the pack, such that
+    /// the bit at position `n` refers to the object at index `pack_order[n]`.
+    pack_order: Vec<EntryIndex>,
+}
+
+/// An entry that associates a commit with its bitmap, stored in the bitmap file.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    /// The index of the commit in the pack index.
+    object_index: EntryIndex,
+    /// If not `0`, the bitmap of the entry this many entries before us has to be XORed with ours.
+    xor_offset: u8,
+    /// The offset to the EWAH bitmap in the file.
+    bitmap_ofs: usize,
+}
+
+///
+#[allow(clippy::empty_docs)]
+mod access;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod init;
@@ -0,0 +1,321 @@
+use std::{
+    collections::HashMap,
+    io,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use gix_features::{
+    progress,
+    progress::{prodash::DynNestedProgress, Count, Progress},
+    zlib,
+};
+use gix_hash::ObjectId;
+use gix_tempfile::{AutoRemove, ContainingDirectory};
+
+use super::{types::LockWriter, Error, Options, Outcome, ProgressId, WriteOutcome};
+use crate::data::{self, decode::entry::ResolvedBase, entry::Header, input};
+
+/// A base object that is not contained in the thin pack, and which was read from the object database instead.
+struct ExternalBase {
+    kind: gix_object::Kind,
+    data: Vec<u8>,
+}
+
+impl crate::Bundle {
+    /// Turn the thin `pack` into a self-contained pack by adding all base objects its deltas refer to by id but which
+    /// aren't contained in it, reading them from `base_objects`, and write the result along with a generated index into
+    /// `directory` if `Some`, or discard all output if `None`.
+    ///
+    /// Whenever possible, deltas are resolved against objects within `pack` so that `base_objects` is only consulted
+    /// for bases that can't be found there, which includes bases that only become resolvable once other bases were added.
+    /// All bases that were added are placed in front of the pack, with all deltas referring to their bases by offset.
+    ///
+    /// * `progress` provides detailed progress information which can be discarded with [`gix_features::progress::Discard`].
+    /// * `should_interrupt` is checked regularly and when true, the whole operation will stop.
+    ///
+    /// `options.iteration_mode` affects how `pack` is read, while all other `options` configure how the index is written.
+    pub fn complete_thin_pack_to_directory(
+        pack: &data::File,
+        directory: Option<&Path>,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        base_objects: impl gix_object::Find,
+        options: Options,
+    ) -> Result<Outcome, Error> {
+        let _span = gix_features::trace::coarse!("gix_pack::Bundle::complete_thin_pack_to_directory()");
+        let object_hash = options.object_hash;
+        let entries = data::input::BytesToEntriesIter::new_from_header(
+            io::BufReader::with_capacity(4096 * 8, std::fs::File::open(pack.path())?),
+            options.iteration_mode,
+            data::input::EntryDataMode::Ignore,
+            object_hash,
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let mut resolve_progress =
+            progress.add_child_with_id("resolve objects".into(), ProgressId::ResolveObjects.into());
+        resolve_progress.init(Some(entries.len()), progress::count("objects"));
+        let (position_by_id, external_bases) = if entries.iter().any(|e| matches!(e.header, Header::RefDelta { .. })) {
+            resolve_ref_delta_bases(pack, &entries, &base_objects, &mut resolve_progress, should_interrupt)?
+        } else {
+            resolve_progress.set(entries.len());
+            Default::default()
+        };
+        let base_by_id: HashMap<_, _> = external_bases
+            .iter()
+            .enumerate()
+            .map(|(idx, (id, _))| (*id, idx))
+            .collect();
+
+        // Bases that are stored after the deltas referring to them can't be referred to by offset,
+        // so these deltas will be stored as base objects instead.
+        let mut undeltified = HashMap::new();
+        {
+            let mut buf = Vec::new();
+            let mut inflate = zlib::Inflate::default();
+            for (idx, entry) in entries.iter().enumerate() {
+                if let Header::RefDelta { base_id } = entry.header {
+                    if base_by_id.contains_key(&base_id) || position_by_id[&base_id] < idx {
+                        continue;
+                    }
+                    let outcome = decode(
+                        pack,
+                        &entries,
+                        &position_by_id,
+                        &base_by_id,
+                        &external_bases,
+                        idx,
+                        &mut buf,
+                        &mut inflate,
+                    )?;
+                    undeltified.insert(
+                        idx,
+                        input::Entry::from_data_obj(&gix_object::Data::new(outcome.kind, &buf), 0)?,
+                    );
+                }
+            }
+        }
+
+        let num_external_bases = external_bases.len();
+        let mut new_pack_offsets = Vec::with_capacity(num_external_bases + entries.len());
+        let mut next_pack_offset = 12; /* pack header */
+        let completed_entries = (0..num_external_bases + entries.len()).map(move |output_idx| {
+            let (mut entry, base_output_idx) = match output_idx.checked_sub(num_external_bases) {
+                None => {
+                    let (_, ExternalBase { kind, data }) = &external_bases[output_idx];
+                    (
+                        input::Entry::from_data_obj(&gix_object::Data::new(*kind, data), 0)?,
+                        None,
+                    )
+                }
+                Some(idx) => match undeltified.remove(&idx) {
+                    Some(entry) => (entry, None),
+                    None => {
+                        let mut entry = entries[idx].clone();
+                        let data_start = entry.pack_offset + entry.header_size as u64;
+                        entry.compressed = Some(
+                            pack.entry_slice(data_start..data_start + entry.compressed_size)
+                                .expect("entry was read from the same pack")
+                                .to_vec(),
+                        );
+                        entry.trailer = None;
+                        let base_output_idx = match entry.header {
+                            Header::OfsDelta { base_distance } => {
+                                let base_pack_offset = entry.pack_offset - base_distance;
+                                Some(
+                                    num_external_bases
+                                        + entries
+                                            .binary_search_by_key(&base_pack_offset, |e| e.pack_offset)
+                                            .expect("delta base is contained in the pack"),
+                                )
+                            }
+                            Header::RefDelta { base_id } => Some(
+                                base_by_id
+                                    .get(&base_id)
+                                    .copied()
+                                    .unwrap_or_else(|| num_external_bases + position_by_id[&base_id]),
+                            ),
+                            _ => None,
+                        };
+                        (entry, base_output_idx)
+                    }
+                },
+            };
+            entry.pack_offset = next_pack_offset;
+            if let Some(base_output_idx) = base_output_idx {
+                let base_pack_offset: u64 = new_pack_offsets[base_output_idx];
+                entry.header = Header::OfsDelta {
+                    base_distance: entry.pack_offset - base_pack_offset,
+                };
+                entry.header_size = entry.header.size(entry.decompressed_size) as u16;
+            }
+            entry.crc32 = Some(entry.compute_crc32());
+            new_pack_offsets.push(entry.pack_offset);
+            next_pack_offset += entry.bytes_in_pack();
+            Ok::<_, input::Error>(entry)
+        });
+
+        let data_file = Arc::new(parking_lot::Mutex::new(io::BufWriter::with_capacity(
+            64 * 1024,
+            match directory {
+                Some(directory) => gix_tempfile::new(directory, ContainingDirectory::Exists, AutoRemove::Tempfile)?,
+                None => gix_tempfile::new(std::env::temp_dir(), ContainingDirectory::Exists, AutoRemove::Tempfile)?,
+            },
+        )));
+        let pack_version = pack.version();
+        let pack_entries_iter = data::input::EntriesToBytesIter::new(
+            completed_entries,
+            LockWriter {
+                writer: data_file.clone(),
+            },
+            pack_version,
+            object_hash,
+        );
+        let WriteOutcome {
+            outcome,
+            data_path,
+            index_path,
+            keep_path,
+        } = crate::Bundle::inner_write(
+            directory,
+            progress,
+            options,
+            data_file,
+            Box::new(pack_entries_iter),
+            should_interrupt,
+            pack_version,
+        )?;
+
+        Ok(Outcome {
+            index: outcome,
+            object_hash,
+            pack_version,
+            data_path,
+            index_path,
+            keep_path,
+        })
+    }
+}
+
+/// Decode all `entries` of `pack` to learn their ids, which is the only way to find the bases of ref-deltas within the pack.
+/// Bases that can't be found in the pack are read from `base_objects`.
+///
+/// Return the position of all entries by their id, along with the bases that were read from `base_objects` in the order
+/// they were needed.
+#[allow(clippy::type_complexity)]
+fn resolve_ref_delta_bases(
+    pack: &data::File,
+    entries: &[input::Entry],
+    base_objects: &dyn gix_object::Find,
+    progress: &mut dyn DynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<(HashMap<ObjectId, usize>, Vec<(ObjectId, ExternalBase)>), Error> {
+    let mut position_by_id = HashMap::with_capacity(entries.len());
+    let mut external_bases = Vec::new();
+    let mut base_by_id = HashMap::new();
+    let mut buf = Vec::new();
+    let mut inflate = zlib::Inflate::default();
+    let mut unresolved: Vec<usize> = (0..entries.len()).collect();
+    while !unresolved.is_empty() {
+        let mut missing_bases = Vec::new();
+        let mut still_unresolved = Vec::new();
+        for &idx in &unresolved {
+            if should_interrupt.load(Ordering::Relaxed) {
+                return Err(io::Error::new(io::ErrorKind::Other, "Interrupted").into());
+            }
+            match decode(
+                pack,
+                entries,
+                &position_by_id,
+                &base_by_id,
+                &external_bases,
+                idx,
+                &mut buf,
+                &mut inflate,
+            ) {
+                Ok(outcome) => {
+                    position_by_id.insert(gix_object::compute_hash(pack.object_hash(), outcome.kind, &buf), idx);
+                    progress.inc();
+                }
+                Err(Error::PackDecode(data::decode::Error::DeltaBaseUnresolved(base_id))) => {
+                    missing_bases.push(base_id);
+                    still_unresolved.push(idx);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        // Only look for bases outside of the pack once no more progress can be made, as they might be deltas within the pack
+        // that we just haven't decoded yet.
+        if still_unresolved.len() == unresolved.len() {
+            let mut found_any = false;
+            for base_id in &missing_bases {
+                if base_by_id.contains_key(base_id) {
+                    continue;
+                }
+                if let Some(obj) = base_objects
+                    .try_find(base_id, &mut buf)
+                    .map_err(|source| Error::FindBase { id: *base_id, source })?
+                {
+                    base_by_id.insert(*base_id, external_bases.len());
+                    external_bases.push((
+                        *base_id,
+                        ExternalBase {
+                            kind: obj.kind,
+                            data: obj.data.to_owned(),
+                        },
+                    ));
+                    found_any = true;
+                }
+            }
+            if !found_any {
+                return Err(data::input::Error::NotFound {
+                    object_id: missing_bases[0],
+                }
+                .into());
+            }
+        }
+        unresolved = still_unresolved;
+    }
+    Ok((position_by_id, external_bases))
+}
+
+/// Decode the object at `idx` in `entries` into `buf`, resolving ref-deltas with the known positions of objects in the
+/// pack or with the bases that were read from the object database.
+#[allow(clippy::too_many_arguments)]
+fn decode(
+    pack: &data::File,
+    entries: &[input::Entry],
+    position_by_id: &HashMap<ObjectId, usize>,
+    base_by_id: &HashMap<ObjectId, usize>,
+    external_bases: &[(ObjectId, ExternalBase)],
+    idx: usize,
+    buf: &mut Vec<u8>,
+    inflate: &mut zlib::Inflate,
+) -> Result<data::decode::entry::Outcome, Error> {
+    let entry = pack
+        .entry(entries[idx].pack_offset)
+        .map_err(data::decode::Error::from)?;
+    Ok(pack.decode_entry(
+        entry,
+        buf,
+        inflate,
+        &|id, out| match position_by_id.get(id) {
+            Some(&idx) => pack.entry(entries[idx].pack_offset).ok().map(ResolvedBase::InPack),
+            None => base_by_id.get(id).map(|&idx| {
+                let base = &external_bases[idx].1;
+                out.resize(base.data.len(), 0);
+                out.copy_from_slice(&base.data);
+                ResolvedBase::OutOfPack {
+                    kind: base.kind,
+                    end: out.len(),
+                }
+            }),
+        },
+        &mut crate::cache::Never,
+    )?)
+}
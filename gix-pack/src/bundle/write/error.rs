@@ -2,7 +2,8 @@ use std::io;
 
 use gix_tempfile::handle::Writable;
 
-/// The error returned by [`Bundle::write_to_directory()`][crate::Bundle::write_to_directory()]
+/// The error returned by [`Bundle::write_to_directory()`][crate::Bundle::write_to_directory()] and
+/// [`Bundle::complete_thin_pack_to_directory()`][crate::Bundle::complete_thin_pack_to_directory()]
 #[derive(thiserror::Error, Debug)]
 #[allow(missing_docs)]
 pub enum Error {
@@ -14,4 +15,11 @@ pub enum Error {
     Persist(#[from] gix_tempfile::handle::persist::Error<Writable>),
     #[error(transparent)]
     IndexWrite(#[from] crate::index::write::Error),
+    #[error(transparent)]
+    PackDecode(#[from] crate::data::decode::Error),
+    #[error("Could not lookup base object {id} of a thin pack")]
+    FindBase {
+        id: gix_hash::ObjectId,
+        source: gix_object::find::Error,
+    },
 }
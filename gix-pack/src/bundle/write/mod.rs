@@ -11,6 +11,8 @@ use gix_tempfile::{AutoRemove, ContainingDirectory};
 
 use crate::data;
 
+mod complete_thin;
+
 mod error;
 pub use error::Error;
 use gix_features::progress::prodash::DynNestedProgress;
@@ -32,6 +34,8 @@ pub enum ProgressId {
     ///
     /// Underneath will be more progress information related to actually producing the index.
     IndexingSteps(PhantomData<crate::index::write::ProgressId>),
+    /// The amount of objects whose id was determined to find the bases of deltas when completing a thin pack.
+    ResolveObjects,
 }
 
 impl From<ProgressId> for gix_features::progress::Id {
@@ -39,6 +43,7 @@ impl From<ProgressId> for gix_features::progress::Id {
         match v {
             ProgressId::ReadPackBytes => *b"BWRB",
             ProgressId::IndexingSteps(_) => *b"BWCI",
+            ProgressId::ResolveObjects => *b"BWRO",
         }
     }
 }
@@ -109,7 +114,7 @@ impl crate::Bundle {
                         writer: data_file.clone(),
                     },
                     pack_version,
-                    gix_hash::Kind::Sha1, // Thin packs imply a pack being transported, and there we only ever know SHA1 at the moment.
+                    object_hash,
                 );
                 (Box::new(pack_entries_iter), pack_version)
             }
@@ -3,7 +3,7 @@ use super::DecodeEntry;
 #[cfg(feature = "pack-cache-lru-dynamic")]
 mod memory {
     use super::DecodeEntry;
-    use crate::cache::set_vec_to_slice;
+    use crate::cache::{set_vec_to_slice, Statistics};
     use clru::WeightScale;
     use std::num::NonZeroUsize;
 
@@ -27,6 +27,7 @@ mod memory {
         inner: clru::CLruCache<Key, Entry, std::collections::hash_map::RandomState, CustomScale>,
         free_list: Vec<Vec<u8>>,
         debug: gix_features::cache::Debug,
+        stats: Statistics,
     }
 
     impl MemoryCappedHashmap {
@@ -40,6 +41,7 @@ mod memory {
                 ),
                 free_list: Vec::new(),
                 debug: gix_features::cache::Debug::new(format!("MemoryCappedHashmap({memory_cap_in_bytes}B)")),
+                stats: Statistics::default(),
             }
         }
     }
@@ -47,6 +49,7 @@ mod memory {
     impl DecodeEntry for MemoryCappedHashmap {
         fn put(&mut self, pack_id: u32, offset: u64, data: &[u8], kind: gix_object::Kind, compressed_size: usize) {
             self.debug.put();
+            self.stats.puts += 1;
             let Some(data) = set_vec_to_slice(self.free_list.pop().unwrap_or_default(), data) else {
                 return;
             };
@@ -71,12 +74,45 @@ mod memory {
                 Some((e.kind, e.compressed_size))
             });
             if res.is_some() {
-                self.debug.hit()
+                self.debug.hit();
+                self.stats.hits += 1;
             } else {
-                self.debug.miss()
+                self.debug.miss();
+                self.stats.misses += 1;
             }
             res
         }
+
+        fn statistics(&self) -> Option<Statistics> {
+            Some(self.stats)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn statistics_and_eviction() {
+            let mut c = MemoryCappedHashmap::new(10);
+            let mut out = Vec::new();
+            c.put(0, 0, &[0; 6], gix_object::Kind::Blob, 1);
+            c.put(0, 1, &[1; 6], gix_object::Kind::Tree, 2);
+            assert!(
+                c.get(0, 0, &mut out).is_none(),
+                "the first entry was evicted to stay within the limit"
+            );
+            assert_eq!(c.get(0, 1, &mut out), Some((gix_object::Kind::Tree, 2)));
+            assert_eq!(out, [1; 6]);
+            assert_eq!(
+                c.statistics(),
+                Some(Statistics {
+                    hits: 1,
+                    misses: 1,
+                    puts: 2
+                })
+            );
+        }
     }
 }
 
@@ -86,7 +122,7 @@ pub use memory::MemoryCappedHashmap;
 #[cfg(feature = "pack-cache-lru-static")]
 mod _static {
     use super::DecodeEntry;
-    use crate::cache::set_vec_to_slice;
+    use crate::cache::{set_vec_to_slice, Statistics};
     struct Entry {
         pack_id: u32,
         offset: u64,
@@ -106,6 +142,7 @@ mod _static {
         mem_used: usize,
         /// The total amount of memory we should be able to hold with all entries combined.
         mem_limit: usize,
+        stats: Statistics,
     }
 
     impl<const SIZE: usize> StaticLinkedList<SIZE> {
@@ -117,6 +154,7 @@ mod _static {
                 debug: gix_features::cache::Debug::new(format!("StaticLinkedList<{SIZE}>")),
                 mem_used: 0,
                 mem_limit: if mem_limit == 0 { usize::MAX } else { mem_limit },
+                stats: Statistics::default(),
             }
         }
     }
@@ -129,6 +167,7 @@ mod _static {
 
     impl<const SIZE: usize> DecodeEntry for StaticLinkedList<SIZE> {
         fn put(&mut self, pack_id: u32, offset: u64, data: &[u8], kind: gix_object::Kind, compressed_size: usize) {
+            self.stats.puts += 1;
             // We cannot possibly hold this much.
             if data.len() > self.mem_limit {
                 return;
@@ -176,12 +215,18 @@ mod _static {
                 }
             });
             if res.is_some() {
-                self.debug.hit()
+                self.debug.hit();
+                self.stats.hits += 1;
             } else {
-                self.debug.miss()
+                self.debug.miss();
+                self.stats.misses += 1;
             }
             res
         }
+
+        fn statistics(&self) -> Option<Statistics> {
+            Some(self.stats)
+        }
     }
 
     #[cfg(test)]
@@ -232,6 +277,24 @@ mod _static {
                 "objects that won't ever fit within the memory limit are ignored"
             );
         }
+
+        #[test]
+        fn statistics() {
+            let mut c = StaticLinkedList::<10>::new(100);
+            let mut out = Vec::new();
+            assert!(c.get(0, 0, &mut out).is_none());
+            c.put(0, 0, &[1, 2], gix_object::Kind::Blob, 1);
+            assert_eq!(c.get(0, 0, &mut out), Some((gix_object::Kind::Blob, 1)));
+            assert_eq!(out, [1, 2]);
+            assert_eq!(
+                c.statistics(),
+                Some(Statistics {
+                    hits: 1,
+                    misses: 1,
+                    puts: 1
+                })
+            );
+        }
     }
 }
 
@@ -1,7 +1,21 @@
-use std::ops::DerefMut;
+use std::{
+    ops::DerefMut,
+    sync::{Arc, Mutex, PoisonError},
+};
 
 use gix_object::Kind;
 
+/// Counts of how a cache was used so far, useful to learn about its efficiency.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Statistics {
+    /// The amount of times an object was found in the cache.
+    pub hits: usize,
+    /// The amount of times an object wasn't found in the cache and had to be decoded instead.
+    pub misses: usize,
+    /// The amount of times an object was offered to the cache for storage.
+    pub puts: usize,
+}
+
 /// A trait to model putting objects at a given pack `offset` into a cache, and fetching them.
 ///
 /// It is used to speed up [pack traversals][crate::index::File::traverse()].
@@ -13,6 +27,10 @@ pub trait DecodeEntry {
     /// Attempt to fetch the object at `offset` and store its decoded bytes in `out`, as previously stored with [`DecodeEntry::put()`], and return
     /// its (object `kind`, `decompressed_size`)
     fn get(&mut self, pack_id: u32, offset: u64, out: &mut Vec<u8>) -> Option<(gix_object::Kind, usize)>;
+    /// Return the statistics about the usage of this cache so far, if the implementation keeps track of them.
+    fn statistics(&self) -> Option<Statistics> {
+        None
+    }
 }
 
 /// A cache that stores nothing and retrieves nothing, thus it _never_ caches.
@@ -34,6 +52,50 @@ impl<T: DecodeEntry + ?Sized> DecodeEntry for Box<T> {
     fn get(&mut self, pack_id: u32, offset: u64, out: &mut Vec<u8>) -> Option<(Kind, usize)> {
         self.deref_mut().get(pack_id, offset, out)
     }
+
+    fn statistics(&self) -> Option<Statistics> {
+        self.as_ref().statistics()
+    }
+}
+
+/// A cache which is shared among all of its clones, making the cache `C` usable from multiple threads at the cost of
+/// locking it on each access.
+///
+/// To use it with object database handles, have the pack cache constructor return clones of the same instance
+/// so that all handles cloned from each other use the same cache.
+pub struct Shared<C>(Arc<Mutex<C>>);
+
+impl<C> Shared<C> {
+    /// Share `cache` with all clones of the returned instance.
+    pub fn new(cache: C) -> Self {
+        Shared(Arc::new(Mutex::new(cache)))
+    }
+}
+
+impl<C> Clone for Shared<C> {
+    fn clone(&self) -> Self {
+        Shared(self.0.clone())
+    }
+}
+
+impl<C: DecodeEntry> DecodeEntry for Shared<C> {
+    fn put(&mut self, pack_id: u32, offset: u64, data: &[u8], kind: Kind, compressed_size: usize) {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .put(pack_id, offset, data, kind, compressed_size)
+    }
+
+    fn get(&mut self, pack_id: u32, offset: u64, out: &mut Vec<u8>) -> Option<(Kind, usize)> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(pack_id, offset, out)
+    }
+
+    fn statistics(&self) -> Option<Statistics> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).statistics()
+    }
 }
 
 /// A way of storing and retrieving entire objects to and from a cache.
@@ -5,7 +5,11 @@ use smallvec::SmallVec;
 
 use crate::{
     cache, data,
-    data::{delta, file::decode::Error, File},
+    data::{
+        delta,
+        file::decode::{Error, Limits},
+        File,
+    },
 };
 
 /// A return value of a resolve function, which given an [`ObjectId`][gix_hash::ObjectId] determines where an object can be found.
@@ -165,10 +169,25 @@ impl File {
         inflate: &mut zlib::Inflate,
         resolve: &dyn Fn(&gix_hash::oid, &mut Vec<u8>) -> Option<ResolvedBase>,
         delta_cache: &mut dyn cache::DecodeEntry,
+    ) -> Result<Outcome, Error> {
+        self.decode_entry_with_limits(entry, out, inflate, resolve, delta_cache, &Limits::default())
+    }
+
+    /// Like [`decode_entry()`](Self::decode_entry()), but fail with [`Error::LimitExceeded`] instead of decoding
+    /// objects that exceed the given `limits`.
+    pub fn decode_entry_with_limits(
+        &self,
+        entry: data::Entry,
+        out: &mut Vec<u8>,
+        inflate: &mut zlib::Inflate,
+        resolve: &dyn Fn(&gix_hash::oid, &mut Vec<u8>) -> Option<ResolvedBase>,
+        delta_cache: &mut dyn cache::DecodeEntry,
+        limits: &Limits,
     ) -> Result<Outcome, Error> {
         use crate::data::entry::Header::*;
-        match entry.header {
+        let outcome = match entry.header {
             Tree | Blob | Commit | Tag => {
+                limits.check_object_size(entry.decompressed_size)?;
                 let size: usize = entry.decompressed_size.try_into().map_err(|_| Error::OutOfMemory)?;
                 if let Some(additional) = size.checked_sub(out.len()) {
                     out.try_reserve(additional)?;
@@ -181,10 +200,14 @@ impl File {
                             &entry,
                             consumed_input,
                         )
-                    })
+                    })?
             }
-            OfsDelta { .. } | RefDelta { .. } => self.resolve_deltas(entry, resolve, inflate, out, delta_cache),
-        }
+            OfsDelta { .. } | RefDelta { .. } => {
+                self.resolve_deltas(entry, resolve, inflate, out, delta_cache, limits)?
+            }
+        };
+        limits.check_tree_entries(outcome.kind, out)?;
+        Ok(outcome)
     }
 
     /// resolve: technically, this shouldn't ever be required as stored local packs don't refer to objects by id
@@ -197,6 +220,7 @@ impl File {
         inflate: &mut zlib::Inflate,
         out: &mut Vec<u8>,
         cache: &mut dyn cache::DecodeEntry,
+        limits: &Limits,
     ) -> Result<Outcome, Error> {
         // all deltas, from the one that produces the desired object (first) to the oldest at the end of the chain
         let mut chain = SmallVec::<[Delta; 10]>::default();
@@ -219,6 +243,8 @@ impl File {
                 }
                 break;
             }
+            limits.check_delta_chain_length(chain.len() + 1)?;
+            limits.check_object_size(cursor.decompressed_size)?;
             // This is a pessimistic guess, as worst possible compression should not be bigger than the data itself.
             // TODO: is this assumption actually true?
             total_delta_data_size += cursor.decompressed_size;
@@ -242,6 +268,7 @@ impl File {
                 Header::RefDelta { base_id } => match resolve(base_id.as_ref(), out) {
                     Some(ResolvedBase::InPack(entry)) => entry,
                     Some(ResolvedBase::OutOfPack { end, kind }) => {
+                        limits.check_object_size(end as u64)?;
                         base_buffer_size = Some(end);
                         object_kind = Some(kind);
                         break;
@@ -252,6 +279,10 @@ impl File {
             };
         }
 
+        if base_buffer_size.is_none() {
+            limits.check_object_size(cursor.decompressed_size)?;
+        }
+
         // This can happen if the cache held the first entry itself
         // We will just treat it as an object then, even though it's technically incorrect.
         if chain.is_empty() {
@@ -294,11 +325,13 @@ impl File {
 
                 let (base_size, offset) = delta::decode_header_size(instructions);
                 let mut bytes_consumed_by_header = offset;
+                limits.check_object_size(base_size)?;
                 biggest_result_size = biggest_result_size.max(base_size);
                 delta.base_size = base_size.try_into().expect("base size fits into usize");
 
                 let (result_size, offset) = delta::decode_header_size(&instructions[offset..]);
                 bytes_consumed_by_header += offset;
+                limits.check_object_size(result_size)?;
                 biggest_result_size = biggest_result_size.max(result_size);
                 delta.result_size = result_size.try_into().expect("result size fits into usize");
 
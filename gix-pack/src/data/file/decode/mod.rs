@@ -8,7 +8,8 @@ pub mod entry;
 pub mod header;
 
 /// Returned by [`File::decode_header()`][crate::data::File::decode_header()],
-/// [`File::decode_entry()`][crate::data::File::decode_entry()] and .
+/// [`File::decode_entry()`][crate::data::File::decode_entry()],
+/// [`File::decode_entry_with_limits()`][crate::data::File::decode_entry_with_limits()] and
 /// [`File::decompress_entry()`][crate::data::File::decompress_entry()]
 #[derive(thiserror::Error, Debug)]
 #[allow(missing_docs)]
@@ -21,6 +22,69 @@ pub enum Error {
     EntryType(#[from] crate::data::entry::decode::Error),
     #[error("Entry too large to fit in memory")]
     OutOfMemory,
+    #[error(transparent)]
+    LimitExceeded(#[from] LimitExceeded),
+}
+
+/// Limits to prevent objects of untrusted packs from taking an unreasonable amount of memory or time to decode, for use
+/// in [`File::decode_entry_with_limits()`][crate::data::File::decode_entry_with_limits()].
+///
+/// All limits are disabled by default.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Limits {
+    /// The maximum size of a decoded object in bytes.
+    ///
+    /// It's checked against the size declared in the headers of objects before allocating memory for them, which also
+    /// applies to the intermediate objects of delta chains and the instructions of each delta. As objects are never
+    /// decompressed past their declared size, the actual size can't exceed it either.
+    pub max_object_size: Option<u64>,
+    /// The maximum amount of deltas to apply to produce an object, which is checked before decompressing any of them.
+    pub max_delta_chain_length: Option<usize>,
+    /// The maximum amount of entries of a decoded tree.
+    pub max_tree_entries: Option<usize>,
+}
+
+/// Returned as part of [`Error::LimitExceeded`] if an object exceeds one of the configured [`Limits`].
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(missing_docs)]
+pub enum LimitExceeded {
+    #[error("An object of {size} bytes exceeds the limit of {max} bytes")]
+    ObjectSize { size: u64, max: u64 },
+    #[error("A delta chain exceeds the limit of {max} deltas")]
+    DeltaChainLength { max: usize },
+    #[error("A tree exceeds the limit of {max} entries")]
+    TreeEntries { max: usize },
+}
+
+impl Limits {
+    /// Fail if an object of `size` bytes exceeds the maximum object size.
+    pub fn check_object_size(&self, size: u64) -> Result<(), LimitExceeded> {
+        match self.max_object_size {
+            Some(max) if size > max => Err(LimitExceeded::ObjectSize { size, max }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if a delta chain of `len` deltas exceeds the maximum delta chain length.
+    pub fn check_delta_chain_length(&self, len: usize) -> Result<(), LimitExceeded> {
+        match self.max_delta_chain_length {
+            Some(max) if len > max => Err(LimitExceeded::DeltaChainLength { max }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if `data` of an object of `kind` is a tree with more entries than the maximum amount of tree entries.
+    pub fn check_tree_entries(&self, kind: gix_object::Kind, data: &[u8]) -> Result<(), LimitExceeded> {
+        match self.max_tree_entries {
+            Some(max)
+                if kind == gix_object::Kind::Tree && gix_object::TreeRefIter::from_bytes(data).nth(max).is_some() =>
+            {
+                Err(LimitExceeded::TreeEntries { max })
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 impl From<TryReserveError> for Error {
@@ -9,14 +9,31 @@ impl data::File {
     /// The `object_hash` is a way to read (and write) the same file format with different hashes, as the hash kind
     /// isn't stored within the file format itself.
     pub fn at(path: impl AsRef<Path>, object_hash: gix_hash::Kind) -> Result<data::File, data::header::decode::Error> {
-        Self::at_inner(path.as_ref(), object_hash)
+        Self::at_inner(path.as_ref(), object_hash, Default::default())
     }
 
-    fn at_inner(path: &Path, object_hash: gix_hash::Kind) -> Result<data::File, data::header::decode::Error> {
+    /// Like [`at()`](Self::at()), but allows to configure how the pack data is held and accessed with `options`.
+    pub fn at_opts(
+        path: impl AsRef<Path>,
+        object_hash: gix_hash::Kind,
+        options: data::init::Options,
+    ) -> Result<data::File, data::header::decode::Error> {
+        Self::at_inner(path.as_ref(), object_hash, options)
+    }
+
+    fn at_inner(
+        path: &Path,
+        object_hash: gix_hash::Kind,
+        data::init::Options { backing, advice }: data::init::Options,
+    ) -> Result<data::File, data::header::decode::Error> {
         use crate::data::header::N32_SIZE;
         let hash_len = object_hash.len_in_bytes();
 
-        let data = crate::mmap::read_only(path).map_err(|e| data::header::decode::Error::Io {
+        let data = match backing {
+            data::init::Backing::MemoryMap => crate::mmap::read_only(path).map(data::Data::Mmap),
+            data::init::Backing::Buffered => std::fs::read(path).map(data::Data::Buffered),
+        }
+        .map_err(|e| data::header::decode::Error::Io {
             source: e,
             path: path.to_owned(),
         })?;
@@ -28,14 +45,20 @@ impl data::File {
         }
         let (kind, num_objects) =
             data::header::decode(&data[..12].try_into().expect("enough data after previous check"))?;
-        Ok(data::File {
+        let pack = data::File {
             data,
+            advice,
             path: path.to_owned(),
             id: gix_features::hash::crc32(path.as_os_str().to_string_lossy().as_bytes()),
             version: kind,
             num_objects,
             hash_len,
             object_hash,
-        })
+        };
+        if advice != data::init::Advice::Normal {
+            // Advice is merely a hint, failing to apply it is no reason to fail opening the pack.
+            pack.advise(advice).ok();
+        }
+        Ok(pack)
     }
 }
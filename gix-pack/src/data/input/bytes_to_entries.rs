@@ -1,6 +1,6 @@
 use std::{fs, io};
 
-use gix_features::{hash::Sha1, zlib::Decompress};
+use gix_features::{hash::Hasher, zlib::Decompress};
 use gix_hash::ObjectId;
 
 use crate::data::input;
@@ -15,7 +15,7 @@ pub struct BytesToEntriesIter<BR> {
     had_error: bool,
     version: crate::data::Version,
     objects_left: u32,
-    hash: Option<Sha1>,
+    hash: Option<Hasher>,
     mode: input::Mode,
     compressed: input::EntryDataMode,
     compressed_buf: Option<Vec<u8>>,
@@ -177,7 +177,7 @@ where
             }
 
             if let Some(hash) = self.hash.take() {
-                let actual_id = gix_hash::ObjectId::from(hash.digest());
+                let actual_id = hash.digest();
                 if self.mode == input::Mode::Restore {
                     id = actual_id;
                 }
@@ -191,7 +191,7 @@ where
             Some(id)
         } else if self.mode == input::Mode::Restore {
             let hash = self.hash.clone().expect("in restore mode a hash is set");
-            Some(gix_hash::ObjectId::from(hash.digest()))
+            Some(hash.digest())
         } else {
             None
         })
@@ -273,7 +273,18 @@ where
 impl crate::data::File {
     /// Returns an iterator over [`Entries`][crate::data::input::Entry], without making use of the memory mapping.
     pub fn streaming_iter(&self) -> Result<BytesToEntriesIter<impl io::BufRead>, input::Error> {
-        let reader = io::BufReader::with_capacity(4096 * 8, fs::File::open(&self.path)?);
+        self.streaming_iter_with_buffer_size(4096 * 8)
+    }
+
+    /// Like [`streaming_iter()`](Self::streaming_iter()), but reads the compressed pack data in chunks of up to
+    /// `buf_size` bytes to feed the decompressor with.
+    ///
+    /// Larger buffers mean fewer reads, which can increase throughput with fast decompressors like `zlib-ng`.
+    pub fn streaming_iter_with_buffer_size(
+        &self,
+        buf_size: usize,
+    ) -> Result<BytesToEntriesIter<impl io::BufRead>, input::Error> {
+        let reader = io::BufReader::with_capacity(buf_size, fs::File::open(&self.path)?);
         BytesToEntriesIter::new_from_header(
             reader,
             input::Mode::Verify,
@@ -303,7 +314,7 @@ where
 /// A utility to automatically generate a hash while writing into an inner writer.
 pub struct HashWrite<'a, T> {
     /// The hash implementation.
-    pub hash: &'a mut Sha1,
+    pub hash: &'a mut Hasher,
     /// The inner writer.
     pub inner: T,
 }
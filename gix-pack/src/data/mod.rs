@@ -7,8 +7,6 @@ pub type Offset = u64;
 /// An identifier to uniquely identify all packs loaded within a known context or namespace.
 pub type Id = u32;
 
-use memmap2::Mmap;
-
 /// An representing an full- or delta-object within a pack
 #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -33,6 +31,48 @@ pub mod header;
 #[allow(clippy::empty_docs)]
 pub mod init {
     pub use super::header::decode::Error;
+
+    /// Options for use in [`File::at_opts()`](super::File::at_opts()).
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct Options {
+        /// The way the pack data is held.
+        pub backing: Backing,
+        /// The access pattern to expect for memory-mapped pack data, as long as no other one is advised
+        /// [temporarily](super::File::advise()).
+        pub advice: Advice,
+    }
+
+    /// Determines how the data of a pack is held, with different trade-offs for performance and resource usage.
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Backing {
+        /// Memory-map the pack data so that the operating system loads it on demand.
+        #[default]
+        MemoryMap,
+        /// Read all pack data into memory with buffered reads when opening the pack.
+        ///
+        /// This is useful on filesystems where memory maps are slow or unreliable, but costs as much memory as the
+        /// pack is large.
+        Buffered,
+    }
+
+    /// The access pattern to advise the operating system of so that it can optimize paging of memory-mapped pack data,
+    /// similar to `madvise()`.
+    ///
+    /// On platforms without support for it, and for [buffered](Backing::Buffered) pack data, advice is ignored.
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Advice {
+        /// No particular access pattern is expected.
+        #[default]
+        Normal,
+        /// Data is accessed in random order, as is typical for lookups of single objects using an index, so reading
+        /// ahead would be wasteful.
+        Random,
+        /// Data is accessed in sequential order, as is typical for scans of the whole pack, so it can be read ahead
+        /// aggressively and freed soon after access.
+        Sequential,
+        /// Data will be accessed soon, so it should be read ahead right away.
+        WillNeed,
+    }
 }
 
 ///
@@ -62,9 +102,27 @@ pub enum Version {
     V3,
 }
 
+/// The storage of the data of a pack.
+enum Data {
+    Mmap(memmap2::Mmap),
+    Buffered(Vec<u8>),
+}
+
+impl std::ops::Deref for Data {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Data::Mmap(data) => data,
+            Data::Buffered(data) => data,
+        }
+    }
+}
+
 /// A pack data file
 pub struct File {
-    data: Mmap,
+    data: Data,
+    advice: init::Advice,
     path: std::path::PathBuf,
     /// A value to represent this pack uniquely when used with cache lookup, or a way to identify this pack by its location on disk.
     /// The same location on disk should yield the same id.
@@ -110,6 +168,35 @@ impl File {
         &self.path
     }
 
+    /// The access pattern that was advised when opening the pack, and which is expected when no other is advised.
+    pub fn advice(&self) -> init::Advice {
+        self.advice
+    }
+
+    /// Advise the operating system that the pack data will be accessed according to `advice` from now on, which can be
+    /// reverted by advising the pattern returned by [`advice()`](Self::advice()).
+    ///
+    /// Note that this affects all users of this instance, and that it does nothing if the pack data isn't
+    /// memory-mapped or if the platform doesn't support it.
+    pub fn advise(&self, advice: init::Advice) -> std::io::Result<()> {
+        match &self.data {
+            #[cfg(unix)]
+            Data::Mmap(data) => {
+                use init::Advice;
+                data.advise(match advice {
+                    Advice::Normal => memmap2::Advice::Normal,
+                    Advice::Random => memmap2::Advice::Random,
+                    Advice::Sequential => memmap2::Advice::Sequential,
+                    Advice::WillNeed => memmap2::Advice::WillNeed,
+                })
+            }
+            _ => {
+                let _ = advice;
+                Ok(())
+            }
+        }
+    }
+
     /// Returns the pack data at the given slice if its range is contained in the mapped pack data
     pub fn entry_slice(&self, slice: EntryRange) -> Option<&[u8]> {
         let entry_end: usize = slice.end.try_into().expect("end of pack fits into usize");
@@ -52,6 +52,7 @@ pub(crate) mod function {
             allow_thin_pack,
             thread_limit,
             chunk_size,
+            max_depth,
         }: Options,
     ) -> impl Iterator<Item = Result<(SequenceId, Vec<output::Entry>), Error>>
            + parallel::reduce::Finalize<Reduce = reduce::Statistics<Error>>
@@ -134,6 +135,68 @@ pub(crate) mod function {
                 index
             }
         };
+        let exceeds_max_depth = match mode {
+            Mode::PackCopyAndBaseObjects => {
+                let mut progress =
+                    progress.add_child_with_id("limiting delta chains".into(), ProgressId::LimitDeltaChains.into());
+                progress.init(Some(counts.len()), gix_features::progress::count("counts"));
+                let start = std::time::Instant::now();
+
+                let mut base_offsets = vec![None; counts.len()];
+                let enough_counts_present = counts.len() > 4_000;
+                parallel::in_parallel_if(
+                    || enough_counts_present,
+                    counts.chunks(chunk_size).zip(base_offsets.chunks_mut(chunk_size)),
+                    thread_limit,
+                    |_n| (),
+                    {
+                        let db = db.clone();
+                        move |(counts, base_offsets), _state| {
+                            for (count, base_offset) in counts.iter().zip(base_offsets.iter_mut()) {
+                                *base_offset = util::ofs_delta_base_offset(&db, count, version);
+                            }
+                            Ok::<_, ()>(())
+                        }
+                    },
+                    parallel::reduce::IdentityWithResult::<(), ()>::default(),
+                )
+                .expect("infallible - objects we can't read are recompressed later");
+
+                // Bases are always stored before the deltas that refer to them, which is why their depth is known
+                // by the time we see the delta.
+                let mut depths = vec![0u32; counts.len()];
+                let mut exceeds_max_depth = vec![false; counts.len()];
+                for (_, pack_range) in &counts_range_by_pack_id {
+                    let counts_in_pack = &counts[pack_range.clone()];
+                    for idx in pack_range.clone() {
+                        let Some(base_offset) = base_offsets[idx] else {
+                            continue;
+                        };
+                        let depth = match counts_in_pack.binary_search_by(|e| {
+                            e.entry_pack_location
+                                .as_ref()
+                                .expect("packed")
+                                .pack_offset
+                                .cmp(&base_offset)
+                        }) {
+                            Ok(base_idx) => depths[pack_range.start + base_idx] + 1,
+                            Err(_) if allow_thin_pack => 1,
+                            Err(_) => continue,
+                        };
+                        if depth > max_depth {
+                            exceeds_max_depth[idx] = true;
+                        } else {
+                            depths[idx] = depth;
+                        }
+                    }
+                }
+
+                progress.set(counts.len());
+                progress.show_throughput(start);
+
+                exceeds_max_depth
+            }
+        };
 
         let counts = Arc::new(counts);
         let progress = Arc::new(parking_lot::Mutex::new(progress));
@@ -155,17 +218,22 @@ pub(crate) mod function {
             },
             {
                 let counts = Arc::clone(&counts);
+                let exceeds_max_depth = Arc::new(exceeds_max_depth);
                 move |(chunk_id, chunk_range): (SequenceId, std::ops::Range<usize>), (buf, progress)| {
                     let mut out = Vec::new();
-                    let chunk = &counts[chunk_range];
+                    let chunk = &counts[chunk_range.clone()];
                     let mut stats = Outcome::default();
                     let mut pack_offsets_to_id = None;
                     progress.init(Some(chunk.len()), gix_features::progress::count("objects"));
 
-                    for count in chunk.iter() {
+                    for (count, exceeds_max_depth) in chunk.iter().zip(&exceeds_max_depth[chunk_range]) {
+                        if *exceeds_max_depth {
+                            stats.objects_exceeding_max_depth += 1;
+                        }
                         out.push(match count
                             .entry_pack_location
                             .as_ref()
+                            .filter(|_| !*exceeds_max_depth)
                             .and_then(|l| db.entry_by_location(l).map(|pe| (l, pe)))
                         {
                             Some((location, pack_entry)) => {
@@ -245,6 +313,30 @@ pub(crate) mod function {
 }
 
 mod util {
+    use crate::{data, data::output};
+
+    /// Return the pack offset of the base of `count` if it is an offset delta in a pack of the given `version`,
+    /// or `None` if it will be copied as base object or isn't packed at all.
+    pub fn ofs_delta_base_offset(
+        db: &impl crate::Find,
+        count: &output::Count,
+        version: data::Version,
+    ) -> Option<data::Offset> {
+        let location = count.entry_pack_location.as_ref()?;
+        let entry = db.entry_by_location(location)?;
+        if entry.version != version {
+            return None;
+        }
+        let pack_offset_must_be_zero = 0;
+        match data::Entry::from_bytes(&entry.data, pack_offset_must_be_zero, count.id.as_slice().len())
+            .ok()?
+            .header
+        {
+            data::entry::Header::OfsDelta { base_distance } => location.pack_offset.checked_sub(base_distance),
+            _ => None,
+        }
+    }
+
     #[derive(Clone)]
     pub struct ChunkRanges {
         cursor: usize,
@@ -335,6 +427,9 @@ mod types {
         pub objects_copied_from_pack: usize,
         /// The amount of objects that ref to their base as ref-delta, an indication for a thin back being created.
         pub ref_delta_objects: usize,
+        /// The amount of delta objects that were decoded and recompressed as base objects as their delta chain would
+        /// otherwise have been longer than [`Options::max_depth`].
+        pub objects_exceeding_max_depth: usize,
     }
 
     impl Outcome {
@@ -345,12 +440,14 @@ mod types {
                 missing_objects,
                 objects_copied_from_pack,
                 ref_delta_objects,
+                objects_exceeding_max_depth,
             }: Self,
         ) {
             self.decoded_and_recompressed_objects += decoded_objects;
             self.missing_objects += missing_objects;
             self.objects_copied_from_pack += objects_copied_from_pack;
             self.ref_delta_objects += ref_delta_objects;
+            self.objects_exceeding_max_depth += objects_exceeding_max_depth;
         }
     }
 
@@ -385,6 +482,12 @@ mod types {
         pub chunk_size: usize,
         /// The pack data version to produce for each entry
         pub version: crate::data::Version,
+        /// The maximum amount of deltas in a delta chain of the resulting pack, similar to `pack.depth` in `git`.
+        ///
+        /// Deltas copied from a pack that would be at the end of a longer chain are decoded and recompressed as base
+        /// objects instead, which also cuts the chains of all deltas that are based on them.
+        /// If `0`, only base objects will be written.
+        pub max_depth: u32,
     }
 
     impl Default for Options {
@@ -395,6 +498,7 @@ mod types {
                 allow_thin_pack: false,
                 chunk_size: 10,
                 version: Default::default(),
+                max_depth: 50,
             }
         }
     }
@@ -418,6 +522,8 @@ mod types {
         ResolveCounts,
         /// Layout pack entries for placement into a pack (by pack-id and by offset).
         SortEntries,
+        /// Determine the length of the delta chains to keep them within [`Options::max_depth`].
+        LimitDeltaChains,
     }
 
     impl From<ProgressId> for gix_features::progress::Id {
@@ -425,6 +531,7 @@ mod types {
             match v {
                 ProgressId::ResolveCounts => *b"ECRC",
                 ProgressId::SortEntries => *b"ECSE",
+                ProgressId::LimitDeltaChains => *b"ECLD",
             }
         }
     }
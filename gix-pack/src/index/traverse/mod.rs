@@ -75,6 +75,9 @@ impl index::File {
     ///
     /// Use [`thread_limit`][Options::thread_limit] to further control parallelism and [`check`][SafetyCheck] to define how much the passed
     /// objects shall be verified beforehand.
+    ///
+    /// As all of `pack` is read, consider opening it with [`Advice::Sequential`](crate::data::init::Advice::Sequential)
+    /// if it isn't shared with other users.
     pub fn traverse<C, Processor, E, F>(
         &self,
         pack: &crate::data::File,
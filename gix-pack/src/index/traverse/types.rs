@@ -46,6 +46,40 @@ impl Default for Statistics {
     }
 }
 
+impl Statistics {
+    /// Account for the decoding `stats` of a single object, summing them up in [`average`](Self::average) until
+    /// [`divide_average()`](Self::divide_average()) is called.
+    pub(crate) fn add(&mut self, stats: crate::data::decode::entry::Outcome) {
+        *self.objects_per_chain_length.entry(stats.num_deltas).or_insert(0) += 1;
+        self.total_decompressed_entries_size += stats.decompressed_size;
+        self.total_compressed_entries_size += stats.compressed_size as u64;
+        self.total_object_size += stats.object_size;
+        use gix_object::Kind::*;
+        match stats.kind {
+            Commit => self.num_commits += 1,
+            Tree => self.num_trees += 1,
+            Blob => self.num_blobs += 1,
+            Tag => self.num_tags += 1,
+        }
+        let average = &mut self.average;
+        average.num_deltas += stats.num_deltas;
+        average.decompressed_size += stats.decompressed_size;
+        average.compressed_size += stats.compressed_size;
+        average.object_size += stats.object_size;
+    }
+
+    /// Turn the sums in [`average`](Self::average) into the average over `num_objects`.
+    pub(crate) fn divide_average(&mut self, num_objects: usize) {
+        let average = &mut self.average;
+        if num_objects != 0 {
+            average.num_deltas = (average.num_deltas as f32 / num_objects as f32) as u32;
+            average.decompressed_size /= num_objects as u64;
+            average.compressed_size /= num_objects;
+            average.object_size /= num_objects as u64;
+        }
+    }
+}
+
 /// The ways to validate decoded objects before passing them to the processor.
 #[derive(Default, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -88,6 +88,10 @@ pub mod integrity {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod stream;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod checksum {
@@ -0,0 +1,261 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use gix_features::zlib;
+
+use crate::{data, index};
+
+/// Returned by [`index::File::verify_stream()`] and its [iterator](Iter).
+#[derive(thiserror::Error, Debug)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The packfiles checksum didn't match the index file checksum: expected {expected}, got {actual}")]
+    PackMismatch {
+        expected: gix_hash::ObjectId,
+        actual: gix_hash::ObjectId,
+    },
+    #[error("The header of the entry for object {id} at offset {offset} could not be decoded")]
+    EntryType {
+        id: gix_hash::ObjectId,
+        offset: data::Offset,
+        source: data::entry::decode::Error,
+    },
+    #[error("Object {id} at offset {offset} could not be decoded")]
+    PackDecode {
+        id: gix_hash::ObjectId,
+        offset: data::Offset,
+        source: data::decode::Error,
+    },
+    #[error("The hash of {kind} object at offset {offset} didn't match the checksum in the index file: expected {expected}, got {actual}")]
+    ObjectMismatch {
+        expected: gix_hash::ObjectId,
+        actual: gix_hash::ObjectId,
+        offset: data::Offset,
+        kind: gix_object::Kind,
+    },
+    #[error("The checksum over all pack data didn't match its trailer: expected {expected}, got {actual}")]
+    PackChecksumMismatch {
+        expected: gix_hash::ObjectId,
+        actual: gix_hash::ObjectId,
+    },
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+/// The result of the CRC32 check of a single pack entry.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Crc32 {
+    /// The CRC32 of the compressed pack entry matches the one stored in the index.
+    Match,
+    /// The CRC32 of the compressed pack entry differs from the one stored in the index.
+    Mismatch {
+        /// The CRC32 stored in the index.
+        expected: u32,
+        /// The CRC32 computed from the pack entry.
+        actual: u32,
+    },
+    /// The index doesn't store CRC32 values, which is the case for V1 indices.
+    Unavailable,
+}
+
+/// An object that was decoded from the pack and whose hash matches the one stored in the index.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Object {
+    /// The id of the object.
+    pub id: gix_hash::ObjectId,
+    /// The kind of the object.
+    pub kind: gix_object::Kind,
+    /// The offset at which the object's entry starts in the pack.
+    pub pack_offset: data::Offset,
+    /// The size of the object after decompressing it and resolving all of its deltas.
+    pub decompressed_size: u64,
+    /// The result of checking the CRC32 of the compressed entry.
+    pub crc32: Crc32,
+    /// Statistics about decoding the pack entry.
+    pub decode: data::decode::entry::Outcome,
+}
+
+/// An iterator over all objects of a pack in the order they are stored in, verifying each one of them, as
+/// returned by [`index::File::verify_stream()`].
+///
+/// Failing objects are returned as error, after which the iteration continues with the next object.
+/// Once all objects were returned, the checksum of the pack is verified, which is computed incrementally
+/// alongside the objects, yielding an error if it doesn't match.
+///
+/// If the interrupt flag is set, [`Error::Interrupted`] is returned and the iteration stops, and
+/// [`statistics()`](Self::statistics()) provides the statistics of all objects seen until then.
+pub struct Iter<'a, C> {
+    index: &'a index::File,
+    pack: &'a data::File,
+    entries: std::vec::IntoIter<index::Entry>,
+    cache: C,
+    buf: Vec<u8>,
+    inflate: zlib::Inflate,
+    hasher: gix_features::hash::Hasher,
+    hashed_until: data::Offset,
+    statistics: index::traverse::Statistics,
+    num_objects_seen: usize,
+    should_interrupt: &'a AtomicBool,
+    done: bool,
+}
+
+/// Streaming verification
+impl index::File {
+    /// Return an iterator that decodes and verifies each object in `pack` one at a time, yielding
+    /// information about it or an error if it is invalid.
+    ///
+    /// Use `cache` to speed up the resolution of delta chains, and `should_interrupt` to stop the verification early.
+    /// Note that objects are verified using the current thread only, whereas [`verify_integrity()`](Self::verify_integrity())
+    /// uses multiple threads and is faster.
+    pub fn verify_stream<'a, C>(
+        &'a self,
+        pack: &'a data::File,
+        cache: C,
+        should_interrupt: &'a AtomicBool,
+    ) -> Result<Iter<'a, C>, Error>
+    where
+        C: crate::cache::DecodeEntry,
+    {
+        if self.pack_checksum() != pack.checksum() {
+            return Err(Error::PackMismatch {
+                expected: self.pack_checksum(),
+                actual: pack.checksum(),
+            });
+        }
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_by_key(|e| e.pack_offset);
+        Ok(Iter {
+            index: self,
+            pack,
+            entries: entries.into_iter(),
+            cache,
+            buf: Vec::with_capacity(2048),
+            inflate: zlib::Inflate::default(),
+            hasher: gix_features::hash::hasher(pack.object_hash()),
+            hashed_until: 0,
+            statistics: index::traverse::Statistics {
+                pack_size: pack.data_len() as u64,
+                ..Default::default()
+            },
+            num_objects_seen: 0,
+            should_interrupt,
+            done: false,
+        })
+    }
+}
+
+impl<'a, C> Iter<'a, C> {
+    /// Return the statistics of all objects that were successfully verified so far.
+    pub fn statistics(&self) -> index::traverse::Statistics {
+        let mut statistics = self.statistics.clone();
+        statistics.divide_average(self.num_objects_seen);
+        statistics
+    }
+
+    /// Hash all pack data up to `end`, which must not be smaller than what was hashed before.
+    fn hash_until(&mut self, end: data::Offset) {
+        if let Some(bytes) = self.pack.entry_slice(self.hashed_until..end) {
+            self.hasher.update(bytes);
+            self.hashed_until = end;
+        }
+    }
+}
+
+impl<'a, C> Iter<'a, C>
+where
+    C: crate::cache::DecodeEntry,
+{
+    fn verify_entry(&mut self, index_entry: index::Entry) -> Result<Object, Error> {
+        let index::Entry {
+            oid: id,
+            pack_offset,
+            crc32: expected_crc32,
+        } = index_entry;
+        let pack_entry = self.pack.entry(pack_offset).map_err(|source| Error::EntryType {
+            id,
+            offset: pack_offset,
+            source,
+        })?;
+        let header_size = (pack_entry.data_offset - pack_offset) as usize;
+        let (index, pack) = (self.index, self.pack);
+        let decode = pack
+            .decode_entry(
+                pack_entry,
+                &mut self.buf,
+                &mut self.inflate,
+                &|id, _| {
+                    let index_position = index.lookup(id)?;
+                    pack.entry(index.pack_offset_at_index(index_position))
+                        .ok()
+                        .map(data::decode::entry::ResolvedBase::InPack)
+                },
+                &mut self.cache,
+            )
+            .map_err(|source| Error::PackDecode {
+                id,
+                offset: pack_offset,
+                source,
+            })?;
+        let entry_end = pack_offset + (header_size + decode.compressed_size) as data::Offset;
+        self.hash_until(entry_end);
+
+        let actual = gix_object::compute_hash(id.kind(), decode.kind, &self.buf);
+        if actual != id {
+            return Err(Error::ObjectMismatch {
+                expected: id,
+                actual,
+                offset: pack_offset,
+                kind: decode.kind,
+            });
+        }
+        let crc32 = match expected_crc32 {
+            Some(expected) => {
+                let actual = pack.entry_crc32(pack_offset, header_size + decode.compressed_size);
+                if actual == expected {
+                    Crc32::Match
+                } else {
+                    Crc32::Mismatch { expected, actual }
+                }
+            }
+            None => Crc32::Unavailable,
+        };
+
+        self.statistics.add(decode.clone());
+        self.num_objects_seen += 1;
+        Ok(Object {
+            id,
+            kind: decode.kind,
+            pack_offset,
+            decompressed_size: decode.object_size,
+            crc32,
+            decode,
+        })
+    }
+}
+
+impl<'a, C> Iterator for Iter<'a, C>
+where
+    C: crate::cache::DecodeEntry,
+{
+    type Item = Result<Object, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.should_interrupt.load(Ordering::Relaxed) {
+            self.done = true;
+            return Some(Err(Error::Interrupted));
+        }
+        match self.entries.next() {
+            Some(entry) => Some(self.verify_entry(entry)),
+            None => {
+                self.done = true;
+                self.hash_until(self.pack.pack_end() as data::Offset);
+                let actual =
+                    std::mem::replace(&mut self.hasher, gix_features::hash::hasher(self.pack.object_hash())).digest();
+                let expected = self.pack.checksum();
+                (actual != expected).then_some(Err(Error::PackChecksumMismatch { expected, actual }))
+            }
+        }
+    }
+}
@@ -2,7 +2,8 @@
 //! is what git packs are concerned about.
 //!
 //! Packs consist of [data files][data::File] and [index files][index::File]. The latter can be generated from a data file
-//! and make accessing objects within a pack feasible.
+//! and make accessing objects within a pack feasible. Optional [bitmap files][bitmap::File] make it possible to obtain
+//! all objects reachable from select commits without traversing the commit graph.
 //!
 //! A [Bundle] conveniently combines a data pack alongside its index to allow [finding][Find] objects or verifying the pack.
 //! Objects returned by `.find(…)` are [objects][gix_object::Data] which know their pack location in order to speed up
@@ -18,6 +19,10 @@
 #![cfg_attr(all(doc, feature = "document-features"), feature(doc_cfg, doc_auto_cfg))]
 #![deny(missing_docs, rust_2018_idioms, unsafe_code)]
 
+///
+#[allow(clippy::empty_docs)]
+pub mod bitmap;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod bundle;
@@ -19,6 +19,9 @@ mod error {
         Interrupted,
         #[error(transparent)]
         OpenIndex(#[from] crate::index::init::Error),
+        #[cfg(all(not(feature = "wasm"), feature = "streaming-input"))]
+        #[error("Could not move the written multi-index into place")]
+        Persist(#[from] gix_tempfile::handle::persist::Error<gix_tempfile::handle::Writable>),
     }
 }
 pub use error::Error;
@@ -73,8 +76,37 @@ impl multi_index::File {
         1 /*num base files */ +
         4 /*num pack files*/;
 
+    /// Like [`write_from_index_paths()`](Self::write_from_index_paths()), but write the multi-index into a temporary file
+    /// next to `multi_index_path` which is then moved into place only once it was written completely, so it's either
+    /// entirely present or not changed at all.
+    #[cfg(all(not(feature = "wasm"), feature = "streaming-input"))]
+    pub fn write_from_index_paths_to_path(
+        index_paths: Vec<PathBuf>,
+        multi_index_path: &std::path::Path,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Outcome, Error> {
+        let directory = multi_index_path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or_else(|| std::path::Path::new("."));
+        let mut file = std::io::BufWriter::new(gix_tempfile::new(
+            directory,
+            gix_tempfile::ContainingDirectory::Exists,
+            gix_tempfile::AutoRemove::Tempfile,
+        )?);
+        let outcome = Self::write_from_index_paths(index_paths, &mut file, progress, should_interrupt, options)?;
+        file.into_inner()
+            .map_err(std::io::IntoInnerError::into_error)?
+            .persist(multi_index_path)?;
+        Ok(outcome)
+    }
+
     /// Create a new multi-index file for writing to `out` from the pack index files at `index_paths`.
     ///
+    /// Objects that are contained in more than one pack are recorded only for the pack whose index was modified last.
+    ///
     /// Progress is sent to `progress` and interruptions checked via `should_interrupt`.
     pub fn write_from_index_paths(
         mut index_paths: Vec<PathBuf>,
@@ -213,7 +245,7 @@ impl multi_index::File {
         }
 
         // write trailing checksum
-        let multi_index_checksum: gix_hash::ObjectId = out.inner.hash.digest().into();
+        let multi_index_checksum = out.inner.hash.digest();
         out.inner.inner.write_all(multi_index_checksum.as_slice())?;
         out.progress.show_throughput(write_start);
 
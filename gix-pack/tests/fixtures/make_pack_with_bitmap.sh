@@ -0,0 +1,29 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+for round in $(seq 150); do
+  dir="d$(( round % 3 ))"
+  mkdir -p "$dir"
+  echo "$round" > "$dir/$round"
+  git add .
+  git commit -qm "$round"
+done
+
+git checkout -q -b side HEAD~50
+echo side > side
+git add side
+git commit -qm "side"
+git tag -m "an annotated tag" annotated
+git checkout -q main
+git merge -q --no-ff -m "merge side" side
+
+git -c pack.writeBitmapLookupTable=true repack -adbq
+
+mkdir reachable
+for commit in $(git rev-list --all); do
+  git rev-list --objects --no-object-names "$commit" | sort > "reachable/$commit"
+done
+git cat-file --batch-all-objects --batch-check='%(objectname) %(objecttype)' > objects
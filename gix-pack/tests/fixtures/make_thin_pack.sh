@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+seq 1000 > file
+git add file
+git commit -qm "base"
+git tag base
+
+for round in $(seq 10); do
+  sed "s/^${round}0\$/changed in round $round/" file > file.tmp
+  mv file.tmp file
+  git add file
+  git commit -qm "$round"
+done
+
+# Without --delta-base-offset, all deltas refer to their base by id, whether it is in the pack or not.
+printf 'main\n^base\n' | git pack-objects -q --revs --thin --stdout --depth=5 > thin.pack
+
+# A repository with only the objects the thin pack is based on.
+git init -q --bare base.git
+git push -q base.git refs/tags/base
@@ -0,0 +1,111 @@
+use std::path::PathBuf;
+
+use gix_pack::{bitmap, index};
+
+fn bitmap_and_index() -> crate::Result<(bitmap::File, index::File, PathBuf)> {
+    let dir = crate::scripted_fixture_read_only("make_pack_with_bitmap.sh")?;
+    let pack_dir = dir.join(".git/objects/pack");
+    let index_path = std::fs::read_dir(&pack_dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .find(|path| matches!(path.extension(), Some(ext) if ext == "idx"))
+        .expect("single pack");
+    let index = index::File::at(&index_path, gix_hash::Kind::Sha1)?;
+    let bitmap = bitmap::File::at(index_path.with_extension("bitmap"), &index)?;
+    Ok((bitmap, index, dir))
+}
+
+fn sorted(mut ids: Vec<gix_hash::ObjectId>) -> Vec<gix_hash::ObjectId> {
+    ids.sort();
+    ids
+}
+
+fn ids_in(content: &str) -> Vec<gix_hash::ObjectId> {
+    content.lines().map(crate::hex_to_id).collect()
+}
+
+#[test]
+fn reachable_objects_match_git() -> crate::Result {
+    let (bitmap, index, dir) = bitmap_and_index()?;
+    assert_eq!(bitmap.version(), bitmap::Version::V1);
+
+    let mut commits_with_bitmap = 0;
+    for entry in std::fs::read_dir(dir.join("reachable"))? {
+        let path = entry?.path();
+        let commit = crate::hex_to_id(path.file_name().and_then(|n| n.to_str()).expect("hex name"));
+        let expected = ids_in(&std::fs::read_to_string(&path)?);
+        match bitmap.reachable_objects(&commit, &index) {
+            Some(actual) => {
+                commits_with_bitmap += 1;
+                assert_eq!(sorted(actual), expected, "{commit}: bitmap contains exactly what git can reach");
+                assert_eq!(
+                    bitmap.count_reachable_objects(&commit, &index),
+                    Some(expected.len()),
+                    "{commit}: counting works without obtaining ids"
+                );
+            }
+            None => assert_eq!(bitmap.count_reachable_objects(&commit, &index), None),
+        }
+    }
+    assert_eq!(
+        commits_with_bitmap,
+        bitmap.num_commits(),
+        "all commits with a bitmap are reachable from refs"
+    );
+    assert!(commits_with_bitmap > 1, "git writes a bitmap for more than one commit");
+    Ok(())
+}
+
+#[test]
+fn objects_without_bitmap_yield_none() -> crate::Result {
+    let (bitmap, index, dir) = bitmap_and_index()?;
+    let blob = std::fs::read_to_string(dir.join("objects"))?
+        .lines()
+        .find_map(|line| line.strip_suffix(" blob"))
+        .map(crate::hex_to_id)
+        .expect("at least one blob");
+    assert_eq!(bitmap.reachable_objects(&blob, &index), None, "only commits have bitmaps");
+    assert_eq!(
+        bitmap.reachable_objects(&gix_hash::Kind::Sha1.null(), &index),
+        None,
+        "unknown objects have no bitmap either"
+    );
+    Ok(())
+}
+
+#[test]
+fn objects_of_kind_match_git() -> crate::Result {
+    let (bitmap, index, dir) = bitmap_and_index()?;
+    let objects = std::fs::read_to_string(dir.join("objects"))?;
+    for kind in [
+        gix_object::Kind::Commit,
+        gix_object::Kind::Tree,
+        gix_object::Kind::Blob,
+        gix_object::Kind::Tag,
+    ] {
+        let expected: Vec<_> = objects
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .filter(|(_, object_kind)| object_kind.as_bytes() == kind.as_bytes())
+            .map(|(hex, _)| crate::hex_to_id(hex))
+            .collect();
+        assert!(!expected.is_empty(), "each kind is present");
+        assert_eq!(sorted(bitmap.objects_of_kind(kind, &index)), sorted(expected), "{kind}");
+    }
+    Ok(())
+}
+
+#[test]
+fn mismatching_index_is_rejected() -> crate::Result {
+    let (bitmap, _index, _dir) = bitmap_and_index()?;
+    let other_index = index::File::at(
+        crate::fixture_path(super::SMALL_PACK_INDEX),
+        gix_hash::Kind::Sha1,
+    )?;
+    let err = bitmap::File::at(bitmap.path(), &other_index)
+        .err()
+        .expect("the index belongs to another pack");
+    assert!(matches!(err, bitmap::init::Error::PackMismatch { .. }), "{err:?}");
+    Ok(())
+}
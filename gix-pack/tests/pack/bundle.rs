@@ -181,3 +181,85 @@ mod write_to_directory {
         .map_err(Into::into)
     }
 }
+
+mod complete_thin_pack_to_directory {
+    use std::sync::atomic::AtomicBool;
+
+    use gix_features::progress;
+    use gix_odb::pack;
+    use gix_testtools::tempfile::TempDir;
+
+    use crate::{hex_to_id, scripted_fixture_read_only};
+
+    fn complete(
+        directory: Option<&std::path::Path>,
+        base_objects: impl gix_object::Find,
+    ) -> Result<pack::bundle::write::Outcome, pack::bundle::write::Error> {
+        let fixture = scripted_fixture_read_only("make_thin_pack.sh").expect("fixture is valid");
+        let thin_pack = pack::data::File::at(fixture.join("thin.pack"), gix_hash::Kind::Sha1).expect("valid pack");
+        pack::Bundle::complete_thin_pack_to_directory(
+            &thin_pack,
+            directory,
+            &mut progress::Discard,
+            &AtomicBool::new(false),
+            base_objects,
+            Default::default(),
+        )
+    }
+
+    #[test]
+    fn bases_are_added_from_the_object_database_and_resolved_within_the_pack() -> crate::Result {
+        let fixture = scripted_fixture_read_only("make_thin_pack.sh")?;
+        let base_objects = gix_odb::at(fixture.join("base.git").join("objects"))?;
+        let dir = TempDir::new()?;
+        let outcome = complete(Some(dir.path()), base_objects)?;
+        assert_eq!(
+            outcome.index.num_objects,
+            30 + 1,
+            "only the base blob is added, all other deltas have their base in the pack, even if it is a delta itself"
+        );
+
+        let bundle = outcome.to_bundle().expect("written to directory")?;
+        bundle.verify_integrity(
+            &mut progress::Discard,
+            &AtomicBool::new(false),
+            pack::index::verify::integrity::Options::default(),
+        )?;
+        let base_blob = hex_to_id("1179824569dcb14413904cb2b5cb036a9551024d");
+        assert!(bundle.index.lookup(base_blob).is_some(), "the base was added");
+        assert_eq!(
+            bundle.pack.entry(12 /* pack header */)?.header,
+            pack::data::entry::Header::Blob,
+            "it is stored in front of the pack"
+        );
+        for entry in bundle.index.iter() {
+            assert!(
+                !matches!(
+                    bundle.pack.entry(entry.pack_offset)?.header,
+                    pack::data::entry::Header::RefDelta { .. }
+                ),
+                "all deltas refer to their base by offset"
+            );
+        }
+
+        let status = std::process::Command::new("git")
+            .arg("verify-pack")
+            .arg(outcome.index_path.expect("written to directory"))
+            .status()?;
+        assert!(status.success(), "git considers the pack valid");
+        Ok(())
+    }
+
+    #[test]
+    fn missing_bases_are_an_error() {
+        let err = complete(None, gix_object::find::Never).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                pack::bundle::write::Error::PackIter(pack::data::input::Error::NotFound { object_id })
+                    if object_id == hex_to_id("1179824569dcb14413904cb2b5cb036a9551024d")
+            ),
+            "the base that the thin pack refers to can't be found: {err:?}"
+        );
+    }
+}
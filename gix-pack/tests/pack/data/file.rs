@@ -11,8 +11,10 @@ mod method {
 
     use gix_features::progress;
 
+    use gix_odb::pack;
+
     use crate::{
-        hex_to_id,
+        fixture_path, hex_to_id,
         pack::{data::file::pack_at, SMALL_PACK},
     };
 
@@ -39,12 +41,62 @@ mod method {
         assert_eq!(it.count(), pack.num_objects() as usize);
         Ok(())
     }
+
+    #[test]
+    fn at_opts() -> Result<(), Box<dyn std::error::Error>> {
+        use gix_pack::data::init::{Advice, Backing, Options};
+        let mapped = pack_at(SMALL_PACK);
+        assert_eq!(mapped.advice(), Advice::Normal, "nothing is advised by default");
+        for backing in [Backing::MemoryMap, Backing::Buffered] {
+            let pack = pack::data::File::at_opts(
+                fixture_path(SMALL_PACK),
+                gix_hash::Kind::Sha1,
+                Options {
+                    backing,
+                    advice: Advice::Random,
+                },
+            )?;
+            assert_eq!(pack.advice(), Advice::Random);
+            assert_eq!(pack.data_len(), mapped.data_len());
+            assert_eq!(
+                pack.entry_slice(0..pack.data_len() as u64),
+                mapped.entry_slice(0..mapped.data_len() as u64)
+            );
+            for advice in [Advice::Sequential, Advice::WillNeed, Advice::Normal, pack.advice()] {
+                pack.advise(advice)?;
+            }
+            assert_eq!(
+                pack.verify_checksum(&mut progress::Discard, &AtomicBool::new(false))?,
+                pack.checksum()
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn iter_with_buffer_size() -> Result<(), Box<dyn std::error::Error>> {
+        let pack = pack_at(SMALL_PACK);
+        let expected = pack.streaming_iter()?.collect::<Result<Vec<_>, _>>()?;
+        for buf_size in [1, 7, 4096 * 64] {
+            let actual = pack
+                .streaming_iter_with_buffer_size(buf_size)?
+                .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(
+                actual, expected,
+                "the size of the read buffer doesn't affect the decompressed entries"
+            );
+        }
+        Ok(())
+    }
 }
 
 /// All hardcoded offsets are obtained via `git pack-verify --verbose  tests/fixtures/packs/pack-a2bf8e71d8c18879e499335762dd95119d93d9f1.idx`
 mod decode_entry {
     use bstr::ByteSlice;
-    use gix_pack::{cache, data::decode::entry::ResolvedBase};
+    use gix_pack::{
+        cache,
+        data::decode::{entry::ResolvedBase, Error, LimitExceeded, Limits},
+    };
 
     use crate::{
         fixture_path, fixup,
@@ -96,7 +148,76 @@ mod decode_entry {
         );
     }
 
+    #[test]
+    fn limits_are_checked_before_allocating() {
+        let limited = |offset, limits| {
+            let mut buf = Vec::new();
+            let res = decode_entry_at_offset_with_limits(offset, &mut buf, &limits);
+            (res, buf)
+        };
+        let (res, buf) = limited(
+            1968,
+            Limits {
+                max_object_size: Some(186),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            res,
+            Err(Error::LimitExceeded(LimitExceeded::ObjectSize { size: 187, max: 186 }))
+        ));
+        assert_eq!(buf.capacity(), 0, "the declared size is checked before allocating");
+
+        let (res, buf) = limited(
+            3033,
+            Limits {
+                max_object_size: Some(173),
+                ..Default::default()
+            },
+        );
+        assert!(
+            matches!(res, Err(Error::LimitExceeded(LimitExceeded::ObjectSize { size, max: 173 })) if size > 173),
+            "the base object of the delta chain is larger than the resulting object"
+        );
+        assert_eq!(buf.capacity(), 0);
+
+        let (res, buf) = limited(
+            3033,
+            Limits {
+                max_delta_chain_length: Some(1),
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            res,
+            Err(Error::LimitExceeded(LimitExceeded::DeltaChainLength { max: 1 }))
+        ));
+        assert_eq!(buf.capacity(), 0, "the delta chain is checked before decompressing it");
+
+        let (res, buf) = limited(
+            3033,
+            Limits {
+                max_object_size: Some(1181),
+                max_delta_chain_length: Some(2),
+                max_tree_entries: Some(0),
+            },
+        );
+        assert!(res.is_ok(), "blobs within these limits can be decoded: {res:?}");
+        assert_eq!(buf.len(), 173);
+    }
+
     fn decode_entry_at_offset(offset: u64) -> Vec<u8> {
+        let mut buf = Vec::new();
+        decode_entry_at_offset_with_limits(offset, &mut buf, &Default::default())
+            .expect("valid offset provides valid entry");
+        buf
+    }
+
+    fn decode_entry_at_offset_with_limits(
+        offset: u64,
+        buf: &mut Vec<u8>,
+        limits: &Limits,
+    ) -> Result<gix_pack::data::decode::entry::Outcome, Error> {
         #[allow(clippy::ptr_arg)]
         fn resolve_with_panic(_oid: &gix_hash::oid, _out: &mut Vec<u8>) -> Option<ResolvedBase> {
             panic!("should not want to resolve an id here")
@@ -104,16 +225,14 @@ mod decode_entry {
 
         let p = pack_at(SMALL_PACK);
         let entry = p.entry(offset).expect("valid object type");
-        let mut buf = Vec::new();
-        p.decode_entry(
+        p.decode_entry_with_limits(
             entry,
-            &mut buf,
+            buf,
             &mut Default::default(),
             &resolve_with_panic,
             &mut cache::Never,
+            limits,
         )
-        .expect("valid offset provides valid entry");
-        buf
     }
 }
 
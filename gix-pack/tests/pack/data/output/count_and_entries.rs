@@ -121,6 +121,7 @@ fn traversals() -> crate::Result {
                     missing_objects: 0,
                     objects_copied_from_pack: 16,
                     ref_delta_objects: 0,
+                    objects_exceeding_max_depth: 0,
                 },
                 hex_to_id("b920bbb055e1efb9080592a409d3975738b6efb3"),
                 None,
@@ -154,6 +155,7 @@ fn traversals() -> crate::Result {
                     missing_objects: 0,
                     objects_copied_from_pack: 103,
                     ref_delta_objects: 74,
+                    objects_exceeding_max_depth: 0,
                 },
                 hex_to_id("25114bd8820b393c402cd53ad8ec7f6a84bb0633"),
                 Some(hex_to_id("29ab9797aff1ca826afb699680356695d19c5acb")),
@@ -187,6 +189,7 @@ fn traversals() -> crate::Result {
                     missing_objects: 0,
                     objects_copied_from_pack: 29,
                     ref_delta_objects: 0,
+                    objects_exceeding_max_depth: 0,
                 },
                 hex_to_id("d83d42128e40957c5174920189a0390b5a70f446"),
                 None,
@@ -208,6 +211,7 @@ fn traversals() -> crate::Result {
                     missing_objects: 0,
                     objects_copied_from_pack: 868,
                     ref_delta_objects: 0,
+                    objects_exceeding_max_depth: 0,
                 },
                 hex_to_id("542ad1d1c7c762ea4e36907570ff9e4b5b7dde1b"),
                 None,
@@ -229,6 +233,7 @@ fn traversals() -> crate::Result {
                     missing_objects: 0,
                     objects_copied_from_pack: 868,
                     ref_delta_objects: 0,
+                    objects_exceeding_max_depth: 0,
                 },
                 hex_to_id("542ad1d1c7c762ea4e36907570ff9e4b5b7dde1b"),
                 None,
@@ -320,6 +325,81 @@ fn traversals() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn delta_chains_are_limited_to_max_depth() -> crate::Result {
+    let db = db(DbKind::DeterministicGeneratedContent)?;
+    let head = hex_to_id("dfcb5e39ac6eb30179808bbab721e8a28ce1b52e");
+    let commits = gix_traverse::commit::Simple::new(Some(head), db.clone())
+        .map(|c| c.map(|c| c.id))
+        .collect::<Result<Vec<_>, _>>()?;
+    let (counts, _stats) = output::count::objects(
+        db.clone(),
+        Box::new(commits.into_iter().map(Ok)),
+        &progress::Discard,
+        &AtomicBool::new(false),
+        count::objects::Options {
+            input_object_expansion: count::objects::ObjectExpansion::TreeContents,
+            ..Default::default()
+        },
+    )?;
+
+    let longest_chain = |max_depth: u32| -> crate::Result<(u32, entry::iter_from_counts::Outcome)> {
+        let mut entries_iter = output::entry::iter_from_counts(
+            counts.clone(),
+            db.clone(),
+            Box::new(progress::Discard),
+            output::entry::iter_from_counts::Options {
+                max_depth,
+                ..Default::default()
+            },
+        );
+        let entries: Vec<_> = InOrderIter::from(entries_iter.by_ref())
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+        let stats = entries_iter.finalize()?;
+        let statistics = write_and_verify(db.clone(), entries, gix_hash::Kind::Sha1.null(), None)?;
+        let longest_chain = *statistics
+            .objects_per_chain_length
+            .keys()
+            .next_back()
+            .expect("at least one object");
+        Ok((longest_chain, stats))
+    };
+
+    let (unlimited_chain, unlimited_stats) = longest_chain(u32::MAX)?;
+    assert_eq!(unlimited_stats.objects_exceeding_max_depth, 0);
+    assert!(
+        unlimited_chain > 2,
+        "the source pack has chains that are long enough to be limited, got {unlimited_chain}"
+    );
+    assert_eq!(
+        longest_chain(50)?.0,
+        unlimited_chain,
+        "the default depth of git doesn't affect the chains of packs written by git"
+    );
+
+    for max_depth in [0, 1, 2] {
+        let (chain, stats) = longest_chain(max_depth)?;
+        assert!(
+            chain <= max_depth,
+            "chain of length {chain} exceeds the maximum depth of {max_depth}"
+        );
+        assert!(stats.objects_exceeding_max_depth > 0);
+        assert_eq!(
+            stats.decoded_and_recompressed_objects,
+            unlimited_stats.decoded_and_recompressed_objects + stats.objects_exceeding_max_depth,
+            "only deltas beyond the maximum depth are recompressed additionally"
+        );
+        assert_eq!(
+            stats.decoded_and_recompressed_objects + stats.objects_copied_from_pack,
+            counts.len()
+        );
+    }
+    Ok(())
+}
+
 #[test]
 fn empty_pack_is_allowed() {
     assert_eq!(
@@ -341,7 +421,7 @@ fn write_and_verify(
     entries: Vec<output::Entry>,
     _expected_pack_hash: gix_hash::ObjectId,
     _expected_thin_pack_hash: Option<gix_hash::ObjectId>,
-) -> crate::Result {
+) -> crate::Result<pack::index::traverse::Statistics> {
     let tmp_dir = gix_testtools::tempfile::TempDir::new()?;
     let pack_file_path = tmp_dir.path().join("new.pack");
     let mut pack_file = std::fs::OpenOptions::new()
@@ -418,7 +498,7 @@ fn write_and_verify(
             make_pack_lookup_cache: || pack::cache::Never,
             thread_limit: None,
         },
-    )?;
-
-    Ok(())
+    )
+    .map(|outcome| outcome.pack_traverse_outcome)
+    .map_err(Into::into)
 }
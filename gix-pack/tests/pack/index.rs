@@ -395,6 +395,27 @@ fn pack_lookup() -> Result<(), Box<dyn std::error::Error>> {
             .values()
             .map(|v| *v as usize)
             .sum::<usize>();
+
+        let should_interrupt = AtomicBool::new(false);
+        let mut objects = idx.verify_stream(&pack, cache::Never, &should_interrupt)?;
+        let mut num_streamed_objects = 0;
+        for object in objects.by_ref() {
+            let object = object?;
+            let expected_crc32 = match idx.version() {
+                index::Version::V1 => index::verify::stream::Crc32::Unavailable,
+                index::Version::V2 => index::verify::stream::Crc32::Match,
+            };
+            assert_eq!(object.crc32, expected_crc32);
+            assert_eq!(object.decompressed_size, object.decode.object_size);
+            num_streamed_objects += 1;
+        }
+        assert_eq!(num_streamed_objects, num_objects, "each object is yielded once");
+        assert_eq!(
+            objects.statistics(),
+            *stats,
+            "streaming verification collects the same statistics as the parallel one"
+        );
+
         let sorted_offsets = idx.sorted_offsets();
         assert_eq!(num_objects, sorted_offsets.len());
         for idx_entry in idx.iter() {
@@ -489,3 +510,60 @@ fn iter() -> Result<(), Box<dyn std::error::Error>> {
     }
     Ok(())
 }
+
+#[test]
+fn verify_stream_can_be_interrupted() -> Result<(), Box<dyn std::error::Error>> {
+    let idx = index::File::at(fixture_path(SMALL_PACK_INDEX), gix_hash::Kind::Sha1)?;
+    let pack = pack::data::File::at(fixture_path(SMALL_PACK), gix_hash::Kind::Sha1)?;
+    let should_interrupt = AtomicBool::new(false);
+    let mut objects = idx.verify_stream(&pack, cache::Never, &should_interrupt)?;
+    for object in objects.by_ref().take(5) {
+        object?;
+    }
+    should_interrupt.store(true, Ordering::SeqCst);
+    assert!(matches!(
+        objects.next(),
+        Some(Err(index::verify::stream::Error::Interrupted))
+    ));
+    assert!(objects.next().is_none(), "the iteration stops after an interrupt");
+
+    let statistics = objects.statistics();
+    assert_eq!(
+        statistics.objects_per_chain_length.values().sum::<u32>(),
+        5,
+        "partial statistics are available"
+    );
+    assert_eq!(
+        statistics.num_commits + statistics.num_trees + statistics.num_blobs + statistics.num_tags,
+        5
+    );
+    Ok(())
+}
+
+#[test]
+fn verify_stream_detects_corrupt_pack_data() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let pack_path = tmp.path().join("pack.pack");
+    let mut data = std::fs::read(fixture_path(SMALL_PACK))?;
+    let idx = index::File::at(fixture_path(SMALL_PACK_INDEX), gix_hash::Kind::Sha1)?;
+    let last_entry_offset = *idx.sorted_offsets().last().expect("non-empty") as usize;
+    let corrupt_byte = &mut data[last_entry_offset + 2];
+    *corrupt_byte = !*corrupt_byte;
+    std::fs::write(&pack_path, data)?;
+
+    let pack = pack::data::File::at(&pack_path, gix_hash::Kind::Sha1)?;
+    let should_interrupt = AtomicBool::new(false);
+    let results: Vec<_> = idx.verify_stream(&pack, cache::Never, &should_interrupt)?.collect();
+    assert_eq!(
+        results.len(),
+        idx.num_objects() as usize + 1,
+        "one result per object, and one more for the failed pack checksum"
+    );
+    assert!(results[..results.len() - 2].iter().all(Result::is_ok));
+    assert!(results[results.len() - 2].is_err(), "the last object is damaged");
+    assert!(matches!(
+        results.last(),
+        Some(Err(index::verify::stream::Error::PackChecksumMismatch { .. }))
+    ));
+    Ok(())
+}
@@ -66,3 +66,51 @@ fn from_paths() -> crate::Result {
     assert_eq!(outcome, file.checksum());
     Ok(())
 }
+
+#[test]
+fn from_paths_to_path_is_accepted_by_git() -> crate::Result {
+    let dir = gix_testtools::tempfile::TempDir::new()?;
+    assert!(std::process::Command::new("git")
+        .args(["init", "--bare", "--quiet"])
+        .current_dir(dir.path())
+        .status()?
+        .success());
+    let pack_dir = dir.path().join("objects").join("pack");
+    let mut input_indices = Vec::new();
+    for entry in std::fs::read_dir(fixture_path_standalone("objects/pack"))? {
+        let path = entry?.path();
+        let destination = pack_dir.join(path.file_name().expect("present"));
+        std::fs::copy(&path, &destination)?;
+        if destination.extension().and_then(std::ffi::OsStr::to_str) == Some("idx") {
+            input_indices.push(destination);
+        }
+    }
+
+    let output_path = pack_dir.join("multi-pack-index");
+    let outcome = gix_pack::multi_index::File::write_from_index_paths_to_path(
+        input_indices,
+        &output_path,
+        &mut progress::Discard,
+        &AtomicBool::new(false),
+        gix_pack::multi_index::write::Options {
+            object_hash: gix_hash::Kind::Sha1,
+        },
+    )?;
+    assert_eq!(
+        outcome.multi_index_checksum,
+        hex_to_id("d34d327039a3554f8a644b29e07b903fa71ef269"),
+        "the result is the same as when writing to a stream"
+    );
+    assert_eq!(
+        std::fs::read_dir(&pack_dir)?.count(),
+        3 * 2 + 1,
+        "no temporary file is left behind"
+    );
+
+    let status = std::process::Command::new("git")
+        .args(["multi-pack-index", "verify"])
+        .current_dir(dir.path())
+        .status()?;
+    assert!(status.success(), "git considers the multi-index valid");
+    Ok(())
+}
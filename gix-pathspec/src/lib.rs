@@ -42,6 +42,9 @@ pub mod parse;
 #[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
 pub struct Defaults {
     /// The default signature.
+    ///
+    /// Set it to [`MagicSignature::ICASE`] to match all pathspecs case-insensitively, for instance to follow `core.ignoreCase`.
+    /// Note that [prefixes](Pattern::prefix_directory()) are still matched case-sensitively.
     pub signature: MagicSignature,
     /// The default search-mode.
     ///
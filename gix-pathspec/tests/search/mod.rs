@@ -320,6 +320,41 @@ fn simplified_search_respects_ignore_case() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn ignore_case_by_default_still_matches_the_prefix_case_sensitively() -> crate::Result {
+    let defaults = gix_pathspec::Defaults {
+        signature: gix_pathspec::MagicSignature::ICASE,
+        ..Default::default()
+    };
+    let specs = ["src/MAIN.rs", ":(literal)LIT*", "dir/*.TXT"]
+        .iter()
+        .map(|spec| gix_pathspec::parse(spec.as_bytes(), defaults))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut search = gix_pathspec::Search::from_specs(specs, Some(Path::new("Sub")), Path::new(""))?;
+    for (path, expected) in [
+        ("Sub/src/main.rs", Some(Verbatim)),
+        ("Sub/SRC/main.RS", Some(Verbatim)),
+        ("sub/src/main.rs", None),
+        ("Sub/lit*", Some(Verbatim)),
+        ("Sub/lita", None),
+        ("Sub/DIR/a.txt", Some(WildcardMatch)),
+        ("sub/dir/a.txt", None),
+    ] {
+        assert_eq!(
+            search
+                .pattern_matching_relative_path(path.into(), None, &mut no_attrs)
+                .map(|m| m.kind),
+            expected,
+            "{path}: the default applies to all specs, which still honor their own magic, but never to the prefix"
+        );
+    }
+    assert!(search.can_match_relative_path("Sub/SRC".into(), Some(true)));
+    assert!(!search.can_match_relative_path("sub".into(), Some(true)));
+    assert!(search.directory_matches_prefix("Sub/DIR".into(), true));
+    assert!(!search.directory_matches_prefix("sub/dir".into(), true));
+    Ok(())
+}
+
 #[test]
 fn simplified_search_respects_all_excluded() -> crate::Result {
     let search = gix_pathspec::Search::from_specs(
@@ -154,7 +154,8 @@ impl Arguments {
             self.args.push(line);
         }
     }
-    /// Set the given filter `spec` when listing references.
+    /// Set the given filter `spec`, typically a [`Filter`](crate::fetch::Filter), to omit objects from the pack
+    /// for a partial clone or fetch.
     pub fn filter(&mut self, spec: &str) {
         debug_assert!(self.filter, "'filter' feature required");
         if self.filter {
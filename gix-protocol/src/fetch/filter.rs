@@ -0,0 +1,62 @@
+use bstr::ByteSlice;
+
+/// A filter to request a partial clone or fetch, which omits certain objects from the pack sent by the server,
+/// as with `git clone --filter=<spec>`.
+///
+/// The server has to advertise the `filter` capability to support it.
+#[derive(PartialEq, Eq, Debug, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Filter {
+    /// `blob:none`, omit all blobs.
+    BlobNone,
+    /// `blob:limit=<n>`, omit all blobs whose size is `n` bytes or more.
+    BlobLimit(u64),
+    /// `tree:<depth>`, omit all blobs and trees whose depth from the root tree is `depth` or more.
+    ///
+    /// `tree:0` thus omits all trees and blobs that aren't explicitly wanted.
+    Tree(u64),
+    /// `sparse:oid=<id>`, omit all blobs that aren't needed for a sparse checkout with the specification
+    /// stored in the blob with the given `id`.
+    SparseOid(gix_hash::ObjectId),
+}
+
+impl Filter {
+    /// Parse `spec` like `blob:limit=1k`, or return `None` if it isn't a valid or supported filter specification.
+    ///
+    /// Like in `git`, the size of `blob:limit` may be suffixed with `k`, `m` or `g` to scale it by 1024
+    /// to the power of 1, 2 or 3 respectively.
+    pub fn from_bytes(spec: &[u8]) -> Option<Self> {
+        Some(if spec == b"blob:none" {
+            Filter::BlobNone
+        } else if let Some(limit) = spec.strip_prefix(b"blob:limit=") {
+            Filter::BlobLimit(parse_size(limit)?)
+        } else if let Some(depth) = spec.strip_prefix(b"tree:") {
+            Filter::Tree(depth.to_str().ok()?.parse().ok()?)
+        } else if let Some(hex) = spec.strip_prefix(b"sparse:oid=") {
+            Filter::SparseOid(gix_hash::ObjectId::from_hex(hex).ok()?)
+        } else {
+            return None;
+        })
+    }
+}
+
+impl std::fmt::Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Filter::BlobNone => f.write_str("blob:none"),
+            Filter::BlobLimit(limit) => write!(f, "blob:limit={limit}"),
+            Filter::Tree(depth) => write!(f, "tree:{depth}"),
+            Filter::SparseOid(id) => write!(f, "sparse:oid={id}"),
+        }
+    }
+}
+
+fn parse_size(input: &[u8]) -> Option<u64> {
+    let (number, factor) = match input.last()?.to_ascii_lowercase() {
+        b'k' => (&input[..input.len() - 1], 1024),
+        b'm' => (&input[..input.len() - 1], 1024 * 1024),
+        b'g' => (&input[..input.len() - 1], 1024 * 1024 * 1024),
+        _ => (input, 1),
+    };
+    number.to_str().ok()?.parse::<u64>().ok()?.checked_mul(factor)
+}
@@ -1,6 +1,9 @@
 mod arguments;
 pub use arguments::Arguments;
 
+mod filter;
+pub use filter::Filter;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod delegate;
@@ -412,5 +412,87 @@ mod arguments {
                     .as_bstr()
             )
         }
+
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn filter() {
+            let mut out = Vec::new();
+            let mut t = transport(&mut out, false);
+            let mut arguments = arguments_v2(["filter"].iter().copied());
+            assert!(arguments.can_use_filter());
+
+            arguments.filter(&crate::fetch::Filter::BlobNone.to_string());
+            arguments.want(id("7b333369de1221f9bfbbe03a3a13e9a09bc1c907"));
+            arguments.send(&mut t, true).await.expect("sending to buffer to work");
+            assert_eq!(
+                out.as_bstr(),
+                b"0012command=fetch
+0001000ethin-pack
+000eofs-delta
+0015filter blob:none
+0032want 7b333369de1221f9bfbbe03a3a13e9a09bc1c907
+0009done
+0000"
+                    .as_bstr()
+            )
+        }
+    }
+}
+
+mod filter {
+    use crate::fetch::Filter;
+
+    #[test]
+    fn round_trips_through_its_string_representation() {
+        for (spec, expected) in [
+            ("blob:none", Filter::BlobNone),
+            ("blob:limit=1024", Filter::BlobLimit(1024)),
+            ("tree:0", Filter::Tree(0)),
+            ("tree:3", Filter::Tree(3)),
+            (
+                "sparse:oid=7b333369de1221f9bfbbe03a3a13e9a09bc1c907",
+                Filter::SparseOid(gix_hash::ObjectId::from_hex(b"7b333369de1221f9bfbbe03a3a13e9a09bc1c907").unwrap()),
+            ),
+        ] {
+            let actual = Filter::from_bytes(spec.as_bytes()).expect("valid");
+            assert_eq!(actual, expected);
+            assert_eq!(actual.to_string(), spec);
+        }
+    }
+
+    #[test]
+    fn blob_limits_support_units_like_git() {
+        for (spec, expected) in [
+            ("blob:limit=0", 0),
+            ("blob:limit=1k", 1024),
+            ("blob:limit=2M", 2 * 1024 * 1024),
+            ("blob:limit=1g", 1024 * 1024 * 1024),
+        ] {
+            assert_eq!(
+                Filter::from_bytes(spec.as_bytes()),
+                Some(Filter::BlobLimit(expected)),
+                "{spec}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_or_unsupported_specs_are_rejected() {
+        for spec in [
+            "",
+            "blob:nothing",
+            "blob:limit=",
+            "blob:limit=k",
+            "blob:limit=-1",
+            "blob:limit=1t",
+            "blob:limit=99999999999999999999g",
+            "tree:",
+            "tree:-1",
+            "sparse:oid=",
+            "sparse:oid=main:path",
+            "sparse:path=file",
+            "combine:blob:none+tree:0",
+        ] {
+            assert_eq!(Filter::from_bytes(spec.as_bytes()), None, "{spec:?}");
+        }
     }
 }
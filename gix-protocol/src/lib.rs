@@ -61,6 +61,13 @@ pub mod ls_refs;
 #[cfg(any(feature = "blocking-client", feature = "async-client"))]
 pub use ls_refs::function::ls_refs;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub mod push;
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub use push::function::push;
+
 mod util;
 pub use util::agent;
 #[cfg(any(feature = "blocking-client", feature = "async-client"))]
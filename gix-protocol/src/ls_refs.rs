@@ -54,6 +54,10 @@ pub(crate) mod function {
     /// Invoke an ls-refs V2 command on `transport`, which requires a prior handshake that yielded
     /// server `capabilities`. `prepare_ls_refs(capabilities, arguments, features)` can be used to alter the _ls-refs_. `progress` is used to provide feedback.
     /// Note that `prepare_ls_refs()` is expected to add the `(agent, Some(name))` to the list of `features`.
+    /// The `symrefs` and `peel` arguments are always sent, as is `unborn` if the server supports it, so symbolic refs come with their
+    /// targets, annotated tags with the object they point to, and an unborn `HEAD` with the branch it points to.
+    /// To limit the refs the server sends, add `ref-prefix <prefix>` arguments for each prefix, like `ref-prefix refs/heads/` for all branches
+    /// or `ref-prefix refs/heads/main` for a single one.
     /// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
     #[maybe_async]
     pub async fn ls_refs(
@@ -0,0 +1,358 @@
+use bstr::{BStr, BString, ByteSlice};
+
+mod error {
+    use bstr::BString;
+
+    /// The error returned by [`push()`][crate::push()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Transport(#[from] gix_transport::client::Error),
+        #[error("The server does not support the '{feature}' capability, which is needed to {purpose}")]
+        MissingServerFeature {
+            feature: &'static str,
+            purpose: &'static str,
+        },
+        #[error("A pack is required as not all commands delete references")]
+        MissingPack,
+        #[error("Could not parse the line {line:?} of the status report")]
+        MalformedReportLine { line: BString },
+        #[error("The status report did not start with the status of unpacking")]
+        MissingUnpackStatus,
+    }
+
+    impl gix_transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Io(err) => err.is_spurious(),
+                Error::Transport(err) => err.is_spurious(),
+                _ => false,
+            }
+        }
+    }
+}
+pub use error::Error;
+
+/// An instruction to the server to change the reference `name` from `old` to `new`.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Command {
+    /// The full name of the reference to update, like `refs/heads/main`.
+    pub name: BString,
+    /// The object the reference is expected to point to on the server, or the null hash if it is expected not to exist.
+    ///
+    /// The server refuses to update the reference if it doesn't match, which is how concurrent pushes are detected.
+    pub old: gix_hash::ObjectId,
+    /// The object the reference should point to, or the null hash to delete the reference.
+    pub new: gix_hash::ObjectId,
+}
+
+impl Command {
+    /// Create a command to change `name`, which the server advertised among its `remote_refs`, to point to `new`.
+    /// If `name` isn't advertised, it will be created.
+    ///
+    /// Note that the server doesn't know if the update is forced or not, so by using the advertised value as expected value
+    /// even non-fast-forward updates are accepted if the server allows them.
+    /// It's up to the caller to prevent these if the push isn't forced.
+    pub fn from_remote_refs(
+        name: impl Into<BString>,
+        new: gix_hash::ObjectId,
+        remote_refs: &[crate::handshake::Ref],
+    ) -> Self {
+        let name = name.into();
+        let old = remote_refs
+            .iter()
+            .find_map(|r| {
+                let (remote_name, object, _peeled) = r.unpack();
+                (remote_name == name).then_some(object).flatten()
+            })
+            .map_or_else(|| new.kind().null(), ToOwned::to_owned);
+        Command { name, old, new }
+    }
+
+    /// Return `true` if this command creates a reference that doesn't exist on the server.
+    pub fn is_create(&self) -> bool {
+        self.old.is_null() && !self.new.is_null()
+    }
+
+    /// Return `true` if this command deletes a reference on the server.
+    pub fn is_delete(&self) -> bool {
+        self.new.is_null()
+    }
+}
+
+/// Options for use in [`push()`][crate::push()].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, the server either applies all reference updates or none of them, and fails the whole push
+    /// if a single update can't be performed.
+    ///
+    /// This requires the server to support the `atomic` capability.
+    pub atomic: bool,
+    /// Strings to pass to the hooks on the server, requiring the `push-options` capability unless empty.
+    pub push_options: Vec<BString>,
+    /// If `true`, ask the server not to send progress information, if it supports the `quiet` capability.
+    pub quiet: bool,
+}
+
+/// The status of a single [`Command`] as reported by the server.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Status {
+    /// The reference was updated.
+    Ok,
+    /// The reference was not updated.
+    Rejected {
+        /// The reason for the rejection as given by the server, like `non-fast-forward` or `atomic push failed`.
+        reason: BString,
+    },
+}
+
+/// The status of the update of a reference as reported by the server.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RefStatus {
+    /// The full name of the reference that was meant to be updated.
+    pub name: BString,
+    /// What happened to it.
+    pub status: Status,
+}
+
+/// The report the server sends after receiving a push if the `report-status` capability is supported.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    /// The error message of the server if the pack couldn't be unpacked, or `None` if it was unpacked successfully.
+    pub unpack_error: Option<BString>,
+    /// The status of each reference that was meant to be updated, in the order of the sent commands.
+    pub refs: Vec<RefStatus>,
+}
+
+impl Report {
+    /// Parse a report from its `lines`, each with or without trailing newline, and without the terminating flush packet.
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a BStr>) -> Result<Self, Error> {
+        let malformed = |line: &BStr| Error::MalformedReportLine { line: line.to_owned() };
+        let mut lines = lines
+            .into_iter()
+            .map(|line| line.trim_end_with(|c| c == '\n').as_bstr());
+        let unpack_error = match lines
+            .next()
+            .and_then(|line| line.strip_prefix(b"unpack ").map(ByteSlice::as_bstr))
+        {
+            Some(status) if status == "ok" => None,
+            Some(error) => Some(error.to_owned()),
+            None => return Err(Error::MissingUnpackStatus),
+        };
+        let refs = lines
+            .map(|line| {
+                if let Some(name) = line.strip_prefix(b"ok ") {
+                    Ok(RefStatus {
+                        name: name.into(),
+                        status: Status::Ok,
+                    })
+                } else if let Some(name_and_reason) = line.strip_prefix(b"ng ") {
+                    let (name, reason) = name_and_reason.split_once_str(" ").ok_or_else(|| malformed(line))?;
+                    Ok(RefStatus {
+                        name: name.into(),
+                        status: Status::Rejected { reason: reason.into() },
+                    })
+                } else {
+                    Err(malformed(line))
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Report { unpack_error, refs })
+    }
+
+    /// Return `true` if the pack was unpacked and all references were updated.
+    pub fn is_success(&self) -> bool {
+        self.unpack_error.is_none() && self.refs.iter().all(|r| r.status == Status::Ok)
+    }
+}
+
+/// The source of the pack to send to the server, which is a blocking or async reader depending on the client feature.
+#[cfg(feature = "blocking-client")]
+pub type PackRead<'a> = dyn std::io::Read + 'a;
+/// The source of the pack to send to the server, which is a blocking or async reader depending on the client feature.
+#[cfg(feature = "async-client")]
+pub type PackRead<'a> = dyn futures_io::AsyncRead + Unpin + 'a;
+
+pub(crate) mod function {
+    use std::borrow::Cow;
+
+    use bstr::{BString, ByteSlice};
+    #[cfg(feature = "async-client")]
+    use futures_lite::io::{copy, AsyncReadExt, AsyncWriteExt};
+    use gix_features::progress::NestedProgress;
+    use gix_transport::client::{Capabilities, MessageKind, Transport, WriteMode};
+    use maybe_async::maybe_async;
+    #[cfg(feature = "blocking-client")]
+    use std::io::{copy, Read, Write};
+
+    use super::{Command, Error, Options, PackRead, Report};
+    use crate::indicate_end_of_interaction;
+
+    /// Ask the server behind `transport` to apply the reference update `commands` after sending it `pack`,
+    /// which requires a prior handshake with the `receive-pack` service that yielded the server `capabilities`.
+    ///
+    /// * `pack` must contain all objects the server needs to perform the updates. It may be a thin pack whose deltas refer
+    ///    to objects that the server advertised, and it may only use offset deltas if the server supports `ofs-delta`.
+    ///    It must be `Some` unless all `commands` delete references, in which case it will be ignored.
+    /// * `options` control the behaviour of the server, like whether all updates should apply [atomically](Options::atomic).
+    /// * `progress` is used to provide feedback, including the progress messages sent by the server.
+    /// * `agent` is the name of the git client to present as `agent`, like `"my-app (v2.0)"`.
+    /// * If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
+    ///
+    /// The server is asked to `report-status` if it can, in which case the [report](Report) of unpacking the pack and updating each
+    /// reference is returned. Note that an unsuccessful report isn't an error, so [`Report::is_success()`] should be checked.
+    /// Without support for `report-status`, `None` is returned once the server received all data.
+    ///
+    /// Deleting references requires the `delete-refs` capability, and nothing but a flush packet is sent if there are no `commands`.
+    ///
+    /// ### Limitations
+    ///
+    /// This function only implements the protocol. As it has no access to an object database, it doesn't select the objects
+    /// to send or create the pack, which can be done with `gix-pack` by counting the objects reachable from the new
+    /// tips but not from what the server advertised, and writing the resulting entries.
+    /// Client-side hooks like `pre-push` aren't run either.
+    #[allow(clippy::too_many_arguments)]
+    #[maybe_async]
+    pub async fn push<P>(
+        mut transport: impl Transport,
+        capabilities: &Capabilities,
+        commands: &[Command],
+        pack: Option<&mut PackRead<'_>>,
+        options: &Options,
+        progress: &mut P,
+        agent: impl Into<String>,
+        trace: bool,
+    ) -> Result<Option<Report>, Error>
+    where
+        P: NestedProgress,
+        P::SubProgress: 'static,
+    {
+        let _span = gix_features::trace::detail!("gix_protocol::push()", capabilities = ?capabilities);
+        if commands.is_empty() {
+            indicate_end_of_interaction(transport, trace).await?;
+            return Ok(None);
+        }
+
+        let mut features = Vec::<(&str, Option<Cow<'_, str>>)>::new();
+        let report_status = capabilities.contains("report-status");
+        if report_status {
+            features.push(("report-status", None));
+        }
+        let side_band = capabilities.contains("side-band-64k");
+        if side_band {
+            features.push(("side-band-64k", None));
+        }
+        if options.quiet && capabilities.contains("quiet") {
+            features.push(("quiet", None));
+        }
+        let needs_pack = commands.iter().any(|cmd| !cmd.is_delete());
+        for (enabled, feature, purpose) in [
+            (
+                commands.iter().any(Command::is_delete),
+                "delete-refs",
+                "delete references",
+            ),
+            (options.atomic, "atomic", "push atomically"),
+            (!options.push_options.is_empty(), "push-options", "send push options"),
+        ] {
+            if !enabled {
+                continue;
+            }
+            if !capabilities.contains(feature) {
+                indicate_end_of_interaction(transport, trace).await?;
+                return Err(Error::MissingServerFeature { feature, purpose });
+            }
+            if feature != "delete-refs" {
+                features.push((feature, None));
+            }
+        }
+        if let Some(object_format) = capabilities.capability("object-format").and_then(|c| c.value()) {
+            features.push(("object-format", Some(object_format.to_str_lossy().into_owned().into())));
+        }
+        features.push(("agent", Some(crate::agent(agent).into())));
+        let pack = match (needs_pack, pack) {
+            (true, None) => {
+                indicate_end_of_interaction(transport, trace).await?;
+                return Err(Error::MissingPack);
+            }
+            (true, Some(pack)) => Some(pack),
+            (false, _) => None,
+        };
+
+        progress.set_name("send commands".into());
+        let mut writer = transport.request(WriteMode::Binary, MessageKind::Flush, trace)?;
+        for (idx, command) in commands.iter().enumerate() {
+            let mut line = BString::from(format!("{} {} {}", command.old, command.new, command.name));
+            if idx == 0 {
+                line.push(0);
+                for (name, value) in &features {
+                    line.push(b' ');
+                    line.extend_from_slice(name.as_bytes());
+                    if let Some(value) = value {
+                        line.push(b'=');
+                        line.extend_from_slice(value.as_bytes());
+                    }
+                }
+            }
+            writer.write_all(&line).await?;
+        }
+        writer.write_message(MessageKind::Flush).await?;
+        if !options.push_options.is_empty() {
+            for push_option in &options.push_options {
+                writer.write_all(push_option).await?;
+            }
+            writer.write_message(MessageKind::Flush).await?;
+        }
+
+        let (mut writer, mut reader) = writer.into_parts();
+        if let Some(pack) = pack {
+            progress.set_name("send pack".into());
+            copy(pack, &mut writer).await?;
+        }
+        writer.flush().await?;
+        drop(writer);
+
+        if side_band {
+            reader.set_progress_handler(Some(Box::new({
+                let mut remote_progress = progress.add_child("remote");
+                move |is_err: bool, data: &[u8]| {
+                    crate::RemoteProgress::translate_to_progress(is_err, data, &mut remote_progress);
+                    gix_transport::packetline::read::ProgressAction::Continue
+                }
+            }) as gix_transport::client::HandleProgress<'_>));
+        }
+        let mut response = Vec::new();
+        reader.read_to_end(&mut response).await?;
+        if !report_status {
+            return Ok(None);
+        }
+
+        let report = if side_band {
+            // The report is encoded as packet lines within the data band.
+            let mut lines = Vec::new();
+            let mut data = response.as_slice();
+            while let gix_transport::packetline::decode::Stream::Complete { line, bytes_consumed } =
+                gix_transport::packetline::decode::streaming(data).map_err(|err| Error::MalformedReportLine {
+                    line: err.to_string().into(),
+                })?
+            {
+                data = &data[bytes_consumed..];
+                match line.as_bstr() {
+                    Some(line) => lines.push(line),
+                    None => break,
+                }
+            }
+            Report::from_lines(lines)?
+        } else {
+            Report::from_lines(response.lines_with_terminator().map(ByteSlice::as_bstr))?
+        };
+        Ok(Some(report))
+    }
+}
@@ -109,6 +109,79 @@ async fn ls_remote() -> crate::Result {
     Ok(())
 }
 
+#[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+async fn ls_refs_with_ref_prefixes() -> crate::Result {
+    let mut transport = transport(
+        Vec::new(),
+        "v2/ls-refs-prefix.response",
+        Protocol::V2,
+        gix_transport::client::git::ConnectMode::Daemon,
+    );
+    let outcome = gix_protocol::handshake(
+        &mut transport,
+        gix_transport::Service::UploadPack,
+        helper_unused,
+        Vec::new(),
+        &mut progress::Discard,
+    )
+    .await?;
+    let refs = gix_protocol::ls_refs(
+        &mut transport,
+        &outcome.capabilities,
+        |_capabilities, arguments, features| {
+            features.push(("agent", Some(gix_protocol::agent("agent").into())));
+            for prefix in ["HEAD", "refs/heads/", "refs/tags/v1"] {
+                arguments.push(format!("ref-prefix {prefix}").into());
+            }
+            Ok(ls_refs::Action::Continue)
+        },
+        &mut progress::Discard,
+        false,
+    )
+    .await?;
+
+    assert_eq!(
+        refs,
+        vec![
+            handshake::Ref::Unborn {
+                full_ref_name: "HEAD".into(),
+                target: "refs/heads/main".into()
+            },
+            handshake::Ref::Direct {
+                full_ref_name: "refs/heads/dev".into(),
+                object: oid("808e50d724f604f69ab93c6da2919c014667bedb")
+            },
+            handshake::Ref::Symbolic {
+                full_ref_name: "refs/heads/tag-alias".into(),
+                target: "refs/tags/v1".into(),
+                tag: Some(oid("4c3f4cce493922e029caa579ba49cff2bf8b4a7a")),
+                object: oid("808e50d724f604f69ab93c6da2919c014667bedb")
+            },
+            handshake::Ref::Peeled {
+                full_ref_name: "refs/tags/v1".into(),
+                tag: oid("4c3f4cce493922e029caa579ba49cff2bf8b4a7a"),
+                object: oid("808e50d724f604f69ab93c6da2919c014667bedb")
+            },
+        ]
+    );
+    assert_eq!(
+        transport.into_inner().1.as_bstr(),
+        "002fgit-upload-pack does/not/matter\0\0version=2\00014command=ls-refs
+0014agent=git/agent
+0001000csymrefs
+0009peel
+000bunborn
+0014ref-prefix HEAD
+001bref-prefix refs/heads/
+001cref-prefix refs/tags/v1
+0000"
+            .as_bytes()
+            .as_bstr(),
+        "symrefs, peel and unborn are requested along with the refs matching the given prefixes only"
+    );
+    Ok(())
+}
+
 #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
 async fn ls_remote_abort_in_prep_ls_refs() -> crate::Result {
     let out = Vec::new();
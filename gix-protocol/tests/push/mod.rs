@@ -0,0 +1,272 @@
+use bstr::ByteSlice;
+use gix_features::progress;
+use gix_protocol::{
+    handshake,
+    push::{self, Command, RefStatus, Report, Status},
+};
+use gix_transport::{client::git::ConnectMode, Protocol, Service};
+
+use crate::fetch::{helper_unused, oid, transport};
+
+#[cfg(feature = "blocking-client")]
+type Pack = std::io::Cursor<Vec<u8>>;
+#[cfg(feature = "async-client")]
+type Pack = futures_lite::io::Cursor<Vec<u8>>;
+
+fn command(name: &str, old: &str, new: &str) -> Command {
+    Command {
+        name: name.into(),
+        old: oid(old),
+        new: oid(new),
+    }
+}
+
+const NULL: &str = "0000000000000000000000000000000000000000";
+const MAIN: &str = "808e50d724f604f69ab93c6da2919c014667bedb";
+const DEV: &str = "7c09ba0c4c3680af369bda4fc8e3c58d3fccdc76";
+const NEW: &str = "32690d87d3943c7c0dda81246d0cde344ca7e633";
+
+#[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+async fn updates_creations_and_deletions_with_sideband_report() -> crate::Result {
+    let mut transport = transport(Vec::new(), "v1/push.response", Protocol::V1, ConnectMode::Daemon);
+    let outcome = gix_protocol::handshake(
+        &mut transport,
+        Service::ReceivePack,
+        helper_unused,
+        Vec::new(),
+        &mut progress::Discard,
+    )
+    .await?;
+    let refs = outcome.refs.expect("V1 sends refs right away");
+    assert_eq!(refs.len(), 3);
+
+    let commands = vec![
+        Command::from_remote_refs("refs/heads/main", oid(NEW), &refs),
+        command("refs/heads/dev", DEV, NEW),
+        Command::from_remote_refs("refs/heads/stale", oid(NULL), &refs),
+        Command::from_remote_refs("refs/heads/new", oid(NEW), &refs),
+    ];
+    assert_eq!(commands[0], command("refs/heads/main", MAIN, NEW));
+    assert!(commands[2].is_delete());
+    assert!(commands[3].is_create());
+
+    let mut pack = Pack::new(b"PACK and more".to_vec());
+    let report = gix_protocol::push(
+        &mut transport,
+        &outcome.capabilities,
+        &commands,
+        Some(&mut pack),
+        &push::Options {
+            atomic: false,
+            push_options: vec!["ci.skip".into()],
+            quiet: true,
+        },
+        &mut progress::Discard,
+        "agent",
+        false,
+    )
+    .await?
+    .expect("report-status is supported");
+
+    assert_eq!(
+        report,
+        Report {
+            unpack_error: None,
+            refs: vec![
+                RefStatus {
+                    name: "refs/heads/main".into(),
+                    status: Status::Ok
+                },
+                RefStatus {
+                    name: "refs/heads/dev".into(),
+                    status: Status::Rejected {
+                        reason: "non-fast-forward".into()
+                    }
+                },
+                RefStatus {
+                    name: "refs/heads/stale".into(),
+                    status: Status::Ok
+                },
+            ]
+        },
+        "nested packet lines are decoded even if they are split across data bands"
+    );
+    assert!(!report.is_success());
+    assert_eq!(
+        transport.into_inner().1.as_bstr(),
+        format!(
+            "0025git-receive-pack does/not/matter\0\
+00b8{MAIN} {NEW} refs/heads/main\0 report-status side-band-64k quiet push-options object-format=sha1 agent=git/agent\
+0064{DEV} {NEW} refs/heads/dev\
+0066{DEV} {NULL} refs/heads/stale\
+0064{NULL} {NEW} refs/heads/new\
+0000000bci.skip0000PACK and more"
+        )
+        .as_bytes()
+        .as_bstr()
+    );
+    Ok(())
+}
+
+#[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+async fn report_without_sideband() -> crate::Result {
+    let mut transport = transport(
+        Vec::new(),
+        "v1/push-without-sideband.response",
+        Protocol::V1,
+        ConnectMode::Daemon,
+    );
+    let outcome = gix_protocol::handshake(
+        &mut transport,
+        Service::ReceivePack,
+        helper_unused,
+        Vec::new(),
+        &mut progress::Discard,
+    )
+    .await?;
+    let mut pack = Pack::new(b"PACK".to_vec());
+    let report = gix_protocol::push(
+        &mut transport,
+        &outcome.capabilities,
+        &[command("refs/heads/main", MAIN, NEW)],
+        Some(&mut pack),
+        &Default::default(),
+        &mut progress::Discard,
+        "agent",
+        false,
+    )
+    .await?
+    .expect("report-status is supported");
+    assert_eq!(
+        report,
+        Report {
+            unpack_error: Some("index-pack abnormal exit".into()),
+            refs: vec![RefStatus {
+                name: "refs/heads/main".into(),
+                status: Status::Rejected {
+                    reason: "unpacker error".into()
+                }
+            }]
+        }
+    );
+    assert_eq!(
+        transport.into_inner().1.as_bstr(),
+        format!(
+            "0025git-receive-pack does/not/matter\0\
+0084{MAIN} {NEW} refs/heads/main\0 report-status agent=git/agent0000PACK"
+        )
+        .as_bytes()
+        .as_bstr()
+    );
+    Ok(())
+}
+
+#[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+async fn unsupported_features_and_missing_packs_fail_before_sending_commands() -> crate::Result {
+    for (commands, options, expected_feature) in [
+        (
+            vec![command("refs/heads/main", MAIN, NEW)],
+            push::Options {
+                atomic: true,
+                ..Default::default()
+            },
+            Some("atomic"),
+        ),
+        (
+            vec![command("refs/heads/main", MAIN, NEW)],
+            push::Options {
+                push_options: vec!["option".into()],
+                ..Default::default()
+            },
+            Some("push-options"),
+        ),
+        (vec![command("refs/heads/main", MAIN, NEW)], Default::default(), None),
+    ] {
+        let mut transport = transport(
+            Vec::new(),
+            "v1/push-without-sideband.response",
+            Protocol::V1,
+            ConnectMode::Daemon,
+        );
+        let outcome = gix_protocol::handshake(
+            &mut transport,
+            Service::ReceivePack,
+            helper_unused,
+            Vec::new(),
+            &mut progress::Discard,
+        )
+        .await?;
+        let err = gix_protocol::push(
+            &mut transport,
+            &outcome.capabilities,
+            &commands,
+            None,
+            &options,
+            &mut progress::Discard,
+            "agent",
+            false,
+        )
+        .await
+        .expect_err("fails");
+        match expected_feature {
+            Some(expected) => {
+                assert!(matches!(err, push::Error::MissingServerFeature { feature, .. } if feature == expected))
+            }
+            None => assert!(matches!(err, push::Error::MissingPack)),
+        }
+        assert_eq!(
+            transport.into_inner().1.as_bstr(),
+            "0025git-receive-pack does/not/matter\x000000",
+            "the server is told that nothing is pushed"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn report_from_lines() -> crate::Result {
+    assert_eq!(
+        Report::from_lines(
+            [
+                "unpack ok\n",
+                "ok refs/heads/main",
+                "ng refs/tags/v1 atomic push failed\n"
+            ]
+            .map(Into::into)
+        )?,
+        Report {
+            unpack_error: None,
+            refs: vec![
+                RefStatus {
+                    name: "refs/heads/main".into(),
+                    status: Status::Ok
+                },
+                RefStatus {
+                    name: "refs/tags/v1".into(),
+                    status: Status::Rejected {
+                        reason: "atomic push failed".into()
+                    }
+                }
+            ]
+        }
+    );
+    assert!(Report::from_lines(["unpack ok"].map(Into::into))?.is_success());
+    assert!(matches!(
+        Report::from_lines(None),
+        Err(push::Error::MissingUnpackStatus)
+    ));
+    assert!(matches!(
+        Report::from_lines(["unpack ok", "ng refs/heads/main"].map(Into::into)),
+        Err(push::Error::MalformedReportLine { line }) if line == "ng refs/heads/main"
+    ));
+    Ok(())
+}
+
+#[test]
+fn commands_for_unborn_refs_are_creations() {
+    let refs = vec![handshake::Ref::Unborn {
+        full_ref_name: "HEAD".into(),
+        target: "refs/heads/main".into(),
+    }];
+    assert!(Command::from_remote_refs("HEAD", oid(NEW), &refs).is_create());
+}
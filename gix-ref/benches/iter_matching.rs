@@ -0,0 +1,46 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use gix_object::bstr::ByteSlice;
+
+/// A sorted `packed-refs` file with `num_remotes` remotes of a thousand branches each, along with their tags.
+fn packed_refs(num_remotes: usize) -> gix_ref::packed::Buffer {
+    let mut names = Vec::new();
+    for remote in 0..num_remotes {
+        for branch in 0..1000 {
+            names.push(format!("refs/remotes/origin-{remote:05}/branch-{branch:04}"));
+            names.push(format!("refs/tags/v{remote}.{branch}"));
+        }
+    }
+    names.sort();
+    let mut buf = b"# pack-refs with: peeled fully-peeled sorted \n".to_vec();
+    for name in names {
+        buf.extend_from_slice(format!("{} {name}\n", "1".repeat(40)).as_bytes());
+    }
+    gix_ref::packed::Buffer::from_bytes(&buf).expect("valid packed-refs")
+}
+
+fn iter_matching(c: &mut Criterion) {
+    let mut group = c.benchmark_group("packed::Buffer::iter_matching(refs/remotes/origin-00001/*)");
+    for num_remotes in [10, 100] {
+        let packed = packed_refs(num_remotes);
+        let pattern = "refs/remotes/origin-00001/*".as_bytes().as_bstr();
+        group.bench_with_input(BenchmarkId::new("glob", num_remotes * 2000), &packed, |b, packed| {
+            b.iter(|| black_box(packed.iter_matching(pattern).unwrap().count()))
+        });
+        group.bench_with_input(BenchmarkId::new("filter", num_remotes * 2000), &packed, |b, packed| {
+            b.iter(|| {
+                black_box(
+                    packed
+                        .iter()
+                        .unwrap()
+                        .filter_map(Result::ok)
+                        .filter(|r| r.name.as_bstr().starts_with(b"refs/remotes/origin-00001/"))
+                        .count(),
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, iter_matching);
+criterion_main!(benches);
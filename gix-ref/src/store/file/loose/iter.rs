@@ -1,22 +1,35 @@
 use std::path::{Path, PathBuf};
 
 use gix_features::fs::walkdir::DirEntryIter;
-use gix_object::bstr::ByteSlice;
+use gix_object::bstr::{BStr, ByteSlice};
 
-use crate::{file::iter::LooseThenPacked, store_impl::file, BString, FullName};
+use crate::{
+    file::iter::LooseThenPacked,
+    store_impl::{file, packed},
+    BString, FullName,
+};
 
 /// An iterator over all valid loose reference paths as seen from a particular base directory.
 pub(in crate::store_impl::file) struct SortedLoosePaths {
     pub(crate) base: PathBuf,
     filename_prefix: Option<BString>,
+    /// If set, only references whose full name matches this glob pattern are returned.
+    pattern: Option<BString>,
     file_walk: Option<DirEntryIter>,
 }
 
 impl SortedLoosePaths {
-    pub fn at(path: &Path, base: PathBuf, filename_prefix: Option<BString>, precompose_unicode: bool) -> Self {
+    pub fn at(
+        path: &Path,
+        base: PathBuf,
+        filename_prefix: Option<BString>,
+        pattern: Option<BString>,
+        precompose_unicode: bool,
+    ) -> Self {
         SortedLoosePaths {
             base,
             filename_prefix,
+            pattern,
             file_walk: path.is_dir().then(|| {
                 // serial iteration as we expect most refs in packed-refs anyway.
                 gix_features::fs::walkdir_sorted_new(
@@ -66,6 +79,11 @@ impl Iterator for SortedLoosePaths {
                         Err(_) => continue, // TODO: silently skipping ill-formed UTF-8 on windows here, maybe there are better ways?
                     };
 
+                    if let Some(pattern) = self.pattern.as_ref() {
+                        if !packed::iter::matches_pattern(pattern.as_bstr(), full_name.as_bstr()) {
+                            continue;
+                        }
+                    }
                     if gix_validate::reference::name_partial(full_name.as_bstr()).is_ok() {
                         let name = FullName(full_name);
                         return Some(Ok((full_path, name)));
@@ -96,4 +114,11 @@ impl file::Store {
     pub fn loose_iter_prefixed(&self, prefix: &Path) -> std::io::Result<LooseThenPacked<'_, '_>> {
         self.iter_prefixed_packed(prefix, None)
     }
+
+    /// Return an iterator over all loose references whose name matches the glob `pattern`, like `refs/tags/v*`.
+    ///
+    /// Otherwise it's similar to [`loose_iter()`][file::Store::loose_iter()].
+    pub fn loose_iter_matching(&self, pattern: &BStr) -> std::io::Result<LooseThenPacked<'_, '_>> {
+        self.iter_matching_packed(pattern, None)
+    }
 }
@@ -231,6 +231,268 @@ pub mod create_or_update {
     use crate::FullNameRef;
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod expire {
+    use std::{io::Write, path::PathBuf};
+
+    use gix_hash::oid;
+    use gix_object::bstr::ByteSlice;
+
+    use crate::{
+        store_impl::{file, file::log},
+        FullNameRef,
+    };
+
+    /// Options for use in [`file::Store::reflog_expire()`].
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+    pub struct Options {
+        /// Remove all entries older than this time, like `gc.reflogExpire` does.
+        pub expire: Option<gix_actor::date::SecondsSinceUnixEpoch>,
+        /// Remove all entries older than this time if the previous or the new object of the entry isn't reachable
+        /// from the tip of the reference anymore, like `gc.reflogExpireUnreachable` does.
+        ///
+        /// This is typically more recent than [`expire`](Self::expire).
+        pub expire_unreachable: Option<gix_actor::date::SecondsSinceUnixEpoch>,
+        /// Keep at most this amount of entries, removing the oldest ones that remain after expiration.
+        pub max_entries: Option<usize>,
+    }
+
+    /// The outcome of [`file::Store::reflog_expire()`].
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+    pub struct Outcome {
+        /// The amount of entries that remain in the reflog.
+        pub num_kept: usize,
+        /// The amount of entries that were removed from the reflog.
+        pub num_removed: usize,
+    }
+
+    impl file::Store {
+        /// Remove entries from the reflog of the reference `name` according to `options`, and return how many entries were kept
+        /// and removed, or `None` if there is no reflog.
+        ///
+        /// `is_reachable(id)` is called with the non-null previous and new object ids of entries which are candidates for
+        /// [unreachable expiration](Options::expire_unreachable), and must return `true` if `id` is reachable from the
+        /// tip of the reference.
+        ///
+        /// The reference is locked with `lock_fail_mode` while its reflog is rewritten, so it's safe to use while
+        /// other processes make changes to it. Note that the reflog is kept even if all entries are removed.
+        pub fn reflog_expire<'a, Name, E>(
+            &self,
+            name: Name,
+            options: Options,
+            lock_fail_mode: gix_lock::acquire::Fail,
+            mut is_reachable: impl FnMut(&oid) -> bool,
+        ) -> Result<Option<Outcome>, Error>
+        where
+            Name: TryInto<&'a FullNameRef, Error = E>,
+            crate::name::Error: From<E>,
+        {
+            let name: &FullNameRef = name.try_into().map_err(|err| Error::RefnameValidation(err.into()))?;
+            let (base, relative_path) = self.reference_path_with_base(name);
+            let _reference_lock = gix_lock::Marker::acquire_to_hold_resource(
+                base.join(relative_path.as_ref()),
+                lock_fail_mode,
+                Some(base.clone().into_owned()),
+            )?;
+
+            let reflog_path = self.reflog_path(name);
+            let buf = match std::fs::read(&reflog_path) {
+                Ok(buf) => buf,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(err) => {
+                    return Err(Error::Io {
+                        source: err,
+                        reflog_path,
+                    })
+                }
+            };
+
+            let mut kept = Vec::new();
+            for (line_idx, raw_line) in buf.lines_with_terminator().enumerate() {
+                let line = raw_line.strip_suffix(b"\n").unwrap_or(raw_line);
+                let line = log::LineRef::from_bytes(line).map_err(|err| Error::Decode {
+                    source: log::iter::decode::Error::new(err, log::iter::decode::LineNumber::FromStart(line_idx)),
+                })?;
+                let time = line.signature.time.seconds;
+                let expired = matches!(options.expire, Some(cutoff) if time < cutoff)
+                    || (matches!(options.expire_unreachable, Some(cutoff) if time < cutoff)
+                        && [line.previous_oid(), line.new_oid()]
+                            .iter()
+                            .any(|id| !id.is_null() && !is_reachable(id)));
+                if !expired {
+                    kept.push(raw_line);
+                }
+            }
+            if let Some(max_entries) = options.max_entries {
+                let num_too_many = kept.len().saturating_sub(max_entries);
+                kept.drain(..num_too_many);
+            }
+
+            let num_lines = buf.lines_with_terminator().count();
+            let outcome = Outcome {
+                num_kept: kept.len(),
+                num_removed: num_lines - kept.len(),
+            };
+            if outcome.num_removed != 0 {
+                let mut lock = gix_lock::File::acquire_to_update_resource(&reflog_path, lock_fail_mode, None)?;
+                for line in kept {
+                    lock.write_all(line).map_err(|err| Error::Io {
+                        source: err,
+                        reflog_path: reflog_path.clone(),
+                    })?;
+                }
+                lock.commit().map_err(|err| Error::Io {
+                    source: err.error,
+                    reflog_path: reflog_path.clone(),
+                })?;
+            }
+            Ok(Some(outcome))
+        }
+    }
+
+    /// The error returned by [`file::Store::reflog_expire()`].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The reflog name or path is not a valid ref name")]
+        RefnameValidation(#[from] crate::name::Error),
+        #[error("The reference or its reflog could not be locked")]
+        LockAcquire(#[from] gix_lock::acquire::Error),
+        #[error("The reflog at {reflog_path:?} could not be read or written")]
+        Io {
+            source: std::io::Error,
+            reflog_path: PathBuf,
+        },
+        #[error(transparent)]
+        Decode { source: log::iter::decode::Error },
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod delete {
+    use std::{io::Write, path::PathBuf};
+
+    use gix_object::bstr::ByteSlice;
+
+    use crate::{
+        log::Line,
+        store_impl::{file, file::log},
+        FullNameRef,
+    };
+
+    /// Options for use in [`file::Store::reflog_delete()`].
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+    pub struct Options {
+        /// If `true`, set the previous object id of the entry that followed the deleted one to the previous object id
+        /// of the deleted entry, so the reflog remains a consistent chain of changes, like `--rewrite` does.
+        pub rewrite: bool,
+        /// If `true` and the most recent entry was deleted, point the reference to the new object id of the entry that
+        /// is now the most recent one, like `--updateref` does.
+        ///
+        /// Note that the reference is left as is if no entry remains.
+        pub update_ref: bool,
+    }
+
+    impl file::Store {
+        /// Delete the entry at `index` from the reflog of the reference `name`, with `0` being the most recent entry,
+        /// similar to `git reflog delete <name>@{<index>}`, and return it, or `None` if there is no such entry.
+        ///
+        /// The reference is locked with `lock_fail_mode` while its reflog is rewritten, and it's updated according to
+        /// `options`. Note that the reflog is kept even if it has no entries anymore.
+        pub fn reflog_delete<'a, Name, E>(
+            &self,
+            name: Name,
+            index: usize,
+            options: Options,
+            lock_fail_mode: gix_lock::acquire::Fail,
+        ) -> Result<Option<Line>, Error>
+        where
+            Name: TryInto<&'a FullNameRef, Error = E>,
+            crate::name::Error: From<E>,
+        {
+            let name: &FullNameRef = name.try_into().map_err(|err| Error::RefnameValidation(err.into()))?;
+            let (base, relative_path) = self.reference_path_with_base(name);
+            let reference_path = base.join(relative_path.as_ref());
+            let mut reference_lock = gix_lock::File::acquire_to_update_resource(
+                &reference_path,
+                lock_fail_mode,
+                Some(base.clone().into_owned()),
+            )?;
+
+            let reflog_path = self.reflog_path(name);
+            let io_err = |source: std::io::Error, path: &PathBuf| Error::Io {
+                source,
+                path: path.clone(),
+            };
+            let buf = match std::fs::read(&reflog_path) {
+                Ok(buf) => buf,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(err) => return Err(io_err(err, &reflog_path)),
+            };
+
+            let mut lines = buf
+                .lines_with_terminator()
+                .enumerate()
+                .map(|(line_idx, raw_line)| {
+                    let line = raw_line.strip_suffix(b"\n").unwrap_or(raw_line);
+                    log::LineRef::from_bytes(line)
+                        .map(|line| (raw_line, line))
+                        .map_err(|err| Error::Decode {
+                            source: log::iter::decode::Error::new(
+                                err,
+                                log::iter::decode::LineNumber::FromStart(line_idx),
+                            ),
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let Some(position) = lines.len().checked_sub(index + 1) else {
+                return Ok(None);
+            };
+            let (_, deleted) = lines.remove(position);
+
+            let mut lock = gix_lock::File::acquire_to_update_resource(&reflog_path, lock_fail_mode, None)?;
+            for (idx, (raw_line, line)) in lines.iter().enumerate() {
+                let res = if options.rewrite && idx == position {
+                    let mut line = line.to_owned();
+                    line.previous_oid = deleted.previous_oid();
+                    line.write_to(&mut lock)
+                } else {
+                    lock.write_all(raw_line)
+                };
+                res.map_err(|err| io_err(err, &reflog_path))?;
+            }
+            lock.commit().map_err(|err| io_err(err.error, &reflog_path))?;
+
+            match lines.last() {
+                Some((_, most_recent)) if options.update_ref && index == 0 => {
+                    writeln!(reference_lock, "{}", most_recent.new_oid).map_err(|err| io_err(err, &reference_path))?;
+                    reference_lock
+                        .commit()
+                        .map_err(|err| io_err(err.error, &reference_path))?;
+                }
+                _ => drop(reference_lock),
+            }
+            Ok(Some(deleted.to_owned()))
+        }
+    }
+
+    /// The error returned by [`file::Store::reflog_delete()`].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The reflog name or path is not a valid ref name")]
+        RefnameValidation(#[from] crate::name::Error),
+        #[error("The reference or its reflog could not be locked")]
+        LockAcquire(#[from] gix_lock::acquire::Error),
+        #[error("The reference or reflog at {path:?} could not be read or written")]
+        Io { source: std::io::Error, path: PathBuf },
+        #[error(transparent)]
+        Decode { source: log::iter::decode::Error },
+    }
+}
+
 mod error {
     /// The error returned by [`crate::file::Store::reflog_iter()`].
     #[derive(Debug, thiserror::Error)]
@@ -130,5 +130,9 @@ pub mod transaction;
 #[allow(clippy::empty_docs)]
 pub mod packed;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod pack;
+
 mod raw_ext;
 pub use raw_ext::ReferenceExt;
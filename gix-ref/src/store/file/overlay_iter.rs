@@ -6,10 +6,12 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use gix_object::bstr::ByteSlice;
+
 use crate::{
     file::{loose, loose::iter::SortedLoosePaths, path_to_name},
     store_impl::{file, packed},
-    BString, FullName, Namespace, Reference,
+    BStr, BString, FullName, Namespace, Reference,
 };
 
 /// An iterator stepping through sorted input of loose references and packed references, preferring loose refs over otherwise
@@ -202,6 +204,15 @@ impl<'s> Platform<'s> {
         self.store
             .iter_prefixed_packed(prefix, self.packed.as_ref().map(|b| &***b))
     }
+
+    /// As [`iter(…)`][file::Store::iter()], but only returns references whose full name matches the glob `pattern`,
+    /// i.e. "refs/tags/v*".
+    ///
+    /// See [`iter_matching_packed()`][file::Store::iter_matching_packed()] for details.
+    pub fn matching(&self, pattern: &BStr) -> std::io::Result<LooseThenPacked<'_, '_>> {
+        self.store
+            .iter_matching_packed(pattern, self.packed.as_ref().map(|b| &***b))
+    }
 }
 
 impl file::Store {
@@ -261,48 +272,37 @@ impl<'a> IterInfo<'a> {
         }
     }
 
-    fn into_iter(self) -> Peekable<SortedLoosePaths> {
+    fn into_iter(self, pattern: Option<&BStr>) -> Peekable<SortedLoosePaths> {
+        let pattern = pattern.map(ToOwned::to_owned);
         match self {
             IterInfo::Base {
                 base,
                 precompose_unicode,
-            } => SortedLoosePaths::at(&base.join("refs"), base.into(), None, precompose_unicode),
+            } => SortedLoosePaths::at(&base.join("refs"), base.into(), None, pattern, precompose_unicode),
             IterInfo::BaseAndIterRoot {
                 base,
                 iter_root,
                 prefix: _,
                 precompose_unicode,
-            } => SortedLoosePaths::at(&iter_root, base.into(), None, precompose_unicode),
+            } => SortedLoosePaths::at(&iter_root, base.into(), None, pattern, precompose_unicode),
             IterInfo::PrefixAndBase {
                 base,
                 prefix,
                 precompose_unicode,
-            } => SortedLoosePaths::at(&base.join(prefix), base.into(), None, precompose_unicode),
+            } => SortedLoosePaths::at(&base.join(prefix), base.into(), None, pattern, precompose_unicode),
             IterInfo::ComputedIterationRoot {
                 iter_root,
                 base,
                 prefix: _,
                 remainder,
                 precompose_unicode,
-            } => SortedLoosePaths::at(&iter_root, base.into(), remainder, precompose_unicode),
+            } => SortedLoosePaths::at(&iter_root, base.into(), remainder, pattern, precompose_unicode),
         }
         .peekable()
     }
 
     fn from_prefix(base: &'a Path, prefix: Cow<'a, Path>, precompose_unicode: bool) -> std::io::Result<Self> {
-        if prefix.is_absolute() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "prefix must be a relative path, like 'refs/heads'",
-            ));
-        }
-        use std::path::Component::*;
-        if prefix.components().any(|c| matches!(c, CurDir | ParentDir)) {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "Refusing to handle prefixes with relative path components",
-            ));
-        }
+        validate_prefix(&prefix)?;
         let iter_root = base.join(prefix.as_ref());
         if iter_root.is_dir() {
             Ok(IterInfo::BaseAndIterRoot {
@@ -338,6 +338,23 @@ impl<'a> IterInfo<'a> {
     }
 }
 
+fn validate_prefix(prefix: &Path) -> std::io::Result<()> {
+    if prefix.is_absolute() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "prefix must be a relative path, like 'refs/heads'",
+        ));
+    }
+    use std::path::Component::*;
+    if prefix.components().any(|c| matches!(c, CurDir | ParentDir)) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Refusing to handle prefixes with relative path components",
+        ));
+    }
+    Ok(())
+}
+
 impl file::Store {
     /// Return an iterator over all references, loose or `packed`, sorted by their name.
     ///
@@ -359,6 +376,7 @@ impl file::Store {
                     precompose_unicode: self.precompose_unicode,
                 }),
                 packed,
+                None,
             ),
             None => self.iter_from_info(
                 IterInfo::Base {
@@ -370,6 +388,7 @@ impl file::Store {
                     precompose_unicode: self.precompose_unicode,
                 }),
                 packed,
+                None,
             ),
         }
     }
@@ -389,7 +408,7 @@ impl file::Store {
                     .common_dir()
                     .map(|base| IterInfo::from_prefix(base, prefix.into(), self.precompose_unicode))
                     .transpose()?;
-                self.iter_from_info(git_dir_info, common_dir_info, packed)
+                self.iter_from_info(git_dir_info, common_dir_info, packed, None)
             }
             Some(namespace) => {
                 let prefix = namespace.to_owned().into_namespaced_prefix(prefix);
@@ -399,33 +418,81 @@ impl file::Store {
                     .common_dir()
                     .map(|base| IterInfo::from_prefix(base, prefix.into(), self.precompose_unicode))
                     .transpose()?;
-                self.iter_from_info(git_dir_info, common_dir_info, packed)
+                self.iter_from_info(git_dir_info, common_dir_info, packed, None)
             }
         }
     }
 
+    /// As [`iter(…)`][file::Store::iter()], but only returns references whose full name matches the glob `pattern`,
+    /// like `refs/remotes/origin/*` or `refs/tags/v?.*`.
+    ///
+    /// `*` matches any amount of characters including `/`, and `?` matches a single character, like in refspecs.
+    /// Only the directory denoted by the portion of `pattern` before its first wildcard is traversed, and `packed`
+    /// references are only visited from the first one starting with that portion on, so the cost of iteration
+    /// depends on the amount of references within the subtree that `pattern` points to, instead of the total amount of references.
+    pub fn iter_matching_packed<'s, 'p>(
+        &'s self,
+        pattern: &BStr,
+        packed: Option<&'p packed::Buffer>,
+    ) -> std::io::Result<LooseThenPacked<'p, 's>> {
+        let pattern: Cow<'_, BStr> = match self.namespace.as_ref() {
+            Some(namespace) => {
+                let mut namespaced = namespace.as_bstr().to_owned();
+                namespaced.extend_from_slice(pattern);
+                Cow::Owned(namespaced)
+            }
+            None => Cow::Borrowed(pattern),
+        };
+        let literal_prefix = packed::iter::literal_prefix(pattern.as_ref());
+        let prefix = gix_path::try_from_bstr(literal_prefix[..literal_prefix.rfind_byte(b'/').unwrap_or(0)].as_bstr())
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "pattern contains ill-formed UTF-8"))?;
+        validate_prefix(&prefix)?;
+        let info = |base| {
+            if prefix.as_os_str().is_empty() {
+                IterInfo::Base {
+                    base,
+                    precompose_unicode: self.precompose_unicode,
+                }
+            } else {
+                IterInfo::PrefixAndBase {
+                    base,
+                    prefix: &prefix,
+                    precompose_unicode: self.precompose_unicode,
+                }
+            }
+        };
+        self.iter_from_info(
+            info(self.git_dir()),
+            self.common_dir().map(info),
+            packed,
+            Some(pattern.as_ref()),
+        )
+    }
+
     fn iter_from_info<'s, 'p>(
         &'s self,
         git_dir_info: IterInfo<'_>,
         common_dir_info: Option<IterInfo<'_>>,
         packed: Option<&'p packed::Buffer>,
+        pattern: Option<&BStr>,
     ) -> std::io::Result<LooseThenPacked<'p, 's>> {
         Ok(LooseThenPacked {
             git_dir: self.git_dir(),
             common_dir: self.common_dir(),
             iter_packed: match packed {
                 Some(packed) => Some(
-                    match git_dir_info.prefix() {
-                        Some(prefix) => packed.iter_prefixed(path_to_name(prefix).into_owned()),
-                        None => packed.iter(),
+                    match (pattern, git_dir_info.prefix()) {
+                        (Some(pattern), _) => packed.iter_matching(pattern),
+                        (None, Some(prefix)) => packed.iter_prefixed(path_to_name(prefix).into_owned()),
+                        (None, None) => packed.iter(),
                     }
                     .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?
                     .peekable(),
                 ),
                 None => None,
             },
-            iter_git_dir: git_dir_info.into_iter(),
-            iter_common_dir: common_dir_info.map(IterInfo::into_iter),
+            iter_git_dir: git_dir_info.into_iter(pattern),
+            iter_common_dir: common_dir_info.map(|info| info.into_iter(pattern)),
             buf: Vec::new(),
             namespace: self.namespace.as_ref(),
         })
@@ -0,0 +1,169 @@
+use crate::{
+    file,
+    file::{loose, transaction::PackedRefs},
+    transaction::{Change, LogChange, PreviousValue, RefEdit},
+    Target,
+};
+
+/// Determine which loose references to move into the `packed-refs` file with [`file::Store::pack_refs()`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Selection {
+    /// Pack all tags, along with all references which are already present in `packed-refs`, like `git pack-refs` does.
+    #[default]
+    TagsAndPacked,
+    /// Pack all references, like `git pack-refs --all` does.
+    All,
+}
+
+/// The outcome of [`file::Store::pack_refs()`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of loose references which were written into `packed-refs`.
+    pub num_packed: usize,
+    /// The amount of loose references which were skipped as they couldn't be parsed, or as their object doesn't exist.
+    pub num_broken: usize,
+}
+
+impl file::Store {
+    /// Move all loose references matching `selection` into the `packed-refs` file and remove the loose references afterwards,
+    /// using `objects` to peel annotated tags to the object they finally point to, so the peeled id can be stored alongside them.
+    ///
+    /// The resulting `packed-refs` file is sorted and written atomically. Symbolic references, references which are private to
+    /// a worktree, references which can't be parsed and references pointing to objects that don't exist are never packed.
+    ///
+    /// `packed-refs` is locked with `lock_fail_mode` while it's rewritten, and references are only packed if they still
+    /// have the value they had when they were first read, failing the operation otherwise.
+    /// Afterwards, each packed loose reference is locked with `lock_fail_mode` and removed only if it still has its packed value.
+    /// Hence, loose references which are locked or changed concurrently are kept, and shadow their packed version as usual.
+    pub fn pack_refs(
+        &self,
+        selection: Selection,
+        objects: &impl gix_object::Find,
+        lock_fail_mode: gix_lock::acquire::Fail,
+    ) -> Result<Outcome, Error> {
+        let packed = self.cached_packed_buffer()?;
+        let mut outcome = Outcome::default();
+        let mut buf = Vec::new();
+        let mut edits = Vec::new();
+        for reference in self.loose_iter()? {
+            let reference = match reference {
+                Ok(reference) => reference,
+                Err(file::iter::loose_then_packed::Error::ReferenceCreation { .. }) => {
+                    outcome.num_broken += 1;
+                    continue;
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let Target::Peeled(id) = reference.target else {
+                continue;
+            };
+            let is_selected = match selection {
+                Selection::All => true,
+                Selection::TagsAndPacked => {
+                    let is_packed = match &packed {
+                        Some(packed) => {
+                            let name = match &self.namespace {
+                                Some(namespace) => namespace.clone().into_namespaced_name(reference.name.as_ref()),
+                                None => reference.name.clone(),
+                            };
+                            matches!(packed.try_find(name.as_ref()), Ok(Some(_)))
+                        }
+                        None => false,
+                    };
+                    is_packed || reference.name.category() == Some(crate::Category::Tag)
+                }
+            };
+            let is_packable = match reference.name.category() {
+                Some(category) => !category.is_worktree_private(),
+                None => reference.name.as_bstr().starts_with(b"refs/"),
+            };
+            if !is_selected || !is_packable {
+                continue;
+            }
+            if objects.try_find(&id, &mut buf)?.is_none() {
+                outcome.num_broken += 1;
+                continue;
+            }
+            edits.push(RefEdit {
+                change: Change::Update {
+                    log: LogChange::default(),
+                    expected: PreviousValue::MustExistAndMatch(Target::Peeled(id)),
+                    new: Target::Peeled(id),
+                },
+                name: reference.name,
+                deref: false,
+            });
+        }
+        drop(packed);
+
+        if edits.is_empty() {
+            return Ok(outcome);
+        }
+        let edits = self
+            .transaction()
+            .packed_refs(PackedRefs::DeletionsAndNonSymbolicUpdates(Box::new(objects)))
+            .prepare(edits, lock_fail_mode, lock_fail_mode)?
+            .commit(None)?;
+        outcome.num_packed = edits.len();
+
+        for edit in edits {
+            let Change::Update {
+                new: Target::Peeled(packed_id),
+                ..
+            } = edit.change
+            else {
+                continue;
+            };
+            let (base, relative_path) = self.reference_path_with_base(edit.name.as_ref());
+            let reference_path = base.join(relative_path.as_ref());
+            let Ok(_lock) =
+                gix_lock::Marker::acquire_to_hold_resource(&reference_path, lock_fail_mode, Some(base.into_owned()))
+            else {
+                // The reference is being changed, so its loose version isn't the packed one anymore.
+                continue;
+            };
+            let loose_ref = self
+                .ref_contents(edit.name.as_ref())?
+                .and_then(|buf| loose::Reference::try_from_path(edit.name.clone(), &buf).ok());
+            let is_unchanged = matches!(loose_ref, Some(reference) if reference.target == Target::Peeled(packed_id));
+            if is_unchanged {
+                if let Err(err) = std::fs::remove_file(&reference_path) {
+                    if err.kind() != std::io::ErrorKind::NotFound {
+                        return Err(Error::DeleteReference {
+                            source: err,
+                            full_name: edit.name.into_inner(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(outcome)
+    }
+}
+
+mod error {
+    use gix_object::bstr::BString;
+
+    use crate::store_impl::{file, packed};
+
+    /// The error returned by [`file::Store::pack_refs()`].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The packed-refs file could not be opened")]
+        PackedOpen(#[from] packed::buffer::open::Error),
+        #[error("The loose references could not be traversed or read")]
+        Traversal(#[from] std::io::Error),
+        #[error(transparent)]
+        Iteration(#[from] file::iter::loose_then_packed::Error),
+        #[error("An object could not be looked up")]
+        FindObject(#[from] gix_object::find::Error),
+        #[error("The transaction to pack references could not be prepared")]
+        Prepare(#[from] file::transaction::prepare::Error),
+        #[error("The transaction to pack references could not be committed")]
+        Commit(#[from] file::transaction::commit::Error),
+        #[error("The packed reference {full_name} could not be removed")]
+        DeleteReference { full_name: BString, source: std::io::Error },
+    }
+}
+pub use error::Error;
@@ -48,31 +48,35 @@ impl<'s, 'p> Transaction<'s, 'p> {
                         RefLog::AndReference => (true, true),
                     };
                     if update_reflog {
+                        // Switching between symbolic and peeled refs, like when detaching `HEAD`, is logged even if
+                        // the object doesn't change.
+                        let is_kind_change = update_ref
+                            && matches!(
+                                (expected, new),
+                                (PreviousValue::MustExistAndMatch(Target::Symbolic(_)), Target::Peeled(_))
+                                    | (PreviousValue::MustExistAndMatch(Target::Peeled(_)), Target::Symbolic(_))
+                            );
+                        let previous = match expected {
+                            // Here, this means that the ref already existed, and that it will receive (even transitively)
+                            // the given value
+                            PreviousValue::MustExistAndMatch(Target::Peeled(oid)) => Some(oid.to_owned()),
+                            _ => None,
+                        }
+                        .or(change.leaf_referent_previous_oid);
                         let log_update = match new {
-                            Target::Symbolic(_) => {
-                                // Special HACK: no reflog for symref changes as there is no OID involved which the reflog needs.
-                                // Unless, the ref is new and we can obtain a peeled id
+                            Target::Symbolic(_) => match expected {
+                                // Special HACK: the ref is new and we can obtain a peeled id
                                 // identified by the expectation of what could be there, as is the case when cloning.
-                                match expected {
-                                    PreviousValue::ExistingMustMatch(Target::Peeled(oid)) => {
-                                        Some((Some(gix_hash::ObjectId::null(oid.kind())), oid))
-                                    }
-                                    _ => None,
-                                }
-                            }
-                            Target::Peeled(new_oid) => {
-                                let previous = match expected {
-                                    // Here, this means that the ref already existed, and that it will receive (even transitively)
-                                    // the given value
-                                    PreviousValue::MustExistAndMatch(Target::Peeled(oid)) => Some(oid.to_owned()),
-                                    _ => None,
+                                PreviousValue::ExistingMustMatch(Target::Peeled(oid)) => {
+                                    Some((Some(gix_hash::ObjectId::null(oid.kind())), oid))
                                 }
-                                .or(change.leaf_referent_previous_oid);
-                                Some((previous, new_oid))
-                            }
+                                // Otherwise there is only an OID for the reflog if the new referent exists.
+                                _ => change.leaf_referent_new_oid.as_ref().map(|new_oid| (previous, new_oid)),
+                            },
+                            Target::Peeled(new_oid) => Some((previous, new_oid)),
                         };
                         if let Some((previous, new_oid)) = log_update {
-                            let do_update = previous.as_ref().map_or(true, |previous| previous != new_oid);
+                            let do_update = is_kind_change || previous.as_ref() != Some(new_oid);
                             if do_update {
                                 self.store.reflog_create_or_append(
                                     change.update.name.as_ref(),
@@ -39,6 +39,9 @@ pub(in crate::store_impl::file) struct Edit {
     /// For symbolic refs, this is the previous OID to put into the reflog instead of our own previous value. It's the
     /// peeled value of the leaf referent.
     leaf_referent_previous_oid: Option<ObjectId>,
+    /// For updates to symbolic refs, this is the new OID to put into the reflog. It's the peeled value of the new referent
+    /// as it will be after the transaction, if it exists.
+    leaf_referent_new_oid: Option<ObjectId>,
 }
 
 impl Edit {
@@ -45,10 +45,24 @@ impl<'s, 'p> Transaction<'s, 'p> {
                 other => Err(other),
             })
             .and_then(|maybe_loose| match (maybe_loose, packed) {
-                (None, Some(packed)) => packed
-                    .try_find(change.update.name.as_ref())
-                    .map(|opt| opt.map(Into::into))
-                    .map_err(Error::from),
+                (None, Some(packed)) => {
+                    let name = match &store.namespace {
+                        Some(namespace) => namespace.clone().into_namespaced_name(change.update.name.as_ref()),
+                        None => change.update.name.clone(),
+                    };
+                    packed
+                        .try_find(name.as_ref())
+                        .map(|opt| {
+                            opt.map(|packed| {
+                                let mut r: Reference = packed.into();
+                                if let Some(namespace) = &store.namespace {
+                                    r.strip_namespace(namespace);
+                                }
+                                r
+                            })
+                        })
+                        .map_err(Error::from)
+                }
                 (None, None) => Ok(None),
                 (maybe_loose, _) => Ok(maybe_loose),
             })?;
@@ -183,7 +197,13 @@ impl<'s, 'p> Transaction<'s, 'p> {
 
                     lock.with_mut(|file| match new {
                         Target::Peeled(oid) => write!(file, "{oid}"),
-                        Target::Symbolic(name) => writeln!(file, "ref: {}", name.0),
+                        // Like git, the referent is stored within the namespace, which is stripped again when reading it.
+                        Target::Symbolic(name) => match &store.namespace {
+                            Some(namespace) => {
+                                writeln!(file, "ref: {}", namespace.clone().into_namespaced_name(name.as_ref()))
+                            }
+                            None => writeln!(file, "ref: {}", name.0),
+                        },
                     })?;
                     Some(lock.close()?)
                 } else {
@@ -229,6 +249,7 @@ impl<'s, 'p> Transaction<'s, 'p> {
                 lock: None,
                 parent_index: None,
                 leaf_referent_previous_oid: None,
+                leaf_referent_new_oid: None,
             })
             .collect();
         updates
@@ -245,10 +266,22 @@ impl<'s, 'p> Transaction<'s, 'p> {
                     lock: None,
                     parent_index: Some(idx),
                     leaf_referent_previous_oid: None,
+                    leaf_referent_new_oid: None,
                 },
             )
             .map_err(Error::PreprocessingFailed)?;
 
+        for idx in 0..updates.len() {
+            if let Change::Update {
+                new: Target::Symbolic(referent),
+                ..
+            } = &updates[idx].update.change
+            {
+                let new_oid = store.peel_symbolic_as_of_transaction(&updates, &updates[idx].update.name, referent)?;
+                updates[idx].leaf_referent_new_oid = new_oid;
+            }
+        }
+
         let mut maybe_updates_for_packed_refs = match self.packed_refs {
             PackedRefs::DeletionsAndNonSymbolicUpdates(_)
             | PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(_) => Some(0_usize),
@@ -388,6 +421,16 @@ impl<'s, 'p> Transaction<'s, 'p> {
                 return Err(err);
             };
 
+            // A symbolic ref that is changed itself logs the previous value of its referent.
+            if let (None, Some(crate::TargetRef::Symbolic(referent))) =
+                (change.parent_index, change.update.change.previous_value())
+            {
+                let referent = referent.to_owned();
+                change.leaf_referent_previous_oid =
+                    self.store
+                        .peel_symbolic_as_of_transaction(&[], &change.update.name, &referent)?;
+            }
+
             // traverse parent chain from leaf/peeled ref and set the leaf previous oid accordingly
             // to help with their reflog entries
             if let (Some(crate::TargetRef::Peeled(oid)), Some(parent_idx)) =
@@ -420,6 +463,48 @@ impl<'s, 'p> Transaction<'s, 'p> {
     }
 }
 
+impl file::Store {
+    /// Follow `referent`, the target of the symbolic ref `name`, to the object it points to, with the changes in `updates`
+    /// taking precedence over what's currently on disk. Return `None` if a reference in the chain doesn't exist.
+    ///
+    /// Fail if `name` would be part of a loop of symbolic refs.
+    fn peel_symbolic_as_of_transaction(
+        &self,
+        updates: &[Edit],
+        name: &FullName,
+        referent: &FullName,
+    ) -> Result<Option<gix_hash::ObjectId>, Error> {
+        let packed = self.assure_packed_refs_uptodate()?;
+        let mut seen = vec![name.clone()];
+        let mut current = referent.clone();
+        loop {
+            if seen.contains(&current) {
+                return Err(Error::SymbolicRefLoop {
+                    full_name: name.as_bstr().to_owned(),
+                    referent: referent.as_bstr().to_owned(),
+                });
+            }
+            let target = match updates.iter().find(|edit| edit.update.name == current) {
+                Some(edit) => match &edit.update.change {
+                    Change::Update { new, .. } => Some(new.clone()),
+                    Change::Delete { .. } => None,
+                },
+                None => self
+                    .find_existing_inner(current.as_ref().as_partial_name(), packed.as_ref().map(|b| &***b))
+                    .ok()
+                    .map(|r| r.target),
+            };
+            match target {
+                Some(Target::Symbolic(next)) => {
+                    seen.push(std::mem::replace(&mut current, next));
+                }
+                Some(Target::Peeled(id)) => return Ok(Some(id)),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
 fn possibly_adjust_name_for_prefixes(name: &FullNameRef) -> Option<FullName> {
     match name.category_and_short_name() {
         Some((c, sn)) => {
@@ -486,6 +571,8 @@ mod error {
         },
         #[error("Could not read reference")]
         ReferenceDecode(#[from] file::loose::reference::decode::Error),
+        #[error("The symbolic reference {full_name:?} can't point to {referent:?} as it would form a loop")]
+        SymbolicRefLoop { full_name: BString, referent: BString },
     }
 }
 
@@ -1,4 +1,4 @@
-use gix_object::bstr::{BString, ByteSlice};
+use gix_object::bstr::{BStr, BString, ByteSlice};
 use winnow::{
     combinator::{preceded, rest},
     prelude::*,
@@ -23,12 +23,48 @@ impl packed::Buffer {
         let first_record_with_prefix = self.binary_search_by(prefix.as_bstr()).unwrap_or_else(|(_, pos)| pos);
         packed::Iter::new_with_prefix(&self.as_ref()[first_record_with_prefix..], Some(prefix))
     }
+
+    /// Return an iterator yielding only references whose full name matches the glob `pattern`, ordered by reference name.
+    ///
+    /// `*` matches any amount of characters including `/` and `?` matches a single character, like in refspecs, so
+    /// `refs/remotes/origin/*` matches all remote tracking branches of `origin`.
+    /// As the buffer is sorted, only the references starting with the portion of `pattern` before its first wildcard are visited.
+    pub fn iter_matching(&self, pattern: &BStr) -> Result<packed::Iter<'_>, packed::iter::Error> {
+        let mut iter = self.iter_prefixed(literal_prefix(pattern).to_owned())?;
+        iter.pattern = Some(pattern.to_owned());
+        Ok(iter)
+    }
+}
+
+/// Return the portion of the glob `pattern` before its first special character, which every matching name must start with.
+pub(crate) fn literal_prefix(pattern: &BStr) -> &BStr {
+    let end = pattern.find_byteset(b"*?[\\").unwrap_or(pattern.len());
+    pattern[..end].as_bstr()
+}
+
+/// Return `true` if `name` matches the glob `pattern`, where `*` also matches `/`.
+pub(crate) fn matches_pattern(pattern: &BStr, name: &BStr) -> bool {
+    gix_glob::wildmatch(pattern, name, gix_glob::wildmatch::Mode::empty())
 }
 
 impl<'a> Iterator for packed::Iter<'a> {
     type Item = Result<packed::Reference<'a>, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let res = self.next_record()?;
+            if let (Ok(reference), Some(pattern)) = (&res, &self.pattern) {
+                if !matches_pattern(pattern.as_bstr(), reference.name.as_bstr()) {
+                    continue;
+                }
+            }
+            return Some(res);
+        }
+    }
+}
+
+impl<'a> packed::Iter<'a> {
+    fn next_record(&mut self) -> Option<Result<packed::Reference<'a>, Error>> {
         if self.cursor.is_empty() {
             return None;
         }
@@ -65,9 +101,7 @@ impl<'a> Iterator for packed::Iter<'a> {
             }
         }
     }
-}
 
-impl<'a> packed::Iter<'a> {
     /// Return a new iterator after successfully parsing the possibly existing first line of the given `packed` refs buffer.
     pub fn new(packed: &'a [u8]) -> Result<Self, Error> {
         Self::new_with_prefix(packed, None)
@@ -84,6 +118,7 @@ impl<'a> packed::Iter<'a> {
             Ok(packed::Iter {
                 cursor: packed,
                 prefix,
+                pattern: None,
                 current_line: 1,
             })
         } else if packed[0] == b'#' {
@@ -96,12 +131,14 @@ impl<'a> packed::Iter<'a> {
             Ok(packed::Iter {
                 cursor: refs,
                 prefix,
+                pattern: None,
                 current_line: 2,
             })
         } else {
             Ok(packed::Iter {
                 cursor: packed,
                 prefix,
+                pattern: None,
                 current_line: 1,
             })
         }
@@ -79,6 +79,8 @@ pub struct Iter<'a> {
     current_line: usize,
     /// If set, references returned will match the prefix, the first failed match will stop all iteration.
     prefix: Option<BString>,
+    /// If set, only references whose name matches this glob pattern will be returned.
+    pattern: Option<BString>,
 }
 
 mod decode;
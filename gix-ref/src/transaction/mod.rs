@@ -4,6 +4,8 @@
 //! Transactions currently allow to…
 //!
 //! * create or update reference
+//!   - symbolic references can be created or changed to point to another reference, or to an object to detach them,
+//!     but they must not form loops
 //! * delete references
 //!
 //! The following guarantees are made:
@@ -138,6 +138,27 @@ mod with_namespace {
         Ok(())
     }
 
+    #[test]
+    fn matching_on_store_with_namespace_applies_the_pattern_within_the_namespace() -> crate::Result {
+        let mut store = store_at("make_namespaced_packed_ref_repository.sh")?;
+        store.namespace = gix_ref::namespace::expand("bar")?.into();
+        let names = store
+            .iter()?
+            .matching("refs/*/multi-link-target?".into())?
+            .map(|r| r.map(|r| r.name.as_bstr().to_owned()))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            names,
+            [
+                "refs/heads/multi-link-target1",
+                "refs/remotes/origin/multi-link-target3",
+                "refs/tags/multi-link-target2"
+            ],
+            "loose and packed references are matched, with the namespace stripped"
+        );
+        Ok(())
+    }
+
     #[test]
     fn iteration_on_store_with_namespace_makes_namespace_transparent() -> crate::Result {
         let ns_two = gix_ref::namespace::expand("bar")?;
@@ -410,6 +431,73 @@ fn overlay_iter() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn overlay_matching_iter() -> crate::Result {
+    use gix_ref::Target::*;
+
+    let store = store_at("make_packed_ref_repository_for_overlay.sh")?;
+    let matching = |pattern: &str| -> crate::Result<Vec<_>> {
+        Ok(store
+            .iter()?
+            .matching(pattern.into())?
+            .map(|r| r.map(|r| (r.name.as_bstr().to_owned(), r.target)))
+            .collect::<Result<Vec<_>, _>>()?)
+    };
+    let c1 = hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03");
+    let c2 = hex_to_id("9902e3c3e8f0c569b4ab295ddf473e6de763e1e7");
+    assert_eq!(
+        matching("refs/heads/*")?,
+        vec![
+            (b"refs/heads/main".as_bstr().to_owned(), Peeled(c1)),
+            ("refs/heads/newer-as-loose".into(), Peeled(c2)),
+        ],
+        "loose references still take precedence over packed ones"
+    );
+    assert_eq!(
+        matching("refs/remotes/origin/*")?,
+        vec![
+            (
+                b"refs/remotes/origin/HEAD".as_bstr().to_owned(),
+                Symbolic("refs/remotes/origin/main".try_into()?),
+            ),
+            ("refs/remotes/origin/main".into(), Peeled(c1)),
+        ]
+    );
+    assert_eq!(
+        matching("refs/*/ma?n")?,
+        vec![
+            (b"refs/heads/main".as_bstr().to_owned(), Peeled(c1)),
+            ("refs/remotes/origin/main".into(), Peeled(c1)),
+        ],
+        "wildcards can be anywhere, and '*' matches across directories"
+    );
+    assert!(matching("refs/heads/m")?.is_empty(), "there are no partial matches");
+    assert!(matching("refs/missing/*")?.is_empty());
+
+    assert_eq!(
+        store
+            .loose_iter_matching("refs/heads/*".into())?
+            .map(|r| r.map(|r| r.name.as_bstr().to_owned()))
+            .collect::<Result<Vec<_>, _>>()?,
+        vec![b"refs/heads/newer-as-loose".as_bstr().to_owned()],
+        "only loose references are returned"
+    );
+    Ok(())
+}
+
+#[test]
+fn overlay_matching_iter_wont_allow_relative_path_components() -> crate::Result {
+    let store = store_with_packed_refs()?;
+    match store.iter()?.matching("refs/../*".into()) {
+        Ok(_) => unreachable!("relative path components aren't allowed"),
+        Err(err) => assert_eq!(
+            err.to_string(),
+            "Refusing to handle prefixes with relative path components"
+        ),
+    }
+    Ok(())
+}
+
 #[test]
 fn overlay_iter_with_prefix_wont_allow_absolute_paths() -> crate::Result {
     let store = store_with_packed_refs()?;
@@ -0,0 +1,139 @@
+use gix_lock::acquire::Fail;
+use gix_object::bstr::BString;
+use gix_ref::file::pack::{Outcome, Selection};
+
+use crate::file::{store_with_packed_refs, store_writable};
+
+#[test]
+fn all() -> crate::Result {
+    let (_keep, store) = store_writable("make_ref_repository.sh")?;
+    assert!(store.open_packed_buffer()?.is_none(), "no packed refs yet");
+    let odb = gix_odb::at(store.git_dir().join("objects"))?;
+
+    let outcome = store.pack_refs(Selection::All, &odb, Fail::Immediately)?;
+    assert_eq!(
+        outcome,
+        Outcome {
+            num_packed: 8,
+            num_broken: 1
+        },
+        "the broken reference is skipped"
+    );
+    assert!(
+        store
+            .loose_iter()?
+            .filter_map(Result::ok)
+            .all(|r| r.kind() == gix_ref::Kind::Symbolic),
+        "only symbolic refs are left"
+    );
+    assert!(store.find_loose("broken").is_err(), "broken refs are left alone");
+
+    let expected_pack_data: BString = std::fs::read(store_with_packed_refs()?.packed_refs_path())?.into();
+    let actual_pack_data: BString = std::fs::read(store.packed_refs_path())?.into();
+    assert_eq!(
+        actual_pack_data, expected_pack_data,
+        "the result is exactly the same as what `git pack-refs --all` produces, including the header and peeled tags"
+    );
+
+    let packed = store.open_packed_buffer()?.expect("written");
+    let tag = packed.find("dt1")?;
+    assert_eq!(
+        tag.object(),
+        store.find("main")?.target.into_id(),
+        "annotated tags are peeled and can be read back"
+    );
+
+    assert_eq!(
+        store.pack_refs(Selection::All, &odb, Fail::Immediately)?,
+        Outcome {
+            num_packed: 0,
+            num_broken: 1
+        },
+        "there is nothing left to pack"
+    );
+    Ok(())
+}
+
+#[test]
+fn tags_and_packed() -> crate::Result {
+    let (_keep, store) = store_writable("make_packed_ref_repository_for_overlay.sh")?;
+    let odb = gix_odb::at(store.git_dir().join("objects"))?;
+    std::fs::copy(
+        store.git_dir().join("refs/heads/newer-as-loose"),
+        store.git_dir().join("refs/heads/new-branch"),
+    )?;
+    std::fs::copy(
+        store.git_dir().join("refs/heads/newer-as-loose"),
+        store.git_dir().join("refs/tags/new-tag"),
+    )?;
+    let newer = store.find("newer-as-loose")?.target.into_id();
+
+    let outcome = store.pack_refs(Selection::TagsAndPacked, &odb, Fail::Immediately)?;
+    assert_eq!(
+        outcome,
+        Outcome {
+            num_packed: 2,
+            num_broken: 0
+        }
+    );
+
+    let loose: Vec<_> = store
+        .loose_iter()?
+        .map(|r| r.map(|r| r.name.as_bstr().to_owned()))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(
+        loose,
+        ["refs/heads/new-branch", "refs/remotes/origin/HEAD"],
+        "only new branches and symbolic refs stay loose, while tags and refs that were already packed are packed"
+    );
+    let packed = store.open_packed_buffer()?.expect("present");
+    assert_eq!(
+        packed.find("newer-as-loose")?.target(),
+        newer,
+        "the packed ref was updated"
+    );
+    assert_eq!(packed.find("new-tag")?.target(), newer);
+    assert_eq!(
+        packed.iter()?.filter_map(Result::ok).count(),
+        5,
+        "nothing was lost, and the new tag was added"
+    );
+    Ok(())
+}
+
+#[test]
+fn worktree_private_refs_are_not_packed() -> crate::Result {
+    let (_keep, store) = store_writable("make_packed_ref_repository_for_overlay.sh")?;
+    let odb = gix_odb::at(store.git_dir().join("objects"))?;
+    for name in ["refs/bisect/good", "refs/worktree/private", "refs/rewritten/one"] {
+        let path = store.git_dir().join(name);
+        std::fs::create_dir_all(path.parent().expect("parent"))?;
+        std::fs::copy(store.git_dir().join("refs/heads/newer-as-loose"), path)?;
+    }
+
+    let outcome = store.pack_refs(Selection::All, &odb, Fail::Immediately)?;
+    assert_eq!(outcome.num_packed, 1, "only the one branch was packed");
+    assert_eq!(
+        store.loose_iter()?.filter_map(Result::ok).count(),
+        4,
+        "worktree-private and symbolic refs stay loose"
+    );
+    Ok(())
+}
+
+#[test]
+fn locked_refs_are_packed_but_stay_loose() -> crate::Result {
+    let (_keep, store) = store_writable("make_ref_repository.sh")?;
+    let odb = gix_odb::at(store.git_dir().join("objects"))?;
+    std::fs::write(store.git_dir().join("refs/heads/main.lock"), b"")?;
+
+    let outcome = store.pack_refs(Selection::All, &odb, Fail::Immediately)?;
+    assert_eq!(outcome.num_packed, 8);
+    let main = store.find_loose("main")?;
+    assert_eq!(
+        store.open_packed_buffer()?.expect("written").find("main")?.target(),
+        main.target.into_id(),
+        "the ref was packed, but as it may change any moment, the loose version is kept to shadow the packed one"
+    );
+    Ok(())
+}
@@ -67,3 +67,248 @@ mod iter_rev {
         Ok(())
     }
 }
+
+fn id(n: u8) -> gix_hash::ObjectId {
+    gix_hash::ObjectId::from([n; 20])
+}
+
+/// Write a reflog for `refs/heads/main` with `times.len()` entries, where each entry moves the branch from `id(n)`
+/// to `id(n + 1)`, starting at the null id.
+fn store_with_reflog(
+    times: &[gix_date::SecondsSinceUnixEpoch],
+) -> crate::Result<(gix_testtools::tempfile::TempDir, gix_ref::file::Store)> {
+    let (dir, store) = crate::file::store_writable("make_repo_for_reflog.sh")?;
+    let mut log = String::new();
+    for (n, time) in times.iter().enumerate() {
+        let previous = if n == 0 {
+            gix_hash::Kind::Sha1.null()
+        } else {
+            id(n as u8)
+        };
+        log.push_str(&format!(
+            "{previous} {} committer <committer@example.com> {time} +0000\tentry {n}\n",
+            id(n as u8 + 1)
+        ));
+    }
+    std::fs::write(store.git_dir().join("logs/refs/heads/main"), log)?;
+    Ok((dir, store))
+}
+
+fn remaining_messages(store: &gix_ref::file::Store) -> crate::Result<Vec<String>> {
+    let mut buf = Vec::new();
+    Ok(store
+        .reflog_iter("refs/heads/main", &mut buf)?
+        .expect("reflog is never deleted")
+        .map(|line| line.map(|line| line.message.to_string()))
+        .collect::<Result<_, _>>()?)
+}
+
+mod expire {
+    use gix_ref::file::log::expire::{Options, Outcome};
+
+    use super::{id, remaining_messages, store_with_reflog};
+
+    #[test]
+    fn non_existing_returns_none() -> crate::Result {
+        let (_dir, store) = crate::file::store_writable("make_repo_for_reflog.sh")?;
+        assert_eq!(
+            store.reflog_expire(
+                "refs/heads/does-not-exist",
+                Options::default(),
+                gix_lock::acquire::Fail::Immediately,
+                |_| true
+            )?,
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn by_time_and_reachability_and_amount() -> crate::Result {
+        let (_dir, store) = store_with_reflog(&[100, 200, 300, 400, 500])?;
+        let expire = |options: Options, unreachable: Option<gix_hash::ObjectId>| {
+            store.reflog_expire(
+                "refs/heads/main",
+                options,
+                gix_lock::acquire::Fail::Immediately,
+                |oid| Some(oid.to_owned()) != unreachable,
+            )
+        };
+
+        assert_eq!(
+            expire(Options::default(), None)?,
+            Some(Outcome {
+                num_kept: 5,
+                num_removed: 0
+            }),
+            "nothing is expired by default"
+        );
+
+        assert_eq!(
+            expire(
+                Options {
+                    expire: Some(200),
+                    ..Default::default()
+                },
+                None
+            )?,
+            Some(Outcome {
+                num_kept: 4,
+                num_removed: 1
+            }),
+            "entries at the cutoff time are kept"
+        );
+        assert_eq!(
+            remaining_messages(&store)?,
+            ["entry 1", "entry 2", "entry 3", "entry 4"]
+        );
+
+        assert_eq!(
+            expire(
+                Options {
+                    expire_unreachable: Some(400),
+                    ..Default::default()
+                },
+                Some(id(3))
+            )?,
+            Some(Outcome {
+                num_kept: 3,
+                num_removed: 1
+            }),
+            "entries that refer to unreachable objects as previous or new id are removed if they are old enough"
+        );
+        assert_eq!(remaining_messages(&store)?, ["entry 1", "entry 3", "entry 4"]);
+
+        assert_eq!(
+            expire(
+                Options {
+                    max_entries: Some(1),
+                    ..Default::default()
+                },
+                None
+            )?,
+            Some(Outcome {
+                num_kept: 1,
+                num_removed: 2
+            }),
+            "the newest entries are kept"
+        );
+        assert_eq!(remaining_messages(&store)?, ["entry 4"]);
+
+        assert_eq!(
+            expire(
+                Options {
+                    expire: Some(1000),
+                    ..Default::default()
+                },
+                None
+            )?,
+            Some(Outcome {
+                num_kept: 0,
+                num_removed: 1
+            })
+        );
+        assert_eq!(
+            remaining_messages(&store)?,
+            Vec::<String>::new(),
+            "all entries can be removed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn locked_references_are_not_touched() -> crate::Result {
+        let (_dir, store) = store_with_reflog(&[100])?;
+        std::fs::write(store.git_dir().join("refs/heads/main.lock"), b"")?;
+        assert!(matches!(
+            store.reflog_expire(
+                "refs/heads/main",
+                Options {
+                    expire: Some(1000),
+                    ..Default::default()
+                },
+                gix_lock::acquire::Fail::Immediately,
+                |_| true
+            ),
+            Err(gix_ref::file::log::expire::Error::LockAcquire(_))
+        ));
+        assert_eq!(remaining_messages(&store)?, ["entry 0"]);
+        Ok(())
+    }
+}
+
+mod delete {
+    use gix_ref::file::log::delete::Options;
+
+    use super::{id, remaining_messages, store_with_reflog};
+
+    fn previous_ids(store: &gix_ref::file::Store) -> crate::Result<Vec<gix_hash::ObjectId>> {
+        let mut buf = Vec::new();
+        Ok(store
+            .reflog_iter("refs/heads/main", &mut buf)?
+            .expect("exists")
+            .map(|line| line.map(|line| line.previous_oid()))
+            .collect::<Result<_, _>>()?)
+    }
+
+    #[test]
+    fn entries_are_counted_from_the_most_recent_one() -> crate::Result {
+        let (_dir, store) = store_with_reflog(&[100, 200, 300])?;
+        let delete = |index: usize, options: Options| {
+            store.reflog_delete("refs/heads/main", index, options, gix_lock::acquire::Fail::Immediately)
+        };
+        assert_eq!(delete(3, Options::default())?, None, "there is no fourth entry");
+        assert_eq!(
+            delete(1, Options::default())?.map(|line| line.message),
+            Some("entry 1".into())
+        );
+        assert_eq!(remaining_messages(&store)?, ["entry 0", "entry 2"]);
+        assert_eq!(
+            previous_ids(&store)?,
+            [gix_hash::Kind::Sha1.null(), id(2)],
+            "without rewriting, the chain is broken"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rewrite_and_update_ref() -> crate::Result {
+        let (_dir, store) = store_with_reflog(&[100, 200, 300])?;
+        let options = Options {
+            rewrite: true,
+            update_ref: true,
+        };
+        let delete =
+            |index: usize| store.reflog_delete("refs/heads/main", index, options, gix_lock::acquire::Fail::Immediately);
+        let previous_target = store.find("refs/heads/main")?.target;
+
+        delete(1)?.expect("present");
+        assert_eq!(
+            previous_ids(&store)?,
+            [gix_hash::Kind::Sha1.null(), id(1)],
+            "the previous id of the deleted entry is passed on"
+        );
+        assert_eq!(
+            store.find("refs/heads/main")?.target,
+            previous_target,
+            "the reference is only updated if the most recent entry is deleted"
+        );
+
+        delete(0)?.expect("present");
+        assert_eq!(remaining_messages(&store)?, ["entry 0"]);
+        assert_eq!(
+            store.find("refs/heads/main")?.target.try_id(),
+            Some(id(1).as_ref()),
+            "the reference points to the new id of the most recent entry"
+        );
+
+        delete(0)?.expect("present");
+        assert_eq!(remaining_messages(&store)?, Vec::<String>::new());
+        assert_eq!(
+            store.find("refs/heads/main")?.target.try_id(),
+            Some(id(1).as_ref()),
+            "without remaining entries, the reference is kept as is"
+        );
+        Ok(())
+    }
+}
@@ -263,6 +263,16 @@ fn namespaced_updates_or_deletions_are_transparent_and_not_observable() -> crate
         .commit(committer().to_ref())?;
 
     assert_eq!(edits, actual);
+    assert_eq!(
+        std::fs::read(store.git_dir().join("refs/namespaces/foo/HEAD"))?.as_bstr(),
+        "ref: refs/namespaces/foo/refs/heads/hello\n",
+        "the referent is namespaced on disk, like git does it"
+    );
+    assert_eq!(
+        store.find_loose("HEAD")?.target,
+        Target::Symbolic("refs/heads/hello".try_into()?),
+        "but the namespace isn't observable when reading it back"
+    );
     Ok(())
 }
 
@@ -907,3 +917,169 @@ fn packed_refs_deletion_in_deletions_and_updates_mode() -> crate::Result {
     );
     Ok(())
 }
+
+#[test]
+fn head_can_be_detached_and_attached_again() -> crate::Result {
+    let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
+    let main_id = store.find("main")?.target.into_id();
+    let num_head_log_lines = reflog_lines(&store, "HEAD")?.len();
+    let update_head = |expected: Target, new: Target, message: &str| RefEdit {
+        change: Change::Update {
+            log: LogChange {
+                message: message.into(),
+                ..Default::default()
+            },
+            expected: PreviousValue::MustExistAndMatch(expected),
+            new,
+        },
+        name: "HEAD".try_into().expect("valid"),
+        deref: false,
+    };
+    let main = Target::Symbolic("refs/heads/main".try_into()?);
+
+    let edits = store
+        .transaction()
+        .prepare(
+            Some(update_head(main.clone(), Target::Peeled(main_id), "detach")),
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    assert_eq!(edits.len(), 1, "there is no split as HEAD isn't dereferenced");
+    assert_eq!(store.find_loose("HEAD")?.target, Target::Peeled(main_id));
+    assert_eq!(
+        store.find_loose("main")?.target,
+        Target::Peeled(main_id),
+        "main is unchanged"
+    );
+    let lines = reflog_lines(&store, "HEAD")?;
+    assert_eq!(lines.len(), num_head_log_lines + 1);
+    assert_eq!(
+        lines.last(),
+        Some(&log_line(main_id, main_id, "detach")),
+        "detaching is logged like git does, even though the object didn't change"
+    );
+
+    store
+        .transaction()
+        .prepare(
+            Some(update_head(Target::Peeled(main_id), main.clone(), "attach")),
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    assert_eq!(store.find_loose("HEAD")?.target, main);
+    let lines = reflog_lines(&store, "HEAD")?;
+    assert_eq!(lines.len(), num_head_log_lines + 2);
+    assert_eq!(
+        lines.last(),
+        Some(&log_line(main_id, main_id, "attach")),
+        "attaching is logged with the id of the new referent"
+    );
+    Ok(())
+}
+
+#[test]
+fn symbolic_refs_can_be_created_and_retargeted_in_the_same_transaction() -> crate::Result {
+    let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
+    let main_id = store.find("main")?.target.into_id();
+    let edits = store
+        .transaction()
+        .prepare(
+            [
+                RefEdit {
+                    change: Change::Update {
+                        log: LogChange {
+                            message: "switch".into(),
+                            ..Default::default()
+                        },
+                        expected: PreviousValue::MustExistAndMatch(Target::Symbolic("refs/heads/main".try_into()?)),
+                        new: Target::Symbolic("refs/heads/new".try_into()?),
+                    },
+                    name: "HEAD".try_into()?,
+                    deref: false,
+                },
+                create_at("refs/heads/new"),
+                create_symbolic_at("refs/heads/alias", "refs/heads/new"),
+            ],
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    assert_eq!(edits.len(), 3);
+    assert_eq!(
+        store.find_loose("alias")?.target,
+        Target::Symbolic("refs/heads/new".try_into()?)
+    );
+    assert_eq!(
+        store.find("HEAD")?.peel_to_id_in_place(&store, &EmptyCommit)?,
+        hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391")
+    );
+    assert_eq!(
+        reflog_lines(&store, "HEAD")?.last(),
+        Some(&log_line(
+            main_id,
+            hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391"),
+            "switch"
+        )),
+        "the new referent is logged with its value after the transaction"
+    );
+    Ok(())
+}
+
+#[test]
+fn symbolic_ref_loops_are_rejected() -> crate::Result {
+    let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
+    let assert_loop = |edits: Vec<RefEdit>, expected_name: &str| -> crate::Result {
+        match store.transaction().prepare(edits, Fail::Immediately, Fail::Immediately) {
+            Err(transaction::prepare::Error::SymbolicRefLoop { full_name, .. }) => {
+                assert_eq!(full_name, expected_name, "the failing edit is named");
+            }
+            Err(err) => panic!("unexpected error: {err}"),
+            Ok(_) => panic!("loops must be rejected"),
+        };
+        Ok(())
+    };
+
+    assert_loop(vec![create_symbolic_at("refs/heads/a", "refs/heads/a")], "refs/heads/a")?;
+    assert_loop(
+        vec![
+            create_symbolic_at("refs/heads/a", "refs/heads/b"),
+            create_symbolic_at("refs/heads/b", "refs/heads/a"),
+        ],
+        "refs/heads/a",
+    )?;
+    assert_loop(
+        vec![RefEdit {
+            change: Change::Update {
+                log: LogChange::default(),
+                expected: PreviousValue::Any,
+                new: Target::Symbolic("HEAD".try_into()?),
+            },
+            name: "refs/heads/main".try_into()?,
+            deref: false,
+        }],
+        "refs/heads/main",
+    )?;
+    assert_eq!(
+        store.find_loose("main")?.kind(),
+        gix_ref::Kind::Peeled,
+        "nothing was changed"
+    );
+    assert!(store.try_find_loose("a")?.is_none());
+
+    store
+        .transaction()
+        .prepare(
+            Some(create_symbolic_at("refs/heads/a", "refs/heads/does-not-exist")),
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    assert_eq!(
+        store.find_loose("a")?.kind(),
+        gix_ref::Kind::Symbolic,
+        "dangling symbolic refs are fine, like HEAD in a new repository"
+    );
+    Ok(())
+}
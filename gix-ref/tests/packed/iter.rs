@@ -78,6 +78,53 @@ fn iter_prefix() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn iter_matching() -> crate::Result {
+    let packed = store_with_packed_refs()?.open_packed_buffer()?.expect("packed-refs");
+    let matching = |pattern: &str| -> crate::Result<Vec<_>> {
+        Ok(packed
+            .iter_matching(pattern.into())?
+            .map(|r| r.map(|r| r.name.as_bstr().to_string()))
+            .collect::<Result<Vec<_>, _>>()?)
+    };
+    assert_eq!(
+        matching("refs/heads/d*")?,
+        ["refs/heads/d1", "refs/heads/dt1"],
+        "the literal portion of the pattern is used to find the first candidate"
+    );
+    assert_eq!(
+        matching("refs/*/d?")?,
+        ["refs/heads/d1"],
+        "'?' matches exactly one character, while '*' matches '/' as well"
+    );
+    assert_eq!(
+        matching("refs/*1")?,
+        [
+            "refs/d1",
+            "refs/heads/d1",
+            "refs/heads/dt1",
+            "refs/tags/dt1",
+            "refs/tags/t1"
+        ]
+    );
+    assert_eq!(
+        matching("refs/tags/t1")?,
+        ["refs/tags/t1"],
+        "patterns without wildcards match exactly"
+    );
+    assert_eq!(
+        matching("refs/heads/main*")?,
+        ["refs/heads/main"],
+        "'*' may match nothing"
+    );
+    assert!(matching("refs/notes/*")?.is_empty());
+    assert!(
+        matching("refs/heads/z*")?.is_empty(),
+        "it's fine to seek past the last reference"
+    );
+    Ok(())
+}
+
 #[test]
 fn packed_refs_without_header() -> crate::Result {
     let packed_refs = b"916840c0e2f67d370291042cb5274a597f4fa9bc refs/tags/TEST-0.0.1
@@ -32,7 +32,10 @@ impl<'a> MatchGroup<'a> {
     /// *Note that this method is correct only for specs*, even though it also *works for push-specs*.
     ///
     /// Note that negative matches are not part of the return value, so they are not observable but will be used to remove mappings.
-    // TODO: figure out how to deal with push-specs, probably when push is being implemented.
+    ///
+    /// Like git, partial names like `main` in fetch specs only match the item they resolve to first, so `refs/tags/main`
+    /// is preferred over `refs/heads/main`. In push specs, all items under `refs/heads/` or `refs/tags/` that match a partial name are retained,
+    /// or all other matches if there are none, and more than one of these is reported by [`Outcome::validated()`] as ambiguity.
     pub fn match_remotes<'item>(self, mut items: impl Iterator<Item = Item<'item>> + Clone) -> Outcome<'a, 'item> {
         let mut out = Vec::new();
         let mut seen = BTreeSet::default();
@@ -67,18 +70,41 @@ impl<'a> MatchGroup<'a> {
                 has_negation = true;
                 continue;
             }
-            for (item_index, item) in items.clone().enumerate() {
-                if let Some(matcher) = matcher {
-                    let (matched, rhs) = matcher.matches_lhs(item);
-                    if matched {
-                        push_unique(Mapping {
-                            item_index: Some(item_index),
-                            lhs: SourceRef::FullName(item.full_ref_name),
-                            rhs,
-                            spec_index,
-                        })
+            let Some(matcher) = matcher else { continue };
+            let matches = items.clone().enumerate().filter_map(|(item_index, item)| {
+                let (matched, rhs) = matcher.matches_lhs(item);
+                matched.then_some((item_index, item, rhs))
+            });
+            let mut push_match = |(item_index, item, rhs): (usize, Item<'item>, _)| {
+                push_unique(Mapping {
+                    item_index: Some(item_index),
+                    lhs: SourceRef::FullName(item.full_ref_name),
+                    rhs,
+                    spec_index,
+                })
+            };
+            match matcher.lhs {
+                Some(Needle::PartialName(name)) => match spec.op {
+                    Operation::Fetch => {
+                        if let Some(best) =
+                            matches.min_by_key(|(_, item, _)| util::partial_name_rank(name, item.full_ref_name))
+                        {
+                            push_match(best);
+                        }
                     }
-                }
+                    Operation::Push => {
+                        let matches: Vec<_> = matches.collect();
+                        let has_strong_match = matches
+                            .iter()
+                            .any(|(_, item, _)| util::is_strong_partial_name_match(name, item.full_ref_name));
+                        for m in matches.into_iter().filter(|(_, item, _)| {
+                            !has_strong_match || util::is_strong_partial_name_match(name, item.full_ref_name)
+                        }) {
+                            push_match(m);
+                        }
+                    }
+                },
+                _ => matches.for_each(push_match),
             }
         }
 
@@ -133,6 +133,30 @@ impl<'a> Needle<'a> {
     }
 }
 
+/// Return the position of the rule that expands the partial `name` to `full_ref_name`, with lower values being better matches,
+/// or `usize::MAX` if there is no match.
+pub(crate) fn partial_name_rank(name: &BStr, full_ref_name: &BStr) -> usize {
+    let mut rank = 0;
+    crate::spec::expand_partial_name(name, |expanded| {
+        if expanded == full_ref_name {
+            Some(rank)
+        } else {
+            rank += 1;
+            None
+        }
+    })
+    .unwrap_or(usize::MAX)
+}
+
+/// Return `true` if the partial `name` matches `full_ref_name` in a way that git considers unambiguous when pushing, which is
+/// the case for branches and tags, or if `name` was spelled out up to `refs/`.
+pub(crate) fn is_strong_partial_name_match(name: &BStr, full_ref_name: &BStr) -> bool {
+    full_ref_name.len() == name.len()
+        || full_ref_name.len() == name.len() + "refs/".len()
+        || full_ref_name.starts_with(b"refs/heads/")
+        || full_ref_name.starts_with(b"refs/tags/")
+}
+
 impl<'a> From<&'a BStr> for Needle<'a> {
     fn from(v: &'a BStr) -> Self {
         if let Some(pos) = v.find_byte(b'*') {
@@ -3,7 +3,8 @@ use std::collections::BTreeMap;
 use bstr::BString;
 
 use crate::{
-    match_group::{Outcome, Source},
+    match_group::{Needle, Outcome, Source},
+    parse::Operation,
     RefSpec,
 };
 
@@ -22,6 +23,13 @@ pub enum Issue {
         /// `sources` and `specs` to be zipped together.
         specs: Vec<BString>,
     },
+    /// A partial name in a push spec matches more than one local reference.
+    AmbiguousSource {
+        /// The spec whose source matches more than one reference.
+        spec: BString,
+        /// All references matched by the spec.
+        sources: Vec<Source>,
+    },
 }
 
 impl std::fmt::Display for Issue {
@@ -43,6 +51,13 @@ impl std::fmt::Display for Issue {
                         .join(", ")
                 )
             }
+            Issue::AmbiguousSource { spec, sources } => {
+                write!(
+                    f,
+                    "Source of {spec:?} is ambiguous as it matches {}",
+                    sources.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+                )
+            }
         }
     }
 }
@@ -106,6 +121,22 @@ impl<'spec, 'item> Outcome<'spec, 'item> {
             }
         }
         let mut issues = Vec::new();
+        for (spec_index, spec) in self.group.specs.iter().enumerate().filter(|(_, spec)| {
+            spec.op == Operation::Push && matches!(spec.src.map(Needle::from), Some(Needle::PartialName(_)))
+        }) {
+            let sources: Vec<_> = self
+                .mappings
+                .iter()
+                .filter(|m| m.spec_index == spec_index)
+                .map(|m| m.lhs.to_owned())
+                .collect();
+            if sources.len() > 1 {
+                issues.push(Issue::AmbiguousSource {
+                    spec: spec.to_bstring(),
+                    sources,
+                });
+            }
+        }
         for (dst, conflicting_sources) in sources_by_destinations.into_iter().filter(|(_, v)| v.len() > 1) {
             issues.push(Issue::Conflict {
                 destination_full_ref_name: dst.to_owned(),
@@ -184,3 +184,101 @@ mod multiple {
         )
     }
 }
+
+mod partial_names {
+    use gix_hash::ObjectId;
+    use gix_refspec::{
+        match_group::{validate::Issue, Item, Source, SourceRef},
+        parse::Operation,
+        MatchGroup,
+    };
+
+    const NAMES: &[&str] = &[
+        "refs/heads/main",
+        "refs/tags/main",
+        "refs/remotes/origin/main",
+        "refs/heads/feature",
+        "refs/remotes/origin/topic",
+        "refs/remotes/upstream/topic",
+    ];
+
+    fn match_names(op: Operation, spec: &str) -> Result<Vec<String>, gix_refspec::match_group::validate::Error> {
+        let id = ObjectId::null(gix_hash::Kind::Sha1);
+        let items = NAMES.iter().map(|name| Item {
+            full_ref_name: (*name).into(),
+            target: &id,
+            object: None,
+        });
+        let spec = gix_refspec::parse(spec.into(), op).expect("valid spec");
+        let group = match op {
+            Operation::Fetch => MatchGroup::from_fetch_specs(Some(spec)),
+            Operation::Push => MatchGroup::from_push_specs(Some(spec)),
+        };
+        let (outcome, _fixes) = group.match_remotes(items).validated()?;
+        Ok(outcome
+            .mappings
+            .into_iter()
+            .map(|m| match m.lhs {
+                SourceRef::FullName(name) => name.to_string(),
+                SourceRef::ObjectId(id) => id.to_string(),
+            })
+            .collect())
+    }
+
+    #[test]
+    fn fetch_uses_the_best_match_like_git() -> crate::Result {
+        assert_eq!(
+            match_names(Operation::Fetch, "main")?,
+            ["refs/tags/main"],
+            "tags are preferred over branches and remote tracking branches"
+        );
+        assert_eq!(
+            match_names(Operation::Fetch, "main:refs/remotes/x")?,
+            ["refs/tags/main"],
+            "just one source, hence no conflict"
+        );
+        assert_eq!(match_names(Operation::Fetch, "heads/main")?, ["refs/heads/main"]);
+        assert_eq!(
+            match_names(Operation::Fetch, "origin/main")?,
+            ["refs/remotes/origin/main"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn push_fails_on_ambiguous_sources_like_git() -> crate::Result {
+        let err = match_names(Operation::Push, "main").unwrap_err();
+        assert_eq!(
+            err.issues,
+            [Issue::AmbiguousSource {
+                spec: "main:main".into(),
+                sources: vec![
+                    Source::FullName("refs/heads/main".into()),
+                    Source::FullName("refs/tags/main".into())
+                ]
+            }],
+            "the remote tracking branch is a weak match that doesn't count"
+        );
+        assert_eq!(
+            err.to_string(),
+            "Found 1 issue that prevents the refspec mapping to be used: \n\tSource of \"main:main\" is ambiguous as it matches refs/heads/main, refs/tags/main"
+        );
+
+        assert_eq!(match_names(Operation::Push, "heads/main")?, ["refs/heads/main"]);
+        assert_eq!(
+            match_names(Operation::Push, "feature:refs/heads/other")?,
+            ["refs/heads/feature"]
+        );
+        assert_eq!(
+            match_names(Operation::Push, "origin/topic")?,
+            ["refs/remotes/origin/topic"],
+            "a single weak match is fine"
+        );
+        assert_eq!(
+            match_names(Operation::Push, "remotes/origin/topic")?,
+            ["refs/remotes/origin/topic"],
+            "spelled out up to `refs/` is a strong match"
+        );
+        Ok(())
+    }
+}
@@ -48,6 +48,8 @@ pub struct Format<'a> {
     /// The `id` of the commit to describe.
     pub id: gix_hash::ObjectId,
     /// The amount of hex characters to use to display `id`.
+    ///
+    /// If `0`, only `name` is displayed, or the full `id` if there is no name, similar to `git describe --abbrev=0`.
     pub hex_len: usize,
     /// The amount of commits between `name` and `id`, where `id` is in the future of `name`.
     pub depth: u32,
@@ -78,11 +80,13 @@ impl<'a> Format<'a> {
 impl<'a> Display for Format<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         if let Some(name) = self.name.as_deref() {
-            if !self.long && self.is_exact_match() {
+            if (!self.long && self.is_exact_match()) || self.hex_len == 0 {
                 name.fmt(f)?;
             } else {
                 write!(f, "{}-{}-g{}", name, self.depth, self.id.to_hex_with_len(self.hex_len))?;
             }
+        } else if self.hex_len == 0 {
+            self.id.to_hex().fmt(f)?;
         } else {
             self.id.to_hex_with_len(self.hex_len).fmt(f)?;
         }
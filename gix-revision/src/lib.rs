@@ -1,4 +1,5 @@
-//! Interact with git revisions by parsing them from rev-specs and describing them in terms of reference names.
+//! Interact with git revisions by parsing them from rev-specs, describing them in terms of reference names,
+//! finding their merge bases, and listing the commits in revision ranges.
 //!
 //! ## Feature Flags
 #![cfg_attr(
@@ -15,6 +16,18 @@ pub mod describe;
 #[cfg(feature = "describe")]
 pub use describe::function::describe;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "merge_base")]
+pub mod merge_base;
+#[cfg(feature = "merge_base")]
+pub use merge_base::function::{is_ancestor, merge_base};
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "range")]
+pub mod range;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod spec;
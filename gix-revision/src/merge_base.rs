@@ -0,0 +1,251 @@
+bitflags::bitflags! {
+    /// The flags used in the graph for finding [merge bases](crate::merge_base()).
+    #[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+    pub struct Flags: u8 {
+        /// The commit is reachable from the first commit.
+        const COMMIT1 = 1 << 0;
+        /// The commit is reachable from one of the other commits.
+        const COMMIT2 = 1 << 1;
+        /// The commit is reachable from a merge base, and thus can't be a merge base itself.
+        const STALE = 1 << 2;
+        /// The commit was already recorded as merge base candidate.
+        const RESULT = 1 << 3;
+    }
+}
+
+/// The error returned by the [`merge_base()`][function::merge_base()] and [`is_ancestor()`][function::is_ancestor()] functions.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A commit could not be inserted into the graph")]
+    InsertCommit(#[from] gix_revwalk::graph::try_lookup_or_insert_default::Error),
+}
+
+pub(crate) mod function {
+    use std::cmp::Ordering;
+
+    use gix_hash::{oid, ObjectId};
+    use gix_revwalk::graph;
+
+    use super::{Error, Flags};
+    use crate::{Graph, PriorityQueue};
+
+    /// Given a commit at `first` id, traverse the commit `graph` and return all merge bases between it and `others`,
+    /// sorted from newest to oldest commit time, or an empty list if there is none.
+    ///
+    /// The first merge base is the one that `git merge-base` would return, and all of them are the ones returned
+    /// by `git merge-base --all`. With more than one commit in `others`, the merge bases are computed between `first` and
+    /// a hypothetical merge commit of all `others`.
+    ///
+    /// Generation numbers of the commit-graph associated with `graph` are used to avoid traversing more commits than
+    /// necessary, and commit times are used otherwise.
+    /// Note that all flags of commits in `graph` are reset before the traversal.
+    pub fn merge_base(
+        first: &oid,
+        others: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let _span = gix_trace::coarse!(
+            "gix_revision::merge_base()",
+            first = %first,
+            num_others = others.len()
+        );
+        if others.is_empty() || others.iter().any(|other| other.as_ref() == first) {
+            return Ok(vec![first.to_owned()]);
+        }
+
+        graph.clear_commit_data(|flags| *flags = Flags::empty());
+        let bases = paint_down_to_common(first, others, None, graph)?;
+        let bases = sorted_by_time_newest_first(
+            bases
+                .into_iter()
+                .filter(|id| !graph[id].data.contains(Flags::STALE))
+                .collect(),
+            graph,
+        );
+        if bases.len() < 2 {
+            return Ok(bases);
+        }
+        let bases = remove_redundant(&bases, graph)?;
+        Ok(sorted_by_time_newest_first(bases, graph))
+    }
+
+    /// Return `true` if the commit at `ancestor` is reachable from the commit at `descendant` using the commit `graph`,
+    /// or if both are the same commit, similar to `git merge-base --is-ancestor`.
+    ///
+    /// Generation numbers of the commit-graph associated with `graph` are used to short-cut the traversal, which can
+    /// often be avoided entirely.
+    /// Note that all flags of commits in `graph` are reset before the traversal.
+    pub fn is_ancestor(
+        ancestor: &oid,
+        descendant: &oid,
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<bool, Error> {
+        if ancestor == descendant {
+            return Ok(true);
+        }
+        let Some(ancestor_generation) = graph
+            .try_lookup_or_insert_commit(ancestor.to_owned(), |_| {})?
+            .map(|commit| commit.generation)
+        else {
+            return Ok(false);
+        };
+        let Some(descendant_generation) = graph
+            .try_lookup_or_insert_commit(descendant.to_owned(), |_| {})?
+            .map(|commit| commit.generation)
+        else {
+            return Ok(false);
+        };
+        if let (Some(ancestor), Some(descendant)) = (ancestor_generation, descendant_generation) {
+            if ancestor >= descendant {
+                return Ok(false);
+            }
+        }
+
+        graph.clear_commit_data(|flags| *flags = Flags::empty());
+        paint_down_to_common(
+            ancestor,
+            &[descendant.to_owned()],
+            Some(ancestor_generation.unwrap_or(GENERATION_UNKNOWN)),
+            graph,
+        )?;
+        let is_ancestor = graph[ancestor].data.contains(Flags::COMMIT2);
+        Ok(is_ancestor)
+    }
+
+    /// Return the subset of `commits` which aren't reachable from any other commit in `commits`.
+    fn remove_redundant(
+        commits: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let mut redundant = vec![false; commits.len()];
+        let mut work = Vec::with_capacity(commits.len());
+        let mut work_idx = Vec::with_capacity(commits.len());
+        for (idx, id) in commits.iter().enumerate() {
+            if redundant[idx] {
+                continue;
+            }
+            work.clear();
+            work_idx.clear();
+            let mut min_generation = graph[id].generation.unwrap_or(GENERATION_UNKNOWN);
+            for (other_idx, other_id) in commits.iter().enumerate() {
+                if other_idx == idx || redundant[other_idx] {
+                    continue;
+                }
+                work.push(*other_id);
+                work_idx.push(other_idx);
+                min_generation = min_generation.min(graph[other_id].generation.unwrap_or(GENERATION_UNKNOWN));
+            }
+
+            graph.clear_commit_data(|flags| *flags = Flags::empty());
+            paint_down_to_common(id, &work, Some(min_generation), graph)?;
+            if graph[id].data.contains(Flags::COMMIT2) {
+                redundant[idx] = true;
+            }
+            for (other_id, other_idx) in work.iter().zip(work_idx.iter()) {
+                if graph[other_id].data.contains(Flags::COMMIT1) {
+                    redundant[*other_idx] = true;
+                }
+            }
+        }
+        graph.clear_commit_data(|flags| *flags = Flags::empty());
+        Ok(commits
+            .iter()
+            .zip(redundant)
+            .filter_map(|(id, redundant)| (!redundant).then_some(*id))
+            .collect())
+    }
+
+    /// Walk down from `first` and `others` and mark all commits reachable from them, and return all commits
+    /// reachable from both in the order they were found. Note that some of them may be marked [`Flags::STALE`]
+    /// as they are reachable from another common commit.
+    ///
+    /// If `min_generation` is set, commits with a lower generation aren't traversed.
+    fn paint_down_to_common(
+        first: &oid,
+        others: &[ObjectId],
+        min_generation: Option<graph::Generation>,
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let mut queue = PriorityQueue::<GenThenTime, ObjectId>::new();
+        if let Some(commit) = graph.try_lookup_or_insert_commit(first.to_owned(), |flags| *flags |= Flags::COMMIT1)? {
+            queue.insert(GenThenTime::from(&*commit), first.to_owned());
+        }
+        for other in others {
+            if let Some(commit) = graph.try_lookup_or_insert_commit(*other, |flags| *flags |= Flags::COMMIT2)? {
+                queue.insert(GenThenTime::from(&*commit), *other);
+            }
+        }
+
+        let mut out = Vec::new();
+        while queue.iter_unordered().any(|id| !graph[id].data.contains(Flags::STALE)) {
+            let (info, commit_id) = queue.pop().expect("we have non-stale");
+            if matches!(min_generation, Some(min_generation) if info.generation < min_generation) {
+                break;
+            }
+            let commit = graph.get_mut(&commit_id).expect("queued commits are in the graph");
+            let mut flags = commit.data & (Flags::COMMIT1 | Flags::COMMIT2 | Flags::STALE);
+            if flags == (Flags::COMMIT1 | Flags::COMMIT2) {
+                if !commit.data.contains(Flags::RESULT) {
+                    commit.data |= Flags::RESULT;
+                    out.push(commit_id);
+                }
+                flags |= Flags::STALE;
+            }
+
+            for parent_id in commit.parents.clone() {
+                let Some(parent) = graph.try_lookup_or_insert_commit(parent_id, |_| {})? else {
+                    // Skip missing commits, which can happen in shallow clones.
+                    continue;
+                };
+                if parent.data.contains(flags) {
+                    continue;
+                }
+                parent.data |= flags;
+                queue.insert(GenThenTime::from(&*parent), parent_id);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Sort `commits` by their commit time, newest first, while keeping the order of commits with the same time.
+    fn sorted_by_time_newest_first(
+        mut commits: Vec<ObjectId>,
+        graph: &Graph<'_, graph::Commit<Flags>>,
+    ) -> Vec<ObjectId> {
+        commits.sort_by_key(|id| std::cmp::Reverse(graph[id].commit_time));
+        commits
+    }
+
+    /// The generation we assume for commits that aren't part of the commit-graph, which is higher than any actual
+    /// generation as these commits are newer than all commits in the commit-graph.
+    const GENERATION_UNKNOWN: graph::Generation = graph::Generation::MAX;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct GenThenTime {
+        /// Note that the generation is [`GENERATION_UNKNOWN`] if it's not available.
+        generation: graph::Generation,
+        time: gix_date::SecondsSinceUnixEpoch,
+    }
+
+    impl From<&graph::Commit<Flags>> for GenThenTime {
+        fn from(commit: &graph::Commit<Flags>) -> Self {
+            GenThenTime {
+                generation: commit.generation.unwrap_or(GENERATION_UNKNOWN),
+                time: commit.commit_time,
+            }
+        }
+    }
+
+    impl Ord for GenThenTime {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.generation.cmp(&other.generation).then(self.time.cmp(&other.time))
+        }
+    }
+
+    impl PartialOrd<Self> for GenThenTime {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+}
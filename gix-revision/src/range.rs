@@ -0,0 +1,399 @@
+use gix_hash::ObjectId;
+
+use crate::{graph, merge_base, Graph, Spec};
+
+pub use function::{count, walk};
+
+/// The commits to start a traversal from, and the commits whose ancestors are to be excluded from it,
+/// just like the positive and negative revisions passed to `git rev-list`.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Tips {
+    /// The commits to include along with their ancestors.
+    pub include: Vec<ObjectId>,
+    /// The commits to exclude along with their ancestors, even if these are reachable from [`include`](Self::include).
+    pub exclude: Vec<ObjectId>,
+}
+
+/// The order in which commits are returned by [`walk()`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sorting {
+    /// Show commits in reverse chronological order by commit time, just like `git rev-list` does by default.
+    ///
+    /// Parents may be shown before all of their children if commit times are skewed.
+    #[default]
+    ByCommitTime,
+    /// Show no parents before all of their children are shown, but otherwise show commits in the commit time order,
+    /// similar to `git rev-list --date-order`.
+    DateOrder,
+    /// Show no parents before all of their children are shown, and avoid showing commits on multiple lines of
+    /// history intermixed, similar to `git rev-list --topo-order`.
+    TopoOrder,
+}
+
+/// The error returned by [`Tips::push_spec()`], [`walk()`] and [`count()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    MergeBase(#[from] merge_base::Error),
+    #[error("A commit could not be inserted into the graph")]
+    InsertCommit(#[from] gix_revwalk::graph::try_lookup_or_insert_default::Error),
+    #[error("The object {id} does not exist or is not a commit")]
+    NotACommit { id: ObjectId },
+}
+
+impl Tips {
+    /// Add the commits that `spec` includes or excludes to our tips, using `graph` to compute merge bases and to
+    /// look up parents as needed.
+    ///
+    /// `A..B` includes `B` and excludes `A`, while `A...B` includes both and excludes all of their merge bases, similar to
+    /// `git rev-list`. All object ids in `spec` must point to commits, so tags have to be peeled beforehand.
+    /// Note that all flags of commits in `graph` are reset if merge bases are computed.
+    pub fn push_spec(
+        &mut self,
+        spec: Spec,
+        graph: &mut Graph<'_, graph::Commit<merge_base::Flags>>,
+    ) -> Result<&mut Self, Error> {
+        match spec {
+            Spec::Include(id) => self.include.push(id),
+            Spec::Exclude(id) => self.exclude.push(id),
+            Spec::Range { from, to } => {
+                self.exclude.push(from);
+                self.include.push(to);
+            }
+            Spec::Merge { theirs, ours } => {
+                let bases = crate::merge_base(&theirs, &[ours], graph)?;
+                self.include.extend([theirs, ours]);
+                self.exclude.extend(bases);
+            }
+            Spec::IncludeOnlyParents(id) => self.include.extend(parents_of(id, graph)?),
+            Spec::ExcludeParents(id) => {
+                self.include.push(id);
+                self.exclude.extend(parents_of(id, graph)?);
+            }
+        }
+        Ok(self)
+    }
+}
+
+fn parents_of(
+    id: ObjectId,
+    graph: &mut Graph<'_, graph::Commit<merge_base::Flags>>,
+) -> Result<impl Iterator<Item = ObjectId>, Error> {
+    let commit = graph
+        .try_lookup_or_insert_commit(id, |_| {})?
+        .ok_or(Error::NotACommit { id })?;
+    Ok(commit.parents.clone().into_iter())
+}
+
+pub(crate) mod function {
+    use std::cmp::Reverse;
+
+    use gix_date::SecondsSinceUnixEpoch;
+    use gix_hash::{oid, ObjectId};
+    use gix_revwalk::graph;
+
+    use super::{Error, Sorting, Tips};
+    use crate::{Graph, PriorityQueue};
+
+    /// Traverse the commit `graph` to return all commits reachable from `tips.include`, but not from `tips.exclude`,
+    /// in the order given by `sorting`, which is the same list of commits that `git rev-list` would return.
+    ///
+    /// Just like `git`, the traversal stops once all commits left to traverse are excluded, while assuring that
+    /// commits in merged lines of history with older commit times are still excluded correctly. This is why
+    /// commits are traversed in the order of their commit time, and only after all of them are known are they
+    /// sorted topologically if requested.
+    ///
+    /// The data of commits in `graph` remains untouched.
+    pub fn walk<T: Default>(
+        tips: &Tips,
+        sorting: Sorting,
+        graph: &mut Graph<'_, graph::Commit<T>>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let _span = gix_trace::coarse!(
+            "gix_revision::range::walk()",
+            num_include = tips.include.len(),
+            num_exclude = tips.exclude.len()
+        );
+        let commits = limit(tips, graph)?;
+        Ok(match sorting {
+            Sorting::ByCommitTime => commits,
+            Sorting::DateOrder | Sorting::TopoOrder => sort_in_topological_order(commits, sorting, graph),
+        })
+    }
+
+    /// Return the amount of commits that [`walk()`] would return for `tips` and `graph`, similar to `git rev-list --count`.
+    ///
+    /// This is faster than counting the commits returned by [`walk()`] as no sorting is needed, and if there are no
+    /// commits to exclude, commits are counted without traversing them in the order of their commit time.
+    pub fn count<T: Default>(tips: &Tips, graph: &mut Graph<'_, graph::Commit<T>>) -> Result<usize, Error> {
+        let _span = gix_trace::coarse!(
+            "gix_revision::range::count()",
+            num_include = tips.include.len(),
+            num_exclude = tips.exclude.len()
+        );
+        if !tips.exclude.is_empty() {
+            return Ok(limit(tips, graph)?.len());
+        }
+
+        let mut seen = gix_hashtable::HashSet::default();
+        let mut stack = Vec::new();
+        for id in &tips.include {
+            lookup_tip(*id, graph)?;
+            if seen.insert(*id) {
+                stack.push(*id);
+            }
+        }
+        while let Some(id) = stack.pop() {
+            let commit = graph.get(&id).expect("stacked commits are in the graph");
+            for parent_id in commit.parents.clone() {
+                if seen.contains(&parent_id) || graph.try_lookup_or_insert_commit(parent_id, |_| {})?.is_none() {
+                    continue;
+                }
+                seen.insert(parent_id);
+                stack.push(parent_id);
+            }
+        }
+        Ok(seen.len())
+    }
+
+    bitflags::bitflags! {
+        #[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+        struct Flags: u8 {
+            /// The commit was put into the queue.
+            const SEEN = 1 << 0;
+            /// The commit is reachable from a commit to exclude.
+            const UNINTERESTING = 1 << 1;
+        }
+    }
+
+    /// The amount of excluded commits to keep traversing after it seems that all further commits are excluded,
+    /// to deal with commit times that are slightly off, just like `git` does.
+    const SLOP: usize = 5;
+
+    /// Traverse all commits reachable from `tips` by commit time, and return all that are interesting
+    /// in the order of traversal.
+    fn limit<T: Default>(tips: &Tips, graph: &mut Graph<'_, graph::Commit<T>>) -> Result<Vec<ObjectId>, Error> {
+        let mut flags = gix_hashtable::HashMap::<ObjectId, Flags>::default();
+        let mut queue = Queue::default();
+        for (ids, tip_flags) in [
+            (&tips.include, Flags::SEEN),
+            (&tips.exclude, Flags::SEEN | Flags::UNINTERESTING),
+        ] {
+            for id in ids {
+                let commit_time = lookup_tip(*id, graph)?;
+                let commit_flags = flags.entry(*id).or_default();
+                let previous = *commit_flags;
+                *commit_flags |= tip_flags;
+                if tip_flags.contains(Flags::UNINTERESTING) {
+                    mark_parents_uninteresting(id, graph, &mut flags);
+                }
+                if !previous.contains(Flags::SEEN) {
+                    queue.insert(commit_time, *id);
+                }
+            }
+        }
+
+        let mut out = Vec::new();
+        let mut slop = SLOP;
+        let mut last_interesting_time = SecondsSinceUnixEpoch::MAX;
+        while let Some(id) = queue.pop() {
+            let is_uninteresting = flags[&id].contains(Flags::UNINTERESTING);
+            let commit = graph.get(&id).expect("queued commits are in the graph");
+            let commit_time = commit.commit_time;
+            for parent_id in commit.parents.clone() {
+                if is_uninteresting {
+                    *flags.entry(parent_id).or_default() |= Flags::UNINTERESTING;
+                }
+                let Some(parent) = graph.try_lookup_or_insert_commit(parent_id, |_| {})? else {
+                    // Skip missing commits, which can happen in shallow clones.
+                    continue;
+                };
+                let parent_time = parent.commit_time;
+                if is_uninteresting {
+                    mark_parents_uninteresting(&parent_id, graph, &mut flags);
+                }
+                let parent_flags = flags.entry(parent_id).or_default();
+                if parent_flags.contains(Flags::SEEN) {
+                    continue;
+                }
+                *parent_flags |= Flags::SEEN;
+                queue.insert(parent_time, parent_id);
+            }
+
+            if is_uninteresting {
+                slop = still_interesting(&queue, &flags, last_interesting_time, slop);
+                if slop == 0 {
+                    break;
+                }
+                continue;
+            }
+            last_interesting_time = commit_time;
+            out.push(id);
+        }
+
+        // Commits may have been found to be reachable from excluded commits only after they were traversed.
+        out.retain(|id| !flags[id].contains(Flags::UNINTERESTING));
+        Ok(out)
+    }
+
+    /// Return the amount of excluded commits to keep traversing, or `0` if the traversal can stop as it's unlikely
+    /// that any of the commits left in `queue` are still interesting.
+    fn still_interesting(
+        queue: &Queue,
+        flags: &gix_hashtable::HashMap<ObjectId, Flags>,
+        last_interesting_time: SecondsSinceUnixEpoch,
+        slop: usize,
+    ) -> usize {
+        let Some(newest_time) = queue.peek_time() else {
+            return 0;
+        };
+        if last_interesting_time <= newest_time
+            || queue
+                .iter_unordered()
+                .any(|id| !flags[id].contains(Flags::UNINTERESTING))
+        {
+            return SLOP;
+        }
+        slop - 1
+    }
+
+    /// Mark all parents of the commit at `id` as uninteresting, along with all of their ancestors that
+    /// we already know about.
+    fn mark_parents_uninteresting<T>(
+        id: &oid,
+        graph: &Graph<'_, graph::Commit<T>>,
+        flags: &mut gix_hashtable::HashMap<ObjectId, Flags>,
+    ) {
+        let mut stack: Vec<ObjectId> = graph.get(id).map(|commit| commit.parents.to_vec()).unwrap_or_default();
+        while let Some(id) = stack.pop() {
+            let commit_flags = flags.entry(id).or_default();
+            if commit_flags.contains(Flags::UNINTERESTING) {
+                continue;
+            }
+            *commit_flags |= Flags::UNINTERESTING;
+            if let Some(commit) = graph.get(&id) {
+                stack.extend(commit.parents.iter().copied());
+            }
+        }
+    }
+
+    /// Sort `commits` so that no parent is shown before all of its children, with `sorting` determining the order of
+    /// commits that can be shown next, just like `git` does it.
+    fn sort_in_topological_order<T>(
+        commits: Vec<ObjectId>,
+        sorting: Sorting,
+        graph: &Graph<'_, graph::Commit<T>>,
+    ) -> Vec<ObjectId> {
+        // An in-degree of `0` means the commit isn't part of `commits` or was already shown, otherwise it's
+        // the amount of children yet to be shown plus one.
+        let mut indegree: gix_hashtable::HashMap<ObjectId, usize> = commits.iter().map(|id| (*id, 1)).collect();
+        for id in &commits {
+            for parent_id in &graph[id].parents {
+                if let Some(degree) = indegree.get_mut(parent_id) {
+                    *degree += 1;
+                }
+            }
+        }
+
+        let tips = commits.iter().filter(|id| indegree[*id] == 1).copied();
+        let mut queue = match sorting {
+            Sorting::TopoOrder => {
+                let mut tips: Vec<_> = tips.collect();
+                // The stack pops the last commit first, but the tips have to be shown in the order we got them in.
+                tips.reverse();
+                Next::Stack(tips)
+            }
+            Sorting::ByCommitTime | Sorting::DateOrder => {
+                let mut queue = Queue::default();
+                for id in tips {
+                    queue.insert(graph[&id].commit_time, id);
+                }
+                Next::ByCommitTime(queue)
+            }
+        };
+
+        let mut out = Vec::with_capacity(commits.len());
+        while let Some(id) = queue.pop() {
+            for parent_id in &graph[&id].parents {
+                let Some(degree) = indegree.get_mut(parent_id).filter(|degree| **degree != 0) else {
+                    continue;
+                };
+                *degree -= 1;
+                if *degree == 1 {
+                    queue.push(*parent_id, graph[parent_id].commit_time);
+                }
+            }
+            indegree.insert(id, 0);
+            out.push(id);
+        }
+        out
+    }
+
+    /// A queue of commits that pops the newest commit first, and commits with the same commit time in insertion order.
+    struct Queue {
+        inner: PriorityQueue<(SecondsSinceUnixEpoch, Reverse<usize>), ObjectId>,
+        num_inserted: usize,
+    }
+
+    impl Default for Queue {
+        fn default() -> Self {
+            Queue {
+                inner: PriorityQueue::new(),
+                num_inserted: 0,
+            }
+        }
+    }
+
+    impl Queue {
+        fn insert(&mut self, commit_time: SecondsSinceUnixEpoch, id: ObjectId) {
+            self.inner.insert((commit_time, Reverse(self.num_inserted)), id);
+            self.num_inserted += 1;
+        }
+
+        fn pop(&mut self) -> Option<ObjectId> {
+            self.inner.pop_value()
+        }
+
+        fn peek_time(&self) -> Option<SecondsSinceUnixEpoch> {
+            self.inner.peek().map(|((commit_time, _), _)| *commit_time)
+        }
+
+        fn iter_unordered(&self) -> impl Iterator<Item = &ObjectId> {
+            self.inner.iter_unordered()
+        }
+    }
+
+    /// The commits that can be shown next while sorting topologically.
+    enum Next {
+        Stack(Vec<ObjectId>),
+        ByCommitTime(Queue),
+    }
+
+    impl Next {
+        fn push(&mut self, id: ObjectId, commit_time: SecondsSinceUnixEpoch) {
+            match self {
+                Next::Stack(stack) => stack.push(id),
+                Next::ByCommitTime(queue) => queue.insert(commit_time, id),
+            }
+        }
+
+        fn pop(&mut self) -> Option<ObjectId> {
+            match self {
+                Next::Stack(stack) => stack.pop(),
+                Next::ByCommitTime(queue) => queue.pop(),
+            }
+        }
+    }
+
+    /// Look up the tip at `id` in `graph` and return its commit time, or fail if it doesn't exist.
+    fn lookup_tip<T: Default>(
+        id: ObjectId,
+        graph: &mut Graph<'_, graph::Commit<T>>,
+    ) -> Result<SecondsSinceUnixEpoch, Error> {
+        graph
+            .try_lookup_or_insert_commit(id, |_| {})?
+            .map(|commit| commit.commit_time)
+            .ok_or(Error::NotACommit { id })
+    }
+}
@@ -450,9 +450,13 @@ where
                         source: None,
                     })
                     .and_then(|date| {
-                        gix_date::parse(date, Some(SystemTime::now())).map_err(|err| Error::Time {
-                            input: nav.into(),
-                            source: err.into(),
+                        let now = SystemTime::now();
+                        gix_date::parse(date, Some(now)).or_else(|err| {
+                            // Like `git`, fall back to fuzzy dates like `yesterday` or `last monday`.
+                            gix_date::parse::approxidate(date, now).ok_or_else(|| Error::Time {
+                                input: nav.into(),
+                                source: err.into(),
+                            })
                         })
                     })?;
                 delegate
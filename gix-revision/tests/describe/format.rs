@@ -54,3 +54,24 @@ fn show_abbrev_hash_if_no_name_is_known() {
     assert_eq!(format.long(false).to_string(), "b920bbb-dirty");
     assert_eq!(format.long(true).to_string(), "b920bbb-dirty");
 }
+
+#[test]
+fn zero_hex_len_shows_only_the_name_or_the_full_hash() {
+    let mut format = describe::Outcome {
+        name: Some(Cow::Borrowed(b"main".as_bstr())),
+        id: hex_to_id("b920bbb055e1efb9080592a409d3975738b6efb3"),
+        depth: 42,
+        name_by_oid: Default::default(),
+        commits_seen: 0,
+    }
+    .into_format(0);
+    assert_eq!(format.to_string(), "main", "like `git describe --abbrev=0`");
+    assert_eq!(format.long(true).to_string(), "main");
+
+    format.dirty_suffix = Some("dirty".into());
+    assert_eq!(format.to_string(), "main-dirty");
+
+    format.name = None;
+    format.dirty_suffix = None;
+    assert_eq!(format.to_string(), "b920bbb055e1efb9080592a409d3975738b6efb3");
+}
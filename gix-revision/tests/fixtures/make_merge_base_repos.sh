@@ -0,0 +1,99 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+function commit() {
+  local message=${1:?first argument is the commit message}
+  tick
+  git commit -q --allow-empty -m "$message"
+  git tag "$message"
+}
+
+function merge() {
+  local message=${1:?first argument is the commit message}
+  shift
+  tick
+  git merge -q -m "$message" "$@"
+  git tag "$message"
+}
+
+# Write the input commits followed by the outputs of `git merge-base` and `git merge-base --all` for them,
+# separated by an empty line.
+function baseline() {
+  {
+    echo "$@"
+    git rev-parse "$@" | tr '\n' ' '
+    echo
+    git merge-base "$@" || :
+    echo "--all"
+    git merge-base --all "$@" || :
+    echo
+  } >> merge-base.baseline
+}
+
+git init -q
+git config merge.ff false
+
+tick
+git checkout -q -b main
+commit root
+commit c1
+
+git checkout -q -b a
+commit a1
+git checkout -q -b b main
+commit b1
+
+# Criss-cross merge: both `a2` and `b2` merge `a1` and `b1`.
+git checkout -q a
+merge a2 b1
+git checkout -q b
+merge b2 a1
+
+git checkout -q a
+commit a3
+git checkout -q b
+commit b3
+
+# An octopus of three branches forking off `main`.
+git checkout -q -b x main
+commit x1
+git checkout -q -b y main
+commit y1
+git checkout -q -b z main
+commit z1
+git checkout -q -b octopus x
+merge octopus y z
+
+git checkout -q --orphan unrelated
+commit unrelated1
+
+git checkout -q main
+commit c2
+
+baseline a3 b3
+baseline b3 a3
+baseline a2 b2
+baseline a3 a1
+baseline a1 a3
+baseline a3 b3 c2
+baseline x1 y1 z1
+baseline octopus x1
+baseline octopus c2
+baseline c2 octopus a3
+baseline a3 unrelated1
+baseline c1 c1
+
+git commit-graph write --no-progress --reachable
+git repack -adq
@@ -0,0 +1,140 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+function commit() {
+  local message=${1:?first argument is the commit message}
+  tick
+  git commit -q --allow-empty -m "$message"
+  git tag "$message"
+}
+
+# Like `commit`, but with the same commit time as the previous commit.
+function commit_same_time() {
+  local message=${1:?first argument is the commit message}
+  git commit -q --allow-empty -m "$message"
+  git tag "$message"
+}
+
+# Like `commit`, but with a commit time that is older than the one of all other commits, as if the clock was skewed.
+function commit_skewed() {
+  local message=${1:?first argument is the commit message}
+  GIT_COMMITTER_DATE="1112900000 -0700" GIT_AUTHOR_DATE="1112900000 -0700" git commit -q --allow-empty -m "$message"
+  git tag "$message"
+}
+
+function merge() {
+  local message=${1:?first argument is the commit message}
+  shift
+  tick
+  git merge -q -m "$message" "$@"
+  git tag "$message"
+}
+
+# Write the arguments followed by the outputs of `git rev-list` with them in all supported orders, and the count.
+function baseline() {
+  {
+    echo "$@"
+    git rev-list "$@"
+    echo "--date-order"
+    git rev-list --date-order "$@"
+    echo "--topo-order"
+    git rev-list --topo-order "$@"
+    echo "--count"
+    git rev-list --count "$@"
+    echo
+  } >> rev-list.baseline
+}
+
+git init -q
+git config merge.ff false
+
+tick
+git checkout -q -b main
+commit root
+commit c1
+
+git checkout -q -b a
+commit a1
+git checkout -q -b b main
+commit b1
+
+# Criss-cross merge: both `a2` and `b2` merge `a1` and `b1`.
+git checkout -q a
+merge a2 b1
+git checkout -q b
+merge b2 a1
+
+git checkout -q a
+commit a3
+git checkout -q b
+commit b3
+
+# An octopus of three branches forking off `main`, with some of their commits having the same time.
+git checkout -q -b x main
+commit x1
+commit_same_time x2
+git checkout -q -b y main
+commit y1
+git checkout -q -b z main
+commit_same_time z1
+git checkout -q -b octopus x
+merge octopus y z
+
+git checkout -q main
+commit c2
+
+# A long line of history that is merged into a newer one which excludes it.
+git checkout -q -b old main
+commit old1
+commit old2
+commit old3
+git checkout -q -b new b
+commit new1
+merge new2 old
+commit new3
+
+# A commit with a skewed clock, so its parent has a newer commit time than itself.
+git checkout -q -b skewed c2
+commit_skewed skewed1
+git checkout -q -b main2 c2
+commit c3
+merge skewed-merge skewed
+
+baseline a3
+baseline b1..a3
+baseline a3..b3
+baseline b3..a3
+baseline a3...b3
+baseline b3...a3
+baseline a2...b2
+baseline ^a1 ^b1 a3 b3
+baseline a3 b3 ^c1
+baseline a3 b3 c2
+baseline a2^!
+baseline b2^@
+baseline octopus
+baseline octopus ^x1
+baseline octopus...c2
+baseline x2..octopus
+baseline old2..new3
+baseline b3..new3
+baseline new3 ^old3 ^a3
+baseline a3...a3
+baseline c2..root
+baseline skewed-merge
+baseline ^c1 skewed-merge
+baseline c2..skewed-merge
+
+git commit-graph write --no-progress --reachable
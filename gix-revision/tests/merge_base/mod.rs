@@ -0,0 +1,125 @@
+use gix_hash::ObjectId;
+
+use crate::hex_to_id;
+
+struct Baseline {
+    /// The arguments as passed to `git merge-base`, for display.
+    args: String,
+    first: ObjectId,
+    others: Vec<ObjectId>,
+    /// The output of `git merge-base`.
+    best: Option<ObjectId>,
+    /// The output of `git merge-base --all`.
+    all: Vec<ObjectId>,
+}
+
+fn ids(lines: &[&str]) -> Vec<ObjectId> {
+    lines
+        .iter()
+        .flat_map(|line| line.split_ascii_whitespace())
+        .map(hex_to_id)
+        .collect()
+}
+
+fn baselines(dir: &std::path::Path) -> gix_testtools::Result<Vec<Baseline>> {
+    let baseline = std::fs::read_to_string(dir.join("merge-base.baseline"))?;
+    Ok(baseline
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| {
+            let lines: Vec<_> = block.lines().collect();
+            let all_pos = lines.iter().position(|l| *l == "--all").expect("marker is present");
+            let mut inputs = ids(&lines[1..2]);
+            Baseline {
+                args: lines[0].to_owned(),
+                first: inputs.remove(0),
+                others: inputs,
+                best: ids(&lines[2..all_pos]).first().copied(),
+                all: ids(&lines[all_pos + 1..]),
+            }
+        })
+        .collect())
+}
+
+#[test]
+fn all_merge_bases_match_git() -> gix_testtools::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_merge_base_repos.sh")?;
+    let store = gix_odb::at(dir.join(".git").join("objects"))?;
+    let baselines = baselines(&dir)?;
+    assert_eq!(baselines.len(), 12, "all baselines were parsed");
+
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info")))
+            .transpose()?;
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        for Baseline {
+            args,
+            first,
+            others,
+            best,
+            all,
+        } in &baselines
+        {
+            let actual = gix_revision::merge_base(first, others, &mut graph)?;
+            assert_eq!(
+                &actual, all,
+                "`git merge-base --all {args}`, commitgraph: {use_commitgraph}"
+            );
+            assert_eq!(
+                actual.first(),
+                best.as_ref(),
+                "`git merge-base {args}`, commitgraph: {use_commitgraph}"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn criss_cross_merges_have_two_merge_bases() -> gix_testtools::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_merge_base_repos.sh")?;
+    let criss_cross = baselines(&dir)?
+        .into_iter()
+        .find(|b| b.args == "a3 b3")
+        .expect("baseline is present");
+    assert_eq!(criss_cross.all.len(), 2, "a1 and b1 are both best merge bases");
+    Ok(())
+}
+
+#[test]
+fn is_ancestor() -> gix_testtools::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_merge_base_repos.sh")?;
+    let store = gix_odb::at(dir.join(".git").join("objects"))?;
+    let tag = |name: &str| -> ObjectId {
+        let hex = std::fs::read(dir.join(".git").join("refs").join("tags").join(name)).expect("tag exists");
+        ObjectId::from_hex(&hex[..40]).expect("40 bytes hex")
+    };
+
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info")))
+            .transpose()?;
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        for (ancestor, descendant, expected) in [
+            ("root", "a3", true),
+            ("a1", "b3", true),
+            ("b1", "a2", true),
+            ("a3", "a3", true),
+            ("x1", "octopus", true),
+            ("z1", "octopus", true),
+            ("a3", "root", false),
+            ("a2", "b3", false),
+            ("octopus", "c2", false),
+            ("root", "unrelated1", false),
+            ("c2", "octopus", false),
+        ] {
+            assert_eq!(
+                gix_revision::is_ancestor(&tag(ancestor), &tag(descendant), &mut graph)?,
+                expected,
+                "{ancestor} is ancestor of {descendant}, commitgraph: {use_commitgraph}"
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+use gix_hash::ObjectId;
+use gix_revision::{
+    range::{Sorting, Tips},
+    Spec,
+};
+
+use crate::hex_to_id;
+
+struct Baseline {
+    /// The arguments as passed to `git rev-list`.
+    args: String,
+    /// The output of `git rev-list`.
+    by_commit_time: Vec<ObjectId>,
+    /// The output of `git rev-list --date-order`.
+    date_order: Vec<ObjectId>,
+    /// The output of `git rev-list --topo-order`.
+    topo_order: Vec<ObjectId>,
+    /// The output of `git rev-list --count`.
+    count: usize,
+}
+
+fn baselines(dir: &std::path::Path) -> gix_testtools::Result<Vec<Baseline>> {
+    let baseline = std::fs::read_to_string(dir.join("rev-list.baseline"))?;
+    Ok(baseline
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| {
+            let lines: Vec<_> = block.lines().collect();
+            let pos = |marker: &str| lines.iter().position(|l| *l == marker).expect("marker is present");
+            let (date_pos, topo_pos, count_pos) = (pos("--date-order"), pos("--topo-order"), pos("--count"));
+            let ids = |lines: &[&str]| lines.iter().copied().map(hex_to_id).collect();
+            Baseline {
+                args: lines[0].to_owned(),
+                by_commit_time: ids(&lines[1..date_pos]),
+                date_order: ids(&lines[date_pos + 1..topo_pos]),
+                topo_order: ids(&lines[topo_pos + 1..count_pos]),
+                count: lines[count_pos + 1].parse().expect("a number"),
+            }
+        })
+        .collect())
+}
+
+/// Turn each of the space-separated `args` into a spec, with all revisions being tag names.
+fn specs(args: &str, tag: impl Fn(&str) -> ObjectId) -> Vec<Spec> {
+    args.split(' ')
+        .map(|arg| {
+            if let Some((theirs, ours)) = arg.split_once("...") {
+                Spec::Merge {
+                    theirs: tag(theirs),
+                    ours: tag(ours),
+                }
+            } else if let Some((from, to)) = arg.split_once("..") {
+                Spec::Range {
+                    from: tag(from),
+                    to: tag(to),
+                }
+            } else if let Some(name) = arg.strip_prefix('^') {
+                Spec::Exclude(tag(name))
+            } else if let Some(name) = arg.strip_suffix("^@") {
+                Spec::IncludeOnlyParents(tag(name))
+            } else if let Some(name) = arg.strip_suffix("^!") {
+                Spec::ExcludeParents(tag(name))
+            } else {
+                Spec::Include(tag(arg))
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn walk_and_count_match_git() -> gix_testtools::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_rev_list_repos.sh")?;
+    let store = gix_odb::at(dir.join(".git").join("objects"))?;
+    let tag = |name: &str| -> ObjectId {
+        let hex = std::fs::read(dir.join(".git").join("refs").join("tags").join(name)).expect("tag exists");
+        ObjectId::from_hex(&hex[..40]).expect("40 bytes hex")
+    };
+    let baselines = baselines(&dir)?;
+    assert_eq!(baselines.len(), 24, "all baselines were parsed");
+
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&store.store_ref().path().join("info")))
+            .transpose()?;
+        let mut graph = gix_revision::Graph::new(&store, cache);
+        for Baseline {
+            args,
+            by_commit_time,
+            date_order,
+            topo_order,
+            count,
+        } in &baselines
+        {
+            let mut tips = Tips::default();
+            for spec in specs(args, tag) {
+                tips.push_spec(spec, &mut graph)?;
+            }
+            for (sorting, expected) in [
+                (Sorting::ByCommitTime, by_commit_time),
+                (Sorting::DateOrder, date_order),
+                (Sorting::TopoOrder, topo_order),
+            ] {
+                assert_eq!(
+                    &gix_revision::range::walk(&tips, sorting, &mut graph)?,
+                    expected,
+                    "`git rev-list {args}` with {sorting:?}, commitgraph: {use_commitgraph}"
+                );
+            }
+            assert_eq!(
+                gix_revision::range::count(&tips, &mut graph)?,
+                *count,
+                "`git rev-list --count {args}`, commitgraph: {use_commitgraph}"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn tips_must_be_commits() -> gix_testtools::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_rev_list_repos.sh")?;
+    let store = gix_odb::at(dir.join(".git").join("objects"))?;
+    let mut graph = gix_revision::Graph::<gix_revision::graph::Commit<()>>::new(&store, None);
+    let tips = Tips {
+        include: vec![gix_hash::Kind::Sha1.null()],
+        exclude: Vec::new(),
+    };
+    assert!(matches!(
+        gix_revision::range::walk(&tips, Sorting::default(), &mut graph),
+        Err(gix_revision::range::Error::NotACommit { .. })
+    ));
+    assert!(matches!(
+        gix_revision::range::count(&tips, &mut graph),
+        Err(gix_revision::range::Error::NotACommit { .. })
+    ));
+    Ok(())
+}
@@ -85,6 +85,20 @@ fn reflog_by_date_for_given_ref_name() {
     }
 }
 
+#[test]
+fn reflog_by_fuzzy_date_for_given_ref_name() {
+    for spec in ["main@{yesterday}", "main@{last monday}", "main@{noon}", "main@{Jan 3}"] {
+        let rec = parse(spec);
+
+        assert_eq!(rec.get_ref(0), "main");
+        assert!(
+            rec.current_branch_reflog_entry[0].is_some(),
+            "{spec}: approximate dates are understood as well"
+        );
+        assert_eq!(rec.calls, 2, "first the ref, then the reflog entry");
+    }
+}
+
 #[test]
 fn reflog_by_entry_for_given_ref_name() {
     for (spec, expected_ref, expected_entry) in [
@@ -196,6 +196,12 @@ impl<'find, T> Graph<'find, Commit<T>> {
         };
         Ok(self.map.get_mut(&id))
     }
+
+    /// Run `clear` on the data of all commits we have seen, while keeping the commits themselves, which is useful
+    /// to run another traversal on the same graph without having to look up commits again.
+    pub fn clear_commit_data(&mut self, mut clear: impl FnMut(&mut T)) {
+        self.map.values_mut().for_each(|c| clear(&mut c.data));
+    }
 }
 
 /// commit access
@@ -42,6 +42,8 @@ pub struct SpawnProcessOnDemand {
     url: gix_url::Url,
     path: BString,
     ssh_cmd: Option<(OsString, ssh::ProgramKind)>,
+    /// The values of the ssh configuration that apply to the host of `url`.
+    ssh_config: ssh::config::Resolved,
     /// The environment variables to set in the invoked command.
     envs: Vec<(&'static str, String)>,
     ssh_disallow_shell: bool,
@@ -51,11 +53,13 @@ pub struct SpawnProcessOnDemand {
 }
 
 impl SpawnProcessOnDemand {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new_ssh(
         url: gix_url::Url,
         program: impl Into<OsString>,
         path: BString,
         ssh_kind: ssh::ProgramKind,
+        ssh_config: ssh::config::Resolved,
         ssh_disallow_shell: bool,
         version: Protocol,
         trace: bool,
@@ -64,6 +68,7 @@ impl SpawnProcessOnDemand {
             url,
             path,
             ssh_cmd: Some((program.into(), ssh_kind)),
+            ssh_config,
             envs: Default::default(),
             ssh_disallow_shell,
             child: None,
@@ -78,8 +83,10 @@ impl SpawnProcessOnDemand {
                 .expect("valid url"),
             path,
             ssh_cmd: None,
-            envs: (version != Protocol::V1)
-                .then(|| vec![("GIT_PROTOCOL", format!("version={}", version as usize))])
+            ssh_config: Default::default(),
+            envs: version
+                .request_parameter()
+                .map(|version| vec![("GIT_PROTOCOL", version.to_owned())])
                 .unwrap_or_default(),
             ssh_disallow_shell: false,
             child: None,
@@ -215,9 +222,15 @@ impl client::Transport for SpawnProcessOnDemand {
     ) -> Result<SetServiceResponse<'_>, client::Error> {
         let (mut cmd, ssh_kind, cmd_name) = match &self.ssh_cmd {
             Some((command, kind)) => (
-                kind.prepare_invocation(command, &self.url, self.desired_version, self.ssh_disallow_shell)
-                    .map_err(client::Error::SshInvocation)?
-                    .stderr(Stdio::piped()),
+                kind.prepare_invocation(
+                    command,
+                    &self.url,
+                    &self.ssh_config,
+                    self.desired_version,
+                    self.ssh_disallow_shell,
+                )
+                .map_err(client::Error::SshInvocation)?
+                .stderr(Stdio::piped()),
                 Some(*kind),
                 Cow::Owned(command.to_owned()),
             ),
@@ -321,6 +334,8 @@ mod tests {
                         command: Some("unrecognized".into()),
                         disallow_shell: false,
                         kind: None,
+                        ignore_ssh_config: true,
+                        ssh_config_path: None,
                     };
                     assert!(matches!(
                         ssh::connect(url, Protocol::V1, options, false),
@@ -105,6 +105,33 @@ impl curl::easy::Handler for Handler {
         };
         true
     }
+
+    /// Print what `curl` tells us in verbose mode like it would, but without the values of sensitive headers.
+    fn debug(&mut self, kind: curl::easy::InfoType, data: &[u8]) {
+        use curl::easy::InfoType;
+        let prefix = match kind {
+            InfoType::HeaderIn => "<",
+            InfoType::HeaderOut => ">",
+            InfoType::DataIn | InfoType::SslDataIn => "{",
+            InfoType::DataOut | InfoType::SslDataOut => "}",
+            _ => "*",
+        };
+        let mut out = io::stderr().lock();
+        match std::str::from_utf8(data) {
+            Ok(text) if matches!(kind, InfoType::HeaderIn | InfoType::HeaderOut) => {
+                for line in text.split_inclusive('\n') {
+                    let (header, line_ending) = line.split_at(line.trim_end_matches(['\r', '\n']).len());
+                    write!(out, "{prefix} {}{line_ending}", http::redact_header(header)).ok();
+                }
+            }
+            Ok(text) => {
+                write!(out, "{prefix} {text}").ok();
+            }
+            Err(_) => {
+                writeln!(out, "{prefix} ({} bytes of data)", data.len()).ok();
+            }
+        }
+    }
 }
 
 pub struct Request {
@@ -145,6 +172,7 @@ pub fn new() -> (
             config:
                 http::Options {
                     extra_headers,
+                    request_headers: _,
                     follow_redirects,
                     low_speed_limit_bytes_per_second,
                     low_speed_time_seconds,
@@ -32,7 +32,7 @@ pub mod curl;
 
 /// The experimental `reqwest` backend.
 ///
-/// It doesn't support any of the shared http options yet, but can be seen as example on how to integrate blocking `http` backends.
+/// It only supports the shared http options for proxies and headers, but can be seen as example on how to integrate blocking `http` backends.
 /// There is also nothing that would prevent it from becoming a fully-featured HTTP backend except for demand and time.
 #[cfg(feature = "http-client-reqwest")]
 pub mod reqwest;
@@ -46,6 +46,9 @@ pub mod options {
     pub type AuthenticateFn =
         dyn FnMut(gix_credentials::helper::Action) -> gix_credentials::protocol::Result + Send + Sync;
 
+    /// A function to obtain additional headers for a request to the given URL, each like `name: value`.
+    pub type RequestHeadersFn = dyn FnMut(&str) -> Vec<String> + Send + Sync;
+
     /// Possible settings for the `http.followRedirects` configuration option.
     #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
     pub enum FollowRedirects {
@@ -129,6 +132,11 @@ pub struct Options {
     ///
     /// Refers to `http.extraHeader` multi-var.
     pub extra_headers: Vec<String>,
+    /// A function to call with the URL of each request, for the `info/refs` request as well as for the one posting to the service,
+    /// to obtain headers to add to it in addition to `extra_headers`.
+    ///
+    /// The returned headers have to be valid in the same way as `extra_headers`.
+    pub request_headers: Option<Arc<Mutex<options::RequestHeadersFn>>>,
     /// How to handle redirects.
     ///
     /// Refers to `http.followRedirects`.
@@ -149,20 +157,22 @@ pub struct Options {
     /// Refers to `http.proxy`.
     pub proxy: Option<String>,
     /// The comma-separated list of hosts to not send through the `proxy`, or `*` to entirely disable all proxying.
+    ///
+    /// Hosts match if they are equal to an entry or are a sub-domain of it, so `example.com` also matches `git.example.com`.
     pub no_proxy: Option<String>,
     /// The way to authenticate against the proxy if the `proxy` field contains a username.
     ///
     /// Refers to `http.proxyAuthMethod`.
     pub proxy_auth_method: options::ProxyAuthMethod,
-    /// If authentication is needed for the proxy as its URL contains a username, this method must be set to provide a password
-    /// for it before making the request, and to store it if the connection succeeds.
+    /// If authentication is needed for the proxy as its URL contains a username without password, this method must be set to provide
+    /// a password for it before making the request, and to store it if the connection succeeds.
     pub proxy_authenticate: Option<(
         gix_credentials::helper::Action,
         Arc<std::sync::Mutex<options::AuthenticateFn>>,
     )>,
     /// The `HTTP` `USER_AGENT` string presented to an `HTTP` server, notably not the user agent present to the `git` server.
     ///
-    /// If not overridden, it defaults to the user agent provided by `curl`, which is a deviation from how `git` handles this.
+    /// If not overridden, it defaults to `git/oxide-<version>` with the version of this crate.
     /// Thus it's expected from the callers to set it to their application, or use higher-level crates which make it easy to do this
     /// more correctly.
     ///
@@ -195,6 +205,7 @@ impl Default for Options {
     fn default() -> Self {
         Options {
             extra_headers: vec![],
+            request_headers: None,
             follow_redirects: Default::default(),
             low_speed_limit_bytes_per_second: 0,
             low_speed_time_seconds: 0,
@@ -224,7 +235,8 @@ pub type Impl = reqwest::Remote;
 /// A transport for supporting arbitrary http clients by abstracting interactions with them into the [Http] trait.
 pub struct Transport<H: Http> {
     url: String,
-    user_agent_header: &'static str,
+    user_agent_header: Cow<'static, str>,
+    request_headers: Option<Arc<Mutex<options::RequestHeadersFn>>>,
     desired_version: Protocol,
     actual_version: Protocol,
     http: H,
@@ -248,7 +260,8 @@ impl<H: Http> Transport<H> {
             });
         Transport {
             url: url.to_bstring().to_string(),
-            user_agent_header: concat!("User-Agent: git/oxide-", env!("CARGO_PKG_VERSION")),
+            user_agent_header: Cow::Borrowed(concat!("User-Agent: git/oxide-", env!("CARGO_PKG_VERSION"))),
+            request_headers: None,
             desired_version,
             actual_version: Default::default(),
             service: None,
@@ -312,6 +325,41 @@ impl<H: Http> Transport<H> {
         }
         Ok(())
     }
+
+    fn add_request_headers(&self, url: &str, headers: &mut Vec<Cow<'_, str>>) {
+        if let Some(request_headers) = &self.request_headers {
+            headers.extend(
+                request_headers.lock().expect("no panics in other threads")(url)
+                    .into_iter()
+                    .map(Cow::Owned),
+            );
+        }
+    }
+}
+
+/// The names of headers whose values are confidential and must not be shown in traces.
+const SENSITIVE_HEADERS: &[&str] = &["authorization", "proxy-authorization", "cookie"];
+
+/// Return `header_line`, like `name: value`, with its value redacted if the header is known to be sensitive, for use in traces.
+///
+/// Like `git`, the authentication scheme of `Authorization` headers is retained.
+pub fn redact_header(header_line: &str) -> Cow<'_, str> {
+    let Some((name, value)) = header_line.split_once(':') else {
+        return Cow::Borrowed(header_line);
+    };
+    if !SENSITIVE_HEADERS
+        .iter()
+        .any(|sensitive| name.trim().eq_ignore_ascii_case(sensitive))
+    {
+        return Cow::Borrowed(header_line);
+    }
+    let value = value.trim_start();
+    match value.split_once(' ') {
+        Some((scheme, _credentials)) if name.trim().to_ascii_lowercase().ends_with("authorization") => {
+            Cow::Owned(format!("{name}: {scheme} <redacted>"))
+        }
+        _ => Cow::Owned(format!("{name}: <redacted>")),
+    }
 }
 
 fn append_url(base: &str, suffix: &str) -> String {
@@ -338,18 +386,16 @@ impl<H: Http> client::TransportWithoutIO for Transport<H> {
         let service = self.service.expect("handshake() must have been called first");
         let url = append_url(&self.url, service.as_str());
         let static_headers = &[
-            Cow::Borrowed(self.user_agent_header),
+            self.user_agent_header.clone(),
             Cow::Owned(format!("Content-Type: application/x-{}-request", service.as_str())),
             format!("Accept: application/x-{}-result", service.as_str()).into(),
         ];
         let mut dynamic_headers = Vec::new();
         self.add_basic_auth_if_present(&mut dynamic_headers)?;
-        if self.actual_version != Protocol::V1 {
-            dynamic_headers.push(Cow::Owned(format!(
-                "Git-Protocol: version={}",
-                self.actual_version as usize
-            )));
+        if let Some(version) = self.actual_version.request_parameter() {
+            dynamic_headers.push(Cow::Owned(format!("Git-Protocol: {version}")));
         }
+        self.add_request_headers(&url, &mut dynamic_headers);
 
         let PostResponse {
             headers,
@@ -388,6 +434,12 @@ impl<H: Http> client::TransportWithoutIO for Transport<H> {
     }
 
     fn configure(&mut self, config: &dyn Any) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        if let Some(options) = config.downcast_ref::<Options>() {
+            if let Some(user_agent) = &options.user_agent {
+                self.user_agent_header = Cow::Owned(format!("User-Agent: {user_agent}"));
+            }
+            self.request_headers = options.request_headers.clone();
+        }
         self.http.configure(config)
     }
 }
@@ -399,17 +451,19 @@ impl<H: Http> client::Transport for Transport<H> {
         extra_parameters: &'a [(&'a str, Option<&'a str>)],
     ) -> Result<client::SetServiceResponse<'_>, client::Error> {
         let url = append_url(self.url.as_ref(), &format!("info/refs?service={}", service.as_str()));
-        let static_headers = [Cow::Borrowed(self.user_agent_header)];
+        let static_headers = [self.user_agent_header.clone()];
         let mut dynamic_headers = Vec::<Cow<'_, str>>::new();
-        if self.desired_version != Protocol::V1 || !extra_parameters.is_empty() {
-            let mut parameters = if self.desired_version != Protocol::V1 {
-                let mut p = format!("version={}", self.desired_version as usize);
-                if !extra_parameters.is_empty() {
-                    p.push(':');
+        let version = self.desired_version.request_parameter();
+        if version.is_some() || !extra_parameters.is_empty() {
+            let mut parameters = match version {
+                Some(version) => {
+                    let mut p = version.to_owned();
+                    if !extra_parameters.is_empty() {
+                        p.push(':');
+                    }
+                    p
                 }
-                p
-            } else {
-                String::new()
+                None => String::new(),
             };
             parameters.push_str(
                 &extra_parameters
@@ -424,6 +478,7 @@ impl<H: Http> client::Transport for Transport<H> {
             dynamic_headers.push(format!("Git-Protocol: {parameters}").into());
         }
         self.add_basic_auth_if_present(&mut dynamic_headers)?;
+        self.add_request_headers(&url, &mut dynamic_headers);
         let GetResponse { headers, body } =
             self.http
                 .get(url.as_ref(), &self.url, static_headers.iter().chain(&dynamic_headers))?;
@@ -21,6 +21,8 @@ pub enum Error {
     ConfigureRequest(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
     #[error(transparent)]
     Redirect(#[from] redirect::Error),
+    #[error(transparent)]
+    Authenticate(#[from] gix_credentials::protocol::Error),
 }
 
 impl crate::IsSpuriousError for Error {
@@ -35,6 +37,7 @@ impl crate::IsSpuriousError for Error {
 }
 
 impl Default for Remote {
+    #[allow(clippy::result_large_err)]
     fn default() -> Self {
         let (req_send, req_recv) = std::sync::mpsc::sync_channel(0);
         let (res_send, res_recv) = std::sync::mpsc::sync_channel(0);
@@ -45,38 +48,7 @@ impl Default for Remote {
 
             // We may error while configuring, which is expected as part of the internal protocol. The error will be
             // received and the sender of the request might restart us.
-            let client = reqwest::blocking::ClientBuilder::new()
-                .connect_timeout(std::time::Duration::from_secs(20))
-                .http1_title_case_headers()
-                .redirect(reqwest::redirect::Policy::custom({
-                    let allow_redirects = allow_redirects.clone();
-                    move |attempt| {
-                        if allow_redirects.load(atomic::Ordering::Relaxed) {
-                            let curr_url = attempt.url();
-                            let prev_urls = attempt.previous();
-
-                            match prev_urls.first() {
-                                Some(prev_url) if prev_url.host_str() != curr_url.host_str() => {
-                                    // git does not want to be redirected to a different host.
-                                    attempt.stop()
-                                }
-                                _ => {
-                                    // emulate default git behaviour which relies on curl default behaviour apparently.
-                                    const CURL_DEFAULT_REDIRS: usize = 50;
-                                    if prev_urls.len() >= CURL_DEFAULT_REDIRS {
-                                        attempt.error("too many redirects")
-                                    } else {
-                                        attempt.follow()
-                                    }
-                                }
-                            }
-                        } else {
-                            attempt.stop()
-                        }
-                    }
-                }))
-                .build()?;
-
+            let mut client = None::<(ProxySettings, reqwest::blocking::Client)>;
             for Request {
                 url,
                 base_url,
@@ -85,6 +57,24 @@ impl Default for Remote {
                 config,
             } in req_recv
             {
+                let mut proxy_auth_action = None;
+                let settings = ProxySettings {
+                    credentials: match (config.proxy.as_deref(), config.proxy_authenticate.clone()) {
+                        (Some(proxy), Some((obtain_creds_action, authenticate))) if !proxy.is_empty() => {
+                            let creds = authenticate.lock().expect("no panics in other threads")(obtain_creds_action)?
+                                .expect("action to fetch credentials");
+                            proxy_auth_action = Some((creds.next, authenticate));
+                            Some(creds.identity)
+                        }
+                        _ => None,
+                    },
+                    proxy: config.proxy.clone(),
+                    no_proxy: config.no_proxy.clone(),
+                };
+                if !matches!(&client, Some((previous, _)) if *previous == settings) {
+                    client = Some((settings.clone(), new_client(&allow_redirects, &settings)?));
+                }
+                let (_, client) = client.as_ref().expect("just set");
                 let effective_url = redirect::swap_tails(redirected_base_url.as_deref(), &base_url, url.clone());
                 let mut req_builder = if upload_body_kind.is_some() {
                     client.post(&effective_url)
@@ -139,8 +129,16 @@ impl Default for Remote {
                     .execute(req)
                     .and_then(reqwest::blocking::Response::error_for_status)
                 {
-                    Ok(res) => res,
+                    Ok(res) => {
+                        if let Some((action, authenticate)) = proxy_auth_action {
+                            authenticate.lock().expect("no panics in other threads")(action.store())?;
+                        }
+                        res
+                    }
                     Err(err) => {
+                        if let Some((action, authenticate)) = proxy_auth_action {
+                            authenticate.lock().expect("no panics in other threads")(action.erase()).ok();
+                        }
                         let (kind, err) = match err.status() {
                             Some(status) => {
                                 let kind = if status == reqwest::StatusCode::UNAUTHORIZED {
@@ -202,6 +200,65 @@ impl Default for Remote {
     }
 }
 
+/// The settings a client was built with which can't be changed per request, to know when to build a new one.
+#[derive(Clone, PartialEq, Eq)]
+struct ProxySettings {
+    proxy: Option<String>,
+    no_proxy: Option<String>,
+    credentials: Option<gix_sec::identity::Account>,
+}
+
+#[allow(clippy::result_large_err)]
+fn new_client(
+    allow_redirects: &Arc<atomic::AtomicBool>,
+    settings: &ProxySettings,
+) -> Result<reqwest::blocking::Client, Error> {
+    let mut builder = reqwest::blocking::ClientBuilder::new()
+        .connect_timeout(std::time::Duration::from_secs(20))
+        .http1_title_case_headers()
+        .redirect(reqwest::redirect::Policy::custom({
+            let allow_redirects = allow_redirects.clone();
+            move |attempt| {
+                if allow_redirects.load(atomic::Ordering::Relaxed) {
+                    let curr_url = attempt.url();
+                    let prev_urls = attempt.previous();
+
+                    match prev_urls.first() {
+                        Some(prev_url) if prev_url.host_str() != curr_url.host_str() => {
+                            // git does not want to be redirected to a different host.
+                            attempt.stop()
+                        }
+                        _ => {
+                            // emulate default git behaviour which relies on curl default behaviour apparently.
+                            const CURL_DEFAULT_REDIRS: usize = 50;
+                            if prev_urls.len() >= CURL_DEFAULT_REDIRS {
+                                attempt.error("too many redirects")
+                            } else {
+                                attempt.follow()
+                            }
+                        }
+                    }
+                } else {
+                    attempt.stop()
+                }
+            }
+        }));
+    match settings.proxy.as_deref() {
+        // An empty proxy disables proxying entirely, even if environment variables are set.
+        Some("") => builder = builder.no_proxy(),
+        Some(proxy) => {
+            let mut proxy = reqwest::Proxy::all(proxy)?
+                .no_proxy(settings.no_proxy.as_deref().and_then(reqwest::NoProxy::from_string));
+            if let Some(creds) = &settings.credentials {
+                proxy = proxy.basic_auth(&creds.username, &creds.password);
+            }
+            builder = builder.proxy(proxy);
+        }
+        None => {}
+    }
+    Ok(builder.build()?)
+}
+
 /// utilities
 impl Remote {
     fn restore_thread_after_failure(&mut self) -> http::Error {
@@ -226,8 +283,12 @@ impl Remote {
         upload_body_kind: Option<PostBodyDataKind>,
     ) -> Result<http::PostResponse<pipe::Reader, pipe::Reader, pipe::Writer>, http::Error> {
         let mut header_map = reqwest::header::HeaderMap::new();
-        for header_line in headers {
-            let header_line = header_line.as_ref();
+        let headers: Vec<_> = headers.into_iter().collect();
+        for header_line in headers
+            .iter()
+            .map(AsRef::as_ref)
+            .chain(self.config.extra_headers.iter().map(String::as_str))
+        {
             let colon_pos = header_line
                 .find(':')
                 .expect("header line must contain a colon to separate key and value");
@@ -238,7 +299,7 @@ impl Remote {
                 .ok()
                 .zip(reqwest::header::HeaderValue::try_from(value.trim()).ok())
             {
-                Some((key, val)) => header_map.insert(key, val),
+                Some((key, val)) => header_map.append(key, val),
                 None => continue,
             };
         }
@@ -0,0 +1,278 @@
+use std::path::{Path, PathBuf};
+
+use bstr::{BStr, ByteSlice};
+
+/// An ssh configuration file like `~/.ssh/config`, as far as it's needed to resolve the host to connect to,
+/// as well as the user, port and identities to use.
+///
+/// Only `Host` and `Match` blocks along with the `HostName`, `User`, `Port` and `IdentityFile` keywords are
+/// relevant to us, and everything else is ignored. Of the `Match` criteria, only `all`, `host`, `originalhost`,
+/// `user` and `localuser` are supported, and blocks with other criteria like `exec` never match.
+/// `Include` directives aren't followed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    blocks: Vec<Block>,
+}
+
+/// The values of an ssh [configuration](Config) that apply to a particular host.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Resolved {
+    /// The name of the host to actually connect to, as set by `HostName`.
+    pub host_name: Option<String>,
+    /// The user to connect as, as set by `User`.
+    pub user: Option<String>,
+    /// The port to connect to, as set by `Port`.
+    pub port: Option<u16>,
+    /// The files with identities to try, as set by `IdentityFile`, in order and with `~` and tokens like `%h` expanded.
+    pub identity_files: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Condition {
+    /// Patterns matched against the original host name, as in `Host`.
+    Host(Vec<String>),
+    /// All criteria have to match, as in `Match`.
+    Match(Vec<Criterion>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Criterion {
+    negated: bool,
+    kind: CriterionKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CriterionKind {
+    All,
+    Host(String),
+    OriginalHost(String),
+    User(String),
+    LocalUser(String),
+    /// A criterion we can't evaluate, which never matches.
+    Unsupported,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Block {
+    condition: Condition,
+    /// Lower-cased keywords with their first argument.
+    options: Vec<(String, String)>,
+}
+
+/// Initialization
+impl Config {
+    /// Parse the ssh configuration in `input`. Lines we don't understand are ignored, just like keywords that don't
+    /// matter to us.
+    pub fn from_bytes(input: &[u8]) -> Self {
+        let mut blocks = vec![Block {
+            condition: Condition::Host(vec!["*".into()]),
+            options: Vec::new(),
+        }];
+        for line in input.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(b"#") {
+                continue;
+            }
+            let keyword_end = line
+                .iter()
+                .position(|b| b.is_ascii_whitespace() || *b == b'=')
+                .unwrap_or(line.len());
+            let (keyword, rest) = line.split_at(keyword_end);
+            let rest = rest.trim_start();
+            let rest = rest.strip_prefix(b"=").unwrap_or(rest);
+            let Ok(keyword) = keyword.to_str() else {
+                continue;
+            };
+            let args = arguments(rest.as_bstr());
+            match keyword.to_ascii_lowercase().as_str() {
+                "host" => blocks.push(Block {
+                    condition: Condition::Host(args),
+                    options: Vec::new(),
+                }),
+                "match" => blocks.push(Block {
+                    condition: Condition::Match(criteria(args)),
+                    options: Vec::new(),
+                }),
+                keyword => {
+                    if let Some(value) = args.into_iter().next() {
+                        blocks
+                            .last_mut()
+                            .expect("always one block")
+                            .options
+                            .push((keyword.to_owned(), value));
+                    }
+                }
+            }
+        }
+        Config { blocks }
+    }
+
+    /// Read the ssh configuration at `path`, or return an empty configuration if it doesn't exist.
+    pub fn at(path: &Path) -> std::io::Result<Self> {
+        match std::fs::read(path) {
+            Ok(input) => Ok(Self::from_bytes(&input)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Access
+impl Config {
+    /// Return the values that apply when connecting to `host`, as `user` if this one is known, like `ssh` would.
+    ///
+    /// Blocks are tried in order, and the first value obtained for a keyword wins, except for `IdentityFile` whose
+    /// values are all collected.
+    pub fn resolve(&self, host: &str, user: Option<&str>) -> Resolved {
+        let mut out = Resolved::default();
+        let local_user = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok();
+        let mut identity_files = Vec::new();
+        for block in &self.blocks {
+            let target_host = out.host_name.as_deref().unwrap_or(host);
+            let target_user = user.or(out.user.as_deref());
+            let matches = match &block.condition {
+                Condition::Host(patterns) => patterns_match(patterns.iter().map(String::as_str), host),
+                Condition::Match(criteria) => criteria.iter().all(|criterion| {
+                    let matches = match &criterion.kind {
+                        CriterionKind::All => true,
+                        CriterionKind::Host(patterns) => patterns_match(patterns.split(','), target_host),
+                        CriterionKind::OriginalHost(patterns) => patterns_match(patterns.split(','), host),
+                        CriterionKind::User(patterns) => {
+                            matches!(target_user, Some(user) if patterns_match(patterns.split(','), user))
+                        }
+                        CriterionKind::LocalUser(patterns) => {
+                            matches!(local_user.as_deref(), Some(user) if patterns_match(patterns.split(','), user))
+                        }
+                        CriterionKind::Unsupported => return false,
+                    };
+                    matches != criterion.negated
+                }),
+            };
+            if !matches {
+                continue;
+            }
+            for (keyword, value) in &block.options {
+                match keyword.as_str() {
+                    "hostname" if out.host_name.is_none() => {
+                        out.host_name = Some(expand_tokens(value, host, None, None));
+                    }
+                    "user" if out.user.is_none() => out.user = Some(value.clone()),
+                    "port" if out.port.is_none() => out.port = value.parse().ok(),
+                    "identityfile" => identity_files.push(value.clone()),
+                    _ => {}
+                }
+            }
+        }
+        let user = user.or(out.user.as_deref());
+        out.identity_files = identity_files
+            .iter()
+            .map(|file| {
+                let home = gix_path::env::home_dir();
+                let file = expand_tokens(file, out.host_name.as_deref().unwrap_or(host), user, home.as_deref());
+                match (file.strip_prefix("~/"), home) {
+                    (Some(relative), Some(home)) => home.join(relative),
+                    _ => file.into(),
+                }
+            })
+            .collect();
+        out
+    }
+}
+
+/// Split `input` into whitespace-separated arguments, where double-quotes can be used to retain whitespace.
+fn arguments(input: &BStr) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = None::<String>;
+    let mut in_quotes = false;
+    for c in input.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.get_or_insert_with(String::new);
+            }
+            c if c.is_whitespace() && !in_quotes => out.extend(current.take()),
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    out.extend(current);
+    out
+}
+
+fn criteria(args: Vec<String>) -> Vec<Criterion> {
+    let mut out = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let lowercase = arg.to_ascii_lowercase();
+        let (negated, name) = match lowercase.strip_prefix('!') {
+            Some(name) => (true, name),
+            None => (false, lowercase.as_str()),
+        };
+        let mut value = || args.next().unwrap_or_default();
+        let kind = match name {
+            "all" => CriterionKind::All,
+            "host" => CriterionKind::Host(value()),
+            "originalhost" => CriterionKind::OriginalHost(value()),
+            "user" => CriterionKind::User(value()),
+            "localuser" => CriterionKind::LocalUser(value()),
+            "canonical" | "final" => CriterionKind::Unsupported,
+            _ => {
+                let _ = value();
+                CriterionKind::Unsupported
+            }
+        };
+        out.push(Criterion { negated, kind });
+    }
+    out
+}
+
+/// Return `true` if `name` matches any of `patterns` and none of the negated ones, like `ssh` does.
+fn patterns_match<'a>(patterns: impl IntoIterator<Item = &'a str>, name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    let mut matched = false;
+    for pattern in patterns {
+        let pattern = pattern.to_ascii_lowercase();
+        match pattern.strip_prefix('!') {
+            Some(negated) => {
+                if wildcard_match(negated.as_bytes(), name.as_bytes()) {
+                    return false;
+                }
+            }
+            None => matched |= wildcard_match(pattern.as_bytes(), name.as_bytes()),
+        }
+    }
+    matched
+}
+
+/// Match `name` against `pattern`, where `*` matches any amount of characters and `?` matches exactly one.
+fn wildcard_match(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| wildcard_match(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && wildcard_match(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && wildcard_match(rest, &name[1..]),
+    }
+}
+
+/// Replace `%h` with `host`, `%r` with `user`, `%d` with `home` and `%%` with `%`, leaving unknown tokens as they are.
+fn expand_tokens(value: &str, host: &str, user: Option<&str>, home: Option<&Path>) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        match (chars.next(), user, home) {
+            (Some('%'), _, _) => out.push('%'),
+            (Some('h'), _, _) => out.push_str(host),
+            (Some('r'), Some(user), _) => out.push_str(user),
+            (Some('d'), _, Some(home)) => out.push_str(&home.to_string_lossy()),
+            (Some(other), _, _) => {
+                out.push('%');
+                out.push(other);
+            }
+            (None, _, _) => out.push('%'),
+        }
+    }
+    out
+}
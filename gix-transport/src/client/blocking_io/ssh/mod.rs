@@ -12,6 +12,11 @@ pub enum Error {
     UnsupportedScheme(gix_url::Url),
     #[error("Host name '{host}' could be mistaken for a command-line argument")]
     AmbiguousHostName { host: String },
+    #[error("Could not read the ssh configuration at '{}'", path.display())]
+    ReadSshConfig {
+        source: std::io::Error,
+        path: std::path::PathBuf,
+    },
 }
 
 impl crate::IsSpuriousError for Error {}
@@ -35,6 +40,10 @@ pub enum ProgramKind {
 
 mod program_kind;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod config;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod invocation {
@@ -61,7 +70,10 @@ pub mod invocation {
 ///
 #[allow(clippy::empty_docs)]
 pub mod connect {
-    use std::ffi::{OsStr, OsString};
+    use std::{
+        ffi::{OsStr, OsString},
+        path::PathBuf,
+    };
 
     use crate::client::ssh::ProgramKind;
 
@@ -79,6 +91,15 @@ pub mod connect {
         /// when invoking the program.
         /// If unset, the `program` basename determines the variant, or an invocation of the `command` itself.
         pub kind: Option<ProgramKind>,
+        /// If `true`, the ssh configuration isn't read, leaving it entirely to the ssh program to interpret it.
+        ///
+        /// Otherwise, for programs that can't read it by themselves, which are `plink`, `putty` and `tortoiseplink`,
+        /// the host name, user, port and identity files are resolved using the `Host` and `Match` blocks of the
+        /// [ssh configuration](Self::ssh_config_path()) and passed on the command line.
+        /// `ssh` always interprets its configuration by itself.
+        pub ignore_ssh_config: bool,
+        /// The ssh configuration file to read instead of `~/.ssh/config`.
+        pub ssh_config_path: Option<PathBuf>,
     }
 
     impl Options {
@@ -89,6 +110,17 @@ pub mod connect {
                 .or_else(|| self.kind.and_then(|kind| kind.exe()))
                 .unwrap_or_else(|| OsStr::new("ssh"))
         }
+
+        /// Return the path to the ssh configuration file to read, defaulting to `~/.ssh/config`, or `None` if it
+        /// [shouldn't be read](Self::ignore_ssh_config) or the home directory isn't known.
+        pub fn ssh_config_path(&self) -> Option<PathBuf> {
+            if self.ignore_ssh_config {
+                return None;
+            }
+            self.ssh_config_path
+                .clone()
+                .or_else(|| gix_path::env::home_dir().map(|home| home.join(".ssh").join("config")))
+        }
     }
 }
 
@@ -96,6 +128,8 @@ pub mod connect {
 ///
 /// The optional `user` identifies the user's account to which to connect, while `port` allows to specify non-standard
 /// ssh ports.
+/// Unless [ignored](connect::Options::ignore_ssh_config), the ssh configuration can change the host, and provide the
+/// user, the port and identity files if these aren't set already, for programs like `plink` that can't read it themselves.
 ///
 /// The `desired_version` is the preferred protocol version when establishing the connection, but note that it can be
 /// downgraded by servers not supporting it.
@@ -134,12 +168,20 @@ pub fn connect(
         };
     }
 
+    let ssh_config = match options.ssh_config_path().filter(|_| kind.needs_resolved_ssh_config()) {
+        Some(path) => config::Config::at(&path)
+            .map_err(|source| Error::ReadSshConfig { source, path })?
+            .resolve(url.host().expect("checked"), url.user()),
+        None => Default::default(),
+    };
+
     let path = gix_url::expand_path::for_shell(url.path.clone());
     Ok(blocking_io::file::SpawnProcessOnDemand::new_ssh(
         url,
         ssh_cmd,
         path,
         kind,
+        ssh_config,
         options.disallow_shell,
         desired_version,
         trace,
@@ -21,11 +21,21 @@ impl ProgramKind {
         }))
     }
 
+    /// Return `true` if this program can't read the ssh configuration by itself, so we have to resolve it and pass
+    /// the host name, user, port and identity files on the command line.
+    pub(crate) fn needs_resolved_ssh_config(&self) -> bool {
+        matches!(
+            self,
+            ProgramKind::Plink | ProgramKind::Putty | ProgramKind::TortoisePlink
+        )
+    }
+
     /// Prepare all information needed to invoke the ssh command
     pub(crate) fn prepare_invocation(
         &self,
         ssh_cmd: &OsStr,
         url: &gix_url::Url,
+        ssh_config: &ssh::config::Resolved,
         desired_version: Protocol,
         disallow_shell: bool,
     ) -> Result<gix_command::Prepare, ssh::invocation::Error> {
@@ -33,12 +43,14 @@ impl ProgramKind {
         if disallow_shell {
             prepare.use_shell = false;
         }
+        // `ssh` interprets its configuration by itself, so it's only applied for programs that can't read it.
+        let ssh_config = self.needs_resolved_ssh_config().then_some(ssh_config);
         match self {
             ProgramKind::Ssh => {
-                if desired_version != Protocol::V1 {
+                if let Some(version) = desired_version.request_parameter() {
                     prepare = prepare
                         .args(["-o", "SendEnv=GIT_PROTOCOL"])
-                        .env("GIT_PROTOCOL", format!("version={}", desired_version as usize))
+                        .env("GIT_PROTOCOL", version)
                 }
                 if let Some(port) = url.port {
                     prepare = prepare.arg(format!("-p{port}"));
@@ -48,10 +60,14 @@ impl ProgramKind {
                 if *self == ProgramKind::TortoisePlink {
                     prepare = prepare.arg("-batch");
                 }
-                if let Some(port) = url.port {
+                // Values in the URL take precedence, just like arguments passed to `ssh` take precedence over its configuration.
+                if let Some(port) = url.port.or(ssh_config.and_then(|config| config.port)) {
                     prepare = prepare.arg("-P");
                     prepare = prepare.arg(port.to_string());
                 }
+                for identity_file in ssh_config.iter().flat_map(|config| &config.identity_files) {
+                    prepare = prepare.arg("-i").arg(identity_file.as_os_str());
+                }
             }
             ProgramKind::Simple => {
                 if url.port.is_some() {
@@ -63,7 +79,17 @@ impl ProgramKind {
             }
         };
 
-        let host_maybe_with_user_as_ssh_arg = match (url.user_as_argument(), url.host_as_argument()) {
+        let user = match url.user_as_argument() {
+            Absent => ssh_config
+                .and_then(|config| config.user.as_deref())
+                .map_or(Absent, as_argument),
+            user => user,
+        };
+        let host = match ssh_config.and_then(|config| config.host_name.as_deref()) {
+            Some(host) => as_argument(host),
+            None => url.host_as_argument(),
+        };
+        let host_maybe_with_user_as_ssh_arg = match (user, host) {
             (Usable(user), Usable(host)) => format!("{user}@{host}"),
             (Usable(user), Dangerous(host)) => format!("{user}@{host}"), // The `user@` makes it safe.
             (Absent, Usable(host)) => host.into(),
@@ -113,6 +139,15 @@ impl ProgramKind {
     }
 }
 
+/// Classify `value` from the ssh configuration like [`gix_url::Url::host_as_argument()`] would.
+fn as_argument(value: &str) -> gix_url::ArgumentSafety<'_> {
+    if value.starts_with('-') {
+        Dangerous(value)
+    } else {
+        Usable(value)
+    }
+}
+
 impl<'a> From<&'a OsStr> for ProgramKind {
     fn from(v: &'a OsStr) -> Self {
         let p = std::path::Path::new(v);
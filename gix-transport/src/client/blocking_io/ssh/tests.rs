@@ -42,6 +42,96 @@ mod options {
     }
 }
 
+mod config {
+    use std::path::PathBuf;
+
+    use crate::client::ssh::config::{Config, Resolved};
+
+    const CONFIG: &[u8] = br#"
+# global values apply to all hosts, but the first value wins
+IdentityFile ~/.ssh/global
+
+Host alias other-alias
+    HostName real.example.com
+    User alias-user
+    Port 2222
+    IdentityFile "/keys/with space"
+
+Host *.example.com !secret.example.com
+    Port=22
+    User wildcard-user
+    IdentityFile /keys/%h-%r
+
+Match host real.example.com user alias-user
+    IdentityFile /keys/match
+
+Match originalhost alias exec "false"
+    IdentityFile /keys/never
+
+Host h?st
+    hostname %h.internal
+"#;
+
+    fn resolve(host: &str, user: Option<&str>) -> Resolved {
+        Config::from_bytes(CONFIG).resolve(host, user)
+    }
+
+    #[test]
+    fn aliases_are_resolved_and_the_first_value_wins() {
+        let home = gix_path::env::home_dir().expect("home is set in tests");
+        assert_eq!(
+            resolve("alias", None),
+            Resolved {
+                host_name: Some("real.example.com".into()),
+                user: Some("alias-user".into()),
+                port: Some(2222),
+                identity_files: vec![
+                    home.join(".ssh/global"),
+                    "/keys/with space".into(),
+                    "/keys/match".into(),
+                ],
+            },
+            "`Match host` sees the resolved host name, and blocks with unsupported criteria never match"
+        );
+    }
+
+    #[test]
+    fn wildcards_and_negations() {
+        let resolved = resolve("git.example.com", Some("me"));
+        assert_eq!(resolved.host_name, None);
+        assert_eq!(resolved.user.as_deref(), Some("wildcard-user"));
+        assert_eq!(resolved.port, Some(22));
+        assert_eq!(
+            resolved.identity_files.last(),
+            Some(&PathBuf::from("/keys/git.example.com-me")),
+            "the user we connect as takes precedence"
+        );
+
+        let resolved = resolve("secret.example.com", None);
+        assert_eq!(resolved.port, None, "negated patterns prevent a match");
+        assert_eq!(resolved.identity_files.len(), 1, "only the global one");
+
+        assert_eq!(
+            resolve("HOST", None).host_name.as_deref(),
+            Some("HOST.internal"),
+            "patterns are case-insensitive and tokens are expanded"
+        );
+        assert_eq!(resolve("hoost", None).host_name, None, "`?` matches a single character");
+    }
+
+    #[test]
+    fn empty_and_invalid_input_resolves_to_nothing() {
+        for input in [
+            &b""[..],
+            b"Host\nPort",
+            b"garbage without meaning",
+            b"Port not-a-number",
+        ] {
+            assert_eq!(Config::from_bytes(input).resolve("host", None), Resolved::default());
+        }
+    }
+}
+
 mod program_kind {
     mod from_os_str {
         use std::ffi::OsStr;
@@ -214,6 +304,60 @@ mod program_kind {
             );
         }
 
+        #[test]
+        fn ssh_config_is_applied_for_programs_that_cannot_read_it_unless_overridden_by_the_url() {
+            let ssh_config = ssh::config::Resolved {
+                host_name: Some("real.example.com".into()),
+                user: Some("config-user".into()),
+                port: Some(2222),
+                identity_files: vec!["/keys/a".into(), "/keys/b".into()],
+            };
+            for (kind, url, expected) in [
+                (ProgramKind::Ssh, "ssh://alias/p", "ssh alias"),
+                (ProgramKind::Ssh, "ssh://user@alias:42/p", "ssh -p42 user@alias"),
+                (
+                    ProgramKind::Plink,
+                    "ssh://alias/p",
+                    "plink -P 2222 -i /keys/a -i /keys/b config-user@real.example.com",
+                ),
+                (
+                    ProgramKind::Putty,
+                    "ssh://user@alias:42/p",
+                    "putty -P 42 -i /keys/a -i /keys/b user@real.example.com",
+                ),
+                (ProgramKind::Simple, "ssh://alias/p", "simple alias"),
+            ] {
+                let ssh_cmd = kind.exe().unwrap_or_else(|| OsStr::new("simple"));
+                let url = gix_url::parse(url.into()).expect("valid url");
+                let cmd = std::process::Command::from(
+                    kind.prepare_invocation(ssh_cmd, &url, &ssh_config, Protocol::V1, false)
+                        .expect("no error"),
+                );
+                assert_eq!(args_of(&cmd), expected);
+            }
+        }
+
+        #[test]
+        fn ambiguous_values_from_ssh_config_are_disallowed() {
+            let url = gix_url::parse("ssh://alias/p".into()).expect("valid url");
+            let ssh_config = ssh::config::Resolved {
+                host_name: Some("-oProxyCommand=evil".into()),
+                ..Default::default()
+            };
+            assert!(matches!(
+                ProgramKind::Plink.prepare_invocation(OsStr::new("plink"), &url, &ssh_config, Protocol::V1, false),
+                Err(ssh::invocation::Error::AmbiguousHostName { host }) if host == "-oProxyCommand=evil"
+            ));
+            let ssh_config = ssh::config::Resolved {
+                user: Some("-oProxyCommand=evil".into()),
+                ..Default::default()
+            };
+            assert!(matches!(
+                ProgramKind::Plink.prepare_invocation(OsStr::new("plink"), &url, &ssh_config, Protocol::V1, false),
+                Err(ssh::invocation::Error::AmbiguousUserName { user }) if user == "-oProxyCommand=evil"
+            ));
+        }
+
         #[test]
         fn ssh_env_v2() {
             let prepare = call(ProgramKind::Ssh, "ssh://host/p", Protocol::V2);
@@ -233,13 +377,23 @@ mod program_kind {
             let url = gix_url::parse("ssh://host/path".into()).expect("valid url");
 
             let disallow_shell = false;
-            let prepare =
-                ProgramKind::Ssh.prepare_invocation(OsStr::new("echo hi"), &url, Protocol::V1, disallow_shell)?;
+            let prepare = ProgramKind::Ssh.prepare_invocation(
+                OsStr::new("echo hi"),
+                &url,
+                &Default::default(),
+                Protocol::V1,
+                disallow_shell,
+            )?;
             assert!(prepare.use_shell, "shells are used when needed");
 
             let disallow_shell = true;
-            let prepare =
-                ProgramKind::Ssh.prepare_invocation(OsStr::new("echo hi"), &url, Protocol::V1, disallow_shell)?;
+            let prepare = ProgramKind::Ssh.prepare_invocation(
+                OsStr::new("echo hi"),
+                &url,
+                &Default::default(),
+                Protocol::V1,
+                disallow_shell,
+            )?;
             assert!(
                 !prepare.use_shell,
                 "but we can enforce it not to be used as well for historical reasons"
@@ -257,13 +411,15 @@ mod program_kind {
         ) -> std::result::Result<gix_command::Prepare, ssh::invocation::Error> {
             let ssh_cmd = kind.exe().unwrap_or_else(|| OsStr::new("simple"));
             let url = gix_url::parse(url.into()).expect("valid url");
-            kind.prepare_invocation(ssh_cmd, &url, version, false)
+            kind.prepare_invocation(ssh_cmd, &url, &Default::default(), version, false)
         }
         fn call(kind: ProgramKind, url: &str, version: Protocol) -> gix_command::Prepare {
             try_call(kind, url, version).expect("no error")
         }
         fn call_args(kind: ProgramKind, url: &str, version: Protocol) -> String {
-            let cmd = std::process::Command::from(call(kind, url, version));
+            args_of(&std::process::Command::from(call(kind, url, version)))
+        }
+        fn args_of(cmd: &std::process::Command) -> String {
             format!(
                 "{} {}",
                 cmd.get_program().to_string_lossy(),
@@ -192,7 +192,8 @@ pub mod recv {
         /// Read the capabilities and version advertisement from the given packetline reader.
         ///
         /// If [`Protocol::V1`] was requested, or the remote decided to downgrade, the remote refs
-        /// advertisement will also be included in the [`Outcome`].
+        /// advertisement will also be included in the [`Outcome`], whose [protocol](Outcome::protocol) is the version the
+        /// remote actually uses.
         pub fn from_lines_with_version_detection<T: io::Read>(
             rd: &mut gix_packetline::StreamingPeekableIter<T>,
         ) -> Result<Outcome<'_>, client::Error> {
@@ -202,6 +203,17 @@ pub mod recv {
             // format looks like, thus there is no binary blob that could ever look like an ERR line by accident.
             rd.fail_on_err_lines(true);
 
+            // Servers asked for V1 explicitly announce it with a `version 1` line before the usual V1 advertisement.
+            let is_explicit_v1 = match rd.peek_line() {
+                Some(line) => matches!(line??.as_text(), Some(line) if line.as_bstr() == "version 1"),
+                None => false,
+            };
+            if is_explicit_v1 {
+                if let Some(line) = rd.read_line() {
+                    line??;
+                }
+            }
+
             Ok(match rd.peek_line() {
                 Some(line) => {
                     let line = line??.as_text().ok_or(client::Error::ExpectedLine("text"))?;
@@ -277,7 +289,8 @@ pub mod recv {
         /// Read the capabilities and version advertisement from the given packetline reader.
         ///
         /// If [`Protocol::V1`] was requested, or the remote decided to downgrade, the remote refs
-        /// advertisement will also be included in the [`Outcome`].
+        /// advertisement will also be included in the [`Outcome`], whose [protocol](Outcome::protocol) is the version the
+        /// remote actually uses.
         pub async fn from_lines_with_version_detection<T: AsyncRead + Unpin>(
             rd: &mut gix_packetline::StreamingPeekableIter<T>,
         ) -> Result<Outcome<'_>, client::Error> {
@@ -287,6 +300,17 @@ pub mod recv {
             // format looks like, thus there is no binary blob that could ever look like an ERR line by accident.
             rd.fail_on_err_lines(true);
 
+            // Servers asked for V1 explicitly announce it with a `version 1` line before the usual V1 advertisement.
+            let is_explicit_v1 = match rd.peek_line().await {
+                Some(line) => matches!(line??.as_text(), Some(line) if line.as_bstr() == "version 1"),
+                None => false,
+            };
+            if is_explicit_v1 {
+                if let Some(line) = rd.read_line().await {
+                    line??;
+                }
+            }
+
             Ok(match rd.peek_line().await {
                 Some(line) => {
                     let line = line??.as_text().ok_or(client::Error::ExpectedLine("text"))?;
@@ -79,7 +79,9 @@ where
             capabilities,
             refs,
             protocol: actual_protocol,
-        } = Capabilities::from_lines_with_version_detection(&mut self.line_provider).await?;
+        } = Capabilities::from_lines_with_version_detection(&mut self.line_provider)
+            .await
+            .map_err(git::handshake_error)?;
         Ok(SetServiceResponse {
             actual_protocol,
             capabilities,
@@ -123,13 +125,19 @@ where
 mod async_net {
     use std::time::Duration;
 
-    use async_std::net::TcpStream;
+    use async_std::net::{TcpStream, ToSocketAddrs};
 
-    use crate::client::{git, Error};
+    use crate::client::{
+        git,
+        git::connect::{host_to_resolve, Error, DEFAULT_PORT},
+    };
 
     impl git::Connection<TcpStream, TcpStream> {
         /// Create a new TCP connection using the `git` protocol of `desired_version`, and make a connection to `host`
         /// at `port` for accessing the repository at `path` on the server side.
+        ///
+        /// `host` may be an IPv6 literal in brackets, like `[::1]`, and all addresses it resolves to are tried in order.
+        /// It's also sent as virtual host to the daemon along with the `port` if set.
         /// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
         pub async fn new_tcp(
             host: &str,
@@ -138,21 +146,48 @@ mod async_net {
             desired_version: crate::Protocol,
             trace: bool,
         ) -> Result<git::Connection<TcpStream, TcpStream>, Error> {
-            let read = async_std::io::timeout(
-                Duration::from_secs(5),
-                TcpStream::connect(&(host, port.unwrap_or(9418))),
-            )
-            .await?;
+            let port_to_connect_to = port.unwrap_or(DEFAULT_PORT);
+            let mut last_err = None;
+            let mut stream = None;
+            for addr in (host_to_resolve(host), port_to_connect_to)
+                .to_socket_addrs()
+                .await
+                .map_err(|source| Error::Resolve {
+                    host: host.to_owned(),
+                    source,
+                })?
+            {
+                match async_std::io::timeout(Duration::from_secs(5), TcpStream::connect(addr)).await {
+                    Ok(connected) => {
+                        stream = Some(connected);
+                        break;
+                    }
+                    Err(err) => last_err = Some(err),
+                }
+            }
+            let read = match (stream, last_err) {
+                (Some(stream), _) => stream,
+                (None, Some(source)) => {
+                    return Err(Error::Connect {
+                        host: host.to_owned(),
+                        port: port_to_connect_to,
+                        source,
+                    })
+                }
+                (None, None) => return Err(Error::NoAddress { host: host.to_owned() }),
+            };
             let write = read.clone();
+            let url = git::connect::url(host, port, &path);
             Ok(git::Connection::new(
                 read,
                 write,
                 desired_version,
                 path,
-                None::<(String, _)>,
+                Some((host, port)),
                 git::ConnectMode::Daemon,
                 trace,
-            ))
+            )
+            .custom_url(Some(url)))
         }
     }
 }
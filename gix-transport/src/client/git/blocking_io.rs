@@ -74,7 +74,7 @@ where
             capabilities,
             refs,
             protocol: actual_protocol,
-        } = Capabilities::from_lines_with_version_detection(&mut self.line_provider)?;
+        } = Capabilities::from_lines_with_version_detection(&mut self.line_provider).map_err(git::handshake_error)?;
         Ok(SetServiceResponse {
             actual_protocol,
             capabilities,
@@ -131,47 +131,43 @@ where
     }
 }
 
-///
-#[allow(clippy::empty_docs)]
-pub mod connect {
+mod tcp {
     use std::net::{TcpStream, ToSocketAddrs};
 
     use bstr::BString;
 
-    use crate::client::git;
-    /// The error used in [`connect()`].
-    #[derive(Debug, thiserror::Error)]
-    #[allow(missing_docs)]
-    pub enum Error {
-        #[error("An IO error occurred when connecting to the server")]
-        Io(#[from] std::io::Error),
-        #[error("Could not parse {host:?} as virtual host with format <host>[:port]")]
-        VirtualHostInvalid { host: String },
-    }
-
-    impl crate::IsSpuriousError for Error {
-        fn is_spurious(&self) -> bool {
-            match self {
-                Error::Io(err) => err.is_spurious(),
-                _ => false,
-            }
-        }
-    }
+    use crate::client::{
+        git,
+        git::connect::{host_to_resolve, Error, DEFAULT_PORT},
+    };
 
     fn parse_host(input: String) -> Result<(String, Option<u16>), Error> {
-        let mut tokens = input.splitn(2, ':');
-        Ok(match (tokens.next(), tokens.next()) {
-            (Some(host), None) => (host.to_owned(), None),
-            (Some(host), Some(port)) => (
-                host.to_owned(),
-                Some(port.parse().map_err(|_| Error::VirtualHostInvalid { host: input })?),
-            ),
-            _ => unreachable!("we expect at least one token, the original string"),
-        })
+        let (host, port) = match input.strip_prefix('[').and_then(|rest| rest.find(']')) {
+            Some(closing_bracket) => {
+                let (host, rest) = input.split_at(closing_bracket + 2);
+                (host, rest.strip_prefix(':'))
+            }
+            None => match input.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (input.as_str(), None),
+            },
+        };
+        Ok((
+            host.to_owned(),
+            port.map(|port| {
+                port.parse()
+                    .map_err(|_| Error::VirtualHostInvalid { host: input.clone() })
+            })
+            .transpose()?,
+        ))
     }
 
     /// Connect to a git daemon running on `host` and optionally `port` and a repository at `path`.
     ///
+    /// `host` may be an IPv6 literal in brackets, like `[::1]`, and all addresses it resolves to are tried in order.
+    /// It's also sent as virtual host to the daemon along with the `port` if set, unless it is overridden with the
+    /// `GIT_OVERRIDE_VIRTUAL_HOST` environment variable.
+    ///
     /// Use `desired_version` to specify a preferred protocol to use, knowing that it can be downgraded by a server not supporting it.
     /// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
     pub fn connect(
@@ -181,19 +177,41 @@ pub mod connect {
         port: Option<u16>,
         trace: bool,
     ) -> Result<git::Connection<TcpStream, TcpStream>, Error> {
-        let read = TcpStream::connect_timeout(
-            &(host, port.unwrap_or(9418))
-                .to_socket_addrs()?
-                .next()
-                .expect("after successful resolution there is an IP address"),
-            std::time::Duration::from_secs(5),
-        )?;
-        let write = read.try_clone()?;
+        let port_to_connect_to = port.unwrap_or(DEFAULT_PORT);
+        let connect_error = |source| Error::Connect {
+            host: host.to_owned(),
+            port: port_to_connect_to,
+            source,
+        };
+        let mut last_err = None;
+        let mut stream = None;
+        for addr in (host_to_resolve(host), port_to_connect_to)
+            .to_socket_addrs()
+            .map_err(|source| Error::Resolve {
+                host: host.to_owned(),
+                source,
+            })?
+        {
+            match TcpStream::connect_timeout(&addr, std::time::Duration::from_secs(5)) {
+                Ok(connected) => {
+                    stream = Some(connected);
+                    break;
+                }
+                Err(err) => last_err = Some(err),
+            }
+        }
+        let read = match (stream, last_err) {
+            (Some(stream), _) => stream,
+            (None, Some(err)) => return Err(connect_error(err)),
+            (None, None) => return Err(Error::NoAddress { host: host.to_owned() }),
+        };
+        let write = read.try_clone().map_err(connect_error)?;
         let vhost = std::env::var("GIT_OVERRIDE_VIRTUAL_HOST")
             .ok()
             .map(parse_host)
             .transpose()?
             .unwrap_or_else(|| (host.to_owned(), port));
+        let url = git::connect::url(host, port, &path);
         Ok(git::Connection::new(
             read,
             write,
@@ -202,8 +220,9 @@ pub mod connect {
             Some(vhost),
             git::ConnectMode::Daemon,
             trace,
-        ))
+        )
+        .custom_url(Some(url)))
     }
 }
 
-pub use connect::connect;
+pub use tcp::connect;
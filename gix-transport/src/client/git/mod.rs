@@ -68,17 +68,14 @@ mod message {
             }
             out.push(0);
         }
-        // We only send the version when needed, as otherwise a V2 server who is asked for V1 will respond with 'version 1'
-        // as extra lines in the reply, which we don't want to handle. Especially since an old server will not respond with that
-        // line (is what I assume, at least), so it's an optional part in the response to understand and handle. There is no value
-        // in that, so let's help V2 servers to respond in a way that assumes V1.
-        let extra_params_need_null_prefix = if desired_version != Protocol::V1 {
-            out.push(0);
-            out.push_str(format!("version={}", desired_version as usize));
-            out.push(0);
-            false
-        } else {
-            true
+        let extra_params_need_null_prefix = match desired_version.request_parameter() {
+            Some(version) => {
+                out.push(0);
+                out.push_str(version);
+                out.push(0);
+                false
+            }
+            None => true,
         };
 
         if !extra_parameters.is_empty() {
@@ -107,6 +104,13 @@ mod message {
             )
         }
         #[test]
+        fn version_0_is_not_requested_explicitly() {
+            assert_eq!(
+                git::message::connect(Service::UploadPack, Protocol::V0, b"hello/world", None, &[]),
+                "git-upload-pack hello/world\0"
+            )
+        }
+        #[test]
         fn version_2_without_host_and_version() {
             assert_eq!(
                 git::message::connect(Service::UploadPack, Protocol::V2, b"hello\\world", None, &[]),
@@ -183,6 +187,76 @@ mod message {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod connect {
+    /// The error used when connecting to a git daemon with [`connect()`][crate::client::git::connect()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not resolve the address of {host:?}")]
+        Resolve { host: String, source: std::io::Error },
+        #[error("The host {host:?} did not resolve to any address")]
+        NoAddress { host: String },
+        #[error("Could not connect to {host:?} at port {port}")]
+        Connect {
+            host: String,
+            port: u16,
+            source: std::io::Error,
+        },
+        #[error("Could not parse {host:?} as virtual host with format <host>[:port]")]
+        VirtualHostInvalid { host: String },
+    }
+
+    impl crate::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Resolve { source, .. } | Error::Connect { source, .. } => source.is_spurious(),
+                _ => false,
+            }
+        }
+    }
+
+    /// The port git daemons listen on by default.
+    pub const DEFAULT_PORT: u16 = 9418;
+
+    /// Return `host` without the brackets around IPv6 literals, which is the form needed to resolve it.
+    pub(crate) fn host_to_resolve(host: &str) -> &str {
+        host.strip_prefix('[')
+            .and_then(|host| host.strip_suffix(']'))
+            .unwrap_or(host)
+    }
+
+    /// Return the URL of the repository at `path` on the git daemon at `host` and `port`.
+    pub(crate) fn url(host: &str, port: Option<u16>, path: &[u8]) -> bstr::BString {
+        let mut url = bstr::BString::from(format!("git://{host}"));
+        if let Some(port) = port {
+            url.extend_from_slice(format!(":{port}").as_bytes());
+        }
+        if !path.starts_with(b"/") {
+            url.push(b'/');
+        }
+        url.extend_from_slice(path);
+        url
+    }
+}
+
+/// Turn IO errors caused by `ERR` lines that the server sent during the handshake into [`client::Error::ServerError`].
+fn handshake_error(err: crate::client::Error) -> crate::client::Error {
+    match err {
+        crate::client::Error::Io(err) => match err
+            .get_ref()
+            .and_then(|err| err.downcast_ref::<gix_packetline::read::Error>())
+        {
+            Some(server_error) => crate::client::Error::ServerError {
+                message: server_error.message.clone(),
+            },
+            None => crate::client::Error::Io(err),
+        },
+        err => err,
+    }
+}
+
 #[cfg(feature = "async-client")]
 mod async_io;
 
@@ -73,7 +73,6 @@ pub(crate) mod connect {
         fn is_spurious(&self) -> bool {
             match self {
                 Error::Connection(err) => {
-                    #[cfg(feature = "blocking-client")]
                     if let Some(err) = err.downcast_ref::<crate::client::git::connect::Error>() {
                         return err.is_spurious();
                     };
@@ -142,6 +141,8 @@ mod error {
         SshInvocation(SshInvocationError),
         #[error("The repository path '{path}' could be mistaken for a command-line argument")]
         AmbiguousPath { path: BString },
+        #[error("The server responded with an error: {message}")]
+        ServerError { message: BString },
     }
 
     impl crate::IsSpuriousError for Error {
@@ -42,6 +42,25 @@ pub enum Service {
     ReceivePack,
 }
 
+impl Protocol {
+    /// Return the `version=<n>` parameter with which a client asks the server to use this protocol version,
+    /// or `None` if nothing has to be sent.
+    ///
+    /// It's passed as `Git-Protocol` header via HTTP, as `GIT_PROTOCOL` environment variable via `ssh` and to local processes,
+    /// and as extra parameter of the initial request of the `git` daemon protocol.
+    /// Servers which don't know the requested version, or which are too old to understand the parameter at all, fall back
+    /// to V0 or V1, and the version they actually use is detected from their response.
+    ///
+    /// Note that V1 isn't requested explicitly as servers speak it anyway, and would otherwise announce it with
+    /// an extra `version 1` line.
+    pub fn request_parameter(&self) -> Option<&'static str> {
+        match self {
+            Protocol::V2 => Some("version=2"),
+            Protocol::V0 | Protocol::V1 => None,
+        }
+    }
+}
+
 impl Service {
     /// Render this instance as string recognized by the git transport layer.
     pub fn as_str(&self) -> &'static str {
@@ -64,3 +64,37 @@ async fn from_lines_with_version_detection_v0() -> crate::Result {
     assert!(caps.contains("side-band-64k"));
     Ok(())
 }
+
+#[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+async fn from_lines_with_version_detection_explicit_v1() -> crate::Result {
+    let mut buf = Vec::<u8>::new();
+    gix_packetline::encode::text_to_write(b"version 1", &mut buf).await?;
+    gix_packetline::encode::text_to_write(
+        b"7814e8a05a59c0cf5fb186661d1551c75d1299b5 HEAD\0side-band-64k agent=git/2.42.0",
+        &mut buf,
+    )
+    .await?;
+    gix_packetline::encode::text_to_write(b"7814e8a05a59c0cf5fb186661d1551c75d1299b5 refs/heads/main", &mut buf)
+        .await?;
+    gix_packetline::encode::flush_to_write(&mut buf).await?;
+    let mut stream =
+        gix_packetline::StreamingPeekableIter::new(buf.as_slice(), &[gix_packetline::PacketLineRef::Flush], false);
+    let outcome = Capabilities::from_lines_with_version_detection(&mut stream).await?;
+    assert_eq!(outcome.protocol, gix_transport::Protocol::V1);
+    assert!(outcome.capabilities.contains("side-band-64k"));
+    let mut refs = outcome.refs.expect("V1 sends refs");
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    while refs.readline_str(&mut line).await? != 0 {
+        lines.push(std::mem::take(&mut line));
+    }
+    assert_eq!(
+        lines,
+        [
+            "7814e8a05a59c0cf5fb186661d1551c75d1299b5 HEAD\n",
+            "7814e8a05a59c0cf5fb186661d1551c75d1299b5 refs/heads/main\n"
+        ],
+        "the version line is skipped, and refs follow as usual"
+    );
+    Ok(())
+}
@@ -443,3 +443,96 @@ async fn handshake_v2_and_request_inner() -> crate::Result {
     );
     Ok(())
 }
+
+#[cfg(feature = "blocking-client")]
+mod daemon {
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    use bstr::{BString, ByteSlice};
+    use gix_transport::{
+        client::{git, Transport, TransportWithoutIO},
+        Protocol, Service,
+    };
+
+    /// Accept a single connection on `listener`, answer it with `response` and return the request packet line.
+    fn serve_once(listener: TcpListener, response: &'static [u8]) -> std::thread::JoinHandle<std::io::Result<BString>> {
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept()?;
+            let mut len = [0u8; 4];
+            stream.read_exact(&mut len)?;
+            let len = usize::from_str_radix(std::str::from_utf8(&len).expect("hex"), 16).expect("valid length");
+            let mut request = vec![0; len - 4];
+            stream.read_exact(&mut request)?;
+            stream.write_all(response)?;
+            Ok(request.into())
+        })
+    }
+
+    #[test]
+    fn request_with_port_ipv6_host_and_extra_parameters() -> crate::Result {
+        let listener = match TcpListener::bind("[::1]:0") {
+            Ok(listener) => listener,
+            Err(_) => return Ok(()), // no IPv6 support
+        };
+        let port = listener.local_addr()?.port();
+        let server = serve_once(listener, b"000eversion 2\n0015agent=git/2.42.0\n000cls-refs\n0000");
+
+        let mut c = git::connect("[::1]", "/repo.git".into(), Protocol::V2, Some(port), false)?;
+        assert_eq!(c.to_url().as_ref(), format!("git://[::1]:{port}/repo.git"));
+        let res = c.handshake(Service::UploadPack, &[("key", Some("value")), ("value-only", None)])?;
+        assert_eq!(res.actual_protocol, Protocol::V2);
+        assert!(res.capabilities.contains("ls-refs"));
+        drop(res);
+
+        assert_eq!(
+            server.join().expect("no panic")?,
+            format!("git-upload-pack /repo.git\0host=[::1]:{port}\0\0version=2\0key=value\0value-only\0"),
+            "the request follows pack-protocol, and the virtual host retains the brackets"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn errors_sent_by_the_daemon_are_typed() -> crate::Result {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let port = listener.local_addr()?.port();
+        let server = serve_once(listener, b"0037ERR access denied or repository not exported: /repo");
+
+        let mut c = git::connect("127.0.0.1", "/repo".into(), Protocol::V1, Some(port), false)?;
+        match c.handshake(Service::UploadPack, &[]) {
+            Err(gix_transport::client::Error::ServerError { message }) => {
+                assert_eq!(message, "access denied or repository not exported: /repo")
+            }
+            Err(err) => panic!("unexpected error: {err:?}"),
+            Ok(_) => panic!("the handshake is expected to fail"),
+        }
+        assert_eq!(
+            server.join().expect("no panic")?,
+            format!("git-upload-pack /repo\0host=127.0.0.1:{port}\0")
+                .as_bytes()
+                .as_bstr()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn connection_failures_are_distinct_from_handshake_failures() -> crate::Result {
+        let port = TcpListener::bind("127.0.0.1:0")?.local_addr()?.port();
+        match git::connect("127.0.0.1", "/repo".into(), Protocol::V2, Some(port), false) {
+            Err(git::connect::Error::Connect {
+                host,
+                port: actual_port,
+                ..
+            }) => {
+                assert_eq!(host, "127.0.0.1");
+                assert_eq!(actual_port, port);
+            }
+            Err(err) => panic!("unexpected error: {err:?}"),
+            Ok(_) => panic!("nothing listens on the port anymore"),
+        }
+        Ok(())
+    }
+}
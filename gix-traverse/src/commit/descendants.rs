@@ -0,0 +1,137 @@
+use std::collections::VecDeque;
+
+use gix_hash::ObjectId;
+use gix_hashtable::HashMap;
+use smallvec::SmallVec;
+
+use super::{find, Descendants, Either};
+
+/// The error returned when creating a [`Descendants`] traversal.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Find(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    ObjectDecode(#[from] gix_object::decode::Error),
+}
+
+/// Initialization
+impl Descendants {
+    /// Create a new instance which returns all descendants of the `start` commit among the `universe` of commits,
+    /// reading commits from `objects` or from `cache` if possible, which greatly accelerates the operation.
+    ///
+    /// All commits in `universe` are read to learn about their parents, so it should be as small as possible while
+    /// covering all descendants of interest, like all commits reachable from a tip.
+    /// Children that aren't part of `universe` are ignored.
+    /// If `cache` fails to provide the parents of a commit, they are read from `objects` instead.
+    pub fn new(
+        start: impl Into<ObjectId>,
+        universe: impl IntoIterator<Item = impl Into<ObjectId>>,
+        objects: impl gix_object::Find,
+        cache: Option<&gix_commitgraph::Graph>,
+    ) -> Result<Self, Error> {
+        let mut ids = Vec::new();
+        let mut index_by_id = HashMap::<ObjectId, usize>::default();
+        for id in universe.into_iter().map(Into::into) {
+            index_by_id.entry(id).or_insert_with(|| {
+                ids.push(id);
+                ids.len() - 1
+            });
+        }
+        let start = start.into();
+        let start = *index_by_id.entry(start).or_insert_with(|| {
+            ids.push(start);
+            ids.len() - 1
+        });
+
+        let mut children = vec![SmallVec::<[usize; 2]>::new(); ids.len()];
+        let mut buf = Vec::new();
+        let mut parent_ids = Vec::new();
+        for (idx, id) in ids.iter().enumerate() {
+            read_parents(id, &objects, cache, &mut buf, &mut parent_ids)?;
+            for parent_id in &parent_ids {
+                if let Some(&parent) = index_by_id.get(parent_id) {
+                    children[parent].push(idx);
+                }
+            }
+        }
+        drop(index_by_id);
+
+        // Only count the parents that are descendants of `start`, or `start` itself, as only these will be returned.
+        let mut indegrees = vec![0; ids.len()];
+        let mut is_descendant = vec![false; ids.len()];
+        let mut queue = vec![start];
+        while let Some(idx) = queue.pop() {
+            for &child in &children[idx] {
+                indegrees[child] += 1;
+                if !is_descendant[child] {
+                    is_descendant[child] = true;
+                    queue.push(child);
+                }
+            }
+        }
+
+        let mut next = VecDeque::new();
+        for &child in &children[start] {
+            indegrees[child] -= 1;
+            if indegrees[child] == 0 {
+                next.push_back(child);
+            }
+        }
+        Ok(Descendants {
+            ids,
+            children,
+            indegrees,
+            next,
+        })
+    }
+}
+
+impl Iterator for Descendants {
+    type Item = ObjectId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.next.pop_front()?;
+        for &child in &self.children[idx] {
+            self.indegrees[child] -= 1;
+            if self.indegrees[child] == 0 {
+                self.next.push_back(child);
+            }
+        }
+        Some(self.ids[idx])
+    }
+}
+
+/// Put the parent ids of the commit with `id` into `out`.
+fn read_parents(
+    id: &gix_hash::oid,
+    objects: &impl gix_object::Find,
+    cache: Option<&gix_commitgraph::Graph>,
+    buf: &mut Vec<u8>,
+    out: &mut Vec<ObjectId>,
+) -> Result<(), Error> {
+    out.clear();
+    match find(cache, objects, id, buf)? {
+        Either::CachedCommit(commit) => {
+            let cache = cache.expect("parents iter is available, backed by `cache`");
+            for parent in commit.iter_parents() {
+                match parent {
+                    Ok(pos) => out.push(cache.id_at(pos).to_owned()),
+                    // The cache is corrupt, use the object database instead.
+                    Err(_) => return read_parents(id, objects, None, buf, out),
+                }
+            }
+        }
+        Either::CommitRefIter(commit) => {
+            for token in commit {
+                match token? {
+                    gix_object::commit::ref_iter::Token::Tree { .. } => continue,
+                    gix_object::commit::ref_iter::Token::Parent { id } => out.push(id),
+                    _ => break,
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use gix_hash::{oid, ObjectId};
+use gix_object::bstr::ByteSlice;
+
+use super::{Grafts, ParentIds};
+
+///
+#[allow(clippy::empty_docs)]
+pub mod decode {
+    use gix_object::bstr::BString;
+
+    /// The error returned by [`Grafts::from_bytes()`](crate::commit::Grafts::from_bytes()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Invalid graft line {line:?} in line {line_number}")]
+        InvalidLine { line: BString, line_number: usize },
+    }
+}
+
+/// Initialization
+impl Grafts {
+    /// Parse the content of an `info/grafts` file, where each line contains the id of a commit followed by the ids
+    /// of its effective parents, separated by spaces. Empty lines and lines starting with `#` are ignored.
+    ///
+    /// If a commit is mentioned multiple times, the last line wins.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, decode::Error> {
+        let mut grafts = Grafts::default();
+        for (line_number, line) in data.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with(b"#") {
+                continue;
+            }
+            let mut ids = line.split_str(" ").map(|hex| ObjectId::from_hex(hex).ok());
+            let (Some(Some(id)), Some(parents)) = (ids.next(), ids.collect::<Option<ParentIds>>()) else {
+                return Err(decode::Error::InvalidLine {
+                    line: line.into(),
+                    line_number: line_number + 1,
+                });
+            };
+            grafts.insert(id, parents);
+        }
+        Ok(grafts)
+    }
+}
+
+/// Mutation
+impl Grafts {
+    /// Set the effective `parents` of the commit with `id`, replacing the previous graft of `id` if there was one.
+    pub fn insert(&mut self, id: ObjectId, parents: impl IntoIterator<Item = ObjectId>) {
+        self.parents_by_id.insert(id, parents.into_iter().collect());
+    }
+
+    /// Mark all commits in `shallow` as shallow, treating them as if they had no parents. This takes precedence over
+    /// grafts of these commits, just like in `git`.
+    pub fn extend_with_shallow(&mut self, shallow: impl IntoIterator<Item = ObjectId>) {
+        for id in shallow {
+            self.insert(id, None);
+        }
+    }
+}
+
+/// Access
+impl Grafts {
+    /// Return the effective parents of the commit with `id`, or `None` if its parents aren't rewritten and thus are the ones
+    /// stored in the commit itself.
+    pub fn parents(&self, id: &oid) -> Option<&[ObjectId]> {
+        self.parents_by_id.get(id).map(ParentIds::as_slice)
+    }
+
+    /// Return `true` if no commit has rewritten parents.
+    pub fn is_empty(&self) -> bool {
+        self.parents_by_id.is_empty()
+    }
+}
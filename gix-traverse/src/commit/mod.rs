@@ -15,6 +15,7 @@ pub struct Simple<Find, Predicate> {
     state: simple::State,
     parents: Parents,
     sorting: simple::Sorting,
+    grafts: Grafts,
 }
 
 /// Simple ancestors traversal, without the need to keep track of graph-state.
@@ -40,6 +41,42 @@ pub struct Topo<Find, Predicate> {
 
 pub mod topo;
 
+/// An iterator over the descendants of a commit in topological order, i.e. each commit is returned after all of its
+/// parents that are descendants as well.
+///
+/// As commits only know their parents, descendants can only be found within a universe of commits that is
+/// provided by the caller, like all commits reachable from one or more tips.
+/// Instantiate with [`Descendants::new()`].
+pub struct Descendants {
+    /// All commits of the universe, indexed by position.
+    ids: Vec<ObjectId>,
+    /// For each commit in `ids`, the positions of its children.
+    children: Vec<SmallVec<[usize; 2]>>,
+    /// For each commit in `ids`, the amount of parents that are descendants and which weren't returned yet.
+    indegrees: Vec<u32>,
+    /// The positions of commits whose parents were all returned, ready to be returned themselves.
+    next: std::collections::VecDeque<usize>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod descendants;
+
+/// A parent-rewriting layer which overrides the parents of select commits, as defined by the `info/grafts` file
+/// and the `shallow` file of a repository.
+///
+/// Grafts can add or remove parents of a commit, while shallow commits are treated as if they had no parents at all,
+/// making them the roots of the history.
+/// Instantiate with [`Grafts::from_bytes()`] or [`Grafts::default()`] for use with [`Simple::grafts()`].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Grafts {
+    parents_by_id: gix_hashtable::HashMap<ObjectId, ParentIds>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod grafts;
+
 /// Specify how to handle commit parents during traversal.
 #[derive(Default, Copy, Clone)]
 pub enum Parents {
@@ -61,6 +98,8 @@ pub struct Info {
     /// The id of the commit.
     pub id: gix_hash::ObjectId,
     /// All parent ids we have encountered. Note that these will be at most one if [`Parents::First`] is enabled.
+    ///
+    /// With [grafts](Simple::grafts()), these are the rewritten parents, which are none for shallow commits.
     pub parent_ids: ParentIds,
     /// The time at which the commit was created. It will only be `Some(_)` if the chosen traversal was
     /// taking dates into consideration.
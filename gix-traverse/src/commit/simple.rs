@@ -80,8 +80,8 @@ mod init {
     use gix_object::{CommitRefIter, FindExt};
 
     use super::{
-        super::{simple::Sorting, Either, Info, ParentIds, Parents, Simple},
-        collect_parents, Error, State,
+        super::{simple::Sorting, Either, Grafts, Info, ParentIds, Parents, Simple},
+        collect_parents, find_commit_time, queue_unless_older, Error, State,
     };
 
     impl Default for State {
@@ -140,11 +140,11 @@ mod init {
         }
 
         /// Change our commit parent handling mode to the given one.
+        ///
+        /// With [`Parents::First`], only the first parent of each commit is followed, which is how
+        /// `git log --first-parent` walks the mainline, while still respecting the chosen [sorting](Self::sorting()).
         pub fn parents(mut self, mode: Parents) -> Self {
             self.parents = mode;
-            if matches!(self.parents, Parents::First) {
-                self.queue_to_vecdeque();
-            }
             self
         }
 
@@ -157,6 +157,15 @@ mod init {
             self
         }
 
+        /// Rewrite the parents of commits according to `grafts` during traversal, which makes shallow commits the roots
+        /// of the history instead of failing to find their parents.
+        ///
+        /// Rewritten parents take precedence over the ones stored in commits and in the [commit-graph](Self::commit_graph()).
+        pub fn grafts(mut self, grafts: Grafts) -> Self {
+            self.grafts = grafts;
+            self
+        }
+
         fn queue_to_vecdeque(&mut self) {
             let state = &mut self.state;
             state.next.extend(
@@ -225,6 +234,7 @@ mod init {
                 state,
                 parents: Default::default(),
                 sorting: Default::default(),
+                grafts: Default::default(),
             }
         }
     }
@@ -250,16 +260,10 @@ mod init {
         type Item = Result<Info, Error>;
 
         fn next(&mut self) -> Option<Self::Item> {
-            if matches!(self.parents, Parents::First) {
-                self.next_by_topology()
-            } else {
-                match self.sorting {
-                    Sorting::BreadthFirst => self.next_by_topology(),
-                    Sorting::ByCommitTimeNewestFirst => self.next_by_commit_date(None),
-                    Sorting::ByCommitTimeNewestFirstCutoffOlderThan { seconds } => {
-                        self.next_by_commit_date(seconds.into())
-                    }
-                }
+            match self.sorting {
+                Sorting::BreadthFirst => self.next_by_topology(),
+                Sorting::ByCommitTimeNewestFirst => self.next_by_commit_date(None),
+                Sorting::ByCommitTimeNewestFirstCutoffOlderThan { seconds } => self.next_by_commit_date(seconds.into()),
             }
         }
     }
@@ -288,8 +292,25 @@ mod init {
 
             let (commit_time, oid) = state.queue.pop()?;
             let mut parents: ParentIds = Default::default();
-            match super::super::find(self.cache.as_ref(), &self.objects, &oid, &mut state.buf) {
-                Ok(Either::CachedCommit(commit)) => {
+            match (
+                super::super::find(self.cache.as_ref(), &self.objects, &oid, &mut state.buf),
+                self.grafts.parents(&oid),
+            ) {
+                (Ok(_), Some(grafted_parents)) => {
+                    for id in grafted_parents.iter().copied() {
+                        parents.push(id);
+                        let was_inserted = state.seen.insert(id);
+                        if was_inserted && (self.predicate)(&id) {
+                            let parent_commit_time =
+                                find_commit_time(self.cache.as_ref(), &self.objects, &id, &mut state.parents_buf);
+                            queue_unless_older(&mut state.queue, id, parent_commit_time, cutoff_older_than);
+                        }
+                        if matches!(self.parents, Parents::First) {
+                            break;
+                        }
+                    }
+                }
+                (Ok(Either::CachedCommit(commit)), None) => {
                     if !collect_parents(&mut state.parent_ids, self.cache.as_ref(), commit.iter_parents()) {
                         // drop corrupt caches and try again with ODB
                         self.cache = None;
@@ -298,35 +319,28 @@ mod init {
                     for (id, parent_commit_time) in state.parent_ids.drain(..) {
                         parents.push(id);
                         let was_inserted = state.seen.insert(id);
-                        if !(was_inserted && (self.predicate)(&id)) {
-                            continue;
+                        if was_inserted && (self.predicate)(&id) {
+                            queue_unless_older(&mut state.queue, id, parent_commit_time, cutoff_older_than);
                         }
-
-                        match cutoff_older_than {
-                            Some(cutoff_older_than) if parent_commit_time < cutoff_older_than => continue,
-                            Some(_) | None => state.queue.insert(parent_commit_time, id),
+                        if matches!(self.parents, Parents::First) {
+                            break;
                         }
                     }
                 }
-                Ok(Either::CommitRefIter(commit_iter)) => {
+                (Ok(Either::CommitRefIter(commit_iter)), None) => {
                     for token in commit_iter {
                         match token {
                             Ok(gix_object::commit::ref_iter::Token::Tree { .. }) => continue,
                             Ok(gix_object::commit::ref_iter::Token::Parent { id }) => {
                                 parents.push(id);
                                 let was_inserted = state.seen.insert(id);
-                                if !(was_inserted && (self.predicate)(&id)) {
-                                    continue;
+                                if was_inserted && (self.predicate)(&id) {
+                                    let parent_commit_time =
+                                        find_commit_time(None, &self.objects, &id, &mut state.parents_buf);
+                                    queue_unless_older(&mut state.queue, id, parent_commit_time, cutoff_older_than);
                                 }
-
-                                let parent = self.objects.find_commit_iter(id.as_ref(), &mut state.parents_buf).ok();
-                                let parent_commit_time = parent
-                                    .and_then(|parent| parent.committer().ok().map(|committer| committer.time.seconds))
-                                    .unwrap_or_default();
-
-                                match cutoff_older_than {
-                                    Some(cutoff_older_than) if parent_commit_time < cutoff_older_than => continue,
-                                    Some(_) | None => state.queue.insert(parent_commit_time, id),
+                                if matches!(self.parents, Parents::First) {
+                                    break;
                                 }
                             }
                             Ok(_unused_token) => break,
@@ -334,7 +348,7 @@ mod init {
                         }
                     }
                 }
-                Err(err) => return Some(Err(err.into())),
+                (Err(err), _) => return Some(Err(err.into())),
             }
             Some(Ok(Info {
                 id: oid,
@@ -354,8 +368,23 @@ mod init {
             let state = &mut self.state;
             let oid = state.next.pop_front()?;
             let mut parents: ParentIds = Default::default();
-            match super::super::find(self.cache.as_ref(), &self.objects, &oid, &mut state.buf) {
-                Ok(Either::CachedCommit(commit)) => {
+            match (
+                super::super::find(self.cache.as_ref(), &self.objects, &oid, &mut state.buf),
+                self.grafts.parents(&oid),
+            ) {
+                (Ok(_), Some(grafted_parents)) => {
+                    for id in grafted_parents.iter().copied() {
+                        parents.push(id);
+                        let was_inserted = state.seen.insert(id);
+                        if was_inserted && (self.predicate)(&id) {
+                            state.next.push_back(id);
+                        }
+                        if matches!(self.parents, Parents::First) {
+                            break;
+                        }
+                    }
+                }
+                (Ok(Either::CachedCommit(commit)), None) => {
                     if !collect_parents(&mut state.parent_ids, self.cache.as_ref(), commit.iter_parents()) {
                         // drop corrupt caches and try again with ODB
                         self.cache = None;
@@ -373,7 +402,7 @@ mod init {
                         }
                     }
                 }
-                Ok(Either::CommitRefIter(commit_iter)) => {
+                (Ok(Either::CommitRefIter(commit_iter)), None) => {
                     for token in commit_iter {
                         match token {
                             Ok(gix_object::commit::ref_iter::Token::Tree { .. }) => continue,
@@ -392,7 +421,7 @@ mod init {
                         }
                     }
                 }
-                Err(err) => return Some(Err(err.into())),
+                (Err(err), _) => return Some(Err(err.into())),
             }
             Some(Ok(Info {
                 id: oid,
@@ -403,6 +432,36 @@ mod init {
     }
 }
 
+/// Return the time at which the commit with `id` was committed, or the default if it can't be found.
+fn find_commit_time(
+    cache: Option<&gix_commitgraph::Graph>,
+    objects: impl gix_object::Find,
+    id: &gix_hash::oid,
+    buf: &mut Vec<u8>,
+) -> SecondsSinceUnixEpoch {
+    match super::find(cache, objects, id, buf) {
+        Ok(super::Either::CachedCommit(commit)) => commit.committer_timestamp() as SecondsSinceUnixEpoch,
+        Ok(super::Either::CommitRefIter(commit)) => commit
+            .committer()
+            .map(|committer| committer.time.seconds)
+            .unwrap_or_default(),
+        Err(_) => Default::default(),
+    }
+}
+
+/// Queue `id` with its `commit_time` for traversal, unless it was committed before `cutoff_older_than`.
+fn queue_unless_older(
+    queue: &mut gix_revwalk::PriorityQueue<SecondsSinceUnixEpoch, ObjectId>,
+    id: ObjectId,
+    commit_time: SecondsSinceUnixEpoch,
+    cutoff_older_than: Option<SecondsSinceUnixEpoch>,
+) {
+    match cutoff_older_than {
+        Some(cutoff_older_than) if commit_time < cutoff_older_than => {}
+        Some(_) | None => queue.insert(commit_time, id),
+    }
+}
+
 fn collect_parents(
     dest: &mut SmallVec<[(gix_hash::ObjectId, gix_date::SecondsSinceUnixEpoch); 2]>,
     cache: Option<&gix_commitgraph::Graph>,
@@ -0,0 +1,52 @@
+use std::path::Path;
+
+use gix_hash::ObjectId;
+use gix_traverse::commit::{Descendants, Simple};
+
+fn tag(repo_dir: &Path, name: &str) -> ObjectId {
+    let hex = std::fs::read(repo_dir.join(".git").join("refs").join("tags").join(name))
+        .expect("the fixture creates a tag for each commit");
+    ObjectId::from_hex(&hex[..40]).expect("40 bytes hex")
+}
+
+#[test]
+fn both_children_of_a_fork_are_returned_once_in_topological_order() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_repo_for_descendants.sh")?;
+    let store = gix_odb::at(dir.join(".git").join("objects"))?;
+    let [root, fork, left, right, merge, tip, outside] =
+        ["root", "fork", "left", "right", "merge", "tip", "outside"].map(|name| tag(&dir, name));
+
+    let universe = Simple::new(Some(tip), &store)
+        .map(|res| res.map(|info| info.id))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert!(
+        !universe.contains(&outside),
+        "the commit on the other branch isn't reachable from `tip`"
+    );
+
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::at(store.store_ref().path().join("info")))
+            .transpose()?;
+        let descendants: Vec<_> = Descendants::new(fork, universe.iter().copied(), &store, cache.as_ref())?.collect();
+
+        let position = |id: ObjectId| descendants.iter().position(|d| *d == id);
+        assert_eq!(descendants.len(), 4, "{descendants:?}");
+        for child in [left, right] {
+            assert_eq!(
+                descendants.iter().filter(|d| **d == child).count(),
+                1,
+                "both children of the fork are returned exactly once"
+            );
+            assert!(position(child) < position(merge), "parents come before their children");
+        }
+        assert_eq!(position(tip), Some(3));
+        assert_eq!(position(outside), None, "children outside of the universe are ignored");
+        assert_eq!(position(fork), None, "the starting commit isn't returned");
+
+        let all: Vec<_> = Descendants::new(root, universe.iter().copied(), &store, cache.as_ref())?.collect();
+        assert_eq!(all.len(), 5);
+        assert_eq!(all[0], fork);
+    }
+    Ok(())
+}
@@ -1,2 +1,3 @@
+mod descendants;
 mod simple;
 mod topo;
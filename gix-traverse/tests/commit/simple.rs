@@ -419,4 +419,154 @@ mod adjusted_dates {
         .with_parents(Parents::First)
         .check()
     }
+
+    #[test]
+    fn head_date_order_with_cutoff_first_parent_only() -> crate::Result {
+        TraversalAssertion::new(
+            "make_traversal_repo_for_commits_with_dates.sh",
+            &["288e509293165cb5630d08f4185bdf2445bf6170"], /* m1b1 */
+            &[],
+        )
+        .with_sorting(Sorting::ByCommitTimeNewestFirstCutoffOlderThan {
+            seconds: 978393600, // =2001-01-02 00:00:00 +0000
+        })
+        .with_parents(Parents::First)
+        .check()
+    }
+
+    #[test]
+    fn date_order_first_parent_only_sorts_across_tips_and_stops_at_seen_commits() -> crate::Result {
+        let dir =
+            gix_testtools::scripted_fixture_read_only_standalone("make_traversal_repo_for_commits_with_dates.sh")?;
+        let store = gix_odb::at(dir.join(".git").join("objects"))?;
+        for use_commitgraph in [false, true] {
+            let ids = Simple::new(
+                [
+                    hex_to_id("9902e3c3e8f0c569b4ab295ddf473e6de763e1e7"), /* c2 */
+                    hex_to_id("288e509293165cb5630d08f4185bdf2445bf6170"), /* m1b1 */
+                ],
+                &store,
+            )
+            .sorting(Sorting::ByCommitTimeNewestFirst)?
+            .parents(Parents::First)
+            .commit_graph(
+                use_commitgraph
+                    .then(|| gix_commitgraph::at(store.store_ref().path().join("info")))
+                    .transpose()?,
+            )
+            .map(|res| res.map(|info| info.id))
+            .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(
+                ids,
+                [
+                    "288e509293165cb5630d08f4185bdf2445bf6170", /* m1b1 */
+                    "9902e3c3e8f0c569b4ab295ddf473e6de763e1e7", /* c2 */
+                    "134385f6d781b7e97062102c6a483440bfda2a03", /* c1 */
+                ]
+                .map(hex_to_id),
+                "the first parent of m1b1 was already seen as tip, and b1c1 is never reached as it's a second parent"
+            );
+        }
+        Ok(())
+    }
+}
+
+mod grafts {
+    use gix_traverse::commit::{self, simple::Sorting, Grafts};
+
+    use crate::hex_to_id;
+
+    const C1: &str = "134385f6d781b7e97062102c6a483440bfda2a03";
+    const C2: &str = "9902e3c3e8f0c569b4ab295ddf473e6de763e1e7";
+    const C3: &str = "17d78c64cef6c33a10a604573fd2c429e477fd63";
+    const C4: &str = "9556057aee5abb06912922e9f26c46386a816822";
+
+    fn odb(repo: &str) -> crate::Result<gix_odb::Handle> {
+        let dir = gix_testtools::scripted_fixture_read_only_standalone("make_repo_with_grafts.sh")?;
+        Ok(gix_odb::at(dir.join(repo).join(".git").join("objects"))?)
+    }
+
+    fn traverse(
+        odb: &gix_odb::Handle,
+        grafts: Grafts,
+        sorting: Sorting,
+        graph: Option<gix_commitgraph::Graph>,
+    ) -> Result<Vec<gix_hash::ObjectId>, commit::simple::Error> {
+        commit::Simple::new(Some(hex_to_id(C4)), odb)
+            .sorting(sorting)?
+            .commit_graph(graph)
+            .grafts(grafts)
+            .map(|res| res.map(|info| info.id))
+            .collect()
+    }
+
+    #[test]
+    fn shallow_commits_are_roots() -> crate::Result {
+        let odb = odb("shallow")?;
+        for sorting in [Sorting::BreadthFirst, Sorting::ByCommitTimeNewestFirst] {
+            assert!(
+                matches!(
+                    traverse(&odb, Grafts::default(), sorting, None),
+                    Err(commit::simple::Error::Find(_))
+                ),
+                "the parent of the shallow commit is missing"
+            );
+
+            let mut grafts = Grafts::from_bytes(format!("{C3} {C1}\n").as_bytes())?;
+            grafts.extend_with_shallow(Some(hex_to_id(C3)));
+            assert_eq!(
+                traverse(&odb, grafts, sorting, None)?,
+                [C4, C3].map(hex_to_id),
+                "shallow commits stay roots even if they are grafted"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn grafts_rewrite_parents_even_with_commit_graph() -> crate::Result {
+        let odb = odb("repo")?;
+        let grafts = Grafts::from_bytes(format!("# comment\n\n{C3} {C1}\n").as_bytes())?;
+        assert_eq!(grafts.parents(&hex_to_id(C3)), Some(&[hex_to_id(C1)][..]));
+        assert_eq!(grafts.parents(&hex_to_id(C2)), None);
+
+        for use_graph in [false, true] {
+            let graph = || {
+                use_graph
+                    .then(|| gix_commitgraph::at(odb.store_ref().path().join("info")))
+                    .transpose()
+                    .expect("graph can be loaded")
+            };
+            for sorting in [Sorting::BreadthFirst, Sorting::ByCommitTimeNewestFirst] {
+                assert_eq!(
+                    traverse(&odb, Grafts::default(), sorting, graph())?,
+                    [C4, C3, C2, C1].map(hex_to_id)
+                );
+                assert_eq!(
+                    traverse(&odb, grafts.clone(), sorting, graph())?,
+                    [C4, C3, C1].map(hex_to_id),
+                    "c2 is skipped as c3 now has c1 as parent"
+                );
+            }
+        }
+
+        let mut grafts = Grafts::default();
+        grafts.insert(hex_to_id(C3), None);
+        assert_eq!(
+            traverse(&odb, grafts, Sorting::BreadthFirst, None)?,
+            [C4, C3].map(hex_to_id),
+            "grafts can remove all parents"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_grafts() {
+        for input in ["not-a-hash\n", "0000000000000000000000000000000000000000 abc\n"] {
+            assert!(matches!(
+                Grafts::from_bytes(input.as_bytes()),
+                Err(commit::grafts::decode::Error::InvalidLine { line_number: 1, .. })
+            ));
+        }
+    }
 }
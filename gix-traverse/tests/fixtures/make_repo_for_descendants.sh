@@ -0,0 +1,45 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+tick
+function commit() {
+  local message=${1:?first argument is the commit message}
+  tick
+  git commit --allow-empty -m "$message"
+  git tag "$message"
+}
+
+git init
+git config merge.ff false
+
+git checkout -q -b main
+commit root
+commit fork
+
+commit left
+
+git checkout -q -b outside fork
+commit outside
+
+git checkout -q -b right fork
+commit right
+
+git checkout -q main
+git merge right -m merge
+git tag merge
+commit tip
+
+git commit-graph write --no-progress --reachable
+git repack -adq
@@ -0,0 +1,13 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q repo
+(cd repo
+  git checkout -q -b main
+  for message in c1 c2 c3 c4; do
+    git commit -q --allow-empty -m "$message"
+  done
+  git commit-graph write --no-progress --reachable
+)
+
+git clone -q --depth 2 "file://$PWD/repo" shallow
@@ -26,6 +26,16 @@ pub mod parse;
 
 /// Parse the given `bytes` as a [git url](Url).
 ///
+/// Like git, this distinguishes between
+///
+/// * URLs with a scheme, like `ssh://user@host:22/path` or `file:///path`,
+/// * scp-like `user@host:path` targets without a scheme and with a `:` before the first `/`, which are [`Scheme::Ssh`] URLs,
+/// * `<transport>::<address>` inputs like `ext::ssh host %S` or `fd::0`, which are [`Scheme::Ext`] URLs with the address
+///   as path that are handled by the respective remote helper,
+/// * and local paths, which are [`Scheme::File`] URLs.
+///
+/// All of these are serialized in the form they were parsed from.
+///
 /// # Note
 ///
 /// We cannot and should never have to deal with UTF-16 encoded windows strings, so bytes input is acceptable.
@@ -39,6 +49,7 @@ pub fn parse(input: &BStr) -> Result<Url, parse::Error> {
         }
         InputScheme::Url { protocol_end } => parse::url(input, protocol_end),
         InputScheme::Scp { colon } => parse::scp(input, colon),
+        InputScheme::Helper { transport_end } => parse::helper(input, transport_end),
     }
 }
 
@@ -105,6 +116,7 @@ pub struct Url {
     /// The port to use when connecting to a host. If `None`, standard ports depending on `scheme` will be used.
     pub port: Option<u16>,
     /// The path portion of the URL, usually the location of the git repository.
+    /// For remote helper URLs like `ext::<address>`, it's the address passed to the helper.
     ///
     /// # Security Warning
     ///
@@ -299,6 +311,10 @@ fn looks_like_command_line_option(b: &[u8]) -> bool {
     b.first() == Some(&b'-')
 }
 
+fn starts_with_drive_letter(path: &[u8]) -> bool {
+    path.len() >= 2 && path[0].is_ascii_alphabetic() && path[1] == b':'
+}
+
 /// Transformation
 impl Url {
     /// Turn a file URL like `file://relative` into `file:///root/relative`, hence it assures the URL's path component is absolute, using
@@ -313,10 +329,24 @@ impl Url {
 /// Serialization
 impl Url {
     /// Write this URL losslessly to `out`, ready to be parsed again.
+    ///
+    /// The alternative form is used if the URL was parsed from it, so scp-like URLs remain scp-like and helper
+    /// URLs like `ext::<address>` retain their syntax.
     pub fn write_to(&self, mut out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        if self.serialize_alternative_form {
+            if let Scheme::Ext(transport) = &self.scheme {
+                out.write_all(transport.as_bytes())?;
+                out.write_all(b"::")?;
+                return out.write_all(&self.path);
+            }
+        }
         if !(self.serialize_alternative_form && (self.scheme == Scheme::File || self.scheme == Scheme::Ssh)) {
             out.write_all(self.scheme.as_str().as_bytes())?;
             out.write_all(b"://")?;
+            if self.scheme == Scheme::File && self.host.is_none() && starts_with_drive_letter(&self.path) {
+                // `file://c:/path` would be parsed back with `c:` as host on non-Windows platforms.
+                out.write_all(b"/")?;
+            }
         }
         match (&self.user, &self.host) {
             (Some(user), Some(host)) => {
@@ -59,10 +59,21 @@ impl UrlKind {
 pub(crate) enum InputScheme {
     Url { protocol_end: usize },
     Scp { colon: usize },
+    Helper { transport_end: usize },
     Local,
 }
 
 pub(crate) fn find_scheme(input: &BStr) -> InputScheme {
+    // `<transport>::<address>` always selects a remote helper, like `ext::` or `fd::`, just like git does.
+    let transport_end = input
+        .iter()
+        .enumerate()
+        .position(|(idx, b)| !(b.is_ascii_alphanumeric() || (idx != 0 && matches!(b, b'+' | b'-' | b'.'))))
+        .unwrap_or(input.len());
+    if transport_end != 0 && input[transport_end..].starts_with(b"::") {
+        return InputScheme::Helper { transport_end };
+    }
+
     // TODO: url's may only contain `:/`, we should additionally check if the characters used for
     //       protocol are all valid
     if let Some(protocol_end) = input.find("://") {
@@ -158,6 +169,27 @@ pub(crate) fn scp(input: &BStr, colon: usize) -> Result<crate::Url, Error> {
     })
 }
 
+pub(crate) fn helper(input: &BStr, transport_end: usize) -> Result<crate::Url, Error> {
+    let address = &input[transport_end + "::".len()..];
+    if address.is_empty() {
+        return Err(Error::MissingRepositoryPath {
+            url: input.to_owned(),
+            kind: UrlKind::Url,
+        });
+    }
+    let transport = input[..transport_end].to_str().expect("checked to be ASCII");
+
+    Ok(crate::Url {
+        serialize_alternative_form: true,
+        scheme: Scheme::Ext(transport.into()),
+        user: None,
+        password: None,
+        host: None,
+        port: None,
+        path: address.into(),
+    })
+}
+
 fn url_user(url: &url::Url) -> Option<String> {
     if url.username().is_empty() && url.password().is_none() {
         None
@@ -124,6 +124,15 @@ fn url_from_relative_path_with_colon_in_name() -> crate::Result {
     )
 }
 
+#[test]
+fn drive_letter_paths_are_serialized_with_an_empty_host() {
+    assert_eq!(
+        url(Scheme::File, None, None, None, b"c:/users/1").to_bstring(),
+        "file:///c:/users/1",
+        "otherwise `c:` would be the host when parsed on non-Windows platforms"
+    );
+}
+
 #[cfg(windows)]
 mod windows {
     use gix_url::Scheme;
@@ -151,9 +160,7 @@ mod windows {
             url_alternate(Scheme::File, None, None, None, b"C:\\users\\1\\"),
         )?;
         // A special hack to support URLs on windows that are prefixed with `/` even though absolute.
-        let url = assert_url("file:///c:/users/2", url(Scheme::File, None, None, None, b"c:/users/2"))?;
-        assert_eq!(url.to_bstring(), "file://c:/users/2");
-        Ok(())
+        assert_url_roundtrip("file:///c:/users/2", url(Scheme::File, None, None, None, b"c:/users/2"))
     }
 
     #[test]
@@ -174,10 +181,16 @@ mod windows {
 
     #[test]
     fn file_path_with_protocol() -> crate::Result {
-        assert_url_roundtrip(
+        let url = assert_url(
             "file://x:/path/to/git",
             url(Scheme::File, None, None, None, b"x:/path/to/git"),
-        )
+        )?;
+        assert_eq!(
+            url.to_bstring(),
+            "file:///x:/path/to/git",
+            "it's serialized so it can be parsed the same on all platforms"
+        );
+        Ok(())
     }
 }
 
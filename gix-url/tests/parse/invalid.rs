@@ -3,11 +3,6 @@ use gix_url::parse::Error::*;
 
 use crate::parse::parse;
 
-#[test]
-fn relative_path_due_to_double_colon() {
-    assert_matches!(parse("invalid:://host.xz/path/to/repo.git/"), Err(RelativeUrl { .. }))
-}
-
 #[test]
 fn ssh_missing_path() {
     assert_matches!(parse("ssh://host.xz"), Err(MissingRepositoryPath { .. }))
@@ -39,3 +34,8 @@ fn file_missing_host_path_separator() {
 fn missing_port_despite_indication() {
     assert_matches!(parse("ssh://host.xz:"), Err(MissingRepositoryPath { .. }))
 }
+
+#[test]
+fn helper_missing_address() {
+    assert_matches!(parse("ext::"), Err(MissingRepositoryPath { .. }))
+}
@@ -121,6 +121,46 @@ mod git {
     }
 }
 
+mod helper {
+    use gix_url::Scheme;
+
+    use crate::parse::{assert_url_roundtrip, url_alternate};
+
+    #[test]
+    fn ext_with_command() -> crate::Result {
+        assert_url_roundtrip(
+            "ext::ssh -i id_rsa %S host.xz",
+            url_alternate(Scheme::Ext("ext".into()), None, None, None, b"ssh -i id_rsa %S host.xz"),
+        )
+    }
+
+    #[test]
+    fn fd_with_file_descriptors() -> crate::Result {
+        assert_url_roundtrip(
+            "fd::3,4/path",
+            url_alternate(Scheme::Ext("fd".into()), None, None, None, b"3,4/path"),
+        )
+    }
+
+    #[test]
+    fn the_helper_takes_precedence_over_the_address() -> crate::Result {
+        assert_url_roundtrip(
+            "invalid:://host.xz/path/to/repo.git/",
+            url_alternate(
+                Scheme::Ext("invalid".into()),
+                None,
+                None,
+                None,
+                b"//host.xz/path/to/repo.git/",
+            ),
+        )?;
+        assert_url_roundtrip(
+            "ssh::git@host.xz:repo",
+            url_alternate(Scheme::Ext("ssh".into()), None, None, None, b"git@host.xz:repo"),
+        )
+    }
+}
+
 mod unknown {
     use gix_url::Scheme;
 
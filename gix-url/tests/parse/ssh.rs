@@ -74,6 +74,21 @@ fn ssh_alias_without_username() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn scp_like_is_distinct_from_ssh() -> crate::Result {
+    let scp = gix_url::parse("git@github.com:org/repo".into())?;
+    let ssh = gix_url::parse("ssh://git@github.com/org/repo".into())?;
+    assert_eq!(scp.scheme, ssh.scheme);
+    assert_ne!(scp, ssh, "the path differs as well as the form");
+    assert_eq!(
+        scp.to_bstring(),
+        "git@github.com:org/repo",
+        "scp-like URLs aren't normalized"
+    );
+    assert_eq!(ssh.to_bstring(), "ssh://git@github.com/org/repo");
+    Ok(())
+}
+
 #[test]
 fn default_port_is_22() -> crate::Result {
     let url = url_alternate(Scheme::Ssh, None, "host.xz", None, b"path/to/git");
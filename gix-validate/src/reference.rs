@@ -26,7 +26,145 @@ pub mod name {
     }
 }
 
-use bstr::BStr;
+///
+#[allow(clippy::empty_docs)]
+pub mod check_format {
+    use bstr::BString;
+
+    /// Further specify what to allow in [`check_format()`](super::check_format()).
+    ///
+    /// Note that the `Default` implementation is the strictest, just like `git check-ref-format` without flags.
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct Options {
+        /// If `true`, allow names without a `/`, like `main` or `HEAD`, similar to `--allow-onelevel`.
+        pub allow_onelevel: bool,
+        /// If `true`, allow a single `*` anywhere in the name so it can be used as pattern in a refspec,
+        /// similar to `--refspec-pattern`.
+        pub refspec_pattern: bool,
+        /// If `true`, remove leading slashes and collapse consecutive slashes before validating the name,
+        /// similar to `--normalize`.
+        pub normalize: bool,
+    }
+
+    /// The error returned by [`check_format()`](super::check_format()), identifying the rule that was violated.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("A ref must not be empty")]
+        Empty,
+        #[error("A ref must not be the single character '@'")]
+        SingleAt,
+        #[error("A ref must contain at least one '/' unless one-level names are allowed")]
+        OneLevel,
+        #[error("A ref must not start with a '/'")]
+        StartsWithSlash,
+        #[error("A ref must not end with a '/'")]
+        EndsWithSlash,
+        #[error("A ref must not end with a '.'")]
+        EndsWithDot,
+        #[error("A ref must not contain multiple consecutive slashes")]
+        RepeatedSlash,
+        #[error("A ref component must not start with a '.': {component:?}")]
+        ComponentStartsWithDot { component: BString },
+        #[error("A ref component must not end with '.lock': {component:?}")]
+        ComponentEndsWithLock { component: BString },
+        #[error("A ref must not contain '..' as it may be mistaken for a range")]
+        DoubleDot,
+        #[error("A ref must not contain '@{{' which is a part of a ref-log")]
+        ReflogPortion,
+        #[error("A ref must not contain ascii control characters, spaces or any of '~^:?[\\': {byte:?}")]
+        InvalidByte { byte: BString },
+        #[error("A ref must not contain '*' unless it's a refspec pattern, which may contain it once")]
+        Asterisk,
+    }
+}
+
+use std::borrow::Cow;
+
+use bstr::{BStr, BString, ByteSlice};
+
+/// Validate `input` as reference name like `git check-ref-format` would, with `options` to control what is allowed,
+/// and return it, possibly normalized.
+///
+/// In contrast to [`name()`], this enforces all documented rules and reports the violated one precisely.
+pub fn check_format(
+    input: &BStr,
+    check_format::Options {
+        allow_onelevel,
+        refspec_pattern,
+        normalize,
+    }: check_format::Options,
+) -> Result<Cow<'_, BStr>, check_format::Error> {
+    use check_format::Error;
+
+    let name: Cow<'_, BStr> = if normalize && (input.starts_with(b"/") || input.find(b"//").is_some()) {
+        let mut out = BString::default();
+        for byte in input.iter().copied() {
+            if byte == b'/' && matches!(out.last(), None | Some(b'/')) {
+                continue;
+            }
+            out.push(byte);
+        }
+        Cow::Owned(out)
+    } else {
+        Cow::Borrowed(input)
+    };
+
+    if name.is_empty() {
+        return Err(Error::Empty);
+    }
+    if name.as_ref() == "@" {
+        return Err(Error::SingleAt);
+    }
+    if name.starts_with(b"/") {
+        return Err(Error::StartsWithSlash);
+    }
+    if name.ends_with(b"/") {
+        return Err(Error::EndsWithSlash);
+    }
+    if name.ends_with(b".") {
+        return Err(Error::EndsWithDot);
+    }
+
+    for component in name.split_str("/") {
+        if component.is_empty() {
+            return Err(Error::RepeatedSlash);
+        }
+        if component.starts_with(b".") {
+            return Err(Error::ComponentStartsWithDot {
+                component: component.into(),
+            });
+        }
+        if component.ends_with(b".lock") {
+            return Err(Error::ComponentEndsWithLock {
+                component: component.into(),
+            });
+        }
+    }
+
+    let mut previous = 0;
+    let mut may_have_asterisk = refspec_pattern;
+    for byte in name.iter().copied() {
+        match byte {
+            b'\\' | b'^' | b':' | b'[' | b'?' | b' ' | b'~' | b'\0'..=b'\x1F' | b'\x7F' => {
+                return Err(Error::InvalidByte {
+                    byte: (&[byte][..]).into(),
+                })
+            }
+            b'*' if may_have_asterisk => may_have_asterisk = false,
+            b'*' => return Err(Error::Asterisk),
+            b'.' if previous == b'.' => return Err(Error::DoubleDot),
+            b'{' if previous == b'@' => return Err(Error::ReflogPortion),
+            _ => {}
+        }
+        previous = byte;
+    }
+
+    if !allow_onelevel && name.find_byte(b'/').is_none() {
+        return Err(Error::OneLevel);
+    }
+    Ok(name)
+}
 
 /// Validate a reference name running all the tests in the book. This disallows lower-case references, but allows
 /// ones like `HEAD`.
@@ -158,3 +158,132 @@ mod name {
         );
     }
 }
+
+mod check_format {
+    use bstr::ByteSlice;
+    use gix_validate::reference::{
+        check_format,
+        check_format::{Error, Options},
+    };
+
+    const ONELEVEL: Options = Options {
+        allow_onelevel: true,
+        refspec_pattern: false,
+        normalize: false,
+    };
+    const PATTERN: Options = Options {
+        allow_onelevel: false,
+        refspec_pattern: true,
+        normalize: false,
+    };
+    const NORMALIZE: Options = Options {
+        allow_onelevel: false,
+        refspec_pattern: false,
+        normalize: true,
+    };
+
+    fn check(input: &str, options: Options) -> Result<String, Error> {
+        check_format(input.as_bytes().as_bstr(), options).map(|name| name.to_string())
+    }
+
+    #[test]
+    fn valid() -> Result<(), Error> {
+        for name in [
+            "refs/heads/main",
+            "heads/feature/x.y",
+            "refs/tags/v1.0-rc_2",
+            "refs/heads/@",
+            "refs/heads/a@b",
+            "refs/heads/你好吗",
+        ] {
+            assert_eq!(check(name, Options::default())?, name);
+        }
+        assert_eq!(check("main", ONELEVEL)?, "main");
+        assert_eq!(check("HEAD", ONELEVEL)?, "HEAD");
+        assert_eq!(check("refs/heads/*", PATTERN)?, "refs/heads/*");
+        assert_eq!(check("refs/heads/feature-*-x", PATTERN)?, "refs/heads/feature-*-x");
+        assert_eq!(check("//refs///heads//main", NORMALIZE)?, "refs/heads/main");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid() {
+        for (name, options, expected) in [
+            ("", Options::default(), "Empty"),
+            ("@", ONELEVEL, "SingleAt"),
+            ("main", Options::default(), "OneLevel"),
+            ("/refs/heads/main", Options::default(), "StartsWithSlash"),
+            ("refs/heads/main/", NORMALIZE, "EndsWithSlash"),
+            ("refs/heads/main.", Options::default(), "EndsWithDot"),
+            ("refs//heads/main", Options::default(), "RepeatedSlash"),
+            ("refs/heads/.hidden", Options::default(), "ComponentStartsWithDot"),
+            ("refs/heads/./main", Options::default(), "ComponentStartsWithDot"),
+            ("refs/heads/main.lock", Options::default(), "ComponentEndsWithLock"),
+            ("refs/heads.lock/main", Options::default(), "ComponentEndsWithLock"),
+            ("refs/heads/a..b", Options::default(), "DoubleDot"),
+            ("refs/heads/a@{1}", Options::default(), "ReflogPortion"),
+            ("refs/heads/a b", Options::default(), "InvalidByte"),
+            ("refs/heads/a~1", Options::default(), "InvalidByte"),
+            ("refs/heads/a^", Options::default(), "InvalidByte"),
+            ("refs/heads/a:b", Options::default(), "InvalidByte"),
+            ("refs/heads/a?", Options::default(), "InvalidByte"),
+            ("refs/heads/[a]", Options::default(), "InvalidByte"),
+            ("refs/heads/a\\b", Options::default(), "InvalidByte"),
+            ("refs/heads/a\x7fb", Options::default(), "InvalidByte"),
+            ("refs/heads/a\tb", Options::default(), "InvalidByte"),
+            ("refs/heads/*", Options::default(), "Asterisk"),
+            ("refs/*/*", PATTERN, "Asterisk"),
+        ] {
+            let err = check(name, options).expect_err(name);
+            assert!(
+                format!("{err:?}").starts_with(expected),
+                "{name:?}: expected {expected}, got {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn matches_git() -> gix_testtools::Result {
+        for name in [
+            "refs/heads/main",
+            "main",
+            "@",
+            "refs/heads/@",
+            "refs/heads/.hidden",
+            "refs/heads.lock/main",
+            "refs/heads/main.",
+            "refs/heads/a..b",
+            "refs/heads/a@{1}",
+            "refs/heads/*",
+            "refs/*/*",
+            "//refs//heads/main",
+            "refs/heads/main/",
+            "refs/heads/a b",
+        ] {
+            for (flags, options) in [
+                (&[][..], Options::default()),
+                (&["--allow-onelevel"][..], ONELEVEL),
+                (&["--refspec-pattern"][..], PATTERN),
+                (&["--normalize"][..], NORMALIZE),
+            ] {
+                let out = std::process::Command::new("git")
+                    .arg("check-ref-format")
+                    .args(flags)
+                    .arg(name)
+                    .output()?;
+                let ours = check(name, options);
+                assert_eq!(
+                    ours.is_ok(),
+                    out.status.success(),
+                    "{name:?} with {flags:?}: we say {ours:?}"
+                );
+                if let Ok(normalized) = ours {
+                    if options.normalize {
+                        assert_eq!(out.stdout.trim().as_bstr(), normalized, "{name:?}");
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
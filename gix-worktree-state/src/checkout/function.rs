@@ -1,4 +1,9 @@
-use std::sync::atomic::AtomicBool;
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use bstr::{BString, ByteSlice};
 
 use gix_features::{interrupt, parallel::in_parallel_with_finalize};
 use gix_worktree::{stack, Stack};
@@ -11,6 +16,8 @@ use crate::checkout::chunk;
 /// operation will abort.
 /// `options` provide a lot of context on how to perform the operation.
 ///
+/// To checkout a tree, turn it into an index first with [`gix_index::State::from_tree()`].
+///
 /// ### Handling the return value
 ///
 /// Note that interruption still produce an `Ok(…)` value, so the caller should look at `should_interrupt` to communicate the outcome.
@@ -50,6 +57,9 @@ where
 {
     let num_files = files.counter();
     let num_bytes = bytes.counter();
+    if options.dry_run {
+        return Ok(dry_run(index, paths, &num_files, options.fs.ignore_case));
+    }
     let dir = dir.into();
     let (chunk_size, thread_limit, num_threads) = gix_features::parallel::optimize_chunk_size_and_thread_limit(
         100,
@@ -150,5 +160,49 @@ where
         bytes_written,
         delayed_paths_unknown,
         delayed_paths_unprocessed,
+        paths_to_write: Vec::new(),
     })
 }
+
+/// List the paths of all entries that would be checked out, along with the paths that would collide with a previously
+/// checked out file or directory if `ignore_case` is `true`.
+fn dry_run(
+    index: &gix_index::State,
+    paths: &gix_index::PathStorageRef,
+    files: &AtomicUsize,
+    ignore_case: bool,
+) -> crate::checkout::Outcome {
+    let mut out = crate::checkout::Outcome::default();
+    let (mut seen_files, mut seen_dirs) = (HashSet::<BString>::new(), HashSet::<BString>::new());
+    for entry in index.entries() {
+        if entry.flags.contains(gix_index::entry::Flags::SKIP_WORKTREE) {
+            files.fetch_add(1, Ordering::Relaxed);
+            continue;
+        }
+        let path = entry.path_in(paths);
+        if ignore_case {
+            let folded = path.to_ascii_lowercase();
+            let leading_dirs = folded
+                .iter()
+                .enumerate()
+                .filter(|(_, b)| **b == b'/')
+                .map(|(idx, _)| folded[..idx].as_bstr());
+            let collides_with_file = leading_dirs.clone().any(|dir| seen_files.contains(dir));
+            for dir in leading_dirs {
+                seen_dirs.insert(dir.to_owned());
+            }
+            if collides_with_file || seen_dirs.contains(folded.as_bstr()) || !seen_files.insert(folded.into()) {
+                out.collisions.push(crate::checkout::Collision {
+                    path: path.to_owned(),
+                    error_kind: std::io::ErrorKind::AlreadyExists,
+                });
+                files.fetch_add(1, Ordering::Relaxed);
+                continue;
+            }
+        }
+        out.paths_to_write.push(path.to_owned());
+        out.files_updated += 1;
+        files.fetch_add(1, Ordering::Relaxed);
+    }
+    out
+}
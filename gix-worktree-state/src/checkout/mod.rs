@@ -34,6 +34,8 @@ pub struct Outcome {
     pub delayed_paths_unknown: Vec<BString>,
     /// All paths that were left unprocessed, because they were never listed by the process even though we passed them.
     pub delayed_paths_unprocessed: Vec<BString>,
+    /// The paths that would have been written if [`Options::dry_run`] is enabled, in the order of their index entries.
+    pub paths_to_write: Vec<BString>,
 }
 
 /// Options to further configure the checkout operation.
@@ -68,6 +70,11 @@ pub struct Options {
     pub filters: gix_filter::Pipeline,
     /// Control how long-running processes may use the 'delay' capability.
     pub filter_process_delay: gix_filter::driver::apply::Delay,
+    /// If true, default false, nothing is written to disk and no object is read, but the paths of all entries that would be
+    /// written are listed in [`Outcome::paths_to_write`] instead.
+    /// If the filesystem [ignores case](gix_fs::Capabilities::ignore_case), the paths that would collide with each other are
+    /// listed in [`Outcome::collisions`] as well.
+    pub dry_run: bool,
 }
 
 /// The error returned by the [checkout()][crate::checkout()] function.
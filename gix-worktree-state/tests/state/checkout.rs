@@ -549,6 +549,55 @@ fn collisions_are_detected_on_a_case_insensitive_filesystem_even_with_delayed_fi
     }
 }
 
+#[test]
+fn dry_run_lists_paths_without_writing_anything() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.dry_run = true;
+    let (_source_tree, destination, index, outcome) =
+        checkout_index_in_tmp_dir_opts(opts, "make_mixed", None, |_| false, |_| Ok(()))?;
+
+    assure_is_empty(destination.path())?;
+    assert_eq!(
+        outcome.paths_to_write,
+        index
+            .entries()
+            .iter()
+            .map(|e| e.path(&index).to_owned())
+            .collect::<Vec<_>>(),
+        "all entries would be written, and no object is needed for that"
+    );
+    assert_eq!(outcome.files_updated, index.entries().len());
+    assert_eq!(outcome.bytes_written, 0);
+    assert!(outcome.collisions.is_empty() && outcome.errors.is_empty());
+    Ok(())
+}
+
+#[test]
+fn dry_run_predicts_collisions_on_a_case_insensitive_filesystem() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.dry_run = true;
+    opts.fs.ignore_case = true;
+    let (_source_tree, destination, _index, outcome) =
+        checkout_index_in_tmp_dir(opts, "make_ignorecase_collisions", None)?;
+
+    assure_is_empty(destination.path())?;
+    assert_eq!(
+        outcome.paths_to_write,
+        [".gitattributes", "D/B", "D/C", "FILE_X", "X", "link-to-X"],
+        "the first of the colliding paths would be written"
+    );
+    assert_eq!(
+        outcome
+            .collisions
+            .iter()
+            .map(|c| (c.path.to_str_lossy().into_owned(), c.error_kind))
+            .collect::<Vec<_>>(),
+        ["FILE_x", "d", "file_X", "file_x", "x"].map(|path| (path.to_owned(), AlreadyExists)),
+        "files collide with files and directories that only differ in case"
+    );
+    Ok(())
+}
+
 fn multi_threaded() -> bool {
     gix_features::parallel::num_threads(None) > 1
 }
@@ -87,10 +87,7 @@ pub(crate) fn write_stream(
 }
 
 fn byte_to_hash(b: u8) -> gix_hash::Kind {
-    match b {
-        0 => gix_hash::Kind::Sha1,
-        _ => unreachable!("BUG: we control the protocol"),
-    }
+    gix_hash::Kind::try_from(b + 1).expect("BUG: we control the protocol")
 }
 
 fn byte_to_mode(b: u8) -> gix_object::tree::EntryMode {
@@ -107,9 +104,7 @@ fn byte_to_mode(b: u8) -> gix_object::tree::EntryMode {
 }
 
 fn hash_to_byte(h: gix_hash::Kind) -> u8 {
-    match h {
-        gix_hash::Kind::Sha1 => 0,
-    }
+    h as u8 - 1
 }
 
 fn mode_to_byte(m: gix_object::tree::EntryMode) -> u8 {
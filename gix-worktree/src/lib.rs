@@ -64,3 +64,7 @@ pub(crate) type PathIdMapping = (BString, gix_hash::ObjectId);
 ///
 #[allow(clippy::empty_docs)]
 pub mod stack;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod sparse;
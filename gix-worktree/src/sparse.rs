@@ -0,0 +1,172 @@
+//! Utilities for sparse checkouts in *cone mode*, where the `.git/info/sparse-checkout` file lists directories instead
+//! of arbitrary patterns.
+use std::collections::BTreeSet;
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+
+/// The directories of a sparse checkout in *cone mode*, along with the directories leading up to them.
+///
+/// All directories are relative to the root of the worktree, without leading or trailing slashes, like `a/b`.
+/// Files at the root of the worktree are always included.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cone {
+    /// Directories whose entire content is included.
+    pub recursive: BTreeSet<BString>,
+    /// Directories of which only the files directly contained in them are included, as they lead up
+    /// to one or more of the `recursive` directories.
+    pub parents: BTreeSet<BString>,
+}
+
+/// Initialization
+impl Cone {
+    /// Create a new instance to contain all `directories` recursively, just like `git sparse-checkout set --cone` would.
+    ///
+    /// Leading and trailing slashes are ignored, as are empty directories and all directories that are already contained
+    /// in another one. All directories leading up to the remaining directories become [parents](Self::parents).
+    pub fn from_directories(directories: impl IntoIterator<Item = impl AsRef<BStr>>) -> Self {
+        let all: BTreeSet<BString> = directories
+            .into_iter()
+            .map(|dir| dir.as_ref().trim_with(|c| c == '/').as_bstr().to_owned())
+            .filter(|dir| !dir.is_empty())
+            .collect();
+        let recursive: BTreeSet<BString> = all
+            .iter()
+            .filter(|dir| !ancestors(dir.as_ref()).any(|ancestor| all.contains(ancestor)))
+            .cloned()
+            .collect();
+        let parents = recursive
+            .iter()
+            .flat_map(|dir| ancestors(dir.as_ref()))
+            .map(ToOwned::to_owned)
+            .collect();
+        Cone { recursive, parents }
+    }
+
+    /// Parse the content of a `sparse-checkout` file and return `None` if it isn't in cone mode, as it contains patterns
+    /// other than the ones `git` would write for cone mode.
+    ///
+    /// Empty lines and comments are ignored, and glob characters have to be escaped with a backslash to be used literally.
+    pub fn from_bytes(input: &[u8]) -> Option<Self> {
+        let mut out = Cone::default();
+        for line in input.lines() {
+            let line = line.trim_end_with(|c| c == '\r');
+            if line.is_empty() || line.starts_with(b"#") || line == b"/*" || line == b"!/*/" {
+                continue;
+            }
+            match line.strip_prefix(b"!") {
+                Some(negated) => {
+                    let dir = unescape_directory(negated.strip_suffix(b"/*/")?)?;
+                    if !out.recursive.remove(&dir) {
+                        return None;
+                    }
+                    out.parents.insert(dir);
+                }
+                None => {
+                    let dir = unescape_directory(line.strip_suffix(b"/")?)?;
+                    out.recursive.insert(dir);
+                }
+            }
+        }
+        Some(out)
+    }
+}
+
+/// Access
+impl Cone {
+    /// Return the lines of the `sparse-checkout` file, without trailing newline, exactly in the order `git` would write them.
+    ///
+    /// Recursive directories that are contained in other recursive directories are skipped, just like parents that are
+    /// recursive or contained in a recursive directory.
+    pub fn to_patterns(&self) -> Vec<BString> {
+        let mut out = vec![BString::from("/*"), BString::from("!/*/")];
+        for dir in self
+            .parents
+            .iter()
+            .filter(|dir| !self.recursive.contains(*dir) && !self.is_in_recursive_directory(dir.as_ref()))
+        {
+            let escaped = escape_directory(dir.as_ref());
+            let mut negated = BString::from("!");
+            negated.push_str(&escaped);
+            negated.push_str("*/");
+            out.push(escaped);
+            out.push(negated);
+        }
+        out.extend(
+            self.recursive
+                .iter()
+                .filter(|dir| !self.is_in_recursive_directory(dir.as_ref()))
+                .map(|dir| escape_directory(dir.as_ref())),
+        );
+        out
+    }
+
+    /// Write all [patterns](Self::to_patterns()) to `out`, each followed by a newline, to produce a `sparse-checkout` file.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        for pattern in self.to_patterns() {
+            out.write_all(&pattern)?;
+            out.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Return `true` if `relative_path`, a directory if `is_dir` is `true`, is contained in the sparse checkout.
+    ///
+    /// Files are included if they are at the root, directly in one of our [parents](Self::parents), or anywhere in one
+    /// of our [recursive directories](Self::recursive). Directories are included if they are one of our directories or
+    /// are contained in a recursive one.
+    pub fn is_included(&self, relative_path: &BStr, is_dir: bool) -> bool {
+        if self.recursive.contains(relative_path) || self.is_in_recursive_directory(relative_path) {
+            return true;
+        }
+        if is_dir {
+            return self.parents.contains(relative_path);
+        }
+        match relative_path.rfind_byte(b'/') {
+            Some(pos) => self.parents.contains(relative_path[..pos].as_bstr()),
+            None => true,
+        }
+    }
+
+    fn is_in_recursive_directory(&self, relative_path: &BStr) -> bool {
+        ancestors(relative_path).any(|ancestor| self.recursive.contains(ancestor))
+    }
+}
+
+/// Return all leading directories of `path`, excluding the root and `path` itself.
+fn ancestors(path: &BStr) -> impl Iterator<Item = &BStr> {
+    path.find_iter("/").map(move |pos| path[..pos].as_bstr())
+}
+
+fn is_glob_special(b: u8) -> bool {
+    matches!(b, b'*' | b'?' | b'[' | b'\\')
+}
+
+/// Turn `dir` into `/dir/`, escaping glob characters like `git` does.
+fn escape_directory(dir: &BStr) -> BString {
+    let mut out = BString::from("/");
+    for &b in dir.iter() {
+        if is_glob_special(b) {
+            out.push(b'\\');
+        }
+        out.push(b);
+    }
+    out.push(b'/');
+    out
+}
+
+/// Turn `/dir` into `dir`, or return `None` if it contains unescaped glob characters.
+fn unescape_directory(pattern: &[u8]) -> Option<BString> {
+    let pattern = pattern.strip_prefix(b"/")?;
+    let mut out = BString::default();
+    let mut bytes = pattern.iter().copied();
+    while let Some(b) = bytes.next() {
+        match b {
+            b'\\' => out.push(bytes.next()?),
+            b if is_glob_special(b) => return None,
+            b => out.push(b),
+        }
+    }
+    let out = out.trim_with(|c| c == '/');
+    (!out.is_empty()).then(|| out.as_bstr().to_owned())
+}
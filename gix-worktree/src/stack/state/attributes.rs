@@ -62,8 +62,10 @@ impl Attributes {
     /// Create a new instance from an attribute match group that represents `globals`. It can more easily be created with
     /// [`AttributeMatchGroup::new_globals()`].
     ///
-    /// * `globals` contribute first and consist of all globally available, static files.
+    /// * `globals` contribute last and consist of all globally available, static files, like the built-in macros.
     /// * `info_attributes` is a path that should refer to `.git/info/attributes`, and it's not an error if the file doesn't exist.
+    ///   Its patterns contribute first, before the ones of all `.gitattributes` files from the deepest directory to the
+    ///   root, and the macros it defines are available to all of them.
     /// * `case` is used to control case-sensitivity during matching.
     /// * `source` specifies from where the directory-based attribute files should be loaded from.
     pub fn new(
@@ -75,6 +77,7 @@ impl Attributes {
         Attributes {
             globals,
             stack: Default::default(),
+            info: Default::default(),
             info_attributes,
             source,
             collection,
@@ -171,9 +174,9 @@ impl Attributes {
         }
 
         // When reading the root, always the first call, we can try to also read the `.git/info/attributes` file which is
-        // by nature never popped, and follows the root, as global.
+        // by nature never popped. Reading it after the root file allows its macros to override the ones defined there.
         if let Some(info_attr) = self.info_attributes.take() {
-            let added = self.stack.add_patterns_file(
+            let added = self.info.add_patterns_file(
                 info_attr,
                 true,
                 None,
@@ -198,7 +201,7 @@ impl Attributes {
         // assure `out` is ready to deal with possibly changed collections (append-only)
         out.initialize(&self.collection);
 
-        let groups = [&self.globals, &self.stack];
+        let groups = [&self.globals, &self.stack, &self.info];
         let mut has_match = false;
         groups.iter().rev().any(|group| {
             has_match |= group.pattern_matching_relative_path(relative_path, case, is_dir, out);
@@ -11,15 +11,16 @@ type IgnoreMatchGroup = gix_ignore::Search;
 #[derive(Default, Clone)]
 #[cfg(feature = "attributes")]
 pub struct Attributes {
-    /// Attribute patterns which aren't tied to the repository root, hence are global, they contribute first.
+    /// Attribute patterns which aren't tied to the repository root, hence are global. They are consulted last.
     globals: AttributeMatchGroup,
     /// Attribute patterns that match the currently set directory (in the stack).
     ///
-    /// Note that the root-level file is always loaded, if present, followed by, the `$GIT_DIR/info/attributes`, if present, based
-    /// on the location of the `info_attributes` file.
+    /// Note that the root-level file is always loaded, if present.
     stack: AttributeMatchGroup,
-    /// The first time we push the root, we have to load additional information from this file if it exists along with the root attributes
-    /// file if possible, and keep them there throughout.
+    /// Attribute patterns of `$GIT_DIR/info/attributes`, which take precedence over all others, just like in `git`.
+    info: AttributeMatchGroup,
+    /// The first time we push the root, we have to load the patterns of `info` from this file if it exists along with the
+    /// root attributes file if possible, and keep them there throughout.
     info_attributes: Option<std::path::PathBuf>,
     /// A lookup table to accelerate searches.
     collection: gix_attributes::search::MetadataCollection,
@@ -39,6 +39,7 @@ function baseline() {
 		echo "a**f test-double-star-no-slash"
 		echo "dir-slash/ never"
 		echo "dir/** always"
+		echo "info-override test=root"
 	) > .gitattributes
 	(
 		echo "g test=a/g"
@@ -48,6 +49,8 @@ function baseline() {
 		echo "h test=a/b/h"
 		echo "d/* test=a/b/d/*"
 		echo "d/yes notest"
+		echo "info-override test=a/b/info-override"
+		echo "info-macro-user info-macro"
 	) > a/b/.gitattributes
 	(
 		echo "global test=global"
@@ -59,6 +62,9 @@ function baseline() {
 	(
 		echo "global test=global"
 		echo "* info=attributes"
+		echo "info-override test=info"
+		echo "[attr]info-macro info-macro-attr -test"
+		echo "[attr]notest test=info-macro"
 	) > .git/info/attributes
 
 
@@ -105,4 +111,7 @@ function baseline() {
   baseline a/b/d/ANY
   baseline a/b/d/yes
   baseline global
+  baseline info-override
+  baseline a/b/info-override
+  baseline a/b/info-macro-user
 )
@@ -0,0 +1,113 @@
+use bstr::{BString, ByteSlice};
+use gix_worktree::sparse::Cone;
+
+fn lines(input: &str) -> Vec<BString> {
+    input.lines().map(Into::into).collect()
+}
+
+#[test]
+fn patterns_are_written_like_git_does() {
+    let cone = Cone::from_directories(["A/B", "C", "x/y/z", "x/", "d*e", "/e/f/", "q[1]/r", "A/B/C", ""]);
+    // Generated with `git sparse-checkout set --cone --skip-checks` using the directories above.
+    let expected = lines(
+        r"/*
+!/*/
+/A/
+!/A/*/
+/e/
+!/e/*/
+/q\[1]/
+!/q\[1]/*/
+/A/B/
+/C/
+/d\*e/
+/e/f/
+/q\[1]/r/
+/x/",
+    );
+    assert_eq!(cone.to_patterns(), expected);
+
+    let mut buf = Vec::new();
+    cone.write_to(&mut buf).unwrap();
+    assert_eq!(
+        buf.lines().map(|l| l.as_bstr().to_owned()).collect::<Vec<_>>(),
+        expected
+    );
+    assert!(buf.ends_with(b"\n"));
+}
+
+#[test]
+fn no_directories_only_include_files_at_the_root() {
+    let cone = Cone::from_directories(None::<&str>);
+    assert_eq!(cone.to_patterns(), lines("/*\n!/*/"));
+    assert!(cone.is_included("file".into(), false));
+    assert!(!cone.is_included("dir".into(), true));
+    assert!(!cone.is_included("dir/file".into(), false));
+}
+
+#[test]
+fn round_trips_through_the_parser() {
+    let cone = Cone::from_directories(["a/b/c", "a/d", "e\\f", "g*"]);
+    let mut buf = Vec::new();
+    cone.write_to(&mut buf).unwrap();
+    assert_eq!(Cone::from_bytes(&buf), Some(cone));
+}
+
+#[test]
+fn generated_patterns_include_exactly_the_directories_and_their_ancestors() {
+    let cone = Cone::from_bytes(
+        Cone::from_directories(["a/b/c", "d"])
+            .to_patterns()
+            .join(&b'\n')
+            .as_slice(),
+    )
+    .expect("cone mode");
+    for (path, is_dir, expected) in [
+        ("root-file", false, true),
+        ("a", true, true),
+        ("a/file", false, true),
+        ("a/b", true, true),
+        ("a/b/file", false, true),
+        ("a/b/c", true, true),
+        ("a/b/c/file", false, true),
+        ("a/b/c/deep/sub/file", false, true),
+        ("a/x", true, false),
+        ("a/x/file", false, false),
+        ("a/b/x", true, false),
+        ("a/b/x/file", false, false),
+        ("a/b/cc", true, false),
+        ("a/b/cc/file", false, false),
+        ("d", true, true),
+        ("d/sub/file", false, true),
+        ("e", true, false),
+        ("e/file", false, false),
+    ] {
+        assert_eq!(cone.is_included(path.into(), is_dir), expected, "{path}");
+    }
+}
+
+#[test]
+fn parsing_ignores_comments_and_empty_lines() {
+    let cone = Cone::from_bytes(b"# comment\n/*\r\n!/*/\n\n/a/\n!/a/*/\n/a/b/\n").expect("cone mode");
+    assert_eq!(cone, Cone::from_directories(["a/b"]));
+}
+
+#[test]
+fn parsing_rejects_non_cone_patterns() {
+    for input in [
+        "*.txt",
+        "/a",
+        "a/",
+        "/a/*/",
+        "!/a/",
+        "!/a/*/",
+        "/*\n!/*/\n!/a/*/\n/a/",
+        "/a*/",
+        "/a?/",
+        "/[ab]/",
+        "/a/**/",
+        "/",
+    ] {
+        assert_eq!(Cone::from_bytes(input.as_bytes()), None, "{input:?}");
+    }
+}
@@ -0,0 +1,402 @@
+//! Find the first commit that introduced a change by searching the history between good and bad commits, similar to
+//! `git bisect`.
+//!
+//! The state of a bisection is stored like Git stores it, with [`BAD`] pointing to the bad commit and one reference for
+//! each good commit whose name starts with [`GOOD_PREFIX`], so it can be continued with `git bisect` and vice versa.
+use std::io::Write;
+
+use gix_hash::ObjectId;
+use gix_ref::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
+use gix_ref::Target;
+use gix_revwalk::graph;
+
+use crate::bstr::{BString, ByteSlice};
+use crate::Repository;
+
+/// The name of the reference pointing to the bad commit.
+pub const BAD: &str = "refs/bisect/bad";
+/// The prefix of the names of the references pointing to good commits, which is followed by their hexadecimal id.
+pub const GOOD_PREFIX: &str = "refs/bisect/good-";
+
+/// The prefix of all references of a bisection.
+const REFS_PREFIX: &str = "refs/bisect/";
+/// The files in the git directory that belong to a bisection.
+const STATE_FILES: &[&str] = &[
+    "BISECT_START",
+    "BISECT_LOG",
+    "BISECT_TERMS",
+    "BISECT_NAMES",
+    "BISECT_EXPECTED_REV",
+    "BISECT_ANCESTORS_OK",
+    "BISECT_RUN",
+    "BISECT_FIRST_PARENT",
+    "BISECT_HEAD",
+];
+
+/// The error returned by the methods of [`Platform`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The merge base {merge_base} is bad, which means the change was reverted between it and the good commits")]
+    MergeBaseIsBad { merge_base: ObjectId },
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::Error),
+    #[error(transparent)]
+    OpenReferences(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    InitReferenceIter(#[from] crate::reference::iter::init::Error),
+    #[error("Could not iterate the references of the bisection")]
+    IterReferences(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    EditReferences(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    ObjectKind(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    LookupCommit(#[from] gix_revwalk::graph::try_lookup_or_insert_default::Error),
+    #[error(transparent)]
+    MergeBase(#[from] gix_revision::merge_base::Error),
+    #[error("Could not read or write the state of the bisection")]
+    Io(#[from] std::io::Error),
+}
+
+/// What to do next to find the first bad commit, as returned by [`Platform::next()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Step {
+    /// The commit with `id` should be tested next, and [marked](Platform::mark_good()) as good or bad accordingly.
+    Test {
+        /// The id of the commit to test.
+        id: ObjectId,
+        /// The number of commits that may still be the first bad commit, including the bad commit itself.
+        suspects: usize,
+    },
+    /// The first bad commit was found, as it's the only suspect left.
+    FirstBad(ObjectId),
+}
+
+/// A way to drive a bisection whose state is stored in the repository, as returned by
+/// [Repository::bisect()](crate::Repository::bisect()).
+#[derive(Clone, Copy)]
+pub struct Platform<'repo> {
+    pub(crate) repo: &'repo Repository,
+}
+
+impl<'repo> Platform<'repo> {
+    /// Start a new bisection between the `good` commits and the `bad` one, after removing the state of a previous
+    /// bisection, similar to `git bisect start <bad> <good>…`.
+    ///
+    /// The current `HEAD` is recorded as the one to return to once the bisection is [done](Self::reset()).
+    pub fn start(
+        &self,
+        good: impl IntoIterator<Item = impl Into<ObjectId>>,
+        bad: impl Into<ObjectId>,
+    ) -> Result<(), Error> {
+        self.reset()?;
+        let repo = self.repo;
+        let start: BString = match repo.head_name()? {
+            Some(name) => name.shorten().into(),
+            None => repo
+                .head()?
+                .id()
+                .map_or_else(|| "HEAD".into(), |id| id.to_string().into()),
+        };
+        std::fs::write(repo.git_dir().join("BISECT_START"), [start.as_slice(), b"\n"].concat())?;
+        std::fs::write(repo.git_dir().join("BISECT_TERMS"), "bad\ngood\n")?;
+        self.append_to_log("git bisect start\n")?;
+        self.mark_bad(bad)?;
+        for id in good {
+            self.mark_good(id)?;
+        }
+        Ok(())
+    }
+
+    /// Mark the commit with `id` as good, as it doesn't have the change that is searched for, similar to
+    /// `git bisect good <id>`.
+    pub fn mark_good(&self, id: impl Into<ObjectId>) -> Result<(), Error> {
+        let id = id.into();
+        self.mark(format!("{GOOD_PREFIX}{id}"), "good", id)
+    }
+
+    /// Mark the commit with `id` as bad, as it has the change that is searched for, similar to `git bisect bad <id>`.
+    ///
+    /// It replaces the previous bad commit, if there was one.
+    pub fn mark_bad(&self, id: impl Into<ObjectId>) -> Result<(), Error> {
+        self.mark(BAD.into(), "bad", id.into())
+    }
+
+    /// Return the next commit to test, or the first bad commit if it was found, or `None` if there is no bad commit
+    /// or no good commit yet.
+    ///
+    /// Just like `git bisect`, the commit to test is the one which splits the suspects, which are reachable from the
+    /// bad commit but not from any of the good ones, most evenly into those it can reach and all others.
+    /// If good commits aren't reachable from the bad commit, their merge bases with it are tested first.
+    pub fn next(&self) -> Result<Option<Step>, Error> {
+        let _span = gix_trace::coarse!("gix::bisect::next()");
+        let (Some(bad), good) = self.state()? else {
+            return Ok(None);
+        };
+        if good.is_empty() {
+            return Ok(None);
+        }
+
+        let suspects = self.suspects(bad, &good)?;
+        let mut merge_base_graph = self.repo.revision_graph();
+        for merge_base in gix_revision::merge_base(&bad, &good, &mut merge_base_graph)? {
+            if merge_base == bad {
+                return Err(Error::MergeBaseIsBad { merge_base });
+            }
+            if !good.contains(&merge_base) {
+                return Ok(Some(Step::Test {
+                    id: merge_base,
+                    suspects: suspects.ids.len(),
+                }));
+            }
+        }
+
+        let num_suspects = suspects.ids.len();
+        if num_suspects <= 1 {
+            return Ok(Some(Step::FirstBad(suspects.ids.first().copied().unwrap_or(bad))));
+        }
+        Ok(Some(Step::Test {
+            id: suspects.ids[suspects.best()],
+            suspects: num_suspects,
+        }))
+    }
+
+    /// Remove all state of the bisection, and return the name of the branch or the commit that `HEAD` pointed to when
+    /// it was [started](Self::start()), similar to `git bisect reset` but without checking it out.
+    ///
+    /// Return `None` if there was no bisection in progress.
+    pub fn reset(&self) -> Result<Option<BString>, Error> {
+        let repo = self.repo;
+        let start = match std::fs::read(repo.git_dir().join("BISECT_START")) {
+            Ok(start) => Some(start.trim_end().into()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+        let mut edits = Vec::new();
+        for reference in repo.references()?.prefixed(REFS_PREFIX)? {
+            let reference = reference.map_err(Error::IterReferences)?;
+            edits.push(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::Any,
+                    log: RefLog::AndReference,
+                },
+                name: reference.name().to_owned(),
+                deref: false,
+            });
+        }
+        repo.edit_references(edits)?;
+        for name in STATE_FILES {
+            match std::fs::remove_file(repo.git_dir().join(name)) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            }
+        }
+        Ok(start)
+    }
+
+    /// Return the bad commit, if there is one, along with all good commits.
+    pub fn state(&self) -> Result<(Option<ObjectId>, Vec<ObjectId>), Error> {
+        let repo = self.repo;
+        let bad = repo.try_find_reference(BAD)?.map(|reference| reference.id().detach());
+        let mut good = Vec::new();
+        for reference in repo.references()?.prefixed(REFS_PREFIX)? {
+            let reference = reference.map_err(Error::IterReferences)?;
+            if reference.name().as_bstr().starts_with(GOOD_PREFIX.as_bytes()) {
+                good.extend(reference.target().try_id().map(ToOwned::to_owned));
+            }
+        }
+        Ok((bad, good))
+    }
+}
+
+/// Utilities
+impl<'repo> Platform<'repo> {
+    fn mark(&self, name: String, term: &str, id: ObjectId) -> Result<(), Error> {
+        let commit = self.repo.find_object(id)?.try_into_commit()?;
+        let subject = commit.message()?.summary();
+        self.repo.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: format!("bisect: mark {term}").into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Peeled(id),
+            },
+            name: name.try_into().expect("valid"),
+            deref: false,
+        })?;
+        self.append_to_log(&format!("# {term}: [{id}] {subject}\ngit bisect {term} {id}\n"))
+    }
+
+    fn append_to_log(&self, lines: &str) -> Result<(), Error> {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.repo.git_dir().join("BISECT_LOG"))?
+            .write_all(lines.as_bytes())?;
+        Ok(())
+    }
+
+    /// Return all commits reachable from `bad` but not from any of the `good` commits.
+    fn suspects(&self, bad: ObjectId, good: &[ObjectId]) -> Result<Suspects, Error> {
+        let mut graph = self.repo.revision_graph::<graph::Commit<Marks>>();
+        let mut queue = gix_revwalk::PriorityQueue::new();
+        let key =
+            |commit: &graph::Commit<Marks>| (commit.generation.unwrap_or(graph::Generation::MAX), commit.commit_time);
+        for (id, is_good) in std::iter::once((bad, false)).chain(good.iter().map(|id| (*id, true))) {
+            if let Some(commit) = graph.try_lookup_or_insert_commit(id, |marks| {
+                marks.good |= is_good;
+                marks.bad |= !is_good;
+            })? {
+                if !commit.data.queued {
+                    commit.data.queued = true;
+                    queue.insert(key(commit), id);
+                }
+            }
+        }
+
+        // Commits are queued only once, and their marks are read when they are popped, so each queued commit that
+        // isn't good yet is counted exactly once.
+        let mut num_queued_not_good = queue.iter_unordered().filter(|id| !graph[id].data.good).count();
+        let mut ids = Vec::new();
+        while num_queued_not_good != 0 {
+            let (_, id) = queue.pop().expect("there are queued commits");
+            let commit = graph.get_mut(&id).expect("queued commits are in the graph");
+            commit.data.queued = false;
+            let marks = commit.data;
+            if !marks.good {
+                num_queued_not_good -= 1;
+            }
+            if marks.bad && !marks.good && !marks.listed {
+                commit.data.listed = true;
+                ids.push(id);
+            }
+            for parent_id in commit.parents.clone() {
+                let Some(parent) = graph.try_lookup_or_insert_commit(parent_id, |_| {})? else {
+                    // Skip missing commits, which can happen in shallow clones.
+                    continue;
+                };
+                let before = parent.data;
+                parent.data.good |= marks.good;
+                parent.data.bad |= marks.bad;
+                if parent.data == before {
+                    continue;
+                }
+                if !parent.data.queued {
+                    parent.data.queued = true;
+                    queue.insert(key(parent), parent_id);
+                    if !parent.data.good {
+                        num_queued_not_good += 1;
+                    }
+                } else if !before.good && parent.data.good {
+                    num_queued_not_good -= 1;
+                }
+            }
+        }
+        ids.retain(|id| !graph[id].data.good);
+        let position_by_id: gix_hashtable::HashMap<_, _> = ids.iter().enumerate().map(|(pos, id)| (*id, pos)).collect();
+        let parents = ids
+            .iter()
+            .map(|id| {
+                graph[id]
+                    .parents
+                    .iter()
+                    .filter_map(|parent| position_by_id.get(parent).copied())
+                    .collect()
+            })
+            .collect();
+        Ok(Suspects { ids, parents })
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+struct Marks {
+    /// The commit is reachable from the bad commit.
+    bad: bool,
+    /// The commit is reachable from a good commit.
+    good: bool,
+    /// The commit was already added to the suspects.
+    listed: bool,
+    /// The commit is in the queue of commits to visit.
+    queued: bool,
+}
+
+/// The commits that may be the first bad commit, newest first, with the positions of their parents among them.
+struct Suspects {
+    ids: Vec<ObjectId>,
+    parents: Vec<Vec<usize>>,
+}
+
+impl Suspects {
+    /// Return the position of the suspect that splits all suspects most evenly, picking the same one as `git bisect`
+    /// if there are several.
+    fn best(&self) -> usize {
+        let num_suspects = self.ids.len();
+        let weights = self.weights();
+        // Git weighs merges first and then one more commit on top of each weighed commit at a time, and stops as soon
+        // as a commit reaches exactly half of the suspects.
+        let mut levels = vec![0; num_suspects];
+        for idx in (0..num_suspects).rev() {
+            if let [parent] = self.parents[idx].as_slice() {
+                levels[idx] = levels[*parent] + 1;
+            }
+        }
+        let mut order: Vec<_> = (0..num_suspects).filter(|idx| !self.parents[*idx].is_empty()).collect();
+        order.sort_by_key(|idx| (self.parents[*idx].len() == 1, levels[*idx]));
+        if let Some(idx) = order
+            .into_iter()
+            .find(|idx| (2 * weights[*idx]).abs_diff(num_suspects) <= 1)
+        {
+            return idx;
+        }
+        weights
+            .iter()
+            .enumerate()
+            .fold((0, 0), |(best, best_distance), (idx, weight)| {
+                let distance = (*weight).min(num_suspects - weight);
+                if distance > best_distance {
+                    (idx, distance)
+                } else {
+                    (best, best_distance)
+                }
+            })
+            .0
+    }
+
+    /// Return the number of suspects reachable from each suspect, including itself.
+    fn weights(&self) -> Vec<usize> {
+        let mut weights = vec![0; self.ids.len()];
+        let mut seen = vec![false; self.ids.len()];
+        let mut stack = Vec::new();
+        // Parents come after their children, so they are weighed first.
+        for idx in (0..self.ids.len()).rev() {
+            weights[idx] = match self.parents[idx].as_slice() {
+                [] => 1,
+                [parent] if weights[*parent] != 0 => weights[*parent] + 1,
+                _ => {
+                    seen.iter_mut().for_each(|seen| *seen = false);
+                    stack.push(idx);
+                    let mut weight = 0;
+                    while let Some(idx) = stack.pop() {
+                        if std::mem::replace(&mut seen[idx], true) {
+                            continue;
+                        }
+                        weight += 1;
+                        stack.extend(self.parents[idx].iter().copied());
+                    }
+                    weight
+                }
+            };
+        }
+        weights
+    }
+}
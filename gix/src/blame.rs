@@ -0,0 +1,344 @@
+//! Attribute each line of a file to the commit that last modified it, similar to `git blame`.
+use std::{
+    collections::{BinaryHeap, HashMap},
+    ops::Range,
+};
+
+use gix_hash::ObjectId;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    diff::Rewrites,
+    object::tree::diff::{change::Event, Action},
+    Repository, Tree,
+};
+
+/// A contiguous range of lines in the blamed file that originates from the same commit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlameHunk {
+    /// The zero-based index of the first line of this hunk in the blamed file.
+    pub start_line: u32,
+    /// The amount of lines in this hunk, which is never 0.
+    pub line_count: u32,
+    /// The id of the commit that introduced these lines.
+    pub commit_id: ObjectId,
+    /// The zero-based index of the first line of this hunk in the file as it was in `commit_id`.
+    pub source_start_line: u32,
+    /// The path of the file in `commit_id`, which differs from the blamed path if the file was renamed since.
+    pub source_path: BString,
+}
+
+/// Options for use in [`blame_with_options()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// If `Some`, follow the file across renames as detected with these settings. If `None`, the history of the file
+    /// ends where it was renamed.
+    ///
+    /// Note that copies aren't tracked yet, so its `copies` field is ignored.
+    pub rewrites: Option<Rewrites>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            rewrites: Some(Rewrites::default()),
+        }
+    }
+}
+
+/// The error returned by [`blame()`] and [`blame_with_options()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The file '{path}' could not be found in commit {commit_id}")]
+    FileMissing { path: BString, commit_id: ObjectId },
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    ObjectKind(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] crate::object::commit::Error),
+    #[error(transparent)]
+    DiffAlgorithm(#[from] crate::config::diff::algorithm::Error),
+    #[error(transparent)]
+    DiffRenamesConfig(#[from] crate::diff::new_rewrites::Error),
+    #[error(transparent)]
+    TreeDiff(#[from] crate::object::tree::diff::for_each::Error),
+}
+
+/// Like [`blame_with_options()`], but follows renames with the default [`Options`].
+pub fn blame(repo: &Repository, path: &BStr, at: impl Into<ObjectId>) -> Result<Vec<BlameHunk>, Error> {
+    blame_with_options(repo, path, at, Options::default())
+}
+
+/// Attribute each line of the file at `path` as it is in the commit `at` to the commit that last modified it, and return
+/// hunks of consecutive lines sorted by their position in the blamed file.
+///
+/// History is traversed from newest to oldest commit, and each version of the file is diffed with the version in each parent
+/// using the configured `diff.algorithm`. Lines that are unchanged in a parent are passed on to it, while all
+/// remaining lines are attributed to the commit itself. If a parent has the exact same version of the file, all lines
+/// are passed on to that parent, which makes merges cheap to traverse.
+///
+/// If the file doesn't exist in a parent, it's followed across renames as configured in `options`.
+/// Note that an empty file has no lines, and thus yields no hunks.
+pub fn blame_with_options(
+    repo: &Repository,
+    path: &BStr,
+    at: impl Into<ObjectId>,
+    options: Options,
+) -> Result<Vec<BlameHunk>, Error> {
+    let at = at.into();
+    let algorithm = repo.config.diff_algorithm()?;
+    let commit = repo.find_object(at)?.try_into_commit()?;
+    let mut buf = Vec::new();
+    let blob_id = find_file(&commit.tree()?, path, &mut buf)?.ok_or_else(|| Error::FileMissing {
+        path: path.to_owned(),
+        commit_id: at,
+    })?;
+    let line_count = gix_diff::blob::sources::byte_lines(&repo.find_object(blob_id)?.data).count() as u32;
+
+    let mut out = Vec::new();
+    if line_count == 0 {
+        return Ok(out);
+    }
+
+    let mut queue = Queue::default();
+    queue.push(
+        at,
+        commit.time()?.seconds,
+        Suspect {
+            path: path.to_owned(),
+            blob_id,
+            hunks: vec![UnblamedHunk {
+                range_in_blamed_file: 0..line_count,
+                suspect_start: 0,
+            }],
+        },
+    );
+
+    while let Some((commit_id, suspects)) = queue.pop() {
+        let commit = repo.find_object(commit_id)?.try_into_commit()?;
+        let tree = commit.tree()?;
+        let parent_ids: Vec<_> = commit.parent_ids().map(crate::Id::detach).collect();
+        for mut suspect in suspects {
+            let mut sources = Vec::with_capacity(parent_ids.len());
+            for parent_id in &parent_ids {
+                let parent = repo.find_object(*parent_id)?.try_into_commit()?;
+                let parent_tree = parent.tree()?;
+                let source = match find_file(&parent_tree, suspect.path.as_ref(), &mut buf)? {
+                    Some(id) => Some((suspect.path.clone(), id)),
+                    None => match options.rewrites {
+                        Some(rewrites) => find_rename_source(&parent_tree, &tree, suspect.path.as_ref(), rewrites)?,
+                        None => None,
+                    },
+                };
+                if let Some((source_path, source_id)) = source {
+                    sources.push((*parent_id, parent.time()?.seconds, source_path, source_id));
+                }
+            }
+
+            if let Some(pos) = sources.iter().position(|source| source.3 == suspect.blob_id) {
+                let (parent_id, time, path, blob_id) = sources.swap_remove(pos);
+                queue.push(
+                    parent_id,
+                    time,
+                    Suspect {
+                        path,
+                        blob_id,
+                        hunks: suspect.hunks,
+                    },
+                );
+                continue;
+            }
+
+            if !sources.is_empty() {
+                let data = repo.find_object(suspect.blob_id)?.detach().data;
+                for (parent_id, time, path, blob_id) in sources {
+                    if suspect.hunks.is_empty() {
+                        break;
+                    }
+                    let hunks =
+                        pass_unchanged_lines(&mut suspect.hunks, &repo.find_object(blob_id)?.data, &data, algorithm);
+                    if !hunks.is_empty() {
+                        queue.push(parent_id, time, Suspect { path, blob_id, hunks });
+                    }
+                }
+            }
+
+            out.extend(suspect.hunks.into_iter().map(|hunk| BlameHunk {
+                start_line: hunk.range_in_blamed_file.start,
+                line_count: hunk.range_in_blamed_file.end - hunk.range_in_blamed_file.start,
+                commit_id,
+                source_start_line: hunk.suspect_start,
+                source_path: suspect.path.clone(),
+            }));
+        }
+    }
+
+    out.sort_by_key(|hunk| hunk.start_line);
+    Ok(coalesce(out))
+}
+
+/// Lines in the blamed file that still have to be attributed to a commit.
+struct UnblamedHunk {
+    range_in_blamed_file: Range<u32>,
+    /// The index of the first line of `range_in_blamed_file` in the version of the file of the current suspect.
+    suspect_start: u32,
+}
+
+/// A commit which may have introduced the `hunks` of lines in the file at `path` and with `blob_id`.
+struct Suspect {
+    path: BString,
+    blob_id: ObjectId,
+    hunks: Vec<UnblamedHunk>,
+}
+
+/// Commits to process, newest first, along with the lines they are suspected to have introduced.
+#[derive(Default)]
+struct Queue {
+    by_time: BinaryHeap<(gix_date::SecondsSinceUnixEpoch, ObjectId)>,
+    suspects: HashMap<ObjectId, Vec<Suspect>>,
+}
+
+impl Queue {
+    fn push(&mut self, commit_id: ObjectId, time: gix_date::SecondsSinceUnixEpoch, suspect: Suspect) {
+        let suspects = self.suspects.entry(commit_id).or_insert_with(|| {
+            self.by_time.push((time, commit_id));
+            Vec::new()
+        });
+        match suspects
+            .iter_mut()
+            .find(|existing| existing.path == suspect.path && existing.blob_id == suspect.blob_id)
+        {
+            Some(existing) => existing.hunks.extend(suspect.hunks),
+            None => suspects.push(suspect),
+        }
+    }
+
+    fn pop(&mut self) -> Option<(ObjectId, Vec<Suspect>)> {
+        let (_, commit_id) = self.by_time.pop()?;
+        let suspects = self
+            .suspects
+            .remove(&commit_id)
+            .expect("queued commits always have suspects");
+        Some((commit_id, suspects))
+    }
+}
+
+/// Return the id of the blob at `path` in `tree`, or `None` if there is no such file.
+fn find_file(tree: &Tree<'_>, path: &BStr, buf: &mut Vec<u8>) -> Result<Option<ObjectId>, Error> {
+    Ok(tree
+        .lookup_entry(path.split_str("/"), buf)?
+        .filter(|entry| entry.mode().is_blob_or_symlink())
+        .map(|entry| entry.object_id()))
+}
+
+/// Return the path and blob id of the file in `parent_tree` that was renamed to `path` in `tree`, if there is one.
+fn find_rename_source(
+    parent_tree: &Tree<'_>,
+    tree: &Tree<'_>,
+    path: &BStr,
+    rewrites: Rewrites,
+) -> Result<Option<(BString, ObjectId)>, Error> {
+    let mut source = None;
+    parent_tree
+        .changes()?
+        .track_path()
+        .track_rewrites(Some(Rewrites {
+            copies: None,
+            ..rewrites
+        }))
+        .for_each_to_obtain_tree(tree, |change| {
+            if let Event::Rewrite {
+                source_location,
+                source_id,
+                copy: false,
+                ..
+            } = change.event
+            {
+                if change.location == path {
+                    source = Some((source_location.to_owned(), source_id.detach()));
+                }
+            }
+            Ok::<_, std::convert::Infallible>(Action::Continue)
+        })?;
+    Ok(source)
+}
+
+/// Diff `old` with `new` and remove all lines from `hunks` that are unchanged in `old`, returning them as hunks relative to `old`.
+/// The lines that remain in `hunks` were introduced in `new`.
+fn pass_unchanged_lines(
+    hunks: &mut Vec<UnblamedHunk>,
+    old: &[u8],
+    new: &[u8],
+    algorithm: gix_diff::blob::Algorithm,
+) -> Vec<UnblamedHunk> {
+    let input = gix_diff::blob::intern::InternedInput::new(old, new);
+    // For each line in `new`, the line in `old` it corresponds to, if it is unchanged.
+    let mut old_line_by_new_line = Vec::with_capacity(input.after.len());
+    let mut old_line = 0;
+    gix_diff::blob::diff(algorithm, &input, |before: Range<u32>, after: Range<u32>| {
+        let unchanged = after.start - old_line_by_new_line.len() as u32;
+        old_line_by_new_line.extend((old_line..old_line + unchanged).map(Some));
+        old_line_by_new_line.extend(after.map(|_| None));
+        old_line = before.end;
+    });
+    let unchanged = input.after.len() as u32 - old_line_by_new_line.len() as u32;
+    old_line_by_new_line.extend((old_line..old_line + unchanged).map(Some));
+
+    let mut passed = Vec::new();
+    let mut kept = Vec::new();
+    for hunk in hunks.drain(..) {
+        let len = hunk.range_in_blamed_file.end - hunk.range_in_blamed_file.start;
+        let old_line_at = |offset: u32| old_line_by_new_line[(hunk.suspect_start + offset) as usize];
+        let mut start = 0;
+        while start < len {
+            let first = old_line_at(start);
+            let mut end = start + 1;
+            while end < len {
+                let continues = match (first, old_line_at(end)) {
+                    (Some(first), Some(next)) => next == first + (end - start),
+                    (None, None) => true,
+                    _ => false,
+                };
+                if !continues {
+                    break;
+                }
+                end += 1;
+            }
+            let range_in_blamed_file = hunk.range_in_blamed_file.start + start..hunk.range_in_blamed_file.start + end;
+            match first {
+                Some(old_start) => passed.push(UnblamedHunk {
+                    range_in_blamed_file,
+                    suspect_start: old_start,
+                }),
+                None => kept.push(UnblamedHunk {
+                    range_in_blamed_file,
+                    suspect_start: hunk.suspect_start + start,
+                }),
+            }
+            start = end;
+        }
+    }
+    *hunks = kept;
+    passed
+}
+
+/// Merge hunks that are adjacent both in the blamed file and in their source.
+fn coalesce(hunks: Vec<BlameHunk>) -> Vec<BlameHunk> {
+    let mut out: Vec<BlameHunk> = Vec::with_capacity(hunks.len());
+    for hunk in hunks {
+        match out.last_mut() {
+            Some(previous)
+                if previous.commit_id == hunk.commit_id
+                    && previous.source_path == hunk.source_path
+                    && previous.start_line + previous.line_count == hunk.start_line
+                    && previous.source_start_line + previous.line_count == hunk.source_start_line =>
+            {
+                previous.line_count += hunk.line_count;
+            }
+            _ => out.push(hunk),
+        }
+    }
+    out
+}
@@ -0,0 +1,65 @@
+//! Apply the changes of a single commit onto another one, similar to `git cherry-pick`.
+use crate::bstr::BString;
+use crate::Id;
+
+/// The error returned by [Repository::cherry_pick()](crate::Repository::cherry_pick()) and
+/// [Repository::revert()](crate::Repository::revert()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Commit {id} is a merge, but no mainline was specified to apply its changes against")]
+    MainlineRequired { id: gix_hash::ObjectId },
+    #[error("Commit {id} doesn't have parent number {mainline}")]
+    NoSuchParent { id: gix_hash::ObjectId, mainline: usize },
+    #[error("Cannot apply the changes of commit {id} as '{path}' would have to be a file and a directory")]
+    DirectoryFileConflict { id: gix_hash::ObjectId, path: BString },
+    #[error("Committer identity is not configured")]
+    CommitterMissing,
+    #[error("Author identity is not configured")]
+    AuthorMissing,
+    #[error(transparent)]
+    ParseTime(#[from] crate::config::time::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    ObjectKind(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    TraverseTree(#[from] gix_traverse::tree::breadthfirst::Error),
+    #[error(transparent)]
+    WriteObject(#[from] crate::object::write::Error),
+}
+
+/// Options for use in [Repository::cherry_pick()](crate::Repository::cherry_pick()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// If `true`, append a line like `(cherry picked from commit <id>)` to the message of the new commit, like `-x`.
+    pub record_origin: bool,
+    /// The number of the parent to pick the changes of a merge commit against, starting at `1`, like `--mainline`.
+    ///
+    /// It must be set to pick a merge commit, and may only be `1` for commits with a single parent.
+    pub mainline: Option<usize>,
+}
+
+/// The outcome of [Repository::cherry_pick()](crate::Repository::cherry_pick()) and
+/// [Repository::revert()](crate::Repository::revert()).
+#[derive(Debug, Clone)]
+pub enum Outcome<'repo> {
+    /// The changes applied cleanly, and a new commit with them was written, whose only parent is the commit they were
+    /// applied onto.
+    ///
+    /// Note that no reference was changed to point to it.
+    Commit(Id<'repo>),
+    /// The changes didn't apply cleanly, so no commit was written.
+    Conflicts {
+        /// The merged index, with the *base*, *ours* and *theirs* sides of each conflict as stage 1, 2 and 3
+        /// respectively, and all cleanly merged paths as unconflicted entries.
+        ///
+        /// *Ours* is the commit the changes were applied onto, and *theirs* is the picked commit, or the parent of the
+        /// reverted commit.
+        index: Box<gix_index::State>,
+        /// The paths that had conflicting changes, sorted by path.
+        conflicts: Vec<BString>,
+    },
+}
@@ -0,0 +1,89 @@
+//! Remove untracked and ignored files from the working tree, similar to `git clean`.
+use crate::bstr::BString;
+use crate::{config, dirwalk};
+
+/// The error returned by [Repository::clean()](crate::Repository::clean()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("clean.requireForce is true and neither dry-run nor force were set; refusing to clean")]
+    ForceRequired,
+    #[error(transparent)]
+    ConfigBoolean(#[from] config::boolean::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    Dirwalk(#[from] dirwalk::Error),
+}
+
+/// How to treat ignored files.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Ignored {
+    /// Keep all ignored files, and only remove untracked ones. This is the default of `git clean`.
+    #[default]
+    Keep,
+    /// Remove ignored files as well as untracked ones, like `-x`.
+    Remove,
+    /// Remove only ignored files and keep untracked ones, like `-X`.
+    Only,
+}
+
+/// How much force to apply when removing entries.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Force {
+    /// Don't force removal, which fails unless `clean.requireForce` is `false`,
+    /// or if it's a [dry-run](Options::dry_run).
+    #[default]
+    None,
+    /// Force removal of files and directories, but skip nested repositories, like `-f`.
+    Once,
+    /// Also remove nested repositories, like `-ff`.
+    Twice,
+}
+
+/// Options for use in [Repository::clean()](crate::Repository::clean()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// If `true`, nothing is removed and entries that would be removed are returned as [`Removal::WouldRemove`],
+    /// like `-n`.
+    pub dry_run: bool,
+    /// If `true`, untracked or ignored directories are removed as well, like `-d`.
+    ///
+    /// Otherwise, only files are removed, and files in untracked directories are kept.
+    pub directories: bool,
+    /// Control what happens with ignored files.
+    ///
+    /// Note that precious files, i.e. ignored files matching patterns starting with `$`, are always kept.
+    pub ignored: Ignored,
+    /// How much to force removal.
+    pub force: Force,
+}
+
+/// What happened to an [`Entry`] during [cleaning](crate::Repository::clean()).
+#[derive(Debug)]
+pub enum Removal {
+    /// The entry would have been removed, but this was a [dry-run](Options::dry_run).
+    WouldRemove,
+    /// The entry was removed.
+    Removed,
+    /// The entry is a nested repository, which is only removed with [`Force::Twice`].
+    SkippedRepository,
+    /// The entry is an empty directory that is also the current working directory, which is never removed.
+    SkippedCurrentDirectory,
+    /// Removing the entry failed.
+    Failed(std::io::Error),
+}
+
+/// A file or directory that was selected for removal by [Repository::clean()](crate::Repository::clean()).
+#[derive(Debug)]
+pub struct Entry {
+    /// The repository-relative path of the entry, without a trailing slash.
+    pub rela_path: BString,
+    /// The kind of the entry on disk, with directories that are repositories as
+    /// [`Repository`](gix_dir::entry::Kind::Repository).
+    pub disk_kind: gix_dir::entry::Kind,
+    /// Whether the entry is untracked or ignored.
+    pub status: gix_dir::entry::Status,
+    /// What happened to the entry.
+    pub removal: Removal,
+}
@@ -35,6 +35,18 @@ impl PrepareFetch {
         self
     }
 
+    /// Make this clone a partial one which omits all objects that don't pass `filter`, which requires the remote to
+    /// support the `filter` capability.
+    ///
+    /// Like `git`, the remote is marked as promisor and remembers the `filter` in its configuration with
+    /// `remote.<name>.promisor` and `remote.<name>.partialCloneFilter` respectively.
+    /// Note that `core.repositoryFormatVersion` isn't raised to `1` and `extensions.partialClone` isn't set.
+    #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+    pub fn with_filter(mut self, filter: crate::remote::fetch::Filter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
     /// Apply the given configuration `values` right before readying the actual fetch from the remote.
     /// The configuration is marked with [source API](gix_config::Source::Api), and will not be written back, it's
     /// retained only in memory.
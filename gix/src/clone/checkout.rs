@@ -66,6 +66,9 @@ pub mod main_worktree {
         /// Note that this is a no-op if the remote was empty, leaving this repository empty as well. This can be validated by checking
         /// if the `head()` of the returned repository is *not* unborn.
         ///
+        /// If the `hooks` feature is enabled, the `post-checkout` [hook](crate::hooks) runs after the checkout, without
+        /// affecting its outcome.
+        ///
         /// # Panics
         ///
         /// If called after it was successful. The reason here is that it auto-deletes the contained repository,
@@ -144,11 +147,33 @@ pub mod main_worktree {
             bytes.show_throughput(start);
 
             index.write(Default::default())?;
+
+            #[cfg(feature = "hooks")]
+            if let Some(head_id) = root_tree_id {
+                // Like in `git`, the outcome of the hook doesn't affect the checkout.
+                repo.run_hook(crate::hooks::Invocation::PostCheckout {
+                    previous_head: gix_hash::ObjectId::null(repo.object_hash()),
+                    new_head: head_id.detach(),
+                    branch_checkout: true,
+                })
+                .ok();
+            }
             Ok((self.repo.take().expect("still present").clone(), outcome))
         }
     }
 }
 
+impl PrepareCheckout {
+    /// Prepare checking out the main worktree of `repo`, which is deleted if the checkout doesn't complete successfully.
+    #[cfg(feature = "blocking-network-client")]
+    pub(crate) fn new(repo: Repository) -> Self {
+        PrepareCheckout {
+            repo: Some(repo),
+            ref_name: None,
+        }
+    }
+}
+
 /// Access
 impl PrepareCheckout {
     /// Get access to the repository while the checkout isn't yet completed.
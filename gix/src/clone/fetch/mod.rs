@@ -14,6 +14,9 @@ pub enum Error {
     Fetch(#[from] crate::remote::fetch::Error),
     #[error(transparent)]
     RemoteInit(#[from] crate::remote::init::Error),
+    #[cfg(feature = "bundle")]
+    #[error(transparent)]
+    FetchBundle(#[from] crate::remote::fetch::bundle::Error),
     #[error("Custom configuration of remote to clone from failed")]
     RemoteConfiguration(#[source] Box<dyn std::error::Error + Send + Sync>),
     #[error("Custom configuration of connection to use when cloning failed")]
@@ -28,6 +31,8 @@ pub enum Error {
     LoadConfig(#[from] gix_config::file::init::from_paths::Error),
     #[error("Failed to store configured remote in memory")]
     SaveConfig(#[from] crate::remote::save::AsError),
+    #[error("Failed to record the partial clone filter in the configuration of the remote")]
+    SaveFilterConfig(#[from] gix_config::file::set_raw_value::Error),
     #[error("Failed to write repository configuration to disk")]
     SaveConfigIo(#[from] std::io::Error),
     #[error("The remote HEAD points to a reference named {head_ref_name:?} which is invalid.")]
@@ -119,7 +124,7 @@ impl PrepareFetch {
             clone_fetch_tags = remote::fetch::Tags::All.into();
         }
 
-        let config = util::write_remote_to_local_config_file(&mut remote, remote_name.clone())?;
+        let config = util::write_remote_to_local_config_file(&mut remote, remote_name.clone(), self.filter)?;
 
         // Now we are free to apply remote configuration we don't want to be written to disk.
         if let Some(fetch_tags) = clone_fetch_tags {
@@ -134,6 +139,48 @@ impl PrepareFetch {
         )
         .expect("valid")
         .to_owned();
+        let reflog_message = {
+            let mut b = self.url.to_bstring();
+            b.insert_str(0, "clone: from ");
+            b
+        };
+
+        #[cfg(feature = "bundle")]
+        if let Some(bundle_path) = util::bundle_path(&self.url) {
+            let mut extra_refspecs = vec![head_refspec];
+            if let Some(ref_name) = &self.ref_name {
+                extra_refspecs.push(
+                    gix_refspec::parse(ref_name.as_ref().as_bstr(), gix_refspec::parse::Operation::Fetch)
+                        .expect("partial names are valid refspecs")
+                        .to_owned(),
+                );
+            }
+            let outcome = remote.fetch_bundle_inner(
+                &bundle_path,
+                progress,
+                should_interrupt,
+                crate::remote::fetch::bundle::Options {
+                    extra_refspecs,
+                    reflog_message: Some(RefLogMessage::Override {
+                        message: reflog_message.clone(),
+                    }),
+                    write_packed_refs_only: true,
+                },
+            )?;
+            if let Some(ref_name) = &self.ref_name {
+                util::find_custom_refname(&outcome.ref_map, ref_name)?;
+            }
+            util::append_config_to_repo_config(repo, config);
+            util::update_head(
+                repo,
+                &outcome.ref_map,
+                reflog_message.as_ref(),
+                remote_name.as_ref(),
+                self.ref_name.as_ref(),
+            )?;
+            return Ok((self.repo.take().expect("still present"), outcome));
+        }
+
         let pending_pack: remote::fetch::Prepare<'_, '_, _> = {
             let mut connection = remote.connect(remote::Direction::Fetch).await?;
             if let Some(f) = self.configure_connection.as_mut() {
@@ -164,19 +211,17 @@ impl PrepareFetch {
         if pending_pack.ref_map().object_hash != repo.object_hash() {
             unimplemented!("configure repository to expect a different object hash as advertised by the server")
         }
-        let reflog_message = {
-            let mut b = self.url.to_bstring();
-            b.insert_str(0, "clone: from ");
-            b
-        };
-        let outcome = pending_pack
+        let mut pending_pack = pending_pack
             .with_write_packed_refs_only(true)
+            .with_write_fetch_head(false)
             .with_reflog_message(RefLogMessage::Override {
                 message: reflog_message.clone(),
             })
-            .with_shallow(self.shallow.clone())
-            .receive_inner(progress, should_interrupt)
-            .await?;
+            .with_shallow(self.shallow.clone());
+        if let Some(filter) = self.filter {
+            pending_pack = pending_pack.with_filter(filter);
+        }
+        let outcome = pending_pack.receive_inner(progress, should_interrupt).await?;
 
         util::append_config_to_repo_config(repo, config);
         util::update_head(
@@ -20,9 +20,21 @@ enum WriteMode {
 pub fn write_remote_to_local_config_file(
     remote: &mut crate::Remote<'_>,
     remote_name: BString,
+    filter: Option<crate::remote::fetch::Filter>,
 ) -> Result<gix_config::File<'static>, Error> {
     let mut config = gix_config::File::new(local_config_meta(remote.repo));
-    remote.save_as_to(remote_name, &mut config)?;
+    remote.save_as_to(remote_name.clone(), &mut config)?;
+    if let Some(filter) = filter {
+        use crate::config::tree::{Key, Remote};
+        let remote_name = Some(remote_name.as_bstr());
+        config.set_raw_value_by("remote", remote_name, Remote::PROMISOR.name(), "true")?;
+        config.set_raw_value_by(
+            "remote",
+            remote_name,
+            Remote::PARTIAL_CLONE_FILTER.name(),
+            filter.to_string().as_str(),
+        )?;
+    }
 
     write_to_local_config(&config, WriteMode::Append)?;
     Ok(config)
@@ -58,6 +70,16 @@ pub fn append_config_to_repo_config(repo: &mut Repository, config: gix_config::F
     repo_config.append(config);
 }
 
+/// Return the path to the bundle that `url` points to, if it is a local file that is a bundle.
+#[cfg(feature = "bundle")]
+pub fn bundle_path(url: &gix_url::Url) -> Option<std::path::PathBuf> {
+    if url.scheme != gix_url::Scheme::File {
+        return None;
+    }
+    let path = gix_path::from_bstr(url.path.as_ref()).into_owned();
+    gix_bundle::is_bundle(&path).then_some(path)
+}
+
 /// HEAD cannot be written by means of refspec by design, so we have to do it manually here. Also create the pointed-to ref
 /// if we have to, as it might not have been naturally included in the ref-specs.
 /// Lastly, use `ref_name` if it was provided instead, and let `HEAD` point to it.
@@ -4,6 +4,19 @@
 /// An empty array of a type usable with the `gix::easy` API to help declaring no parents should be used
 pub const NO_PARENT_IDS: [gix_hash::ObjectId; 0] = [];
 
+/// Options for [`commit_as_opts(…)`][crate::Repository::commit_as_opts()].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// If `true`, allow creating a commit with the same tree as its only parent, similar to `git commit --allow-empty`.
+    ///
+    /// Initial commits and merge commits are never considered empty.
+    pub allow_empty: bool,
+    /// If `true`, don't run the `pre-commit` and `commit-msg` hooks, similar to `git commit --no-verify`.
+    ///
+    /// Hooks are only run if the `hooks` feature is enabled.
+    pub no_verify: bool,
+}
+
 /// The error returned by [`commit(…)`][crate::Repository::commit()].
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
@@ -20,6 +33,39 @@ pub enum Error {
     WriteObject(#[from] crate::object::write::Error),
     #[error(transparent)]
     ReferenceEdit(#[from] crate::reference::edit::Error),
+    #[error("The commit would have the same tree {tree} as its parent {parent}, and empty commits aren't allowed")]
+    Empty {
+        tree: gix_hash::ObjectId,
+        parent: gix_hash::ObjectId,
+    },
+    #[error("Could not find the parent commit to compare its tree")]
+    FindParent(#[from] crate::object::find::existing::Error),
+    #[error("Could not decode the parent commit to obtain its tree")]
+    DecodeParent(#[from] gix_object::decode::Error),
+    #[cfg(feature = "signing")]
+    #[error(transparent)]
+    ConfigBoolean(#[from] crate::config::boolean::Error),
+    #[cfg(feature = "signing")]
+    #[error(transparent)]
+    SigningOptions(#[from] crate::signing::options::Error),
+    #[cfg(feature = "signing")]
+    #[error("Could not sign the commit")]
+    Sign(#[from] crate::signing::sign::Error),
+    #[cfg(feature = "hooks")]
+    #[error(transparent)]
+    RunHook(#[from] crate::hooks::run::Error),
+    #[cfg(feature = "hooks")]
+    #[error("The {name} hook rejected the commit")]
+    HookRejected {
+        name: &'static str,
+        stderr: crate::bstr::BString,
+    },
+    #[cfg(feature = "hooks")]
+    #[error("Could not write or read the commit message file at '{}'", path.display())]
+    MessageFile {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
 }
 
 ///
@@ -31,7 +77,11 @@ pub mod describe {
     use gix_hash::ObjectId;
     use gix_hashtable::HashMap;
 
-    use crate::{bstr::BStr, ext::ObjectIdExt, Repository};
+    use crate::{
+        bstr::{BStr, BString, ByteSlice},
+        ext::ObjectIdExt,
+        Repository,
+    };
 
     /// The result of [`try_resolve()`][Platform::try_resolve()].
     pub struct Resolution<'repo> {
@@ -39,13 +89,27 @@ pub mod describe {
         pub outcome: gix_revision::describe::Outcome<'static>,
         /// The id to describe.
         pub id: crate::Id<'repo>,
+        /// The minimal amount of hex characters to use for the `id`, or `None` to use the configured default.
+        pub(crate) abbrev: Option<usize>,
     }
 
     impl<'repo> Resolution<'repo> {
         /// Turn this instance into something displayable.
         pub fn format(self) -> Result<gix_revision::describe::Format<'static>, Error> {
-            let prefix = self.id.shorten()?;
-            Ok(self.outcome.into_format(prefix.hex_len()))
+            let hex_len = self.hex_len()?;
+            Ok(self.outcome.into_format(hex_len))
+        }
+
+        fn hex_len(&self) -> Result<usize, Error> {
+            Ok(match self.abbrev {
+                None => self.id.shorten()?.hex_len(),
+                Some(0) => 0,
+                Some(hex_len) => {
+                    // Like `git`, don't use fewer than 4 characters.
+                    let hex_len = hex_len.clamp(4, self.id.kind().len_in_hex());
+                    self.id.shorten_to(hex_len)?.hex_len()
+                }
+            })
         }
 
         /// Turn this instance into something displayable, possibly with dirty-suffix.
@@ -59,12 +123,12 @@ pub mod describe {
             self,
             dirty_suffix: impl Into<Option<String>>,
         ) -> Result<gix_revision::describe::Format<'static>, Error> {
-            let prefix = self.id.shorten()?;
+            let hex_len = self.hex_len()?;
             let mut dirty_suffix = dirty_suffix.into();
             if dirty_suffix.is_some() && !self.id.repo.is_dirty()? {
                 dirty_suffix.take();
             }
-            let mut format = self.outcome.into_format(prefix.hex_len());
+            let mut format = self.outcome.into_format(hex_len);
             format.dirty_suffix = dirty_suffix;
             Ok(format)
         }
@@ -100,8 +164,14 @@ pub mod describe {
     }
 
     impl SelectRef {
-        fn names(&self, repo: &Repository) -> Result<HashMap<ObjectId, Cow<'static, BStr>>, Error> {
+        fn names(
+            &self,
+            repo: &Repository,
+            patterns: &[BString],
+            exclude_patterns: &[BString],
+        ) -> Result<HashMap<ObjectId, Cow<'static, BStr>>, Error> {
             let platform = repo.references()?;
+            let is_selected = |r: &crate::Reference<'_>| is_selected(r.name().as_bstr(), patterns, exclude_patterns);
 
             Ok(match self {
                 SelectRef::AllTags | SelectRef::AllRefs => {
@@ -111,6 +181,7 @@ pub mod describe {
                         _ => unreachable!(),
                     }
                     .filter_map(Result::ok)
+                    .filter(is_selected)
                     .filter_map(|mut r: crate::Reference<'_>| {
                         let target_id = r.target().try_id().map(ToOwned::to_owned);
                         let peeled_id = r.peel_to_id_in_place().ok()?;
@@ -146,6 +217,7 @@ pub mod describe {
                     let mut peeled_commits_and_tag_date: Vec<_> = platform
                         .tags()?
                         .filter_map(Result::ok)
+                        .filter(is_selected)
                         .filter_map(|r: crate::Reference<'_>| {
                             // TODO: we assume direct refs for tags, which is the common case, but it doesn't have to be
                             //       so rather follow symrefs till the first object and then peel tags after the first object was found.
@@ -169,6 +241,26 @@ pub mod describe {
         }
     }
 
+    /// Return `true` if the reference with `full_name` matches one of `patterns` if there are any, and none of `exclude_patterns`.
+    ///
+    /// Like `git describe`, tags are matched by their name without the `refs/tags/` prefix, and branches without their
+    /// `refs/heads/` or `refs/remotes/` prefix. Other references never match if there are any patterns.
+    fn is_selected(full_name: &BStr, patterns: &[BString], exclude_patterns: &[BString]) -> bool {
+        if patterns.is_empty() && exclude_patterns.is_empty() {
+            return true;
+        }
+        let Some(name) = ["refs/tags/", "refs/heads/", "refs/remotes/"]
+            .iter()
+            .find_map(|prefix| full_name.strip_prefix(prefix.as_bytes()))
+        else {
+            return false;
+        };
+        let matches = |pattern: &BString| {
+            gix_glob::wildmatch(pattern.as_bstr(), name.as_bstr(), gix_glob::wildmatch::Mode::empty())
+        };
+        !exclude_patterns.iter().any(matches) && (patterns.is_empty() || patterns.iter().any(matches))
+    }
+
     /// A support type to allow configuring a `git describe` operation
     pub struct Platform<'repo> {
         pub(crate) id: gix_hash::ObjectId,
@@ -177,6 +269,9 @@ pub mod describe {
         pub(crate) first_parent: bool,
         pub(crate) id_as_fallback: bool,
         pub(crate) max_candidates: usize,
+        pub(crate) abbrev: Option<usize>,
+        pub(crate) patterns: Vec<BString>,
+        pub(crate) exclude_patterns: Vec<BString>,
     }
 
     impl<'repo> Platform<'repo> {
@@ -204,6 +299,31 @@ pub mod describe {
             self
         }
 
+        /// Only consider names of references that match any of the given glob `patterns`, similar to `git describe --match`.
+        ///
+        /// Tags are matched without their `refs/tags/` prefix, and branches without their `refs/heads/` or `refs/remotes/` prefix.
+        pub fn match_patterns(mut self, patterns: impl IntoIterator<Item = impl Into<BString>>) -> Self {
+            self.patterns = patterns.into_iter().map(Into::into).collect();
+            self
+        }
+
+        /// Don't consider names of references that match any of the given glob `patterns`, similar to `git describe --exclude`.
+        ///
+        /// Exclusions take precedence over [matches](Self::match_patterns()), and are matched the same way.
+        pub fn exclude_patterns(mut self, patterns: impl IntoIterator<Item = impl Into<BString>>) -> Self {
+            self.exclude_patterns = patterns.into_iter().map(Into::into).collect();
+            self
+        }
+
+        /// Use at least `hex_len` hex characters to display the commit id, or more if needed to keep it unique, similar to
+        /// `git describe --abbrev=<hex_len>`, instead of the length configured in `core.abbrev`.
+        ///
+        /// If `hex_len` is `0`, only the name is displayed, or the full id if there is no name.
+        pub fn abbrev(mut self, hex_len: usize) -> Self {
+            self.abbrev = Some(hex_len);
+            self
+        }
+
         /// Try to find a name for the configured commit id using all prior configuration, returning `Some(describe::Format)`
         /// if one was found, or `None` if that wasn't the case.
         pub fn try_format(&self) -> Result<Option<gix_revision::describe::Format<'static>>, Error> {
@@ -228,7 +348,7 @@ pub mod describe {
                 &self.id,
                 &mut graph,
                 gix_revision::describe::Options {
-                    name_by_oid: self.select.names(self.repo)?,
+                    name_by_oid: self.select.names(self.repo, &self.patterns, &self.exclude_patterns)?,
                     fallback_to_oid: self.id_as_fallback,
                     first_parent: self.first_parent,
                     max_candidates: self.max_candidates,
@@ -238,6 +358,7 @@ pub mod describe {
             Ok(outcome.map(|outcome| Resolution {
                 outcome,
                 id: self.id.attach(self.repo),
+                abbrev: self.abbrev,
             }))
         }
 
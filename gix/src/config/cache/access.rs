@@ -330,6 +330,7 @@ impl Cache {
             destination_is_initially_empty: false,
             overwrite_existing: false,
             keep_going: false,
+            dry_run: false,
             stat_options: self.stat_options().map_err(|err| match err {
                 config::stat_options::Error::ConfigCheckStat(err) => {
                     config::checkout_options::Error::ConfigCheckStat(err)
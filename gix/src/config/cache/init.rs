@@ -154,6 +154,7 @@ impl Cache {
             true,
             lenient_config,
         )?;
+        let pack_options = util::query_pack_options(&config, lenient_config)?;
         #[cfg(feature = "revision")]
         let object_kind_hint = util::disambiguate_hint(&config, lenient_config)?;
         let (static_pack_cache_limit_bytes, pack_cache_bytes, object_cache_bytes) =
@@ -162,11 +163,14 @@ impl Cache {
         Ok(Cache {
             resolved: config.into(),
             use_multi_pack_index,
+            pack_options,
             object_hash,
             #[cfg(feature = "revision")]
             object_kind_hint,
             static_pack_cache_limit_bytes,
             pack_cache_bytes,
+            #[cfg(feature = "max-performance-safe")]
+            shared_pack_cache: util::shared_pack_cache(pack_cache_bytes),
             object_cache_bytes,
             reflog,
             refs_namespace,
@@ -241,11 +245,17 @@ impl Cache {
             self.diff_renames = Default::default();
             self.diff_algorithm = Default::default();
         }
+        #[cfg(feature = "max-performance-safe")]
+        let prev_pack_cache_bytes = self.pack_cache_bytes;
         (
             self.static_pack_cache_limit_bytes,
             self.pack_cache_bytes,
             self.object_cache_bytes,
         ) = util::parse_object_caches(config, self.lenient_config, self.filter_config_section)?;
+        #[cfg(feature = "max-performance-safe")]
+        if prev_pack_cache_bytes != self.pack_cache_bytes {
+            self.shared_pack_cache = util::shared_pack_cache(self.pack_cache_bytes);
+        }
         #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
         {
             self.url_scheme = Default::default();
@@ -593,10 +603,6 @@ fn apply_environment_overrides(
                 let key = &Core::SSH_COMMAND;
                 (env(key), key.name, git_prefix)
             },
-            {
-                let key = &Core::USE_REPLACE_REFS;
-                (env(key), key.name, objects)
-            },
         ] {
             if let Some(value) = var_as_bstring(var, permission) {
                 section.push_with_comment(
@@ -607,6 +613,19 @@ fn apply_environment_overrides(
             }
         }
 
+        {
+            // Like `git`, the mere presence of the variable disables replacements, no matter its value.
+            let key = &Core::USE_REPLACE_REFS;
+            let var = env(key);
+            if var_as_bstring(var, objects).is_some() {
+                section.push_with_comment(
+                    key.name.try_into().expect("statically known to be valid"),
+                    Some("false".into()),
+                    format!("from {var}").as_str(),
+                );
+            }
+        }
+
         if section.num_values() == 0 {
             let id = section.id();
             env_override.remove_section_by_id(id);
@@ -67,6 +67,33 @@ pub(crate) fn query_refs_namespace(
         .with_leniency(lenient_config)
 }
 
+pub(crate) fn query_pack_options(
+    config: &gix_config::File<'static>,
+    lenient_config: bool,
+) -> Result<gix_pack::data::init::Options, Error> {
+    let mmap = config_bool(
+        config,
+        &gitoxide::Core::PACKED_GIT_MMAP,
+        "gitoxide.core.packedGitMmap",
+        true,
+        lenient_config,
+    )?;
+    let advice = config
+        .string("gitoxide.core.packedGitAdvice")
+        .map(|value| gitoxide::Core::PACKED_GIT_ADVICE.try_into_advice(value))
+        .transpose()
+        .with_leniency(lenient_config)?
+        .unwrap_or_default();
+    Ok(gix_pack::data::init::Options {
+        backing: if mmap {
+            gix_pack::data::init::Backing::MemoryMap
+        } else {
+            gix_pack::data::init::Backing::Buffered
+        },
+        advice,
+    })
+}
+
 pub(crate) fn reflog_or_default(
     config_reflog: Option<gix_ref::store::WriteReflog>,
     has_worktree: bool,
@@ -103,6 +130,16 @@ pub(crate) fn parse_object_caches(
     Ok((static_pack_cache_limit, pack_cache_bytes, object_cache_bytes))
 }
 
+/// Return a new pack cache to share among all object database handles, if `pack_cache_bytes` is set and not 0.
+#[cfg(feature = "max-performance-safe")]
+pub(crate) fn shared_pack_cache(
+    pack_cache_bytes: Option<usize>,
+) -> Option<gix_pack::cache::Shared<gix_pack::cache::lru::MemoryCappedHashmap>> {
+    pack_cache_bytes
+        .filter(|bytes| *bytes != 0)
+        .map(|bytes| gix_pack::cache::Shared::new(gix_pack::cache::lru::MemoryCappedHashmap::new(bytes)))
+}
+
 pub(crate) fn parse_core_abbrev(
     config: &gix_config::File<'static>,
     object_hash: gix_hash::Kind,
@@ -274,8 +274,12 @@ pub mod ssh_connect_options {
     /// The error produced when obtaining ssh connection configuration.
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
-    #[error(transparent)]
-    pub struct Error(#[from] super::key::GenericErrorWithValue);
+    pub enum Error {
+        #[error(transparent)]
+        Variant(#[from] super::key::GenericErrorWithValue),
+        #[error(transparent)]
+        IgnoreSshConfig(#[from] super::boolean::Error),
+    }
 }
 
 ///
@@ -597,6 +601,8 @@ pub(crate) struct Cache {
     pub object_hash: gix_hash::Kind,
     /// If true, multi-pack indices, whether present or not, may be used by the object database.
     pub use_multi_pack_index: bool,
+    /// How the object database should hold and access the data of packs.
+    pub pack_options: gix_pack::data::init::Options,
     /// The representation of `core.logallrefupdates`, or `None` if the variable wasn't set.
     pub reflog: Option<gix_ref::store::WriteReflog>,
     /// The representation of `gitoxide.core.refsNamespace`, or `None` if the variable wasn't set.
@@ -619,6 +625,10 @@ pub(crate) struct Cache {
     /// The amount of bytes to use for a memory backed delta pack cache. If `Some(0)`, no cache is used, if `None`
     /// a standard cache is used which costs near to nothing and always pays for itself.
     pub(crate) pack_cache_bytes: Option<usize>,
+    /// The memory backed delta pack cache with a capacity of `pack_cache_bytes`, shared by the object database handles
+    /// of all repositories that share this configuration so that the limit applies to all of them together, like in `git`.
+    #[cfg(feature = "max-performance-safe")]
+    pub(crate) shared_pack_cache: Option<gix_pack::cache::Shared<gix_pack::cache::lru::MemoryCappedHashmap>>,
     /// The amount of bytes to use for caching whole objects, or 0 to turn it off entirely.
     pub(crate) object_cache_bytes: usize,
     /// The amount of bytes we can hold in our static LRU cache. Otherwise, go with the defaults.
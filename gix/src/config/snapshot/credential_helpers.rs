@@ -246,7 +246,10 @@ pub(super) mod function {
         fn ignore_empty(self) -> Self {
             match self {
                 Ok(maybe_path) => Ok(maybe_path),
-                Err(gix_config::path::interpolate::Error::Missing { .. }) => Ok(None),
+                Err(
+                    gix_config::path::interpolate::Error::Missing { .. }
+                    | gix_config::path::interpolate::Error::UserNotFound { .. },
+                ) => Ok(None),
                 Err(err) => Err(err),
             }
         }
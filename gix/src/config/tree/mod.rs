@@ -22,8 +22,13 @@ pub(crate) mod root {
         pub const BRANCH: sections::Branch = sections::Branch;
         /// The `checkout` section.
         pub const CHECKOUT: sections::Checkout = sections::Checkout;
+        /// The `clean` section.
+        #[cfg(feature = "dirwalk")]
+        pub const CLEAN: sections::Clean = sections::Clean;
         /// The `clone` section.
         pub const CLONE: sections::Clone = sections::Clone;
+        /// The `commit` section.
+        pub const COMMIT: sections::Commit = sections::Commit;
         /// The `committer` section.
         pub const COMMITTER: sections::Committer = sections::Committer;
         /// The `core` section.
@@ -39,6 +44,8 @@ pub(crate) mod root {
         pub const FETCH: sections::Fetch = sections::Fetch;
         /// The `gitoxide` section.
         pub const GITOXIDE: sections::Gitoxide = sections::Gitoxide;
+        /// The `gpg` section.
+        pub const GPG: sections::Gpg = sections::Gpg;
         /// The `http` section.
         pub const HTTP: sections::Http = sections::Http;
         /// The `index` section.
@@ -62,6 +69,8 @@ pub(crate) mod root {
         /// The `status` section.
         #[cfg(feature = "status")]
         pub const STATUS: sections::Status = sections::Status;
+        /// The `tag` section.
+        pub const TAG: sections::Tag = sections::Tag;
         /// The `user` section.
         pub const USER: sections::User = sections::User;
         /// The `url` section.
@@ -73,7 +82,10 @@ pub(crate) mod root {
                 &Self::AUTHOR,
                 &Self::BRANCH,
                 &Self::CHECKOUT,
+                #[cfg(feature = "dirwalk")]
+                &Self::CLEAN,
                 &Self::CLONE,
+                &Self::COMMIT,
                 &Self::COMMITTER,
                 &Self::CORE,
                 &Self::CREDENTIAL,
@@ -82,6 +94,7 @@ pub(crate) mod root {
                 &Self::EXTENSIONS,
                 &Self::FETCH,
                 &Self::GITOXIDE,
+                &Self::GPG,
                 &Self::HTTP,
                 &Self::INDEX,
                 &Self::INIT,
@@ -94,6 +107,7 @@ pub(crate) mod root {
                 &Self::SSH,
                 #[cfg(feature = "status")]
                 &Self::STATUS,
+                &Self::TAG,
                 &Self::USER,
                 &Self::URL,
             ]
@@ -103,10 +117,12 @@ pub(crate) mod root {
 
 mod sections;
 pub use sections::{
-    branch, checkout, core, credential, extensions, fetch, gitoxide, http, index, protocol, push, remote, ssh, Author,
-    Branch, Checkout, Clone, Committer, Core, Credential, Extensions, Fetch, Gitoxide, Http, Index, Init, Mailmap,
-    Pack, Protocol, Push, Remote, Safe, Ssh, Url, User,
+    branch, checkout, core, credential, extensions, fetch, gitoxide, gpg, http, index, protocol, push, remote, ssh,
+    Author, Branch, Checkout, Clone, Commit, Committer, Core, Credential, Extensions, Fetch, Gitoxide, Gpg, Http,
+    Index, Init, Mailmap, Pack, Protocol, Push, Remote, Safe, Ssh, Tag, Url, User,
 };
+#[cfg(feature = "dirwalk")]
+pub use sections::Clean;
 #[cfg(feature = "blob-diff")]
 pub use sections::{diff, Diff};
 #[cfg(feature = "status")]
@@ -0,0 +1,19 @@
+use crate::{
+    config,
+    config::tree::{keys, Clean, Key, Section},
+};
+
+impl Clean {
+    /// The `clean.requireForce` key.
+    pub const REQUIRE_FORCE: keys::Boolean = keys::Boolean::new_boolean("requireForce", &config::Tree::CLEAN);
+}
+
+impl Section for Clean {
+    fn name(&self) -> &str {
+        "clean"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::REQUIRE_FORCE]
+    }
+}
@@ -0,0 +1,20 @@
+use crate::{
+    config,
+    config::tree::{keys, Commit, Key, Section},
+};
+
+impl Commit {
+    /// The `commit.gpgSign` key.
+    pub const GPG_SIGN: keys::Boolean = keys::Boolean::new_boolean("gpgSign", &config::Tree::COMMIT)
+        .with_note("Only used if the `signing` feature is enabled, and ignored otherwise");
+}
+
+impl Section for Commit {
+    fn name(&self) -> &str {
+        "commit"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::GPG_SIGN]
+    }
+}
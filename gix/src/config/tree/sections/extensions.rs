@@ -9,7 +9,7 @@ impl Extensions {
     /// The `extensions.objectFormat` key.
     pub const OBJECT_FORMAT: ObjectFormat =
         ObjectFormat::new_with_validate("objectFormat", &config::Tree::EXTENSIONS, validate::ObjectFormat).with_note(
-            "Support for SHA256 is prepared but not fully implemented yet. For now we abort when encountered",
+            "SHA256 is only supported with the `sha256` feature, and we abort when encountering it otherwise",
         );
 }
 
@@ -26,6 +26,10 @@ mod object_format {
             &'static self,
             value: Cow<'_, BStr>,
         ) -> Result<gix_hash::Kind, config::key::GenericErrorWithValue> {
+            #[cfg(feature = "sha256")]
+            if value.as_ref().eq_ignore_ascii_case(b"sha256") {
+                return Ok(gix_hash::Kind::Sha256);
+            }
             if value.as_ref().eq_ignore_ascii_case(b"sha1") {
                 Ok(gix_hash::Kind::Sha1)
             } else {
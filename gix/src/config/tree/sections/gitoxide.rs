@@ -89,6 +89,32 @@ mod subsections {
         }
     }
 
+    /// The `gitoxide.core.packedGitAdvice` key.
+    pub type PackedGitAdvice = keys::Any<super::validate::PackedGitAdvice>;
+
+    impl PackedGitAdvice {
+        /// Derive the access pattern to advise for memory-mapped pack data from `value`.
+        pub fn try_into_advice(
+            &'static self,
+            value: std::borrow::Cow<'_, crate::bstr::BStr>,
+        ) -> Result<gix_pack::data::init::Advice, crate::config::key::GenericErrorWithValue> {
+            use crate::bstr::ByteSlice;
+            use gix_pack::data::init::Advice;
+            Ok(match value.as_ref().as_bytes() {
+                b"normal" => Advice::Normal,
+                b"random" => Advice::Random,
+                b"sequential" => Advice::Sequential,
+                b"willneed" => Advice::WillNeed,
+                _ => {
+                    return Err(crate::config::key::GenericErrorWithValue::from_value(
+                        self,
+                        value.into_owned(),
+                    ))
+                }
+            })
+        }
+    }
+
     impl Core {
         /// The `gitoxide.core.defaultPackCacheMemoryLimit` key.
         pub const DEFAULT_PACK_CACHE_MEMORY_LIMIT: keys::UnsignedInteger =
@@ -133,6 +159,19 @@ mod subsections {
         pub const REFS_NAMESPACE: RefsNamespace =
             keys::Any::new_with_validate("refsNamespace", &Gitoxide::CORE, super::validate::RefsNamespace)
                 .with_environment_override("GIT_NAMESPACE");
+
+        /// The `gitoxide.core.packedGitMmap` key (default `true`).
+        ///
+        /// If `false`, the data of packs is read into memory when opening them instead of being memory-mapped.
+        pub const PACKED_GIT_MMAP: keys::Boolean = keys::Boolean::new_boolean("packedGitMmap", &Gitoxide::CORE);
+
+        /// The `gitoxide.core.packedGitAdvice` key (default `normal`).
+        ///
+        /// The access pattern to advise the operating system of for memory-mapped pack data, one of `normal`, `random`,
+        /// `sequential` or `willneed`.
+        pub const PACKED_GIT_ADVICE: PackedGitAdvice =
+            keys::Any::new_with_validate("packedGitAdvice", &Gitoxide::CORE, super::validate::PackedGitAdvice)
+                .with_note("it is ignored on platforms without `madvise()`");
     }
 
     impl Section for Core {
@@ -150,6 +189,8 @@ mod subsections {
                 &Self::FILTER_PROCESS_DELAY,
                 &Self::EXTERNAL_COMMAND_STDERR,
                 &Self::REFS_NAMESPACE,
+                &Self::PACKED_GIT_MMAP,
+                &Self::PACKED_GIT_ADVICE,
             ]
         }
 
@@ -350,6 +391,9 @@ mod subsections {
             keys::Executable::new_executable("commandWithoutShellFallback", &Gitoxide::SSH)
                 .with_environment_override("GIT_SSH")
                 .with_note("is always executed without shell and treated as fallback");
+        /// The `gitoxide.ssh.ignoreSshConfig` key.
+        pub const IGNORE_SSH_CONFIG: keys::Boolean = keys::Boolean::new_boolean("ignoreSshConfig", &Gitoxide::SSH)
+            .with_note("if true, ~/.ssh/config isn't used to resolve the host, user, port and identities to pass to plink or putty");
     }
 
     impl Section for Ssh {
@@ -358,7 +402,7 @@ mod subsections {
         }
 
         fn keys(&self) -> &[&dyn Key] {
-            &[&Self::COMMAND_WITHOUT_SHELL_FALLBACK]
+            &[&Self::COMMAND_WITHOUT_SHELL_FALLBACK, &Self::IGNORE_SSH_CONFIG]
         }
 
         fn parent(&self) -> Option<&dyn Section> {
@@ -556,4 +600,12 @@ pub mod validate {
             Ok(())
         }
     }
+
+    pub struct PackedGitAdvice;
+    impl Validate for PackedGitAdvice {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+            super::Core::PACKED_GIT_ADVICE.try_into_advice(value.into())?;
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,156 @@
+use crate::{
+    config,
+    config::tree::{keys, Gpg, Key, Section},
+};
+
+impl Gpg {
+    /// The `gpg.format` key.
+    pub const FORMAT: Format = Format::new_with_validate("format", &config::Tree::GPG, validate::Format)
+        .with_note("If unset, `openpgp` is assumed");
+    /// The `gpg.program` key.
+    pub const PROGRAM: keys::Executable = keys::Executable::new_executable("program", &config::Tree::GPG)
+        .with_note("Used for OpenPGP signatures if `gpg.openpgp.program` is unset");
+
+    /// The `gpg.openpgp` section.
+    pub const OPENPGP: OpenPgp = OpenPgp;
+    /// The `gpg.x509` section.
+    pub const X509: X509 = X509;
+    /// The `gpg.ssh` section.
+    pub const SSH: Ssh = Ssh;
+}
+
+/// The `gpg.format` key.
+pub type Format = keys::Any<validate::Format>;
+
+#[cfg(feature = "signing")]
+mod format {
+    use std::borrow::Cow;
+
+    use crate::{bstr::BStr, config, config::tree::gpg::Format};
+
+    impl Format {
+        /// Convert `value` into the signature format it names.
+        pub fn try_into_format(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<crate::signing::Format, config::key::GenericErrorWithValue> {
+            use crate::{bstr::ByteSlice, signing};
+            Ok(match value.as_ref().as_bytes() {
+                b"openpgp" => signing::Format::OpenPgp,
+                b"x509" => signing::Format::X509,
+                b"ssh" => signing::Format::Ssh,
+                _ => return Err(config::key::GenericErrorWithValue::from_value(self, value.into_owned())),
+            })
+        }
+    }
+}
+
+impl Section for Gpg {
+    fn name(&self) -> &str {
+        "gpg"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::FORMAT, &Self::PROGRAM]
+    }
+
+    fn sub_sections(&self) -> &[&dyn Section] {
+        &[&Self::OPENPGP, &Self::X509, &Self::SSH]
+    }
+}
+
+mod subsections {
+    use crate::config::{
+        tree::{keys, Gpg, Key, Section},
+        Tree,
+    };
+
+    /// The `gpg.openpgp` sub-section.
+    #[derive(Copy, Clone, Default)]
+    pub struct OpenPgp;
+
+    impl OpenPgp {
+        /// The `gpg.openpgp.program` key.
+        pub const PROGRAM: keys::Executable =
+            keys::Executable::new_executable("program", &Gpg::OPENPGP).with_fallback(&Gpg::PROGRAM);
+    }
+
+    impl Section for OpenPgp {
+        fn name(&self) -> &str {
+            "openpgp"
+        }
+
+        fn keys(&self) -> &[&dyn Key] {
+            &[&Self::PROGRAM]
+        }
+
+        fn parent(&self) -> Option<&dyn Section> {
+            Some(&Tree::GPG)
+        }
+    }
+
+    /// The `gpg.x509` sub-section.
+    #[derive(Copy, Clone, Default)]
+    pub struct X509;
+
+    impl X509 {
+        /// The `gpg.x509.program` key.
+        pub const PROGRAM: keys::Executable =
+            keys::Executable::new_executable("program", &Gpg::X509).with_note("If unset, `gpgsm` is used");
+    }
+
+    impl Section for X509 {
+        fn name(&self) -> &str {
+            "x509"
+        }
+
+        fn keys(&self) -> &[&dyn Key] {
+            &[&Self::PROGRAM]
+        }
+
+        fn parent(&self) -> Option<&dyn Section> {
+            Some(&Tree::GPG)
+        }
+    }
+
+    /// The `gpg.ssh` sub-section.
+    #[derive(Copy, Clone, Default)]
+    pub struct Ssh;
+
+    impl Ssh {
+        /// The `gpg.ssh.program` key.
+        pub const PROGRAM: keys::Executable =
+            keys::Executable::new_executable("program", &Gpg::SSH).with_note("If unset, `ssh-keygen` is used");
+        /// The `gpg.ssh.allowedSignersFile` key.
+        pub const ALLOWED_SIGNERS_FILE: keys::Path =
+            keys::Path::new_path("allowedSignersFile", &Gpg::SSH).with_note("Required to verify SSH signatures");
+    }
+
+    impl Section for Ssh {
+        fn name(&self) -> &str {
+            "ssh"
+        }
+
+        fn keys(&self) -> &[&dyn Key] {
+            &[&Self::PROGRAM, &Self::ALLOWED_SIGNERS_FILE]
+        }
+
+        fn parent(&self) -> Option<&dyn Section> {
+            Some(&Tree::GPG)
+        }
+    }
+}
+pub use subsections::{OpenPgp, Ssh, X509};
+
+mod validate {
+    use crate::{bstr::BStr, config::tree::keys};
+
+    pub struct Format;
+    impl keys::Validate for Format {
+        fn validate(&self, _value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            #[cfg(feature = "signing")]
+            super::Gpg::FORMAT.try_into_format(_value.into())?;
+            Ok(())
+        }
+    }
+}
@@ -15,11 +15,23 @@ pub mod branch;
 pub struct Checkout;
 pub mod checkout;
 
+/// The `clean` top-level section.
+#[derive(Copy, Clone, Default)]
+#[cfg(feature = "dirwalk")]
+pub struct Clean;
+#[cfg(feature = "dirwalk")]
+mod clean;
+
 /// The `clone` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Clone;
 mod clone;
 
+/// The `commit` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Commit;
+mod commit;
+
 /// The `committer` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Committer;
@@ -57,6 +69,11 @@ pub mod fetch;
 pub struct Gitoxide;
 pub mod gitoxide;
 
+/// The `gpg` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Gpg;
+pub mod gpg;
+
 /// The `http` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Http;
@@ -113,6 +130,11 @@ pub struct Status;
 #[cfg(feature = "status")]
 pub mod status;
 
+/// The `tag` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Tag;
+mod tag;
+
 /// The `user` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct User;
@@ -0,0 +1,20 @@
+use crate::{
+    config,
+    config::tree::{keys, Key, Section, Tag},
+};
+
+impl Tag {
+    /// The `tag.gpgSign` key.
+    pub const GPG_SIGN: keys::Boolean = keys::Boolean::new_boolean("gpgSign", &config::Tree::TAG)
+        .with_note("Only used if the `signing` feature is enabled, and ignored otherwise");
+}
+
+impl Section for Tag {
+    fn name(&self) -> &str {
+        "tag"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::GPG_SIGN]
+    }
+}
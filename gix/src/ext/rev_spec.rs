@@ -11,6 +11,7 @@ impl RevSpecExt for gix_revision::Spec {
             path: None,
             first_ref: None,
             second_ref: None,
+            reflog_date_out_of_range: None,
             repo,
         }
     }
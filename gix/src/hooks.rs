@@ -0,0 +1,182 @@
+//! Find and run hooks, the programs `git` invokes at certain points of its operation as documented in `githooks(5)`.
+//!
+//! Hooks are found in the directory configured with `core.hooksPath`, or in the `hooks` directory of the repository,
+//! and are only run in repositories that are fully trusted and whose [permissions](crate::open::Permissions::hooks)
+//! allow it.
+//!
+//! Hooks are run as part of [creating commits](crate::Repository::commit()) and after [cloning](crate::clone),
+//! while other hooks, like `pre-push` and `post-merge`, are only run on request with
+//! [`Repository::run_hook()`](crate::Repository::run_hook()).
+use std::{ffi::OsString, path::Path};
+
+use gix_hash::ObjectId;
+
+use crate::bstr::{BStr, BString, ByteVec};
+
+/// A hook along with the information it is invoked with, as documented in `githooks(5)`.
+#[derive(Debug, Clone, Copy)]
+pub enum Invocation<'a> {
+    /// `pre-commit`, run before a commit is created, which doesn't happen if the hook fails.
+    PreCommit,
+    /// `commit-msg`, run with the path to the file containing the message of the commit to be created, which the hook
+    /// may edit. The commit isn't created if the hook fails.
+    CommitMsg {
+        /// The file containing the commit message.
+        message_file: &'a Path,
+    },
+    /// `pre-push`, run before references are pushed to a remote, which doesn't happen if the hook fails.
+    ///
+    /// Note that `gix` can't push yet, so this hook is only run on request.
+    PrePush {
+        /// The name of the remote, or its `url` if the remote has no name.
+        remote_name: &'a BStr,
+        /// The url to push to.
+        url: &'a BStr,
+        /// The references to update on the remote, with one line per update on the standard input of the hook.
+        updates: &'a [PushUpdate<'a>],
+    },
+    /// `post-checkout`, run after `HEAD` was changed and the worktree was updated. Its outcome doesn't affect the
+    /// checkout.
+    PostCheckout {
+        /// The commit `HEAD` pointed to previously, which is the null id if there was none.
+        previous_head: ObjectId,
+        /// The commit `HEAD` points to now.
+        new_head: ObjectId,
+        /// `true` if a branch was checked out, or `false` if only files were checked out.
+        branch_checkout: bool,
+    },
+    /// `post-merge`, run after a successful merge. Its outcome doesn't affect the merge.
+    ///
+    /// Note that `gix` can't merge into the worktree yet, so this hook is only run on request.
+    PostMerge {
+        /// `true` if the merge was a squash merge.
+        squash: bool,
+    },
+    /// Any other hook with the given `name`, invoked with `args` and `stdin`.
+    Other {
+        /// The name of the hook, like `pre-rebase`.
+        name: &'a str,
+        /// The arguments to pass to the hook.
+        args: &'a [&'a BStr],
+        /// The data to write to the standard input of the hook.
+        stdin: &'a [u8],
+    },
+}
+
+/// A reference update as passed to the `pre-push` hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PushUpdate<'a> {
+    /// The full name of the local reference, like `refs/heads/main`, or `(delete)` if the remote reference is deleted.
+    pub local_ref: &'a BStr,
+    /// The id the local reference points to, or the null id if the remote reference is deleted.
+    pub local_id: ObjectId,
+    /// The full name of the reference on the remote.
+    pub remote_ref: &'a BStr,
+    /// The id the remote reference points to, or the null id if it doesn't exist yet.
+    pub remote_id: ObjectId,
+}
+
+impl Invocation<'_> {
+    /// The name of the hook, which is also the name of its file in the hooks directory.
+    pub fn name(&self) -> &str {
+        match self {
+            Invocation::PreCommit => "pre-commit",
+            Invocation::CommitMsg { .. } => "commit-msg",
+            Invocation::PrePush { .. } => "pre-push",
+            Invocation::PostCheckout { .. } => "post-checkout",
+            Invocation::PostMerge { .. } => "post-merge",
+            Invocation::Other { name, .. } => name,
+        }
+    }
+
+    /// The arguments the hook is invoked with.
+    pub fn args(&self) -> Vec<OsString> {
+        let flag = |value: bool| OsString::from(if value { "1" } else { "0" });
+        match self {
+            Invocation::PreCommit => Vec::new(),
+            Invocation::CommitMsg { message_file } => vec![message_file.as_os_str().to_owned()],
+            Invocation::PrePush { remote_name, url, .. } => vec![
+                gix_path::from_bstr(*remote_name).into_owned().into(),
+                gix_path::from_bstr(*url).into_owned().into(),
+            ],
+            Invocation::PostCheckout {
+                previous_head,
+                new_head,
+                branch_checkout,
+            } => vec![
+                previous_head.to_string().into(),
+                new_head.to_string().into(),
+                flag(*branch_checkout),
+            ],
+            Invocation::PostMerge { squash } => vec![flag(*squash)],
+            Invocation::Other { args, .. } => args
+                .iter()
+                .map(|arg| gix_path::from_bstr(*arg).into_owned().into())
+                .collect(),
+        }
+    }
+
+    /// The data to write to the standard input of the hook.
+    pub fn stdin(&self) -> BString {
+        match self {
+            Invocation::PrePush { updates, .. } => {
+                let mut out = BString::default();
+                for update in *updates {
+                    out.push_str(update.local_ref);
+                    out.push_str(format!(" {} ", update.local_id));
+                    out.push_str(update.remote_ref);
+                    out.push_str(format!(" {}\n", update.remote_id));
+                }
+                out
+            }
+            Invocation::Other { stdin, .. } => (*stdin).into(),
+            _ => BString::default(),
+        }
+    }
+}
+
+/// The outcome of running a hook.
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    /// The exit status of the hook.
+    pub status: std::process::ExitStatus,
+    /// Everything the hook wrote to its standard output.
+    pub stdout: BString,
+    /// Everything the hook wrote to its standard error.
+    pub stderr: BString,
+}
+
+impl Outcome {
+    /// Return `true` if the hook succeeded, which allows the operation it was run for to proceed.
+    pub fn success(&self) -> bool {
+        self.status.success()
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod dir {
+    /// The error returned by [`Repository::hooks_dir()`](crate::Repository::hooks_dir()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not interpolate the path in core.hooksPath")]
+        PathInterpolation(#[from] gix_config::path::interpolate::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod run {
+    use std::path::PathBuf;
+
+    /// The error returned by [`Repository::run_hook()`](crate::Repository::run_hook()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Dir(#[from] super::dir::Error),
+        #[error("Could not run the hook at '{}'", path.display())]
+        Spawn { path: PathBuf, source: std::io::Error },
+    }
+}
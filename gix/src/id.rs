@@ -41,12 +41,26 @@ impl<'repo> Id<'repo> {
     }
 
     /// Turn this object id into a shortened id with a length in hex as configured by `core.abbrev`.
+    ///
+    /// The prefix is made longer until it is unambiguous in the object database, using the sorted pack indices
+    /// to avoid scanning objects, which may yield the full id in the worst case.
     pub fn shorten(&self) -> Result<gix_hash::Prefix, shorten::Error> {
+        let max_hex_len = self.inner.kind().len_in_hex();
         let hex_len = self.repo.config.hex_len.map_or_else(
-            || self.repo.objects.packed_object_count().map(calculate_auto_hex_len),
+            || {
+                self.repo
+                    .objects
+                    .packed_object_count()
+                    .map(|count| calculate_auto_hex_len(count).min(max_hex_len))
+            },
             Ok,
         )?;
+        self.shorten_to(hex_len)
+    }
 
+    /// Turn this object id into a shortened id with at least `hex_len` hex characters, with the length being increased
+    /// until the prefix is unique.
+    pub(crate) fn shorten_to(&self, hex_len: usize) -> Result<gix_hash::Prefix, shorten::Error> {
         let prefix = gix_odb::store::prefix::disambiguate::Candidate::new(self.inner, hex_len)
             .expect("BUG: internal hex-len must always be valid");
         self.repo
@@ -71,7 +85,7 @@ fn calculate_auto_hex_len(num_packed_objects: u64) -> usize {
 ///
 #[allow(clippy::empty_docs)]
 pub mod shorten {
-    /// Returned by [`Id::prefix()`][super::Id::shorten()].
+    /// Returned by [`Id::shorten()`][super::Id::shorten()] and [`Repository::shorten_id()`][crate::Repository::shorten_id()].
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
     pub enum Error {
@@ -197,6 +211,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[cfg_attr(feature = "sha256", ignore = "SHA256 makes object ids larger")]
     fn size_of_oid() {
         let actual = std::mem::size_of::<Id<'_>>();
         let ceiling = 32;
@@ -46,6 +46,18 @@
 //! Use the `cache-efficiency-debug` cargo feature to learn how efficient the cache actually is - it's easy to end up with lowered
 //! performance if the cache is not hit in 50% of the time.
 //!
+//! ### Progress and Interrupts
+//!
+//! Long-running operations, like `fetch::Prepare::receive()`, `clone::PrepareCheckout::main_worktree()` or
+//! [`Repository::repack_loose_objects()`], report their progress to implementations of the traits in [`progress`],
+//! like [`NestedProgress`] or [`DynNestedProgress`]. Pass [`progress::Discard`] if there is no interest in progress
+//! information.
+//!
+//! These operations also take an `AtomicBool` which is checked cooperatively to stop them once it's set, with
+//! [`interrupt::IS_INTERRUPTED`] being a flag that can be set when receiving signals.
+//! An interrupted fetch doesn't update any reference, and an interrupted repack doesn't remove loose objects unless
+//! they are packed.
+//!
 //! ### Terminology
 //!
 //! #### `WorkingTree` and `WorkTree`
@@ -95,6 +107,8 @@
 pub use gix_actor as actor;
 #[cfg(feature = "attributes")]
 pub use gix_attributes as attrs;
+#[cfg(feature = "bundle")]
+pub use gix_bundle as bundle;
 #[cfg(feature = "command")]
 pub use gix_command as command;
 pub use gix_commitgraph as commitgraph;
@@ -104,6 +118,8 @@ pub use gix_date as date;
 #[cfg(feature = "dirwalk")]
 pub use gix_dir as dir;
 pub use gix_features as features;
+#[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+pub use gix_fetchhead as fetchhead;
 use gix_features::threading::OwnShared;
 pub use gix_features::{
     parallel,
@@ -120,6 +136,8 @@ pub use gix_ignore as ignore;
 #[cfg(feature = "index")]
 pub use gix_index as index;
 pub use gix_lock as lock;
+#[cfg(feature = "merge")]
+pub use gix_merge as merge;
 #[cfg(feature = "credentials")]
 pub use gix_negotiate as negotiate;
 pub use gix_object as objs;
@@ -174,6 +192,10 @@ pub use types::{
 #[cfg(feature = "attributes")]
 pub use types::{Pathspec, PathspecDetached, Submodule};
 
+#[cfg(feature = "merge")]
+pub mod cherry_pick;
+#[cfg(feature = "dirwalk")]
+pub mod clean;
 ///
 #[allow(clippy::empty_docs)]
 pub mod clone;
@@ -206,6 +228,13 @@ pub mod push;
 #[allow(clippy::empty_docs)]
 pub mod diff;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "blob-diff")]
+pub mod blame;
+#[cfg(feature = "revision")]
+pub mod bisect;
+
 /// See [`ThreadSafeRepository::discover()`], but returns a [`Repository`] instead.
 ///
 /// # Note
@@ -312,6 +341,12 @@ pub mod config;
 #[cfg(feature = "mailmap")]
 pub mod mailmap;
 
+#[cfg(feature = "signing")]
+pub mod signing;
+
+#[cfg(feature = "hooks")]
+pub mod hooks;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod worktree;
@@ -337,6 +372,16 @@ pub mod state;
 #[cfg(feature = "status")]
 pub mod status;
 
+#[cfg(feature = "stash")]
+pub mod stash;
+#[cfg(feature = "merge")]
+mod tree_merge;
+
+#[cfg(feature = "worktree-mutation")]
+pub mod reset;
+#[cfg(feature = "merge")]
+pub mod revert;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod shallow;
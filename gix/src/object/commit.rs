@@ -107,6 +107,26 @@ impl<'repo> Commit<'repo> {
             .map(|s| s.trim())
     }
 
+    /// Return the commits author as mapped by `mailmap`, typically obtained with [`open_mailmap()`](crate::Repository::open_mailmap()),
+    /// to obtain their canonical name and email.
+    #[cfg(feature = "mailmap")]
+    pub fn author_mapped(
+        &self,
+        mailmap: &gix_mailmap::Snapshot,
+    ) -> Result<gix_actor::Signature, gix_object::decode::Error> {
+        self.author().map(|signature| mailmap.resolve(signature))
+    }
+
+    /// Return the commits committer as mapped by `mailmap`, typically obtained with [`open_mailmap()`](crate::Repository::open_mailmap()),
+    /// to obtain their canonical name and email.
+    #[cfg(feature = "mailmap")]
+    pub fn committer_mapped(
+        &self,
+        mailmap: &gix_mailmap::Snapshot,
+    ) -> Result<gix_actor::Signature, gix_object::decode::Error> {
+        self.committer().map(|signature| mailmap.resolve(signature))
+    }
+
     /// Decode this commits parent ids on the fly without allocating.
     // TODO: tests
     pub fn parent_ids(&self) -> impl Iterator<Item = crate::Id<'repo>> + '_ {
@@ -154,17 +174,38 @@ impl<'repo> Commit<'repo> {
             first_parent: false,
             id_as_fallback: false,
             max_candidates: 10,
+            abbrev: None,
+            patterns: Vec::new(),
+            exclude_patterns: Vec::new(),
         }
     }
 
     /// Extracts the PGP signature and the data that was used to create the signature, or `None` if it wasn't signed.
-    // TODO: make it possible to verify the signature, probably by wrapping `SignedData`. It's quite some work to do it properly.
+    ///
+    /// Use [`verify_signature()`](Self::verify_signature()) to verify it.
     pub fn signature(
         &self,
     ) -> Result<Option<(std::borrow::Cow<'_, BStr>, gix_object::commit::SignedData<'_>)>, gix_object::decode::Error>
     {
         gix_object::CommitRefIter::signature(&self.data)
     }
+
+    /// Verify the signature of this commit with the [signing options](crate::Repository::signing_options()) of its repository,
+    /// similar to `git verify-commit`, or return `None` if it isn't signed.
+    ///
+    /// Note that the returned outcome has to be [inspected](crate::signing::verify::Outcome::is_valid()) to know if the
+    /// signature is valid and trusted.
+    #[cfg(feature = "signing")]
+    pub fn verify_signature(
+        &self,
+    ) -> Result<Option<crate::signing::verify::Outcome>, crate::object::verify_signature::Error> {
+        let Some((signature, signed_data)) = self.signature()? else {
+            return Ok(None);
+        };
+        let payload = bstr::BString::from(signed_data);
+        let options = self.repo.signing_options()?;
+        Ok(Some(crate::signing::verify(&payload, signature.as_ref(), &options)?))
+    }
 }
 
 impl<'r> std::fmt::Debug for Commit<'r> {
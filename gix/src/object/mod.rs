@@ -23,6 +23,24 @@ mod tag;
 #[allow(clippy::empty_docs)]
 pub mod tree;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "signing")]
+pub mod verify_signature {
+    /// The error returned by [`Commit::verify_signature()`](crate::Commit::verify_signature()) and
+    /// [`Tag::verify_signature()`](crate::Tag::verify_signature()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The object could not be decoded to find its signature")]
+        Decode(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        SigningOptions(#[from] crate::signing::options::Error),
+        #[error(transparent)]
+        Verify(#[from] crate::signing::verify::Error),
+    }
+}
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod try_into {
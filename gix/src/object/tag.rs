@@ -23,6 +23,24 @@ impl<'repo> Tag<'repo> {
     pub fn tagger(&self) -> Result<Option<gix_actor::SignatureRef<'_>>, gix_object::decode::Error> {
         gix_object::TagRefIter::from_bytes(&self.data).tagger()
     }
+
+    /// Verify the signature of this tag with the [signing options](crate::Repository::signing_options()) of its repository,
+    /// similar to `git verify-tag`, or return `None` if it isn't signed.
+    ///
+    /// Note that the returned outcome has to be [inspected](crate::signing::verify::Outcome::is_valid()) to know if the
+    /// signature is valid and trusted.
+    #[cfg(feature = "signing")]
+    pub fn verify_signature(
+        &self,
+    ) -> Result<Option<crate::signing::verify::Outcome>, crate::object::verify_signature::Error> {
+        let Some(signature) = self.decode()?.pgp_signature else {
+            return Ok(None);
+        };
+        // The signature is the last part of the tag, and everything before it was signed.
+        let payload = &self.data[..self.data.len() - signature.len()];
+        let options = self.repo.signing_options()?;
+        Ok(Some(crate::signing::verify(payload, signature, &options)?))
+    }
 }
 
 /// Remove Lifetime
@@ -11,14 +11,22 @@ pub struct Permissions {
     pub config: permissions::Config,
     /// Permissions related to where `gitattributes` should be loaded from.
     pub attributes: permissions::Attributes,
+    /// Whether hooks may be run as part of operations that support them, like creating commits, if the repository is
+    /// fully trusted.
+    ///
+    /// Set it to `false` to never run any hook, which is useful for applications that don't want to execute programs
+    /// provided by a repository. It's also `false` for [isolated](Permissions::isolated()) permissions.
+    pub hooks: bool,
 }
 
 /// The options used in [`ThreadSafeRepository::open_opts()`][crate::ThreadSafeRepository::open_opts()].
 ///
 /// ### Replacement Objects for the object database
 ///
+/// Objects are transparently replaced by the objects that `refs/replace/<id>` points to, unless `core.useReplaceRefs` is `false`.
 /// The environment variables `GIT_REPLACE_REF_BASE` and `GIT_NO_REPLACE_OBJECTS` are mapped to `gitoxide.objects.replaceRefBase`
-/// and `gitoxide.objects.noReplace` respectively and then interpreted exactly as their environment variable counterparts.
+/// and `core.useReplaceRefs` respectively and then interpreted exactly as their environment variable counterparts.
+/// To bypass replacements temporarily, set `ignore_replacements` on the [object database handle](crate::Repository::objects).
 ///
 /// Use [Permissions] to control which environment variables can be read, and config-overrides to control these values programmatically.
 #[derive(Clone)]
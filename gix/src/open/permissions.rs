@@ -177,6 +177,7 @@ impl Permissions {
             env: Environment::all(),
             config: Config::all(),
             attributes: Attributes::all(),
+            hooks: true,
         }
     }
 
@@ -187,15 +188,17 @@ impl Permissions {
             env: Environment::all(),
             config: Config::all(),
             attributes: Attributes::all(),
+            hooks: true,
         }
     }
 
-    /// Don't read any but the local git configuration and deny reading any environment variables.
+    /// Don't read any but the local git configuration, deny reading any environment variables and don't run hooks.
     pub fn isolated() -> Self {
         Permissions {
             config: Config::isolated(),
             attributes: Attributes::isolated(),
             env: Environment::isolated(),
+            hooks: false,
         }
     }
 }
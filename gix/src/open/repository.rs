@@ -170,6 +170,7 @@ impl ThreadSafeRepository {
                     ref env,
                     config,
                     attributes,
+                    hooks: _,
                 },
             ref api_config_overrides,
             ref cli_config_overrides,
@@ -327,6 +328,7 @@ impl ThreadSafeRepository {
                     object_hash: config.object_hash,
                     use_multi_pack_index: config.use_multi_pack_index,
                     current_dir: current_dir.to_owned().into(),
+                    pack_options: config.pack_options,
                 },
             )?),
             common_dir,
@@ -338,19 +340,20 @@ impl ThreadSafeRepository {
             #[cfg(feature = "index")]
             index: gix_fs::SharedFileSnapshotMut::new().into(),
             shallow_commits: gix_fs::SharedFileSnapshotMut::new().into(),
+            grafts: gix_fs::SharedFileSnapshotMut::new().into(),
             #[cfg(feature = "attributes")]
             modules: gix_fs::SharedFileSnapshotMut::new().into(),
         })
     }
 }
 
-// TODO: tests
+/// Return the prefix of the refs that define replacement objects, or `None` if replacements are disabled.
 fn replacement_objects_refs_prefix(
     config: &gix_config::File<'static>,
     lenient: bool,
     mut filter_config_section: fn(&gix_config::file::Metadata) -> bool,
 ) -> Result<Option<PathBuf>, Error> {
-    let is_disabled = config::shared::is_replace_refs_enabled(config, lenient, filter_config_section)
+    let is_disabled = !config::shared::is_replace_refs_enabled(config, lenient, filter_config_section)
         .map_err(config::Error::ConfigBoolean)?
         .unwrap_or(true);
 
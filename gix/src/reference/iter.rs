@@ -5,6 +5,8 @@ use std::path::Path;
 use gix_macros::momo;
 use gix_ref::file::ReferenceExt;
 
+use crate::bstr::BStr;
+
 /// A platform to create iterators over references.
 #[must_use = "Iterators should be obtained from this iterator platform"]
 pub struct Platform<'r> {
@@ -48,6 +50,15 @@ impl<'r> Platform<'r> {
         Ok(Iter::new(self.repo, self.platform.prefixed(prefix.as_ref())?))
     }
 
+    /// Return an iterator over all references whose full name matches the glob `pattern`, like `refs/remotes/origin/*` or `refs/tags/v*`.
+    ///
+    /// `*` matches any amount of characters including `/`, and `?` matches a single character.
+    /// Only references within the portion of `pattern` before its first wildcard are visited, which is fast even
+    /// if there are many references in total.
+    pub fn matching(&self, pattern: &BStr) -> Result<Iter<'_>, init::Error> {
+        Ok(Iter::new(self.repo, self.platform.matching(pattern)?))
+    }
+
     // TODO: tests
     /// Return an iterator over all references that are tags.
     ///
@@ -28,11 +28,14 @@ impl<'repo> Remote<'repo> {
     }
 
     /// Return the url used for the given `direction` with rewrites from `url.<base>.insteadOf|pushInsteadOf`, unless the instance
-    /// was created with one of the `_without_url_rewrite()` methods.
+    /// was created with one of the `_without_url_rewrite()` methods. This is the url that connections are made to.
     /// For pushing, this is the `remote.<name>.pushUrl` or the `remote.<name>.url` used for fetching, and for fetching it's
     /// the `remote.<name>.url`.
     /// Note that it's possible to only have the push url set, in which case there will be no way to fetch from the remote as
     /// the push-url isn't used for that.
+    ///
+    /// Like in git, `pushInsteadOf` only rewrites the `remote.<name>.url` when it's used for pushing, whereas an explicitly
+    /// set `remote.<name>.pushUrl` is only rewritten by `insteadOf`.
     pub fn url(&self, direction: remote::Direction) -> Option<&gix_url::Url> {
         match direction {
             remote::Direction::Fetch => self.url_alias.as_ref().or(self.url.as_ref()),
@@ -43,6 +46,15 @@ impl<'repo> Remote<'repo> {
                 .or_else(|| self.url(remote::Direction::Fetch)),
         }
     }
+
+    /// Return the url for the given `direction` as it was configured, without any of the rewrites applied that
+    /// [`url()`](Self::url()) would return, which makes it suitable for display.
+    pub fn url_without_url_rewrite(&self, direction: remote::Direction) -> Option<&gix_url::Url> {
+        match direction {
+            remote::Direction::Fetch => self.url.as_ref(),
+            remote::Direction::Push => self.push_url.as_ref().or(self.url.as_ref()),
+        }
+    }
 }
 
 /// Modification
@@ -52,21 +64,30 @@ impl Remote<'_> {
     /// This happens only once, and one if them may be changed even when reporting an error.
     /// If both urls fail, only the first error (for fetch urls) is reported.
     pub fn rewrite_urls(&mut self) -> Result<&mut Self, remote::init::Error> {
-        let url_err = match remote::init::rewrite_url(&self.repo.config, self.url.as_ref(), remote::Direction::Fetch) {
+        let url_err = match remote::init::rewrite_url(
+            &self.repo.config,
+            self.url.as_ref(),
+            self.push_url.as_ref(),
+            remote::Direction::Fetch,
+        ) {
             Ok(url) => {
                 self.url_alias = url;
                 None
             }
             Err(err) => err.into(),
         };
-        let push_url_err =
-            match remote::init::rewrite_url(&self.repo.config, self.push_url.as_ref(), remote::Direction::Push) {
-                Ok(url) => {
-                    self.push_url_alias = url;
-                    None
-                }
-                Err(err) => err.into(),
-            };
+        let push_url_err = match remote::init::rewrite_url(
+            &self.repo.config,
+            self.url.as_ref(),
+            self.push_url.as_ref(),
+            remote::Direction::Push,
+        ) {
+            Ok(url) => {
+                self.push_url_alias = url;
+                None
+            }
+            Err(err) => err.into(),
+        };
         url_err.or(push_url_err).map(Err::<&mut Self, _>).transpose()?;
         Ok(self)
     }
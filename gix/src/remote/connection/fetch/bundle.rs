@@ -0,0 +1,197 @@
+use std::{path::Path, sync::atomic::AtomicBool};
+
+use crate::{
+    remote,
+    remote::{
+        connection::fetch::config,
+        fetch::{self, refs, Atomic, DryRun, Outcome, RefLogMessage, RefMap, Status, WritePackedRefs},
+    },
+    Remote,
+};
+
+/// The error returned by [`Remote::fetch_bundle()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Open(#[from] gix_bundle::file::init::Error),
+    #[error("Could not obtain the configuration for writing packs")]
+    PackConfiguration(#[from] fetch::Error),
+    #[error(transparent)]
+    MappingValidation(#[from] gix_refspec::match_group::validate::Error),
+    #[error(transparent)]
+    WritePack(#[from] gix_bundle::file::write::Error),
+    #[error(transparent)]
+    UpdateRefs(#[from] refs::update::Error),
+    #[error("Failed to remove .keep file at \"{}\"", path.display())]
+    RemovePackKeepFile {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+}
+
+/// Options for [`Remote::fetch_bundle()`].
+#[derive(Default)]
+pub struct Options {
+    /// Refspecs to use in addition to the ones of the remote, which also receive the implicit tag refspec as configured
+    /// by the remote.
+    pub extra_refspecs: Vec<gix_refspec::RefSpec>,
+    /// The way reflog messages are composed, which defaults to using `fetch` as prefix.
+    pub reflog_message: Option<RefLogMessage>,
+    /// If `true`, write updated refs straight into the `packed-refs` file, which is useful when the repository is new.
+    pub write_packed_refs_only: bool,
+}
+
+impl<'repo> Remote<'repo> {
+    /// Fetch from the bundle at `path` instead of connecting to the remote, installing its pack into the object database and
+    /// updating local references with the refs it contains according to the refspecs of this remote, similar to what `git fetch`
+    /// does when given a bundle.
+    ///
+    /// Before any object is written, we assure that all prerequisites of the bundle are present.
+    /// As bundles don't have a handshake, it remains empty in the returned [`RefMap`], and negotiation doesn't happen.
+    ///
+    /// Note that bundles created with a `filter` contain an incomplete pack, which is written nonetheless.
+    pub fn fetch_bundle<P>(
+        &self,
+        path: &Path,
+        mut progress: P,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Outcome, Error>
+    where
+        P: crate::NestedProgress,
+        P::SubProgress: 'static,
+    {
+        self.fetch_bundle_inner(path, &mut progress, should_interrupt, options)
+    }
+
+    pub(crate) fn fetch_bundle_inner(
+        &self,
+        path: &Path,
+        progress: &mut dyn crate::DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        Options {
+            mut extra_refspecs,
+            reflog_message,
+            write_packed_refs_only,
+        }: Options,
+    ) -> Result<Outcome, Error> {
+        let _span = gix_trace::coarse!("remote::fetch_bundle()");
+        let repo = self.repo;
+        let bundle = gix_bundle::File::at(path)?;
+
+        if let Some(tag_spec) = self.fetch_tags.to_refspec().map(|spec| spec.to_owned()) {
+            if !extra_refspecs.contains(&tag_spec) {
+                extra_refspecs.push(tag_spec);
+            }
+        };
+        let specs = {
+            let mut s = self.fetch_specs.clone();
+            s.extend(extra_refspecs.clone());
+            s
+        };
+        let remote_refs = remote_refs(repo, &bundle.header.refs);
+        let (mappings, fixes) =
+            remote::connection::ref_map::map_remote_refs(&specs, self.fetch_specs.len(), &remote_refs)?;
+
+        let options = gix_pack::bundle::write::Options {
+            thread_limit: config::index_threads(repo)?,
+            index_version: config::pack_index_version(repo)?,
+            iteration_mode: gix_pack::data::input::Mode::Verify,
+            object_hash: repo.object_hash(),
+        };
+        let mut write_pack_bundle = bundle.write_pack_to_directory(
+            Some(&repo.objects.store_ref().path().join("pack")),
+            progress,
+            should_interrupt,
+            {
+                let mut objects = repo.objects.clone();
+                objects.ignore_replacements = true;
+                objects
+            },
+            options,
+        )?;
+
+        let update_refs = refs::update(
+            repo,
+            reflog_message.unwrap_or_else(|| RefLogMessage::Prefixed { action: "fetch".into() }),
+            &mappings,
+            self.refspecs(remote::Direction::Fetch),
+            &extra_refspecs,
+            self.fetch_tags,
+            DryRun::No,
+            if write_packed_refs_only {
+                WritePackedRefs::Only
+            } else {
+                WritePackedRefs::Never
+            },
+            Atomic::No,
+        )?;
+
+        if !update_refs.edits.is_empty() || write_pack_bundle.index.num_objects == 0 {
+            if let Some(path) = write_pack_bundle.keep_path.take() {
+                std::fs::remove_file(&path).map_err(|err| Error::RemovePackKeepFile { path, source: err })?;
+            }
+        }
+
+        Ok(Outcome {
+            ref_map: RefMap {
+                mappings,
+                extra_refspecs,
+                fixes,
+                remote_refs,
+                handshake: Default::default(),
+                object_hash: bundle.header.object_hash,
+            },
+            status: Status::Change {
+                negotiate: Default::default(),
+                write_pack_bundle,
+                update_refs,
+            },
+        })
+    }
+}
+
+/// Turn the refs of a bundle into refs as advertised by a server.
+///
+/// Bundles store `HEAD` like any other ref, so like `git`, we assume it points to the branch with the same object,
+/// preferring the configured default branch if there are multiple.
+fn remote_refs(repo: &crate::Repository, refs: &[gix_bundle::Ref]) -> Vec<gix_protocol::handshake::Ref> {
+    let default_branch = {
+        let mut name = crate::bstr::BString::from("refs/heads/");
+        name.extend_from_slice(
+            repo.config
+                .resolved
+                .string(crate::config::tree::Init::DEFAULT_BRANCH)
+                .as_deref()
+                .map_or(crate::init::DEFAULT_BRANCH_NAME.as_bytes(), |name| &**name),
+        );
+        name
+    };
+    refs.iter()
+        .map(|r| {
+            if r.name != "HEAD" {
+                return gix_protocol::handshake::Ref::Direct {
+                    full_ref_name: r.name.clone(),
+                    object: r.id,
+                };
+            }
+            let branch = refs
+                .iter()
+                .find(|b| b.id == r.id && b.name == default_branch)
+                .or_else(|| refs.iter().find(|b| b.id == r.id && b.name.starts_with(b"refs/heads/")));
+            match branch {
+                Some(branch) => gix_protocol::handshake::Ref::Symbolic {
+                    full_ref_name: r.name.clone(),
+                    target: branch.name.clone(),
+                    tag: None,
+                    object: r.id,
+                },
+                None => gix_protocol::handshake::Ref::Direct {
+                    full_ref_name: r.name.clone(),
+                    object: r.id,
+                },
+            }
+        })
+        .collect()
+}
@@ -28,6 +28,11 @@ pub enum Error {
         path: std::path::PathBuf,
         source: std::io::Error,
     },
+    #[error("Failed to write FETCH_HEAD at \"{}\"", path.display())]
+    WriteFetchHead {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
     #[error(transparent)]
     ShallowOpen(#[from] crate::shallow::open::Error),
     #[error("Server lack feature {feature:?}: {description}")]
@@ -45,6 +50,8 @@ pub enum Error {
     RejectShallowRemote,
     #[error(transparent)]
     NegotiationAlgorithmConfig(#[from] config::key::GenericErrorWithValue),
+    #[error("Interrupted before updating the shallow file and references")]
+    Interrupted,
     #[error("Failed to read remaining bytes in stream")]
     ReadRemainingBytes(#[source] std::io::Error),
     #[error("None of the refspec(s) {} matched any of the {num_remote_refs} refs on the remote", refspecs.iter().map(|r| r.to_ref().instruction().to_bstring().to_string()).collect::<Vec<_>>().join(", "))]
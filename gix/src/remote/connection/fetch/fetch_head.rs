@@ -0,0 +1,83 @@
+use std::borrow::Cow;
+
+use crate::{
+    bstr::BString,
+    remote,
+    remote::fetch::{self, refs, Error, SpecIndex},
+};
+
+/// Write `FETCH_HEAD` into the git directory of the repository of `remote` to list all refs in `ref_map` that were fetched,
+/// with `update_refs` being the outcome of updating local refs accordingly.
+///
+/// Like `git`, the upstream ref of the current branch is marked for merge if it's configured to be fetched from `remote`,
+/// or the ref matched by the first ref-spec if it's not a pattern otherwise.
+/// Tags that are implicitly included are only listed if they are new.
+pub(crate) fn write(
+    remote: &crate::Remote<'_>,
+    ref_map: &fetch::RefMap,
+    update_refs: &refs::update::Outcome,
+) -> Result<(), Error> {
+    let refspecs = remote.refspecs(remote::Direction::Fetch);
+    let merge_ref = upstream_of_head_branch(remote);
+    let first_spec_is_for_merge = refspecs
+        .first()
+        .is_some_and(|spec| spec.to_ref().source().is_some_and(|src| !src.contains(&b'*')));
+    let implicit_tag_refspec = remote
+        .fetch_tags
+        .to_refspec()
+        .filter(|_| matches!(remote.fetch_tags, fetch::Tags::Included));
+
+    let entries: Vec<_> = update_refs
+        .iter_mapping_updates(&ref_map.mappings, refspecs, &ref_map.extra_refspecs)
+        .enumerate()
+        .filter_map(|(idx, (update, mapping, spec, _edit))| {
+            let id = mapping.remote.as_id()?.to_owned();
+            let is_implicit_tag =
+                implicit_tag_refspec.is_some_and(|tag_spec| spec.map(|spec| spec.to_ref()) == Some(tag_spec));
+            if is_implicit_tag && update.mode != refs::update::Mode::New {
+                return None;
+            }
+            let remote_ref_name: BString = mapping
+                .remote
+                .as_name()
+                .map_or_else(|| id.to_string().into(), ToOwned::to_owned);
+            let for_merge = match (&merge_ref, mapping.spec_index) {
+                (Some(merge_ref), SpecIndex::ExplicitInRemote(_)) => merge_ref.as_bstr() == remote_ref_name,
+                (None, SpecIndex::ExplicitInRemote(0)) => idx == 0 && first_spec_is_for_merge,
+                _ => false,
+            };
+            Some(gix_fetchhead::Entry {
+                id,
+                for_merge,
+                remote_ref_name,
+            })
+        })
+        .collect();
+
+    let url = remote
+        .url(remote::Direction::Fetch)
+        .map(|url| {
+            let mut url = url.clone();
+            url.set_user(None);
+            url.set_password(None);
+            url.to_bstring()
+        })
+        .unwrap_or_default();
+    let mut buf = Vec::new();
+    gix_fetchhead::write_to(&entries, url.as_ref(), &mut buf).expect("in-memory writes don't fail");
+    let path = remote.repo.git_dir().join("FETCH_HEAD");
+    std::fs::write(&path, buf).map_err(|err| Error::WriteFetchHead { path, source: err })
+}
+
+/// Return the name of the ref to merge into the branch `HEAD` points to, if it's configured to be fetched from `remote`.
+fn upstream_of_head_branch(remote: &crate::Remote<'_>) -> Option<gix_ref::FullName> {
+    let repo = remote.repo;
+    let head_name = repo.head_name().ok().flatten()?;
+    let branch_remote = repo.branch_remote_name(head_name.shorten(), remote::Direction::Fetch)?;
+    if remote.name()?.as_bstr() != branch_remote.as_bstr() {
+        return None;
+    }
+    repo.branch_remote_ref_name(head_name.as_ref(), remote::Direction::Fetch)?
+        .ok()
+        .map(Cow::into_owned)
+}
@@ -4,7 +4,7 @@ use crate::{
     bstr::BString,
     remote,
     remote::{
-        fetch::{DryRun, RefMap},
+        fetch::{Atomic, DryRun, RefMap},
         ref_map, Connection,
     },
     Progress,
@@ -194,7 +194,10 @@ where
             dry_run: DryRun::No,
             reflog_message: None,
             write_packed_refs: WritePackedRefs::Never,
+            atomic: Atomic::No,
+            write_fetch_head: true,
             shallow: Default::default(),
+            filter: None,
         })
     }
 }
@@ -210,7 +213,13 @@ where
 }
 
 mod config;
+mod fetch_head;
 mod receive_pack;
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "bundle")]
+pub mod bundle;
 ///
 #[path = "update_refs/mod.rs"]
 pub mod refs;
@@ -225,7 +234,10 @@ where
     dry_run: DryRun,
     reflog_message: Option<RefLogMessage>,
     write_packed_refs: WritePackedRefs,
+    atomic: Atomic,
+    write_fetch_head: bool,
     shallow: remote::fetch::Shallow,
+    filter: Option<remote::fetch::Filter>,
 }
 
 /// Builder
@@ -254,6 +266,25 @@ where
         self
     }
 
+    /// If enabled, update local refs in an all-or-nothing fashion, so no ref is changed if the update of any of them is rejected,
+    /// for example because it isn't a fast-forward. The rejection is reported per ref, and all updates that would otherwise
+    /// have been applied are marked as [rejected](refs::update::Mode::RejectedByAtomicTransaction).
+    ///
+    /// Otherwise, and by default, rejected updates don't affect the other updates.
+    pub fn with_atomic(mut self, enabled: bool) -> Self {
+        self.atomic = if enabled { Atomic::Yes } else { Atomic::No };
+        self
+    }
+
+    /// If enabled, and by default, write `FETCH_HEAD` to list all fetched refs like `git fetch` does, unless in dry-run mode.
+    ///
+    /// The upstream of the current branch is marked for merge if it's fetched from this remote, or the ref matched by the
+    /// first ref-spec if it's not a pattern otherwise. All other refs are marked as `not-for-merge`.
+    pub fn with_write_fetch_head(mut self, enabled: bool) -> Self {
+        self.write_fetch_head = enabled;
+        self
+    }
+
     /// Set the reflog message to use when updating refs after fetching a pack.
     pub fn with_reflog_message(mut self, reflog_message: RefLogMessage) -> Self {
         self.reflog_message = reflog_message.into();
@@ -267,6 +298,14 @@ where
         self.shallow = shallow;
         self
     }
+
+    /// Ask the remote to omit all objects from the pack that don't pass `filter`, for a partial clone or fetch.
+    ///
+    /// Note that the remote must support the `filter` capability, or fetching fails.
+    pub fn with_filter(mut self, filter: remote::fetch::Filter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
 }
 
 impl<'remote, 'repo, T> Drop for Prepare<'remote, 'repo, T>
@@ -53,6 +53,12 @@ where
     /// A known application for this behaviour is in `remote-helper` implementations which should send this path via `lock <path>` to stdout
     /// to inform git about the file that it will remove once it updated the refs accordingly.
     ///
+    /// ### Interruption
+    ///
+    /// `should_interrupt` is checked while the pack is received and indexed, and once more before the shallow file and
+    /// references are updated, failing with [`Error::Interrupted`](super::Error::Interrupted) in the latter case.
+    /// Either way, neither references nor the shallow file are changed, and a pack is only ever present in full.
+    ///
     /// ### Deviation
     ///
     /// When **updating refs**, the `git-fetch` docs state that the following:
@@ -126,6 +132,15 @@ where
             arguments.use_include_tag();
         }
         let (shallow_commits, mut shallow_lock) = add_shallow_args(&mut arguments, &self.shallow, repo)?;
+        if let Some(filter) = &self.filter {
+            if !arguments.can_use_filter() {
+                return Err(Error::MissingServerFeature {
+                    feature: "filter",
+                    description: "partial clones and fetches need server support to omit objects from the pack",
+                });
+            }
+            arguments.filter(&filter.to_string());
+        }
 
         if self.ref_map.object_hash != repo.object_hash() {
             return Err(Error::IncompatibleObjectHash {
@@ -252,7 +267,9 @@ where
                 drop(graph_repo);
                 drop(negotiate_span);
 
-                let previous_response = previous_response.expect("knowledge of a pack means a response was received");
+                let previous_response = previous_response
+                    .as_ref()
+                    .expect("knowledge of a pack means a response was received");
                 if !previous_response.shallow_updates().is_empty() && shallow_lock.is_none() {
                     let reject_shallow_remote = repo
                         .config
@@ -285,8 +302,10 @@ where
                         progress,
                         should_interrupt,
                         Some(Box::new({
-                            let repo = repo.clone();
-                            repo.objects
+                            let mut objects = repo.objects.clone();
+                            // Bases of thin packs are the objects the remote knows, not their replacements.
+                            objects.ignore_replacements = true;
+                            objects
                         })),
                         options,
                     )?;
@@ -319,15 +338,28 @@ where
                         .ok();
                 }
 
-                if let Some(shallow_lock) = shallow_lock {
-                    if !previous_response.shallow_updates().is_empty() {
-                        crate::shallow::write(shallow_lock, shallow_commits, previous_response.shallow_updates())?;
-                    }
-                }
                 (write_pack_bundle, Some(outcome::Negotiate { graph, rounds }))
             }
         };
 
+        if should_interrupt.load(Ordering::Relaxed) {
+            // Neither the shallow file nor any reference will point to the objects of the new pack, which
+            // then must not be kept forever.
+            if let Some(path) = write_pack_bundle.as_ref().and_then(|bundle| bundle.keep_path.as_ref()) {
+                std::fs::remove_file(path).map_err(|err| Error::RemovePackKeepFile {
+                    path: path.clone(),
+                    source: err,
+                })?;
+            }
+            return Err(Error::Interrupted);
+        }
+
+        if let Some((shallow_lock, response)) = shallow_lock.zip(previous_response) {
+            if !response.shallow_updates().is_empty() {
+                crate::shallow::write(shallow_lock, shallow_commits, response.shallow_updates())?;
+            }
+        }
+
         let update_refs = refs::update(
             repo,
             self.reflog_message
@@ -339,8 +371,13 @@ where
             con.remote.fetch_tags,
             self.dry_run,
             self.write_packed_refs,
+            self.atomic,
         )?;
 
+        if self.write_fetch_head && matches!(self.dry_run, fetch::DryRun::No) {
+            super::fetch_head::write(con.remote, &self.ref_map, &update_refs)?;
+        }
+
         if let Some(bundle) = write_pack_bundle.as_mut() {
             if !update_refs.edits.is_empty() || bundle.index.num_objects == 0 {
                 if let Some(path) = bundle.keep_path.take() {
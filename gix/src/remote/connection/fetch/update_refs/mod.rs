@@ -60,6 +60,9 @@ impl From<Mode> for Update {
 /// * …existing refs would not become 'unborn', i.e. point to a reference that doesn't exist and won't be created due to ref-specs
 ///
 /// With these safeguards in place, one can handle each naturally and implement mirrors or bare repos easily.
+///
+/// If `atomic` is set, no ref is changed if any update is rejected, and all updates that would have
+/// been applied are marked as [rejected by the atomic transaction](update::Mode::RejectedByAtomicTransaction).
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn update(
     repo: &Repository,
@@ -70,6 +73,7 @@ pub(crate) fn update(
     fetch_tags: fetch::Tags,
     dry_run: fetch::DryRun,
     write_packed_refs: fetch::WritePackedRefs,
+    atomic: fetch::Atomic,
 ) -> Result<update::Outcome, update::Error> {
     let _span = gix_trace::detail!("update_refs()", mappings = mappings.len());
     let mut edits = Vec::new();
@@ -314,6 +318,17 @@ pub(crate) fn update(
         }
     }
 
+    if atomic == fetch::Atomic::Yes && updates.iter().any(|update| update.mode.is_rejected()) {
+        edits.clear();
+        for update in updates.iter_mut().filter(|update| update.edit_index.is_some()) {
+            if update.mode != Mode::NoChangeNeeded && !update.mode.is_rejected() {
+                update.mode = Mode::RejectedByAtomicTransaction;
+                update.type_change = None;
+            }
+            update.edit_index = None;
+        }
+    }
+
     let edits = match dry_run {
         fetch::DryRun::No => {
             let _span = gix_trace::detail!("apply", edits = edits.len());
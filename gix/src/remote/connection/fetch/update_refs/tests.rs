@@ -150,6 +150,7 @@ mod update {
                 fetch::Tags::None,
                 reflog_message.map_or(fetch::DryRun::No, |_| fetch::DryRun::Yes),
                 fetch::WritePackedRefs::Never,
+                fetch::Atomic::No,
             )
             .unwrap();
 
@@ -214,6 +215,7 @@ mod update {
                 fetch::Tags::None,
                 fetch::DryRun::Yes,
                 fetch::WritePackedRefs::Never,
+                fetch::Atomic::No,
             )?;
 
             assert_eq!(
@@ -246,6 +248,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )?;
         assert_eq!(
             out.updates,
@@ -273,6 +276,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )?;
         assert_eq!(
             out.updates,
@@ -313,6 +317,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )?;
         assert_eq!(
             out.updates,
@@ -364,6 +369,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )?;
         assert_eq!(
             out.updates,
@@ -412,6 +418,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )?;
         assert_eq!(
             out.updates,
@@ -442,6 +449,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )?;
         assert_eq!(
             out.updates,
@@ -586,6 +594,7 @@ mod update {
                 fetch::Tags::None,
                 fetch::DryRun::Yes,
                 fetch::WritePackedRefs::Never,
+                fetch::Atomic::No,
             )
             .unwrap();
 
@@ -618,6 +627,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )
         .unwrap();
 
@@ -663,6 +673,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )
         .unwrap();
 
@@ -690,6 +701,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )
         .unwrap();
 
@@ -741,6 +753,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )
         .unwrap();
 
@@ -791,6 +804,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::Yes,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )
         .unwrap();
 
@@ -826,6 +840,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::No,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )
         .unwrap();
 
@@ -849,6 +864,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::No,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )
         .unwrap();
 
@@ -870,6 +886,70 @@ mod update {
         }
     }
 
+    #[test]
+    fn rejections_prevent_all_updates_if_atomic() {
+        let (repo, _tmp) = repo_rw("two-origins");
+        let (mappings, specs) = mapping_from_specs(
+            &[
+                "refs/heads/main:refs/remotes/origin/g",
+                "refs/remotes/origin/g:refs/heads/not-currently-checked-out",
+            ],
+            &repo,
+        );
+        let previous_id = repo.find_reference("refs/remotes/origin/g").unwrap().id().detach();
+        for atomic in [fetch::Atomic::Yes, fetch::Atomic::No] {
+            let out = fetch::refs::update(
+                &repo,
+                prefixed("action"),
+                &mappings,
+                &specs,
+                &[],
+                fetch::Tags::None,
+                fetch::DryRun::No,
+                fetch::WritePackedRefs::Never,
+                atomic,
+            )
+            .unwrap();
+
+            let (expected_first_update, expected_edits) = match atomic {
+                fetch::Atomic::Yes => (
+                    fetch::refs::Update {
+                        mode: fetch::refs::update::Mode::RejectedByAtomicTransaction,
+                        type_change: None,
+                        edit_index: None,
+                    },
+                    0,
+                ),
+                fetch::Atomic::No => (
+                    fetch::refs::Update {
+                        mode: fetch::refs::update::Mode::FastForward,
+                        type_change: None,
+                        edit_index: Some(0),
+                    },
+                    1,
+                ),
+            };
+            assert_eq!(
+                out.updates,
+                vec![
+                    expected_first_update,
+                    fetch::refs::Update {
+                        mode: fetch::refs::update::Mode::RejectedNonFastForward,
+                        type_change: None,
+                        edit_index: None,
+                    }
+                ],
+                "the rejection is always reported per ref"
+            );
+            assert_eq!(out.edits.len(), expected_edits);
+            assert_eq!(
+                repo.find_reference("refs/remotes/origin/g").unwrap().id() == previous_id,
+                atomic == fetch::Atomic::Yes,
+                "the fast-forward is only applied if the update isn't atomic"
+            );
+        }
+    }
+
     #[test]
     fn fast_forwards_are_called_out_even_if_force_is_given() {
         let (repo, _tmp) = repo_rw("two-origins");
@@ -883,6 +963,7 @@ mod update {
             fetch::Tags::None,
             fetch::DryRun::No,
             fetch::WritePackedRefs::Never,
+            fetch::Atomic::No,
         )
         .unwrap();
 
@@ -908,8 +989,18 @@ mod update {
         spec: &str,
         remote_repo: &gix::Repository,
     ) -> (Vec<fetch::Mapping>, Vec<gix::refspec::RefSpec>) {
-        let spec = gix_refspec::parse(spec.into(), gix_refspec::parse::Operation::Fetch).unwrap();
-        let group = gix_refspec::MatchGroup::from_fetch_specs(Some(spec));
+        mapping_from_specs(&[spec], remote_repo)
+    }
+
+    fn mapping_from_specs(
+        specs: &[&str],
+        remote_repo: &gix::Repository,
+    ) -> (Vec<fetch::Mapping>, Vec<gix::refspec::RefSpec>) {
+        let specs: Vec<_> = specs
+            .iter()
+            .map(|spec| gix_refspec::parse((*spec).into(), gix_refspec::parse::Operation::Fetch).unwrap())
+            .collect();
+        let group = gix_refspec::MatchGroup::from_fetch_specs(specs.iter().copied());
         let references = remote_repo.references().unwrap();
         let mut references: Vec<_> = references.all().unwrap().map(|r| into_remote_ref(r.unwrap())).collect();
         references.push(into_remote_ref(remote_repo.find_reference("HEAD").unwrap()));
@@ -929,7 +1020,7 @@ mod update {
                 spec_index: SpecIndex::ExplicitInRemote(m.spec_index),
             })
             .collect();
-        (mappings, vec![spec.to_owned()])
+        (mappings, specs.iter().map(gix_refspec::RefSpecRef::to_owned).collect())
     }
 
     fn into_remote_ref(mut r: gix::Reference<'_>) -> gix_protocol::handshake::Ref {
@@ -83,6 +83,24 @@ pub enum Mode {
         /// The path(s) to the worktree directory where the branch is checked out.
         worktree_dirs: Vec<PathBuf>,
     },
+    /// The update would have been performed, but wasn't as another update was rejected while all updates
+    /// were to be applied atomically.
+    RejectedByAtomicTransaction,
+}
+
+impl Mode {
+    /// Return `true` if the update of the reference was rejected.
+    pub fn is_rejected(&self) -> bool {
+        matches!(
+            self,
+            Mode::RejectedSourceObjectNotFound { .. }
+                | Mode::RejectedTagUpdate
+                | Mode::RejectedNonFastForward
+                | Mode::RejectedToReplaceWithUnborn
+                | Mode::RejectedCurrentlyCheckedOut { .. }
+                | Mode::RejectedByAtomicTransaction
+        )
+    }
 }
 
 impl std::fmt::Display for Mode {
@@ -97,6 +115,7 @@ impl std::fmt::Display for Mode {
             Mode::RejectedTagUpdate => "rejected (would overwrite existing tag)",
             Mode::RejectedNonFastForward => "rejected (non-fast-forward)",
             Mode::RejectedToReplaceWithUnborn => "rejected (refusing to overwrite existing with unborn ref)",
+            Mode::RejectedByAtomicTransaction => "rejected (atomic update aborted)",
             Mode::RejectedCurrentlyCheckedOut { worktree_dirs } => {
                 return write!(
                     f,
@@ -113,7 +113,6 @@ where
         }: Options,
     ) -> Result<fetch::RefMap, Error> {
         let _span = gix_trace::coarse!("remote::Connection::ref_map()");
-        let null = gix_hash::ObjectId::null(gix_hash::Kind::Sha1); // OK to hardcode Sha1, it's not supposed to match, ever.
 
         if let Some(tag_spec) = self.remote.fetch_tags.to_refspec().map(|spec| spec.to_owned()) {
             if !extra_refspecs.contains(&tag_spec) {
@@ -133,40 +132,7 @@ where
                 progress,
             )
             .await?;
-        let num_explicit_specs = self.remote.fetch_specs.len();
-        let group = gix_refspec::MatchGroup::from_fetch_specs(specs.iter().map(gix_refspec::RefSpec::to_ref));
-        let (res, fixes) = group
-            .match_remotes(remote.refs.iter().map(|r| {
-                let (full_ref_name, target, object) = r.unpack();
-                gix_refspec::match_group::Item {
-                    full_ref_name,
-                    target: target.unwrap_or(&null),
-                    object,
-                }
-            }))
-            .validated()?;
-
-        let mappings = res.mappings;
-        let mappings = mappings
-            .into_iter()
-            .map(|m| fetch::Mapping {
-                remote: m.item_index.map_or_else(
-                    || {
-                        fetch::Source::ObjectId(match m.lhs {
-                            gix_refspec::match_group::SourceRef::ObjectId(id) => id,
-                            _ => unreachable!("no item index implies having an object id"),
-                        })
-                    },
-                    |idx| fetch::Source::Ref(remote.refs[idx].clone()),
-                ),
-                local: m.rhs.map(std::borrow::Cow::into_owned),
-                spec_index: if m.spec_index < num_explicit_specs {
-                    SpecIndex::ExplicitInRemote(m.spec_index)
-                } else {
-                    SpecIndex::Implicit(m.spec_index - num_explicit_specs)
-                },
-            })
-            .collect();
+        let (mappings, fixes) = map_remote_refs(&specs, self.remote.fetch_specs.len(), &remote.refs)?;
 
         let object_hash = extract_object_format(self.remote.repo, &remote.outcome)?;
         Ok(fetch::RefMap {
@@ -253,6 +219,52 @@ where
     }
 }
 
+/// Match `remote_refs` against `specs`, of which the first `num_explicit_specs` are the ones configured for the remote.
+pub(crate) fn map_remote_refs(
+    specs: &[gix_refspec::RefSpec],
+    num_explicit_specs: usize,
+    remote_refs: &[gix_protocol::handshake::Ref],
+) -> Result<
+    (Vec<fetch::Mapping>, Vec<gix_refspec::match_group::validate::Fix>),
+    gix_refspec::match_group::validate::Error,
+> {
+    let null = gix_hash::ObjectId::null(gix_hash::Kind::Sha1); // OK to hardcode Sha1, it's not supposed to match, ever.
+    let group = gix_refspec::MatchGroup::from_fetch_specs(specs.iter().map(gix_refspec::RefSpec::to_ref));
+    let (res, fixes) = group
+        .match_remotes(remote_refs.iter().map(|r| {
+            let (full_ref_name, target, object) = r.unpack();
+            gix_refspec::match_group::Item {
+                full_ref_name,
+                target: target.unwrap_or(&null),
+                object,
+            }
+        }))
+        .validated()?;
+
+    let mappings = res
+        .mappings
+        .into_iter()
+        .map(|m| fetch::Mapping {
+            remote: m.item_index.map_or_else(
+                || {
+                    fetch::Source::ObjectId(match m.lhs {
+                        gix_refspec::match_group::SourceRef::ObjectId(id) => id,
+                        _ => unreachable!("no item index implies having an object id"),
+                    })
+                },
+                |idx| fetch::Source::Ref(remote_refs[idx].clone()),
+            ),
+            local: m.rhs.map(std::borrow::Cow::into_owned),
+            spec_index: if m.spec_index < num_explicit_specs {
+                SpecIndex::ExplicitInRemote(m.spec_index)
+            } else {
+                SpecIndex::Implicit(m.spec_index - num_explicit_specs)
+            },
+        })
+        .collect();
+    Ok((mappings, fixes))
+}
+
 /// Assume sha1 if server says nothing, otherwise configure anything beyond sha1 in the local repo configuration
 #[allow(clippy::result_large_err)]
 fn extract_object_format(
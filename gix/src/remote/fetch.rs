@@ -12,11 +12,20 @@ pub mod negotiate {
     };
 }
 
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+pub use gix_protocol::fetch::Filter;
+
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
 pub use super::connection::fetch::{
     outcome, prepare, refs, Error, Outcome, Prepare, ProgressId, RefLogMessage, Status,
 };
 
+#[cfg(all(
+    feature = "bundle",
+    any(feature = "blocking-network-client", feature = "async-network-client")
+))]
+pub use super::connection::fetch::bundle;
+
 /// If `Yes`, don't really make changes but do as much as possible to get an idea of what would be done.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
@@ -37,6 +46,16 @@ pub(crate) enum WritePackedRefs {
     Only,
 }
 
+/// How to apply ref updates after fetching.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+pub(crate) enum Atomic {
+    /// Apply all ref updates or none of them, which is the case if at least one of them was rejected.
+    Yes,
+    /// Apply all ref updates that weren't rejected.
+    No,
+}
+
 /// Describe how to handle tags when fetching
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Tags {
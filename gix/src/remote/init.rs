@@ -77,15 +77,17 @@ impl<'repo> Remote<'repo> {
         should_rewrite_urls: bool,
         repo: &'repo Repository,
     ) -> Result<Self, Error> {
-        let (url_alias, _) = should_rewrite_urls
-            .then(|| rewrite_urls(&repo.config, Some(&url), None))
-            .unwrap_or(Ok((None, None)))?;
+        let (url_alias, push_url_alias) = if should_rewrite_urls {
+            rewrite_urls(&repo.config, Some(&url), None)?
+        } else {
+            (None, None)
+        };
         Ok(Remote {
             name: None,
             url: Some(url),
             url_alias,
             push_url: None,
-            push_url_alias: None,
+            push_url_alias,
             fetch_specs: Vec::new(),
             push_specs: Vec::new(),
             fetch_tags: Default::default(),
@@ -94,12 +96,23 @@ impl<'repo> Remote<'repo> {
     }
 }
 
+/// Return the `url` or `push_url` to use for `direction` after applying `url.<base>.insteadOf|pushInsteadOf` rules to it,
+/// or `None` if no rule matched.
+///
+/// Like git, an explicitly set `push_url` is only affected by `insteadOf`, while `pushInsteadOf` applies only to
+/// the `url` if it's also used for pushing.
 pub(crate) fn rewrite_url(
     config: &config::Cache,
     url: Option<&gix_url::Url>,
+    push_url: Option<&gix_url::Url>,
     direction: remote::Direction,
 ) -> Result<Option<gix_url::Url>, Error> {
-    url.and_then(|url| config.url_rewrite().longest(url, direction))
+    let (url, rules) = match (direction, push_url) {
+        (remote::Direction::Fetch, _) => (url, remote::Direction::Fetch),
+        (remote::Direction::Push, Some(push_url)) => (Some(push_url), remote::Direction::Fetch),
+        (remote::Direction::Push, None) => (url, remote::Direction::Push),
+    };
+    url.and_then(|url| config.url_rewrite().longest(url, rules))
         .map(|url| {
             gix_url::parse(url.as_ref()).map_err(|err| Error::RewrittenUrlInvalid {
                 kind: match direction {
@@ -118,8 +131,8 @@ pub(crate) fn rewrite_urls(
     url: Option<&gix_url::Url>,
     push_url: Option<&gix_url::Url>,
 ) -> Result<(Option<gix_url::Url>, Option<gix_url::Url>), Error> {
-    let url_alias = rewrite_url(config, url, remote::Direction::Fetch)?;
-    let push_url_alias = rewrite_url(config, push_url, remote::Direction::Push)?;
+    let url_alias = rewrite_url(config, url, push_url, remote::Direction::Fetch)?;
+    let push_url_alias = rewrite_url(config, url, push_url, remote::Direction::Push)?;
 
     Ok((url_alias, push_url_alias))
 }
@@ -0,0 +1,143 @@
+use gix_hash::ObjectId;
+
+use crate::bstr::{BString, ByteSlice};
+use crate::cherry_pick::{Error, Options, Outcome};
+use crate::tree_merge::{self, Entries};
+use crate::{ext::ObjectIdExt, Repository};
+
+impl Repository {
+    /// Apply the changes `commit` introduced compared to its first parent onto the commit `onto`, similar to
+    /// `git cherry-pick`, by merging them with the changes of `onto` while using the tree of the parent as base.
+    ///
+    /// If there are no conflicts, a new commit with the merged tree is written whose only parent is `onto`. It keeps
+    /// the author and message of `commit`, while its committer is the one configured for this repository.
+    /// Otherwise, the merged index with all conflicting sides is returned as [`Outcome::Conflicts`] without writing a
+    /// commit. Note that neither references nor the index or the worktree are changed in either case.
+    ///
+    /// Use `options` to pick the changes of a merge commit against one of its parents, or to record which commit
+    /// was picked.
+    pub fn cherry_pick(
+        &self,
+        commit: impl Into<ObjectId>,
+        onto: impl Into<ObjectId>,
+        options: Options,
+    ) -> Result<Outcome<'_>, Error> {
+        let _span = gix_trace::coarse!("gix::cherry_pick");
+        let id = commit.into();
+        let commit = self.find_object(id)?.try_into_commit()?;
+        let parent = mainline_parent(&commit, options.mainline)?;
+        let other = format!("{} ({})", commit.id().shorten_or_id(), commit.message()?.summary());
+        let onto = onto.into();
+        let tree = match self.merge_commit_changes(&commit, parent, onto, false, &other)? {
+            Ok(tree) => tree,
+            Err(outcome) => return Ok(outcome),
+        };
+
+        let decoded = commit.decode()?;
+        let mut message: BString = decoded.message.to_owned();
+        if options.record_origin {
+            append_origin(&mut message, id);
+        }
+        let new_commit = gix_object::Commit {
+            message,
+            tree,
+            author: decoded.author.into(),
+            committer: self.committer().ok_or(Error::CommitterMissing)??.into(),
+            encoding: decoded.encoding.map(ToOwned::to_owned),
+            parents: vec![onto].into(),
+            extra_headers: Default::default(),
+        };
+        Ok(Outcome::Commit(self.write_object(&new_commit)?))
+    }
+
+    /// Merge the changes `commit` introduced compared to `parent`, or the inverse of these if `reverse` is `true`, with
+    /// the ones of the commit `onto`, and write the merged tree. `label` describes the changes in conflict markers.
+    ///
+    /// Return the id of the merged tree, or the outcome with all conflicts if the changes didn't apply cleanly.
+    pub(crate) fn merge_commit_changes(
+        &self,
+        commit: &crate::Commit<'_>,
+        parent: Option<ObjectId>,
+        onto: ObjectId,
+        reverse: bool,
+        label: &str,
+    ) -> Result<Result<ObjectId, Outcome<'_>>, Error> {
+        let commit_entries = |id: ObjectId| -> Result<Entries, Error> {
+            tree_merge::tree_entries(self, self.find_object(id)?.try_into_commit()?.tree_id()?.detach())
+        };
+        let mut base = parent.map(commit_entries).transpose()?.unwrap_or_default();
+        let ours = commit_entries(onto)?;
+        let mut theirs = tree_merge::tree_entries::<Error>(self, commit.tree_id()?.detach())?;
+        if reverse {
+            std::mem::swap(&mut base, &mut theirs);
+        }
+
+        let current = onto.attach(self).shorten_or_id().to_string();
+        let merged = tree_merge::merge::<Error>(
+            self,
+            &base,
+            &ours,
+            &theirs,
+            gix_merge::blob::Labels {
+                ancestor: None,
+                current: Some(current.as_str().into()),
+                other: Some(label.into()),
+            },
+        )?;
+        if !merged.conflicts.is_empty() {
+            return Ok(Err(Outcome::Conflicts {
+                index: tree_merge::index_from_entries(self, &merged.index, &merged.conflicts, &[]).into(),
+                conflicts: merged.conflicts.into_iter().map(|conflict| conflict.path).collect(),
+            }));
+        }
+        if let Some(path) = tree_merge::directory_file_conflict(&merged.index) {
+            return Err(Error::DirectoryFileConflict {
+                id: commit.id,
+                path: path.clone(),
+            });
+        }
+        Ok(Ok(tree_merge::write_tree(self, &merged.index)?))
+    }
+}
+
+/// Return the parent of `commit` selected by `mainline`, which must be set for merge commits, or `None` if `commit` has
+/// no parent.
+pub(crate) fn mainline_parent(commit: &crate::Commit<'_>, mainline: Option<usize>) -> Result<Option<ObjectId>, Error> {
+    let id = commit.id;
+    let parents: Vec<_> = commit.parent_ids().map(crate::Id::detach).collect();
+    Ok(match mainline {
+        None if parents.len() > 1 => return Err(Error::MainlineRequired { id }),
+        None => parents.first().copied(),
+        Some(mainline) => Some(
+            *mainline
+                .checked_sub(1)
+                .and_then(|index| parents.get(index))
+                .ok_or(Error::NoSuchParent { id, mainline })?,
+        ),
+    })
+}
+
+/// Append the line recording that the commit `id` was picked to `message`, separated by an empty line unless the last
+/// paragraph of `message` consists of trailers only, just like `git cherry-pick -x` does.
+fn append_origin(message: &mut BString, id: ObjectId) {
+    const PREFIX: &str = "(cherry picked from commit ";
+    if !message.ends_with(b"\n") {
+        message.push(b'\n');
+    }
+    let has_trailers = message
+        .trim_end()
+        .rfind("\n\n")
+        .map(|pos| &message[pos + 2..])
+        .is_some_and(|paragraph| {
+            paragraph.trim_end().lines().all(|line| {
+                line.starts_with(PREFIX.as_bytes())
+                    || line.find(": ").is_some_and(|pos| {
+                        pos > 0 && line[..pos].iter().all(|b| b.is_ascii_alphanumeric() || *b == b'-')
+                    })
+            })
+        });
+    if !has_trailers {
+        message.push(b'\n');
+    }
+    message.extend_from_slice(format!("{PREFIX}{id})\n").as_bytes());
+}
@@ -0,0 +1,130 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use gix_dir::entry::{Kind, PathspecMatch, Property, Status};
+use gix_dir::walk::{EmissionMode, ForDeletionMode};
+
+use crate::bstr::{BStr, ByteSlice};
+use crate::clean::{Entry, Error, Force, Ignored, Options, Removal};
+use crate::config::{cache::util::ApplyLeniency, tree::Clean};
+use crate::Repository;
+
+impl Repository {
+    /// Remove untracked files from the working tree as configured by `options`, similar to `git clean`, and return all
+    /// entries that were considered for removal along with what happened to them, sorted by path.
+    /// Use `patterns` to limit the paths to clean, and `should_interrupt` to stop the operation, which either
+    /// fails the directory walk or stops removing entries, leaving the remaining ones out of the result.
+    ///
+    /// Unless this is a [dry-run](Options::dry_run), this fails if `clean.requireForce` isn't `false` and
+    /// [force](Options::force) wasn't set, just like `git clean` does.
+    /// Nested repositories are only removed if such [directories](Options::directories) are allowed with
+    /// [`Force::Twice`], but are otherwise returned as [`Removal::SkippedRepository`].
+    ///
+    /// Note that each entry is removed on its own, so a failure to remove one of them is recorded as
+    /// [`Removal::Failed`], while all remaining entries are still removed.
+    pub fn clean(
+        &self,
+        patterns: impl IntoIterator<Item = impl AsRef<BStr>>,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Vec<Entry>, Error> {
+        let _span = gix_trace::coarse!("gix::clean");
+        if !options.dry_run && options.force == Force::None && self.clean_require_force()? {
+            return Err(Error::ForceRequired);
+        }
+
+        let index = self.index_or_empty()?;
+        let remove_ignored = options.ignored != Ignored::Keep;
+        let dirwalk_options = self
+            .dirwalk_options()?
+            .for_deletion(Some(if remove_ignored && options.directories {
+                ForDeletionMode::FindRepositoriesInIgnoredDirectories
+            } else {
+                ForDeletionMode::default()
+            }))
+            .classify_untracked_bare_repositories(true)
+            .emit_untracked(EmissionMode::CollapseDirectory)
+            .emit_ignored(remove_ignored.then_some(EmissionMode::CollapseDirectory))
+            .empty_patterns_match_prefix(true)
+            .emit_empty_directories(true);
+        let mut collect = gix_dir::walk::delegate::Collect::default();
+        self.dirwalk(&index, patterns, should_interrupt, dirwalk_options, &mut collect)?;
+        let workdir = self.work_dir().expect("present as the dirwalk succeeded");
+
+        let mut out = Vec::new();
+        for (entry, collapsed_directory_status) in collect.into_entries_by_path() {
+            if collapsed_directory_status.is_some()
+                || entry.pathspec_match.map_or(true, |m| m == PathspecMatch::Excluded)
+            {
+                continue;
+            }
+            let is_selected = match entry.status {
+                Status::Pruned | Status::Tracked => false,
+                Status::Untracked => options.ignored != Ignored::Only,
+                Status::Ignored(gix_ignore::Kind::Expendable) => remove_ignored,
+                Status::Ignored(gix_ignore::Kind::Precious) => false,
+            };
+            if !is_selected {
+                continue;
+            }
+
+            let path = workdir.join(gix_path::from_bstr(entry.rela_path.as_bstr()));
+            let Some(mut disk_kind) = entry
+                .disk_kind
+                .or_else(|| path.symlink_metadata().ok().map(|m| m.file_type().into()))
+            else {
+                continue;
+            };
+            if disk_kind == Kind::Directory && gix_discover::is_git(&path).is_ok() {
+                disk_kind = Kind::Repository;
+            }
+            if !options.directories
+                && (disk_kind.is_dir()
+                    || entry.rela_path.rfind_byte(b'/').is_some_and(|pos| {
+                        index
+                            .entry_closest_to_directory(entry.rela_path[..pos].as_bstr())
+                            .is_none()
+                    }))
+            {
+                continue;
+            }
+
+            let removal = if disk_kind == Kind::Repository && options.force != Force::Twice {
+                Removal::SkippedRepository
+            } else if entry.property == Some(Property::EmptyDirectoryAndCWD) {
+                Removal::SkippedCurrentDirectory
+            } else if options.dry_run {
+                Removal::WouldRemove
+            } else if should_interrupt.load(Ordering::Relaxed) {
+                break;
+            } else {
+                let res = if disk_kind.is_dir() {
+                    std::fs::remove_dir_all(&path)
+                } else {
+                    std::fs::remove_file(&path)
+                };
+                match res {
+                    Ok(()) => Removal::Removed,
+                    Err(err) => Removal::Failed(err),
+                }
+            };
+            out.push(Entry {
+                rela_path: entry.rela_path,
+                disk_kind,
+                status: entry.status,
+                removal,
+            });
+        }
+        Ok(out)
+    }
+
+    fn clean_require_force(&self) -> Result<bool, crate::config::boolean::Error> {
+        Ok(self
+            .config
+            .resolved
+            .boolean(&Clean::REQUIRE_FORCE)
+            .map(|res| Clean::REQUIRE_FORCE.enrich_error(res))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?
+            .unwrap_or(true))
+    }
+}
@@ -70,6 +70,13 @@ impl crate::Repository {
                 .and_then(|variant| Ssh::VARIANT.try_into_variant(variant).transpose())
                 .transpose()
                 .with_leniency(self.options.lenient_config)?,
+            ignore_ssh_config: config
+                .boolean_filter(gitoxide::Ssh::IGNORE_SSH_CONFIG, &mut trusted)
+                .map(|value| gitoxide::Ssh::IGNORE_SSH_CONFIG.enrich_error(value))
+                .transpose()
+                .with_leniency(self.options.lenient_config)?
+                .unwrap_or_default(),
+            ssh_config_path: None,
         };
         Ok(opts)
     }
@@ -15,7 +15,7 @@ impl crate::Repository {
     ///
     /// For transports that support proxy authentication, the
     /// [default authentication method](crate::config::Snapshot::credential_helpers()) will be used with the url of the proxy
-    /// if it contains a user name.
+    /// if it contains a user name but no password, just like `git` does. Otherwise, credentials in the proxy url are used as is.
     #[cfg_attr(
         not(any(
             feature = "blocking-http-transport-reqwest",
@@ -278,7 +278,7 @@ impl crate::Repository {
                         .filter(|url| !url.is_empty())
                         .map(|url| gix_url::parse(url.into()))
                         .transpose()?
-                        .filter(|url| url.user().is_some())
+                        .filter(|url| url.user().is_some() && url.password().is_none())
                         .map(|url| -> Result<_, config::transport::http::Error> {
                             let (mut cascade, action_with_normalized_url, prompt_opts) =
                                 self.config_snapshot().credential_helpers(url)?;
@@ -316,7 +316,7 @@ impl crate::Repository {
                                 .transpose()
                             })
                             .transpose()?
-                            .or_else(|| Some(crate::env::agent().into()));
+                            .or_else(|| self.config.user_agent_tuple().1.map(Cow::into_owned));
                     }
 
                     {
@@ -0,0 +1,131 @@
+use std::{io::Write, path::PathBuf};
+
+use crate::{
+    bstr::{BStr, BString},
+    commit,
+    config::tree::Core,
+    hooks,
+};
+
+/// Hooks
+impl crate::Repository {
+    /// Return the directory containing the hooks of this repository, which is the one configured with `core.hooksPath`
+    /// or the `hooks` directory of the [common directory](Self::common_dir()). It may not exist.
+    ///
+    /// A relative `core.hooksPath` is relative to the directory the hooks run in, which is the root of the worktree,
+    /// or the `git` directory of bare repositories.
+    pub fn hooks_dir(&self) -> Result<PathBuf, hooks::dir::Error> {
+        Ok(match self.config.trusted_file_path(Core::HOOKS_PATH).transpose()? {
+            Some(path) => self.hooks_cwd().join(path),
+            None => self.current_dir().join(self.common_dir()).join("hooks"),
+        })
+    }
+
+    /// Return the path to the hook named `name`, like `pre-commit`, or `None` if there is no such hook.
+    ///
+    /// Like in `git`, hooks must be executable on platforms that support it, or they are ignored.
+    pub fn hook_path(&self, name: &str) -> Result<Option<PathBuf>, hooks::dir::Error> {
+        let path = self.hooks_dir()?.join(name);
+        let is_hook = std::fs::metadata(&path)
+            .is_ok_and(|metadata| metadata.is_file() && (cfg!(not(unix)) || gix_fs::is_executable(&metadata)));
+        Ok(is_hook.then_some(path))
+    }
+
+    /// Run the hook described by `invocation` and return its outcome, or `None` if it didn't run.
+    ///
+    /// Hooks don't run if there is no such hook, if [hooks are not permitted](crate::open::Permissions::hooks), or if
+    /// this repository isn't fully trusted, as hooks could otherwise be used to execute arbitrary programs.
+    ///
+    /// The hook runs in the root of the worktree, or in the `git` directory of bare repositories, with `GIT_DIR` set.
+    /// Its standard output and standard error are captured.
+    ///
+    /// Note that this is the only way to run the `pre-push` and `post-merge` hooks, as `gix` can neither push nor merge
+    /// into the worktree yet.
+    pub fn run_hook(&self, invocation: hooks::Invocation<'_>) -> Result<Option<hooks::Outcome>, hooks::run::Error> {
+        if !self.may_run_hooks() {
+            return Ok(None);
+        }
+        let Some(path) = self.hook_path(invocation.name())? else {
+            return Ok(None);
+        };
+        let _span = gix_trace::coarse!("gix::run_hook", name = invocation.name());
+        let mut cmd: std::process::Command = gix_command::prepare(&path)
+            .args(invocation.args())
+            .env("GIT_DIR", self.current_dir().join(self.git_dir()))
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .into();
+        if matches!(
+            invocation,
+            hooks::Invocation::PreCommit | hooks::Invocation::CommitMsg { .. }
+        ) {
+            cmd.env("GIT_INDEX_FILE", self.current_dir().join(self.index_path()));
+        }
+        cmd.current_dir(self.hooks_cwd());
+        let spawn_error = |source| hooks::run::Error::Spawn {
+            path: path.clone(),
+            source,
+        };
+        let mut child = cmd.spawn().map_err(spawn_error)?;
+        let mut stdin = child.stdin.take().expect("configured");
+        let input = invocation.stdin();
+        let output = std::thread::scope(|scope| {
+            // Write from another thread as the hook may produce output before consuming all of its input, if any.
+            let writer = scope.spawn(move || stdin.write_all(&input));
+            let output = child.wait_with_output();
+            writer.join().expect("no panic").ok();
+            output
+        })
+        .map_err(spawn_error)?;
+        Ok(Some(hooks::Outcome {
+            status: output.status,
+            stdout: output.stdout.into(),
+            stderr: output.stderr.into(),
+        }))
+    }
+
+    /// Run the `pre-commit` and `commit-msg` hooks for a commit with `message`, and return the message as left by
+    /// `commit-msg`.
+    pub(crate) fn run_commit_hooks(&self, message: &BStr) -> Result<BString, commit::Error> {
+        let reject = |name, outcome: hooks::Outcome| -> Result<(), commit::Error> {
+            if outcome.success() {
+                Ok(())
+            } else {
+                Err(commit::Error::HookRejected {
+                    name,
+                    stderr: outcome.stderr,
+                })
+            }
+        };
+        if let Some(outcome) = self.run_hook(hooks::Invocation::PreCommit)? {
+            reject("pre-commit", outcome)?;
+        }
+        if !self.may_run_hooks() || self.hook_path("commit-msg").map_err(hooks::run::Error::from)?.is_none() {
+            return Ok(message.into());
+        }
+
+        let message_file = self.current_dir().join(self.git_dir()).join("COMMIT_EDITMSG");
+        let message_file_error = |source| commit::Error::MessageFile {
+            path: message_file.clone(),
+            source,
+        };
+        std::fs::write(&message_file, message).map_err(message_file_error)?;
+        if let Some(outcome) = self.run_hook(hooks::Invocation::CommitMsg {
+            message_file: &message_file,
+        })? {
+            reject("commit-msg", outcome)?;
+        }
+        Ok(std::fs::read(&message_file).map_err(message_file_error)?.into())
+    }
+
+    fn may_run_hooks(&self) -> bool {
+        self.options.permissions.hooks && self.git_dir_trust() == gix_sec::Trust::Full
+    }
+
+    /// The directory hooks run in.
+    fn hooks_cwd(&self) -> PathBuf {
+        self.current_dir()
+            .join(self.work_dir().unwrap_or_else(|| self.git_dir()))
+    }
+}
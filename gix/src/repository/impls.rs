@@ -10,6 +10,7 @@ impl Clone for crate::Repository {
             #[cfg(feature = "index")]
             self.index.clone(),
             self.shallow_commits.clone(),
+            self.grafts.clone(),
             #[cfg(feature = "attributes")]
             self.modules.clone(),
         )
@@ -46,6 +47,7 @@ impl From<&crate::ThreadSafeRepository> for crate::Repository {
             #[cfg(feature = "index")]
             repo.index.clone(),
             repo.shallow_commits.clone(),
+            repo.grafts.clone(),
             #[cfg(feature = "attributes")]
             repo.modules.clone(),
         )
@@ -64,6 +66,7 @@ impl From<crate::ThreadSafeRepository> for crate::Repository {
             #[cfg(feature = "index")]
             repo.index,
             repo.shallow_commits,
+            repo.grafts,
             #[cfg(feature = "attributes")]
             repo.modules.clone(),
         )
@@ -84,6 +87,7 @@ impl From<crate::Repository> for crate::ThreadSafeRepository {
             #[cfg(feature = "attributes")]
             modules: r.modules,
             shallow_commits: r.shallow_commits,
+            grafts: r.grafts,
         }
     }
 }
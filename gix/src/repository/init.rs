@@ -11,6 +11,7 @@ impl crate::Repository {
         linked_worktree_options: crate::open::Options,
         #[cfg(feature = "index")] index: crate::worktree::IndexStorage,
         shallow_commits: crate::shallow::CommitsStorage,
+        grafts: crate::revision::walk::GraftsStorage,
         #[cfg(feature = "attributes")] modules: crate::submodule::ModulesFileStorage,
     ) -> Self {
         setup_objects(&mut objects, &config);
@@ -25,6 +26,7 @@ impl crate::Repository {
             #[cfg(feature = "index")]
             index,
             shallow_commits,
+            grafts,
             #[cfg(feature = "attributes")]
             modules,
         }
@@ -48,9 +50,13 @@ pub(crate) fn setup_objects(objects: &mut crate::OdbHandle, config: &crate::conf
                 }
             },
             Some(0) => objects.unset_pack_cache(),
-            Some(bytes) => objects.set_pack_cache(move || -> Box<gix_odb::cache::PackCache> {
-                Box::new(gix_pack::cache::lru::MemoryCappedHashmap::new(bytes))
-            }),
+            Some(_bytes) => {
+                let cache = config
+                    .shared_pack_cache
+                    .clone()
+                    .expect("always set for a non-zero amount of bytes");
+                objects.set_pack_cache(move || -> Box<gix_odb::cache::PackCache> { Box::new(cache.clone()) })
+            }
         };
         if config.object_cache_bytes == 0 {
             objects.unset_object_cache();
@@ -2,7 +2,6 @@ use crate::config::tree::{Key, Mailmap};
 use crate::Id;
 
 impl crate::Repository {
-    // TODO: tests
     /// Similar to [`open_mailmap_into()`][crate::Repository::open_mailmap_into()], but ignores all errors and returns at worst
     /// an empty mailmap, e.g. if there is no mailmap or if there were errors loading them.
     ///
@@ -14,7 +13,6 @@ impl crate::Repository {
         out
     }
 
-    // TODO: tests
     /// Try to merge mailmaps from the following locations into `target`:
     ///
     /// - read the `.mailmap` file without following symlinks from the working tree, if present
@@ -22,6 +20,9 @@ impl crate::Repository {
     /// - read the mailmap as configured in `mailmap.blob`, if set.
     /// - read the file as configured by `mailmap.file`, following symlinks, if set.
     ///
+    /// Like in git, entries of later sources override the ones of earlier sources, so `mailmap.blob` takes precedence
+    /// over the `.mailmap` file in the working tree, and `mailmap.file` takes precedence over both.
+    ///
     /// Only the first error will be reported, and as many source mailmaps will be merged into `target` as possible.
     /// Parsing errors will be ignored.
     pub fn open_mailmap_into(&self, target: &mut gix_mailmap::Snapshot) -> Result<(), crate::mailmap::load::Error> {
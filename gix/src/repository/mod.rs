@@ -39,6 +39,10 @@ impl crate::Repository {
 #[cfg(any(feature = "attributes", feature = "excludes"))]
 pub mod attributes;
 mod cache;
+#[cfg(feature = "merge")]
+mod cherry_pick;
+#[cfg(feature = "dirwalk")]
+mod clean;
 mod config;
 ///
 #[allow(clippy::empty_docs)]
@@ -53,6 +57,8 @@ mod dirwalk;
 #[cfg(feature = "attributes")]
 pub mod filter;
 mod graph;
+#[cfg(feature = "hooks")]
+mod hooks;
 pub(crate) mod identity;
 mod impls;
 #[cfg(feature = "index")]
@@ -67,9 +73,16 @@ mod object;
 mod pathspec;
 mod reference;
 mod remote;
+mod repack;
+#[cfg(feature = "worktree-mutation")]
+mod reset;
+#[cfg(feature = "merge")]
+mod revert;
 #[cfg(feature = "revision")]
 mod revision;
 mod shallow;
+#[cfg(feature = "signing")]
+mod signing;
 mod state;
 #[cfg(feature = "attributes")]
 mod submodule;
@@ -185,9 +198,67 @@ pub mod worktree_stream {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod repack_loose_objects {
+    /// The error returned by [`Repository::repack_loose_objects()`](crate::Repository::repack_loose_objects()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The value to configure pack threads should be 0 to auto-configure or the amount of threads to use")]
+        PackThreads(#[from] crate::config::unsigned_integer::Error),
+        #[error("The value to configure the pack index version should be 1 or 2")]
+        PackIndexVersion(#[from] crate::config::key::GenericError),
+        #[error(transparent)]
+        Repack(#[from] gix_odb::loose::repack::Error),
+    }
+
+    /// The options for use in [`Repository::repack_loose_objects()`](crate::Repository::repack_loose_objects()).
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct Options {
+        /// If `true`, remove all loose objects that were written into the new pack once the pack is in place.
+        pub prune: bool,
+    }
+
+    /// The outcome of [`Repository::repack_loose_objects()`](crate::Repository::repack_loose_objects()).
+    pub type Outcome = gix_odb::loose::repack::Outcome;
+}
+
 ///
 #[cfg(feature = "worktree-archive")]
 pub mod worktree_archive {
     /// The error returned by [`Repository::worktree_archive()`][crate::Repository::worktree_archive()].
     pub type Error = gix_archive::Error;
 }
+
+///
+#[allow(clippy::empty_docs)]
+pub mod find_prefix {
+    use gix_hash::ObjectId;
+
+    /// The maximum amount of candidates returned in [`Outcome::Ambiguous`].
+    pub const MAX_AMBIGUOUS_CANDIDATES: usize = 16;
+
+    /// The error returned by [`Repository::find_prefix()`][crate::Repository::find_prefix()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        InvalidPrefix(#[from] gix_hash::prefix::from_hex::Error),
+        #[error(transparent)]
+        LookupPrefix(#[from] gix_odb::store::prefix::lookup::Error),
+        #[error(transparent)]
+        FindHeader(#[from] crate::object::find::existing::Error),
+    }
+
+    /// The outcome of [`Repository::find_prefix()`][crate::Repository::find_prefix()].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Outcome {
+        /// Exactly one object matched the prefix.
+        Single(ObjectId),
+        /// Multiple objects matched the prefix, sorted by id and truncated to [`MAX_AMBIGUOUS_CANDIDATES`].
+        Ambiguous(Vec<ObjectId>),
+        /// No object matched the prefix.
+        NotFound,
+    }
+}
@@ -75,6 +75,62 @@ impl crate::Repository {
         }
     }
 
+    /// Return the shortest hexadecimal prefix of `id` that is unambiguous within the object database, with
+    /// at least as many characters as configured by `core.abbrev`, or as derived from the amount of packed objects
+    /// if it isn't set, similar to how `git` abbreviates object ids.
+    ///
+    /// The full id is returned if no shorter prefix is unambiguous, and it's an error if `id` doesn't exist.
+    /// Use [`Id::shorten_or_id()`] to obtain the full id instead of an error.
+    #[doc(alias = "abbrev", alias = "git2")]
+    pub fn shorten_id(&self, id: impl Into<ObjectId>) -> Result<String, crate::id::shorten::Error> {
+        id.into().attach(self).shorten().map(|prefix| prefix.to_string())
+    }
+
+    /// Find the object whose id starts with the hexadecimal `prefix`, which must be at least 4 characters long,
+    /// or report all candidates if it is ambiguous, similar to how `git rev-parse` resolves short ids.
+    ///
+    /// If `kind` is set, only objects of that kind are considered, which allows to disambiguate prefixes
+    /// shared by objects of different kinds.
+    ///
+    /// # Performance
+    ///
+    /// All pack indices are loaded and searched using their sorted ids, while loose objects are listed
+    /// only from the directory matching the first byte of `prefix`.
+    /// If `kind` is set, the header of each candidate needs to be obtained as well.
+    pub fn find_prefix(
+        &self,
+        prefix: &str,
+        kind: Option<gix_object::Kind>,
+    ) -> Result<crate::repository::find_prefix::Outcome, crate::repository::find_prefix::Error> {
+        use crate::repository::find_prefix::{Outcome, MAX_AMBIGUOUS_CANDIDATES};
+        let prefix = gix_hash::Prefix::from_hex(prefix)?;
+        let mut candidates = std::collections::HashSet::default();
+        if self.objects.lookup_prefix(prefix, Some(&mut candidates))?.is_none() {
+            return Ok(Outcome::NotFound);
+        }
+        let mut candidates: Vec<_> = match kind {
+            Some(kind) => {
+                let mut matching = Vec::with_capacity(candidates.len());
+                for id in candidates {
+                    if self.find_header(id)?.kind() == kind {
+                        matching.push(id);
+                    }
+                }
+                matching
+            }
+            None => candidates.into_iter().collect(),
+        };
+        Ok(match candidates.len() {
+            0 => Outcome::NotFound,
+            1 => Outcome::Single(candidates[0]),
+            _ => {
+                candidates.sort();
+                candidates.truncate(MAX_AMBIGUOUS_CANDIDATES);
+                Outcome::Ambiguous(candidates)
+            }
+        })
+    }
+
     /// Obtain information about an object without fully decoding it, or `None` if the object doesn't exist.
     ///
     /// Note that despite being cheaper than [`Self::try_find_object()`], there is still some effort traversing delta-chains.
@@ -201,6 +257,11 @@ impl crate::Repository {
     ///
     /// It will be created with `constraint` which is most commonly to [only create it][PreviousValue::MustNotExist]
     /// or to [force overwriting a possibly existing tag](PreviousValue::Any).
+    ///
+    /// If `tag.gpgSign` is enabled and the `signing` feature is active, the tag is signed according to
+    /// the [signing options](crate::Repository::signing_options()).
+    ///
+    /// Like `git tag`, names starting with `-` are rejected.
     #[momo]
     pub fn tag(
         &self,
@@ -211,14 +272,89 @@ impl crate::Repository {
         message: impl AsRef<str>,
         constraint: PreviousValue,
     ) -> Result<Reference<'_>, tag::Error> {
+        self.tag_inner(
+            name.as_ref(),
+            target.as_ref(),
+            target_kind,
+            tagger,
+            message.as_ref(),
+            constraint,
+            None,
+        )
+    }
+
+    /// Create an annotated tag named `name` (without `refs/tags/` prefix) for `target` with `tagger` and `message`,
+    /// similar to [`tag()`](Self::tag()), but look up the kind of object `target` is, and configure the operation
+    /// with `options`.
+    ///
+    /// Unless [forced](tag::Options::force), the operation fails if a tag named `name` exists already.
+    /// The tag is [signed](tag::Options::sign) if `tag.gpgSign` is enabled, unless overridden by `options`.
+    /// Use [`tag_reference()`](Self::tag_reference()) to create a lightweight tag instead.
+    #[momo]
+    pub fn tag_opts(
+        &self,
+        name: impl AsRef<str>,
+        target: impl Into<ObjectId>,
+        tagger: Option<gix_actor::SignatureRef<'_>>,
+        message: impl AsRef<str>,
+        options: tag::Options,
+    ) -> Result<Reference<'_>, tag::Error> {
+        let target = target.into();
+        let target_kind = self.find_header(target)?.kind();
+        self.tag_inner(
+            name.as_ref(),
+            &target,
+            target_kind,
+            tagger,
+            message.as_ref(),
+            if options.force {
+                PreviousValue::Any
+            } else {
+                PreviousValue::MustNotExist
+            },
+            options.sign,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn tag_inner(
+        &self,
+        name: &str,
+        target: &gix_hash::oid,
+        target_kind: gix_object::Kind,
+        tagger: Option<gix_actor::SignatureRef<'_>>,
+        message: &str,
+        constraint: PreviousValue,
+        sign: Option<bool>,
+    ) -> Result<Reference<'_>, tag::Error> {
+        if name.starts_with('-') {
+            return Err(tag::Error::InvalidName { name: name.into() });
+        }
+        // Validate early as invalid names can't be encoded.
+        let _ = FullName::try_from(format!("refs/tags/{name}"))?;
         let tag = gix_object::Tag {
-            target: target.as_ref().into(),
+            target: target.into(),
             target_kind,
-            name: name.as_ref().into(),
+            name: name.into(),
             tagger: tagger.map(|t| t.to_owned()),
-            message: message.as_ref().into(),
+            message: message.into(),
             pgp_signature: None,
         };
+        #[cfg(feature = "signing")]
+        let tag = gix_object::Tag {
+            // The signature is separated from the message by a newline, which is part of the signed data.
+            pgp_signature: self.sign_object_if_configured::<tag::Error>(
+                &crate::config::tree::Tag::GPG_SIGN,
+                sign,
+                &tag,
+                b"\n",
+            )?,
+            ..tag
+        };
+        #[cfg(not(feature = "signing"))]
+        if sign == Some(true) {
+            return Err(tag::Error::SigningUnavailable);
+        }
         let tag_id = self.write_object(&tag)?;
         self.tag_reference(name, tag_id, constraint).map_err(Into::into)
     }
@@ -246,9 +382,43 @@ impl crate::Repository {
             message.as_ref(),
             tree.into(),
             parents.into_iter().map(Into::into).collect(),
+            commit::Options {
+                allow_empty: true,
+                no_verify: false,
+            },
         )
     }
 
+    /// Like [`commit_as(…)`](Self::commit_as()), but configure the operation with `options`.
+    ///
+    /// Particularly, by default it fails if the commit has the same tree as its only parent, as there would be no changes.
+    #[allow(clippy::too_many_arguments)]
+    pub fn commit_as_opts<'a, 'c, Name, E>(
+        &self,
+        committer: impl Into<gix_actor::SignatureRef<'c>>,
+        author: impl Into<gix_actor::SignatureRef<'a>>,
+        reference: Name,
+        message: impl AsRef<str>,
+        tree: impl Into<ObjectId>,
+        parents: impl IntoIterator<Item = impl Into<ObjectId>>,
+        options: commit::Options,
+    ) -> Result<Id<'_>, commit::Error>
+    where
+        Name: TryInto<FullName, Error = E>,
+        commit::Error: From<E>,
+    {
+        self.commit_as_inner(
+            committer.into(),
+            author.into(),
+            reference.try_into()?,
+            message.as_ref(),
+            tree.into(),
+            parents.into_iter().map(Into::into).collect(),
+            options,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn commit_as_inner(
         &self,
         committer: gix_actor::SignatureRef<'_>,
@@ -257,16 +427,35 @@ impl crate::Repository {
         message: &str,
         tree: ObjectId,
         parents: SmallVec<[ObjectId; 1]>,
+        commit::Options {
+            allow_empty,
+            #[cfg_attr(not(feature = "hooks"), allow(unused_variables))]
+            no_verify,
+        }: commit::Options,
     ) -> Result<Id<'_>, commit::Error> {
         use gix_ref::{
             transaction::{Change, RefEdit},
             Target,
         };
 
+        if let (false, [parent]) = (allow_empty, parents.as_slice()) {
+            if self.find_object(*parent)?.to_commit_ref_iter().tree_id()? == tree {
+                return Err(commit::Error::Empty { tree, parent: *parent });
+            }
+        }
+
+        let message = crate::bstr::BString::from(message);
+        #[cfg(feature = "hooks")]
+        let message = if no_verify {
+            message
+        } else {
+            self.run_commit_hooks(message.as_ref())?
+        };
+
         // TODO: possibly use CommitRef to save a few allocations (but will have to allocate for object ids anyway.
         //       This can be made vastly more efficient though if we wanted to, so we lie in the API
         let commit = gix_object::Commit {
-            message: message.into(),
+            message,
             tree,
             author: author.into(),
             committer: committer.into(),
@@ -274,6 +463,18 @@ impl crate::Repository {
             parents,
             extra_headers: Default::default(),
         };
+        #[cfg(feature = "signing")]
+        let commit = gix_object::Commit {
+            // Like in `git`, the header doesn't retain the trailing newline of the signature.
+            extra_headers: self
+                .sign_object_if_configured::<commit::Error>(&crate::config::tree::Commit::GPG_SIGN, None, &commit, &[])?
+                .map(|signature| {
+                    use crate::bstr::ByteSlice;
+                    vec![("gpgsig".into(), signature.trim_end().into())]
+                })
+                .unwrap_or_default(),
+            ..commit
+        };
 
         let commit_id = self.write_object(&commit)?;
         self.edit_reference(RefEdit {
@@ -314,6 +515,14 @@ impl crate::Repository {
     /// If there is no parent, the `reference` is expected to not exist yet.
     ///
     /// The method fails immediately if a `reference` lock can't be acquired.
+    ///
+    /// If `commit.gpgSign` is enabled and the `signing` feature is active, the commit is signed according to
+    /// the [signing options](crate::Repository::signing_options()), with the signature stored in its `gpgsig` header.
+    ///
+    /// If the `hooks` feature is active, the `pre-commit` and `commit-msg` [hooks](crate::hooks) run before the commit
+    /// is created, and the commit isn't created if one of them fails. The message is the one left by `commit-msg`,
+    /// which receives it in the `COMMIT_EDITMSG` file in the `git` directory. Use [`commit::Options::no_verify`] with
+    /// [`commit_as_opts()`](Self::commit_as_opts()) to skip them.
     pub fn commit<Name, E>(
         &self,
         reference: Name,
@@ -0,0 +1,63 @@
+use std::sync::atomic::AtomicBool;
+
+use gix_hash::ObjectId;
+
+use crate::config::{cache::util::ApplyLeniency, tree::Pack};
+use crate::repository::repack_loose_objects::{Error, Options, Outcome};
+use crate::Repository;
+
+impl Repository {
+    /// Write the loose objects of this repository into a single new pack, similar to `git repack -d` for loose objects,
+    /// reporting to `progress` and stopping with an error once `should_interrupt` is set.
+    ///
+    /// If `tips` is `Some`, only loose objects reachable from them are packed. Otherwise, all loose objects are.
+    /// The amount of threads and the version of the pack index are configured by `pack.threads` and
+    /// `pack.indexVersion`.
+    ///
+    /// The new pack is only moved into place once it's complete, and loose objects are only removed afterwards,
+    /// so all objects remain accessible at all times, even if the operation is interrupted.
+    pub fn repack_loose_objects(
+        &self,
+        tips: Option<&[ObjectId]>,
+        progress: &mut dyn crate::DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Outcome, Error> {
+        let _span = gix_trace::coarse!("gix::repack_loose_objects()");
+        let thread_limit = self
+            .config
+            .resolved
+            .integer_filter(Pack::THREADS, &mut self.filter_config_section())
+            .map(|threads| Pack::THREADS.try_into_usize(threads))
+            .transpose()
+            .with_leniency(self.options.lenient_config)?;
+        let index_version = self
+            .config
+            .resolved
+            .integer(Pack::INDEX_VERSION)
+            .map(|value| Pack::INDEX_VERSION.try_into_index_version(value))
+            .transpose()
+            .with_leniency(self.options.lenient_config)?
+            .unwrap_or(gix_pack::index::Version::V2);
+
+        let loose = gix_odb::loose::Store::at(self.objects.store_ref().path(), self.object_hash());
+        let objects = self.objects.clone();
+        let selection = match tips {
+            Some(tips) => gix_odb::loose::repack::Selection::ReachableFrom {
+                tips,
+                objects: &objects,
+            },
+            None => gix_odb::loose::repack::Selection::All,
+        };
+        Ok(loose.repack(
+            selection,
+            progress,
+            should_interrupt,
+            gix_odb::loose::repack::Options {
+                prune: options.prune,
+                thread_limit,
+                index_version,
+            },
+        )?)
+    }
+}
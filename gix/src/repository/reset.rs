@@ -0,0 +1,262 @@
+use std::sync::atomic::AtomicBool;
+
+use gix_hash::ObjectId;
+use gix_index::entry::Stage;
+use gix_ref::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
+use gix_ref::Target;
+
+use crate::bstr::{BStr, ByteSlice};
+use crate::reset::{Error, Mode};
+use crate::Repository;
+
+/// The files recording the state of a merge, cherry-pick or revert in progress, which are removed by a reset.
+const BRANCH_STATE_FILES: &[&str] = &[
+    "MERGE_HEAD",
+    "MERGE_MSG",
+    "MERGE_MODE",
+    "MERGE_RR",
+    "AUTO_MERGE",
+    "CHERRY_PICK_HEAD",
+    "REVERT_HEAD",
+];
+
+impl Repository {
+    /// Set `HEAD`, or the branch it points to, to the commit `target` and reset the index and the worktree to it as
+    /// indicated by `mode`, similar to `git reset --<mode> <target>`.
+    ///
+    /// The change is recorded in the reflog as `reset: moving to <target>`, and the commit `HEAD` pointed to before,
+    /// if there is one, is written to `ORIG_HEAD`. The state of a merge, cherry-pick or revert in progress is removed.
+    /// Note that a [hard](Mode::Hard) reset overwrites all changes of tracked files, while untracked files are kept
+    /// unless a file of `target` is in their way. Submodules are left as they are.
+    pub fn reset(&self, target: impl Into<ObjectId>, mode: Mode) -> Result<(), Error> {
+        let _span = gix_trace::coarse!("gix::reset");
+        if mode == Mode::Soft && self.git_dir().join("MERGE_HEAD").is_file() {
+            return Err(Error::SoftResetDuringMerge);
+        }
+        if mode != Mode::Soft && self.work_dir().is_none() {
+            return Err(Error::MissingWorkDir { mode });
+        }
+        let target = target.into();
+        let commit = self.find_object(target)?.peel_to_kind(gix_object::Kind::Commit)?;
+        let commit_id = commit.id;
+
+        if mode != Mode::Soft {
+            let previous = self.index_or_empty()?;
+            let mut index = self.index_from_tree(&commit.peel_to_tree()?.id)?;
+            if mode == Mode::Hard {
+                self.reset_worktree(&previous, &mut index)?;
+            } else {
+                for (entry, rela_path) in index.entries_mut_with_paths() {
+                    if let Some(previous) = unchanged_entry(&previous, rela_path, entry) {
+                        entry.stat = previous.stat;
+                    }
+                }
+            }
+            index.write(Default::default())?;
+        }
+
+        let mut edits = Vec::new();
+        if let Some(previous_head) = self.head()?.id() {
+            edits.push(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: "updating ORIG_HEAD".into(),
+                    },
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(previous_head.detach()),
+                },
+                name: "ORIG_HEAD".try_into().expect("valid"),
+                deref: false,
+            });
+        }
+        edits.push(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: format!("reset: moving to {target}").into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Peeled(commit_id),
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: true,
+        });
+        self.edit_references(edits)?;
+
+        for name in BRANCH_STATE_FILES {
+            match std::fs::remove_file(self.git_dir().join(name)) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Reset the index entries of all paths matching `patterns` to the ones in the tree of `target`, similar to
+    /// `git reset <target> -- <patterns>`, and remove the entries of matching paths that don't exist there.
+    ///
+    /// `HEAD` and the worktree are left as they are.
+    pub fn reset_paths(
+        &self,
+        target: impl Into<ObjectId>,
+        patterns: impl IntoIterator<Item = impl AsRef<BStr>>,
+    ) -> Result<(), Error> {
+        let _span = gix_trace::coarse!("gix::reset_paths");
+        let tree = self.find_object(target.into())?.peel_to_tree()?;
+        let target = self.index_from_tree(&tree.id)?;
+        let mut index: gix_index::File = (**self.index_or_empty()?).clone();
+        let mut pathspec = self.pathspec(
+            true,
+            patterns,
+            false,
+            &index,
+            gix_worktree::stack::state::attributes::Source::IdMapping,
+        )?;
+
+        let mut removed = Vec::new();
+        index.remove_entries(|_, rela_path, entry| {
+            let is_match = pathspec.is_included(rela_path, Some(entry.mode.is_sparse()));
+            if is_match {
+                removed.push((rela_path.to_owned(), entry.clone()));
+            }
+            is_match
+        });
+        let mut changed = Vec::new();
+        for entry in target.entries() {
+            let rela_path = entry.path(&target);
+            if !pathspec.is_included(rela_path, Some(false)) {
+                continue;
+            }
+            let previous = removed.iter().find(|(path, previous)| {
+                path == rela_path
+                    && previous.stage() == Stage::Unconflicted
+                    && previous.id == entry.id
+                    && previous.mode == entry.mode
+            });
+            if previous.is_none() {
+                changed.push(rela_path.to_owned());
+            }
+            index.dangerously_push_entry(
+                previous.map(|(_, previous)| previous.stat).unwrap_or_default(),
+                entry.id,
+                gix_index::entry::Flags::empty(),
+                entry.mode,
+                rela_path,
+            );
+        }
+        changed.extend(removed.into_iter().filter_map(|(rela_path, entry)| {
+            target
+                .entry_by_path_and_stage(rela_path.as_bstr(), Stage::Unconflicted)
+                .filter(|new| entry.stage() == Stage::Unconflicted && new.id == entry.id && new.mode == entry.mode)
+                .is_none()
+                .then_some(rela_path)
+        }));
+        index.sort_entries();
+        if let Some(tree) = index.tree_mut() {
+            for rela_path in &changed {
+                tree.invalidate_path(rela_path.as_bstr());
+            }
+        }
+        index.write(Default::default())?;
+        Ok(())
+    }
+
+    /// Make the worktree, which is assumed to be checked out from the `previous` index, match `index`, and update the
+    /// file stats of `index` accordingly.
+    fn reset_worktree(&self, previous: &gix_index::State, index: &mut gix_index::State) -> Result<(), Error> {
+        let workdir = self.work_dir().expect("checked by caller");
+        let remove_file = |rela_path: &BStr| -> std::io::Result<()> {
+            match std::fs::remove_file(workdir.join(gix_path::from_bstr(rela_path))) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            }
+        };
+        for entry in previous.entries() {
+            let rela_path = entry.path(previous);
+            if entry.mode.is_submodule() || entry.mode.is_sparse() || index.entry_by_path(rela_path).is_some() {
+                continue;
+            }
+            remove_file(rela_path)?;
+            let path = workdir.join(gix_path::from_bstr(rela_path));
+            let mut dir = path.parent();
+            while let Some(parent) = dir.filter(|dir| *dir != workdir) {
+                if std::fs::remove_dir(parent).is_err() {
+                    break;
+                }
+                dir = parent.parent();
+            }
+        }
+
+        let stat_options = self.stat_options()?;
+        let fs = self.filesystem_options()?;
+        let timestamp = previous.timestamp();
+        let mut to_checkout = gix_index::State::new(self.object_hash());
+        for (entry, rela_path) in index.entries_mut_with_paths() {
+            if entry.mode.is_submodule() {
+                continue;
+            }
+            let path = workdir.join(gix_path::from_bstr(rela_path));
+            let stat = unchanged_entry(previous, rela_path, entry).and_then(|previous| {
+                let metadata = gix_index::fs::Metadata::from_path_no_follow(&path).ok()?;
+                let stat = gix_index::entry::Stat::from_fs(&metadata).ok()?;
+                (previous
+                    .mode
+                    .change_to_match_fs(&metadata, fs.symlink, fs.executable_bit)
+                    .is_none()
+                    && previous.stat.matches(&stat, stat_options)
+                    && !previous.stat.is_racy(timestamp, stat_options))
+                .then_some(previous.stat)
+            });
+            match stat {
+                Some(stat) => entry.stat = stat,
+                None => {
+                    // Files are overwritten, but not truncated, by the checkout, so they need to be removed first.
+                    if path.symlink_metadata().is_ok_and(|metadata| !metadata.is_dir()) {
+                        remove_file(rela_path)?;
+                    }
+                    to_checkout.dangerously_push_entry(
+                        Default::default(),
+                        entry.id,
+                        gix_index::entry::Flags::empty(),
+                        entry.mode,
+                        rela_path,
+                    );
+                }
+            }
+        }
+
+        let mut options = self
+            .config
+            .checkout_options(self, gix_worktree::stack::state::attributes::Source::IdMapping)?;
+        options.overwrite_existing = true;
+        gix_worktree_state::checkout(
+            &mut to_checkout,
+            workdir,
+            self.objects.clone().into_arc()?,
+            &gix_features::progress::Discard,
+            &gix_features::progress::Discard,
+            &AtomicBool::default(),
+            options,
+        )?;
+        for (rela_path, stat) in to_checkout.entries_with_paths_by_filter_map(|_, entry| Some(entry.stat)) {
+            if let Some(entry) = index.entry_mut_by_path_and_stage(rela_path, Stage::Unconflicted) {
+                entry.stat = stat;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Return the unconflicted entry of `previous` at `rela_path` if it has the same id and mode as `entry`.
+fn unchanged_entry<'a>(
+    previous: &'a gix_index::State,
+    rela_path: &BStr,
+    entry: &gix_index::Entry,
+) -> Option<&'a gix_index::Entry> {
+    previous
+        .entry_by_path_and_stage(rela_path, Stage::Unconflicted)
+        .filter(|previous| previous.id == entry.id && previous.mode == entry.mode)
+}
@@ -0,0 +1,66 @@
+use gix_hash::ObjectId;
+
+use crate::bstr::{BStr, ByteSlice};
+use crate::repository::cherry_pick::mainline_parent;
+use crate::revert::{Error, Options, Outcome};
+use crate::Repository;
+
+impl Repository {
+    /// Apply the inverse of the changes `commit` introduced compared to its first parent onto the commit `onto`,
+    /// similar to `git revert`, by merging them with the changes of `onto` while using the tree of `commit` as base.
+    ///
+    /// If there are no conflicts, a new commit with the merged tree is written whose only parent is `onto`, authored
+    /// and committed by the identities configured for this repository. Its message is like `Revert "<subject>"`,
+    /// followed by a paragraph naming the reverted commit.
+    /// Otherwise, the merged index with all conflicting sides is returned as [`Outcome::Conflicts`] without writing a
+    /// commit. Note that neither references nor the index or the worktree are changed in either case.
+    ///
+    /// Use `options` to select the parent of a merge commit whose changes should be reverted.
+    pub fn revert(
+        &self,
+        commit: impl Into<ObjectId>,
+        onto: impl Into<ObjectId>,
+        options: Options,
+    ) -> Result<Outcome<'_>, Error> {
+        let _span = gix_trace::coarse!("gix::revert");
+        let id = commit.into();
+        let commit = self.find_object(id)?.try_into_commit()?;
+        let parent = mainline_parent(&commit, options.mainline)?;
+        let subject = commit.message()?.summary();
+        let label = format!("parent of {} ({subject})", commit.id().shorten_or_id());
+        let onto = onto.into();
+        let tree = match self.merge_commit_changes(&commit, parent, onto, true, &label)? {
+            Ok(tree) => tree,
+            Err(outcome) => return Ok(outcome),
+        };
+
+        let mut message = revert_subject(subject.as_ref());
+        message.push_str(&format!("\n\nThis reverts commit {id}"));
+        if let Some(parent) = parent.filter(|_| commit.parent_ids().nth(1).is_some()) {
+            message.push_str(&format!(", reversing\nchanges made to {parent}"));
+        }
+        message.push_str(".\n");
+        let new_commit = gix_object::Commit {
+            message: message.into(),
+            tree,
+            author: self.author().ok_or(Error::AuthorMissing)??.into(),
+            committer: self.committer().ok_or(Error::CommitterMissing)??.into(),
+            encoding: None,
+            parents: vec![onto].into(),
+            extra_headers: Default::default(),
+        };
+        Ok(Outcome::Commit(self.write_object(&new_commit)?))
+    }
+}
+
+/// Return the subject of a commit reverting a commit with `subject`, which reapplies the original commit if `subject`
+/// is the one of a revert itself, just like `git revert` does.
+fn revert_subject(subject: &BStr) -> String {
+    match subject
+        .strip_prefix(b"Revert \"")
+        .filter(|reverted| !reverted.starts_with(b"Revert \""))
+    {
+        Some(reverted) => format!("Reapply \"{}", reverted.as_bstr()),
+        None => format!("Revert \"{subject}\""),
+    }
+}
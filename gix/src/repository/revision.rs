@@ -45,4 +45,10 @@ impl crate::Repository {
     ) -> revision::walk::Platform<'_> {
         revision::walk::Platform::new(tips, self)
     }
+
+    /// Return a platform to find the first commit that introduced a change by bisecting the history between good and
+    /// bad commits, similar to `git bisect`.
+    pub fn bisect(&self) -> crate::bisect::Platform<'_> {
+        crate::bisect::Platform { repo: self }
+    }
 }
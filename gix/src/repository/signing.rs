@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+use crate::{
+    bstr::{BString, ByteVec},
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{gpg, Gpg, Key, User},
+    },
+    signing,
+};
+
+/// Signing
+impl crate::Repository {
+    /// Return the options to [create](signing::sign()) and [verify](signing::verify()) signatures as configured by
+    /// `gpg.format`, `gpg.program`, `gpg.<format>.program`, `gpg.ssh.allowedSignersFile` and `user.signingKey`.
+    ///
+    /// Like in `git`, if there is no `user.signingKey`, OpenPGP and X.509 signatures are made with the key
+    /// of the [committer](crate::Repository::committer()).
+    pub fn signing_options(&self) -> Result<signing::Options, signing::options::Error> {
+        let format = match self.config.resolved.string(Gpg::FORMAT) {
+            Some(value) => Gpg::FORMAT.try_into_format(value)?,
+            None => signing::Format::default(),
+        };
+        let config = self.config_snapshot();
+        let signing_key = self
+            .config
+            .resolved
+            .string(User::SIGNING_KEY)
+            .map(Cow::into_owned)
+            .or_else(|| {
+                (format != signing::Format::Ssh)
+                    .then(|| self.committer()?.ok())
+                    .flatten()
+                    .map(|committer| {
+                        let mut key = BString::from(committer.name);
+                        key.push_str(" <");
+                        key.push_str(committer.email);
+                        key.push_str(">");
+                        key
+                    })
+            });
+        let allowed_signers_file = config
+            .trusted_path(gpg::Ssh::ALLOWED_SIGNERS_FILE.logical_name().as_str())
+            .transpose()?
+            .map(Cow::into_owned);
+        Ok(signing::Options {
+            format,
+            openpgp_program: config
+                .trusted_program(gpg::OpenPgp::PROGRAM.logical_name().as_str())
+                .or_else(|| config.trusted_program(Gpg::PROGRAM.logical_name().as_str()))
+                .map(Cow::into_owned),
+            x509_program: config
+                .trusted_program(gpg::X509::PROGRAM.logical_name().as_str())
+                .map(Cow::into_owned),
+            ssh_program: config
+                .trusted_program(gpg::Ssh::PROGRAM.logical_name().as_str())
+                .map(Cow::into_owned),
+            signing_key,
+            allowed_signers_file,
+        })
+    }
+}
+
+impl crate::Repository {
+    /// Sign the `object` if `gpg_sign` is enabled, or if `sign` is `Some(true)` to override the configuration,
+    /// and return the signature of its serialized form with `suffix` appended, or `None` if it shouldn't be signed.
+    pub(crate) fn sign_object_if_configured<E>(
+        &self,
+        gpg_sign: &'static crate::config::tree::keys::Boolean,
+        sign: Option<bool>,
+        object: &dyn gix_object::WriteTo,
+        suffix: &[u8],
+    ) -> Result<Option<BString>, E>
+    where
+        E: From<crate::config::boolean::Error> + From<signing::options::Error> + From<signing::sign::Error>,
+    {
+        let enabled = match sign {
+            Some(sign) => sign,
+            None => self
+                .config
+                .resolved
+                .boolean(gpg_sign)
+                .map(|res| gpg_sign.enrich_error(res))
+                .transpose()
+                .with_leniency(self.config.lenient_config)?
+                .unwrap_or_default(),
+        };
+        if !enabled {
+            return Ok(None);
+        }
+        let mut payload = Vec::with_capacity(object.size() as usize + suffix.len());
+        object.write_to(&mut payload).expect("write to memory works");
+        payload.extend_from_slice(suffix);
+        Ok(Some(signing::sign(&payload, &self.signing_options()?)?))
+    }
+}
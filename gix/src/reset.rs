@@ -0,0 +1,52 @@
+//! Reset `HEAD`, the index and the worktree to a commit, similar to `git reset`.
+
+/// The error returned by [Repository::reset()](crate::Repository::reset()) and
+/// [Repository::reset_paths()](crate::Repository::reset_paths()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Cannot do a soft reset in the middle of a merge")]
+    SoftResetDuringMerge,
+    #[error("A worktree is required for a {mode:?} reset")]
+    MissingWorkDir { mode: Mode },
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelObject(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+    #[error(transparent)]
+    Pathspec(#[from] crate::pathspec::init::Error),
+    #[error(transparent)]
+    StatOptions(#[from] crate::config::stat_options::Error),
+    #[error(transparent)]
+    FilesystemOptions(#[from] crate::config::boolean::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error("Could not update the worktree or remove the state of an operation in progress")]
+    Io(#[from] std::io::Error),
+}
+
+/// What to reset along with `HEAD`, for use in [Repository::reset()](crate::Repository::reset()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Mode {
+    /// Only move `HEAD`, and keep the index and the worktree as they are, like `--soft`.
+    Soft,
+    /// Move `HEAD` and replace the index with the tree of the target commit, while keeping the worktree as it is,
+    /// like `--mixed`. This is the default of `git reset`.
+    #[default]
+    Mixed,
+    /// Move `HEAD`, replace the index with the tree of the target commit, and make the worktree match it, like
+    /// `--hard`. All changes of tracked files are lost, and tracked files that aren't in the target commit are removed.
+    Hard,
+}
@@ -0,0 +1,11 @@
+//! Apply the inverse of the changes of a single commit onto another one, similar to `git revert`.
+pub use crate::cherry_pick::{Error, Outcome};
+
+/// Options for use in [Repository::revert()](crate::Repository::revert()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The number of the parent whose changes of a merge commit should be reverted, starting at `1`, like `--mainline`.
+    ///
+    /// It must be set to revert a merge commit, and may only be `1` for commits with a single parent.
+    pub mainline: Option<usize>,
+}
@@ -0,0 +1,240 @@
+//! Limit the commits of a revision walk to those that changed paths matching a pathspec, similar to `git log -- <path>`.
+use gix_hash::ObjectId;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    diff::Rewrites,
+    object::tree::diff::{change::Event, Action},
+    revision, Pathspec, Tree,
+};
+
+/// The error returned by [`History::follow()`] and the [`History`] iterator.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Following renames requires exactly one pathspec that is the path to a file")]
+    FollowNeedsSinglePath,
+    #[error(transparent)]
+    Walk(#[from] gix_traverse::commit::simple::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] crate::object::commit::Error),
+    #[error(transparent)]
+    DiffRenamesConfig(#[from] crate::diff::new_rewrites::Error),
+    #[error(transparent)]
+    TreeDiff(#[from] crate::object::tree::diff::for_each::Error),
+}
+
+/// An iterator over the commits of a [revision walk](revision::Walk) that changed any of the paths matched by a pathspec,
+/// as returned by [`Walk::with_pathspec()`](revision::Walk::with_pathspec()).
+///
+/// Each commit is compared to its first parent, and only returned if files matching the pathspec differ between them.
+/// Root commits are returned if they contain any such file. With [merge simplification](History::simplify_merges()),
+/// merges are only skipped if they don't differ from any of their parents.
+///
+/// Note that the walk itself isn't pruned, so all commits are traversed and diffed even if they can't change the result.
+pub struct History<'repo> {
+    walk: revision::Walk<'repo>,
+    pathspec: Pathspec<'repo>,
+    simplify_merges: bool,
+    /// The path of the file we currently follow across renames detected with the given rewrite configuration.
+    follow: Option<(BString, Rewrites)>,
+    buf: Vec<u8>,
+}
+
+impl<'repo> revision::Walk<'repo> {
+    /// Only return commits that changed files matching `pathspec`, similar to `git log -- <pathspec>`.
+    pub fn with_pathspec(self, pathspec: Pathspec<'repo>) -> History<'repo> {
+        History {
+            walk: self,
+            pathspec,
+            simplify_merges: true,
+            follow: None,
+            buf: Vec::new(),
+        }
+    }
+}
+
+/// Builder methods
+impl<'repo> History<'repo> {
+    /// If `toggle` is `true` (the default), merges are compared to all of their parents and are only skipped if all matching
+    /// files are the same as in *every* parent, which is when `git` considers them `TREESAME`, similar to
+    /// `git log --full-history -- <pathspec>`.
+    ///
+    /// If `false`, merges are compared to their first parent only, like all other commits, which is what
+    /// `git log --first-parent -- <pathspec>` does in a walk [limited to first parents](revision::walk::Platform::first_parent_only()).
+    pub fn simplify_merges(mut self, toggle: bool) -> Self {
+        self.simplify_merges = toggle;
+        self
+    }
+
+    /// Follow the single file matched by our pathspec across renames, similar to `git log --follow -- <file>`,
+    /// with renames detected as configured by `diff.renames`, or with the default [`Rewrites`] if rename tracking
+    /// is turned off.
+    ///
+    /// This fails if the pathspec doesn't consist of exactly one path without wildcards.
+    ///
+    /// Just like `git`, only one path is followed, which is changed to the source of a rename whenever a commit that
+    /// renamed the file is returned. Thus, this works best for linear histories.
+    /// Merges are never returned while following a file, as `git` doesn't show them either unless asked to diff merges.
+    pub fn follow(mut self) -> Result<Self, Error> {
+        let path = {
+            let mut patterns = self.pathspec.search().patterns();
+            match (patterns.next(), patterns.next()) {
+                (Some(pattern), None)
+                    if !pattern.is_excluded()
+                        && !pattern.is_nil()
+                        && pattern.attributes.is_empty()
+                        && (pattern.search_mode == gix_pathspec::SearchMode::Literal
+                            || !pattern.path().iter().any(|b| matches!(b, b'*' | b'?' | b'['))) =>
+                {
+                    pattern.path().to_owned()
+                }
+                _ => return Err(Error::FollowNeedsSinglePath),
+            }
+        };
+        let rewrites = self.walk.repo.config.diff_renames()?.unwrap_or_default();
+        self.follow = Some((path, rewrites));
+        Ok(self)
+    }
+}
+
+/// Access
+impl<'repo> History<'repo> {
+    /// Return the path of the file that is currently followed across renames, if [following](History::follow()) is enabled.
+    pub fn followed_path(&self) -> Option<&BStr> {
+        self.follow.as_ref().map(|(path, _)| path.as_bstr())
+    }
+}
+
+impl<'repo> Iterator for History<'repo> {
+    type Item = Result<revision::walk::Info<'repo>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let info = match self.walk.next()? {
+                Ok(info) => info,
+                Err(err) => return Some(Err(err.into())),
+            };
+            match self.is_changed(&info) {
+                Ok(true) => return Some(Ok(info)),
+                Ok(false) => continue,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+impl<'repo> History<'repo> {
+    /// Return `true` if the commit of `info` changed any matching file, and switch the followed path if it was renamed.
+    fn is_changed(&mut self, info: &revision::walk::Info<'repo>) -> Result<bool, Error> {
+        let repo = self.walk.repo;
+        let tree = info.object()?.tree()?;
+        let mut parent_trees = info
+            .parent_ids
+            .iter()
+            .map(|id| repo.find_object(*id)?.into_commit().tree().map_err(Error::from));
+        let Some(first_parent_tree) = parent_trees.next().transpose()? else {
+            return self.differs(&repo.empty_tree(), &tree);
+        };
+        if self.follow.is_some() && info.parent_ids.len() > 1 {
+            return Ok(false);
+        }
+
+        let mut is_changed = self.differs(&first_parent_tree, &tree)?;
+        if !is_changed && self.simplify_merges {
+            for parent_tree in parent_trees {
+                if self.differs(&parent_tree?, &tree)? {
+                    is_changed = true;
+                    break;
+                }
+            }
+        }
+        if !is_changed {
+            return Ok(false);
+        }
+
+        if let Some((path, rewrites)) = self.follow.as_mut() {
+            if find_file(&first_parent_tree, path.as_ref(), &mut self.buf)?.is_none() {
+                if let Some(source_path) = find_rename_source(&first_parent_tree, &tree, path.as_ref(), *rewrites)? {
+                    *path = source_path;
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Return `true` if any file matching the pathspec, or the followed file, differs between `old` and `new`.
+    fn differs(&mut self, old: &Tree<'_>, new: &Tree<'_>) -> Result<bool, Error> {
+        if let Some((path, _)) = &self.follow {
+            let old = find_file(old, path.as_ref(), &mut self.buf)?;
+            let new = find_file(new, path.as_ref(), &mut self.buf)?;
+            return Ok(old != new);
+        }
+
+        let pathspec = &mut self.pathspec;
+        let res = old
+            .changes()?
+            .track_path()
+            .track_rewrites(None)
+            .for_each_to_obtain_tree(new, |change| {
+                let is_tree = change.event.entry_mode().is_tree();
+                Ok::<_, std::convert::Infallible>(if !is_tree && pathspec.is_included(change.location, Some(false)) {
+                    Action::Cancel
+                } else {
+                    Action::Continue
+                })
+            });
+        match res {
+            Ok(_) => Ok(false),
+            Err(crate::object::tree::diff::for_each::Error::Diff(gix_diff::tree::changes::Error::Cancelled)) => {
+                Ok(true)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Return the mode and id of the file at `path` in `tree`, or `None` if there is no such file.
+fn find_file(
+    tree: &Tree<'_>,
+    path: &BStr,
+    buf: &mut Vec<u8>,
+) -> Result<Option<(gix_object::tree::EntryMode, ObjectId)>, Error> {
+    Ok(tree
+        .lookup_entry(path.split_str("/"), buf)?
+        .filter(|entry| !entry.mode().is_tree())
+        .map(|entry| (entry.mode(), entry.object_id())))
+}
+
+/// Return the path of the file in `parent_tree` that was renamed to `path` in `tree`, if there is one.
+fn find_rename_source(
+    parent_tree: &Tree<'_>,
+    tree: &Tree<'_>,
+    path: &BStr,
+    rewrites: Rewrites,
+) -> Result<Option<BString>, Error> {
+    let mut source = None;
+    parent_tree
+        .changes()?
+        .track_path()
+        .track_rewrites(Some(Rewrites {
+            copies: None,
+            ..rewrites
+        }))
+        .for_each_to_obtain_tree(tree, |change| {
+            if let Event::Rewrite {
+                source_location,
+                copy: false,
+                ..
+            } = change.event
+            {
+                if change.location == path {
+                    source = Some(source_location.to_owned());
+                }
+            }
+            Ok::<_, std::convert::Infallible>(Action::Continue)
+        })?;
+    Ok(source)
+}
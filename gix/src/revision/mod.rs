@@ -10,6 +10,13 @@ pub use gix_revision as plumbing;
 pub mod walk;
 pub use walk::iter::Walk;
 
+///
+#[cfg(feature = "blob-diff")]
+#[allow(clippy::empty_docs)]
+pub mod history;
+#[cfg(feature = "blob-diff")]
+pub use history::History;
+
 ///
 #[cfg(feature = "revision")]
 #[allow(clippy::empty_docs)]
@@ -30,5 +37,7 @@ pub struct Spec<'repo> {
     pub(crate) first_ref: Option<gix_ref::Reference>,
     /// The second name of a reference as seen while parsing a `RevSpec`, for completeness.
     pub(crate) second_ref: Option<gix_ref::Reference>,
+    /// The time of the oldest reflog entry if a lookup like `main@{1.year.ago}` predated it.
+    pub(crate) reflog_date_out_of_range: Option<gix_date::Time>,
     pub(crate) repo: &'repo crate::Repository,
 }
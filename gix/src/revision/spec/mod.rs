@@ -43,6 +43,7 @@ impl<'repo> Spec<'repo> {
             repo: id.repo,
             first_ref: None,
             second_ref: None,
+            reflog_date_out_of_range: None,
         }
     }
 }
@@ -85,6 +86,15 @@ impl<'repo> Spec<'repo> {
         self.second_ref.as_ref()
     }
 
+    /// Return the time of the oldest reflog entry if a lookup by date like `main@{1.year.ago}` predated it, or `None`
+    /// if all dates were covered by their reflogs.
+    ///
+    /// In that case, like `git`, the oldest known value of the reference was used instead, which is where `git` prints a
+    /// warning like `log for 'main' only goes back to <date>`.
+    pub fn reflog_date_out_of_range(&self) -> Option<gix_date::Time> {
+        self.reflog_date_out_of_range
+    }
+
     /// Return the single included object represented by this instance, or `None` if it is a range of any kind.
     pub fn single(&self) -> Option<Id<'repo>> {
         match self.inner {
@@ -24,6 +24,7 @@ impl<'repo> Delegate<'repo> {
             err: Vec::new(),
             prefix: Default::default(),
             last_call_was_disambiguate_prefix: Default::default(),
+            reflog_date_out_of_range: None,
             opts,
             repo,
         }
@@ -105,6 +106,7 @@ impl<'repo> Delegate<'repo> {
             first_ref: self.refs[0].take(),
             second_ref: self.refs[1].take(),
             inner: kind_to_spec(self.kind, range)?,
+            reflog_date_out_of_range: self.reflog_date_out_of_range,
             repo: self.repo,
         })
     }
@@ -106,31 +106,49 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
     fn reflog(&mut self, query: ReflogLookup) -> Option<()> {
         self.unset_disambiguate_call();
         match query {
-            ReflogLookup::Date(_date) => {
-                // TODO: actually do this - this should be possible now despite incomplete date parsing
-                self.err.push(Error::Planned {
-                    dependency: "remote handling and ref-specs are fleshed out more",
-                });
-                None
+            ReflogLookup::Date(date) => {
+                let r = self.reflog_reference()?;
+                let mut platform = r.log_iter();
+                let mut oldest = None;
+                for line in platform
+                    .rev()
+                    .ok()
+                    .flatten()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Result::ok)
+                {
+                    if line.signature.time.seconds <= date.seconds {
+                        self.objs[self.idx]
+                            .get_or_insert_with(HashSet::default)
+                            .insert(line.new_oid);
+                        return Some(());
+                    }
+                    oldest = Some(line);
+                }
+                match oldest {
+                    // Like `git`, fall back to the oldest known value if the date predates the reflog.
+                    Some(line) => {
+                        let id = if line.previous_oid.is_null() {
+                            line.new_oid
+                        } else {
+                            line.previous_oid
+                        };
+                        self.objs[self.idx].get_or_insert_with(HashSet::default).insert(id);
+                        self.reflog_date_out_of_range.get_or_insert(line.signature.time);
+                        Some(())
+                    }
+                    None => {
+                        self.err.push(Error::MissingRefLog {
+                            reference: r.name().as_bstr().into(),
+                            action: "lookup entry by date",
+                        });
+                        None
+                    }
+                }
             }
             ReflogLookup::Entry(no) => {
-                let r = match &mut self.refs[self.idx] {
-                    Some(r) => r.clone().attach(self.repo),
-                    val @ None => match self.repo.head().map(crate::Head::try_into_referent) {
-                        Ok(Some(r)) => {
-                            *val = Some(r.clone().detach());
-                            r
-                        }
-                        Ok(None) => {
-                            self.err.push(Error::UnbornHeadsHaveNoRefLog);
-                            return None;
-                        }
-                        Err(err) => {
-                            self.err.push(err.into());
-                            return None;
-                        }
-                    },
-                };
+                let r = self.reflog_reference()?;
                 let mut platform = r.log_iter();
                 match platform.rev().ok().flatten() {
                     Some(mut it) => match it.nth(no).and_then(Result::ok) {
@@ -265,3 +283,26 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
         None
     }
 }
+
+impl<'repo> Delegate<'repo> {
+    /// Return the reference whose reflog to use, which is the current one or the one `HEAD` points to.
+    fn reflog_reference(&mut self) -> Option<crate::Reference<'repo>> {
+        Some(match &mut self.refs[self.idx] {
+            Some(r) => r.clone().attach(self.repo),
+            val @ None => match self.repo.head().map(crate::Head::try_into_referent) {
+                Ok(Some(r)) => {
+                    *val = Some(r.clone().detach());
+                    r
+                }
+                Ok(None) => {
+                    self.err.push(Error::UnbornHeadsHaveNoRefLog);
+                    return None;
+                }
+                Err(err) => {
+                    self.err.push(err.into());
+                    return None;
+                }
+            },
+        })
+    }
+}
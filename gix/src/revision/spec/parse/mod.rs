@@ -62,6 +62,8 @@ struct Delegate<'repo> {
     prefix: [Option<gix_hash::Prefix>; 2],
     /// If true, we didn't try to do any other transformation which might have helped with disambiguation.
     last_call_was_disambiguate_prefix: [bool; 2],
+    /// The time of the oldest reflog entry if a lookup by date predated it.
+    reflog_date_out_of_range: Option<gix_date::Time>,
 
     repo: &'repo Repository,
 }
@@ -1,5 +1,4 @@
 use gix_hash::ObjectId;
-use gix_object::FindExt;
 
 use crate::{ext::ObjectIdExt, revision, Repository};
 
@@ -11,6 +10,13 @@ pub enum Error {
     SimpleTraversal(#[from] gix_traverse::commit::simple::Error),
     #[error(transparent)]
     ShallowCommits(#[from] crate::shallow::open::Error),
+    #[error("Could not read the grafts file at \"{}\"", path.display())]
+    ReadGrafts {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error(transparent)]
+    DecodeGrafts(#[from] gix_traverse::commit::grafts::decode::Error),
     #[error(transparent)]
     ConfigBoolean(#[from] crate::config::boolean::Error),
 }
@@ -21,6 +27,8 @@ pub struct Info<'repo> {
     /// The detached id of the commit.
     pub id: gix_hash::ObjectId,
     /// All parent ids we have encountered. Note that these will be at most one if [`Parents::First`][gix_traverse::commit::Parents::First] is enabled.
+    ///
+    /// These are the parents as rewritten by the `info/grafts` file, and shallow commits have no parents, just like in `git`.
     pub parent_ids: gix_traverse::commit::ParentIds,
     /// The time at which the commit was created. It will only be `Some(_)` if the chosen traversal was
     /// taking dates into consideration.
@@ -151,10 +159,7 @@ impl<'repo> Platform<'repo> {
     /// if the traversal should exclude it and its ancestry entirely.
     ///
     /// If `filter` is None, no pruning of the graph will be performed which is the default.
-    pub fn selected(
-        self,
-        mut filter: impl FnMut(&gix_hash::oid) -> bool + 'repo,
-    ) -> Result<revision::Walk<'repo>, Error> {
+    pub fn selected(self, filter: impl FnMut(&gix_hash::oid) -> bool + 'repo) -> Result<revision::Walk<'repo>, Error> {
         let Platform {
             repo,
             tips,
@@ -166,43 +171,16 @@ impl<'repo> Platform<'repo> {
         Ok(revision::Walk {
             repo,
             inner: Box::new(
-                gix_traverse::commit::Simple::filtered(tips, &repo.objects, {
-                    // Note that specific shallow handling for commit-graphs isn't needed as these contain
-                    // all information there is, and exclude shallow parents to be structurally consistent.
-                    let shallow_commits = repo.shallow_commits()?;
-                    let mut grafted_parents_to_skip = Vec::new();
-                    let mut buf = Vec::new();
-                    move |id| {
-                        if !filter(id) {
-                            return false;
-                        }
-                        match shallow_commits.as_ref() {
-                            Some(commits) => {
-                                let id = id.to_owned();
-                                if let Ok(idx) = grafted_parents_to_skip.binary_search(&id) {
-                                    grafted_parents_to_skip.remove(idx);
-                                    return false;
-                                };
-                                if commits.binary_search(&id).is_ok() {
-                                    if let Ok(commit) = repo.objects.find_commit_iter(&id, &mut buf) {
-                                        grafted_parents_to_skip.extend(commit.parent_ids());
-                                        grafted_parents_to_skip.sort();
-                                    }
-                                };
-                                true
-                            }
-                            None => true,
-                        }
-                    }
-                })
-                .sorting(sorting)?
-                .parents(parents)
-                .commit_graph(
-                    commit_graph.or(use_commit_graph
-                        .map_or_else(|| self.repo.config.may_use_commit_graph(), Ok)?
-                        .then(|| self.repo.commit_graph().ok())
-                        .flatten()),
-                ),
+                gix_traverse::commit::Simple::filtered(tips, &repo.objects, filter)
+                    .sorting(sorting)?
+                    .parents(parents)
+                    .grafts(grafts(repo)?)
+                    .commit_graph(
+                        commit_graph.or(use_commit_graph
+                            .map_or_else(|| self.repo.config.may_use_commit_graph(), Ok)?
+                            .then(|| self.repo.commit_graph().ok())
+                            .flatten()),
+                    ),
             ),
         })
     }
@@ -217,6 +195,34 @@ impl<'repo> Platform<'repo> {
     }
 }
 
+/// A lazily loaded and auto-updated snapshot of the `info/grafts` file, shared across all clones of a repository.
+pub(crate) type GraftsStorage =
+    gix_features::threading::OwnShared<gix_fs::SharedFileSnapshotMut<gix_traverse::commit::Grafts>>;
+
+/// Obtain the parent-rewrites of the `info/grafts` file and of shallow commits, which are treated as roots, as `git` does.
+fn grafts(repo: &Repository) -> Result<gix_traverse::commit::Grafts, Error> {
+    let path = repo.common_dir().join("info").join("grafts");
+    let mut grafts = repo
+        .grafts
+        .recent_snapshot(
+            || path.metadata().and_then(|m| m.modified()).ok(),
+            || match std::fs::read(&path) {
+                Ok(data) => Ok(Some(gix_traverse::commit::Grafts::from_bytes(&data)?)),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(source) => Err(Error::ReadGrafts {
+                    path: path.clone(),
+                    source,
+                }),
+            },
+        )?
+        .map(|grafts| (**grafts).clone())
+        .unwrap_or_default();
+    if let Some(shallow_commits) = repo.shallow_commits()? {
+        grafts.extend_with_shallow(shallow_commits.iter().copied());
+    }
+    Ok(grafts)
+}
+
 pub(crate) mod iter {
     /// The iterator returned by [`crate::revision::walk::Platform::all()`].
     pub struct Walk<'repo> {
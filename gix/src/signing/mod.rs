@@ -0,0 +1,139 @@
+//! Create and verify signatures of commits and tags by invoking `gpg`, `gpgsm` or `ssh-keygen`, just like `git` does.
+use std::{ffi::OsString, io::Write, path::PathBuf};
+
+use crate::bstr::{BStr, BString, ByteSlice};
+
+/// The kind of signatures to create, as configured with `gpg.format`.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// OpenPGP signatures as created with `gpg`.
+    #[default]
+    OpenPgp,
+    /// X.509 signatures as created with `gpgsm`.
+    X509,
+    /// SSH signatures as created with `ssh-keygen`.
+    Ssh,
+}
+
+impl Format {
+    /// Return the format of the armored `signature`, or `None` if it's not known.
+    pub fn from_signature(signature: &[u8]) -> Option<Self> {
+        let signature = signature.trim_start();
+        [
+            (Format::OpenPgp, b"-----BEGIN PGP SIGNATURE-----".as_slice()),
+            (Format::OpenPgp, b"-----BEGIN PGP MESSAGE-----"),
+            (Format::X509, b"-----BEGIN SIGNED MESSAGE-----"),
+            (Format::Ssh, b"-----BEGIN SSH SIGNATURE-----"),
+        ]
+        .into_iter()
+        .find_map(|(format, header)| signature.starts_with(header).then_some(format))
+    }
+
+    /// The name of the program to use if none is configured.
+    pub fn default_program(&self) -> &'static str {
+        match self {
+            Format::OpenPgp => "gpg",
+            Format::X509 => "gpgsm",
+            Format::Ssh => "ssh-keygen",
+        }
+    }
+}
+
+/// Configuration for [creating](sign()) and [verifying](verify()) signatures, typically obtained with
+/// [`Repository::signing_options()`](crate::Repository::signing_options()).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The format of signatures to create.
+    pub format: Format,
+    /// The program to use for OpenPGP signatures, as configured with `gpg.openpgp.program` or `gpg.program`.
+    pub openpgp_program: Option<OsString>,
+    /// The program to use for X.509 signatures, as configured with `gpg.x509.program`.
+    pub x509_program: Option<OsString>,
+    /// The program to use for SSH signatures, as configured with `gpg.ssh.program`.
+    pub ssh_program: Option<OsString>,
+    /// The key to sign with, as configured with `user.signingKey`.
+    ///
+    /// For OpenPGP and X.509 signatures, it's passed to the program for it to find the key, and it defaults to the
+    /// committer identity. For SSH signatures, it's the path to the private key or to a public key whose private key
+    /// is held by the `ssh-agent`, or the public key itself if prefixed with `key::` or starting with `ssh-`.
+    pub signing_key: Option<BString>,
+    /// The file with the public keys of all trusted signers for verifying SSH signatures, as configured with `gpg.ssh.allowedSignersFile`.
+    pub allowed_signers_file: Option<PathBuf>,
+}
+
+impl Options {
+    /// Return the program to invoke for signatures of `format`, which is either configured or the
+    /// [default](Format::default_program()).
+    pub fn program(&self, format: Format) -> OsString {
+        match format {
+            Format::OpenPgp => self.openpgp_program.as_ref(),
+            Format::X509 => self.x509_program.as_ref(),
+            Format::Ssh => self.ssh_program.as_ref(),
+        }
+        .cloned()
+        .unwrap_or_else(|| format.default_program().into())
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod options {
+    /// The error returned by [`Repository::signing_options()`](crate::Repository::signing_options()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Format(#[from] crate::config::key::GenericErrorWithValue),
+        #[error(transparent)]
+        PathInterpolation(#[from] gix_config::path::interpolate::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod sign;
+pub use sign::function::sign;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod verify;
+pub use verify::function::verify;
+
+/// Run `program` with `args` and feed `input` to its standard input, returning its exit status along with
+/// everything it wrote to standard output and standard error.
+fn run(program: OsString, args: &[&std::ffi::OsStr], input: &[u8]) -> std::io::Result<(bool, BString, BString)> {
+    let mut child = gix_command::prepare(program)
+        .args(args.iter().copied())
+        .stdin(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()?;
+    let mut stdin = child.stdin.take().expect("configured");
+    let output = std::thread::scope(|scope| {
+        // Write from another thread as the program may produce output before consuming all of its input.
+        let writer = scope.spawn(move || stdin.write_all(input));
+        let output = child.wait_with_output();
+        writer.join().expect("no panic").ok();
+        output
+    })?;
+    Ok((output.status.success(), output.stdout.into(), output.stderr.into()))
+}
+
+/// Write `data` into a new temporary file that is removed when the returned handle is dropped, and return it
+/// along with its path.
+fn tempfile_with(data: &[u8]) -> std::io::Result<(gix_tempfile::Handle<gix_tempfile::handle::Writable>, PathBuf)> {
+    let mut file = gix_tempfile::new(
+        std::env::temp_dir(),
+        gix_tempfile::ContainingDirectory::Exists,
+        gix_tempfile::AutoRemove::Tempfile,
+    )?;
+    file.write_all(data)?;
+    file.flush()?;
+    let path = file.with_mut(|file| file.path().to_owned())?;
+    Ok((file, path))
+}
+
+fn lines_with_prefix<'a>(output: &'a BStr, prefix: &'a str) -> impl Iterator<Item = &'a BStr> + 'a {
+    output
+        .lines()
+        .filter_map(move |line| line.strip_prefix(prefix.as_bytes()).map(ByteSlice::as_bstr))
+}
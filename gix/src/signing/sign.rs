@@ -0,0 +1,78 @@
+/// The error returned by [`sign()`](crate::signing::sign()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A signing key must be configured with `user.signingKey`")]
+    MissingSigningKey,
+    #[error("Could not run the signing program {program:?}")]
+    Io {
+        source: std::io::Error,
+        program: std::ffi::OsString,
+    },
+    #[error("The signing program {program:?} failed to create a signature: {stderr}")]
+    Failed {
+        program: std::ffi::OsString,
+        stderr: crate::bstr::BString,
+    },
+}
+
+pub(super) mod function {
+    use std::ffi::OsStr;
+
+    use super::Error;
+    use crate::{
+        bstr::{BString, ByteSlice},
+        signing::{lines_with_prefix, run, tempfile_with, Format, Options},
+    };
+
+    /// Create a detached signature of `payload` in the format configured in `options`, like `git` does when signing
+    /// commits or tags, and return it as produced by the signing program, including its trailing newline.
+    ///
+    /// OpenPGP and X.509 signatures are created by passing `payload` to `gpg -bsau <key>` or `gpgsm -bsau <key>`
+    /// respectively, and SSH signatures by passing it to `ssh-keygen -Y sign -n git -f <key>`.
+    pub fn sign(payload: &[u8], options: &Options) -> Result<BString, Error> {
+        let key = options.signing_key.as_ref().ok_or(Error::MissingSigningKey)?;
+        let program = options.program(options.format);
+        let io_err = |source| Error::Io {
+            source,
+            program: program.clone(),
+        };
+        let (success, signature, stderr) = match options.format {
+            Format::OpenPgp | Format::X509 => {
+                let key = gix_path::from_bstr(key.as_bstr());
+                let (success, signature, stderr) = run(
+                    program.clone(),
+                    &["--status-fd=2".as_ref(), "-bsau".as_ref(), key.as_os_str()],
+                    payload,
+                )
+                .map_err(io_err)?;
+                let created =
+                    lines_with_prefix(stderr.as_ref(), "[GNUPG:] ").any(|status| status.starts_with(b"SIG_CREATED "));
+                (success && created, signature, stderr)
+            }
+            Format::Ssh => {
+                let literal_key = key
+                    .strip_prefix(b"key::")
+                    .or_else(|| key.starts_with(b"ssh-").then_some(key.as_slice()));
+                let mut args: Vec<&OsStr> = vec!["-Y".as_ref(), "sign".as_ref(), "-n".as_ref(), "git".as_ref()];
+                match literal_key {
+                    Some(public_key) => {
+                        // Let `ssh-agent` find the private key of the public key.
+                        let (_file, path) = tempfile_with(public_key).map_err(io_err)?;
+                        args.extend(["-U".as_ref(), "-f".as_ref(), path.as_os_str()]);
+                        run(program.clone(), &args, payload).map_err(io_err)?
+                    }
+                    None => {
+                        let path = gix_path::from_bstr(key.as_bstr());
+                        args.extend(["-f".as_ref(), path.as_os_str()]);
+                        run(program.clone(), &args, payload).map_err(io_err)?
+                    }
+                }
+            }
+        };
+        if !success || signature.trim_end().is_empty() {
+            return Err(Error::Failed { program, stderr });
+        }
+        Ok(signature)
+    }
+}
@@ -0,0 +1,289 @@
+use crate::bstr::BString;
+
+/// The error returned by [`verify()`](crate::signing::verify()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The format of the signature is unknown")]
+    UnknownFormat,
+    #[error("SSH signatures can only be verified if `gpg.ssh.allowedSignersFile` is configured")]
+    MissingAllowedSignersFile,
+    #[error("Could not run the verification program {program:?}")]
+    Io {
+        source: std::io::Error,
+        program: std::ffi::OsString,
+    },
+    #[error("The verification program {program:?} didn't report the status of the signature: {stderr}")]
+    NoStatus {
+        program: std::ffi::OsString,
+        stderr: BString,
+    },
+}
+
+/// The verdict on a signature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Status {
+    /// The signature is valid, but it's still necessary to check the [`trust`](Outcome::trust) in its key.
+    Good,
+    /// The signature doesn't match the signed data.
+    Bad,
+    /// The signature is valid, but expired.
+    ExpiredSignature,
+    /// The signature is valid, but was made by an expired key.
+    ExpiredKey,
+    /// The signature is valid, but was made by a revoked key.
+    RevokedKey,
+    /// The signature couldn't be checked, typically because the key isn't known.
+    MissingKey,
+}
+
+/// The trust in the key that made a signature, from the least to the most trusted one, with the same meaning as in `gpg`.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Trust {
+    /// Nothing is known about the key.
+    #[default]
+    Undefined,
+    /// The key must not be trusted.
+    Never,
+    /// The key is trusted marginally.
+    Marginal,
+    /// The key is fully trusted, which is also the case for SSH keys listed in the allowed-signers file.
+    Fully,
+    /// The key is trusted ultimately, which usually is the case for one's own keys.
+    Ultimate,
+}
+
+/// The outcome of [`verify()`](crate::signing::verify()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The format of the verified signature.
+    pub format: super::Format,
+    /// The verdict on the signature.
+    pub status: Status,
+    /// The trust in the key that made the signature.
+    pub trust: Trust,
+    /// The identity of the signer, like the user id of an OpenPGP key or the principal of an SSH key, if known.
+    pub signer: Option<BString>,
+    /// The identifier of the key that made the signature, like the long id of an OpenPGP key or the fingerprint of an SSH key, if known.
+    pub key: Option<BString>,
+    /// The human-readable output of the verification program.
+    pub output: BString,
+}
+
+impl Outcome {
+    /// Return `true` if the signature is [good](Status::Good) and made by a key that is at least trusted as much as `min_trust`.
+    pub fn is_valid(&self, min_trust: Trust) -> bool {
+        self.status == Status::Good && self.trust >= min_trust
+    }
+}
+
+pub(super) mod function {
+    use std::ffi::OsStr;
+
+    use super::{Error, Outcome, Status, Trust};
+    use crate::{
+        bstr::{BStr, BString, ByteSlice},
+        signing::{lines_with_prefix, run, tempfile_with, Format, Options},
+    };
+
+    /// Verify that `signature` was made over `payload`, like `git verify-commit` and `git verify-tag` do, and return
+    /// whether it's valid along with the trust in the key that made it.
+    ///
+    /// The format of `signature` determines the program from `options` to use for verification.
+    /// SSH signatures are only trusted if the signer is listed in the file of [allowed signers](Options::allowed_signers_file).
+    /// Note that an [`Outcome`] is returned for bad signatures as well, and errors only occur if the status of the
+    /// signature couldn't be determined.
+    pub fn verify(payload: &[u8], signature: &[u8], options: &Options) -> Result<Outcome, Error> {
+        let format = Format::from_signature(signature).ok_or(Error::UnknownFormat)?;
+        let program = options.program(format);
+        let io_err = |source| Error::Io {
+            source,
+            program: program.clone(),
+        };
+        let mut signature = signature.to_owned();
+        if !signature.ends_with(b"\n") {
+            signature.push(b'\n');
+        }
+        let (_file, signature_path) = tempfile_with(&signature).map_err(io_err)?;
+
+        match format {
+            Format::OpenPgp | Format::X509 => {
+                let (_success, status, stderr) = run(
+                    program.clone(),
+                    &[
+                        "--keyid-format=long".as_ref(),
+                        "--status-fd=1".as_ref(),
+                        "--verify".as_ref(),
+                        signature_path.as_os_str(),
+                        "-".as_ref(),
+                    ],
+                    payload,
+                )
+                .map_err(io_err)?;
+                parse_gpg_status(format, status.as_ref(), stderr.clone()).ok_or(Error::NoStatus { program, stderr })
+            }
+            Format::Ssh => {
+                let allowed_signers = options
+                    .allowed_signers_file
+                    .as_deref()
+                    .ok_or(Error::MissingAllowedSignersFile)?;
+                let (found, principals, _stderr) = run(
+                    program.clone(),
+                    &[
+                        "-Y".as_ref(),
+                        "find-principals".as_ref(),
+                        "-f".as_ref(),
+                        allowed_signers.as_os_str(),
+                        "-s".as_ref(),
+                        signature_path.as_os_str(),
+                    ],
+                    &[],
+                )
+                .map_err(io_err)?;
+
+                let mut output = BString::default();
+                if found {
+                    for principal in principals.lines().filter(|line| !line.is_empty()) {
+                        let principal = gix_path::from_bstr(principal.as_bstr());
+                        let (success, stdout, stderr) = run(
+                            program.clone(),
+                            &[
+                                "-Y".as_ref(),
+                                "verify".as_ref(),
+                                "-n".as_ref(),
+                                "git".as_ref(),
+                                "-f".as_ref(),
+                                allowed_signers.as_os_str(),
+                                "-I".as_ref(),
+                                principal.as_os_str(),
+                                "-s".as_ref(),
+                                signature_path.as_os_str(),
+                            ],
+                            payload,
+                        )
+                        .map_err(io_err)?;
+                        output = stdout;
+                        output.extend_from_slice(&stderr);
+                        if success {
+                            return Ok(ssh_outcome(Status::Good, Trust::Fully, output));
+                        }
+                    }
+                    return Ok(ssh_outcome(Status::Bad, Trust::Undefined, output));
+                }
+
+                // The signer isn't known, but the signature itself can still be checked.
+                let args: [&OsStr; 6] = [
+                    "-Y".as_ref(),
+                    "check-novalidate".as_ref(),
+                    "-n".as_ref(),
+                    "git".as_ref(),
+                    "-s".as_ref(),
+                    signature_path.as_os_str(),
+                ];
+                let (success, stdout, stderr) = run(program.clone(), &args, payload).map_err(io_err)?;
+                output = stdout;
+                output.extend_from_slice(&stderr);
+                Ok(ssh_outcome(
+                    if success { Status::Good } else { Status::Bad },
+                    Trust::Undefined,
+                    output,
+                ))
+            }
+        }
+    }
+
+    /// Parse lines like `Good "git" signature for <principal> with ED25519 key SHA256:<hash>`.
+    fn ssh_outcome(status: Status, trust: Trust, output: BString) -> Outcome {
+        let good = output
+            .lines()
+            .find_map(|line| line.strip_prefix(br#"Good "git" signature "#));
+        let (signer, key) = match good {
+            Some(line) => {
+                let (principal, key) = match line.find(" key ") {
+                    Some(pos) => (
+                        &line[..pos],
+                        Some(line[pos + " key ".len()..].trim().as_bstr().to_owned()),
+                    ),
+                    None => (line, None),
+                };
+                let signer = principal.strip_prefix(b"for ").and_then(|principal| {
+                    principal
+                        .rfind(" with ")
+                        .map(|pos| principal[..pos].as_bstr().to_owned())
+                });
+                (signer, key)
+            }
+            None => (None, None),
+        };
+        Outcome {
+            format: Format::Ssh,
+            status,
+            trust,
+            signer,
+            key,
+            output,
+        }
+    }
+
+    /// Parse the machine-readable status lines of `gpg` or `gpgsm`, as described in `doc/DETAILS` of GnuPG.
+    fn parse_gpg_status(format: Format, status: &BStr, output: BString) -> Option<Outcome> {
+        let mut outcome = None::<Outcome>;
+        let mut trust = Trust::Undefined;
+        for line in lines_with_prefix(status, "[GNUPG:] ") {
+            let mut tokens = line.splitn(3, |b| *b == b' ');
+            let keyword = tokens.next()?;
+            let verdict = match keyword {
+                b"GOODSIG" => Status::Good,
+                b"BADSIG" => Status::Bad,
+                b"EXPSIG" => Status::ExpiredSignature,
+                b"EXPKEYSIG" => Status::ExpiredKey,
+                b"REVKEYSIG" => Status::RevokedKey,
+                b"ERRSIG" => Status::MissingKey,
+                b"TRUST_UNDEFINED" => {
+                    trust = Trust::Undefined;
+                    continue;
+                }
+                b"TRUST_NEVER" => {
+                    trust = Trust::Never;
+                    continue;
+                }
+                b"TRUST_MARGINAL" => {
+                    trust = Trust::Marginal;
+                    continue;
+                }
+                b"TRUST_FULLY" => {
+                    trust = Trust::Fully;
+                    continue;
+                }
+                b"TRUST_ULTIMATE" => {
+                    trust = Trust::Ultimate;
+                    continue;
+                }
+                _ => continue,
+            };
+            let key = tokens.next().map(|key| key.as_bstr().to_owned());
+            let signer = (verdict != Status::MissingKey)
+                .then(|| tokens.next().map(|signer| signer.as_bstr().to_owned()))
+                .flatten();
+            match outcome.as_mut() {
+                // Like `git`, don't trust multiple signatures as it's unclear which signed what.
+                Some(previous) => previous.status = Status::MissingKey,
+                None => {
+                    outcome = Some(Outcome {
+                        format,
+                        status: verdict,
+                        trust: Trust::Undefined,
+                        signer,
+                        key,
+                        output: Default::default(),
+                    })
+                }
+            }
+        }
+        outcome.map(|mut outcome| {
+            outcome.trust = trust;
+            outcome.output = output;
+            outcome
+        })
+    }
+}
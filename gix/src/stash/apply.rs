@@ -0,0 +1,194 @@
+use gix_hash::ObjectId;
+
+use crate::bstr::BString;
+use crate::stash::util::{self, Entries};
+use crate::{tree_merge, Repository};
+
+/// The error returned by [Repository::stash_apply()] and [Repository::stash_pop()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A worktree is required to apply a stash")]
+    MissingWorkDir,
+    #[error("There is no stash at index {index}")]
+    NotFound { index: usize },
+    #[error("Commit {id} isn't a stash as it has less than two parents")]
+    NotAStash { id: ObjectId },
+    #[error("Cannot apply a stash while the index has conflicts")]
+    Conflicts,
+    #[error("The stashed changes of the index don't apply cleanly, try again without restoring the index")]
+    IndexConflicts,
+    #[error("Local changes or untracked files at '{path}' would be overwritten by applying the stash")]
+    WouldOverwrite { path: BString },
+    #[error("Cannot apply the stash as '{path}' would have to be a file and a directory")]
+    DirectoryFileConflict { path: BString },
+    #[error(transparent)]
+    List(#[from] super::list::Error),
+    #[error(transparent)]
+    Drop(#[from] super::drop::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    ObjectKind(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    TraverseTree(#[from] gix_traverse::tree::breadthfirst::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    Status(#[from] crate::status::Error),
+    #[error(transparent)]
+    IndexWorktreeIter(#[from] crate::status::index_worktree::iter::Error),
+    #[error(transparent)]
+    IndexWorktree(#[from] crate::status::index_worktree::Error),
+    #[error(transparent)]
+    WriteObject(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error("Could not write to the worktree")]
+    Io(#[from] std::io::Error),
+}
+
+/// Options for use in [Repository::stash_apply()] and [Repository::stash_pop()].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// If `true`, the changes that were added to the index when stashing are added to the index again, like `--index`.
+    ///
+    /// Otherwise, only files that were added by the stash are added to the index.
+    pub index: bool,
+}
+
+/// The outcome of [Repository::stash_apply()] and [Repository::stash_pop()].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The id of the stash commit that was applied.
+    pub id: ObjectId,
+    /// The paths that had conflicting changes, sorted by path.
+    ///
+    /// These are recorded as conflicts in the index, and files in the worktree contain conflict markers where possible.
+    pub conflicts: Vec<BString>,
+}
+
+impl Repository {
+    /// Apply the stash at `index`, with `0` being the most recent one, to the index and the worktree by merging the
+    /// stashed changes with the current ones, similar to `git stash apply stash@{<index>}`.
+    ///
+    /// Changes that conflict with the ones in the index are recorded as conflicts in the index and the
+    /// [outcome](Outcome), and the conflicting files are written to the worktree with conflict markers.
+    /// The operation fails without making any changes if a file with local changes, or an untracked file, would be
+    /// overwritten. Untracked files in the stash are restored as untracked files.
+    pub fn stash_apply(&self, index: usize, options: Options) -> Result<Outcome, Error> {
+        let _span = gix_trace::coarse!("gix::stash_apply");
+        if self.work_dir().is_none() {
+            return Err(Error::MissingWorkDir);
+        }
+        let id = self
+            .stash_list()?
+            .into_iter()
+            .nth(index)
+            .ok_or(Error::NotFound { index })?
+            .id;
+        let stash = self.find_object(id)?.try_into_commit()?;
+        let parents: Vec<_> = stash.parent_ids().map(crate::Id::detach).collect();
+        let (base_commit, index_commit) = match parents.as_slice() {
+            [base, index, ..] => (*base, *index),
+            _ => return Err(Error::NotAStash { id }),
+        };
+        let commit_entries = |id: ObjectId| -> Result<Entries, Error> {
+            util::tree_entries(self, self.find_object(id)?.try_into_commit()?.tree_id()?.detach())
+        };
+        let base = commit_entries(base_commit)?;
+        let stashed_index = commit_entries(index_commit)?;
+        let stashed_worktree = util::tree_entries::<Error>(self, stash.tree_id()?.detach())?;
+        let stashed_untracked = parents
+            .get(2)
+            .map(|id| commit_entries(*id))
+            .transpose()?
+            .unwrap_or_default();
+
+        let current_index = self.index_or_empty()?;
+        if current_index
+            .entries()
+            .iter()
+            .any(|entry| entry.stage() != gix_index::entry::Stage::Unconflicted)
+        {
+            return Err(Error::Conflicts);
+        }
+        let ours = util::index_entries(&current_index);
+        let changes = util::worktree_changes::<Error>(self, &current_index, true)?;
+
+        let labels = gix_merge::blob::Labels {
+            ancestor: None,
+            current: Some("Updated upstream".into()),
+            other: Some("Stashed changes".into()),
+        };
+        let merged = tree_merge::merge::<Error>(self, &base, &ours, &stashed_worktree, labels)?;
+        let merged_index = if options.index && stashed_index != base {
+            let merged_index = tree_merge::merge::<Error>(self, &base, &ours, &stashed_index, labels)?;
+            if !merged_index.conflicts.is_empty() {
+                return Err(Error::IndexConflicts);
+            }
+            Some(merged_index.index)
+        } else {
+            None
+        };
+
+        let mut target = merged.worktree;
+        for (rela_path, entry) in stashed_untracked {
+            if target.contains_key(&rela_path) {
+                return Err(Error::WouldOverwrite { path: rela_path });
+            }
+            target.insert(rela_path, entry);
+        }
+        if let Some(path) = util::directory_file_conflict(&target) {
+            return Err(Error::DirectoryFileConflict { path: path.clone() });
+        }
+        let changed_paths = target
+            .iter()
+            .filter(|(rela_path, entry)| ours.get(*rela_path) != Some(entry))
+            .map(|(rela_path, _)| rela_path)
+            .chain(ours.keys().filter(|rela_path| !target.contains_key(*rela_path)));
+        for rela_path in changed_paths {
+            if changes.modified.contains(rela_path) || changes.untracked.contains(rela_path) {
+                return Err(Error::WouldOverwrite {
+                    path: rela_path.clone(),
+                });
+            }
+        }
+
+        let checked_out = util::update_worktree::<Error>(self, &ours, &target)?;
+        let index_entries = match merged_index {
+            Some(entries) => entries,
+            None if merged.conflicts.is_empty() => {
+                let mut entries = ours;
+                for (rela_path, entry) in merged.index {
+                    entries.entry(rela_path).or_insert(entry);
+                }
+                entries
+            }
+            None => merged.index,
+        };
+        let new_index =
+            util::index_from_entries(self, &index_entries, &merged.conflicts, &[&checked_out, &current_index]);
+        gix_index::File::from_state(new_index, self.index_path()).write(Default::default())?;
+        Ok(Outcome {
+            id,
+            conflicts: merged.conflicts.into_iter().map(|conflict| conflict.path).collect(),
+        })
+    }
+
+    /// Like [`stash_apply()`](Self::stash_apply()), but also [drop](Self::stash_drop()) the stash at `index` if it
+    /// was applied without conflicts, similar to `git stash pop stash@{<index>}`.
+    pub fn stash_pop(&self, index: usize, options: Options) -> Result<Outcome, Error> {
+        let outcome = self.stash_apply(index, options)?;
+        if outcome.conflicts.is_empty() {
+            self.stash_drop(index)?;
+        }
+        Ok(outcome)
+    }
+}
@@ -0,0 +1,50 @@
+use gix_ref::transaction::{Change, PreviousValue, RefEdit, RefLog};
+
+use crate::{stash, Repository};
+
+/// The error returned by [Repository::stash_drop()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("There is no stash at index {index}")]
+    NotFound { index: usize },
+    #[error(transparent)]
+    DeleteReflogEntry(#[from] gix_ref::file::log::delete::Error),
+    #[error(transparent)]
+    List(#[from] stash::list::Error),
+    #[error(transparent)]
+    DeleteReference(#[from] crate::reference::edit::Error),
+}
+
+impl Repository {
+    /// Remove the stash at `index`, with `0` being the most recent one, similar to `git stash drop stash@{<index>}`,
+    /// and return the id of its commit.
+    ///
+    /// The [stash reference](stash::REFERENCE) is removed along with its reflog once there is no stash left, and is
+    /// otherwise updated to point to the most recent remaining stash.
+    pub fn stash_drop(&self, index: usize) -> Result<gix_hash::ObjectId, Error> {
+        let line = self
+            .refs
+            .reflog_delete(
+                stash::REFERENCE,
+                index,
+                gix_ref::file::log::delete::Options {
+                    rewrite: true,
+                    update_ref: true,
+                },
+                gix_lock::acquire::Fail::Immediately,
+            )?
+            .ok_or(Error::NotFound { index })?;
+        if self.stash_list()?.is_empty() {
+            self.edit_reference(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::Any,
+                    log: RefLog::AndReference,
+                },
+                name: stash::REFERENCE.try_into().expect("valid"),
+                deref: false,
+            })?;
+        }
+        Ok(line.new_oid)
+    }
+}
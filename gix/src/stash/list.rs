@@ -0,0 +1,38 @@
+use crate::{stash, Repository};
+
+/// The error returned by [Repository::stash_list()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::Error),
+    #[error("Could not read the reflog of the stash")]
+    ReadReflog(#[from] std::io::Error),
+    #[error(transparent)]
+    DecodeReflog(#[from] gix_ref::file::log::iter::reverse::Error),
+}
+
+impl Repository {
+    /// Return all stashes with the most recent one first, similar to `git stash list`, so that the position of each
+    /// stash is the index to refer to it as `stash@{<index>}`.
+    ///
+    /// The list is empty if there is no stash.
+    pub fn stash_list(&self) -> Result<Vec<stash::Entry>, Error> {
+        let Some(reference) = self.try_find_reference(stash::REFERENCE)? else {
+            return Ok(Vec::new());
+        };
+        let mut log = reference.log_iter();
+        let Some(lines) = log.rev()? else {
+            return Ok(Vec::new());
+        };
+        lines
+            .map(|line| {
+                line.map(|line| stash::Entry {
+                    id: line.new_oid,
+                    message: line.message,
+                })
+                .map_err(Into::into)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,35 @@
+//! Save local changes of the index and the worktree to the stash, and apply them again later, similar to `git stash`.
+//!
+//! Stashes are stored just like Git stores them, so they can be used interchangeably with `git stash`.
+//! Each stash is a commit with the tree of the worktree, whose first parent is the commit `HEAD` pointed to, and whose
+//! second parent is a commit with the tree of the index. A third parent with the tree of all untracked files exists if
+//! these were stashed as well.
+//! [`REFERENCE`] points to the most recent stash, while its reflog lists all stashes with the most recent one last.
+use crate::bstr::BString;
+
+/// The name of the reference pointing to the most recent stash, with all stashes being recorded in its reflog.
+pub const REFERENCE: &str = "refs/stash";
+
+/// A stash as listed by [Repository::stash_list()](crate::Repository::stash_list()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The id of the stash commit.
+    pub id: gix_hash::ObjectId,
+    /// The message describing the stash, like `WIP on main: 1234567 subject`.
+    pub message: BString,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod apply;
+///
+#[allow(clippy::empty_docs)]
+pub mod drop;
+///
+#[allow(clippy::empty_docs)]
+pub mod list;
+///
+#[allow(clippy::empty_docs)]
+pub mod push;
+
+mod util;
@@ -0,0 +1,202 @@
+use gix_ref::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
+use gix_ref::Target;
+
+use crate::bstr::{BString, ByteSlice};
+use crate::stash::util::{self, Entries};
+use crate::{ext::ObjectIdExt, stash, Id, Repository};
+
+/// The error returned by [Repository::stash_push()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A worktree is required to stash local changes")]
+    MissingWorkDir,
+    #[error("Cannot stash local changes without an initial commit")]
+    UnbornHead,
+    #[error("Cannot stash local changes while the index has conflicts")]
+    Conflicts,
+    #[error("Committer identity is not configured")]
+    CommitterMissing,
+    #[error("Author identity is not configured")]
+    AuthorMissing,
+    #[error(transparent)]
+    ParseTime(#[from] crate::config::time::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelHead(#[from] crate::head::peel::to_commit::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    NotATree(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    TraverseTree(#[from] gix_traverse::tree::breadthfirst::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    Status(#[from] crate::status::Error),
+    #[error(transparent)]
+    IndexWorktreeIter(#[from] crate::status::index_worktree::iter::Error),
+    #[error(transparent)]
+    IndexWorktree(#[from] crate::status::index_worktree::Error),
+    #[error(transparent)]
+    FilesystemOptions(#[from] crate::config::boolean::Error),
+    #[error(transparent)]
+    FilterPipeline(#[from] crate::repository::filter::pipeline::Error),
+    #[error(transparent)]
+    ConvertToGit(#[from] crate::filter::pipeline::convert_to_git::Error),
+    #[error(transparent)]
+    WriteObject(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error("Could not read from or write to the worktree")]
+    Io(#[from] std::io::Error),
+}
+
+/// Options for use in [Repository::stash_push()].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The message to describe the stash with, which is shown as `On <branch>: <message>`.
+    ///
+    /// If `None`, the stash is described as `WIP on <branch>: <short-id> <subject>` with the commit `HEAD` points to.
+    pub message: Option<String>,
+    /// If `true`, untracked files are stashed as well and removed from the worktree, like `--include-untracked`.
+    ///
+    /// Note that ignored files are never stashed.
+    pub include_untracked: bool,
+    /// If `true`, all changes that are already added to the index are kept in the index and in the worktree,
+    /// like `--keep-index`. They are stashed nonetheless.
+    pub keep_index: bool,
+}
+
+impl Repository {
+    /// Save the changes of the index and the worktree compared to `HEAD` as a new stash, and reset the index and
+    /// worktree to `HEAD` afterwards, similar to `git stash push`. Return the id of the stash commit, or `None` if
+    /// there are no local changes to stash, in which case nothing is changed.
+    ///
+    /// The stash becomes the most recent one, referred to as `stash@{0}`, and is recorded in the reflog of
+    /// [the stash reference](stash::REFERENCE), which is created if needed.
+    /// Use `options` to stash untracked files as well, or to keep the changes that were added to the index.
+    ///
+    /// Note that changes of submodules aren't stashed, and that entries which are only intended to be added are kept.
+    pub fn stash_push(&self, options: Options) -> Result<Option<Id<'_>>, Error> {
+        let _span = gix_trace::coarse!("gix::stash_push");
+        if self.work_dir().is_none() {
+            return Err(Error::MissingWorkDir);
+        }
+        let mut head = self.head()?;
+        if head.is_unborn() {
+            return Err(Error::UnbornHead);
+        }
+        let head_commit = head.peel_to_commit_in_place()?;
+        let head_entries = util::tree_entries::<Error>(self, head_commit.tree_id()?.detach())?;
+
+        let index = self.index_or_empty()?;
+        if index
+            .entries()
+            .iter()
+            .any(|entry| entry.stage() != gix_index::entry::Stage::Unconflicted)
+        {
+            return Err(Error::Conflicts);
+        }
+        let index_entries = util::index_entries(&index);
+        let changes = util::worktree_changes::<Error>(self, &index, options.include_untracked)?;
+
+        let (mut pipeline, _) = self.filter_pipeline(None)?;
+        let fs = self.filesystem_options()?;
+        let mut worktree_entries = index_entries.clone();
+        for rela_path in &changes.modified {
+            let index_mode = index_entries.get(rela_path).map(|(mode, _)| *mode);
+            match util::worktree_blob::<Error>(self, &mut pipeline, &index, rela_path.as_bstr(), index_mode, &fs)? {
+                Some(entry) => worktree_entries.insert(rela_path.clone(), entry),
+                None => worktree_entries.remove(rela_path),
+            };
+        }
+        let mut untracked_entries = Entries::new();
+        for rela_path in &changes.untracked {
+            if let Some(entry) =
+                util::worktree_blob::<Error>(self, &mut pipeline, &index, rela_path.as_bstr(), None, &fs)?
+            {
+                untracked_entries.insert(rela_path.clone(), entry);
+            }
+        }
+        if index_entries == head_entries && worktree_entries == index_entries && untracked_entries.is_empty() {
+            return Ok(None);
+        }
+
+        let branch: BString = match self.head_name()? {
+            Some(name) => name.shorten().to_owned(),
+            None => "(no branch)".into(),
+        };
+        let on_branch = format!(
+            "{branch}: {} {}",
+            head_commit.id().shorten_or_id(),
+            head_commit.message()?.summary()
+        );
+        let committer = self.committer().ok_or(Error::CommitterMissing)??;
+        let author = self.author().ok_or(Error::AuthorMissing)??;
+        let write_commit = |message: String, tree: &Entries, parents: Vec<gix_hash::ObjectId>| -> Result<_, Error> {
+            let commit = gix_object::Commit {
+                message: message.into(),
+                tree: util::write_tree(self, tree)?,
+                author: author.into(),
+                committer: committer.into(),
+                encoding: None,
+                parents: parents.into(),
+                extra_headers: Default::default(),
+            };
+            Ok(self.write_object(&commit)?.detach())
+        };
+
+        let index_commit = write_commit(format!("index on {on_branch}\n"), &index_entries, vec![head_commit.id])?;
+        let mut parents = vec![head_commit.id, index_commit];
+        if !untracked_entries.is_empty() {
+            parents.push(write_commit(
+                format!("untracked files on {on_branch}\n"),
+                &untracked_entries,
+                Vec::new(),
+            )?);
+        }
+        let message = match &options.message {
+            Some(message) => format!("On {branch}: {message}"),
+            None => format!("WIP on {on_branch}"),
+        };
+        let stash_commit = write_commit(message.clone(), &worktree_entries, parents)?;
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: true,
+                    message: message.into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Peeled(stash_commit),
+            },
+            name: stash::REFERENCE.try_into().expect("valid"),
+            deref: false,
+        })?;
+
+        let target = if options.keep_index {
+            &index_entries
+        } else {
+            &head_entries
+        };
+        let mut current = worktree_entries;
+        current.extend(untracked_entries);
+        let checked_out = util::update_worktree::<Error>(self, &current, target)?;
+        gix_index::File::from_state(
+            util::index_from_entries(self, target, &[], &[&checked_out, &index]),
+            self.index_path(),
+        )
+        .write(Default::default())?;
+        Ok(Some(stash_commit.attach(self)))
+    }
+}
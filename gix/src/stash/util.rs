@@ -0,0 +1,214 @@
+use std::collections::BTreeSet;
+use std::io::Read;
+use std::sync::atomic::AtomicBool;
+
+use gix_hash::ObjectId;
+use gix_object::tree::{EntryKind, EntryMode};
+
+use crate::bstr::{BStr, BString, ByteSlice};
+pub(super) use crate::tree_merge::{directory_file_conflict, index_from_entries, tree_entries, write_tree, Entries};
+use crate::Repository;
+
+/// The changes of the worktree compared to an index.
+#[derive(Default)]
+pub(super) struct WorktreeChanges {
+    /// The paths of tracked files that were modified, removed, or changed their type.
+    pub modified: BTreeSet<BString>,
+    /// The paths of untracked files, if they were requested.
+    pub untracked: BTreeSet<BString>,
+}
+
+/// Return all unconflicted entries of `index`, without those that are only intended to be added.
+pub(super) fn index_entries(index: &gix_index::State) -> Entries {
+    index
+        .entries()
+        .iter()
+        .filter(|entry| {
+            entry.stage() == gix_index::entry::Stage::Unconflicted
+                && !entry.mode.is_sparse()
+                && !entry.flags.contains(gix_index::entry::Flags::INTENT_TO_ADD)
+        })
+        .filter_map(|entry| {
+            Some((
+                entry.path(index).to_owned(),
+                (entry.mode.to_tree_entry_mode()?, entry.id),
+            ))
+        })
+        .collect()
+}
+
+/// Obtain the changes of the worktree compared to `index`, along with all untracked files if `untracked` is `true`.
+///
+/// Note that submodules are ignored.
+pub(super) fn worktree_changes<E>(
+    repo: &Repository,
+    index: &gix_index::File,
+    untracked: bool,
+) -> Result<WorktreeChanges, E>
+where
+    E: From<crate::status::Error>
+        + From<crate::status::index_worktree::iter::Error>
+        + From<crate::status::index_worktree::Error>,
+{
+    use crate::status::{index_worktree::iter::Item, UntrackedFiles};
+    use gix_status::index_as_worktree::{Change, EntryStatus};
+
+    let mut out = WorktreeChanges::default();
+    let iter = repo
+        .status(gix_features::progress::Discard)?
+        .index_worktree_submodules(None)
+        .untracked_files(if untracked {
+            UntrackedFiles::Files
+        } else {
+            UntrackedFiles::None
+        })
+        .index(crate::worktree::IndexPersistedOrInMemory::InMemory(index.clone()))
+        .into_index_worktree_iter(Vec::new())?;
+    for item in iter {
+        match item? {
+            Item::Modification {
+                rela_path,
+                status: EntryStatus::Change(change),
+                ..
+            } => {
+                if !matches!(change, Change::SubmoduleModification(_)) {
+                    out.modified.insert(rela_path);
+                }
+            }
+            Item::DirectoryContents { entry, .. } => {
+                if entry.status == gix_dir::entry::Status::Untracked
+                    && matches!(
+                        entry.disk_kind,
+                        Some(gix_dir::entry::Kind::File | gix_dir::entry::Kind::Symlink)
+                    )
+                {
+                    out.untracked.insert(entry.rela_path);
+                }
+            }
+            Item::Modification { .. } | Item::Rewrite { .. } => {}
+        }
+    }
+    Ok(out)
+}
+
+/// Write the file at `rela_path` in the worktree as blob after converting it with `pipeline`, and return it along with
+/// its mode. Return `None` if the file doesn't exist or isn't a file or symlink.
+///
+/// `index_mode` is the mode of the entry in the index, if there is one, and it's used if the filesystem can't represent
+/// executable bits or symlinks as indicated by `fs`.
+pub(super) fn worktree_blob<E>(
+    repo: &Repository,
+    pipeline: &mut crate::filter::Pipeline<'_>,
+    index: &gix_index::State,
+    rela_path: &BStr,
+    index_mode: Option<EntryMode>,
+    fs: &gix_fs::Capabilities,
+) -> Result<Option<(EntryMode, ObjectId)>, E>
+where
+    E: From<std::io::Error> + From<crate::filter::pipeline::convert_to_git::Error> + From<crate::object::write::Error>,
+{
+    let path = repo
+        .work_dir()
+        .expect("only called with a worktree")
+        .join(gix_path::from_bstr(rela_path));
+    let metadata = match path.symlink_metadata() {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    let index_kind = index_mode.map(|mode| mode.kind());
+    let (kind, data) = if metadata.file_type().is_symlink() {
+        (
+            EntryKind::Link,
+            gix_path::into_bstr(std::fs::read_link(&path)?).into_owned().into(),
+        )
+    } else if !metadata.is_file() {
+        return Ok(None);
+    } else if !fs.symlink && index_kind == Some(EntryKind::Link) {
+        (EntryKind::Link, std::fs::read(&path)?)
+    } else {
+        let mut data = Vec::new();
+        pipeline
+            .convert_to_git(std::fs::File::open(&path)?, &gix_path::from_bstr(rela_path), index)?
+            .read_to_end(&mut data)?;
+        let is_executable = if fs.executable_bit {
+            gix_fs::is_executable(&metadata)
+        } else {
+            index_kind == Some(EntryKind::BlobExecutable)
+        };
+        (
+            if is_executable {
+                EntryKind::BlobExecutable
+            } else {
+                EntryKind::Blob
+            },
+            data,
+        )
+    };
+    Ok(Some((kind.into(), repo.write_blob(data)?.detach())))
+}
+
+/// Change the worktree so that all paths whose entries in `target` differ from the ones in `current`, the entries the
+/// worktree matches right now, are checked out, and remove the files of all entries that are only in `current` along
+/// with their emptied leading directories.
+///
+/// Return an index with all entries that were checked out, along with their file stats.
+pub(super) fn update_worktree<E>(repo: &Repository, current: &Entries, target: &Entries) -> Result<gix_index::State, E>
+where
+    E: From<std::io::Error> + From<crate::config::checkout_options::Error> + From<gix_worktree_state::checkout::Error>,
+{
+    let workdir = repo.work_dir().expect("only called with a worktree");
+    let remove_file = |rela_path: &BStr| -> std::io::Result<()> {
+        match std::fs::remove_file(workdir.join(gix_path::from_bstr(rela_path))) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    };
+    for (rela_path, (mode, _)) in current {
+        if mode.is_commit() || target.contains_key(rela_path) {
+            continue;
+        }
+        remove_file(rela_path.as_bstr())?;
+        let path = workdir.join(gix_path::from_bstr(rela_path.as_bstr()));
+        let mut dir = path.parent();
+        while let Some(parent) = dir.filter(|dir| *dir != workdir) {
+            if std::fs::remove_dir(parent).is_err() {
+                break;
+            }
+            dir = parent.parent();
+        }
+    }
+
+    let mut index = gix_index::State::new(repo.object_hash());
+    for (rela_path, (mode, id)) in target {
+        if current.get(rela_path) != Some(&(*mode, *id)) {
+            // Files are overwritten, but not truncated, by the checkout, so they need to be removed first.
+            if current.get(rela_path).is_some_and(|(mode, _)| !mode.is_commit()) {
+                remove_file(rela_path.as_bstr())?;
+            }
+            index.dangerously_push_entry(
+                Default::default(),
+                *id,
+                gix_index::entry::Flags::empty(),
+                (*mode).into(),
+                rela_path.as_bstr(),
+            );
+        }
+    }
+    index.sort_entries();
+
+    let mut options = repo
+        .config
+        .checkout_options(repo, gix_worktree::stack::state::attributes::Source::IdMapping)?;
+    options.overwrite_existing = true;
+    gix_worktree_state::checkout(
+        &mut index,
+        workdir,
+        repo.objects.clone().into_arc()?,
+        &gix_features::progress::Discard,
+        &gix_features::progress::Discard,
+        &AtomicBool::default(),
+        options,
+    )?;
+    Ok(index)
+}
@@ -0,0 +1,126 @@
+use crate::bstr::{BStr, BString};
+use crate::status::{index_worktree, tree_index, Platform};
+use crate::worktree::IndexPersistedOrInMemory;
+
+/// An item of the [status iterator](crate::status::Iter).
+#[derive(Clone, PartialEq, Debug)]
+pub enum Item {
+    /// A change between the head tree and the index, i.e. a *staged* change.
+    TreeIndex(tree_index::Change),
+    /// A change between the index and the worktree, i.e. an *unstaged* change, or an untracked or ignored file.
+    IndexWorktree(index_worktree::iter::Item),
+}
+
+/// Access
+impl Item {
+    /// The repository-relative path of the entry contained in this item.
+    pub fn location(&self) -> &BStr {
+        match self {
+            Item::TreeIndex(change) => change.location(),
+            Item::IndexWorktree(item) => item.rela_path(),
+        }
+    }
+}
+
+/// The error returned by [Platform::into_iter()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Index(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    HeadTreeId(#[from] crate::reference::head_tree_id::Error),
+    #[error(transparent)]
+    Pathspec(#[from] crate::pathspec::init::Error),
+    #[error(transparent)]
+    TreeIndex(#[from] tree_index::Error),
+    #[error(transparent)]
+    IndexWorktree(#[from] index_worktree::iter::Error),
+}
+
+/// Lifecycle
+impl<'repo, Progress> Platform<'repo, Progress>
+where
+    Progress: gix_features::progress::Progress,
+{
+    /// Turn the platform into an iterator for all changes between the head tree and the index, followed by
+    /// all changes between the index and the working tree, similar to what `git status` shows.
+    ///
+    /// * `patterns`
+    ///     - Optional patterns to use to limit the paths to look at. If empty, all paths are considered.
+    ///
+    /// Note that the changes between the head tree and the index are computed in advance, before the
+    /// index-worktree status is started.
+    /// Use [`porcelain::entries()`](crate::status::porcelain::entries()) to turn the items into the
+    /// short format of `git status`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn into_iter(mut self, patterns: impl IntoIterator<Item = BString>) -> Result<crate::status::Iter, Error> {
+        let index = match self.index.take() {
+            None => IndexPersistedOrInMemory::Persisted(self.repo.index_or_empty()?),
+            Some(index) => index,
+        };
+        let patterns: Vec<_> = patterns.into_iter().collect();
+
+        let repo = self.repo;
+        let tree_id = match self.head_tree {
+            Some(id) => id,
+            None if repo.head()?.is_unborn() => gix_hash::ObjectId::empty_tree(repo.object_hash()),
+            None => repo.head_tree_id()?.detach(),
+        };
+        let mut pathspec = repo.pathspec(
+            self.index_worktree_options
+                .dirwalk_options
+                .as_ref()
+                .is_some_and(|opts| opts.empty_patterns_match_prefix),
+            &patterns,
+            true, /* inherit ignore case */
+            &index,
+            crate::worktree::stack::state::attributes::Source::IdMapping,
+        )?;
+        let mut tree_index = Vec::new();
+        repo.tree_index_status(
+            &tree_id,
+            &index,
+            Some(&mut pathspec),
+            self.tree_index_rewrites,
+            |change| {
+                tree_index.push(change);
+                crate::object::tree::diff::Action::Continue
+            },
+        )?;
+        drop(pathspec);
+
+        self.index = Some(index);
+        Ok(crate::status::Iter {
+            tree_index: tree_index.into_iter(),
+            index_worktree: self.into_index_worktree_iter(patterns)?,
+        })
+    }
+}
+
+impl Iterator for crate::status::Iter {
+    type Item = Result<Item, index_worktree::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(change) = self.tree_index.next() {
+            return Some(Ok(Item::TreeIndex(change)));
+        }
+        self.index_worktree.next().map(|res| res.map(Item::IndexWorktree))
+    }
+}
+
+/// Access
+impl crate::status::Iter {
+    /// Return the outcome of the index-worktree part of the iteration, or `None` if the iterator isn't fully consumed.
+    pub fn outcome_mut(&mut self) -> Option<&mut index_worktree::iter::Outcome> {
+        self.index_worktree.outcome_mut()
+    }
+
+    /// Turn the iterator into the outcome of the index-worktree part of the iteration, which is `None` on error
+    /// or if the iteration isn't complete.
+    pub fn into_outcome(self) -> Option<index_worktree::iter::Outcome> {
+        self.index_worktree.into_outcome()
+    }
+}
@@ -13,9 +13,21 @@ where
     index: Option<crate::worktree::IndexPersistedOrInMemory>,
     submodules: Submodule,
     index_worktree_options: index_worktree::Options,
+    head_tree: Option<gix_hash::ObjectId>,
+    tree_index_rewrites: Option<gix_diff::Rewrites>,
     should_interrupt: Option<OwnedOrStaticAtomicBool>,
 }
 
+/// An iterator for changes between the head tree and the index, followed by changes between the index and the worktree,
+/// as created by [`Platform::into_iter()`].
+///
+/// Note that the changes between the head tree and the index are computed in advance, and the limitations of the
+/// [index-worktree iterator](index_worktree::Iter) apply for all remaining items.
+pub struct Iter {
+    tree_index: std::vec::IntoIter<tree_index::Change>,
+    index_worktree: index_worktree::Iter,
+}
+
 /// How to obtain a submodule's status.
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum Submodule {
@@ -61,6 +73,18 @@ impl Default for Submodule {
     }
 }
 
+/// How ignored files should be handled.
+#[derive(Default, Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum IgnoredFiles {
+    /// Do not show any ignored files.
+    #[default]
+    None,
+    /// If possible, collapse ignored files into their parent folders, similar to `git status --ignored=traditional`.
+    Collapsed,
+    /// Show each individual ignored file, similar to `git status --ignored=matching --untracked-files=all`.
+    Files,
+}
+
 /// The error returned by [status()](Repository::status).
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
@@ -69,6 +93,8 @@ pub enum Error {
     DirwalkOptions(#[from] config::boolean::Error),
     #[error(transparent)]
     ConfigureUntrackedFiles(#[from] config::key::GenericErrorWithValue),
+    #[error(transparent)]
+    DiffRenames(#[from] crate::diff::new_rewrites::Error),
 }
 
 /// Status
@@ -86,6 +112,9 @@ impl Repository {
     /// collapsed by default. If that needs to be controlled,
     /// [configure the directory walk explicitly](Platform::dirwalk_options) or more [implicitly](Platform::untracked_files).
     ///
+    /// Renames between the head tree and the index are tracked as configured by `diff.renames`, which defaults
+    /// to tracking renames just like in `git`.
+    ///
     /// Pass `progress` to receive progress information on file modifications on this repository.
     /// Use [`progress::Discard`](crate::progress::Discard) to discard all progress information.
     ///
@@ -94,16 +123,25 @@ impl Repository {
     /// Whereas Git runs the index-modified check before the directory walk to set entries
     /// as up-to-date to (potentially) safe some disk-access, we run both in parallel which
     /// ultimately is much faster.
+    ///
+    /// Further, `status.renames` isn't respected.
     pub fn status<P>(&self, progress: P) -> Result<Platform<'_, P>, Error>
     where
         P: gix_features::progress::Progress + 'static,
     {
+        let tree_index_rewrites = if self.config.resolved.boolean(config::tree::Diff::RENAMES).is_some() {
+            self.config.diff_renames()?
+        } else {
+            Some(Default::default())
+        };
         let platform = Platform {
             repo: self,
             progress,
             index: None,
             submodules: Submodule::default(),
             should_interrupt: None,
+            head_tree: None,
+            tree_index_rewrites,
             index_worktree_options: index_worktree::Options {
                 sorting: None,
                 dirwalk_options: Some(self.dirwalk_options()?),
@@ -140,23 +178,41 @@ pub mod is_dirty {
         StatusPlatform(#[from] crate::status::Error),
         #[error(transparent)]
         CreateStatusIterator(#[from] crate::status::index_worktree::iter::Error),
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        HeadTreeId(#[from] crate::reference::head_tree_id::Error),
+        #[error(transparent)]
+        TreeIndexStatus(#[from] crate::status::tree_index::Error),
     }
 
     impl Repository {
         /// Returns `true` if the repository is dirty.
         /// This means it's changed in one of the following ways:
         ///
-        /// * the index was changed in comparison to its working tree
+        /// * the index was changed in comparison to the tree of `HEAD`
         /// * the working tree was changed in comparison to the index
         /// * submodules are taken in consideration, along with their `ignore` and `isActive` configuration
         ///
         /// Note that *untracked files* do *not* affect this flag.
-        ///
-        /// ### Incomplete Implementation Warning
-        ///
-        /// Currently, this does not compute changes between the head and the index.
-        // TODO: use iterator which also tests for head->index changes.
         pub fn is_dirty(&self) -> Result<bool, Error> {
+            let index = self.index_or_empty()?;
+            let head_tree_id = if self.head()?.is_unborn() {
+                gix_hash::ObjectId::empty_tree(self.object_hash())
+            } else {
+                self.head_tree_id()?.detach()
+            };
+            let mut index_is_dirty = false;
+            self.tree_index_status(&head_tree_id, &index, None, None, |_change| {
+                index_is_dirty = true;
+                crate::object::tree::diff::Action::Cancel
+            })?;
+            if index_is_dirty {
+                return Ok(true);
+            }
+
             let is_dirty = self
                 .status(gix_features::progress::Discard)?
                 .index_worktree_rewrites(None)
@@ -164,6 +220,7 @@ pub mod is_dirty {
                 .index_worktree_options_mut(|opts| {
                     opts.dirwalk_options = None;
                 })
+                .index(index.into())
                 .into_index_worktree_iter(Vec::new())?
                 .take_while(Result::is_ok)
                 .next()
@@ -178,3 +235,15 @@ mod platform;
 ///
 #[allow(clippy::empty_docs)]
 pub mod index_worktree;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod tree_index;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod iter;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod porcelain;
@@ -1,4 +1,4 @@
-use crate::status::{index_worktree, OwnedOrStaticAtomicBool, Platform, Submodule, UntrackedFiles};
+use crate::status::{index_worktree, IgnoredFiles, OwnedOrStaticAtomicBool, Platform, Submodule, UntrackedFiles};
 use std::sync::atomic::AtomicBool;
 
 /// Builder
@@ -40,6 +40,19 @@ where
         self.dirwalk_options(|cb| cb.emit_untracked(mode))
     }
 
+    /// A simple way to explicitly set the desired way of listing `ignored_files`, which aren't listed by default.
+    ///
+    /// Note that if no dirwalk options are present anymore, for instance after setting [untracked files](Self::untracked_files())
+    /// to [`None`](UntrackedFiles::None), this call has no effect.
+    pub fn ignored_files(self, ignored_files: IgnoredFiles) -> Self {
+        let mode = match ignored_files {
+            IgnoredFiles::None => None,
+            IgnoredFiles::Collapsed => Some(gix_dir::walk::EmissionMode::CollapseDirectory),
+            IgnoredFiles::Files => Some(gix_dir::walk::EmissionMode::Matching),
+        };
+        self.dirwalk_options(|cb| cb.emit_ignored(mode))
+    }
+
     /// Set the interrupt flag to `should_interrupt`, which typically is an application-wide flag
     /// that is ultimately controlled by user interrupts.
     ///
@@ -82,6 +95,22 @@ where
         self
     }
 
+    /// Set the `id` of the tree to compare the index with, instead of the tree of the `HEAD` commit, which is the default.
+    ///
+    /// If `HEAD` is unborn, the index is compared with the empty tree, making all of its entries additions.
+    pub fn head_tree(mut self, id: impl Into<gix_hash::ObjectId>) -> Self {
+        self.head_tree = Some(id.into());
+        self
+    }
+
+    /// Configure the tree-to-index rename tracking with `rewrites`, which is configured by `diff.renames` by default.
+    ///
+    /// If `None`, renames of staged files are shown as deletion and addition.
+    pub fn tree_index_rewrites(mut self, rewrites: impl Into<Option<gix_diff::Rewrites>>) -> Self {
+        self.tree_index_rewrites = rewrites.into();
+        self
+    }
+
     /// Configure the index-to-worktree rename tracking with `rewrites`, which is `None` by default.
     ///
     /// Note that Git does not have configuration related to rename tracking of changes between the index
@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+
+use crate::bstr::{BStr, BString};
+use crate::status::{index_worktree, iter::Item, tree_index};
+use gix_status::index_as_worktree::{Change, Conflict, EntryStatus};
+
+/// The single-letter status code used in the short format of `git status`, as `X` for the index and `Y` for the worktree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Code {
+    /// There was no change, displayed as space.
+    Unmodified,
+    /// The content or the executable bit changed, displayed as `M`.
+    Modified,
+    /// The kind of the entry changed, for instance from file to symlink, displayed as `T`.
+    TypeChange,
+    /// The entry was added, displayed as `A`.
+    Added,
+    /// The entry was deleted, displayed as `D`.
+    Deleted,
+    /// The entry was renamed, displayed as `R`.
+    Renamed,
+    /// The entry was copied, displayed as `C`.
+    Copied,
+    /// The entry is part of a merge-conflict, displayed as `U`.
+    Unmerged,
+    /// The entry is untracked, displayed as `?`.
+    Untracked,
+    /// The entry is ignored, displayed as `!`.
+    Ignored,
+}
+
+impl Code {
+    /// Return the character `git` uses to display this code.
+    pub fn as_char(&self) -> char {
+        match self {
+            Code::Unmodified => ' ',
+            Code::Modified => 'M',
+            Code::TypeChange => 'T',
+            Code::Added => 'A',
+            Code::Deleted => 'D',
+            Code::Renamed => 'R',
+            Code::Copied => 'C',
+            Code::Unmerged => 'U',
+            Code::Untracked => '?',
+            Code::Ignored => '!',
+        }
+    }
+}
+
+impl std::fmt::Display for Code {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use std::fmt::Write;
+        f.write_char(self.as_char())
+    }
+}
+
+/// A line in the short format of `git status`, like `XY path` or `XY source -> path`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// The status of the entry in the index compared to the head tree, the `X` in `XY`.
+    pub index: Code,
+    /// The status of the entry in the worktree compared to the index, the `Y` in `XY`.
+    pub worktree: Code,
+    /// The repository-relative path of the entry, with a trailing slash if it's a directory.
+    pub rela_path: BString,
+    /// The repository-relative path of the source of a rename or copy.
+    pub source_rela_path: Option<BString>,
+}
+
+impl Entry {
+    fn unmodified(rela_path: &BStr) -> Self {
+        Entry {
+            index: Code::Unmodified,
+            worktree: Code::Unmodified,
+            rela_path: rela_path.to_owned(),
+            source_rela_path: None,
+        }
+    }
+}
+
+/// Display the entry like `git status --porcelain` would, but without quoting paths.
+impl std::fmt::Display for Entry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{} ", self.index, self.worktree)?;
+        if let Some(source) = &self.source_rela_path {
+            write!(f, "{source} -> ")?;
+        }
+        write!(f, "{}", self.rela_path)
+    }
+}
+
+/// Turn all status `items` into entries of the short format of `git status`, with one entry per path.
+///
+/// Like in `git`, changes to tracked files come first, sorted by path, followed by untracked files and ignored files.
+/// Items that don't affect the short format, like entries that merely need an index update, are skipped.
+pub fn entries(items: impl IntoIterator<Item = Item>) -> Vec<Entry> {
+    fn entry_at<'a>(tracked: &'a mut BTreeMap<BString, Entry>, rela_path: &BStr) -> &'a mut Entry {
+        tracked
+            .entry(rela_path.to_owned())
+            .or_insert_with(|| Entry::unmodified(rela_path))
+    }
+    let mut tracked = BTreeMap::new();
+    let mut untracked = Vec::new();
+    let mut ignored = Vec::new();
+    for item in items {
+        match item {
+            Item::TreeIndex(change) => {
+                let (code, source) = match &change {
+                    tree_index::Change::Addition { .. } => (Code::Added, None),
+                    tree_index::Change::Deletion { .. } => (Code::Deleted, None),
+                    tree_index::Change::Modification {
+                        previous_entry_mode,
+                        entry_mode,
+                        ..
+                    } => (
+                        if is_type_change(*previous_entry_mode, *entry_mode) {
+                            Code::TypeChange
+                        } else {
+                            Code::Modified
+                        },
+                        None,
+                    ),
+                    tree_index::Change::Rewrite {
+                        source_location, copy, ..
+                    } => (
+                        if *copy { Code::Copied } else { Code::Renamed },
+                        Some(source_location.clone()),
+                    ),
+                };
+                let entry = entry_at(&mut tracked, change.location());
+                entry.index = code;
+                entry.source_rela_path = source;
+            }
+            Item::IndexWorktree(item) => match item {
+                index_worktree::iter::Item::Modification { rela_path, status, .. } => {
+                    let (index, worktree) = match status {
+                        EntryStatus::Conflict(conflict) => {
+                            let (index, worktree) = conflict_codes(conflict);
+                            (Some(index), worktree)
+                        }
+                        EntryStatus::Change(Change::Removed) => (None, Code::Deleted),
+                        EntryStatus::Change(Change::Type) => (None, Code::TypeChange),
+                        EntryStatus::Change(Change::Modification { .. } | Change::SubmoduleModification(_)) => {
+                            (None, Code::Modified)
+                        }
+                        EntryStatus::IntentToAdd => (None, Code::Added),
+                        EntryStatus::NeedsUpdate(_) => continue,
+                    };
+                    let entry = entry_at(&mut tracked, rela_path.as_ref());
+                    if let Some(index) = index {
+                        entry.index = index;
+                    }
+                    entry.worktree = worktree;
+                }
+                index_worktree::iter::Item::DirectoryContents { entry, .. } => {
+                    let (code, out) = match entry.status {
+                        gix_dir::entry::Status::Untracked => (Code::Untracked, &mut untracked),
+                        gix_dir::entry::Status::Ignored(_) => (Code::Ignored, &mut ignored),
+                        gix_dir::entry::Status::Pruned | gix_dir::entry::Status::Tracked => continue,
+                    };
+                    let mut rela_path = entry.rela_path;
+                    if matches!(
+                        entry.disk_kind,
+                        Some(gix_dir::entry::Kind::Directory | gix_dir::entry::Kind::Repository)
+                    ) {
+                        rela_path.push(b'/');
+                    }
+                    out.push(Entry {
+                        index: code,
+                        worktree: code,
+                        rela_path,
+                        source_rela_path: None,
+                    });
+                }
+                index_worktree::iter::Item::Rewrite {
+                    source,
+                    dirwalk_entry,
+                    copy,
+                    ..
+                } => {
+                    let entry = entry_at(&mut tracked, dirwalk_entry.rela_path.as_ref());
+                    entry.worktree = if copy { Code::Copied } else { Code::Renamed };
+                    if entry.source_rela_path.is_none() {
+                        entry.source_rela_path = Some(source.rela_path().to_owned());
+                    }
+                }
+            },
+        }
+    }
+    untracked.sort_by(|a, b| a.rela_path.cmp(&b.rela_path));
+    ignored.sort_by(|a, b| a.rela_path.cmp(&b.rela_path));
+    tracked.into_values().chain(untracked).chain(ignored).collect()
+}
+
+/// Return `true` if going from `previous` to `current` is more than a change of the executable bit.
+fn is_type_change(previous: gix_object::tree::EntryMode, current: gix_object::tree::EntryMode) -> bool {
+    use gix_object::tree::EntryKind::*;
+    !matches!(
+        (previous.kind(), current.kind()),
+        (Blob | BlobExecutable, Blob | BlobExecutable) | (Link, Link) | (Commit, Commit) | (Tree, Tree)
+    )
+}
+
+/// Return the `XY` codes `git` uses for `conflict`.
+fn conflict_codes(conflict: Conflict) -> (Code, Code) {
+    match conflict {
+        Conflict::BothDeleted => (Code::Deleted, Code::Deleted),
+        Conflict::AddedByUs => (Code::Added, Code::Unmerged),
+        Conflict::DeletedByThem => (Code::Unmerged, Code::Deleted),
+        Conflict::AddedByThem => (Code::Unmerged, Code::Added),
+        Conflict::DeletedByUs => (Code::Deleted, Code::Unmerged),
+        Conflict::BothAdded => (Code::Added, Code::Added),
+        Conflict::BothModified => (Code::Unmerged, Code::Unmerged),
+    }
+}
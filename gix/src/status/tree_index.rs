@@ -0,0 +1,353 @@
+use crate::bstr::{BStr, BString, ByteSlice};
+use crate::object::tree::diff::Action;
+use crate::{Pathspec, Repository};
+use gix_diff::rewrites::tracker::ChangeKind;
+use gix_hash::ObjectId;
+use gix_object::tree::EntryMode;
+
+/// The error returned by [Repository::tree_index_status()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindTree(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    NotATree(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    TraverseTree(#[from] gix_traverse::tree::breadthfirst::Error),
+    #[error(transparent)]
+    AttributeStack(#[from] crate::config::attribute_stack::Error),
+    #[error(transparent)]
+    ResourceCache(#[from] crate::diff::resource_cache::Error),
+    #[error(transparent)]
+    RewriteTracking(#[from] gix_diff::rewrites::tracker::emit::Error),
+}
+
+/// A change between a tree and the index, as the index was changed to look like the worktree after checking out the tree.
+///
+/// In `git status`, these are the *staged* changes, the ones that would be committed next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// An entry was added to the index and isn't present in the tree.
+    Addition {
+        /// The repository-relative path of the added entry.
+        location: BString,
+        /// The mode of the entry in the index.
+        entry_mode: EntryMode,
+        /// The id of the entry in the index.
+        id: ObjectId,
+    },
+    /// An entry is present in the tree but was removed from the index.
+    Deletion {
+        /// The repository-relative path of the deleted entry.
+        location: BString,
+        /// The mode of the entry in the tree.
+        entry_mode: EntryMode,
+        /// The id of the entry in the tree.
+        id: ObjectId,
+    },
+    /// An entry is present in both the tree and the index, but its mode or id changed.
+    Modification {
+        /// The repository-relative path of the modified entry.
+        location: BString,
+        /// The mode of the entry in the tree.
+        previous_entry_mode: EntryMode,
+        /// The id of the entry in the tree.
+        previous_id: ObjectId,
+        /// The mode of the entry in the index.
+        entry_mode: EntryMode,
+        /// The id of the entry in the index.
+        id: ObjectId,
+    },
+    /// An entry in the tree was renamed or copied to a new location in the index, which is only emitted if
+    /// rewrite tracking is enabled.
+    Rewrite {
+        /// The repository-relative path of the entry in the tree.
+        source_location: BString,
+        /// The mode of the entry in the tree.
+        source_entry_mode: EntryMode,
+        /// The id of the entry in the tree.
+        source_id: ObjectId,
+        /// The repository-relative path of the entry in the index.
+        location: BString,
+        /// The mode of the entry in the index.
+        entry_mode: EntryMode,
+        /// The id of the entry in the index.
+        id: ObjectId,
+        /// If `true`, the entry was copied, and `source_location` still exists in the index.
+        /// Otherwise, it was renamed.
+        copy: bool,
+    },
+}
+
+/// Access
+impl Change {
+    /// Return the repository-relative path of the entry in the index, or in the tree if it was deleted.
+    pub fn location(&self) -> &BStr {
+        match self {
+            Change::Addition { location, .. }
+            | Change::Deletion { location, .. }
+            | Change::Modification { location, .. }
+            | Change::Rewrite { location, .. } => location.as_bstr(),
+        }
+    }
+}
+
+/// The part of a change that is known to the rewrite tracker.
+#[derive(Clone)]
+enum TrackedChange {
+    Addition {
+        entry_mode: EntryMode,
+        id: ObjectId,
+    },
+    Deletion {
+        entry_mode: EntryMode,
+        id: ObjectId,
+    },
+    Modification {
+        previous_entry_mode: EntryMode,
+        previous_id: ObjectId,
+        entry_mode: EntryMode,
+        id: ObjectId,
+    },
+}
+
+impl TrackedChange {
+    fn into_change(self, location: &BStr) -> Change {
+        let location = location.to_owned();
+        match self {
+            TrackedChange::Addition { entry_mode, id } => Change::Addition {
+                location,
+                entry_mode,
+                id,
+            },
+            TrackedChange::Deletion { entry_mode, id } => Change::Deletion {
+                location,
+                entry_mode,
+                id,
+            },
+            TrackedChange::Modification {
+                previous_entry_mode,
+                previous_id,
+                entry_mode,
+                id,
+            } => Change::Modification {
+                location,
+                previous_entry_mode,
+                previous_id,
+                entry_mode,
+                id,
+            },
+        }
+    }
+}
+
+impl gix_diff::rewrites::tracker::Change for TrackedChange {
+    fn id(&self) -> &gix_hash::oid {
+        self.id_and_entry_mode().0
+    }
+
+    fn kind(&self) -> ChangeKind {
+        match self {
+            TrackedChange::Addition { .. } => ChangeKind::Addition,
+            TrackedChange::Deletion { .. } => ChangeKind::Deletion,
+            TrackedChange::Modification { .. } => ChangeKind::Modification,
+        }
+    }
+
+    fn entry_mode(&self) -> EntryMode {
+        self.id_and_entry_mode().1
+    }
+
+    fn id_and_entry_mode(&self) -> (&gix_hash::oid, EntryMode) {
+        match self {
+            TrackedChange::Addition { entry_mode, id }
+            | TrackedChange::Deletion { entry_mode, id }
+            | TrackedChange::Modification { entry_mode, id, .. } => (id, *entry_mode),
+        }
+    }
+}
+
+impl Repository {
+    /// Compare the tree with `tree_id` to the `index` and call `cb` for each [change](Change), which are the changes that
+    /// would be committed next, similar to `git diff --cached`.
+    ///
+    /// * `pathspec`
+    ///     - If `Some`, only entries matching the pathspec are considered.
+    /// * `rewrites`
+    ///     - If `Some`, additions and deletions are paired up to [rewrites](Change::Rewrite) if they are similar enough.
+    ///       Otherwise, changes are emitted in the order of their paths.
+    /// * `cb`
+    ///     - Called for each change, and the operation stops early if it returns [`Action::Cancel`].
+    ///
+    /// Returns the outcome of the rewrite tracking, if it was enabled and not cancelled.
+    ///
+    /// Note that conflicting entries and entries that are only intended to be added are ignored, as they are
+    /// part of the [index-worktree status](Repository::index_worktree_status()).
+    /// Sparse directory entries are ignored as well.
+    pub fn tree_index_status(
+        &self,
+        tree_id: &gix_hash::oid,
+        index: &gix_index::State,
+        mut pathspec: Option<&mut Pathspec<'_>>,
+        rewrites: Option<gix_diff::Rewrites>,
+        mut cb: impl FnMut(Change) -> Action,
+    ) -> Result<Option<gix_diff::rewrites::Outcome>, Error> {
+        let _span = gix_trace::coarse!("gix::tree_index_status");
+        let mut is_included = |location: &BStr| {
+            pathspec
+                .as_mut()
+                .map_or(true, |spec| spec.is_included(location, Some(false)))
+        };
+
+        let mut all_tree_entries = self
+            .find_object(tree_id)?
+            .try_into_tree()?
+            .traverse()
+            .breadthfirst
+            .files()?;
+        all_tree_entries.retain(|entry| !entry.mode.is_tree());
+        all_tree_entries.sort_by(|a, b| a.filepath.cmp(&b.filepath));
+
+        let mut tracker = rewrites.map(gix_diff::rewrites::Tracker::<TrackedChange>::new);
+        let mut emit = |change: TrackedChange, location: &BStr| -> Action {
+            match tracker.as_mut() {
+                Some(tracker) => match tracker.try_push_change(change, location) {
+                    None => Action::Continue,
+                    Some(change) => cb(change.into_change(location)),
+                },
+                None => cb(change.into_change(location)),
+            }
+        };
+
+        let mut tree_entries = all_tree_entries.iter().peekable();
+        let mut index_entries = index
+            .entries()
+            .iter()
+            .filter(|entry| !entry.mode.is_sparse() && !entry.flags.contains(gix_index::entry::Flags::INTENT_TO_ADD))
+            .map(|entry| (entry, entry.path(index)))
+            .peekable();
+        loop {
+            let action = match (tree_entries.peek(), index_entries.peek()) {
+                (None, None) => break,
+                (Some(tree_entry), Some((index_entry, index_path))) if tree_entry.filepath == *index_path => {
+                    let (tree_entry, (index_entry, index_path)) = (
+                        tree_entries.next().expect("peeked"),
+                        index_entries.next().expect("peeked"),
+                    );
+                    if index_entry.stage() != gix_index::entry::Stage::Unconflicted {
+                        while index_entries.next_if(|(_, path)| *path == index_path).is_some() {}
+                        continue;
+                    }
+                    let entry_mode = index_mode(index_entry);
+                    if (entry_mode == tree_entry.mode && index_entry.id == tree_entry.oid) || !is_included(index_path) {
+                        continue;
+                    }
+                    emit(
+                        TrackedChange::Modification {
+                            previous_entry_mode: tree_entry.mode,
+                            previous_id: tree_entry.oid,
+                            entry_mode,
+                            id: index_entry.id,
+                        },
+                        index_path,
+                    )
+                }
+                (Some(tree_entry), index_entry)
+                    if index_entry.map_or(true, |(_, index_path)| tree_entry.filepath.as_bstr() < *index_path) =>
+                {
+                    let tree_entry = tree_entries.next().expect("peeked");
+                    if !is_included(tree_entry.filepath.as_ref()) {
+                        continue;
+                    }
+                    emit(
+                        TrackedChange::Deletion {
+                            entry_mode: tree_entry.mode,
+                            id: tree_entry.oid,
+                        },
+                        tree_entry.filepath.as_ref(),
+                    )
+                }
+                _ => {
+                    let (index_entry, index_path) = index_entries.next().expect("peeked");
+                    if index_entry.stage() != gix_index::entry::Stage::Unconflicted {
+                        while index_entries.next_if(|(_, path)| *path == index_path).is_some() {}
+                        continue;
+                    }
+                    if !is_included(index_path) {
+                        continue;
+                    }
+                    emit(
+                        TrackedChange::Addition {
+                            entry_mode: index_mode(index_entry),
+                            id: index_entry.id,
+                        },
+                        index_path,
+                    )
+                }
+            };
+            if action == Action::Cancel {
+                return Ok(None);
+            }
+        }
+
+        let Some(mut tracker) = tracker else {
+            return Ok(None);
+        };
+        let mut diff_cache = crate::diff::resource_cache(
+            self,
+            gix_diff::blob::pipeline::Mode::ToGit,
+            self.attributes_only(index, gix_worktree::stack::state::attributes::Source::IdMapping)?
+                .detach(),
+            Default::default(),
+        )?;
+        let outcome = tracker.emit(
+            |dest, source| {
+                let action = match source {
+                    Some(source) => {
+                        let (id, entry_mode) = gix_diff::rewrites::tracker::Change::id_and_entry_mode(&dest.change);
+                        cb(Change::Rewrite {
+                            source_location: source.location.to_owned(),
+                            source_entry_mode: source.entry_mode,
+                            source_id: source.id,
+                            location: dest.location.to_owned(),
+                            entry_mode,
+                            id: id.to_owned(),
+                            copy: source.kind == gix_diff::rewrites::tracker::visit::SourceKind::Copy,
+                        })
+                    }
+                    None => cb(dest.change.into_change(dest.location)),
+                };
+                match action {
+                    Action::Continue => gix_diff::tree::visit::Action::Continue,
+                    Action::Cancel => gix_diff::tree::visit::Action::Cancel,
+                }
+            },
+            &mut diff_cache,
+            &self.objects,
+            |push| {
+                for entry in &all_tree_entries {
+                    push(
+                        TrackedChange::Modification {
+                            previous_entry_mode: entry.mode,
+                            previous_id: entry.oid,
+                            entry_mode: entry.mode,
+                            id: entry.oid,
+                        },
+                        entry.filepath.as_ref(),
+                    );
+                }
+                Ok::<_, std::convert::Infallible>(())
+            },
+        )?;
+        Ok(Some(outcome))
+    }
+}
+
+/// Return the mode of `entry` as tree entry mode, treating unknown modes like blobs.
+fn index_mode(entry: &gix_index::Entry) -> EntryMode {
+    entry
+        .mode
+        .to_tree_entry_mode()
+        .unwrap_or_else(|| gix_object::tree::EntryKind::Blob.into())
+}
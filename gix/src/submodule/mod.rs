@@ -21,6 +21,11 @@ pub(crate) const MODULES_FILE: &str = ".gitmodules";
 mod errors;
 pub use errors::*;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(all(feature = "blocking-network-client", feature = "worktree-mutation"))]
+pub mod update;
+
 /// A platform maintaining state needed to interact with submodules, created by [`Repository::submodules()].
 pub(crate) struct SharedState<'repo> {
     pub(crate) repo: &'repo Repository,
@@ -0,0 +1,415 @@
+use std::{
+    borrow::Cow,
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Target,
+};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    config::tree::Core,
+    remote,
+    submodule::{config, head_id, index_id, modules, open},
+    Repository, Submodule,
+};
+
+/// The error returned by [`Repository::update_submodules()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Modules(#[from] modules::Error),
+    #[error(transparent)]
+    Path(#[from] config::path::Error),
+    #[error(transparent)]
+    Url(#[from] config::url::Error),
+    #[error(transparent)]
+    UpdateConfiguration(#[from] config::update::Error),
+    #[error("Could not read the `shallow` field of submodule '{name}'")]
+    ShallowConfiguration {
+        name: BString,
+        source: gix_config::value::Error,
+    },
+    #[error(transparent)]
+    IndexId(#[from] index_id::Error),
+    #[error(transparent)]
+    HeadId(#[from] head_id::Error),
+    #[error(transparent)]
+    Open(#[from] open::Error),
+    #[error(transparent)]
+    FindRemote(#[from] remote::find::existing::Error),
+    #[error("The relative url \"{url}\" of submodule '{name}' can't be resolved against \"{base}\"")]
+    RelativeUrl { name: BString, url: BString, base: BString },
+    #[error(transparent)]
+    UrlParse(#[from] gix_url::parse::Error),
+    #[error(transparent)]
+    PrepareClone(#[from] crate::clone::Error),
+    #[error(transparent)]
+    Clone(#[from] crate::clone::fetch::Error),
+    #[error(transparent)]
+    Connect(#[from] remote::connect::Error),
+    #[error(transparent)]
+    PrepareFetch(#[from] remote::fetch::prepare::Error),
+    #[error(transparent)]
+    Fetch(#[from] remote::fetch::Error),
+    #[error("The commit {id} of submodule '{name}' could not be fetched from its remote")]
+    MissingCommit { name: BString, id: gix_hash::ObjectId },
+    #[error(transparent)]
+    LoadConfig(#[from] gix_config::file::init::from_paths::Error),
+    #[error(transparent)]
+    SetConfig(#[from] gix_config::file::set_raw_value::Error),
+    #[error("Failed to write the configuration of the submodule or the superproject")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to detach HEAD of the submodule at the commit to check out")]
+    DetachHead(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    Checkout(#[from] crate::clone::checkout::main_worktree::Error),
+}
+
+/// Options for [`Repository::update_submodules()`].
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The amount of levels of nested submodules to update, or `None` to not limit it.
+    ///
+    /// With `Some(0)`, only the submodules of the repository itself are updated.
+    pub max_depth: Option<usize>,
+}
+
+/// The progress ids used in [`Repository::update_submodules()`].
+///
+/// Use this information to selectively extract the progress of interest in case the parent application has custom visualization.
+#[derive(Debug, Copy, Clone)]
+pub enum ProgressId {
+    /// The amount of submodules that were processed thus far.
+    Submodules,
+}
+
+impl From<ProgressId> for gix_features::progress::Id {
+    fn from(v: ProgressId) -> Self {
+        match v {
+            ProgressId::Submodules => *b"SMUP",
+        }
+    }
+}
+
+/// What happened to a submodule during [`Repository::update_submodules()`].
+#[derive(Debug)]
+pub enum Status {
+    /// The submodule is configured with `update = none`.
+    SkippedUpdateNone,
+    /// The submodule is configured with `update = !command`, which isn't supported.
+    SkippedUpdateCommand,
+    /// The superproject's index doesn't have a gitlink for the submodule, so there is nothing to check out.
+    SkippedNotInIndex,
+    /// The repository of the submodule already exists, but its `HEAD` or working tree doesn't match the superproject.
+    ///
+    /// Updating existing checkouts isn't supported.
+    SkippedExisting {
+        /// The commit the submodule is supposed to be at according to the superproject's index.
+        index_id: gix_hash::ObjectId,
+        /// The commit currently checked out in the submodule, if its `HEAD` could be obtained.
+        checked_out_head_id: Option<gix_hash::ObjectId>,
+    },
+    /// The repository of the submodule exists and is checked out at the commit recorded in the superproject already.
+    UpToDate {
+        /// The checked out commit.
+        id: gix_hash::ObjectId,
+    },
+    /// The submodule was cloned and the commit recorded in the superproject was checked out on a detached `HEAD`.
+    CheckedOut {
+        /// The checked out commit.
+        id: gix_hash::ObjectId,
+        /// Information about the checkout of the working tree.
+        checkout: gix_worktree_state::checkout::Outcome,
+    },
+}
+
+/// A submodule that was processed by [`Repository::update_submodules()`].
+#[derive(Debug)]
+pub struct Item {
+    /// The name of the submodule.
+    pub name: BString,
+    /// The path to the submodule relative to the working tree of its superproject.
+    pub path: BString,
+    /// What happened to the submodule.
+    pub status: Status,
+    /// The nested submodules that were updated within this submodule, which is empty if the depth limit was reached.
+    pub submodules: Vec<Item>,
+}
+
+/// The outcome of [`Repository::update_submodules()`].
+#[derive(Debug, Default)]
+pub struct Outcome {
+    /// The submodules of the repository, in the order they are configured in.
+    pub submodules: Vec<Item>,
+}
+
+impl Repository {
+    /// Initialize, fetch and check out all submodules configured in `.gitmodules` recursively, similar to
+    /// `git submodule update --init --recursive`, which is what `git clone --recurse-submodules` does after checking out
+    /// the superproject.
+    ///
+    /// Each submodule is cloned into `.git/modules/<name>` from its url, with relative urls being resolved against the url
+    /// of the default remote of this repository, or against its working tree if there is none. The commit recorded in the index
+    /// is fetched explicitly if it isn't reachable from the remote branches, and is then checked out on a detached `HEAD`.
+    /// Submodules marked as `shallow` are fetched with a depth of 1, and those with `update = none` are skipped.
+    /// The url of each cloned submodule is recorded in the local configuration file, marking it as active, but note that the
+    /// configuration snapshot of this instance isn't updated.
+    ///
+    /// Submodules whose repository exists already are left as they are, but nested submodules within them are updated.
+    /// Use [`Options::max_depth`] to limit how deeply nested submodules are updated.
+    ///
+    /// `progress` receives one child per submodule, and `should_interrupt` is checked to stop the operation.
+    pub fn update_submodules<P>(
+        &self,
+        mut progress: P,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Outcome, Error>
+    where
+        P: crate::NestedProgress,
+        P::SubProgress: 'static,
+    {
+        let _span = gix_trace::coarse!("gix::Repository::update_submodules()");
+        Ok(Outcome {
+            submodules: self.update_submodules_inner(&mut progress, should_interrupt, options.max_depth)?,
+        })
+    }
+
+    fn update_submodules_inner(
+        &self,
+        progress: &mut dyn crate::DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        max_depth: Option<usize>,
+    ) -> Result<Vec<Item>, Error> {
+        let Some(submodules) = self.submodules()? else {
+            return Ok(Vec::new());
+        };
+        let submodules: Vec<_> = submodules.collect();
+        progress.init(Some(submodules.len()), crate::progress::count("submodules"));
+
+        let mut out = Vec::with_capacity(submodules.len());
+        for sm in submodules {
+            let path = sm.path()?.into_owned();
+            let mut sm_progress = progress.add_child_with_id(path.to_string(), ProgressId::Submodules.into());
+            let (status, sm_repo) = update_one(&sm, &mut sm_progress, should_interrupt)?;
+            let submodules = match (sm_repo, max_depth) {
+                (Some(sm_repo), None) => sm_repo.update_submodules_inner(&mut sm_progress, should_interrupt, None)?,
+                (Some(sm_repo), Some(depth)) if depth > 0 => {
+                    sm_repo.update_submodules_inner(&mut sm_progress, should_interrupt, Some(depth - 1))?
+                }
+                _ => Vec::new(),
+            };
+            out.push(Item {
+                name: sm.name().to_owned(),
+                path,
+                status,
+                submodules,
+            });
+            progress.inc();
+        }
+        Ok(out)
+    }
+}
+
+/// Bring `sm` to the commit recorded in the superproject and return what happened, along with the submodule repository
+/// if nested submodules should be updated as well.
+fn update_one(
+    sm: &Submodule<'_>,
+    progress: &mut crate::progress::BoxedDynNestedProgress,
+    should_interrupt: &AtomicBool,
+) -> Result<(Status, Option<Repository>), Error> {
+    let update = sm.update()?.unwrap_or_default();
+    match update {
+        config::Update::None => return Ok((Status::SkippedUpdateNone, None)),
+        config::Update::Command(_) => return Ok((Status::SkippedUpdateCommand, None)),
+        config::Update::Checkout | config::Update::Rebase | config::Update::Merge => {}
+    }
+    let Some(id) = sm.index_id()? else {
+        return Ok((Status::SkippedNotInIndex, None));
+    };
+
+    if sm.state()?.repository_exists {
+        let sm_repo = sm.open()?;
+        let checked_out_head_id = sm_repo
+            .as_ref()
+            .and_then(|repo| repo.head_id().ok())
+            .map(crate::Id::detach);
+        let status = if checked_out_head_id == Some(id) && sm.state()?.worktree_checkout {
+            Status::UpToDate { id }
+        } else {
+            Status::SkippedExisting {
+                index_id: id,
+                checked_out_head_id,
+            }
+        };
+        return Ok((status, sm_repo));
+    }
+
+    let repo = sm.state.repo;
+    let name = sm.name();
+    let url = resolve_url(repo, name, sm.url()?)?;
+    let is_shallow = sm
+        .shallow()
+        .map_err(|err| Error::ShallowConfiguration {
+            name: name.to_owned(),
+            source: err,
+        })?
+        .unwrap_or_default();
+    let shallow = if is_shallow {
+        remote::fetch::Shallow::DepthAtRemote(1.try_into().expect("non-zero"))
+    } else {
+        remote::fetch::Shallow::NoChange
+    };
+
+    let git_dir = sm.git_dir();
+    let work_dir = sm.work_dir()?;
+    std::fs::create_dir_all(&git_dir)?;
+    std::fs::create_dir_all(&work_dir)?;
+    let (sm_repo, _outcome) = crate::clone::PrepareFetch::new(
+        url.clone(),
+        &git_dir,
+        crate::create::Kind::Bare,
+        Default::default(),
+        repo.options.clone(),
+    )?
+    .with_shallow(shallow.clone())
+    .fetch_only(&mut *progress, should_interrupt)?;
+
+    if !sm_repo.has_object(id) {
+        let remote = sm_repo
+            .find_default_remote(remote::Direction::Fetch)
+            .expect("a remote was configured when cloning")?;
+        let spec = gix_refspec::parse(
+            id.to_string().as_bytes().as_bstr(),
+            gix_refspec::parse::Operation::Fetch,
+        )
+        .expect("object ids are valid refspecs")
+        .to_owned();
+        remote
+            .connect(remote::Direction::Fetch)?
+            .prepare_fetch(
+                &mut *progress,
+                remote::ref_map::Options {
+                    extra_refspecs: vec![spec],
+                    ..Default::default()
+                },
+            )?
+            .with_shallow(shallow)
+            .receive(&mut *progress, should_interrupt)?;
+        if !sm_repo.has_object(id) {
+            return Err(Error::MissingCommit {
+                name: name.to_owned(),
+                id,
+            });
+        }
+    }
+
+    let (relative_work_dir, relative_git_dir) = relative_locations(repo, &git_dir, &work_dir);
+    let mut config = gix_config::File::from_path_no_includes(git_dir.join("config"), gix_config::Source::Local)?;
+    config.set_raw_value(&Core::BARE, "false")?;
+    config.set_raw_value(&Core::WORKTREE, gix_path::into_bstr(relative_work_dir).as_ref())?;
+    std::fs::write(git_dir.join("config"), config.to_bstring())?;
+    std::fs::write(
+        work_dir.join(gix_discover::DOT_GIT_DIR),
+        format!(
+            "gitdir: {}\n",
+            gix_path::to_unix_separators(gix_path::into_bstr(relative_git_dir))
+        ),
+    )?;
+    drop(sm_repo);
+
+    let mut superproject_config =
+        gix_config::File::from_path_no_includes(repo.common_dir().join("config"), gix_config::Source::Local)?;
+    superproject_config.set_raw_value_by("submodule", Some(name), "url", url.to_bstring().as_bstr())?;
+    superproject_config.set_raw_value_by("submodule", Some(name), "active", "true")?;
+    std::fs::write(repo.common_dir().join("config"), superproject_config.to_bstring())?;
+
+    let sm_repo = sm.open()?.expect("just created");
+    sm_repo.edit_reference(RefEdit {
+        change: Change::Update {
+            log: LogChange {
+                mode: RefLog::AndReference,
+                force_create_reflog: false,
+                message: format!("checkout: moving to {id}").into(),
+            },
+            expected: PreviousValue::Any,
+            new: Target::Peeled(id),
+        },
+        name: "HEAD".try_into().expect("valid"),
+        deref: false,
+    })?;
+    let (sm_repo, checkout) =
+        crate::clone::PrepareCheckout::new(sm_repo).main_worktree(&mut *progress, should_interrupt)?;
+    Ok((Status::CheckedOut { id, checkout }, Some(sm_repo)))
+}
+
+/// Resolve `url` relative to the url of the default remote of `repo` if it starts with `./` or `../`, like `git` does.
+/// Without a remote, it's relative to the working tree of `repo`.
+fn resolve_url(repo: &Repository, name: &BStr, url: gix_url::Url) -> Result<gix_url::Url, Error> {
+    if url.scheme != gix_url::Scheme::File || !(url.path.starts_with(b"./") || url.path.starts_with(b"../")) {
+        return Ok(url);
+    }
+    let mut base = match repo.find_default_remote(remote::Direction::Fetch).transpose()? {
+        Some(remote) => remote.url(remote::Direction::Fetch).cloned(),
+        None => None,
+    }
+    .map_or_else(
+        || {
+            let dir = repo.work_dir().unwrap_or_else(|| repo.git_dir());
+            gix_url::Url::from_bytes(gix_path::into_bstr(dir).as_ref())
+        },
+        Ok,
+    )?;
+
+    let mut base_path: BString = base.path.trim_end_with(|c| c == '/').into();
+    let mut relative = url.path.as_bstr();
+    loop {
+        if let Some(rest) = relative.strip_prefix(b"./") {
+            relative = rest.as_bstr();
+        } else if let Some(rest) = relative.strip_prefix(b"../") {
+            relative = rest.as_bstr();
+            let Some(pos) = base_path.rfind_byte(b'/').filter(|_| !base_path.is_empty()) else {
+                return Err(Error::RelativeUrl {
+                    name: name.to_owned(),
+                    url: url.path.clone(),
+                    base: base.to_bstring(),
+                });
+            };
+            base_path.truncate(pos);
+        } else {
+            break;
+        }
+    }
+    base_path.push_byte(b'/');
+    base_path.push_str(relative);
+    base.path = base_path;
+    Ok(base)
+}
+
+/// Return the path to `work_dir` relative to `git_dir`, and the path to `git_dir` relative to `work_dir`, or absolute paths
+/// if these aren't both contained in the working tree of `repo`.
+fn relative_locations<'a>(repo: &Repository, git_dir: &'a Path, work_dir: &'a Path) -> (Cow<'a, Path>, Cow<'a, Path>) {
+    let relative_to_superproject = |path: &Path| -> Option<PathBuf> {
+        let rela_path = path.strip_prefix(repo.work_dir()?).ok()?;
+        rela_path
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)))
+            .then(|| rela_path.to_owned())
+    };
+    match (relative_to_superproject(git_dir), relative_to_superproject(work_dir)) {
+        (Some(rela_git_dir), Some(rela_work_dir)) => (
+            gix_path::relativize_with_prefix(&rela_work_dir, &rela_git_dir)
+                .into_owned()
+                .into(),
+            gix_path::relativize_with_prefix(&rela_git_dir, &rela_work_dir)
+                .into_owned()
+                .into(),
+        ),
+        _ => (work_dir.into(), git_dir.into()),
+    }
+}
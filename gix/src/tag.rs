@@ -2,16 +2,44 @@
 #![allow(clippy::empty_docs)]
 mod error {
 
-    /// The error returned by [`tag(…)`][crate::Repository::tag()].
+    /// The error returned by [`tag(…)`][crate::Repository::tag()] and [`tag_opts(…)`][crate::Repository::tag_opts()].
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
     pub enum Error {
+        #[error("Tag names must not start with '-', got \"{name}\"")]
+        InvalidName { name: crate::bstr::BString },
         #[error(transparent)]
         ReferenceNameValidation(#[from] gix_ref::name::Error),
         #[error(transparent)]
+        FindTarget(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
         WriteObject(#[from] crate::object::write::Error),
         #[error(transparent)]
         ReferenceEdit(#[from] crate::reference::edit::Error),
+        #[cfg(feature = "signing")]
+        #[error(transparent)]
+        ConfigBoolean(#[from] crate::config::boolean::Error),
+        #[cfg(feature = "signing")]
+        #[error(transparent)]
+        SigningOptions(#[from] crate::signing::options::Error),
+        #[cfg(feature = "signing")]
+        #[error("Could not sign the tag")]
+        Sign(#[from] crate::signing::sign::Error),
+        #[cfg(not(feature = "signing"))]
+        #[error("Tags can only be signed if the `signing` feature is enabled")]
+        SigningUnavailable,
     }
 }
 pub use error::Error;
+
+/// Options for [`Repository::tag_opts()`](crate::Repository::tag_opts()).
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// If `true`, overwrite a tag with the same name if it exists already, or fail otherwise.
+    pub force: bool,
+    /// If `Some(true)`, sign the tag, or don't sign it if `Some(false)`.
+    /// If `None`, the tag is signed if `tag.gpgSign` is enabled.
+    ///
+    /// Signing requires the `signing` feature.
+    pub sign: Option<bool>,
+}
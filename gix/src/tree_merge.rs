@@ -0,0 +1,227 @@
+//! Utilities to merge the entries of trees and indices path by path, for use by stashes and cherry-picks.
+use std::collections::{BTreeMap, BTreeSet};
+
+use gix_hash::ObjectId;
+use gix_index::conflict::Side;
+use gix_object::tree::{EntryKind, EntryMode};
+
+use crate::bstr::{BStr, BString, ByteSlice};
+use crate::Repository;
+
+/// All non-tree entries of a tree or an index, with their mode and id by repository-relative path.
+pub(crate) type Entries = BTreeMap<BString, (EntryMode, ObjectId)>;
+
+/// A path that was changed differently by *ours* and *theirs*.
+pub(crate) struct Conflict {
+    pub path: BString,
+    pub base: Option<(EntryMode, ObjectId)>,
+    pub ours: Option<(EntryMode, ObjectId)>,
+    pub theirs: Option<(EntryMode, ObjectId)>,
+}
+
+/// The result of merging the entries of *ours* and *theirs*.
+#[derive(Default)]
+pub(crate) struct Merge {
+    /// All entries that could be merged without conflict.
+    pub index: Entries,
+    /// All entries to write to the worktree, with conflict markers in blobs that couldn't be merged cleanly.
+    pub worktree: Entries,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Return all non-tree entries of the tree with `id`, recursively.
+pub(crate) fn tree_entries<E>(repo: &Repository, id: ObjectId) -> Result<Entries, E>
+where
+    E: From<crate::object::find::existing::Error>
+        + From<crate::object::try_into::Error>
+        + From<gix_traverse::tree::breadthfirst::Error>,
+{
+    Ok(repo
+        .find_object(id)?
+        .try_into_tree()?
+        .traverse()
+        .breadthfirst
+        .files()?
+        .into_iter()
+        .filter(|entry| !entry.mode.is_tree())
+        .map(|entry| (entry.filepath, (entry.mode, entry.oid)))
+        .collect())
+}
+
+/// Write a tree with all `entries` along with all of its sub-trees, and return its id.
+pub(crate) fn write_tree(repo: &Repository, entries: &Entries) -> Result<ObjectId, crate::object::write::Error> {
+    let entries: Vec<_> = entries
+        .iter()
+        .map(|(path, (mode, id))| (path.as_bstr(), *mode, *id))
+        .collect();
+    write_tree_inner(repo, &entries)
+}
+
+fn write_tree_inner(
+    repo: &Repository,
+    entries: &[(&BStr, EntryMode, ObjectId)],
+) -> Result<ObjectId, crate::object::write::Error> {
+    let mut tree = gix_object::Tree::empty();
+    let mut cursor = 0;
+    while let Some((path, mode, id)) = entries.get(cursor).copied() {
+        match path.find_byte(b'/') {
+            None => {
+                tree.entries.push(gix_object::tree::Entry {
+                    mode,
+                    filename: path.into(),
+                    oid: id,
+                });
+                cursor += 1;
+            }
+            Some(pos) => {
+                let dir = &path[..=pos];
+                let end = entries[cursor..]
+                    .iter()
+                    .position(|(path, _, _)| !path.starts_with(dir))
+                    .map_or(entries.len(), |num_entries| cursor + num_entries);
+                let children: Vec<_> = entries[cursor..end]
+                    .iter()
+                    .map(|(path, mode, id)| (path[pos + 1..].as_bstr(), *mode, *id))
+                    .collect();
+                tree.entries.push(gix_object::tree::Entry {
+                    mode: EntryKind::Tree.into(),
+                    filename: path[..pos].into(),
+                    oid: write_tree_inner(repo, &children)?,
+                });
+                cursor = end;
+            }
+        }
+    }
+    tree.entries.sort();
+    Ok(repo.write_object(&tree)?.detach())
+}
+
+/// Return the path of the first entry that is also a leading directory of another entry, if there is one.
+pub(crate) fn directory_file_conflict(entries: &Entries) -> Option<&BString> {
+    entries.keys().find(|path| {
+        path.find_iter("/")
+            .any(|pos| entries.contains_key(path[..pos].as_bstr()))
+    })
+}
+
+/// Merge the changes of `ours` and `theirs` compared to `base` path by path, and merge the content of blobs that were
+/// changed on both sides, using `labels` for the conflict markers.
+pub(crate) fn merge<E>(
+    repo: &Repository,
+    base: &Entries,
+    ours: &Entries,
+    theirs: &Entries,
+    labels: gix_merge::blob::Labels<'_>,
+) -> Result<Merge, E>
+where
+    E: From<crate::object::find::existing::Error> + From<crate::object::write::Error>,
+{
+    let mut out = Merge::default();
+    let paths: BTreeSet<_> = base.keys().chain(ours.keys()).chain(theirs.keys()).collect();
+    for rela_path in paths {
+        let (b, o, t) = (
+            base.get(rela_path).copied(),
+            ours.get(rela_path).copied(),
+            theirs.get(rela_path).copied(),
+        );
+        let resolution = if o == t || b == t {
+            Some(o)
+        } else if b == o {
+            Some(t)
+        } else {
+            None
+        };
+        if let Some(entry) = resolution {
+            if let Some(entry) = entry {
+                out.index.insert(rela_path.clone(), entry);
+                out.worktree.insert(rela_path.clone(), entry);
+            }
+            continue;
+        }
+
+        match (o, t) {
+            (Some(o), Some(t)) if is_blob(o.0) && is_blob(t.0) => {
+                let base_data = match b {
+                    Some(b) if is_blob(b.0) => repo.find_object(b.1)?.detach().data,
+                    _ => Vec::new(),
+                };
+                let ours_data = repo.find_object(o.1)?.detach().data;
+                let theirs_data = repo.find_object(t.1)?.detach().data;
+                if [&base_data, &ours_data, &theirs_data]
+                    .iter()
+                    .any(|data| data[..data.len().min(8000)].contains(&0))
+                {
+                    out.worktree.insert(rela_path.clone(), o);
+                } else {
+                    let outcome = gix_merge::merge_blobs(
+                        &base_data,
+                        &ours_data,
+                        &theirs_data,
+                        gix_merge::blob::Options {
+                            labels,
+                            ..Default::default()
+                        },
+                    );
+                    let mode = if b.map(|b| b.0) == Some(o.0) { t.0 } else { o.0 };
+                    let entry = (mode, repo.write_blob(&outcome.merged)?.detach());
+                    out.worktree.insert(rela_path.clone(), entry);
+                    if outcome.num_conflicts == 0 {
+                        out.index.insert(rela_path.clone(), entry);
+                        continue;
+                    }
+                }
+            }
+            _ => {
+                if let Some(entry) = o.or(t) {
+                    out.worktree.insert(rela_path.clone(), entry);
+                }
+            }
+        }
+        out.conflicts.push(Conflict {
+            path: rela_path.clone(),
+            base: b,
+            ours: o,
+            theirs: t,
+        });
+    }
+    Ok(out)
+}
+
+fn is_blob(mode: EntryMode) -> bool {
+    matches!(mode.kind(), EntryKind::Blob | EntryKind::BlobExecutable)
+}
+
+/// Turn `entries` into an index, with the file stats of each entry taken from the first of `stats_from` that has the
+/// same entry at its path, and add all `conflicts` to it.
+pub(crate) fn index_from_entries(
+    repo: &Repository,
+    entries: &Entries,
+    conflicts: &[Conflict],
+    stats_from: &[&gix_index::State],
+) -> gix_index::State {
+    let mut index = gix_index::State::new(repo.object_hash());
+    for (rela_path, (mode, id)) in entries {
+        let mode = (*mode).into();
+        let stat = stats_from
+            .iter()
+            .find_map(|index| {
+                index
+                    .entry_by_path(rela_path.as_bstr())
+                    .filter(|entry| entry.id == *id && entry.mode == mode)
+            })
+            .map(|entry| entry.stat)
+            .unwrap_or_default();
+        index.dangerously_push_entry(stat, *id, gix_index::entry::Flags::empty(), mode, rela_path.as_bstr());
+    }
+    index.sort_entries();
+    let side = |entry: Option<(EntryMode, ObjectId)>| entry.map(|(mode, id)| Side { mode: mode.into(), id });
+    for conflict in conflicts {
+        index.add_conflict(
+            conflict.path.as_bstr(),
+            side(conflict.base),
+            side(conflict.ours),
+            side(conflict.theirs),
+        );
+    }
+    index
+}
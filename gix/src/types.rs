@@ -163,6 +163,7 @@ pub struct Repository {
     #[cfg(feature = "attributes")]
     pub(crate) modules: crate::submodule::ModulesFileStorage,
     pub(crate) shallow_commits: crate::shallow::CommitsStorage,
+    pub(crate) grafts: crate::revision::walk::GraftsStorage,
 }
 
 /// An instance with access to everything a git repository entails, best imagined as container implementing `Sync + Send` for _most_
@@ -193,6 +194,7 @@ pub struct ThreadSafeRepository {
     #[cfg(feature = "attributes")]
     pub(crate) modules: crate::submodule::ModulesFileStorage,
     pub(crate) shallow_commits: crate::shallow::CommitsStorage,
+    pub(crate) grafts: crate::revision::walk::GraftsStorage,
 }
 
 /// A remote which represents a way to interact with hosts for remote clones of the parent repository.
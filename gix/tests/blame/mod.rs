@@ -0,0 +1,87 @@
+use gix::{
+    blame::{BlameHunk, Options},
+    bstr::{BString, ByteSlice},
+};
+
+use crate::util::named_repo;
+
+#[test]
+fn matches_git_across_renames_and_merges() -> crate::Result {
+    let repo = named_repo("make_blame_repo.sh")?;
+    for (rev, baseline) in [
+        ("HEAD", "renamed.txt.baseline"),
+        ("HEAD~1", "renamed.txt.HEAD~1.baseline"),
+    ] {
+        let at = repo.rev_parse_single(rev)?;
+        let actual = gix::blame::blame(&repo, "renamed.txt".into(), at)?;
+        assert_eq!(actual, git_baseline(&repo, baseline)?, "{rev}");
+    }
+    Ok(())
+}
+
+#[test]
+fn renames_can_be_ignored() -> crate::Result {
+    let repo = named_repo("make_blame_repo.sh")?;
+    let rename = repo.rev_parse_single("HEAD~1~2")?.detach();
+    let actual =
+        gix::blame::blame_with_options(&repo, "renamed.txt".into(), repo.head_id()?, Options { rewrites: None })?;
+    assert_eq!(
+        actual
+            .iter()
+            .filter(|hunk| hunk.commit_id == rename)
+            .map(|hunk| hunk.line_count)
+            .sum::<u32>(),
+        6,
+        "all lines that were unchanged since before the rename are attributed to it"
+    );
+    assert!(actual.iter().all(|hunk| hunk.source_path == "renamed.txt"));
+    Ok(())
+}
+
+#[test]
+fn empty_files_have_no_hunks() -> crate::Result {
+    let repo = named_repo("make_blame_repo.sh")?;
+    assert_eq!(gix::blame::blame(&repo, "empty".into(), repo.head_id()?)?, Vec::new());
+    Ok(())
+}
+
+#[test]
+fn missing_files_are_an_error() -> crate::Result {
+    let repo = named_repo("make_blame_repo.sh")?;
+    let err = gix::blame::blame(&repo, "file.txt".into(), repo.head_id()?).unwrap_err();
+    assert!(matches!(err, gix::blame::Error::FileMissing { .. }));
+    Ok(())
+}
+
+/// Parse the hunks out of `git blame --porcelain` output.
+fn git_baseline(repo: &gix::Repository, name: &str) -> crate::Result<Vec<BlameHunk>> {
+    let data = std::fs::read(repo.work_dir().expect("non-bare").join(name))?;
+    let mut out = Vec::new();
+    let mut path_by_commit = std::collections::HashMap::new();
+    let mut lines = data.lines();
+    while let Some(line) = lines.next() {
+        let fields: Vec<_> = line.split_str(" ").collect();
+        let [commit_id, source_line, line, count] = fields.as_slice() else {
+            continue;
+        };
+        let Ok(commit_id) = gix::ObjectId::from_hex(commit_id) else {
+            continue;
+        };
+        let number = |field: &[u8]| -> u32 { field.to_str().expect("ascii").parse().expect("number") };
+        // The filename is only provided along with the commit information when the commit is seen for the first time.
+        for line in lines.by_ref().take_while(|line| !line.starts_with(b"\t")) {
+            if let Some(path) = line.strip_prefix(b"filename ") {
+                path_by_commit.insert(commit_id, BString::from(path));
+            }
+        }
+        let source_path = path_by_commit[&commit_id].clone();
+        out.push(BlameHunk {
+            start_line: number(line) - 1,
+            line_count: number(count),
+            commit_id,
+            source_start_line: number(source_line) - 1,
+            source_path,
+        });
+    }
+    Ok(out)
+}
@@ -3,13 +3,13 @@ use crate::{remote, util::restricted};
 #[cfg(all(feature = "worktree-mutation", feature = "blocking-network-client"))]
 mod blocking_io {
     use std::path::Path;
-    use std::{borrow::Cow, sync::atomic::AtomicBool};
+    use std::{borrow::Cow, io::Write, sync::atomic::AtomicBool};
 
     use gix::{
         bstr::BString,
         config::tree::{Clone, Core, Init, Key},
         remote::{
-            fetch::{Shallow, SpecIndex},
+            fetch::{Filter, Shallow, SpecIndex},
             Direction,
         },
     };
@@ -117,8 +117,8 @@ mod blocking_io {
             vec![
                 hex_to_id("2d9d136fb0765f2e24c44a0f91984318d580d03b"),
                 hex_to_id("dfd0954dabef3b64f458321ef15571cc1a46d552"),
-                hex_to_id("dfd0954dabef3b64f458321ef15571cc1a46d552"),
-            ]
+            ],
+            "duplicates are removed"
         );
         assert_eq!(
             repo.config_snapshot().boolean("my.marker"),
@@ -232,6 +232,63 @@ mod blocking_io {
         Ok(())
     }
 
+    #[test]
+    fn partial_clone_requires_filter_capability() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let err = gix::prepare_clone_bare(remote::repo("base").path(), tmp.path())?
+            .with_filter(Filter::BlobNone)
+            .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                gix::clone::fetch::Error::Fetch(gix::remote::fetch::Error::MissingServerFeature {
+                    feature: "filter",
+                    ..
+                })
+            ),
+            "the remote doesn't allow filters by default: {err:?}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn partial_clone_with_blob_none_filter() -> crate::Result {
+        let remote_dir = gix_testtools::scripted_fixture_writable("make_remote_repos.sh")?;
+        let remote_repo = remote_dir.path().join("base");
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(remote_repo.join(".git").join("config"))?
+            .write_all(b"[uploadpack]\n\tallowFilter = true\n")?;
+
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::prepare_clone_bare(remote_repo, tmp.path())?
+            .with_filter(Filter::BlobNone)
+            .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let config = repo.config_snapshot();
+        assert_eq!(config.boolean("remote.origin.promisor"), Some(true));
+        assert_eq!(
+            config
+                .string("remote.origin.partialCloneFilter")
+                .expect("present")
+                .as_ref(),
+            "blob:none"
+        );
+
+        let tree = repo.head_commit()?.tree()?;
+        let blob = tree
+            .iter()
+            .map(|entry| entry.expect("valid"))
+            .find(|entry| entry.mode().is_blob())
+            .expect("at least one blob");
+        assert!(
+            repo.try_find_object(blob.oid())?.is_none(),
+            "blobs aren't sent, but commits and trees are"
+        );
+        Ok(())
+    }
+
     #[test]
     fn fetch_only_with_configuration() -> crate::Result {
         let tmp = gix_testtools::tempfile::TempDir::new()?;
@@ -504,6 +561,40 @@ mod blocking_io {
         assure_index_entries_on_disk(&index, repo.work_dir().expect("non-bare"));
         Ok(())
     }
+
+    #[test]
+    #[cfg(all(unix, feature = "hooks"))]
+    fn fetch_and_checkout_runs_post_checkout_hook() -> crate::Result {
+        use std::os::unix::fs::PermissionsExt;
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let hooks_dir = tmp.path().join("hooks");
+        std::fs::create_dir(&hooks_dir)?;
+        let hook = hooks_dir.join("post-checkout");
+        std::fs::write(&hook, "#!/bin/sh\necho \"$@\" > ../post-checkout-args\nexit 1\n")?;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+
+        let mut opts = restricted().config_overrides([format!("core.hooksPath={}", hooks_dir.display())]);
+        opts.permissions.hooks = true;
+        let mut prepare = gix::clone::PrepareFetch::new(
+            remote::repo("base").path(),
+            tmp.path().join("clone"),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            opts,
+        )?;
+        let (mut checkout, _out) =
+            prepare.fetch_then_checkout(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        let (repo, _) = checkout.main_worktree(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let null = gix::ObjectId::null(repo.object_hash());
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join("post-checkout-args"))?,
+            format!("{null} {} 1\n", repo.head_id()?),
+            "the hook runs in the worktree, and its failure doesn't affect the checkout"
+        );
+        Ok(())
+    }
+
     #[test]
     fn fetch_and_checkout_specific_ref() -> crate::Result {
         let tmp = gix_testtools::tempfile::TempDir::new()?;
@@ -676,6 +767,75 @@ mod blocking_io {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "bundle")]
+    fn fetch_and_checkout_from_bundle() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let bundle = gix_testtools::scripted_fixture_read_only("make_bundle_repos.sh")?.join("full.bundle");
+        let mut prepare = gix::clone::PrepareFetch::new(
+            bundle.as_path(),
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?;
+        let (mut checkout, out) =
+            prepare.fetch_then_checkout(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+        assert!(
+            matches!(out.status, gix::remote::fetch::Status::Change { write_pack_bundle, .. } if write_pack_bundle.index.num_objects == 6),
+            "the whole pack of the bundle was installed"
+        );
+        let (repo, _) = checkout.main_worktree(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        assert_eq!(
+            repo.head_name()?.expect("not detached").as_bstr(),
+            "refs/heads/main",
+            "the branch of HEAD is guessed, preferring the default branch over others pointing to the same commit"
+        );
+        let second = hex_to_id("2d1e7bfd3dfc05a225d330e82b8627eadf3c5df7");
+        assert_eq!(repo.head_id()?, second);
+        for (name, expected) in [
+            ("refs/remotes/origin/main", second),
+            ("refs/remotes/origin/alias", second),
+            ("refs/tags/v1", hex_to_id("7cc695c1f307a6e9ac4509590c41e16dddf56bd5")),
+        ] {
+            assert_eq!(repo.find_reference(name)?.id(), expected, "{name}");
+        }
+        assert!(
+            repo.find_remote("origin")?
+                .url(Direction::Fetch)
+                .expect("present")
+                .path
+                .ends_with(b"full.bundle"),
+            "the bundle is the url of the remote"
+        );
+        let index = repo.index()?;
+        assert_eq!(index.entries().len(), 1);
+        assure_index_entries_on_disk(&index, repo.work_dir().expect("non-bare"));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "bundle")]
+    fn fetch_only_from_bundle_with_missing_prerequisites() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let bundle = gix_testtools::scripted_fixture_read_only("make_bundle_repos.sh")?.join("incremental.bundle");
+        let err = gix::clone::PrepareFetch::new(
+            bundle.as_path(),
+            tmp.path(),
+            gix::create::Kind::Bare,
+            Default::default(),
+            restricted(),
+        )?
+        .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The bundle requires 1 object(s) which aren't present: 7cc695c1f307a6e9ac4509590c41e16dddf56bd5"
+        );
+        Ok(())
+    }
+
     #[test]
     fn fetch_only_without_configuration() -> crate::Result {
         let tmp = gix_testtools::tempfile::TempDir::new()?;
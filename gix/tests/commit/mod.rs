@@ -69,6 +69,72 @@ mod describe {
         Ok(())
     }
 
+    #[test]
+    fn names_can_be_filtered_by_match_and_exclude_patterns() -> crate::Result {
+        let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
+        let describe = || repo.head_commit().expect("present").describe();
+        assert_eq!(describe().match_patterns(["v2*"]).format()?.to_string(), "v2");
+        assert_eq!(
+            describe().names(AllTags).match_patterns(["v2*"]).format()?.to_string(),
+            "v2",
+            "annotated tags are still preferred"
+        );
+        assert_eq!(describe().exclude_patterns(["v4", "v5"]).format()?.to_string(), "v2");
+        assert_eq!(
+            describe()
+                .match_patterns(["v*"])
+                .exclude_patterns(["v*"])
+                .try_format()?
+                .map(|f| f.to_string()),
+            None,
+            "exclusions take precedence"
+        );
+        assert_eq!(
+            describe().names(AllRefs).exclude_patterns(["v*"]).format()?.to_string(),
+            "main",
+            "branches are matched without their prefix"
+        );
+        assert_eq!(
+            describe().names(AllTags).match_patterns(["l*"]).format()?.to_string(),
+            "l0-2-g17d78c6"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn abbrev_sets_the_minimal_hex_len() -> crate::Result {
+        let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
+        let describe = || {
+            repo.head_commit()
+                .expect("present")
+                .describe()
+                .names(AllTags)
+                .match_patterns(["l*"])
+        };
+        for (abbrev, expected) in [
+            (12, "l0-2-g17d78c64cef6"),
+            (2, "l0-2-g17d7"),
+            (0, "l0"),
+            (100, "l0-2-g17d78c64cef6c33a10a604573fd2c429e477fd63"),
+        ] {
+            assert_eq!(describe().abbrev(abbrev).format()?.to_string(), expected, "{abbrev}");
+        }
+
+        let describe = || {
+            repo.head_commit()
+                .expect("present")
+                .describe()
+                .match_patterns(["nothing"])
+        };
+        assert_eq!(describe().format()?.to_string(), "17d78c6");
+        assert_eq!(
+            describe().abbrev(0).format()?.to_string(),
+            "17d78c64cef6c33a10a604573fd2c429e477fd63",
+            "without a name, the full id is shown"
+        );
+        Ok(())
+    }
+
     #[test]
     fn lightweight_tags_are_sorted_lexicographically() -> crate::Result {
         let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
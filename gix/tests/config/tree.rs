@@ -676,6 +676,16 @@ mod extensions {
         );
         assert!(Extensions::OBJECT_FORMAT.validate("sha1".into()).is_ok());
         assert!(Extensions::OBJECT_FORMAT.validate("invalid".into()).is_err());
+        #[cfg(feature = "sha256")]
+        assert_eq!(
+            Extensions::OBJECT_FORMAT.try_into_object_format(bcow("sha256"))?,
+            gix_hash::Kind::Sha256
+        );
+        #[cfg(not(feature = "sha256"))]
+        assert!(
+            Extensions::OBJECT_FORMAT.validate("sha256".into()).is_err(),
+            "SHA256 needs the `sha256` feature"
+        );
         Ok(())
     }
 }
@@ -803,6 +813,40 @@ mod protocol {
 }
 
 mod gitoxide {
+    mod core {
+        use gix::config::tree::{gitoxide, Key};
+        use gix::odb::pack::data::init::Advice;
+
+        use crate::config::tree::bcow;
+
+        #[test]
+        fn packed_git_advice() -> crate::Result {
+            for (value, expected) in [
+                ("normal", Advice::Normal),
+                ("random", Advice::Random),
+                ("sequential", Advice::Sequential),
+                ("willneed", Advice::WillNeed),
+            ] {
+                assert_eq!(
+                    gitoxide::Core::PACKED_GIT_ADVICE.try_into_advice(bcow(value))?,
+                    expected
+                );
+                assert!(gitoxide::Core::PACKED_GIT_ADVICE.validate(value.into()).is_ok());
+            }
+            assert_eq!(
+                gitoxide::Core::PACKED_GIT_ADVICE
+                    .try_into_advice(bcow("Random"))
+                    .unwrap_err()
+                    .to_string(),
+                "The key \"gitoxide.core.packedGitAdvice=Random\" was invalid"
+            );
+            assert!(gitoxide::Core::PACKED_GIT_ADVICE
+                .validate("sequentially".into())
+                .is_err());
+            Ok(())
+        }
+    }
+
     mod http {
         use std::time::Duration;
 
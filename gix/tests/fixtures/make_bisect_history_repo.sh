@@ -0,0 +1,47 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function commit() {
+  echo "$1" >"$1"
+  git add "$1" && git commit -q -m "$1"
+  git tag "$1"
+}
+
+git init -q linear
+(cd linear
+  for n in $(seq 1 10); do
+    if [ "$n" = 7 ]; then
+      echo bug >bug && git add bug
+    fi
+    commit "c$n"
+  done
+
+  git bisect start c10 c1 >/dev/null 2>&1
+  git bisect run sh -c '! test -f bug' >/dev/null 2>&1
+  git rev-parse refs/bisect/bad >first-bad
+  git bisect log | sed -n 's/^git bisect \(good\|bad\) \(.*\)$/\2/p' >visited
+  git bisect reset >/dev/null 2>&1
+)
+
+git init -q merges
+(cd merges
+  commit base
+  git checkout -q -b side
+  commit s1
+  commit s2
+  echo bug >bug && git add bug
+  commit s3-bug
+  commit s4
+  git checkout -q main
+  commit m1
+  commit m2
+  git merge -q --no-edit side
+  git tag merge
+  commit m3
+
+  git bisect start m3 base >/dev/null 2>&1
+  git bisect run sh -c '! test -f bug' >/dev/null 2>&1
+  git rev-parse refs/bisect/bad >first-bad
+  git bisect log | sed -n 's/^git bisect \(good\|bad\) \(.*\)$/\2/p' >visited
+  git bisect reset >/dev/null 2>&1
+)
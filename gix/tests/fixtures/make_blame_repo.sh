@@ -0,0 +1,43 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+time=1000000000
+function commit() {
+  time=$((time + 60))
+  GIT_AUTHOR_DATE="$time +0000" GIT_COMMITTER_DATE="$time +0000" git commit -q -m "$1"
+}
+
+printf '%s\n' 1 2 3 4 5 6 7 8 > file.txt
+touch empty
+git add file.txt empty
+commit initial
+
+printf '%s\n' 1 two 3 4 5 6 7 8 9 > file.txt
+git add file.txt
+commit "change 2, add 9"
+
+git mv file.txt renamed.txt
+commit rename
+
+printf '%s\n' 1 two 3 4 5 six 7 8 9 > renamed.txt
+git add renamed.txt
+commit "change 6"
+
+git checkout -q -b side
+printf '%s\n' one two 3 4 5 six 7 8 9 > renamed.txt
+git add renamed.txt
+commit "change 1 on side"
+
+git checkout -q main
+printf '%s\n' 1 two 3 4 5 six 7 eight 9 ten > renamed.txt
+git add renamed.txt
+commit "change 8, add 10"
+
+time=$((time + 60))
+GIT_AUTHOR_DATE="$time +0000" GIT_COMMITTER_DATE="$time +0000" git merge -q --no-edit side
+
+git blame --porcelain renamed.txt > renamed.txt.baseline
+git blame --porcelain HEAD~1 -- renamed.txt > renamed.txt.HEAD~1.baseline
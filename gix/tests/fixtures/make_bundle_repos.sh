@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q remote
+(cd remote
+  git checkout -q -b main
+  echo one > file && git add file && git commit -q -m "first"
+  git tag v1
+  echo two > file && git commit -q -am "second"
+  git branch alias
+
+  git bundle create -q ../full.bundle --all
+  git bundle create -q ../incremental.bundle main~1..main
+)
+
+git init -q --bare base
+git -C base fetch -q ../remote refs/tags/v1:refs/heads/main
@@ -0,0 +1,41 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+echo a >a
+mkdir dir && echo b >dir/b
+git add . && git commit -q -m "initial"
+
+git checkout -q -b side
+echo side >side
+git add side && git commit -q -m "add side"
+
+git checkout -q -b feature main
+echo feature >>dir/b
+git commit -q -am "change b" -m "Signed-off-by: Author <author@example.com>"
+echo feature >>a
+git commit -q -am "conflicting change of a"
+
+git checkout -q main
+echo main >>a
+git commit -q -am "change a"
+
+git checkout -q -b merged
+git merge -q --no-ff -m "Merge branch 'side'" side
+
+git checkout -q -b picked main
+git cherry-pick -x feature~1 >/dev/null
+
+git checkout -q -b picked-merge feature~1
+git cherry-pick -x -m 1 merged >/dev/null
+
+git checkout -q main
+
+git checkout -q -b reverted main
+git revert --no-edit HEAD >/dev/null
+
+git checkout -q -b reverted-merge merged
+git revert --no-edit -m 1 HEAD >/dev/null
+
+git checkout -q main
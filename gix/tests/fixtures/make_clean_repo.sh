@@ -0,0 +1,32 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q repo
+(cd repo
+  cat >.gitignore <<'EOF2'
+*.o
+build/
+EOF2
+  echo tracked >tracked
+  mkdir tracked-dir && echo tracked >tracked-dir/file
+  git add . && git commit -q -m init
+
+  echo untracked >untracked
+  echo untracked >tracked-dir/untracked
+  mkdir -p untracked-dir/sub && echo untracked >untracked-dir/sub/file
+  mkdir empty
+  echo ignored >file.o
+  echo ignored >tracked-dir/file.o
+  mkdir build && echo ignored >build/out
+
+  git init -q nested
+  echo untracked >nested/file
+  mkdir untracked-with-repo
+  echo untracked >untracked-with-repo/file
+  git init -q untracked-with-repo/inner
+)
+
+for args in -n -nd -nx -ndx -nX -ndX -ndff -ndxff; do
+  git -C repo clean $args >"clean$args.baseline"
+done
+git -C repo clean -nd -- tracked-dir untracked-dir >clean-nd-pathspec.baseline
@@ -126,6 +126,11 @@ git init http-verbose
   git config gitoxide.http.verbose true
 )
 
+git init http-proxy-with-password
+(cd http-proxy-with-password
+  git config http.proxy user:pass@localhost:9090
+)
+
 git init http-proxy-authenticated
 (cd http-proxy-authenticated
   git config http.proxy user@localhost:9090
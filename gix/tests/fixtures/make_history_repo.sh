@@ -0,0 +1,88 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+git config merge.ff false
+
+time=1000000000
+function tick() {
+  time=$((time + 60))
+  export GIT_AUTHOR_DATE="$time +0000" GIT_COMMITTER_DATE="$time +0000"
+}
+
+function commit() {
+  tick
+  git add -A
+  git commit -q --allow-empty -m "$1"
+}
+
+function merge() {
+  tick
+  git merge -q --no-edit -m "$1" "$2"
+}
+
+mkdir dir
+echo 1 > file.txt
+echo 1 > other.txt
+commit initial
+
+echo 2 > other.txt
+commit "change other"
+
+echo 1 > dir/a.txt
+commit "add dir/a"
+
+echo 2 > file.txt
+commit "change file"
+
+git checkout -q -b side
+echo 3 > file.txt
+commit "change file on side"
+
+git checkout -q main
+echo 3 > other.txt
+echo 2 > dir/a.txt
+commit "change other and dir/a"
+
+# The file was only changed on the side branch, so the merge is the same as its second parent for it.
+merge "merge side" side
+
+git checkout -q -b side2
+echo 4 > other.txt
+commit "change other on side2"
+
+git checkout -q main
+echo 1 > dir/b.txt
+commit "add dir/b"
+
+# The file is the same in both parents and the merge.
+merge "merge side2" side2
+
+# Use content large enough for the rename to be detected despite the modification.
+printf '%s\n' 1 2 3 4 5 6 7 8 9 10 > file.txt
+commit "rewrite file"
+
+git mv file.txt renamed.txt
+printf '%s\n' 1 2 3 4 5 6 7 8 9 10 11 > renamed.txt
+commit "rename and change file"
+
+echo 5 > other.txt
+commit "change other again"
+
+printf '%s\n' 1 2 3 4 5 6 7 8 9 10 11 12 > renamed.txt
+commit "change renamed"
+
+function baseline() {
+  local name=${1:?first argument is the baseline name}
+  shift
+  git log --format=%H "$@" > "$name.baseline"
+}
+
+baseline file-full-history --full-history -- file.txt
+baseline file-first-parent --first-parent -- file.txt
+baseline renamed-full-history --full-history -- renamed.txt
+baseline renamed-follow --follow -- renamed.txt
+baseline dir-full-history --full-history -- dir
+baseline glob-full-history --full-history -- '*a.txt'
+baseline exclude-full-history --full-history -- ':!other.txt'
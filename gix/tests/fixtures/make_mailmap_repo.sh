@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q worktree
+(cd worktree
+  cat <<EOF >mailmap-blob
+Blob Author <author@example.com>
+Blob Committer <blob-committer@example.com> <committer@example.com>
+EOF
+  git add mailmap-blob
+  GIT_AUTHOR_NAME=author GIT_AUTHOR_EMAIL=author@example.com \
+  GIT_COMMITTER_NAME=committer GIT_COMMITTER_EMAIL=committer@example.com \
+    git commit -q -m "init"
+
+  cat <<EOF >.mailmap
+Worktree Author <author@example.com>
+Worktree Name <worktree@example.com> committer <committer@example.com>
+EOF
+
+  git config mailmap.blob HEAD:mailmap-blob
+  git log -1 --format='%aN <%aE>%n%cN <%cE>' > baseline.with-blob
+)
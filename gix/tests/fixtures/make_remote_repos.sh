@@ -181,6 +181,35 @@ EOF
   } > baseline.git
 )
 
+git init --bare url-rewriting-longest-match
+(cd url-rewriting-longest-match
+
+  git remote add origin https://github.com/org/repo
+  git remote add explicit-push https://github.com/org/other
+  git config remote.explicit-push.pushUrl https://gh/org/push
+  cat <<EOF >> config
+
+[url "ssh://short/"]
+  insteadOf = https://
+[url "git@github.com:"]
+  insteadOf = https://github.com/
+  insteadOf = https://gh/
+[url "git@github.com:org-mirror/"]
+  insteadOf = https://github.com/org/
+[url "ssh://push.example.com/"]
+  pushInsteadOf = https://github.com/
+[url "ssh://push.example.com/org/"]
+  pushInsteadOf = https://github.com/org/
+EOF
+
+  {
+    git remote get-url origin
+    git remote get-url origin --push
+    git remote get-url explicit-push
+    git remote get-url explicit-push --push
+  } > baseline.git
+)
+
 git init --bare bad-url-rewriting
 (cd bad-url-rewriting
 
@@ -190,8 +219,8 @@ git init --bare bad-url-rewriting
 [remote "origin"]
   pushUrl = "file://dev/null"
 
-[url "invalid:://"]
-  pushInsteadOf = "file://"
+[url "invalid://["]
+  insteadOf = "file://"
 
 [url "https://github.com/byron/"]
   insteadOf = https://github.com/foobar/
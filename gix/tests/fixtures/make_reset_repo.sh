@@ -0,0 +1,27 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q local-changes
+(cd local-changes
+  echo a >a
+  mkdir dir && echo b >dir/b
+  echo c >c
+  git add . && git commit -q -m "first"
+
+  echo a2 >a
+  git rm -q dir/b
+  echo d >d
+  git add . && git commit -q -m "second"
+
+  echo local >>a
+  echo staged >>d && git add d
+  echo untracked >untracked
+)
+
+for mode in soft mixed hard; do
+  cp -R local-changes git-$mode
+  (cd git-$mode && git reset -q --$mode HEAD~1)
+done
+
+cp -R local-changes git-paths
+(cd git-paths && git reset -q HEAD~1 -- a dir)
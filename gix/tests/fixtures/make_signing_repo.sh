@@ -0,0 +1,26 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+ssh-keygen -q -t ed25519 -N '' -C '' -f ssh-key
+ssh-keygen -q -t ed25519 -N '' -C '' -f untrusted-ssh-key
+echo "committer@example.com $(cat ssh-key.pub)" >allowed-signers
+
+mkdir -m 700 gnupg
+gnupg_home="$PWD/gnupg"
+# Don't leave the gpg-agent started by key generation running.
+trap 'GNUPGHOME="$gnupg_home" gpgconf --kill all' EXIT
+GNUPGHOME="$gnupg_home" gpg --batch --quiet --passphrase '' --quick-gen-key 'committer <committer@example.com>' ed25519 sign never
+cat <<EOF >gpg
+#!/bin/sh
+GNUPGHOME="$gnupg_home" exec gpg "\$@"
+EOF
+chmod +x gpg
+
+git init -q repo
+(cd repo
+  git config user.name committer
+  git config user.email committer@example.com
+  git config gpg.program "$PWD/../gpg"
+  git config gpg.ssh.allowedSignersFile "$PWD/../allowed-signers"
+  git commit -q --allow-empty -m "initial"
+)
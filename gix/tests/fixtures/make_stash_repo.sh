@@ -0,0 +1,33 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function make_changes() {
+  echo staged >>a && git add a
+  echo unstaged >>dir/b
+  git rm -q c
+  echo new >new && git add new
+  echo untracked >untracked
+}
+
+git init -q local-changes
+(cd local-changes
+  echo a >a
+  mkdir dir && echo b >dir/b
+  echo c >c
+  echo ignored >.gitignore
+  git add . && git commit -q -m "initial"
+  echo ignored >ignored
+  make_changes
+)
+
+cp -R local-changes git-stashed
+(cd git-stashed
+  git stash push -q --include-untracked
+  git stash list >../git-stashed.list
+)
+
+cp -R git-stashed conflicting
+(cd conflicting
+  echo upstream >>dir/b
+  git commit -q -am "upstream change"
+)
@@ -13,3 +13,83 @@ git init -q untracked-only
   mkdir new
   touch new/untracked subdir/untracked
 )
+
+git init -q all-changes
+(cd all-changes
+  printf '*.ignored\nignored-dir/\n' >.gitignore
+  for name in modified staged-modified staged-and-modified deleted staged-deleted type-changed; do
+    echo "$name" >"$name"
+  done
+  echo "content that is unique enough to be tracked as rename" >renamed-source
+  mkdir dir
+  echo tracked >dir/tracked
+  git add .
+  git commit -q -m init
+
+  echo change >>modified
+  echo change >>staged-modified
+  git add staged-modified
+  echo change >>staged-and-modified
+  git add staged-and-modified
+  echo change-again >>staged-and-modified
+  rm deleted
+  git rm -q staged-deleted
+  git mv renamed-source renamed-destination
+  rm type-changed && ln -s modified type-changed
+  echo new >added
+  git add added
+  echo new >added-and-modified
+  git add added-and-modified
+  echo change >>added-and-modified
+  echo new >intent-to-add
+  git add -N intent-to-add
+
+  echo untracked >untracked
+  mkdir untracked-dir
+  echo untracked >untracked-dir/a
+  echo untracked >untracked-dir/b
+  echo untracked >dir/untracked
+  echo ignored >file.ignored
+  mkdir ignored-dir
+  echo ignored >ignored-dir/file
+
+  git status --porcelain >../all-changes.baseline
+  git status --porcelain --no-renames >../all-changes-no-renames.baseline
+  git status --porcelain --untracked-files=all >../all-changes-untracked-files-all.baseline
+  git status --porcelain --ignored >../all-changes-ignored.baseline
+)
+
+git init -q conflicts
+(cd conflicts
+  echo base >both-modified
+  echo base >deleted-by-them
+  git add .
+  git commit -q -m base
+
+  git checkout -q -b other
+  echo other >both-modified
+  git rm -q deleted-by-them
+  echo other >added-by-both
+  git add .
+  git commit -q -m other
+
+  git checkout -q -
+  echo ours >both-modified
+  echo ours >deleted-by-them
+  echo ours >added-by-both
+  git add .
+  git commit -q -m ours
+  git merge -q other >/dev/null || :
+
+  git status --porcelain >../conflicts.baseline
+)
+
+git init -q staged-only
+(cd staged-only
+  echo content >file
+  git add file
+  git commit -q -m init
+
+  echo change >>file
+  git add file
+)
@@ -0,0 +1,34 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q module1
+(cd module1
+  touch this
+  git add . && git commit -q -m c1
+  echo hello >> this
+  git commit -q -am c2
+)
+
+git init -q module2
+(cd module2
+  touch that
+  git add . && git commit -q -m c1
+  git submodule add -q ../module1 nested
+  git commit -q -m "add nested submodule"
+)
+
+git init -q super
+(cd super
+  touch file
+  git add . && git commit -q -m init
+
+  git submodule add -q ../module2 m2
+  mkdir dir
+  git submodule add -q ../module1 dir/m1
+  git submodule add -q --name skipped ../module1 skipped
+  git config -f .gitmodules submodule.skipped.update none
+  git submodule add -q ../module1 shallow
+  (cd shallow && git checkout -q @~1)
+  git config -f .gitmodules submodule.shallow.shallow true
+  git add . && git commit -q -m "add submodules"
+)
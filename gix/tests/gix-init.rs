@@ -220,7 +220,7 @@ mod with_overrides {
             ("gitoxide.http.sslNoVerify", "true"),
             ("gitoxide.http.verbose", "true"),
             ("gitoxide.allow.protocolFromUser", "file-allowed"),
-            ("core.useReplaceRefs", "no-replace"),
+            ("core.useReplaceRefs", "false"),
             #[cfg(feature = "blob-diff")]
             ("diff.external", "external-diff-env"),
             ("gitoxide.objects.replaceRefBase", "refs/replace-mine"),
@@ -256,4 +256,24 @@ mod with_overrides {
     fn cow_bstr(s: &str) -> Cow<BStr> {
         Cow::Borrowed(s.into())
     }
+
+    #[test]
+    #[serial]
+    fn no_replace_objects_environment_variable_disables_replacements_no_matter_its_value() -> gix_testtools::Result {
+        let _env = Env::new().set("GIT_NO_REPLACE_OBJECTS", "1");
+        let mut opts = gix::open::Options::isolated();
+        opts.permissions.env.objects = Permission::Allow;
+        let repo = gix::ThreadSafeRepository::open_opts(
+            gix_testtools::scripted_fixture_read_only("../../../gix-odb/tests/fixtures/make_replaced_history.sh")?,
+            opts,
+        )?
+        .to_thread_local();
+        assert_eq!(
+            repo.config_snapshot().boolean("core.useReplaceRefs"),
+            Some(false),
+            "the value is irrelevant, which is different from how booleans are usually interpreted"
+        );
+        assert_eq!(repo.objects.store_ref().replacements().count(), 0);
+        Ok(())
+    }
 }
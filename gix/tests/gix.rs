@@ -2,6 +2,8 @@
 mod util;
 use util::*;
 
+#[cfg(feature = "blob-diff")]
+mod blame;
 mod clone;
 mod commit;
 mod config;
@@ -55,6 +55,39 @@ fn prefix() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn shorten_id_is_unambiguous() -> crate::Result {
+    let base = gix_testtools::scripted_fixture_read_only("make_rev_spec_parse_repos.sh")?;
+    let repo = gix::open(base.join("ambiguous_blob_tree_commit"))?;
+    for (id, expected) in [
+        ("0000000000e4f9fbd19cf1e932319e5ad0d1d00b", "0000000000e"),
+        ("0000000000b36b6aa7ea4b75318ed078f55505c3", "0000000000b"),
+    ] {
+        assert_eq!(
+            repo.shorten_id(hex_to_id(id))?,
+            expected,
+            "the prefix grows past `core.abbrev` until no other object shares it"
+        );
+    }
+
+    let unambiguous = crate::basic_repo()?;
+    assert_eq!(
+        unambiguous.shorten_id(unambiguous.head_id()?)?,
+        "3189cd3",
+        "the default length suffices"
+    );
+
+    let null = gix_hash::ObjectId::null(repo.object_hash());
+    assert!(
+        matches!(
+            repo.shorten_id(null).unwrap_err(),
+            gix::id::shorten::Error::NotFound { oid } if oid == null
+        ),
+        "missing objects can't be shortened"
+    );
+    Ok(())
+}
+
 #[test]
 fn display_and_debug() -> crate::Result {
     let repo = crate::basic_repo()?;
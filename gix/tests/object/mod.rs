@@ -3,6 +3,7 @@ mod commit;
 mod tree;
 
 #[test]
+#[cfg_attr(feature = "sha256", ignore = "SHA256 makes object ids larger")]
 fn object_ref_size_in_memory() {
     assert_eq!(
         std::mem::size_of::<gix::Object<'_>>(),
@@ -12,6 +13,7 @@ fn object_ref_size_in_memory() {
 }
 
 #[test]
+#[cfg_attr(feature = "sha256", ignore = "SHA256 makes object ids larger")]
 fn oid_size_in_memory() {
     assert_eq!(
         std::mem::size_of::<gix::Id<'_>>(),
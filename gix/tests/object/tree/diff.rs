@@ -563,7 +563,7 @@ mod track_rewrites {
                         source: CopySource::FromSetOfModifiedFilesAndAllSources,
                         ..Default::default()
                     }),
-                    limit: 2, // similarity checks can't be made that way
+                    limit: 1, // similarity checks can't be made that way
                     ..Default::default()
                 }
                 .into(),
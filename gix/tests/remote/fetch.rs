@@ -112,6 +112,78 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn interrupted_fetch_changes_no_reference() -> crate::Result {
+        let (repo, _tmp) = repo_rw("two-origins");
+        let refs = |repo: &gix::Repository| -> crate::Result<Vec<_>> {
+            repo.references()?
+                .all()?
+                .map(|r| r.map(|r| (r.name().to_owned(), r.target().into_owned())))
+                .collect()
+        };
+        let refs_before = refs(&repo)?;
+        let receive = |should_interrupt: bool| -> crate::Result<Result<fetch::Outcome, fetch::Error>> {
+            Ok(repo
+                .find_remote("origin")?
+                .with_refspecs(Some("refs/heads/*:refs/remotes/copy/*"), Fetch)?
+                .with_fetch_tags(fetch::Tags::None)
+                .connect(Fetch)?
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .receive(gix::progress::Discard, &AtomicBool::new(should_interrupt)))
+        };
+
+        let err = receive(true)?.unwrap_err();
+        assert!(matches!(err, fetch::Error::Interrupted), "{err:?}");
+        assert_eq!(refs(&repo)?, refs_before, "no reference was changed");
+        assert!(!repo.git_dir().join("FETCH_HEAD").exists());
+
+        let outcome = receive(false)?;
+        assert!(
+            matches!(
+                outcome?.status,
+                Status::NoPackReceived { update_refs, .. } if !update_refs.edits.is_empty()
+            ),
+            "all objects are present, but references would have been created without interruption"
+        );
+        assert_ne!(refs(&repo)?, refs_before);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_head_is_written_like_git() -> crate::Result {
+        for remote_name in ["origin", "changes-on-top-of-origin"] {
+            let (repo, tmp) = repo_rw("two-origins");
+            let fetch_head = repo.git_dir().join("FETCH_HEAD");
+            for dry_run in [true, false] {
+                repo.find_remote(remote_name)?
+                    .connect(Fetch)?
+                    .prepare_fetch(gix::progress::Discard, Default::default())?
+                    .with_dry_run(dry_run)
+                    .receive(gix::progress::Discard, &AtomicBool::default())?;
+                assert_eq!(fetch_head.is_file(), !dry_run, "dry-runs don't write FETCH_HEAD");
+            }
+            let actual = std::fs::read_to_string(&fetch_head)?;
+
+            let (git_repo, git_tmp) = repo_rw("two-origins");
+            let status = std::process::Command::new("git")
+                .args(["fetch", "--quiet", remote_name])
+                .current_dir(git_repo.git_dir())
+                .status()?;
+            assert!(status.success(), "git fetch must succeed");
+            let expected = std::fs::read_to_string(git_repo.git_dir().join("FETCH_HEAD"))?.replace(
+                git_tmp.path().to_str().expect("valid UTF-8"),
+                tmp.path().to_str().expect("valid UTF-8"),
+            );
+            assert_eq!(
+                actual, expected,
+                "{remote_name}: the same refs are listed and marked for merge"
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "blocking-network-client")]
     fn fetch_with_alternates_adds_tips_from_alternates() -> crate::Result<()> {
@@ -652,3 +724,49 @@ mod blocking_and_async_io {
         Ok(())
     }
 }
+
+#[cfg(all(feature = "bundle", feature = "blocking-network-client"))]
+mod bundle {
+    use std::sync::atomic::AtomicBool;
+
+    use gix::remote::{fetch, Direction::Fetch};
+    use gix_features::progress;
+
+    use crate::util::hex_to_id;
+
+    #[test]
+    fn fetch_incremental_bundle_into_repository_with_prerequisites() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_bundle_repos.sh")?;
+        let repo = gix::open_opts(tmp.path().join("base"), crate::restricted())?;
+        let remote = repo
+            .remote_at(tmp.path().join("incremental.bundle"))?
+            .with_refspecs(Some("refs/heads/*:refs/remotes/bundle/*"), Fetch)?;
+
+        let out = remote.fetch_bundle(
+            &tmp.path().join("incremental.bundle"),
+            progress::Discard,
+            &AtomicBool::default(),
+            Default::default(),
+        )?;
+        let fetch::Status::Change {
+            write_pack_bundle,
+            update_refs,
+            ..
+        } = out.status
+        else {
+            unreachable!("bundles always bring a pack")
+        };
+        assert_eq!(write_pack_bundle.index.num_objects, 3, "the thin pack was completed");
+        assert!(
+            write_pack_bundle.keep_path.is_none(),
+            "keep files are removed once refs are written"
+        );
+        assert_eq!(update_refs.edits.len(), 1);
+        assert!(out.ref_map.handshake.refs.is_none(), "bundles don't have a handshake");
+
+        let second = hex_to_id("2d1e7bfd3dfc05a225d330e82b8627eadf3c5df7");
+        assert_eq!(repo.find_reference("refs/remotes/bundle/main")?.id(), second);
+        assert_eq!(repo.find_object(second)?.kind, gix::object::Kind::Commit);
+        Ok(())
+    }
+}
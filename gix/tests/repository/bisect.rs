@@ -0,0 +1,120 @@
+use gix::bisect::{Error, Step};
+
+fn writable_repo(name: &str) -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+    let tmp = gix_testtools::scripted_fixture_writable("make_bisect_history_repo.sh")?;
+    let repo = gix::open_opts(tmp.path().join(name), crate::restricted())?;
+    Ok((repo, tmp))
+}
+
+fn id(repo: &gix::Repository, spec: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.rev_parse_single(spec)?.detach())
+}
+
+/// Bisect between `good` and `bad` by testing whether the file `bug` exists, just like the fixture does with git, and
+/// return the tested commits along with the first bad one.
+fn bisect(repo: &gix::Repository, good: &str, bad: &str) -> crate::Result<(Vec<gix::ObjectId>, gix::ObjectId)> {
+    let bisect = repo.bisect();
+    bisect.start(Some(id(repo, good)?), id(repo, bad)?)?;
+    let mut visited = Vec::new();
+    loop {
+        match bisect.next()?.expect("good and bad commits are set") {
+            Step::Test { id, suspects } => {
+                assert!(suspects > 1, "there is more than one suspect left");
+                visited.push(id);
+                let has_bug = repo.find_object(id)?.peel_to_tree()?.find_entry("bug").is_some();
+                if has_bug {
+                    bisect.mark_bad(id)?;
+                } else {
+                    bisect.mark_good(id)?;
+                }
+            }
+            Step::FirstBad(id) => return Ok((visited, id)),
+        }
+    }
+}
+
+fn expected(repo: &gix::Repository) -> crate::Result<(Vec<gix::ObjectId>, gix::ObjectId)> {
+    let workdir = repo.work_dir().expect("non-bare");
+    let visited = std::fs::read_to_string(workdir.join("visited"))?
+        .lines()
+        .map(|hex| gix::ObjectId::from_hex(hex.as_bytes()))
+        .collect::<Result<_, _>>()?;
+    let first_bad = gix::ObjectId::from_hex(std::fs::read_to_string(workdir.join("first-bad"))?.trim().as_bytes())?;
+    Ok((visited, first_bad))
+}
+
+#[test]
+fn linear_history_is_bisected_like_git_does() -> crate::Result {
+    let (repo, _tmp) = writable_repo("linear")?;
+    let (visited, first_bad) = bisect(&repo, "c1", "c10")?;
+    assert_eq!(first_bad, id(&repo, "c7")?);
+    assert_eq!((visited, first_bad), expected(&repo)?);
+    Ok(())
+}
+
+#[test]
+fn merges_are_bisected_like_git_does() -> crate::Result {
+    let (repo, _tmp) = writable_repo("merges")?;
+    let (visited, first_bad) = bisect(&repo, "base", "m3")?;
+    assert_eq!(first_bad, id(&repo, "s3-bug")?);
+    assert_eq!((visited, first_bad), expected(&repo)?);
+    Ok(())
+}
+
+#[test]
+fn state_is_stored_in_references_and_removed_on_reset() -> crate::Result {
+    let (repo, _tmp) = writable_repo("linear")?;
+    let bisect = repo.bisect();
+    assert_eq!(bisect.next()?, None, "nothing to do without a bisection");
+    assert_eq!(bisect.reset()?, None, "nothing to reset without a bisection");
+
+    let (good, bad) = (id(&repo, "c1")?, id(&repo, "c10")?);
+    bisect.start(None::<gix::ObjectId>, bad)?;
+    assert_eq!(bisect.next()?, None, "a good commit is needed as well");
+    bisect.mark_good(good)?;
+    assert_eq!(bisect.state()?, (Some(bad), vec![good]));
+    assert_eq!(repo.find_reference(gix::bisect::BAD)?.id(), bad);
+    assert_eq!(
+        repo.find_reference(format!("{}{good}", gix::bisect::GOOD_PREFIX).as_str())?
+            .id(),
+        good
+    );
+    let log = std::fs::read_to_string(repo.git_dir().join("BISECT_LOG"))?;
+    assert_eq!(
+        log,
+        format!(
+            "git bisect start\n# bad: [{bad}] c10\ngit bisect bad {bad}\n# good: [{good}] c1\ngit bisect good {good}\n"
+        )
+    );
+
+    assert_eq!(
+        bisect.reset()?,
+        Some("main".into()),
+        "the branch that was checked out when starting"
+    );
+    assert_eq!(bisect.state()?, (None, Vec::new()));
+    assert!(repo.try_find_reference(gix::bisect::BAD)?.is_none());
+    assert!(!repo.git_dir().join("BISECT_LOG").exists());
+    assert!(!repo.git_dir().join("BISECT_START").exists());
+    Ok(())
+}
+
+#[test]
+fn merge_bases_are_tested_first_unless_they_are_bad() -> crate::Result {
+    let (repo, _tmp) = writable_repo("merges")?;
+    let bisect = repo.bisect();
+    bisect.start(Some(id(&repo, "m2")?), id(&repo, "s4")?)?;
+    let base = id(&repo, "base")?;
+    assert!(
+        matches!(bisect.next()?, Some(Step::Test { id, .. }) if id == base),
+        "the merge base isn't known to be good, so it must be tested first"
+    );
+
+    let bad = id(&repo, "m1")?;
+    bisect.start(Some(id(&repo, "m3")?), bad)?;
+    assert!(
+        matches!(bisect.next(), Err(Error::MergeBaseIsBad { merge_base }) if merge_base == bad),
+        "the bad commit is an ancestor of the good one"
+    );
+    Ok(())
+}
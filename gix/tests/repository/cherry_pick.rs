@@ -0,0 +1,131 @@
+use gix::cherry_pick::{Error, Options, Outcome};
+
+fn writable_repo() -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+    let tmp = gix_testtools::scripted_fixture_writable("make_cherry_pick_onto_repo.sh")?;
+    let repo = gix::open_opts(tmp.path(), crate::restricted())?;
+    Ok((repo, tmp))
+}
+
+fn id(repo: &gix::Repository, spec: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.rev_parse_single(spec)?.detach())
+}
+
+/// Assert that `actual` has the same tree, message, author and parents as the commit `expected` that git created.
+fn assert_same_commit(repo: &gix::Repository, actual: gix::Id<'_>, expected: &str) -> crate::Result {
+    let actual = actual.object()?.into_commit();
+    let expected = repo.rev_parse_single(expected)?.object()?.into_commit();
+    assert_eq!(actual.tree_id()?, expected.tree_id()?);
+    assert_eq!(actual.message_raw()?, expected.message_raw()?);
+    assert_eq!(actual.author()?, expected.author()?);
+    assert_eq!(
+        actual.parent_ids().collect::<Vec<_>>(),
+        expected.parent_ids().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        actual.committer()?.name,
+        repo.committer().expect("configured")?.name,
+        "the committer is the current one"
+    );
+    Ok(())
+}
+
+#[test]
+fn picks_like_git_and_records_the_origin() -> crate::Result {
+    let (repo, _tmp) = writable_repo()?;
+    let head_before = id(&repo, "HEAD")?;
+    let outcome = repo.cherry_pick(
+        id(&repo, "feature~1")?,
+        id(&repo, "main")?,
+        Options {
+            record_origin: true,
+            ..Default::default()
+        },
+    )?;
+    let Outcome::Commit(commit) = outcome else {
+        panic!("the changes apply cleanly")
+    };
+    assert_same_commit(&repo, commit, "picked")?;
+    assert_eq!(id(&repo, "HEAD")?, head_before, "no reference is changed");
+
+    let Outcome::Commit(commit) = repo.cherry_pick(id(&repo, "feature~1")?, id(&repo, "main")?, Options::default())?
+    else {
+        panic!("the changes apply cleanly")
+    };
+    assert_eq!(
+        commit.object()?.into_commit().message_raw()?,
+        "change b\n\nSigned-off-by: Author <author@example.com>\n",
+        "the message is kept as is"
+    );
+    Ok(())
+}
+
+#[test]
+fn merge_commits_are_picked_against_the_mainline() -> crate::Result {
+    let (repo, _tmp) = writable_repo()?;
+    let (merge, onto) = (id(&repo, "merged")?, id(&repo, "feature~1")?);
+    assert!(matches!(
+        repo.cherry_pick(merge, onto, Options::default()),
+        Err(Error::MainlineRequired { id }) if id == merge
+    ));
+    assert!(matches!(
+        repo.cherry_pick(
+            merge,
+            onto,
+            Options {
+                mainline: Some(3),
+                ..Default::default()
+            }
+        ),
+        Err(Error::NoSuchParent { mainline: 3, .. })
+    ));
+    assert!(
+        matches!(
+            repo.cherry_pick(
+                onto,
+                merge,
+                Options {
+                    mainline: Some(2),
+                    ..Default::default()
+                }
+            ),
+            Err(Error::NoSuchParent { mainline: 2, .. })
+        ),
+        "commits with a single parent only have a first parent"
+    );
+
+    let outcome = repo.cherry_pick(
+        merge,
+        onto,
+        Options {
+            record_origin: true,
+            mainline: Some(1),
+        },
+    )?;
+    let Outcome::Commit(commit) = outcome else {
+        panic!("the changes apply cleanly")
+    };
+    assert_same_commit(&repo, commit, "picked-merge")?;
+    Ok(())
+}
+
+#[test]
+fn conflicts_are_returned_with_all_stages_in_the_merged_index() -> crate::Result {
+    let (repo, _tmp) = writable_repo()?;
+    let outcome = repo.cherry_pick(id(&repo, "feature")?, id(&repo, "main")?, Options::default())?;
+    let Outcome::Conflicts { index, conflicts } = outcome else {
+        panic!("both sides changed 'a'")
+    };
+    assert_eq!(conflicts, ["a"]);
+
+    let blob = |spec: &str| -> crate::Result<_> { Ok(Some(id(&repo, spec)?)) };
+    let conflict = index.conflict_by_path("a".into()).expect("conflicted");
+    assert_eq!(conflict.base.map(|side| side.id), blob("feature~1:a")?);
+    assert_eq!(conflict.ours.map(|side| side.id), blob("main:a")?);
+    assert_eq!(conflict.theirs.map(|side| side.id), blob("feature:a")?);
+    assert_eq!(
+        index.entry_by_path("dir/b".into()).map(|entry| entry.id),
+        blob("main:dir/b")?,
+        "paths without conflicts are merged"
+    );
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::bstr::BString;
+use gix::clean::{Force, Ignored, Options, Removal};
+
+fn baseline(name: &str) -> crate::Result<Vec<String>> {
+    let path = gix_testtools::scripted_fixture_read_only("make_clean_repo.sh")?.join(format!("clean{name}.baseline"));
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .filter(|line| line.starts_with("Would remove "))
+        .map(ToOwned::to_owned)
+        .collect())
+}
+
+fn would_remove(repo: &gix::Repository, patterns: &[&str], options: Options) -> crate::Result<Vec<String>> {
+    Ok(repo
+        .clean(
+            patterns.iter().map(|p| BString::from(*p)),
+            &AtomicBool::default(),
+            options,
+        )?
+        .into_iter()
+        .filter(|entry| matches!(entry.removal, Removal::WouldRemove))
+        .map(|entry| {
+            format!(
+                "Would remove {}{}",
+                entry.rela_path,
+                if entry.disk_kind.is_dir() { "/" } else { "" }
+            )
+        })
+        .collect())
+}
+
+fn dry_run(directories: bool, ignored: Ignored) -> Options {
+    Options {
+        dry_run: true,
+        directories,
+        ignored,
+        force: Force::None,
+    }
+}
+
+#[test]
+fn dry_run_matches_git() -> crate::Result {
+    let repo = crate::named_subrepo_opts("make_clean_repo.sh", "repo", crate::restricted())?;
+    for (args, options) in [
+        ("-n", dry_run(false, Ignored::Keep)),
+        ("-nd", dry_run(true, Ignored::Keep)),
+        ("-nx", dry_run(false, Ignored::Remove)),
+        ("-ndx", dry_run(true, Ignored::Remove)),
+        ("-nX", dry_run(false, Ignored::Only)),
+        ("-ndX", dry_run(true, Ignored::Only)),
+    ] {
+        assert_eq!(would_remove(&repo, &[], options)?, baseline(args)?, "git clean {args}");
+    }
+    assert_eq!(
+        would_remove(&repo, &["tracked-dir", "untracked-dir"], dry_run(true, Ignored::Keep))?,
+        baseline("-nd-pathspec")?,
+        "git clean -nd -- tracked-dir untracked-dir"
+    );
+    Ok(())
+}
+
+#[test]
+fn nested_repositories_are_skipped_unless_forced_twice() -> crate::Result {
+    let repo = crate::named_subrepo_opts("make_clean_repo.sh", "repo", crate::restricted())?;
+    let skipped: Vec<_> = repo
+        .clean(None::<&str>, &AtomicBool::default(), dry_run(true, Ignored::Keep))?
+        .into_iter()
+        .filter(|entry| matches!(entry.removal, Removal::SkippedRepository))
+        .map(|entry| entry.rela_path)
+        .collect();
+    assert_eq!(skipped, ["nested", "untracked-with-repo/inner"]);
+
+    let mut options = dry_run(true, Ignored::Remove);
+    options.force = Force::Twice;
+    let actual = would_remove(&repo, &[], options)?;
+    assert!(actual.contains(&"Would remove nested/".to_string()));
+    assert!(actual.contains(&"Would remove untracked-with-repo/inner/".to_string()));
+    Ok(())
+}
+
+#[test]
+fn execution_requires_force_by_default() -> crate::Result {
+    let tmp = gix_testtools::scripted_fixture_writable("make_clean_repo.sh")?;
+    let mut repo = gix::open_opts(tmp.path().join("repo"), crate::restricted())?;
+    let options = Options {
+        directories: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        repo.clean(None::<&str>, &AtomicBool::default(), options),
+        Err(gix::clean::Error::ForceRequired)
+    ));
+    assert!(tmp.path().join("repo/untracked").is_file(), "nothing was removed");
+
+    repo.config_snapshot_mut()
+        .set_value(&gix::config::tree::Clean::REQUIRE_FORCE, "false")?;
+    let entries = repo.clean(None::<&str>, &AtomicBool::default(), options)?;
+    assert!(entries
+        .iter()
+        .all(|entry| matches!(entry.removal, Removal::Removed | Removal::SkippedRepository)));
+    assert!(!tmp.path().join("repo/untracked").exists());
+    Ok(())
+}
+
+#[test]
+fn execution_removes_entries_and_reports_each_of_them() -> crate::Result {
+    let tmp = gix_testtools::scripted_fixture_writable("make_clean_repo.sh")?;
+    let repo_dir = tmp.path().join("repo");
+    let repo = gix::open_opts(&repo_dir, crate::restricted())?;
+    let entries = repo.clean(
+        None::<&str>,
+        &AtomicBool::default(),
+        Options {
+            directories: true,
+            ignored: Ignored::Remove,
+            force: Force::Once,
+            ..Default::default()
+        },
+    )?;
+    for entry in &entries {
+        let exists = repo_dir.join(gix::path::from_bstr(entry.rela_path.as_ref())).exists();
+        match entry.removal {
+            Removal::Removed => assert!(!exists, "{} was removed", entry.rela_path),
+            Removal::SkippedRepository => assert!(exists, "{} is a repository and was kept", entry.rela_path),
+            _ => unreachable!("no other outcome expected"),
+        }
+    }
+    for kept in [
+        "tracked",
+        "tracked-dir/file",
+        ".gitignore",
+        "nested/file",
+        "untracked-with-repo/inner",
+    ] {
+        assert!(repo_dir.join(kept).exists(), "{kept} is tracked or in a repository");
+    }
+    for removed in [
+        "untracked",
+        "tracked-dir/file.o",
+        "build",
+        "empty",
+        "untracked-with-repo/file",
+    ] {
+        assert!(!repo_dir.join(removed).exists(), "{removed} was removed");
+    }
+
+    let entries = repo.clean(
+        None::<&str>,
+        &AtomicBool::default(),
+        Options {
+            directories: true,
+            force: Force::Twice,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(entries.len(), 2, "only the repositories are left");
+    assert!(!repo_dir.join("nested").exists());
+    assert!(
+        !repo_dir.join("untracked-with-repo/inner").exists(),
+        "the directory that contained it stays as it is emptied only now"
+    );
+    Ok(())
+}
@@ -15,6 +15,18 @@ mod ssh_options {
         assert_eq!(opts.command.as_deref(), Some(OsStr::new("ssh -VVV")));
         assert_eq!(opts.kind, Some(gix::protocol::transport::client::ssh::ProgramKind::Ssh));
         assert!(!opts.disallow_shell, "we can use the shell by default");
+        assert!(!opts.ignore_ssh_config, "the ssh configuration is used by default");
+        Ok(())
+    }
+
+    #[test]
+    fn ssh_config_can_be_ignored() -> crate::Result {
+        let mut repo = repo("ssh-all-options");
+        repo.config_snapshot_mut()
+            .set_value(&gix::config::tree::gitoxide::Ssh::IGNORE_SSH_CONFIG, "true")?;
+        let opts = repo.ssh_connect_options()?;
+        assert!(opts.ignore_ssh_config);
+        assert_eq!(opts.ssh_config_path(), None, "nothing will be read then");
         Ok(())
     }
 
@@ -0,0 +1,173 @@
+use gix::{config::tree::Core, hooks::Invocation};
+
+use crate::util::{repo_rw_opts, restricted};
+
+fn hooks_repo() -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+    let mut opts = restricted();
+    opts.permissions.hooks = true;
+    repo_rw_opts("make_basic_repo.sh", opts)
+}
+
+#[cfg(unix)]
+fn write_hook(dir: &std::path::Path, name: &str, script: &str) -> crate::Result {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{script}\n"))?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[test]
+fn hooks_dir_defaults_to_hooks_directory_in_git_dir() -> crate::Result {
+    let (repo, _tmp) = hooks_repo()?;
+    assert_eq!(repo.hooks_dir()?, repo.current_dir().join(repo.git_dir()).join("hooks"));
+    assert_eq!(
+        repo.hook_path("pre-commit")?,
+        None,
+        "sample hooks don't count, and there are no others"
+    );
+    Ok(())
+}
+
+#[test]
+fn hooks_dir_is_configured_with_core_hooks_path() -> crate::Result {
+    let (mut repo, tmp) = hooks_repo()?;
+    repo.config_snapshot_mut()
+        .set_value(&Core::HOOKS_PATH, "custom-hooks")?;
+    assert_eq!(
+        repo.hooks_dir()?,
+        repo.current_dir().join(tmp.path()).join("custom-hooks"),
+        "relative paths are relative to the worktree root"
+    );
+
+    let absolute = tmp.path().join("elsewhere");
+    repo.config_snapshot_mut()
+        .set_value(&Core::HOOKS_PATH, gix::path::into_bstr(&absolute).as_ref())?;
+    assert_eq!(repo.hooks_dir()?, absolute);
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn run_hook_passes_arguments_and_stdin_and_captures_output() -> crate::Result {
+    let (repo, tmp) = hooks_repo()?;
+    write_hook(
+        &repo.hooks_dir()?,
+        "pre-push",
+        r#"echo "$@ in $(pwd)"; cat; echo "to stderr" >&2; exit 3"#,
+    )?;
+    let id = repo.head_id()?.detach();
+    let outcome = repo
+        .run_hook(Invocation::PrePush {
+            remote_name: "origin".into(),
+            url: "https://example.com/repo".into(),
+            updates: &[gix::hooks::PushUpdate {
+                local_ref: "refs/heads/main".into(),
+                local_id: id,
+                remote_ref: "refs/heads/main".into(),
+                remote_id: gix::ObjectId::null(repo.object_hash()),
+            }],
+        })?
+        .expect("the hook exists and hooks are permitted");
+    assert!(!outcome.success());
+    assert_eq!(outcome.status.code(), Some(3));
+    assert_eq!(
+        outcome.stdout,
+        format!(
+            "origin https://example.com/repo in {}\nrefs/heads/main {id} refs/heads/main {}\n",
+            tmp.path().canonicalize()?.display(),
+            gix::ObjectId::null(repo.object_hash())
+        )
+    );
+    assert_eq!(outcome.stderr, "to stderr\n");
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn hooks_are_not_run_without_permission() -> crate::Result {
+    let (repo, _tmp) = crate::util::repo_rw("make_basic_repo.sh")?;
+    write_hook(&repo.hooks_dir()?, "pre-commit", "exit 1")?;
+    assert!(repo.hook_path("pre-commit")?.is_some());
+    assert!(
+        repo.run_hook(Invocation::PreCommit)?.is_none(),
+        "isolated repositories don't run hooks"
+    );
+
+    let head = repo.head_commit()?;
+    repo.commit("HEAD", "not verified", head.tree_id()?, Some(head.id))?;
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn non_executable_hooks_are_ignored() -> crate::Result {
+    let (repo, _tmp) = hooks_repo()?;
+    let hooks_dir = repo.hooks_dir()?;
+    write_hook(&hooks_dir, "pre-commit", "exit 1")?;
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(hooks_dir.join("pre-commit"), std::fs::Permissions::from_mode(0o644))?;
+    assert_eq!(repo.hook_path("pre-commit")?, None);
+    assert!(repo.run_hook(Invocation::PreCommit)?.is_none());
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn failing_pre_commit_hook_prevents_commit_unless_skipped() -> crate::Result {
+    let (repo, _tmp) = hooks_repo()?;
+    write_hook(&repo.hooks_dir()?, "pre-commit", "echo rejected >&2; exit 1")?;
+    let head = repo.head_commit()?;
+    let err = repo
+        .commit("HEAD", "rejected", head.tree_id()?, Some(head.id))
+        .unwrap_err();
+    assert!(
+        matches!(&err, gix::commit::Error::HookRejected { name: "pre-commit", stderr } if stderr == "rejected\n"),
+        "{err:?}"
+    );
+    assert_eq!(repo.head_id()?, head.id, "HEAD wasn't changed");
+
+    let committer = repo.committer().expect("configured")?;
+    let id = repo.commit_as_opts(
+        committer,
+        committer,
+        "HEAD",
+        "not verified",
+        head.tree_id()?,
+        Some(head.id),
+        gix::commit::Options {
+            allow_empty: true,
+            no_verify: true,
+        },
+    )?;
+    assert_eq!(repo.head_id()?, id, "hooks don't run with `no_verify`");
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn commit_msg_hook_can_edit_and_reject_the_message() -> crate::Result {
+    let (repo, _tmp) = hooks_repo()?;
+    let hooks_dir = repo.hooks_dir()?;
+    write_hook(&hooks_dir, "pre-commit", "exit 0")?;
+    write_hook(
+        &hooks_dir,
+        "commit-msg",
+        r#"grep -q WIP "$1" && exit 1; echo "Signed-off-by: hook" >> "$1""#,
+    )?;
+    let head = repo.head_commit()?;
+    let id = repo.commit("HEAD", "edited\n\n", head.tree_id()?, Some(head.id))?;
+    assert_eq!(
+        id.object()?.into_commit().message_raw()?,
+        "edited\n\nSigned-off-by: hook\n"
+    );
+
+    let err = repo.commit("HEAD", "WIP", head.tree_id()?, Some(id)).unwrap_err();
+    assert!(
+        matches!(err, gix::commit::Error::HookRejected { name: "commit-msg", .. }),
+        "{err:?}"
+    );
+    assert_eq!(repo.head_id()?, id, "HEAD wasn't changed");
+    Ok(())
+}
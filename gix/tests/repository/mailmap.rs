@@ -0,0 +1,46 @@
+use gix::config::tree::Mailmap;
+
+fn baseline(repo: &gix::Repository) -> crate::Result<Vec<String>> {
+    let baseline = std::fs::read_to_string(repo.work_dir().expect("non-bare").join("baseline.with-blob"))?;
+    Ok(baseline.lines().map(ToOwned::to_owned).collect())
+}
+
+fn mapped_author_and_committer(repo: &gix::Repository) -> crate::Result<Vec<String>> {
+    let mut mailmap = gix::mailmap::Snapshot::default();
+    repo.open_mailmap_into(&mut mailmap)?;
+    let commit = repo.head_commit()?;
+    Ok([commit.author_mapped(&mailmap)?, commit.committer_mapped(&mailmap)?]
+        .iter()
+        .map(|s| format!("{} <{}>", s.name, s.email))
+        .collect())
+}
+
+#[test]
+fn blob_overrides_worktree_file_like_in_git() -> crate::Result {
+    let repo = crate::named_subrepo_opts("make_mailmap_repo.sh", "worktree", crate::restricted())?;
+    assert_eq!(
+        mapped_author_and_committer(&repo)?,
+        baseline(&repo)?,
+        "the author is mapped by the blob, while the committer is mapped by the more specific entry of the worktree file"
+    );
+    assert_eq!(
+        repo.head_commit()?.author()?.name,
+        "author",
+        "the unmapped signature is still available"
+    );
+    Ok(())
+}
+
+#[test]
+fn configured_file_overrides_everything() -> crate::Result {
+    let mut repo = crate::named_subrepo_opts("make_mailmap_repo.sh", "worktree", crate::restricted())?;
+    let tmp = gix_testtools::tempfile::NamedTempFile::new()?;
+    std::fs::write(tmp.path(), "File Author <author@example.com>\n")?;
+    repo.config_snapshot_mut()
+        .set_value(&Mailmap::FILE, gix::path::into_bstr(tmp.path()).as_ref())?;
+
+    let mut expected = baseline(&repo)?;
+    expected[0] = "File Author <author@example.com>".into();
+    assert_eq!(mapped_author_and_committer(&repo)?, expected);
+    Ok(())
+}
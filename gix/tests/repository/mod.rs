@@ -1,17 +1,36 @@
 use gix::Repository;
 
+#[cfg(feature = "revision")]
+mod bisect;
+#[cfg(feature = "merge")]
+mod cherry_pick;
+#[cfg(feature = "dirwalk")]
+mod clean;
 mod config;
 #[cfg(feature = "excludes")]
 mod excludes;
 #[cfg(feature = "attributes")]
 mod filter;
+#[cfg(feature = "hooks")]
+mod hooks;
+#[cfg(feature = "mailmap")]
+mod mailmap;
 mod object;
 mod open;
 #[cfg(feature = "attributes")]
 mod pathspec;
 mod reference;
 mod remote;
+mod repack;
+#[cfg(feature = "worktree-mutation")]
+mod reset;
+#[cfg(feature = "merge")]
+mod revert;
 mod shallow;
+#[cfg(feature = "signing")]
+mod signing;
+#[cfg(feature = "stash")]
+mod stash;
 mod state;
 #[cfg(feature = "attributes")]
 mod submodule;
@@ -19,7 +38,7 @@ mod worktree;
 
 #[cfg(feature = "dirwalk")]
 mod dirwalk {
-    use gix_dir::entry::Kind::*;
+    use gix_dir::entry::{Kind::*, Status};
     use gix_dir::walk::EmissionMode;
     use std::sync::atomic::AtomicBool;
 
@@ -74,6 +93,58 @@ mod dirwalk {
         );
         Ok(())
     }
+
+    #[test]
+    fn excludes_from_git_dir_and_global_file_in_pathspec_scope() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let global_excludes = tmp.path().join("global-ignore");
+        std::fs::write(&global_excludes, "excluded-globally\n")?;
+        let (repo, _tmp) = crate::util::repo_rw_opts(
+            "make_basic_repo.sh",
+            crate::util::restricted()
+                .config_overrides([format!("core.excludesFile={}", gix::path::into_bstr(&global_excludes))]),
+        )?;
+        std::fs::write(repo.git_dir().join("info").join("exclude"), "excluded-by-info\n")?;
+        let workdir = repo.work_dir().expect("non-bare");
+        for dir in ["scope", "outside"] {
+            std::fs::create_dir(workdir.join(dir))?;
+            for file in ["excluded-by-info", "excluded-globally", "untracked"] {
+                std::fs::write(workdir.join(dir).join(file), "")?;
+            }
+        }
+
+        let mut collect = gix::dir::walk::delegate::Collect::default();
+        let index = repo.index()?;
+        repo.dirwalk(
+            &index,
+            Some("scope"),
+            &AtomicBool::default(),
+            repo.dirwalk_options()?
+                .emit_untracked(EmissionMode::Matching)
+                .emit_ignored(Some(EmissionMode::Matching)),
+            &mut collect,
+        )?;
+        assert_eq!(
+            collect
+                .into_entries_by_path()
+                .into_iter()
+                .map(|e| (e.0.rela_path.to_string(), e.0.status))
+                .collect::<Vec<_>>(),
+            [
+                (
+                    "scope/excluded-by-info".to_string(),
+                    Status::Ignored(gix::ignore::Kind::Expendable)
+                ),
+                (
+                    "scope/excluded-globally".into(),
+                    Status::Ignored(gix::ignore::Kind::Expendable)
+                ),
+                ("scope/untracked".into(), Status::Untracked),
+            ],
+            "both exclude files apply, and only paths matching the pathspec are seen"
+        );
+        Ok(())
+    }
 }
 
 #[test]
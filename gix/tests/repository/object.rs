@@ -111,7 +111,7 @@ fn writes_avoid_io_using_duplicate_check() -> crate::Result {
 mod find {
     use gix_pack::Find;
 
-    use crate::basic_repo;
+    use crate::{basic_repo, util::hex_to_id};
 
     #[test]
     fn find_and_try_find_with_and_without_object_cache() -> crate::Result {
@@ -152,6 +152,89 @@ mod find {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "max-performance-safe")]
+    fn delta_base_cache_limit_configures_a_pack_cache_shared_by_all_clones() -> crate::Result {
+        let dir = gix_testtools::scripted_fixture_read_only("../../../gix-pack/tests/fixtures/make_pack_gen_repo.sh")?;
+        let mut repo = gix::open_opts(dir, crate::restricted())?;
+        repo.config_snapshot_mut()
+            .set_value(&gix::config::tree::Core::DELTA_BASE_CACHE_LIMIT, "64m")?;
+        let ids = repo.objects.iter()?.collect::<Result<Vec<_>, _>>()?;
+        let find_all = |repo: &gix::Repository| -> crate::Result {
+            for id in &ids {
+                repo.find_object(*id)?;
+            }
+            Ok(())
+        };
+
+        let other = repo.clone();
+        find_all(&other)?;
+        let first = repo
+            .objects
+            .pack_cache_statistics()
+            .expect("memory capped caches keep statistics");
+        assert!(first.misses > 0, "deltas had to be resolved");
+        assert_eq!(
+            other.objects.pack_cache_statistics(),
+            Some(first),
+            "the cache is shared by all clones"
+        );
+
+        find_all(&repo)?;
+        let second = repo.objects.pack_cache_statistics().expect("present");
+        assert_eq!(
+            second.misses, first.misses,
+            "the cache is large enough to not resolve any delta twice"
+        );
+        assert!(second.hits > first.hits, "deltas were retrieved from the cache instead");
+        Ok(())
+    }
+
+    #[test]
+    fn prefix_with_and_without_kind() -> crate::Result {
+        use gix::repository::find_prefix::Outcome;
+        let base = gix_testtools::scripted_fixture_read_only("make_rev_spec_parse_repos.sh")?;
+        let repo = gix::open(base.join("ambiguous_blob_tree_commit"))?;
+        let (blob, tree, commit) = (
+            hex_to_id("0000000000b36b6aa7ea4b75318ed078f55505c3"),
+            hex_to_id("0000000000cdcf04beb2fab69e65622616294984"),
+            hex_to_id("0000000000e4f9fbd19cf1e932319e5ad0d1d00b"),
+        );
+
+        assert_eq!(
+            repo.find_prefix("0000000000", None)?,
+            Outcome::Ambiguous(vec![blob, tree, commit]),
+            "candidates are sorted"
+        );
+        assert_eq!(repo.find_prefix("0000000000e", None)?, Outcome::Single(commit));
+        for (kind, expected) in [
+            (gix_object::Kind::Blob, blob),
+            (gix_object::Kind::Tree, tree),
+            (gix_object::Kind::Commit, commit),
+        ] {
+            assert_eq!(
+                repo.find_prefix("0000", Some(kind))?,
+                Outcome::Single(expected),
+                "the kind disambiguates"
+            );
+        }
+        assert_eq!(
+            repo.find_prefix("0000", Some(gix_object::Kind::Tag))?,
+            Outcome::NotFound
+        );
+        assert_eq!(repo.find_prefix("12345678", None)?, Outcome::NotFound);
+        assert!(
+            matches!(
+                repo.find_prefix("000", None).unwrap_err(),
+                gix::repository::find_prefix::Error::InvalidPrefix(gix_hash::prefix::from_hex::Error::TooShort {
+                    hex_len: 3
+                })
+            ),
+            "git requires at least 4 hex characters"
+        );
+        Ok(())
+    }
+
     #[test]
     fn empty_tree_can_always_be_found() -> crate::Result {
         let repo = basic_repo()?;
@@ -220,6 +303,64 @@ mod tag {
         assert_eq!(tag.message, message);
         Ok(())
     }
+
+    #[test]
+    fn opts_detect_target_kind_and_refuse_to_overwrite_unless_forced() -> crate::Result {
+        let (repo, _keep) = crate::repo_rw("make_basic_repo.sh")?;
+        let head = repo.head_commit()?;
+        let tagger = repo.committer().expect("present")?;
+        let tag_ref = repo.tag_opts("v1", head.tree_id()?, Some(tagger), "tree", Default::default())?;
+        let tag = tag_ref.id().object()?.into_tag();
+        assert_eq!(tag.target_id()?, head.tree_id()?);
+        assert_eq!(
+            tag.decode()?.target_kind,
+            gix_object::Kind::Tree,
+            "the kind is looked up in the object database"
+        );
+        assert_eq!(tag.decode()?.pgp_signature, None, "tags aren't signed by default");
+
+        let err = repo
+            .tag_opts("v1", head.id, Some(tagger), "commit", Default::default())
+            .unwrap_err();
+        assert!(
+            matches!(err, gix::tag::Error::ReferenceEdit(_)),
+            "existing tags aren't overwritten by default"
+        );
+        let tag_ref = repo.tag_opts(
+            "v1",
+            head.id,
+            Some(tagger),
+            "commit",
+            gix::tag::Options {
+                force: true,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(tag_ref.id().object()?.into_tag().target_id()?, head.id);
+
+        for invalid in ["-v2", "v2..v3", "v2.lock", ""] {
+            assert!(
+                repo.tag_opts(invalid, head.id, Some(tagger), "invalid", Default::default())
+                    .is_err(),
+                "{invalid:?} isn't a valid tag name"
+            );
+        }
+        assert!(matches!(
+            repo.tag_opts("-v2", head.id, Some(tagger), "invalid", Default::default()),
+            Err(gix::tag::Error::InvalidName { name }) if name == "-v2"
+        ));
+        assert!(
+            repo.try_find_reference("refs/tags/-v2")?.is_none(),
+            "nothing was written for invalid names"
+        );
+
+        let missing = gix::ObjectId::from_hex(b"1111111111111111111111111111111111111111")?;
+        assert!(matches!(
+            repo.tag_opts("v2", missing, Some(tagger), "missing", Default::default()),
+            Err(gix::tag::Error::FindTarget(_))
+        ));
+        Ok(())
+    }
 }
 
 mod commit_as {
@@ -263,6 +404,74 @@ mod commit_as {
         assert_eq!(commit.author()?, author.to_ref());
         Ok(())
     }
+
+    #[test]
+    fn opts_reject_empty_commits_unless_allowed() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let repo = gix::ThreadSafeRepository::init_opts(
+            &tmp,
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted_and_git(),
+        )?
+        .to_thread_local();
+        let signature = gix::actor::Signature {
+            name: "c".into(),
+            email: "c@example.com".into(),
+            time: gix::date::Time::new(1, 0),
+        };
+        let empty_tree = repo.empty_tree().id;
+        let commit = |message: &str, parents: &[gix::ObjectId], options| {
+            repo.commit_as_opts(
+                &signature,
+                &signature,
+                "HEAD",
+                message,
+                empty_tree,
+                parents.iter().copied(),
+                options,
+            )
+        };
+
+        let initial = commit("initial", &[], Default::default())?.detach();
+        assert_eq!(
+            repo.head_ref()?.expect("born").name().as_bstr(),
+            "refs/heads/main",
+            "the unborn branch HEAD points to was created"
+        );
+        let err = commit("empty", &[initial], Default::default()).unwrap_err();
+        assert!(
+            matches!(err, gix::commit::Error::Empty { tree, parent } if tree == empty_tree && parent == initial),
+            "the tree didn't change compared to the parent"
+        );
+        assert_eq!(repo.head_id()?, initial, "HEAD wasn't changed");
+
+        let empty = commit(
+            "empty",
+            &[initial],
+            gix::commit::Options {
+                allow_empty: true,
+                ..Default::default()
+            },
+        )?
+        .detach();
+        let merge = commit("merge", &[empty, initial], Default::default())?;
+        assert_eq!(repo.head_id()?, merge, "merges are never considered empty");
+
+        let head_log_entries: Vec<_> = repo
+            .head()?
+            .log_iter()
+            .rev()?
+            .expect("log present")
+            .map(Result::unwrap)
+            .map(|l| l.message)
+            .collect();
+        assert_eq!(
+            head_log_entries,
+            vec!["commit (merge): merge", "commit: empty", "commit (initial): initial"]
+        );
+        Ok(())
+    }
 }
 
 mod commit {
@@ -130,6 +130,35 @@ fn non_bare_split_worktree_invalid_worktree_path_empty() -> crate::Result {
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "sha256")]
+fn sha256_repo() -> crate::Result {
+    let repo = crate::util::named_repo("../../../gix-odb/tests/fixtures/make_sha256_repo.sh")?;
+    assert_eq!(repo.object_hash(), gix::hash::Kind::Sha256);
+
+    let commit = repo.head_commit()?;
+    assert_eq!(commit.id.kind(), gix::hash::Kind::Sha256);
+    assert_eq!(commit.message_raw()?, "packed commit\n");
+    assert_eq!(commit.tree_id()?.kind(), gix::hash::Kind::Sha256);
+
+    let blob_id = std::fs::read_to_string(repo.work_dir().expect("non-bare").join("packed-blob-id"))?;
+    let blob = repo.find_object(gix::ObjectId::from_hex(blob_id.trim().as_bytes())?)?;
+    assert_eq!(blob.data, b"packed\n");
+    Ok(())
+}
+
+#[test]
+#[cfg(not(feature = "sha256"))]
+fn sha256_repo_needs_sha256_feature() -> crate::Result {
+    let repo_path = gix_testtools::scripted_fixture_read_only("../../../gix-odb/tests/fixtures/make_sha256_repo.sh")?;
+    let err = gix::open_opts(repo_path, gix::open::Options::isolated()).unwrap_err();
+    assert_eq!(
+        err.source().expect("present").to_string(),
+        "The key \"extensions.objectFormat=sha256\" was invalid"
+    );
+    Ok(())
+}
+
 mod missing_config_file {
 
     use crate::util::named_subrepo_opts;
@@ -289,6 +318,136 @@ mod object_caches {
     }
 }
 
+mod pack_options {
+    use gix::odb::pack::data::init::{Advice, Backing};
+
+    use crate::util::repo_opts;
+
+    fn repo(overrides: &[&str]) -> crate::Result<gix::Repository> {
+        Ok(repo_opts(
+            "make_packed_and_loose.sh",
+            gix::open::Options::isolated().config_overrides(overrides.iter().copied()),
+        )?
+        .to_thread_local())
+    }
+
+    #[test]
+    fn memory_maps_without_advice_by_default() -> crate::Result {
+        let repo = repo(&[])?;
+        let options = repo.objects.store_ref().pack_options();
+        assert_eq!(options.backing, Backing::MemoryMap);
+        assert_eq!(options.advice, Advice::Normal);
+        Ok(())
+    }
+
+    #[test]
+    fn can_be_configured() -> crate::Result {
+        let repo = repo(&[
+            "gitoxide.core.packedGitMmap=false",
+            "gitoxide.core.packedGitAdvice=sequential",
+        ])?;
+        let options = repo.objects.store_ref().pack_options();
+        assert_eq!(options.backing, Backing::Buffered);
+        assert_eq!(options.advice, Advice::Sequential);
+        assert_eq!(
+            repo.rev_walk(Some(repo.head_id()?)).all()?.count(),
+            3,
+            "packed objects can still be read"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_advice_is_rejected_unless_lenient() -> crate::Result {
+        let overrides = ["gitoxide.core.packedGitAdvice=none"];
+        let repo = repo(&overrides)?;
+        assert_eq!(
+            repo.objects.store_ref().pack_options().advice,
+            Advice::Normal,
+            "invalid values fall back to the default"
+        );
+
+        let err = repo_opts(
+            "make_packed_and_loose.sh",
+            gix::open::Options::isolated()
+                .config_overrides(overrides)
+                .strict_config(true),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err, gix::open::Error::Config(gix::config::Error::ConfigTypedString(_))),
+            "{err:?}"
+        );
+        Ok(())
+    }
+}
+
+mod replacement_objects {
+    use crate::util::{hex_to_id, repo_opts};
+
+    const SHORT_HISTORY_LINK: &str = "434e5a872d6738d1fffd1e11e52a1840b73668c6";
+
+    fn repo(overrides: &[&str]) -> crate::Result<gix::Repository> {
+        Ok(repo_opts(
+            // The history with replacements that is also used by `gix-odb`.
+            "../../../gix-odb/tests/fixtures/make_replaced_history.sh",
+            gix::open::Options::isolated().config_overrides(overrides.iter().copied()),
+        )?
+        .to_thread_local())
+    }
+
+    fn commits_reachable_from_head(repo: &gix::Repository) -> crate::Result<Vec<gix::ObjectId>> {
+        Ok(repo
+            .rev_walk(Some(repo.head_id()?.detach()))
+            .all()?
+            .map(|info| info.map(|info| info.id))
+            .collect::<Result<_, _>>()?)
+    }
+
+    #[test]
+    fn are_applied_by_default_and_followed_during_traversal() -> crate::Result {
+        let repo = repo(&[])?;
+        let id = hex_to_id(SHORT_HISTORY_LINK);
+        let commit = repo.find_object(id)?.into_commit();
+        assert_eq!(commit.id, id, "the replaced object keeps the id it was requested with");
+        assert_eq!(
+            commit.parent_ids().map(gix::Id::detach).collect::<Vec<_>>(),
+            [hex_to_id("753ccf815e7b69c9147db5bbf633fe5f7da24ad7")],
+            "the content is the one of the replacement"
+        );
+        assert_eq!(
+            commits_reachable_from_head(&repo)?.len(),
+            4,
+            "the long history is visible through the replaced parent"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn can_be_disabled() -> crate::Result {
+        for overrides in [
+            &["core.useReplaceRefs=false"][..],
+            &["gitoxide.objects.replaceRefBase=refs/does-not-exist/"],
+        ] {
+            let repo = repo(overrides)?;
+            assert_eq!(
+                commits_reachable_from_head(&repo)?.len(),
+                3,
+                "{overrides:?}: the short history ends in an orphan"
+            );
+        }
+
+        let mut repo = repo(&[])?;
+        repo.objects.ignore_replacements = true;
+        assert_eq!(
+            commits_reachable_from_head(&repo)?.len(),
+            3,
+            "replacements can also be ignored at runtime"
+        );
+        Ok(())
+    }
+}
+
 mod worktree {
     use gix::open;
 
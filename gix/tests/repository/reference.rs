@@ -1,5 +1,8 @@
 mod set_namespace {
-    use gix::refs::transaction::PreviousValue;
+    use gix::refs::{
+        transaction::{Change, PreviousValue, RefEdit},
+        Target,
+    };
     use gix_testtools::tempfile;
 
     fn easy_repo_rw() -> crate::Result<(gix::Repository, tempfile::TempDir)> {
@@ -78,6 +81,84 @@ mod set_namespace {
         );
         Ok(())
     }
+
+    #[test]
+    fn namespaces_have_disjoint_references_but_share_objects() -> crate::Result {
+        let (repo, _keep) = easy_repo_rw()?;
+        let open_in_namespace = |namespace: &str| {
+            gix::open_opts(
+                repo.path(),
+                crate::restricted().cli_overrides([format!("gitoxide.core.refsNamespace={namespace}")]),
+            )
+        };
+        let (a, b) = (open_in_namespace("a")?, open_in_namespace("b")?);
+        assert_eq!(
+            a.namespace().map(|ns| ns.as_bstr().to_owned()),
+            Some("refs/namespaces/a/".into())
+        );
+
+        let id = a.write_blob("shared")?.detach();
+        a.reference("refs/heads/main", id, PreviousValue::MustNotExist, "create in a")?;
+        a.edit_reference(RefEdit {
+            change: Change::Update {
+                log: Default::default(),
+                expected: PreviousValue::MustNotExist,
+                new: Target::Symbolic("refs/heads/main".try_into()?),
+            },
+            name: "HEAD".try_into()?,
+            deref: false,
+        })?;
+        b.reference("refs/heads/other", id, PreviousValue::MustNotExist, "create in b")?;
+
+        let names = |repo: &gix::Repository| -> crate::Result<Vec<_>> {
+            repo.references()?
+                .all()?
+                .map(|r| r.map(|r| r.name().as_bstr().to_owned()))
+                .collect()
+        };
+        assert_eq!(
+            names(&a)?,
+            ["HEAD", "refs/heads/main"],
+            "HEAD is stored as reference within the namespace, so it's visible in iteration"
+        );
+        assert_eq!(
+            names(&b)?,
+            ["refs/heads/other"],
+            "each namespace only sees its own references"
+        );
+
+        assert_eq!(
+            a.head_name()?.expect("not detached").as_bstr(),
+            "refs/heads/main",
+            "HEAD is namespaced, and so is its referent"
+        );
+        assert_eq!(a.head_id()?, id);
+        assert_eq!(
+            std::fs::read(repo.git_dir().join("refs/namespaces/a/HEAD"))?,
+            b"ref: refs/namespaces/a/refs/heads/main\n",
+            "on disk, the referent is within the namespace just like git would write it"
+        );
+        assert!(b.try_find_reference("HEAD")?.is_none(), "b has no HEAD of its own");
+        assert!(b.find_object(id).is_ok(), "objects are shared though");
+
+        let all: Vec<_> = repo
+            .references()?
+            .all()?
+            .filter_map(Result::ok)
+            .map(|r| r.name().as_bstr().to_owned())
+            .collect();
+        for name in [
+            "refs/namespaces/a/HEAD",
+            "refs/namespaces/a/refs/heads/main",
+            "refs/namespaces/b/refs/heads/other",
+        ] {
+            assert!(
+                all.iter().any(|n| n == name),
+                "without namespace, all namespaced references are visible"
+            );
+        }
+        Ok(())
+    }
 }
 
 mod iter_references {
@@ -148,6 +229,33 @@ mod iter_references {
         Ok(())
     }
 
+    #[test]
+    fn matching() -> crate::Result {
+        let repo = repo()?;
+        let matching = |pattern: &str| -> crate::Result<Vec<_>> {
+            Ok(repo
+                .references()?
+                .matching(pattern.into())?
+                .filter_map(Result::ok)
+                .map(|r| r.name().as_bstr().to_string())
+                .collect())
+        };
+        assert_eq!(
+            matching("refs/heads/multi-link-target?")?,
+            ["refs/heads/multi-link-target1"]
+        );
+        assert_eq!(
+            matching("refs/*/multi-link*")?,
+            [
+                "refs/heads/multi-link-target1",
+                "refs/remotes/origin/multi-link-target3",
+                "refs/tags/multi-link-target2"
+            ],
+            "'*' matches across directories"
+        );
+        Ok(())
+    }
+
     #[test]
     fn prefixed_and_peeled() -> crate::Result {
         let repo = repo()?;
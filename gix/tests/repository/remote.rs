@@ -61,10 +61,27 @@ mod remote_at {
             .remote_at("https://github.com/foobar/gitoxide".to_owned())?
             .push_url("file://dev/null".to_owned())?;
         assert_eq!(remote.url(Direction::Fetch).unwrap().to_bstring(), rewritten_fetch_url);
+        assert_eq!(
+            remote.url(Direction::Push).unwrap().to_bstring(),
+            "file://dev/null",
+            "push-url rewrite rules don't apply to explicit push urls, and no fetch rule matches"
+        );
+
+        let remote = repo.remote_at("file://dev/null")?;
+        assert_eq!(
+            remote.url(Direction::Fetch).unwrap().to_bstring(),
+            "file://dev/null",
+            "push-url rewrite rules don't affect fetching…"
+        );
         assert_eq!(
             remote.url(Direction::Push).unwrap().to_bstring(),
             "ssh://dev/null",
-            "push-url rewrite rules are applied"
+            "…but apply when the fetch url is used for pushing"
+        );
+        assert_eq!(
+            remote.url_without_url_rewrite(Direction::Push).unwrap().to_bstring(),
+            "file://dev/null",
+            "the original is retained"
         );
         Ok(())
     }
@@ -198,14 +215,15 @@ mod find_remote {
 
         let remote = repo.find_remote("origin")?;
         assert_eq!(remote.url(Direction::Fetch).unwrap().to_bstring(), expected_fetch_url,);
-        {
-            let actual_push_url = remote.url(Direction::Push).unwrap().to_bstring();
-            assert_ne!(
-                actual_push_url, expected_push_url,
-                "here we actually resolve something that git doesn't probably because it's missing the host. Our parser is OK with it for some reason."
-            );
-            assert_eq!(actual_push_url, "ssh://dev/null", "file:// gets replaced actually");
-        }
+        assert_eq!(
+            remote.url(Direction::Push).unwrap().to_bstring(),
+            expected_push_url,
+            "`pushInsteadOf` doesn't apply to explicit push urls"
+        );
+        assert_eq!(
+            remote.url_without_url_rewrite(Direction::Fetch).unwrap().to_bstring(),
+            "https://github.com/foobar/gitoxide"
+        );
 
         let mut remote = repo.try_find_remote_without_url_rewrite("origin").expect("exists")?;
         assert_eq!(
@@ -214,7 +232,27 @@ mod find_remote {
         );
         assert_eq!(remote.url(Direction::Push).unwrap().to_bstring(), "file://dev/null");
         remote.rewrite_urls()?;
-        assert_eq!(remote.url(Direction::Push).unwrap().to_bstring(), "ssh://dev/null");
+        assert_eq!(remote.url(Direction::Fetch).unwrap().to_bstring(), expected_fetch_url);
+        assert_eq!(remote.url(Direction::Push).unwrap().to_bstring(), expected_push_url);
+        Ok(())
+    }
+
+    #[test]
+    fn url_rewrites_use_the_longest_match_like_git() -> crate::Result {
+        let repo = remote::repo("url-rewriting-longest-match");
+
+        let baseline = std::fs::read(repo.git_dir().join("baseline.git"))?;
+        let mut baseline = baseline.lines().map_while(Result::ok);
+        for name in ["origin", "explicit-push"] {
+            let remote = repo.find_remote(name)?;
+            for direction in [Direction::Fetch, Direction::Push] {
+                assert_eq!(
+                    remote.url(direction).unwrap().to_bstring(),
+                    baseline.next().expect("one line per remote and direction"),
+                    "{name} {direction:?}"
+                );
+            }
+        }
         Ok(())
     }
 
@@ -227,15 +265,15 @@ mod find_remote {
         let expected_fetch_url: BString = baseline.next().expect("fetch").into();
         let expected_push_url: BString = baseline.next().expect("push").into();
         assert_eq!(
-            expected_push_url, "file://dev/null",
-            "git leaves the failed one as is without any indication…"
+            expected_push_url, "invalid://[dev/null",
+            "git doesn't validate the rewritten url…"
         );
         assert_eq!(
             expected_fetch_url, "https://github.com/byron/gitoxide",
             "…but is able to replace the fetch url successfully"
         );
 
-        let expected_err_msg = "The rewritten push url \"invalid:://dev/null\" failed to parse";
+        let expected_err_msg = "The rewritten push url \"invalid://[dev/null\" failed to parse";
         assert_eq!(
             repo.find_remote("origin").unwrap_err().to_string(),
             expected_err_msg,
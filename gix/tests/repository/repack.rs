@@ -0,0 +1,105 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::repository::repack_loose_objects::{Error, Options};
+
+use crate::util::repo_rw;
+
+fn loose_objects(repo: &gix::Repository) -> usize {
+    gix::odb::loose::Store::at(repo.objects.store_ref().path(), repo.object_hash())
+        .iter()
+        .count()
+}
+
+fn packs(repo: &gix::Repository) -> crate::Result<usize> {
+    Ok(std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "pack"))
+        .count())
+}
+
+#[test]
+fn all_loose_objects_are_packed_and_pruned() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_packed_and_loose.sh")?;
+    assert_eq!(
+        loose_objects(&repo),
+        3,
+        "the last commit with its tree and the only new blob"
+    );
+    assert_eq!(packs(&repo)?, 1);
+
+    let outcome = repo.repack_loose_objects(
+        None,
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+        Options { prune: true },
+    )?;
+    assert_eq!(outcome.num_objects_packed, 3);
+    assert_eq!(outcome.num_loose_objects_removed, 3);
+    assert!(outcome.bytes_reclaimed > 0);
+    assert_eq!(outcome.write.expect("a pack was written").index.num_objects, 3);
+    assert_eq!(loose_objects(&repo), 0);
+    assert_eq!(packs(&repo)?, 2);
+
+    let head = repo.head_commit()?;
+    assert_eq!(head.message()?.title, "loose\n", "all objects can still be read");
+    assert_eq!(head.tree()?.iter().count(), 2);
+
+    let outcome = repo.repack_loose_objects(
+        None,
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+        Options { prune: true },
+    )?;
+    assert_eq!(outcome.num_objects_packed, 0, "there is nothing left to pack");
+    assert!(outcome.write.is_none());
+    Ok(())
+}
+
+#[test]
+fn only_loose_objects_reachable_from_tips_are_packed() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_packed_and_loose.sh")?;
+    let parent = repo.rev_parse_single("HEAD~1")?.detach();
+    let outcome = repo.repack_loose_objects(
+        Some(&[parent]),
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+        Options { prune: true },
+    )?;
+    assert_eq!(
+        outcome.num_objects_packed, 0,
+        "everything reachable from the parent is packed already"
+    );
+    assert_eq!(loose_objects(&repo), 3);
+
+    let head = repo.head_id()?.detach();
+    let outcome = repo.repack_loose_objects(
+        Some(&[head]),
+        &mut gix::progress::Discard,
+        &AtomicBool::default(),
+        Options::default(),
+    )?;
+    assert_eq!(outcome.num_objects_packed, 3);
+    assert_eq!(outcome.num_loose_objects_removed, 0, "pruning is disabled");
+    assert_eq!(loose_objects(&repo), 3);
+    Ok(())
+}
+
+#[test]
+fn interruption_leaves_loose_objects_and_packs_untouched() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_packed_and_loose.sh")?;
+    let err = repo
+        .repack_loose_objects(
+            None,
+            &mut gix::progress::Discard,
+            &AtomicBool::new(true),
+            Options { prune: true },
+        )
+        .unwrap_err();
+    assert!(
+        matches!(err, Error::Repack(gix::odb::loose::repack::Error::Interrupted)),
+        "{err:?}"
+    );
+    assert_eq!(loose_objects(&repo), 3);
+    assert_eq!(packs(&repo)?, 1, "no partial pack is left behind");
+    Ok(())
+}
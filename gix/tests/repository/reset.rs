@@ -0,0 +1,138 @@
+use gix::bstr::BString;
+use gix::reset::{Error, Mode};
+
+fn writable_repo(name: &str) -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+    let tmp = gix_testtools::scripted_fixture_writable("make_reset_repo.sh")?;
+    let repo = gix::open_opts(tmp.path().join(name), crate::restricted())?;
+    Ok((repo, tmp))
+}
+
+fn git_repo(tmp: &gix_testtools::tempfile::TempDir, name: &str) -> crate::Result<gix::Repository> {
+    Ok(gix::open_opts(tmp.path().join(name), crate::restricted())?)
+}
+
+fn read(repo: &gix::Repository, rela_path: &str) -> Option<String> {
+    std::fs::read_to_string(repo.work_dir().expect("non-bare").join(rela_path)).ok()
+}
+
+fn index_entries(
+    repo: &gix::Repository,
+) -> crate::Result<
+    Vec<(
+        BString,
+        gix::ObjectId,
+        gix::index::entry::Mode,
+        gix::index::entry::Stage,
+    )>,
+> {
+    let index = repo.open_index()?;
+    Ok(index
+        .entries()
+        .iter()
+        .map(|entry| (entry.path(&index).to_owned(), entry.id, entry.mode, entry.stage()))
+        .collect())
+}
+
+fn first_commit(repo: &gix::Repository) -> crate::Result<gix::ObjectId> {
+    Ok(repo.rev_parse_single("HEAD~1")?.detach())
+}
+
+/// Assert that `HEAD` of `repo` now points to its first commit, and that its previous commit is in `ORIG_HEAD`.
+fn assert_head_moved(repo: &gix::Repository, first: gix::ObjectId, previous: gix::ObjectId) -> crate::Result {
+    assert_eq!(repo.head_id()?, first);
+    assert_eq!(repo.head_name()?.expect("not detached").shorten(), "main");
+    assert_eq!(repo.find_reference("ORIG_HEAD")?.id(), previous);
+    let head = repo.find_reference("HEAD")?;
+    let mut log = head.log_iter();
+    let last = log.rev()?.expect("reflog exists").next().expect("one line")?;
+    assert_eq!(last.message, format!("reset: moving to {first}"));
+    assert_eq!(last.new_oid, first);
+    Ok(())
+}
+
+#[test]
+fn soft_only_moves_head() -> crate::Result {
+    let (repo, tmp) = writable_repo("local-changes")?;
+    let (first, previous) = (first_commit(&repo)?, repo.head_id()?.detach());
+    let index_before = index_entries(&repo)?;
+    repo.reset(first, Mode::Soft)?;
+
+    assert_head_moved(&repo, first, previous)?;
+    assert_eq!(index_entries(&repo)?, index_before, "the index is unchanged");
+    assert_eq!(index_entries(&repo)?, index_entries(&git_repo(&tmp, "git-soft")?)?);
+    assert_eq!(read(&repo, "a").as_deref(), Some("a2\nlocal\n"));
+    Ok(())
+}
+
+#[test]
+fn soft_fails_during_a_merge() -> crate::Result {
+    let (repo, _tmp) = writable_repo("local-changes")?;
+    let previous = repo.head_id()?.detach();
+    std::fs::write(repo.git_dir().join("MERGE_HEAD"), format!("{previous}\n"))?;
+    assert!(matches!(
+        repo.reset(first_commit(&repo)?, Mode::Soft),
+        Err(Error::SoftResetDuringMerge)
+    ));
+    assert_eq!(repo.head_id()?, previous, "nothing was changed");
+
+    repo.reset(first_commit(&repo)?, Mode::Mixed)?;
+    assert!(
+        !repo.git_dir().join("MERGE_HEAD").exists(),
+        "other modes abort the merge"
+    );
+    Ok(())
+}
+
+#[test]
+fn mixed_resets_the_index_but_not_the_worktree() -> crate::Result {
+    let (repo, tmp) = writable_repo("local-changes")?;
+    let (first, previous) = (first_commit(&repo)?, repo.head_id()?.detach());
+    repo.reset(first, Mode::default())?;
+
+    assert_head_moved(&repo, first, previous)?;
+    assert_eq!(index_entries(&repo)?, index_entries(&git_repo(&tmp, "git-mixed")?)?);
+    assert_eq!(read(&repo, "a").as_deref(), Some("a2\nlocal\n"));
+    assert_eq!(read(&repo, "d").as_deref(), Some("d\nstaged\n"));
+    assert_eq!(read(&repo, "dir/b"), None, "files aren't restored either");
+    Ok(())
+}
+
+#[test]
+fn hard_resets_the_index_and_the_worktree() -> crate::Result {
+    let (repo, tmp) = writable_repo("local-changes")?;
+    let (first, previous) = (first_commit(&repo)?, repo.head_id()?.detach());
+    repo.reset(first, Mode::Hard)?;
+
+    assert_head_moved(&repo, first, previous)?;
+    let git = git_repo(&tmp, "git-hard")?;
+    assert_eq!(index_entries(&repo)?, index_entries(&git)?);
+    for path in ["a", "c", "dir/b", "d", "untracked"] {
+        assert_eq!(read(&repo, path), read(&git, path), "{path}");
+    }
+    assert_eq!(
+        read(&repo, "a").as_deref(),
+        Some("a\n"),
+        "modified files are overwritten"
+    );
+    assert_eq!(read(&repo, "d"), None, "files that aren't in the target are removed");
+    assert_eq!(read(&repo, "untracked").as_deref(), Some("untracked\n"));
+    assert!(!repo.is_dirty()?);
+    Ok(())
+}
+
+#[test]
+fn paths_only_reset_matching_index_entries() -> crate::Result {
+    let (repo, tmp) = writable_repo("local-changes")?;
+    let previous = repo.head_id()?.detach();
+    repo.reset_paths(first_commit(&repo)?, ["a", "dir"])?;
+
+    assert_eq!(repo.head_id()?, previous, "HEAD is unchanged");
+    assert_eq!(index_entries(&repo)?, index_entries(&git_repo(&tmp, "git-paths")?)?);
+    assert_eq!(
+        read(&repo, "a").as_deref(),
+        Some("a2\nlocal\n"),
+        "the worktree is unchanged"
+    );
+    assert_eq!(read(&repo, "dir/b"), None);
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use gix::revert::{Error, Options, Outcome};
+
+fn writable_repo() -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+    let tmp = gix_testtools::scripted_fixture_writable("make_cherry_pick_onto_repo.sh")?;
+    let repo = gix::open_opts(tmp.path(), crate::restricted())?;
+    Ok((repo, tmp))
+}
+
+fn id(repo: &gix::Repository, spec: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.rev_parse_single(spec)?.detach())
+}
+
+/// Assert that `actual` has the same tree, message and parents as the commit `expected` that git created, and
+/// that it was authored by the configured author.
+fn assert_same_commit(repo: &gix::Repository, actual: gix::Id<'_>, expected: &str) -> crate::Result {
+    let actual = actual.object()?.into_commit();
+    let expected = repo.rev_parse_single(expected)?.object()?.into_commit();
+    assert_eq!(actual.tree_id()?, expected.tree_id()?);
+    assert_eq!(actual.message_raw()?, expected.message_raw()?);
+    assert_eq!(
+        actual.parent_ids().collect::<Vec<_>>(),
+        expected.parent_ids().collect::<Vec<_>>()
+    );
+    assert_eq!(actual.author()?.name, repo.author().expect("configured")?.name);
+    Ok(())
+}
+
+#[test]
+fn reverts_like_git_and_reapplies_reverts() -> crate::Result {
+    let (repo, _tmp) = writable_repo()?;
+    let Outcome::Commit(revert) = repo.revert(id(&repo, "main")?, id(&repo, "main")?, Options::default())? else {
+        panic!("the inverse changes apply cleanly")
+    };
+    assert_same_commit(&repo, revert, "reverted")?;
+
+    let Outcome::Commit(reapply) = repo.revert(revert, revert, Options::default())? else {
+        panic!("the inverse changes apply cleanly")
+    };
+    let reapply = reapply.object()?.into_commit();
+    assert_eq!(reapply.tree_id()?, id(&repo, "main^{tree}")?);
+    assert_eq!(
+        reapply.message_raw()?,
+        format!("Reapply \"change a\"\n\nThis reverts commit {revert}.\n").as_str(),
+        "reverting a revert is described as reapplying the original commit, like newer versions of git do"
+    );
+    Ok(())
+}
+
+#[test]
+fn merge_commits_are_reverted_against_the_mainline() -> crate::Result {
+    let (repo, _tmp) = writable_repo()?;
+    let merge = id(&repo, "merged")?;
+    assert!(matches!(
+        repo.revert(merge, merge, Options::default()),
+        Err(Error::MainlineRequired { id }) if id == merge
+    ));
+    assert!(matches!(
+        repo.revert(merge, merge, Options { mainline: Some(3) }),
+        Err(Error::NoSuchParent { mainline: 3, .. })
+    ));
+
+    let Outcome::Commit(commit) = repo.revert(merge, merge, Options { mainline: Some(1) })? else {
+        panic!("the inverse changes apply cleanly")
+    };
+    assert_same_commit(&repo, commit, "reverted-merge")?;
+    Ok(())
+}
+
+#[test]
+fn conflicts_are_returned_with_all_stages_in_the_merged_index() -> crate::Result {
+    let (repo, _tmp) = writable_repo()?;
+    let outcome = repo.revert(id(&repo, "main")?, id(&repo, "feature")?, Options::default())?;
+    let Outcome::Conflicts { index, conflicts } = outcome else {
+        panic!("both sides changed the same line of 'a'")
+    };
+    assert_eq!(conflicts, ["a"]);
+
+    let blob = |spec: &str| -> crate::Result<_> { Ok(Some(id(&repo, spec)?)) };
+    let conflict = index.conflict_by_path("a".into()).expect("conflicted");
+    assert_eq!(conflict.base.map(|side| side.id), blob("main:a")?);
+    assert_eq!(conflict.ours.map(|side| side.id), blob("feature:a")?);
+    assert_eq!(conflict.theirs.map(|side| side.id), blob("main~1:a")?);
+    assert_eq!(
+        index.entry_by_path("dir/b".into()).map(|entry| entry.id),
+        blob("feature:dir/b")?,
+        "paths without conflicts are merged"
+    );
+    Ok(())
+}
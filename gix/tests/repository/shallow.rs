@@ -60,9 +60,16 @@ mod traverse {
                     .ancestors()
                     .use_commit_graph(toggle)
                     .all()?
-                    .map(|c| c.map(|c| c.id))
+                    .map(|c| c.map(|c| (c.id, c.parent_ids)))
                     .collect::<Result<_, _>>()?;
-                assert_eq!(commits, [hex_to_id("30887839de28edf7ab66c860e5c58b4d445f6b12")]);
+                assert_eq!(
+                    commits,
+                    [(
+                        hex_to_id("30887839de28edf7ab66c860e5c58b4d445f6b12"),
+                        Default::default()
+                    )],
+                    "shallow commits have no parents, just like in `git`"
+                );
             }
         }
         Ok(())
@@ -121,4 +128,82 @@ mod traverse {
         }
         Ok(())
     }
+
+    #[test]
+    #[parallel]
+    fn grafts_file_rewrites_parents() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_shallow_repo.sh")?;
+        let repo = gix::open_opts(tmp.path().join("base"), crate::restricted())?;
+        let head = repo.head_id()?.detach();
+        let root = repo.rev_parse_single("HEAD~2")?.detach();
+        std::fs::write(repo.git_dir().join("info").join("grafts"), format!("{head} {root}\n"))?;
+
+        for toggle in [false, true] {
+            let commits: Vec<_> = repo
+                .head_id()?
+                .ancestors()
+                .use_commit_graph(toggle)
+                .all()?
+                .map(|c| c.map(|c| c.id))
+                .collect::<Result<_, _>>()?;
+            assert_eq!(
+                commits,
+                [head, root],
+                "the parent of HEAD was replaced by its grandparent"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+mod write {
+    use gix::protocol::fetch::response::ShallowUpdate;
+    use serial_test::parallel;
+
+    use crate::util::hex_to_id;
+
+    #[test]
+    #[parallel]
+    fn updates_are_applied_without_duplicates_and_empty_files_are_removed() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_shallow_repo.sh")?;
+        let repo = gix::open_opts(tmp.path().join("shallow"), crate::restricted())?;
+        let existing = hex_to_id("30887839de28edf7ab66c860e5c58b4d445f6b12");
+        let new = hex_to_id("d8523dfd5a7aa16562fa1c3e1d3b4a4494f97876");
+
+        gix::shallow::write(
+            lock(&repo)?,
+            repo.shallow_commits()?,
+            &[
+                ShallowUpdate::Shallow(new),
+                ShallowUpdate::Shallow(existing),
+                ShallowUpdate::Shallow(new),
+            ],
+        )?;
+        assert_eq!(
+            std::fs::read_to_string(repo.shallow_file())?,
+            format!("{existing}\n{new}\n"),
+            "the file is sorted and doesn't contain duplicates"
+        );
+
+        let commits = Some(vec![existing, new]);
+        let updates = [ShallowUpdate::Unshallow(existing), ShallowUpdate::Unshallow(new)];
+        gix::shallow::write(lock(&repo)?, commits.map(snapshot), &updates)?;
+        assert!(!repo.shallow_file().exists(), "empty shallow files are removed");
+
+        gix::shallow::write(lock(&repo)?, None, &updates)?;
+        assert!(
+            !repo.shallow_file().exists(),
+            "it's no error if there is no shallow file to remove"
+        );
+        Ok(())
+    }
+
+    fn lock(repo: &gix::Repository) -> Result<gix::lock::File, gix::lock::acquire::Error> {
+        gix::lock::File::acquire_to_update_resource(repo.shallow_file(), gix::lock::acquire::Fail::Immediately, None)
+    }
+
+    fn snapshot(commits: Vec<gix::ObjectId>) -> gix::shallow::Commits {
+        gix::shallow::Commits::new(commits.into())
+    }
 }
@@ -0,0 +1,267 @@
+use gix::{
+    config::tree::{Commit, Gpg, Tag, User},
+    signing::{verify::Status, verify::Trust, Format},
+};
+
+/// The directory of the signing fixture, which stops the `gpg-agent` that was started for it when dropped.
+struct Fixture(gix_testtools::tempfile::TempDir);
+
+impl Fixture {
+    fn path(&self) -> &std::path::Path {
+        self.0.path()
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        std::process::Command::new("gpgconf")
+            .args(["--kill", "all"])
+            .env("GNUPGHOME", self.path().join("gnupg"))
+            .status()
+            .ok();
+    }
+}
+
+/// Return `true` if the programs needed to sign and verify aren't installed, unless we are on CI where they must be.
+fn signing_programs_are_missing() -> bool {
+    if gix_testtools::is_ci::cached() {
+        return false;
+    }
+    ["gpg", "gpgconf", "ssh-keygen"].into_iter().any(|program| {
+        std::process::Command::new(program)
+            .arg("--version")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_err()
+    })
+}
+
+/// Return the repository of the signing fixture, or `None` if the signing programs aren't available.
+fn signing_repo() -> crate::Result<Option<(gix::Repository, Fixture)>> {
+    if signing_programs_are_missing() {
+        return Ok(None);
+    }
+    let dir = Fixture(gix_testtools::scripted_fixture_writable_with_args(
+        "make_signing_repo.sh",
+        None::<String>,
+        gix_testtools::Creation::ExecuteScript,
+    )?);
+    let mut repo = gix::open_opts(dir.path().join("repo"), gix::open::Options::isolated())?;
+    let mut config = repo.config_snapshot_mut();
+    config.set_value(&Commit::GPG_SIGN, "true")?;
+    config.set_value(&Tag::GPG_SIGN, "true")?;
+    config.commit()?;
+    Ok(Some((repo, dir)))
+}
+
+fn git(repo: &gix::Repository, args: &[&str]) -> crate::Result<bool> {
+    Ok(gix_testtools::run_git(repo.work_dir().expect("non-bare"), args)?.success())
+}
+
+fn commit_and_tag(repo: &gix::Repository) -> crate::Result<(gix::ObjectId, gix::ObjectId)> {
+    let head = repo.head_commit()?;
+    let commit_id = repo.commit("HEAD", "signed", head.tree_id()?, Some(head.id))?.detach();
+    let tagger = repo.committer().expect("configured")?;
+    let tag = repo.tag(
+        "signed",
+        commit_id,
+        gix::object::Kind::Commit,
+        Some(tagger),
+        "signed",
+        gix_ref::transaction::PreviousValue::MustNotExist,
+    )?;
+    let tag_id = tag.id().detach();
+    Ok((commit_id, tag_id))
+}
+
+#[test]
+fn ssh_signatures_of_commits_and_tags_are_verified_by_git_and_by_us() -> crate::Result {
+    let Some((mut repo, dir)) = signing_repo()? else {
+        return Ok(());
+    };
+    let mut config = repo.config_snapshot_mut();
+    config.set_value(&Gpg::FORMAT, "ssh")?;
+    config.set_value(
+        &User::SIGNING_KEY,
+        gix::path::into_bstr(dir.path().join("ssh-key")).as_ref(),
+    )?;
+    config.commit()?;
+
+    let (commit_id, tag_id) = commit_and_tag(&repo)?;
+    assert!(git(&repo, &["verify-commit", &commit_id.to_string()])?);
+    assert!(git(&repo, &["verify-tag", "signed"])?);
+
+    let commit = repo.find_object(commit_id)?.into_commit();
+    let outcome = commit.verify_signature()?.expect("signed");
+    assert_eq!(outcome.format, Format::Ssh);
+    assert_eq!(outcome.status, Status::Good);
+    assert_eq!(
+        outcome.trust,
+        Trust::Fully,
+        "the signer is listed in the allowed signers file"
+    );
+    assert_eq!(outcome.signer, Some("committer@example.com".into()));
+    assert!(outcome.key.as_ref().expect("present").starts_with(b"SHA256:"));
+    assert!(outcome.is_valid(Trust::Fully));
+
+    let tag = repo.find_object(tag_id)?.into_tag();
+    assert_eq!(
+        tag.decode()?.message,
+        "signed",
+        "the message isn't affected by the signature"
+    );
+    assert!(
+        tag.decode()?
+            .pgp_signature
+            .expect("signed")
+            .ends_with(b"-----END SSH SIGNATURE-----\n"),
+        "like in git, the signature retains its trailing newline"
+    );
+    let outcome = tag.verify_signature()?.expect("signed");
+    assert!(outcome.is_valid(Trust::Fully));
+
+    let untrusted_key = dir.path().join("untrusted-ssh-key");
+    assert!(git(
+        &repo,
+        &[
+            "-c",
+            "gpg.format=ssh",
+            "-c",
+            &format!("user.signingKey={}", untrusted_key.display()),
+            "commit",
+            "-q",
+            "-S",
+            "--allow-empty",
+            "-m",
+            "signed by git"
+        ]
+    )?);
+    let outcome = repo.head_commit()?.verify_signature()?.expect("signed");
+    assert_eq!(outcome.status, Status::Good, "the signature itself is valid…");
+    assert_eq!(outcome.trust, Trust::Undefined, "…but its signer isn't known");
+    assert_eq!(outcome.signer, None);
+    assert!(!outcome.is_valid(Trust::Marginal));
+    Ok(())
+}
+
+#[test]
+fn openpgp_signatures_of_commits_and_tags_are_verified_by_git_and_by_us() -> crate::Result {
+    let Some((repo, _dir)) = signing_repo()? else {
+        return Ok(());
+    };
+    let options = repo.signing_options()?;
+    assert_eq!(options.format, Format::OpenPgp);
+    assert_eq!(
+        options.signing_key,
+        Some("committer <committer@example.com>".into()),
+        "the committer identity is the default key"
+    );
+
+    let (commit_id, tag_id) = commit_and_tag(&repo)?;
+    assert!(git(&repo, &["verify-commit", &commit_id.to_string()])?);
+    assert!(git(&repo, &["verify-tag", "signed"])?);
+
+    for outcome in [
+        repo.find_object(commit_id)?.into_commit().verify_signature()?,
+        repo.find_object(tag_id)?.into_tag().verify_signature()?,
+    ] {
+        let outcome = outcome.expect("signed");
+        assert_eq!(outcome.format, Format::OpenPgp);
+        assert_eq!(outcome.status, Status::Good);
+        assert_eq!(outcome.trust, Trust::Ultimate, "our own keys are trusted ultimately");
+        assert_eq!(outcome.signer, Some("committer <committer@example.com>".into()));
+        assert!(outcome.key.is_some());
+    }
+
+    let unsigned = repo.find_object(repo.head_commit()?.parent_ids().next().expect("parent"))?;
+    assert_eq!(
+        unsigned.into_commit().verify_signature()?,
+        None,
+        "unsigned commits have no signature"
+    );
+    Ok(())
+}
+
+#[test]
+fn tag_signing_can_be_overridden() -> crate::Result {
+    let Some((mut repo, _dir)) = signing_repo()? else {
+        return Ok(());
+    };
+    let head = repo.head_id()?.detach();
+    let tagger = repo.committer().expect("configured")?;
+    let unsigned = repo.tag_opts(
+        "unsigned",
+        head,
+        Some(tagger),
+        "unsigned",
+        gix::tag::Options {
+            sign: Some(false),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        unsigned.id().object()?.into_tag().verify_signature()?,
+        None,
+        "tag.gpgSign is overridden"
+    );
+
+    repo.config_snapshot_mut().set_value(&Tag::GPG_SIGN, "false")?;
+    let signed = repo.tag_opts(
+        "signed",
+        head,
+        Some(repo.committer().expect("configured")?),
+        "signed",
+        gix::tag::Options {
+            sign: Some(true),
+            ..Default::default()
+        },
+    )?;
+    let outcome = signed.id().object()?.into_tag().verify_signature()?.expect("signed");
+    assert_eq!(outcome.status, Status::Good);
+    assert!(git(&repo, &["verify-tag", "signed"])?);
+    Ok(())
+}
+
+#[test]
+fn tampered_data_fails_verification() -> crate::Result {
+    let Some((mut repo, dir)) = signing_repo()? else {
+        return Ok(());
+    };
+    let (commit_id, _) = commit_and_tag(&repo)?;
+    {
+        let commit = repo.find_object(commit_id)?.into_commit();
+        let (signature, signed_data) = commit.signature()?.expect("signed");
+        let mut payload = gix::bstr::BString::from(signed_data);
+        payload.extend_from_slice(b"tampered");
+        let options = repo.signing_options()?;
+        let outcome = gix::signing::verify(&payload, signature.as_ref(), &options)?;
+        assert_eq!(outcome.status, Status::Bad);
+        assert!(!outcome.is_valid(Trust::Undefined));
+    }
+
+    let mut config = repo.config_snapshot_mut();
+    config.set_value(&Gpg::FORMAT, "ssh")?;
+    config.set_value(
+        &User::SIGNING_KEY,
+        gix::path::into_bstr(dir.path().join("ssh-key")).as_ref(),
+    )?;
+    config.commit()?;
+    let options = repo.signing_options()?;
+    let signature = gix::signing::sign(b"payload", &options)?;
+    assert_eq!(
+        gix::signing::verify(b"tampered", signature.as_ref(), &options)?.status,
+        Status::Bad
+    );
+
+    let mut options = options;
+    options.allowed_signers_file = None;
+    assert!(
+        matches!(
+            gix::signing::verify(b"payload", signature.as_ref(), &options),
+            Err(gix::signing::verify::Error::MissingAllowedSignersFile)
+        ),
+        "like git, we don't verify SSH signatures without allowed signers"
+    );
+    Ok(())
+}
@@ -0,0 +1,234 @@
+use gix::bstr::BString;
+use gix::stash::{apply, push};
+
+fn writable_repo(name: &str) -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+    let tmp = gix_testtools::scripted_fixture_writable("make_stash_repo.sh")?;
+    let repo = gix::open_opts(tmp.path().join(name), crate::restricted())?;
+    Ok((repo, tmp))
+}
+
+fn read(repo: &gix::Repository, rela_path: &str) -> Option<String> {
+    std::fs::read_to_string(repo.work_dir().expect("non-bare").join(rela_path)).ok()
+}
+
+/// Return the tree and message of the stash commit `id`, followed by the ones of its index and untracked files commits.
+fn stash_trees_and_messages(repo: &gix::Repository, id: gix::ObjectId) -> crate::Result<Vec<(gix::ObjectId, BString)>> {
+    let commit = repo.find_object(id)?.try_into_commit()?;
+    let mut out = vec![(commit.tree_id()?.detach(), commit.message_raw()?.to_owned())];
+    for id in commit.parent_ids().skip(1) {
+        let commit = id.object()?.try_into_commit()?;
+        out.push((commit.tree_id()?.detach(), commit.message_raw()?.to_owned()));
+    }
+    Ok(out)
+}
+
+fn messages(repo: &gix::Repository) -> crate::Result<Vec<String>> {
+    Ok(repo
+        .stash_list()?
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| format!("stash@{{{index}}}: {}", entry.message))
+        .collect())
+}
+
+fn index_entries(repo: &gix::Repository) -> crate::Result<Vec<(BString, gix::ObjectId, gix::index::entry::Stage)>> {
+    let index = repo.open_index()?;
+    Ok(index
+        .entries()
+        .iter()
+        .map(|entry| (entry.path(&index).to_owned(), entry.id, entry.stage()))
+        .collect())
+}
+
+#[test]
+fn push_creates_the_same_stash_as_git_and_resets_the_worktree() -> crate::Result {
+    let (repo, tmp) = writable_repo("local-changes")?;
+    let git_repo = gix::open_opts(tmp.path().join("git-stashed"), crate::restricted())?;
+
+    let id = repo
+        .stash_push(push::Options {
+            include_untracked: true,
+            ..Default::default()
+        })?
+        .expect("there are local changes");
+    let expected = git_repo.find_reference(gix::stash::REFERENCE)?.id();
+    assert_eq!(
+        stash_trees_and_messages(&repo, id.detach())?,
+        stash_trees_and_messages(&git_repo, expected.detach())?,
+        "the commits of the worktree, the index and untracked files are the same, except for their dates"
+    );
+    assert_eq!(
+        messages(&repo)?,
+        std::fs::read_to_string(tmp.path().join("git-stashed.list"))?
+            .lines()
+            .collect::<Vec<_>>(),
+        "the reflog message is the one `git stash list` shows"
+    );
+
+    assert!(!repo.is_dirty()?, "index and worktree are reset to HEAD");
+    assert_eq!(read(&repo, "a").as_deref(), Some("a\n"));
+    assert_eq!(read(&repo, "dir/b").as_deref(), Some("b\n"));
+    assert_eq!(read(&repo, "c").as_deref(), Some("c\n"), "deleted files are restored");
+    assert_eq!(read(&repo, "new"), None, "added files are removed");
+    assert_eq!(read(&repo, "untracked"), None, "untracked files were stashed as well");
+    assert_eq!(
+        read(&repo, "ignored").as_deref(),
+        Some("ignored\n"),
+        "ignored files are kept"
+    );
+    assert_eq!(index_entries(&repo)?, index_entries(&git_repo)?);
+    Ok(())
+}
+
+#[test]
+fn push_without_changes_does_nothing() -> crate::Result {
+    let (repo, _tmp) = writable_repo("git-stashed")?;
+    assert_eq!(repo.stash_push(Default::default())?, None);
+    assert_eq!(repo.stash_list()?.len(), 1, "the existing stash is untouched");
+    Ok(())
+}
+
+#[test]
+fn pop_restores_changes_of_a_stash_created_by_git() -> crate::Result {
+    let (repo, _tmp) = writable_repo("git-stashed")?;
+    let outcome = repo.stash_pop(0, Default::default())?;
+    assert!(outcome.conflicts.is_empty());
+
+    assert_eq!(read(&repo, "a").as_deref(), Some("a\nstaged\n"));
+    assert_eq!(read(&repo, "dir/b").as_deref(), Some("b\nunstaged\n"));
+    assert_eq!(read(&repo, "c"), None);
+    assert_eq!(read(&repo, "new").as_deref(), Some("new\n"));
+    assert_eq!(read(&repo, "untracked").as_deref(), Some("untracked\n"));
+
+    let index = repo.open_index()?;
+    let mut head_tree = repo.head_tree_id()?.object()?.peel_to_tree()?;
+    for path in ["a", "c", "dir/b"] {
+        assert_eq!(
+            index.entry_by_path(path.into()).map(|entry| entry.id),
+            head_tree.peel_to_entry_by_path(path)?.map(|entry| entry.object_id()),
+            "{path}: without restoring the index, changes aren't staged"
+        );
+    }
+    assert!(index.entry_by_path("new".into()).is_some(), "new files are added");
+    assert!(index.entry_by_path("untracked".into()).is_none());
+
+    assert!(repo.stash_list()?.is_empty(), "the stash was dropped");
+    assert!(repo.try_find_reference(gix::stash::REFERENCE)?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn apply_with_index_restores_the_index_and_keeps_the_stash() -> crate::Result {
+    let (repo, tmp) = writable_repo("git-stashed")?;
+    let outcome = repo.stash_apply(0, apply::Options { index: true })?;
+    assert!(outcome.conflicts.is_empty());
+    assert_eq!(
+        repo.stash_list()?.into_iter().map(|e| e.id).collect::<Vec<_>>(),
+        [outcome.id]
+    );
+
+    let local_changes = gix::open_opts(tmp.path().join("local-changes"), crate::restricted())?;
+    assert_eq!(index_entries(&repo)?, index_entries(&local_changes)?);
+    for path in ["a", "dir/b", "c", "new", "untracked"] {
+        assert_eq!(read(&repo, path), read(&local_changes, path), "{path}");
+    }
+    Ok(())
+}
+
+#[test]
+fn apply_records_conflicts_and_pop_keeps_the_stash() -> crate::Result {
+    let (repo, _tmp) = writable_repo("conflicting")?;
+    let outcome = repo.stash_pop(0, Default::default())?;
+    assert_eq!(outcome.conflicts, ["dir/b"]);
+    assert_eq!(
+        read(&repo, "dir/b").as_deref(),
+        Some("b\n<<<<<<< Updated upstream\nupstream\n=======\nunstaged\n>>>>>>> Stashed changes\n")
+    );
+    assert_eq!(
+        read(&repo, "a").as_deref(),
+        Some("a\nstaged\n"),
+        "other changes are applied"
+    );
+
+    let index = repo.open_index()?;
+    let conflict = index.conflict_by_path("dir/b".into()).expect("conflicted");
+    assert!(conflict.base.is_some() && conflict.ours.is_some() && conflict.theirs.is_some());
+    assert_eq!(
+        repo.stash_list()?.len(),
+        1,
+        "the stash is kept if it didn't apply cleanly"
+    );
+    Ok(())
+}
+
+#[test]
+fn apply_fails_if_local_changes_would_be_overwritten() -> crate::Result {
+    let (repo, _tmp) = writable_repo("git-stashed")?;
+    let path = repo.work_dir().expect("non-bare").join("dir/b");
+    std::fs::write(&path, "local\n")?;
+    assert!(matches!(
+        repo.stash_apply(0, Default::default()),
+        Err(apply::Error::WouldOverwrite { path }) if path == "dir/b"
+    ));
+    assert_eq!(std::fs::read_to_string(&path)?, "local\n");
+
+    std::fs::write(&path, "b\n")?;
+    std::fs::write(repo.work_dir().expect("non-bare").join("untracked"), "local\n")?;
+    assert!(matches!(
+        repo.stash_apply(0, Default::default()),
+        Err(apply::Error::WouldOverwrite { path }) if path == "untracked"
+    ));
+    assert_eq!(read(&repo, "a").as_deref(), Some("a\n"), "nothing was changed");
+    Ok(())
+}
+
+#[test]
+fn push_with_keep_index_and_drop() -> crate::Result {
+    let (repo, _tmp) = writable_repo("local-changes")?;
+    let first = repo
+        .stash_push(push::Options {
+            keep_index: true,
+            ..Default::default()
+        })?
+        .expect("changes");
+    assert_eq!(
+        read(&repo, "a").as_deref(),
+        Some("a\nstaged\n"),
+        "staged changes are kept"
+    );
+    assert_eq!(read(&repo, "new").as_deref(), Some("new\n"));
+    assert_eq!(read(&repo, "c"), None);
+    assert_eq!(
+        read(&repo, "dir/b").as_deref(),
+        Some("b\n"),
+        "unstaged changes are stashed"
+    );
+    assert_eq!(read(&repo, "untracked").as_deref(), Some("untracked\n"));
+
+    let second = repo
+        .stash_push(push::Options {
+            message: Some("second".into()),
+            ..Default::default()
+        })?
+        .expect("the index still has changes");
+    assert_eq!(read(&repo, "a").as_deref(), Some("a\n"));
+    assert_eq!(messages(&repo)?.len(), 2);
+    assert_eq!(messages(&repo)?[0], "stash@{0}: On main: second");
+
+    assert_eq!(repo.stash_drop(1)?, first.detach());
+    assert_eq!(
+        repo.stash_list()?.into_iter().map(|e| e.id).collect::<Vec<_>>(),
+        [second]
+    );
+    assert_eq!(repo.find_reference(gix::stash::REFERENCE)?.id(), second);
+
+    assert_eq!(repo.stash_drop(0)?, second.detach());
+    assert!(repo.stash_list()?.is_empty());
+    assert!(repo.try_find_reference(gix::stash::REFERENCE)?.is_none());
+    assert!(matches!(
+        repo.stash_drop(0),
+        Err(gix::stash::drop::Error::NotFound { index: 0 })
+    ));
+    Ok(())
+}
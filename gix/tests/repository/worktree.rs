@@ -329,6 +329,16 @@ fn run_assertions(main_repo: gix::Repository, should_be_bare: bool) {
         assert_eq!(worktree.lock_reason(), proxy_lock_reason);
         assert_eq!(worktree.is_locked(), proxy_is_locked);
         assert_eq!(worktree.id(), Some(proxy_id.as_ref()));
+        assert_eq!(
+            repo.head_id().unwrap(),
+            expected.peeled,
+            "HEAD is private to each worktree, and isn't the one of the main worktree"
+        );
+        assert_eq!(
+            repo.head_name().unwrap().map(|name| name.as_bstr().to_owned()),
+            expected.branch,
+            "the branch checked out in the worktree is read from its private HEAD"
+        );
         assert_eq!(
             repo.main_repo().unwrap(),
             main_repo,
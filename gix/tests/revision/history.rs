@@ -0,0 +1,138 @@
+use gix::bstr::{BStr, ByteSlice};
+use gix_worktree::stack::state::attributes::Source;
+
+use crate::util::named_repo;
+
+fn baseline(repo: &gix::Repository, name: &str) -> Vec<gix::ObjectId> {
+    let path = repo.work_dir().expect("non-bare").join(format!("{name}.baseline"));
+    std::fs::read_to_string(path)
+        .expect("baseline exists")
+        .lines()
+        .map(|hex| gix::ObjectId::from_hex(hex.as_bytes()).expect("valid hex"))
+        .collect()
+}
+
+fn history<'repo>(
+    repo: &'repo gix::Repository,
+    patterns: &[&str],
+    configure_walk: impl FnOnce(gix::revision::walk::Platform<'repo>) -> gix::revision::walk::Platform<'repo>,
+) -> crate::Result<gix::revision::History<'repo>> {
+    let pathspec = repo.pathspec(
+        true,
+        patterns.iter().map(|p| BStr::new(p.as_bytes())),
+        false,
+        &gix::index::State::new(repo.object_hash()),
+        Source::IdMapping,
+    )?;
+    let walk = configure_walk(
+        repo.rev_walk(Some(repo.head_id()?))
+            .sorting(gix::traverse::commit::simple::Sorting::ByCommitTimeNewestFirst),
+    );
+    Ok(walk.all()?.with_pathspec(pathspec))
+}
+
+fn commit_by_message(repo: &gix::Repository, message: &str) -> crate::Result<gix::ObjectId> {
+    for info in repo.rev_walk(Some(repo.head_id()?)).all()? {
+        let info = info?;
+        if info.object()?.message_raw()?.trim() == message.as_bytes() {
+            return Ok(info.id);
+        }
+    }
+    Err(format!("no commit with message '{message}'").into())
+}
+
+fn ids(history: gix::revision::History<'_>) -> crate::Result<Vec<gix::ObjectId>> {
+    Ok(history.map(|info| info.map(|info| info.id)).collect::<Result<_, _>>()?)
+}
+
+#[test]
+fn merges_are_only_skipped_if_they_are_the_same_as_all_parents() -> crate::Result {
+    let repo = named_repo("make_history_repo.sh")?;
+    assert_eq!(
+        ids(history(&repo, &["file.txt"], |walk| walk)?)?,
+        baseline(&repo, "file-full-history"),
+        "`git log --full-history -- file.txt`"
+    );
+    assert_eq!(
+        ids(history(&repo, &["renamed.txt"], |walk| walk)?)?,
+        baseline(&repo, "renamed-full-history"),
+        "without following renames, history ends where the file was added"
+    );
+    Ok(())
+}
+
+#[test]
+fn without_simplification_merges_are_compared_to_the_first_parent() -> crate::Result {
+    let repo = named_repo("make_history_repo.sh")?;
+    let expected = baseline(&repo, "file-first-parent");
+    assert_eq!(
+        ids(history(&repo, &["file.txt"], gix::revision::walk::Platform::first_parent_only)?.simplify_merges(false))?,
+        expected,
+        "`git log --first-parent -- file.txt`"
+    );
+
+    let all_commits = ids(history(&repo, &["file.txt"], |walk| walk)?.simplify_merges(false))?;
+    let merge_side = commit_by_message(&repo, "merge side")?;
+    assert!(
+        all_commits.contains(&merge_side),
+        "the merge changes the file compared to its first parent"
+    );
+    assert!(all_commits.len() > expected.len(), "commits on the side are included");
+    Ok(())
+}
+
+#[test]
+fn directories_globs_and_exclusions() -> crate::Result {
+    let repo = named_repo("make_history_repo.sh")?;
+    for (patterns, name) in [
+        (&["dir"][..], "dir-full-history"),
+        (&["*a.txt"], "glob-full-history"),
+        (&[":!other.txt"], "exclude-full-history"),
+    ] {
+        assert_eq!(
+            ids(history(&repo, patterns, |walk| walk)?)?,
+            baseline(&repo, name),
+            "`git log --full-history -- {patterns:?}`"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn follow_switches_to_the_source_of_renames() -> crate::Result {
+    let repo = named_repo("make_history_repo.sh")?;
+    let mut history = history(&repo, &["renamed.txt"], |walk| walk)?.follow()?;
+    assert_eq!(history.followed_path(), Some("renamed.txt".into()));
+    let mut actual = Vec::new();
+    for info in history.by_ref() {
+        actual.push(info?.id);
+    }
+    assert_eq!(
+        actual,
+        baseline(&repo, "renamed-follow"),
+        "`git log --follow -- renamed.txt`"
+    );
+    assert_eq!(
+        history.followed_path(),
+        Some("file.txt".into()),
+        "the path was switched when passing the rename"
+    );
+    let rename = commit_by_message(&repo, "rename and change file")?;
+    assert!(actual.contains(&rename), "the rename itself is a change");
+    Ok(())
+}
+
+#[test]
+fn follow_needs_a_single_path() -> crate::Result {
+    let repo = named_repo("make_history_repo.sh")?;
+    for patterns in [&["file.txt", "other.txt"][..], &["*.txt"], &[":!file.txt"], &[]] {
+        assert!(
+            matches!(
+                history(&repo, patterns, |walk| walk)?.follow(),
+                Err(gix::revision::history::Error::FollowNeedsSinglePath)
+            ),
+            "{patterns:?}"
+        );
+    }
+    Ok(())
+}
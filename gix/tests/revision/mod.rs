@@ -1 +1,3 @@
+#[cfg(feature = "blob-diff")]
+mod history;
 mod spec;
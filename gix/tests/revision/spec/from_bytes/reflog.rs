@@ -78,10 +78,49 @@ fn by_index() {
 }
 
 #[test]
-fn by_date_is_planned_until_git_date_crate_is_implements_parsing() {
-    let repo = repo("complex_graph").unwrap();
+fn by_date() {
+    let repo = &repo("complex_graph").unwrap();
+    for (spec, expected) in [
+        (
+            "main@{2005-04-07 15:18:00 -0700}",
+            "a8fbd8e0c8753ef07ccdbfa93da030d53b68f0bc",
+        ),
+        (
+            "main@{2005-04-07 15:20:13 -0700}",
+            "5b3f9e24965d0b28780b7ce5daf2b5b7f7e0459f",
+        ),
+        ("main@{2.days.ago}", "55e825ebe8fd2ff78cad3826afb696b96b576a7e"),
+        ("@{1 week ago}", "55e825ebe8fd2ff78cad3826afb696b96b576a7e"),
+        ("main@{last monday}", "55e825ebe8fd2ff78cad3826afb696b96b576a7e"),
+    ] {
+        let parsed = parse_spec_no_baseline(spec, repo).unwrap_or_else(|err| panic!("{spec}: {err}"));
+        assert_eq!(parsed, Spec::from_id(hex_to_id(expected).attach(repo)), "{spec}");
+        assert_eq!(parsed.first_reference().expect("set").name.as_bstr(), "refs/heads/main");
+        assert_eq!(parsed.reflog_date_out_of_range(), None, "{spec}");
+    }
+}
+
+#[test]
+fn by_date_before_the_oldest_entry_uses_the_oldest_value() {
+    let repo = &repo("complex_graph").unwrap();
+    let spec = parse_spec_no_baseline("main@{1979-02-26 18:30:00}", repo).unwrap();
+    assert_eq!(
+        spec,
+        Spec::from_id(hex_to_id("9f9eac6bd1cd4b4cc6a494f044b28c985a22972b").attach(repo)),
+        "like git, the value the oldest entry was created with is used"
+    );
+    assert_eq!(
+        spec.reflog_date_out_of_range().map(|time| time.seconds),
+        Some(1112911993),
+        "the caller can learn that the reflog doesn't go back that far"
+    );
+}
+
+#[test]
+fn by_date_unborn_head() {
+    let repo = &repo("new").unwrap();
     assert!(matches!(
-        parse_spec_no_baseline("main@{1979-02-26 18:30:00}", &repo).unwrap_err(),
-        Error::Planned { .. }
+        parse_spec_no_baseline("@{1979-02-26 18:30:00}", repo).unwrap_err(),
+        Error::UnbornHeadsHaveNoRefLog
     ));
 }
@@ -23,6 +23,7 @@ mod index_worktree {
         use pretty_assertions::assert_eq;
 
         #[test]
+        #[cfg_attr(feature = "sha256", ignore = "SHA256 makes object ids larger")]
         fn item_size() {
             assert_eq!(
                 std::mem::size_of::<gix::status::index_worktree::iter::Item>(),
@@ -165,10 +166,109 @@ mod is_dirty {
         Ok(())
     }
 
+    #[test]
+    fn staged_changes_are_picked_up() -> crate::Result {
+        let repo = crate::status::repo("staged-only")?;
+        assert!(
+            repo.is_dirty()?,
+            "the index differs from the head tree, but not from the worktree"
+        );
+        Ok(())
+    }
+
     #[test]
     fn no_changes() -> crate::Result {
-        let repo = submodule_repo("with-submodules")?;
+        let repo = submodule_repo("with-submodules-after-clone")?;
         assert!(!repo.is_dirty()?, "there are no changes");
+
+        let repo = submodule_repo("with-submodules")?;
+        assert!(
+            repo.is_dirty()?,
+            "the worktree matches the index, but a submodule was added to the index"
+        );
+        Ok(())
+    }
+}
+
+mod porcelain {
+    use crate::status::repo;
+    use gix::status::{porcelain, IgnoredFiles, UntrackedFiles};
+    use pretty_assertions::assert_eq;
+
+    fn baseline(repo: &gix::Repository, name: &str) -> Vec<String> {
+        let path = repo
+            .work_dir()
+            .and_then(std::path::Path::parent)
+            .expect("non-bare repo in fixture directory")
+            .join(format!("{name}.baseline"));
+        std::fs::read_to_string(path)
+            .expect("baseline exists")
+            .lines()
+            .map(ToOwned::to_owned)
+            .collect()
+    }
+
+    fn status(
+        repo: &gix::Repository,
+        configure: impl FnOnce(
+            gix::status::Platform<'_, gix::progress::Discard>,
+        ) -> gix::status::Platform<'_, gix::progress::Discard>,
+    ) -> crate::Result<Vec<String>> {
+        let items = configure(repo.status(gix::progress::Discard)?)
+            .into_iter(None)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(porcelain::entries(items).iter().map(ToString::to_string).collect())
+    }
+
+    #[test]
+    fn staged_unstaged_and_untracked_changes() -> crate::Result {
+        let repo = repo("all-changes")?;
+        let actual = status(&repo, |status| status)?;
+        assert_eq!(actual, baseline(&repo, "all-changes"), "`git status --porcelain`");
+        assert!(
+            actual
+                .iter()
+                .any(|line| line == "R  renamed-source -> renamed-destination"),
+            "renames are tracked by default"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn without_rename_tracking() -> crate::Result {
+        let repo = repo("all-changes")?;
+        assert_eq!(
+            status(&repo, |status| status.tree_index_rewrites(None))?,
+            baseline(&repo, "all-changes-no-renames"),
+            "`git status --porcelain --no-renames`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn untracked_files_and_ignored_files() -> crate::Result {
+        let repo = repo("all-changes")?;
+        assert_eq!(
+            status(&repo, |status| status.untracked_files(UntrackedFiles::Files))?,
+            baseline(&repo, "all-changes-untracked-files-all"),
+            "`git status --porcelain --untracked-files=all`"
+        );
+        assert_eq!(
+            status(&repo, |status| status.ignored_files(IgnoredFiles::Collapsed))?,
+            baseline(&repo, "all-changes-ignored"),
+            "`git status --porcelain --ignored`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn conflicts() -> crate::Result {
+        let repo = repo("conflicts")?;
+        assert_eq!(
+            status(&repo, |status| status)?,
+            baseline(&repo, "conflicts"),
+            "`git status --porcelain`"
+        );
         Ok(())
     }
 }
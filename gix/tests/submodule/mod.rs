@@ -342,3 +342,195 @@ mod open {
         Ok(())
     }
 }
+
+#[cfg(all(feature = "blocking-network-client", feature = "worktree-mutation"))]
+mod superproject_configuration {
+    use gix::bstr::ByteSlice;
+    use gix::submodule::config::{Branch, Update};
+
+    use crate::util::named_subrepo_opts;
+
+    #[test]
+    fn overrides_values_from_gitmodules() -> crate::Result {
+        let repo = named_subrepo_opts(
+            "make_submodules.sh",
+            "with-submodules",
+            gix::open::Options::isolated().config_overrides([
+                "submodule.m1.branch=other",
+                "submodule.m1.update=!true",
+                "submodule.m1.path=overridden",
+            ]),
+        )?;
+        let gitmodules = std::fs::read_to_string(repo.work_dir().expect("non-bare").join(".gitmodules"))?;
+        let mut submodules = repo.submodules()?.expect("modules present");
+        let (m1, dir_m1) = (submodules.next().expect("m1"), submodules.next().expect("dir/m1"));
+        assert_eq!(m1.name(), "m1");
+
+        assert!(gitmodules.contains("url = ../module1"), "{gitmodules}");
+        let configured_url = repo
+            .config_snapshot()
+            .string("submodule.m1.url")
+            .expect("set by `git submodule add`");
+        assert_ne!(configured_url.as_ref(), "../module1");
+        assert_eq!(
+            m1.url()?.to_bstring(),
+            configured_url.as_ref(),
+            "the superproject configuration takes precedence"
+        );
+
+        assert_eq!(m1.branch()?, Some(Branch::Name("other".into())));
+        assert_eq!(
+            m1.update()?,
+            Some(Update::Command("true".into())),
+            "commands are only allowed from the superproject configuration"
+        );
+        assert_eq!(
+            m1.path()?.as_bytes().as_bstr(),
+            "m1",
+            "the path can only be set in .gitmodules"
+        );
+        assert_eq!(dir_m1.branch()?, None, "overrides only affect the submodule they name");
+        assert_eq!(dir_m1.update()?, None);
+
+        assert!(m1.index_id()?.is_some());
+        assert_eq!(m1.head_id()?, m1.index_id()?, "the gitlink was committed");
+        assert!(dir_m1.index_id()?.is_some());
+        assert_eq!(dir_m1.head_id()?, None, "the gitlink was only added to the index");
+        Ok(())
+    }
+}
+
+mod update {
+    use std::sync::atomic::AtomicBool;
+
+    use gix::submodule::update::{Item, Options, Status};
+
+    use crate::util::restricted;
+
+    fn clone_superproject(tmp: &gix_testtools::tempfile::TempDir) -> crate::Result<gix::Repository> {
+        let remote = gix_testtools::scripted_fixture_read_only("make_submodules_for_update.sh")?.join("super");
+        let mut prepare = gix::clone::PrepareFetch::new(
+            remote,
+            tmp.path().join("super"),
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted(),
+        )?;
+        let (mut checkout, _out) = prepare.fetch_then_checkout(gix::progress::Discard, &AtomicBool::default())?;
+        let (repo, _) = checkout.main_worktree(gix::progress::Discard, &AtomicBool::default())?;
+        Ok(repo)
+    }
+
+    fn find<'a>(items: &'a [Item], name: &str) -> &'a Item {
+        items.iter().find(|item| item.name == name).expect("submodule present")
+    }
+
+    #[test]
+    fn recursive_after_clone() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let repo = clone_superproject(&tmp)?;
+        let outcome = repo.update_submodules(gix::progress::Discard, &AtomicBool::default(), Options::default())?;
+        assert_eq!(outcome.submodules.len(), 4);
+
+        for sm in repo.submodules()?.expect("modules present") {
+            let item = find(&outcome.submodules, sm.name().to_string().as_str());
+            assert_eq!(item.path, sm.path()?.as_ref());
+            if sm.name() == "skipped" {
+                assert!(matches!(item.status, Status::SkippedUpdateNone));
+                assert!(sm.open()?.is_none(), "nothing was cloned");
+                continue;
+            }
+            let index_id = sm.index_id()?.expect("gitlink present");
+            assert!(
+                matches!(item.status, Status::CheckedOut { id, ref checkout } if id == index_id && checkout.files_updated > 0),
+                "{item:?}"
+            );
+            let sm_repo = sm.open()?.expect("cloned");
+            assert_eq!(
+                sm_repo.head_id()?,
+                index_id,
+                "the commit recorded in the superproject is checked out"
+            );
+            assert!(sm_repo.head()?.is_detached());
+            assert_eq!(
+                sm_repo.work_dir(),
+                Some(sm.work_dir()?.as_path()),
+                "core.worktree is set"
+            );
+            assert_eq!(
+                sm.state()?,
+                gix::submodule::State {
+                    repository_exists: true,
+                    is_old_form: false,
+                    worktree_checkout: true,
+                    superproject_configuration: false,
+                },
+                "the configuration snapshot of the superproject isn't updated in memory"
+            );
+            assert!(
+                gix::open_opts(sm.work_dir()?, restricted())?.work_dir().is_some(),
+                "it can be opened from its working tree as well"
+            );
+        }
+
+        let reopened = gix::open_opts(repo.path(), restricted())?;
+        for name in ["m2", "dir/m1", "shallow"] {
+            assert!(
+                reopened
+                    .config_snapshot()
+                    .boolean(format!("submodule.{name}.active").as_str())
+                    .expect("present"),
+                "cloned submodules are recorded in the superproject's configuration"
+            );
+        }
+        assert!(reopened.config_snapshot().string("submodule.skipped.url").is_none());
+
+        let shallow = find(&outcome.submodules, "shallow");
+        assert!(
+            repo.submodules()?
+                .expect("modules present")
+                .find(|sm| sm.name() == "shallow")
+                .expect("present")
+                .open()?
+                .expect("cloned")
+                .is_shallow(),
+            "the older commit was fetched explicitly as it isn't a branch tip"
+        );
+        assert!(shallow.submodules.is_empty(), "module1 has no submodules");
+
+        let m2 = find(&outcome.submodules, "m2");
+        assert_eq!(m2.submodules.len(), 1);
+        assert!(
+            matches!(m2.submodules[0].status, Status::CheckedOut { .. }),
+            "nested submodules are updated and their relative url is resolved against their superproject remote"
+        );
+        assert!(repo.work_dir().expect("non-bare").join("m2/nested/this").is_file());
+
+        let outcome = repo.update_submodules(gix::progress::Discard, &AtomicBool::default(), Options::default())?;
+        assert!(
+            outcome
+                .submodules
+                .iter()
+                .filter(|item| item.name != "skipped")
+                .all(|item| matches!(item.status, Status::UpToDate { .. })),
+            "running it again doesn't change anything"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn max_depth_limits_recursion() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let repo = clone_superproject(&tmp)?;
+        let outcome = repo.update_submodules(
+            gix::progress::Discard,
+            &AtomicBool::default(),
+            Options { max_depth: Some(0) },
+        )?;
+        let m2 = find(&outcome.submodules, "m2");
+        assert!(matches!(m2.status, Status::CheckedOut { .. }));
+        assert!(m2.submodules.is_empty(), "nested submodules are not updated");
+        assert!(!repo.work_dir().expect("non-bare").join("m2/nested/this").exists());
+        Ok(())
+    }
+}